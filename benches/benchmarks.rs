@@ -197,6 +197,23 @@ fn create_example_table_nrows(n: usize) -> String {
     s
 }
 
+fn create_wide_table_string(rows: usize, cols: usize) -> String {
+    let mut s = String::from("let table = [[");
+    for i in 0..cols {
+        write!(s, "col_{i} ").unwrap();
+    }
+    s.push_str("]; ");
+    for _ in 0..rows {
+        s.push('[');
+        for i in 0..cols {
+            write!(s, "'value {i}' ").unwrap();
+        }
+        s.push_str("] ");
+    }
+    s.push(']');
+    s
+}
+
 fn bench_record_create(n: usize) -> impl IntoBenchmarks {
     bench_command(
         format!("record_create_{n}"),
@@ -293,6 +310,48 @@ fn bench_table_insert_col(n: usize, m: usize) -> impl IntoBenchmarks {
     bench_command(format!("table_insert_col_{n}_{m}"), insert, stack, engine)
 }
 
+fn bench_table_wide_create(rows: usize, cols: usize) -> impl IntoBenchmarks {
+    bench_command(
+        format!("table_wide_create_{rows}_{cols}"),
+        create_wide_table_string(rows, cols),
+        Stack::new(),
+        setup_engine(),
+    )
+}
+
+fn bench_table_wide_each_update(rows: usize, cols: usize) -> impl IntoBenchmarks {
+    let setup_command = create_wide_table_string(rows, cols);
+    let (stack, engine) = setup_stack_and_engine_from_command(&setup_command);
+    bench_command(
+        format!("table_wide_each_update_{rows}_{cols}"),
+        "$table | each {|row| $row | update col_0 changed } | ignore",
+        stack,
+        engine,
+    )
+}
+
+fn bench_table_wide_each_get(rows: usize, cols: usize) -> impl IntoBenchmarks {
+    let setup_command = create_wide_table_string(rows, cols);
+    let (stack, engine) = setup_stack_and_engine_from_command(&setup_command);
+    bench_command(
+        format!("table_wide_each_get_{rows}_{cols}"),
+        "$table | each {|row| $row.col_0 } | ignore",
+        stack,
+        engine,
+    )
+}
+
+fn bench_table_wide_compare(rows: usize, cols: usize) -> impl IntoBenchmarks {
+    let setup_command = create_wide_table_string(rows, cols);
+    let (stack, engine) = setup_stack_and_engine_from_command(&setup_command);
+    bench_command(
+        format!("table_wide_compare_{rows}_{cols}"),
+        "$table | where $it == $table.0 | ignore",
+        stack,
+        engine,
+    )
+}
+
 fn bench_eval_interleave(n: usize) -> impl IntoBenchmarks {
     let engine = setup_engine();
     let stack = Stack::new();
@@ -519,6 +578,18 @@ tango_benchmarks!(
     bench_table_insert_col(10, 10),
     bench_table_insert_col(100, 10),
     bench_table_insert_col(1000, 10),
+    bench_table_wide_create(100, 10),
+    bench_table_wide_create(100, 100),
+    bench_table_wide_create(1000, 100),
+    bench_table_wide_each_update(100, 10),
+    bench_table_wide_each_update(100, 100),
+    bench_table_wide_each_update(1000, 100),
+    bench_table_wide_each_get(100, 10),
+    bench_table_wide_each_get(100, 100),
+    bench_table_wide_each_get(1000, 100),
+    bench_table_wide_compare(100, 10),
+    bench_table_wide_compare(100, 100),
+    bench_table_wide_compare(1000, 100),
     // Eval
     // Interleave
     bench_eval_interleave(100),
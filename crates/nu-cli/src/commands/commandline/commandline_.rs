@@ -14,6 +14,10 @@ impl Command for Commandline {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View the current command line input buffer."
     }
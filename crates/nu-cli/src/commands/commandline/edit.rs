@@ -1,4 +1,7 @@
 use nu_engine::command_prelude::*;
+use nu_protocol::IntRange;
+use std::ops::Bound;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Clone)]
 pub struct CommandlineEdit;
@@ -26,6 +29,12 @@ impl Command for CommandlineEdit {
                 "replaces the current contents of the buffer (default)",
                 Some('r'),
             )
+            .named(
+                "range",
+                SyntaxShape::Range,
+                "replaces the graphemes in the range, like a token from `commandline tokens`",
+                Some('R'),
+            )
             .switch(
                 "accept",
                 "immediately executes the result after edit",
@@ -39,6 +48,10 @@ impl Command for CommandlineEdit {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Modify the current command line input buffer."
     }
@@ -55,8 +68,26 @@ impl Command for CommandlineEdit {
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let str: String = call.req(engine_state, stack, 0)?;
+        let range: Option<IntRange> = call.get_flag(engine_state, stack, "range")?;
         let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
-        if call.has_flag(engine_state, stack, "append")? {
+        if let Some(range) = range {
+            let indices = repl
+                .buffer
+                .grapheme_indices(true)
+                .map(|(i, _)| i)
+                .chain(std::iter::once(repl.buffer.len()))
+                .collect::<Vec<_>>();
+            let byte_pos = |pos: usize| indices[pos.min(indices.len() - 1)];
+            let (start, end) = range.absolute_bounds(indices.len() - 1);
+            let end = match end {
+                Bound::Excluded(end) => byte_pos(end),
+                Bound::Included(end) => byte_pos(end + 1),
+                Bound::Unbounded => repl.buffer.len(),
+            };
+            let start = byte_pos(start);
+            repl.buffer.replace_range(start..end, &str);
+            repl.cursor_pos = start + str.len();
+        } else if call.has_flag(engine_state, stack, "append")? {
             repl.buffer.push_str(&str);
         } else if call.has_flag(engine_state, stack, "insert")? {
             let cursor_pos = repl.cursor_pos;
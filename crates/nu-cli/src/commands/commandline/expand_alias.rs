@@ -0,0 +1,152 @@
+use nu_engine::command_prelude::*;
+use nu_parser::{lex, lite_parse};
+use nu_protocol::{Alias, AliasArgument, ast::Expr, engine::StateWorkingSet};
+
+#[derive(Clone)]
+pub struct CommandlineExpandAlias;
+
+impl Command for CommandlineExpandAlias {
+    fn name(&self) -> &str {
+        "commandline expand-alias"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Replace the alias of the command at the cursor with what it stands for."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The arguments of an alias with parameters, like
+`alias --parameters gco = git checkout {branch}`, are put in place of the parameters.
+Missing arguments are left as placeholders. The command line doesn't change if the command
+isn't an alias. This is meant to be bound to a key with `executehostcommand`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "preview", "keybinding"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Expand aliases when pressing Alt+E",
+            example: r#"$env.config.keybindings ++= [{
+    name: expand_alias
+    modifier: alt
+    keycode: char_e
+    mode: [emacs vi_insert vi_normal]
+    event: { send: executehostcommand, cmd: "commandline expand-alias" }
+}]"#,
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let offset = working_set.next_span_start();
+        let _ = working_set.add_file("commandline".into(), repl.buffer.as_bytes());
+
+        let expansion = expand_alias(
+            &working_set,
+            repl.buffer.as_bytes(),
+            offset,
+            repl.cursor_pos,
+        );
+        if let Some((range, expansion)) = expansion {
+            if repl.cursor_pos >= range.end {
+                repl.cursor_pos = repl.cursor_pos - range.len() + expansion.len();
+            } else {
+                repl.cursor_pos = range.start + expansion.len();
+            }
+            repl.buffer.replace_range(range, &expansion);
+        }
+
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+}
+
+/// The range of `buffer` with the alias of the command at `cursor_pos`, with its arguments
+/// if it has parameters, and what it expands to.
+fn expand_alias(
+    working_set: &StateWorkingSet,
+    buffer: &[u8],
+    offset: usize,
+    cursor_pos: usize,
+) -> Option<(std::ops::Range<usize>, String)> {
+    let (tokens, _) = lex(buffer, offset, &[], &[], false);
+    let (block, _) = lite_parse(&tokens, working_set);
+
+    // The command the cursor is in, or the last one before it
+    let parts = block
+        .block
+        .iter()
+        .flat_map(|pipeline| &pipeline.commands)
+        .map(|command| &command.parts)
+        .take_while(|parts| {
+            parts
+                .first()
+                .is_some_and(|s| s.start - offset <= cursor_pos)
+        })
+        .last()?;
+
+    // Names of aliases can have several words, like those of commands
+    let (name_len, alias) = (1..=parts.len()).rev().find_map(|len| {
+        let name = parts[..len]
+            .iter()
+            .map(|span| working_set.get_span_contents(*span))
+            .collect::<Vec<_>>()
+            .join(&b' ');
+        let decl_id = working_set.find_decl(&name)?;
+        Some((len, working_set.get_decl(decl_id).as_alias()?))
+    })?;
+
+    let text =
+        |span: Span| String::from_utf8_lossy(working_set.get_span_contents(span)).into_owned();
+    let start = parts[0].start;
+    let mut end = parts[name_len - 1].end;
+    let expansion = if alias.arguments.is_empty() {
+        text(alias.wrapped_call.span)
+    } else {
+        let mut args = parts[name_len..].iter();
+        let mut words = vec![text(aliased_command_span(alias))];
+        for argument in &alias.arguments {
+            match argument {
+                AliasArgument::Fixed(span) => words.push(text(*span)),
+                AliasArgument::Parameter(name) => match args.next() {
+                    Some(span) => {
+                        words.push(text(*span));
+                        end = span.end;
+                    }
+                    None => words.push(format!("{{{name}}}")),
+                },
+            }
+        }
+        words.join(" ")
+    };
+
+    Some((start - offset..end - offset, expansion))
+}
+
+/// The span of the name of the command wrapped by `alias`.
+fn aliased_command_span(alias: &Alias) -> Span {
+    match &alias.wrapped_call.expr {
+        Expr::Call(call) => call.head,
+        Expr::ExternalCall(head, _) => head.span,
+        _ => alias.wrapped_call.span,
+    }
+}
@@ -16,6 +16,10 @@ impl Command for CommandlineGetCursor {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Get the current cursor position."
     }
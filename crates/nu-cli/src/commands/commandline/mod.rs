@@ -1,9 +1,15 @@
 mod commandline_;
 mod edit;
+mod expand_alias;
 mod get_cursor;
+mod newline;
 mod set_cursor;
+mod tokens;
 
 pub use commandline_::Commandline;
 pub use edit::CommandlineEdit;
+pub use expand_alias::CommandlineExpandAlias;
 pub use get_cursor::CommandlineGetCursor;
+pub use newline::CommandlineNewline;
 pub use set_cursor::CommandlineSetCursor;
+pub use tokens::CommandlineTokens;
@@ -0,0 +1,130 @@
+use crate::validation::is_incomplete;
+use nu_engine::command_prelude::*;
+
+/// The indentation of one level of blocks.
+const INDENT: &str = "    ";
+
+#[derive(Clone)]
+pub struct CommandlineNewline;
+
+impl Command for CommandlineNewline {
+    fn name(&self) -> &str {
+        "commandline newline"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Insert an indented newline if the command line is unfinished, or run it otherwise."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The new line is indented by four spaces for every block, list, record or parenthesis which is
+open at the cursor. This is meant to be bound to a key with `executehostcommand`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "indent", "multiline", "enter"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Indent unfinished blocks when pressing Alt+Enter",
+            example: r#"$env.config.keybindings ++= [{
+    name: auto_indent
+    modifier: alt
+    keycode: enter
+    mode: [emacs vi_insert]
+    event: { send: executehostcommand, cmd: "commandline newline" }
+}]"#,
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let mut repl = engine_state.repl_state.lock().expect("repl state mutex");
+        if is_incomplete(engine_state, &repl.buffer) {
+            let cursor_pos = repl.cursor_pos;
+            let depth = open_blocks(&repl.buffer[..cursor_pos]);
+            let newline = format!("\n{}", INDENT.repeat(depth));
+            repl.buffer.insert_str(cursor_pos, &newline);
+            repl.cursor_pos += newline.len();
+        } else {
+            repl.accept = true;
+        }
+
+        Ok(Value::nothing(call.head).into_pipeline_data())
+    }
+}
+
+/// The number of brackets which are open at the end of `source`, outside of strings and
+/// comments.
+fn open_blocks(source: &str) -> usize {
+    let mut depth = 0usize;
+    let mut chars = source.chars().peekable();
+    let mut at_token_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth = depth.saturating_sub(1),
+            '"' | '\'' | '`' => {
+                // Only double quoted strings have escapes
+                let mut escaped = false;
+                for s in chars.by_ref() {
+                    if escaped {
+                        escaped = false;
+                    } else if c == '"' && s == '\\' {
+                        escaped = true;
+                    } else if s == c {
+                        break;
+                    }
+                }
+            }
+            '#' if at_token_start => {
+                for s in chars.by_ref() {
+                    if s == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+        at_token_start = c.is_whitespace() || matches!(c, '{' | '[' | '(' | ';' | '|');
+    }
+    depth
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_open_blocks() {
+        assert_eq!(open_blocks("def foo [] {"), 1);
+        assert_eq!(open_blocks("ls | each {|f|\n    if $f.size > 1kb {"), 2);
+        assert_eq!(open_blocks("[1 (2 + 3)] | each {"), 1);
+        assert_eq!(open_blocks("}"), 0);
+    }
+
+    #[test]
+    fn ignores_strings_and_comments() {
+        assert_eq!(open_blocks(r#"print "{ \" {" {"#), 1);
+        assert_eq!(open_blocks("print '{\\' {"), 1);
+        assert_eq!(open_blocks("{ # comment {\n"), 1);
+        assert_eq!(open_blocks("echo a#b {"), 1);
+    }
+}
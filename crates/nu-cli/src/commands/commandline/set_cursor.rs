@@ -22,6 +22,10 @@ impl Command for CommandlineSetCursor {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Set the current cursor position."
     }
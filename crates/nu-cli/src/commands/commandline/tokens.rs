@@ -0,0 +1,121 @@
+use nu_engine::command_prelude::*;
+use nu_parser::{flatten_block, parse};
+use nu_protocol::engine::StateWorkingSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Clone)]
+pub struct CommandlineTokens;
+
+impl Command for CommandlineTokens {
+    fn name(&self) -> &str {
+        "commandline tokens"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![
+                (
+                    Type::Nothing,
+                    Type::Table(
+                        [
+                            ("content".into(), Type::String),
+                            ("shape".into(), Type::String),
+                            ("start".into(), Type::Int),
+                            ("end".into(), Type::Int),
+                        ]
+                        .into(),
+                    ),
+                ),
+                (Type::Nothing, Type::record()),
+            ])
+            .switch(
+                "cursor",
+                "only return the token at the cursor, or nothing if there isn't one",
+                Some('c'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Get the parsed tokens of the current command line input buffer."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The shapes are the same as for syntax highlighting. The positions are counted in graphemes
+like for `commandline get-cursor`, and the end is exclusive, so a token can be replaced with
+`commandline edit --range $token.start..<$token.end`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["repl", "interactive", "parse", "flatten", "keybinding"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Quote the argument under the cursor",
+                example: r#"let token = commandline tokens --cursor; commandline edit --range $token.start..<$token.end $"'($token.content)'""#,
+                result: None,
+            },
+            Example {
+                description: "List the flags in the command line",
+                example: "commandline tokens | where shape == shape_flag",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let at_cursor = call.has_flag(engine_state, stack, "cursor")?;
+        let (buffer, cursor_pos) = {
+            let repl = engine_state.repl_state.lock().expect("repl state mutex");
+            (repl.buffer.clone(), repl.cursor_pos)
+        };
+
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let block = parse(&mut working_set, None, buffer.as_bytes(), false);
+        let offset = engine_state.next_span_start();
+        let to_graphemes = |pos: usize| buffer[..pos].graphemes(true).count() as i64;
+
+        let mut tokens = flatten_block(&working_set, &block)
+            .into_iter()
+            // Spans of other files, like of the definitions of aliases, are skipped
+            .filter(|(span, _)| span.start >= offset && span.end <= offset + buffer.len())
+            .map(|(span, shape)| {
+                let (start, end) = (span.start - offset, span.end - offset);
+                let token = Value::record(
+                    record! {
+                        "content" => Value::string(&buffer[start..end], head),
+                        "shape" => Value::string(shape.to_string(), head),
+                        "start" => Value::int(to_graphemes(start), head),
+                        "end" => Value::int(to_graphemes(end), head),
+                    },
+                    head,
+                );
+                (start..=end, token)
+            });
+
+        if at_cursor {
+            let token = tokens
+                .find(|(range, _)| range.contains(&cursor_pos))
+                .map(|(_, token)| token)
+                .unwrap_or(Value::nothing(head));
+            Ok(token.into_pipeline_data())
+        } else {
+            let tokens = tokens.map(|(_, token)| token).collect();
+            Ok(Value::list(tokens, head).into_pipeline_data())
+        }
+    }
+}
@@ -14,8 +14,11 @@ pub fn add_cli_context(mut engine_state: EngineState) -> EngineState {
         bind_command! {
             Commandline,
             CommandlineEdit,
+            CommandlineExpandAlias,
             CommandlineGetCursor,
+            CommandlineNewline,
             CommandlineSetCursor,
+            CommandlineTokens,
             History,
             Keybindings,
             KeybindingsDefault,
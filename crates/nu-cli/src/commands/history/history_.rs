@@ -34,6 +34,10 @@ impl Command for History {
             .category(Category::History)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -52,6 +56,9 @@ impl Command for History {
         };
 
         if call.has_flag(engine_state, stack, "clear")? {
+            if let Some(sandbox) = &engine_state.sandbox {
+                sandbox.check_effect(self.name(), Effect::Write, head)?;
+            }
             let _ = std::fs::remove_file(history_path);
             // TODO: FIXME also clear the auxiliary files when using sqlite
             return Ok(PipelineData::empty());
@@ -64,6 +64,10 @@ Note that history item IDs are ignored when importing from file."#
         ]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -18,6 +18,10 @@ impl Command for HistorySession {
             .input_output_types(vec![(Type::Nothing, Type::Int)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![Example {
             example: "history session",
@@ -15,6 +15,10 @@ impl Command for KeybindingsDefault {
             .input_output_types(vec![(Type::Nothing, Type::table())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "List default keybindings."
     }
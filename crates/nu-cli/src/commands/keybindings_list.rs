@@ -23,6 +23,10 @@ impl Command for KeybindingsList {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "List available options that can be used to create keybindings."
     }
@@ -28,6 +28,10 @@ impl Command for KeybindingsListen {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
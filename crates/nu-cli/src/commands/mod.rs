@@ -6,7 +6,10 @@ mod keybindings_default;
 mod keybindings_list;
 mod keybindings_listen;
 
-pub use commandline::{Commandline, CommandlineEdit, CommandlineGetCursor, CommandlineSetCursor};
+pub use commandline::{
+    Commandline, CommandlineEdit, CommandlineExpandAlias, CommandlineGetCursor, CommandlineNewline,
+    CommandlineSetCursor, CommandlineTokens,
+};
 pub use history::*;
 pub use keybindings::Keybindings;
 pub use keybindings_default::KeybindingsDefault;
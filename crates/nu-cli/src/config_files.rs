@@ -183,12 +183,9 @@ pub fn add_plugin_file(engine_state: &mut EngineState, plugin_file: Option<Spann
                     ),
                 );
             }
-        } else if let Some(plugin_path) = nu_path::nu_config_dir() {
+        } else if let Some(plugin_path) = nu_path::nu_state_file(PLUGIN_FILE) {
             // Path to store plugins signatures
-            let mut plugin_path =
-                canonicalize_with(&plugin_path, &cwd).unwrap_or(plugin_path.into());
-            plugin_path.push(PLUGIN_FILE);
-            let plugin_path = canonicalize_with(&plugin_path, &cwd).unwrap_or(plugin_path);
+            let plugin_path = canonicalize_with(&plugin_path, &cwd).unwrap_or(plugin_path.into());
             engine_state.plugin_path = Some(plugin_path);
         }
     }
@@ -319,8 +316,13 @@ pub fn migrate_old_plugin_file(engine_state: &EngineState) -> bool {
     }
 
     // Write the new file
-    let new_plugin_file_path = config_dir.join(PLUGIN_FILE);
-    if let Err(err) = std::fs::File::create(&new_plugin_file_path)
+    let Some(new_plugin_file_path) = engine_state.plugin_path.clone() else {
+        return false;
+    };
+    if let Err(err) = new_plugin_file_path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::File::create(&new_plugin_file_path))
         .map_err(|err| {
             IoError::new_internal_with_path(
                 err,
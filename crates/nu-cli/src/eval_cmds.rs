@@ -11,7 +11,7 @@ use nu_protocol::{
 };
 use std::sync::Arc;
 
-use crate::util::print_pipeline;
+use crate::util::{eval_deferred_sources, print_pipeline};
 
 #[derive(Default)]
 pub struct EvaluateCommandsOpts {
@@ -61,7 +61,12 @@ pub fn evaluate_commands(
 
         let mut working_set = StateWorkingSet::new(engine_state);
 
-        let output = parse(&mut working_set, None, commands.item.as_bytes(), false);
+        let output = parse(
+            &mut working_set,
+            Some("<commandline>"),
+            commands.item.as_bytes(),
+            false,
+        );
         if let Some(warning) = working_set.parse_warnings.first() {
             report_parse_warning(&working_set, warning);
         }
@@ -96,6 +101,7 @@ pub fn evaluate_commands(
     }
 
     print_pipeline(engine_state, stack, pipeline_data, no_newline)?;
+    eval_deferred_sources(engine_state, stack);
     info!("evaluate {}:{}:{}", file!(), line!(), column!());
     let pipefail = nu_experimental::PIPE_FAIL.get();
     if !pipefail {
@@ -140,8 +140,8 @@ mod test {
     use rstest::rstest;
 
     #[rstest]
-    #[case("who", 5, 8, &["whoami", "each"])]
-    #[case("hash", 1, 5, &["hash", "hash md5", "hash sha256"])]
+    #[case("who", 5, 8, &["whoami", "each", "parse", "render", "from auto", "config get", "search text", "ulid parse", "config diff", "import dynamic"])]
+    #[case("hash", 1, 5, &["hash", "hash md5", "hash sha256", "env trust"])]
     #[case("into f", 0, 6, &["into float", "into filesize"])]
     #[case("into nonexistent", 0, 16, &[])]
     fn test_help_completer(
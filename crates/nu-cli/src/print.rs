@@ -57,8 +57,8 @@ Since this command has no output, there is no point in piping it with other comm
         let no_newline = call.has_flag(engine_state, stack, "no-newline")?;
         let raw = call.has_flag(engine_state, stack, "raw")?;
 
-        // if we're in the LSP *always* print to stderr
-        let to_stderr = if engine_state.is_lsp {
+        // if we're in the LSP or the JSON server *always* print to stderr
+        let to_stderr = if engine_state.is_lsp || engine_state.is_json_server {
             true
         } else {
             call.has_flag(engine_state, stack, "stderr")?
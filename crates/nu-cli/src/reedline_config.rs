@@ -705,6 +705,15 @@ fn add_menu_keybindings(keybindings: &mut Keybindings) {
     );
 }
 
+fn add_editor_keybindings(keybindings: &mut Keybindings) {
+    // Continue editing the buffer in `buffer_editor`, or $env.VISUAL or $env.EDITOR
+    keybindings.add_binding(
+        KeyModifiers::CONTROL,
+        KeyCode::Char('o'),
+        ReedlineEvent::OpenEditor,
+    );
+}
+
 pub enum KeybindingsMode {
     Emacs(Keybindings),
     Vi {
@@ -723,10 +732,13 @@ pub(crate) fn create_keybindings(config: &Config) -> Result<KeybindingsMode, She
     match config.edit_mode {
         EditBindings::Emacs => {
             add_menu_keybindings(&mut emacs_keybindings);
+            add_editor_keybindings(&mut emacs_keybindings);
         }
         EditBindings::Vi => {
             add_menu_keybindings(&mut insert_keybindings);
             add_menu_keybindings(&mut normal_keybindings);
+            add_editor_keybindings(&mut insert_keybindings);
+            add_editor_keybindings(&mut normal_keybindings);
         }
     }
     for keybinding in parsed_keybindings {
@@ -1010,6 +1022,45 @@ fn parse_event(value: &Value, config: &Config) -> Result<Option<ReedlineEvent>,
     }
 }
 
+/// The name of the source of `command` when it's run by an `executehostcommand` keybinding, like
+/// `keybinding (config.nu:12)`, so that errors in it lead back to where the keybinding was set.
+///
+/// Commands typed in the REPL don't come from keybindings, unless they're the same text.
+pub(crate) fn host_command_source_name(
+    engine_state: &EngineState,
+    command: &str,
+) -> Option<String> {
+    let span = engine_state
+        .get_config()
+        .keybindings
+        .iter()
+        .find_map(|keybinding| host_command_span(&keybinding.event, command))?;
+    Some(StateWorkingSet::new(engine_state).virtual_file_name("keybinding", span))
+}
+
+/// The span of the `cmd` of the `executehostcommand` event in `event` which runs `command`.
+fn host_command_span(event: &Value, command: &str) -> Option<Span> {
+    match event {
+        Value::Record { val: record, .. } => {
+            if let Some(events) = record.get("until") {
+                return host_command_span(events, command);
+            }
+            let is_host_command = record
+                .get("send")
+                .and_then(|send| send.as_str().ok())
+                .is_some_and(|send| send.eq_ignore_ascii_case("executehostcommand"));
+            record
+                .get("cmd")
+                .filter(|cmd| is_host_command && cmd.as_str().is_ok_and(|cmd| cmd == command))
+                .map(Value::span)
+        }
+        Value::List { vals, .. } => vals
+            .iter()
+            .find_map(|event| host_command_span(event, command)),
+        _ => None,
+    }
+}
+
 fn event_from_record(
     name: &str,
     record: &Record,
@@ -1421,6 +1472,40 @@ mod test {
     use super::*;
     use nu_protocol::record;
 
+    #[test]
+    fn host_command_source_name_points_to_the_keybinding() {
+        let mut engine_state = EngineState::new();
+        let contents = r#"$env.config.keybindings = [{
+  name: broken
+  event: { send: executehostcommand, cmd: 'print (' }
+}]"#;
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        let file_id = working_set.add_file("config.nu".into(), contents.as_bytes());
+        let file_start = working_set.get_span_for_file(file_id).start;
+        engine_state.merge_delta(working_set.render()).unwrap();
+
+        let cmd_start = file_start + contents.find("'print ('").unwrap();
+        let cmd = Value::string("print (", Span::new(cmd_start, cmd_start + 9));
+        let mut config = Config::default();
+        config.keybindings = vec![ParsedKeybinding {
+            name: Some(Value::test_string("broken")),
+            modifier: Value::test_string("control"),
+            keycode: Value::test_string("char_x"),
+            event: Value::test_list(vec![Value::test_record(record! {
+                "send" => Value::test_string("ExecuteHostCommand"),
+                "cmd" => cmd,
+            })]),
+            mode: Value::test_string("emacs"),
+        }];
+        engine_state.set_config(config);
+
+        assert_eq!(
+            host_command_source_name(&engine_state, "print (").as_deref(),
+            Some("keybinding (config.nu:3)")
+        );
+        assert_eq!(host_command_source_name(&engine_state, "print )"), None);
+    }
+
     #[test]
     fn test_send_event() {
         let event = record! {
@@ -10,13 +10,13 @@ use crate::{
     completions::NuCompleter,
     nu_highlight::NoOpHighlighter,
     prompt_update,
-    reedline_config::{KeybindingsMode, add_menus, create_keybindings},
-    util::eval_source,
+    reedline_config::{KeybindingsMode, add_menus, create_keybindings, host_command_source_name},
+    util::{eval_deferred_sources, eval_source},
 };
 use crossterm::cursor::SetCursorStyle;
 use log::{error, trace, warn};
 use miette::{ErrReport, IntoDiagnostic, Result};
-use nu_cmd_base::util::get_editor;
+use nu_cmd_base::{dir_env::DirEnv, util::get_editor};
 use nu_color_config::StyleComputer;
 #[allow(deprecated)]
 use nu_engine::env_to_strings;
@@ -176,6 +176,7 @@ pub fn evaluate_repl(
     // Setup initial engine_state and stack state
     let mut previous_engine_state = engine_state.clone();
     let mut previous_stack_arc = Arc::new(unique_stack);
+    let mut dir_env = DirEnv::default();
     loop {
         // clone these values so that they can be moved by AssertUnwindSafe
         // If there is a panic within this iteration the last engine_state and stack
@@ -197,6 +198,7 @@ pub fn evaluate_repl(
                 use_color,
                 entry_num: &mut entry_num,
                 hostname: hostname.as_deref(),
+                dir_env: &mut dir_env,
             });
 
             // pass the most recent version of the line_editor back
@@ -293,6 +295,7 @@ struct LoopContext<'a> {
     use_color: bool,
     entry_num: &'a mut usize,
     hostname: Option<&'a str>,
+    dir_env: &'a mut DirEnv,
 }
 
 /// Perform one iteration of the REPL loop
@@ -312,6 +315,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
         use_color,
         entry_num,
         hostname,
+        dir_env,
     } = ctx;
 
     let mut start_time = std::time::Instant::now();
@@ -326,6 +330,13 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     engine_state.reset_signals();
     perf!("reset signals", start_time, use_color);
 
+    start_time = std::time::Instant::now();
+    // Load or unload the `.nu-env` file of the current directory
+    if let Err(error) = dir_env.update(engine_state, &mut stack) {
+        report_shell_error(engine_state, &error)
+    }
+    perf!("dir env", start_time, use_color);
+
     start_time = std::time::Instant::now();
     // Check all the environment variables they ask for
     // fire the "env_change" hook
@@ -338,6 +349,13 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     }
     perf!("env-change hook", start_time, use_color);
 
+    start_time = std::time::Instant::now();
+    // Run the closures of `watch-var` whose variable changed
+    if let Err(error) = hook::eval_var_watches(engine_state, &mut stack) {
+        report_shell_error(engine_state, &error)
+    }
+    perf!("var watches", start_time, use_color);
+
     start_time = std::time::Instant::now();
     // Next, right before we start our prompt and take input from the user, fire the "pre_prompt" hook
     if let Err(err) = hook::eval_hooks(
@@ -351,6 +369,9 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
     }
     perf!("pre-prompt hook", start_time, use_color);
 
+    // The hooks can queue files with `import dynamic` too
+    eval_deferred_sources(engine_state, &mut stack);
+
     let engine_reference = Arc::new(engine_state.clone());
     let config = stack.get_config(engine_state);
 
@@ -549,6 +570,7 @@ fn loop_iteration(ctx: LoopContext) -> (bool, Stack, Reedline) {
                 ) {
                     report_shell_error(engine_state, &err);
                 }
+                eval_deferred_sources(engine_state, &mut stack);
             }
 
             perf!("pre_execution_hook", start_time, use_color);
@@ -967,14 +989,22 @@ fn do_run_cmd(
         run_shell_integration_osc2(Some(s), engine_state, stack, use_color);
     }
 
+    if let Some(limits) = engine_state.signals().limits() {
+        limits.start(&engine_state.get_config().limits);
+    }
+    let fname =
+        host_command_source_name(engine_state, s).unwrap_or_else(|| format!("entry #{entry_num}"));
     eval_source(
         engine_state,
         stack,
         s.as_bytes(),
-        &format!("entry #{entry_num}"),
+        &fname,
         PipelineData::empty(),
         false,
     );
+    if let Some(limits) = engine_state.signals().limits() {
+        limits.stop();
+    }
 
     // if there was a warning before, and we got to this point, it means
     // the possible call to cleanup_exit did not occur.
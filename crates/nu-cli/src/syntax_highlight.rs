@@ -2,7 +2,7 @@ use log::trace;
 use nu_ansi_term::Style;
 use nu_color_config::{get_matching_brackets_style, get_shape_color};
 use nu_engine::env;
-use nu_parser::{FlatShape, flatten_block, parse};
+use nu_parser::{FlatShape, flatten_block, parse, resolve_externals};
 use nu_protocol::{
     Span,
     ast::{Block, Expr, Expression, PipelineRedirection, RecordItem},
@@ -62,26 +62,11 @@ pub(crate) fn highlight_syntax(
     let block = parse(&mut working_set, None, line.as_bytes(), false);
     let (shapes, global_span_offset) = {
         let mut shapes = flatten_block(&working_set, &block);
-        // Highlighting externals has a config point because of concerns that using which to resolve
+        // Highlighting externals has a config point because of concerns that resolving
         // externals may slow down things too much.
-        if highlight_resolved_externals {
-            for (span, shape) in shapes.iter_mut() {
-                if let FlatShape::External(aliased_command_span) = shape {
-                    let str_contents = working_set.get_span_contents(**aliased_command_span);
-                    let str_word = String::from_utf8_lossy(str_contents).to_string();
-                    let paths = env::path_str(engine_state, stack, *span).ok();
-                    let res = if let Ok(cwd) = engine_state.cwd(Some(stack)) {
-                        which::which_in(str_word, paths.as_ref(), cwd).ok()
-                    } else {
-                        which::which_in_global(str_word, paths.as_ref())
-                            .ok()
-                            .and_then(|mut i| i.next())
-                    };
-                    if res.is_some() {
-                        *shape = FlatShape::ExternalResolved;
-                    }
-                }
-            }
+        if highlight_resolved_externals && let Ok(cwd) = engine_state.cwd(Some(stack)) {
+            let paths = env::path_str(engine_state, stack, Span::unknown()).unwrap_or_default();
+            resolve_externals(&working_set, &mut shapes, &paths, cwd.as_std_path());
         }
         (shapes, engine_state.next_span_start())
     };
@@ -4,7 +4,7 @@ use nu_cmd_base::hook::eval_hook;
 use nu_engine::{eval_block, eval_block_with_early_return};
 use nu_parser::{Token, TokenContents, lex, parse, unescape_unquote_string};
 use nu_protocol::{
-    PipelineData, ShellError, Span, Value,
+    IntoPipelineData, ListStream, PipelineData, ShellError, Signals, Span, Value, ast,
     debugger::WithoutDebug,
     engine::{EngineState, Stack, StateWorkingSet},
     process::check_exit_status_future,
@@ -14,7 +14,17 @@ use nu_protocol::{
 #[cfg(windows)]
 use nu_utils::enable_vt_processing;
 use nu_utils::{escape_quote_string, perf};
-use std::path::Path;
+use std::{
+    io::IsTerminal,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Streams with more rows than this aren't paged, as they'd have to be collected first.
+const MAX_PAGED_ROWS: usize = 10_000;
+
+/// How long streams are collected to page them, before they're printed as streams instead.
+const MAX_PAGE_WAIT: Duration = Duration::from_secs(1);
 
 // This will collect environment variables from std::env and adds them to a stack.
 //
@@ -217,6 +227,30 @@ pub fn print_pipeline(
     pipeline: PipelineData,
     no_newline: bool,
 ) -> Result<(), ShellError> {
+    let pipeline = match find_display_renderer(engine_state, stack, &pipeline) {
+        Some(renderer) => {
+            let rendered = eval_hook(
+                engine_state,
+                stack,
+                Some(pipeline),
+                vec![],
+                &renderer,
+                "display_renderers",
+            )?;
+            // Renderers which return text or bytes have finished rendering
+            if matches!(
+                rendered,
+                PipelineData::ByteStream(..)
+                    | PipelineData::Value(Value::String { .. } | Value::Binary { .. }, ..)
+            ) {
+                return rendered.print_raw(engine_state, no_newline, false);
+            }
+            rendered
+        }
+        None => pipeline,
+    };
+    let pipeline = page_pipeline(engine_state, stack, pipeline)?;
+
     if let Some(hook) = engine_state.get_config().hooks.display_output.clone() {
         let pipeline = eval_hook(
             engine_state,
@@ -233,6 +267,117 @@ pub fn print_pipeline(
     }
 }
 
+/// Show a list or record in `explore` instead, if `pager.auto` is set and it doesn't fit on the
+/// screen. Each row is counted as one line.
+///
+/// Returns the value which was picked in the pager, or `pipeline` itself if it isn't paged.
+fn page_pipeline(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    pipeline: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    if !stack.get_config(engine_state).pager.auto || !std::io::stdout().is_terminal() {
+        return Ok(pipeline);
+    }
+    let (Some(decl_id), Ok((_, height))) = (
+        engine_state.find_decl(b"explore", &[]),
+        crossterm::terminal::size(),
+    ) else {
+        return Ok(pipeline);
+    };
+
+    let span = pipeline.span().unwrap_or(Span::unknown());
+    let metadata = pipeline.metadata();
+    // Streams have to be collected to know whether they fit, unless they're long or endless
+    let value = match pipeline {
+        PipelineData::ListStream(stream, metadata) => {
+            let signals = engine_state.signals().clone();
+            match collect_short_stream(stream, signals, MAX_PAGED_ROWS, MAX_PAGE_WAIT) {
+                Ok(vals) => Value::list(vals, span),
+                Err(stream) => return Ok(PipelineData::list_stream(stream, metadata)),
+            }
+        }
+        PipelineData::Value(value @ (Value::List { .. } | Value::Record { .. }), _) => value,
+        pipeline => return Ok(pipeline),
+    };
+    // The borders and the header of the table, and the prompt afterwards
+    let lines = match &value {
+        Value::List { vals, .. } => vals.len() + 5,
+        Value::Record { val, .. } => val.len() + 3,
+        _ => 0,
+    };
+    let input = value.into_pipeline_data_with_metadata(metadata);
+    if lines <= height as usize {
+        return Ok(input);
+    }
+
+    let call = ast::Call::new(span);
+    engine_state
+        .get_decl(decl_id)
+        .run(engine_state, stack, &(&call).into(), input)
+}
+
+/// Collect `stream` if it ends within `max_rows` rows and about `max_wait`. Otherwise, return a
+/// stream of the same rows.
+fn collect_short_stream(
+    stream: ListStream,
+    signals: Signals,
+    max_rows: usize,
+    max_wait: Duration,
+) -> Result<Vec<Value>, ListStream> {
+    let span = stream.span();
+    let mut rows = stream.into_iter();
+    let mut vals = vec![];
+    let start = Instant::now();
+    while vals.len() < max_rows && start.elapsed() < max_wait {
+        match rows.next() {
+            Some(val) => vals.push(val),
+            None => return Ok(vals),
+        }
+    }
+    Err(ListStream::new(vals.into_iter().chain(rows), span, signals))
+}
+
+/// The `display_renderers` hook for `pipeline`.
+///
+/// A renderer for the content type in the metadata is preferred, then one for the type name of a
+/// custom value, and then one for the type of the value, like `duration` or `record`.
+fn find_display_renderer(
+    engine_state: &EngineState,
+    stack: &Stack,
+    pipeline: &PipelineData,
+) -> Option<Value> {
+    let config = stack.get_config(engine_state);
+    let renderers = &config.hooks.display_renderers;
+    if renderers.is_empty() {
+        return None;
+    }
+
+    let metadata = pipeline.metadata();
+    let content_type = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.content_type.as_deref())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim);
+    let (type_name, custom_type_name) = match pipeline {
+        PipelineData::Value(Value::Custom { val, .. }, ..) => {
+            (Some("custom".to_string()), Some(val.type_name()))
+        }
+        PipelineData::Value(value, ..) => (Some(value.get_type().get_non_specified_string()), None),
+        _ => (None, None),
+    };
+
+    [
+        content_type,
+        custom_type_name.as_deref(),
+        type_name.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|key| renderers.get(key))
+    .cloned()
+}
+
 pub fn eval_source(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -257,6 +402,8 @@ pub fn eval_source(
         }
     };
 
+    eval_deferred_sources(engine_state, stack);
+
     // reset vt processing, aka ansi because illbehaved externals can break it
     #[cfg(windows)]
     {
@@ -275,6 +422,28 @@ pub fn eval_source(
     exit_code
 }
 
+/// Evaluate the source code queued by `import dynamic`, so its definitions are added to the
+/// engine state.
+pub fn eval_deferred_sources(engine_state: &mut EngineState, stack: &mut Stack) {
+    let sources = std::mem::take(
+        &mut *engine_state
+            .deferred_sources
+            .lock()
+            .expect("deferred sources mutex"),
+    );
+    for source in sources {
+        // Files which are queued from these files are evaluated by the nested call
+        eval_source(
+            engine_state,
+            stack,
+            source.as_bytes(),
+            "import dynamic",
+            PipelineData::empty(),
+            false,
+        );
+    }
+}
+
 fn evaluate_source(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -332,6 +501,44 @@ fn evaluate_source(
 mod test {
     use super::*;
 
+    fn stream(iter: impl Iterator<Item = i64> + Send + 'static) -> ListStream {
+        ListStream::new(
+            iter.map(Value::test_int),
+            Span::test_data(),
+            Signals::empty(),
+        )
+    }
+
+    #[test]
+    fn short_streams_are_collected() {
+        let vals =
+            collect_short_stream(stream(0..3), Signals::empty(), 10, Duration::from_secs(60));
+        assert_eq!(vals.ok(), Some((0..3).map(Value::test_int).collect()));
+    }
+
+    #[test]
+    fn endless_streams_are_not_collected() {
+        let Err(rest) =
+            collect_short_stream(stream(0..), Signals::empty(), 10, Duration::from_secs(60))
+        else {
+            panic!("an endless stream was collected");
+        };
+        let vals = rest.into_iter().take(12).collect::<Vec<_>>();
+        assert_eq!(vals, (0..12).map(Value::test_int).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn slow_streams_are_not_collected() {
+        let slow = (0..3).inspect(|_| std::thread::sleep(Duration::from_millis(50)));
+        let result = collect_short_stream(
+            stream(slow),
+            Signals::empty(),
+            10,
+            Duration::from_millis(10),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_gather_env_vars() {
         let mut engine_state = EngineState::new();
@@ -12,16 +12,22 @@ pub struct NuValidator {
 
 impl Validator for NuValidator {
     fn validate(&self, line: &str) -> ValidationResult {
-        let mut working_set = StateWorkingSet::new(&self.engine_state);
-        parse(&mut working_set, None, line.as_bytes(), false);
-
-        if matches!(
-            working_set.parse_errors.first(),
-            Some(ParseError::UnexpectedEof(..))
-        ) {
+        if is_incomplete(&self.engine_state, line) {
             ValidationResult::Incomplete
         } else {
             ValidationResult::Complete
         }
     }
 }
+
+/// Whether `line` ends inside a block, a string or another unfinished expression, so that
+/// <Enter> continues it on the next line.
+pub(crate) fn is_incomplete(engine_state: &EngineState, line: &str) -> bool {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    parse(&mut working_set, None, line.as_bytes(), false);
+
+    matches!(
+        working_set.parse_errors.first(),
+        Some(ParseError::UnexpectedEof(..))
+    )
+}
@@ -0,0 +1,37 @@
+use nu_test_support::nu;
+
+#[test]
+fn commandline_expand_alias_replaces_alias() {
+    let actual = nu!(r#"
+        alias ll = ls --long
+        commandline edit "ll | length"
+        commandline set-cursor 1
+        commandline expand-alias
+        $"(commandline) (commandline get-cursor)"
+    "#);
+
+    assert_eq!(actual.out, "ls --long | length 9");
+}
+
+#[test]
+fn commandline_expand_alias_with_parameters() {
+    let actual = nu!(r#"
+        alias --parameters gco = git checkout {branch} --quiet
+        commandline edit "gco main --force"
+        commandline expand-alias
+        commandline
+    "#);
+
+    assert_eq!(actual.out, "git checkout main --quiet --force");
+}
+
+#[test]
+fn commandline_expand_alias_keeps_other_commands() {
+    let actual = nu!(r#"
+        commandline edit "echo foo"
+        commandline expand-alias
+        commandline
+    "#);
+
+    assert_eq!(actual.out, "echo foo");
+}
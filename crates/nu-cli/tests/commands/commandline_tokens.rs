@@ -0,0 +1,37 @@
+use nu_test_support::nu;
+
+#[test]
+fn commandline_tokens_have_positions_in_graphemes() {
+    let actual = nu!(r#"
+        commandline edit "echo ä --help"
+        commandline tokens | last | $"($in.content) ($in.shape) ($in.start) ($in.end)"
+    "#);
+
+    assert_eq!(actual.out, "--help shape_flag 7 13");
+}
+
+#[test]
+fn commandline_tokens_at_cursor() {
+    let actual = nu!(r#"
+        commandline edit "echo foo  bar"
+        commandline set-cursor 6
+        let foo = commandline tokens --cursor | get content
+        commandline set-cursor 9
+        $"($foo) (commandline tokens --cursor | describe)"
+    "#);
+
+    assert_eq!(actual.out, "foo nothing");
+}
+
+#[test]
+fn commandline_edit_replaces_token() {
+    let actual = nu!(r#"
+        commandline edit "echo foo bar"
+        commandline set-cursor 6
+        let token = commandline tokens --cursor
+        commandline edit --range $token.start..<$token.end $"'($token.content)'"
+        $"(commandline) (commandline get-cursor)"
+    "#);
+
+    assert_eq!(actual.out, "echo 'foo' bar 10");
+}
@@ -28,10 +28,11 @@ impl Test {
     }
 
     fn nu(&self, cmd: impl AsRef<str>) -> Outcome {
-        let env = [(
-            "XDG_CONFIG_HOME".to_string(),
-            self.cfg_dir.path().to_str().unwrap().to_string(),
-        )];
+        let dir = self.cfg_dir.path().to_str().unwrap().to_string();
+        let env = [
+            ("XDG_CONFIG_HOME".to_string(), dir.clone()),
+            ("XDG_DATA_HOME".to_string(), dir),
+        ];
         let env_config = self.cfg_dir.path().join("env.nu");
         nu!(envs: env, env_config: env_config, cmd.as_ref())
     }
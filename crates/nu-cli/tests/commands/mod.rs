@@ -1,3 +1,5 @@
+mod commandline_expand_alias;
+mod commandline_tokens;
 mod keybindings_list;
 mod nu_highlight;
 
@@ -516,6 +516,20 @@ fn which_command_completions() {
 }
 
 /// Suppress completions for invalid values
+/// `chmod` and `chown` are subcommands of `fs`, so they don't shadow the externals
+#[cfg(unix)]
+#[test]
+fn fs_subcommand_completions() {
+    let (_, _, engine, stack) = new_engine();
+    let mut completer = NuCompleter::new(Arc::new(engine), Arc::new(stack));
+
+    let completion_str = "fs ch";
+    let suggestions = completer.complete(completion_str, completion_str.len());
+    assert_eq!(2, suggestions.len());
+    let expected: Vec<_> = vec!["fs chmod", "fs chown"];
+    match_suggestions(&expected, &suggestions);
+}
+
 #[test]
 fn customcompletions_invalid() {
     let (_, _, mut engine, mut stack) = new_engine();
@@ -1752,17 +1766,19 @@ fn flag_completions() {
     let mut completer = NuCompleter::new(Arc::new(engine), Arc::new(stack));
     // Test completions for the 'ls' flags
     let suggestions = completer.complete("ls -", 4);
-    assert_eq!(18, suggestions.len());
+    assert_eq!(21, suggestions.len());
     let expected: Vec<_> = vec![
         "--all",
         "--directory",
         "--du",
+        "--fields",
         "--full-paths",
         "--help",
         "--long",
         "--mime-type",
         "--short-names",
         "--threads",
+        "--unsorted",
         "-a",
         "-D",
         "-d",
@@ -1772,6 +1788,7 @@ fn flag_completions() {
         "-m",
         "-s",
         "-t",
+        "-u",
     ];
     // Match results
     match_suggestions(&expected, &suggestions);
@@ -2076,7 +2093,7 @@ fn variables_completions() {
     // Test completions for $nu
     let suggestions = completer.complete("$nu.", 4);
 
-    assert_eq!(20, suggestions.len());
+    assert_eq!(21, suggestions.len());
 
     let expected: Vec<_> = vec![
         "cache-dir",
@@ -2095,6 +2112,7 @@ fn variables_completions() {
         "os-info",
         "pid",
         "plugin-path",
+        "profile",
         "startup-time",
         "temp-path",
         "user-autoload-dirs",
@@ -0,0 +1,442 @@
+//! Per-directory environment files.
+//!
+//! A `.nu-env` file is evaluated when the REPL enters its directory (or any directory below it)
+//! and the environment changes it made are reverted when leaving again. Files are only evaluated
+//! once they've been approved with `env trust`, which records the hash of their contents, so a
+//! modified file has to be approved again.
+
+use nu_engine::eval_block;
+use nu_parser::parse;
+use nu_protocol::{
+    PipelineData, ShellError, ShellWarning, Value,
+    debugger::WithoutDebug,
+    engine::{EngineState, Stack, StateWorkingSet},
+    report_error::report_parse_error,
+    report_shell_warning,
+    shell_error::io::IoError,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// Name of the per-directory environment file.
+pub const DIR_ENV_FILE: &str = ".nu-env";
+
+/// Name of the file in the data directory which lists the trusted environment files.
+const TRUST_STORE_FILE: &str = "trusted-env.json";
+
+/// Find the environment file which applies to `dir`, i.e. the closest one in `dir` or its
+/// parents.
+pub fn find_dir_env_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(DIR_ENV_FILE))
+        .find(|file| file.is_file())
+}
+
+/// The hash of an environment file, as stored in the trust store.
+pub fn content_hash(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn trust_store_path() -> Option<PathBuf> {
+    nu_path::nu_data_dir().map(|dir| dir.join(TRUST_STORE_FILE).into_std_path_buf())
+}
+
+/// A path in the trust store: a string if it's valid UTF-8, and otherwise the platform's encoding
+/// of it, so every path is stored exactly.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredPath {
+    String(String),
+    #[cfg(unix)]
+    Bytes(Vec<u8>),
+    #[cfg(windows)]
+    Wide(Vec<u16>),
+}
+
+impl From<&Path> for StoredPath {
+    fn from(path: &Path) -> Self {
+        match path.to_str() {
+            Some(path) => StoredPath::String(path.into()),
+            #[cfg(unix)]
+            None => {
+                use std::os::unix::ffi::OsStrExt;
+                StoredPath::Bytes(path.as_os_str().as_bytes().to_vec())
+            }
+            #[cfg(windows)]
+            None => {
+                use std::os::windows::ffi::OsStrExt;
+                StoredPath::Wide(path.as_os_str().encode_wide().collect())
+            }
+            #[cfg(not(any(unix, windows)))]
+            None => StoredPath::String(path.to_string_lossy().into_owned()),
+        }
+    }
+}
+
+impl From<StoredPath> for PathBuf {
+    fn from(path: StoredPath) -> Self {
+        match path {
+            StoredPath::String(path) => path.into(),
+            #[cfg(unix)]
+            StoredPath::Bytes(bytes) => {
+                use std::os::unix::ffi::OsStringExt;
+                std::ffi::OsString::from_vec(bytes).into()
+            }
+            #[cfg(windows)]
+            StoredPath::Wide(wide) => {
+                use std::os::windows::ffi::OsStringExt;
+                std::ffi::OsString::from_wide(&wide).into()
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrustedFile {
+    path: StoredPath,
+    hash: String,
+}
+
+fn parse_trust_store(contents: &str) -> Vec<(PathBuf, String)> {
+    serde_json::from_str::<Vec<TrustedFile>>(contents)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|file| (file.path.into(), file.hash))
+        .collect()
+}
+
+fn serialize_trust_store(trusted: &[(PathBuf, String)]) -> String {
+    let files = trusted
+        .iter()
+        .map(|(path, hash)| TrustedFile {
+            path: path.as_path().into(),
+            hash: hash.clone(),
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&files).expect("trusted files can be serialized")
+}
+
+/// List the trusted environment files, together with the hash of their approved contents.
+pub fn trusted_files() -> Vec<(PathBuf, String)> {
+    let Some(store) = trust_store_path() else {
+        return vec![];
+    };
+    let Ok(contents) = std::fs::read_to_string(store) else {
+        return vec![];
+    };
+
+    parse_trust_store(&contents)
+}
+
+/// Trust the current contents of `file`, or revoke the trust if `hash` is `None`.
+pub fn set_trusted(file: &Path, hash: Option<&str>) -> Result<(), ShellError> {
+    let Some(store) = trust_store_path() else {
+        return Err(ShellError::GenericError {
+            error: "Could not find the data directory".into(),
+            msg: "can't store trusted environment files".into(),
+            span: None,
+            help: Some("set $env.XDG_DATA_HOME to choose one".into()),
+            inner: vec![],
+        });
+    };
+
+    let mut trusted = trusted_files();
+    trusted.retain(|(path, _)| path != file);
+    if let Some(hash) = hash {
+        trusted.push((file.to_owned(), hash.to_owned()));
+    }
+
+    let contents = serialize_trust_store(&trusted);
+
+    if let Some(dir) = store.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| {
+            IoError::new_internal_with_path(
+                err,
+                "Could not create the data directory",
+                nu_protocol::location!(),
+                dir.to_owned(),
+            )
+        })?;
+    }
+    std::fs::write(&store, contents).map_err(|err| {
+        IoError::new_internal_with_path(
+            err,
+            "Could not write the trusted environment files",
+            nu_protocol::location!(),
+            store,
+        )
+    })?;
+
+    Ok(())
+}
+
+fn is_trusted(file: &Path, hash: &str) -> bool {
+    trusted_files()
+        .iter()
+        .any(|(path, trusted_hash)| path == file && trusted_hash == hash)
+}
+
+/// Environment variables which are managed by the engine and must not be reverted.
+const AUTOMATIC_ENV_VARS: &[&str] = &["PWD", "OLDPWD", "FILE_PWD", "CURRENT_FILE"];
+
+#[derive(Debug)]
+struct ActiveDirEnv {
+    file: PathBuf,
+    /// The values the changed variables had before the file was evaluated.
+    previous: Vec<(String, Option<Value>)>,
+}
+
+/// Tracks the environment file applied for the current directory of the REPL.
+#[derive(Debug, Default)]
+pub struct DirEnv {
+    active: Option<ActiveDirEnv>,
+    /// An untrusted file the user has already been told about.
+    untrusted: Option<PathBuf>,
+}
+
+impl DirEnv {
+    /// Apply or revert environment files after the current directory changed.
+    pub fn update(
+        &mut self,
+        engine_state: &mut EngineState,
+        stack: &mut Stack,
+    ) -> Result<(), ShellError> {
+        let cwd = engine_state.cwd(Some(stack))?;
+        let file = find_dir_env_file(cwd.as_std_path());
+
+        if self.active.as_ref().map(|active| &active.file) == file.as_ref() {
+            return Ok(());
+        }
+
+        self.unload(engine_state, stack)?;
+
+        let Some(file) = file else {
+            self.untrusted = None;
+            return Ok(());
+        };
+
+        let contents = match std::fs::read(&file) {
+            Ok(contents) => contents,
+            // The file may have been removed in the meantime.
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                return Err(IoError::new_internal_with_path(
+                    err,
+                    "Could not read environment file",
+                    nu_protocol::location!(),
+                    file,
+                )
+                .into());
+            }
+        };
+
+        if !is_trusted(&file, &content_hash(&contents)) {
+            if self.untrusted.as_ref() != Some(&file) {
+                report_shell_warning(
+                    engine_state,
+                    &ShellWarning::UntrustedEnvFile {
+                        file: file.display().to_string(),
+                    },
+                );
+                self.untrusted = Some(file);
+            }
+            return Ok(());
+        }
+        self.untrusted = None;
+
+        self.load(engine_state, stack, file, &contents)
+    }
+
+    /// Evaluate a trusted environment file, remembering what it changed so it can be reverted.
+    fn load(
+        &mut self,
+        engine_state: &mut EngineState,
+        stack: &mut Stack,
+        file: PathBuf,
+        contents: &[u8],
+    ) -> Result<(), ShellError> {
+        let before = stack.get_env_vars(engine_state);
+        eval_dir_env_file(engine_state, stack, &file, contents)?;
+        let after = stack.get_env_vars(engine_state);
+
+        let previous = before
+            .keys()
+            .chain(after.keys().filter(|key| !before.contains_key(*key)))
+            .filter(|key| !AUTOMATIC_ENV_VARS.contains(&key.as_str()))
+            .filter(|key| before.get(*key) != after.get(*key))
+            .map(|key| (key.clone(), before.get(key).cloned()))
+            .collect::<Vec<_>>();
+
+        if previous.iter().any(|(key, _)| key == "config") {
+            stack.update_config(engine_state)?;
+        }
+
+        self.active = Some(ActiveDirEnv { file, previous });
+
+        Ok(())
+    }
+
+    /// Revert the changes of the active environment file, if there is one.
+    fn unload(&mut self, engine_state: &EngineState, stack: &mut Stack) -> Result<(), ShellError> {
+        match self.active.take() {
+            Some(active) => revert(active, engine_state, stack),
+            None => Ok(()),
+        }
+    }
+}
+
+fn eval_dir_env_file(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    file: &Path,
+    contents: &[u8],
+) -> Result<(), ShellError> {
+    let file_name = file.to_string_lossy();
+    let (block, delta) = {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let block = parse(&mut working_set, Some(&file_name), contents, false);
+
+        if let Some(err) = working_set.parse_errors.first() {
+            report_parse_error(&working_set, err);
+            return Err(ShellError::GenericError {
+                error: format!("Failed to load {file_name}"),
+                msg: "source code has errors".into(),
+                span: None,
+                help: None,
+                inner: vec![],
+            });
+        }
+
+        (block, working_set.render())
+    };
+
+    engine_state.merge_delta(delta)?;
+    eval_block::<WithoutDebug>(engine_state, stack, &block, PipelineData::empty())?
+        .body
+        .drain()?;
+
+    Ok(())
+}
+
+fn revert(
+    active: ActiveDirEnv,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+) -> Result<(), ShellError> {
+    let mut config_changed = false;
+
+    for (key, value) in active.previous {
+        config_changed |= key == "config";
+        match value {
+            Some(value) => stack.add_env_var(key, value),
+            None => {
+                stack.remove_env_var(engine_state, &key);
+            }
+        }
+    }
+
+    if config_changed {
+        stack.update_config(engine_state)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn env_var(engine_state: &EngineState, stack: &Stack, name: &str) -> Option<String> {
+        stack
+            .get_env_var(engine_state, name)
+            .map(|value| value.to_expanded_string("", engine_state.get_config()))
+    }
+
+    #[test]
+    fn unload_reverts_changed_and_added_vars() {
+        let mut engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var("KEPT".into(), Value::test_string("before"));
+        stack.add_env_var("CHANGED".into(), Value::test_string("before"));
+
+        let mut dir_env = DirEnv::default();
+        dir_env
+            .load(
+                &mut engine_state,
+                &mut stack,
+                PathBuf::from(DIR_ENV_FILE),
+                b"$env.CHANGED = 'after'; $env.ADDED = 'new'",
+            )
+            .expect("the file loads");
+
+        assert_eq!(
+            env_var(&engine_state, &stack, "KEPT").as_deref(),
+            Some("before")
+        );
+        assert_eq!(
+            env_var(&engine_state, &stack, "CHANGED").as_deref(),
+            Some("after")
+        );
+        assert_eq!(
+            env_var(&engine_state, &stack, "ADDED").as_deref(),
+            Some("new")
+        );
+
+        dir_env
+            .unload(&engine_state, &mut stack)
+            .expect("the changes are reverted");
+
+        assert_eq!(
+            env_var(&engine_state, &stack, "KEPT").as_deref(),
+            Some("before")
+        );
+        assert_eq!(
+            env_var(&engine_state, &stack, "CHANGED").as_deref(),
+            Some("before")
+        );
+        assert_eq!(env_var(&engine_state, &stack, "ADDED"), None);
+    }
+
+    #[test]
+    fn unload_without_active_file_does_nothing() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        stack.add_env_var("FOO".into(), Value::test_string("bar"));
+
+        DirEnv::default()
+            .unload(&engine_state, &mut stack)
+            .expect("nothing to revert");
+        assert_eq!(
+            env_var(&engine_state, &stack, "FOO").as_deref(),
+            Some("bar")
+        );
+    }
+
+    fn assert_round_trips(trusted: Vec<(PathBuf, String)>) {
+        assert_eq!(parse_trust_store(&serialize_trust_store(&trusted)), trusted);
+    }
+
+    #[test]
+    fn trust_store_round_trips_paths() {
+        assert_round_trips(vec![
+            (PathBuf::from("/plain/.nu-env"), content_hash(b"a")),
+            (PathBuf::from("/tab\there/.nu-env"), content_hash(b"b")),
+            (PathBuf::from("/new\nline/.nu-env"), content_hash(b"c")),
+        ]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn trust_store_round_trips_non_utf8_paths() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        assert_round_trips(vec![(
+            Path::new("/").join(OsStr::from_bytes(b"caf\xe9/.nu-env")),
+            content_hash(b"d"),
+        )]);
+    }
+}
@@ -36,6 +36,45 @@ pub fn eval_env_change_hook(
     Ok(())
 }
 
+/// Run the closures of `watch-var` whose variable changed since they were last checked.
+pub fn eval_var_watches(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+) -> Result<(), ShellError> {
+    // Collect the changes first, so that the closures can add watches themselves
+    let changes: Vec<_> = engine_state
+        .var_watches
+        .lock()
+        .expect("var watches lock is poisoned!")
+        .iter_mut()
+        .filter_map(|watch| {
+            let current = stack.get_var(watch.var_id, Span::unknown()).ok();
+            if current == watch.last {
+                return None;
+            }
+            let before = std::mem::replace(&mut watch.last, current.clone());
+            Some((
+                watch.closure.clone(),
+                before.unwrap_or_default(),
+                current.unwrap_or_default(),
+            ))
+        })
+        .collect();
+
+    for (closure, before, after) in changes {
+        eval_hook(
+            engine_state,
+            stack,
+            None,
+            vec![("$before".into(), before), ("$after".into(), after)],
+            &closure,
+            "var_change",
+        )?;
+    }
+
+    Ok(())
+}
+
 pub fn eval_hooks(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -84,12 +123,8 @@ pub fn eval_hook(
                     vars.push((var_id, val));
                 }
 
-                let output = parse(
-                    &mut working_set,
-                    Some(&format!("{hook_name} hook")),
-                    val.as_bytes(),
-                    false,
-                );
+                let fname = working_set.virtual_file_name(&format!("{hook_name} hook"), span);
+                let output = parse(&mut working_set, Some(&fname), val.as_bytes(), false);
                 if let Some(err) = working_set.parse_errors.first() {
                     report_parse_error(&working_set, err);
                     return Err(ShellError::GenericError {
@@ -208,12 +243,10 @@ pub fn eval_hook(
                                 vars.push((var_id, val));
                             }
 
-                            let output = parse(
-                                &mut working_set,
-                                Some(&format!("{hook_name} hook")),
-                                val.as_bytes(),
-                                false,
-                            );
+                            let fname = working_set
+                                .virtual_file_name(&format!("{hook_name} hook"), source_span);
+                            let output =
+                                parse(&mut working_set, Some(&fname), val.as_bytes(), false);
                             if let Some(err) = working_set.parse_errors.first() {
                                 report_parse_error(&working_set, err);
                                 return Err(ShellError::GenericError {
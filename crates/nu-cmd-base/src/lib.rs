@@ -1,4 +1,5 @@
 #![doc = include_str!("../README.md")]
+pub mod dir_env;
 pub mod formats;
 pub mod hook;
 pub mod input_handler;
@@ -53,6 +53,10 @@ impl Command for SubCommand {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Add a color gradient (using ANSI color codes) to the given string."
     }
@@ -1,5 +1,26 @@
-use nu_cmd_base::input_handler::{CellPathOnlyArgs, operate};
+use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
+use num_format::Locale;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+    no_prefix: bool,
+    localized: Option<Localized>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+/// How to format numbers as text for people, with the separators of a locale.
+struct Localized {
+    locale: Locale,
+    decimals: Option<usize>,
+    percent: bool,
+    currency: Option<String>,
+}
 
 #[derive(Clone)]
 pub struct FormatNumber;
@@ -15,17 +36,58 @@ impl Command for FormatNumber {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("format number")
-            .input_output_types(vec![(Type::Number, Type::record())])
+            .input_output_types(vec![
+                (Type::Number, Type::record()),
+                (Type::Number, Type::String),
+            ])
             .switch(
                 "no-prefix",
                 "don't include the binary, hex or octal prefixes",
                 Some('n'),
             )
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "format the number as text with the separators of a locale, like 'de' or 'fr-CH'",
+                Some('l'),
+            )
+            .named(
+                "decimals",
+                SyntaxShape::Int,
+                "format the number as text, rounded to this number of decimals",
+                Some('d'),
+            )
+            .switch(
+                "percent",
+                "format the number as a percentage, so 0.25 is 25%",
+                Some('p'),
+            )
+            .named(
+                "currency",
+                SyntaxShape::String,
+                "format the number as an amount of money with this currency symbol or code",
+                Some('c'),
+            )
             .category(Category::Conversions)
     }
 
+    fn extra_description(&self) -> &str {
+        r#"With --locale, --decimals, --percent or --currency, the number is formatted as text with
+the digit group and decimal separators of the locale instead. The locale defaults to
+`$env.config.locale`, and to the one of the system if that isn't set. The currency comes before
+the number in locales with a decimal point, and after it in those with a decimal comma."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["display", "render", "fmt"]
+        vec![
+            "display",
+            "render",
+            "fmt",
+            "locale",
+            "currency",
+            "percent",
+            "thousands",
+        ]
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
@@ -58,6 +120,21 @@ impl Command for FormatNumber {
                         "octal" =>    Value::test_string("400110753412172702437"),
                 })),
             },
+            Example {
+                description: "Format a number with the separators of a locale",
+                example: "1234567.891 | format number --locale de --decimals 2",
+                result: Some(Value::test_string("1.234.567,89")),
+            },
+            Example {
+                description: "Format a price",
+                example: "-1299.5 | format number --locale en-US --currency '$' --decimals 2",
+                result: Some(Value::test_string("-$1,299.50")),
+            },
+            Example {
+                description: "Format a ratio as a percentage",
+                example: "0.1234 | format number --locale fr --percent --decimals 1",
+                result: Some(Value::test_string("12,3%")),
+            },
         ]
     }
 
@@ -79,44 +156,76 @@ pub(crate) fn format_number(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-    let args = CellPathOnlyArgs::from(cell_paths);
-    if call.has_flag(engine_state, stack, "no-prefix")? {
-        operate(
-            action_no_prefix,
-            args,
-            input,
-            call.head,
-            engine_state.signals(),
-        )
+    let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+    let no_prefix = call.has_flag(engine_state, stack, "no-prefix")?;
+
+    let locale: Option<Spanned<String>> = call.get_flag(engine_state, stack, "locale")?;
+    let decimals: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "decimals")?;
+    let percent = call.has_flag(engine_state, stack, "percent")?;
+    let currency: Option<String> = call.get_flag(engine_state, stack, "currency")?;
+
+    let localized = if locale.is_some() || decimals.is_some() || percent || currency.is_some() {
+        let locale = match locale {
+            Some(name) => nu_utils::locale_from_name(&name.item).ok_or_else(|| {
+                ShellError::IncorrectValue {
+                    msg: "unknown locale".into(),
+                    val_span: name.span,
+                    call_span: call.head,
+                }
+            })?,
+            None => stack.get_config(engine_state).number_locale(),
+        };
+        let decimals = decimals
+            .map(|decimals| {
+                usize::try_from(decimals.item).map_err(|_| ShellError::NeedsPositiveValue {
+                    span: decimals.span,
+                })
+            })
+            .transpose()?;
+        Some(Localized {
+            locale,
+            decimals,
+            percent,
+            currency,
+        })
     } else {
-        operate(action, args, input, call.head, engine_state.signals())
-    }
+        None
+    };
+
+    let args = Arguments {
+        cell_paths,
+        no_prefix,
+        localized,
+    };
+    operate(action, args, input, call.head, engine_state.signals())
 }
 
-fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
-    match input {
-        Value::Float { val, .. } => format_f64(*val, false, span),
-        Value::Int { val, .. } => format_i64(*val, false, span),
-        Value::Filesize { val, .. } => format_i64(val.get(), false, span),
-        // Propagate errors by explicitly matching them before the final case.
-        Value::Error { .. } => input.clone(),
-        other => Value::error(
-            ShellError::OnlySupportsThisInputType {
-                exp_input_type: "float, int, or filesize".into(),
-                wrong_type: other.get_type().to_string(),
-                dst_span: span,
-                src_span: other.span(),
-            },
-            span,
-        ),
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
+    if let Some(localized) = &args.localized {
+        return match input {
+            Value::Float { val, .. } => Value::string(localized.format(Number::Float(*val)), span),
+            Value::Int { val, .. } => Value::string(localized.format(Number::Int(*val)), span),
+            Value::Filesize { val, .. } => {
+                Value::string(localized.format(Number::Int(val.get())), span)
+            }
+            Value::Error { .. } => input.clone(),
+            other => Value::error(
+                ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "float, int, or filesize".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: span,
+                    src_span: other.span(),
+                },
+                span,
+            ),
+        };
     }
-}
 
-fn action_no_prefix(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
+    let no_prefix = args.no_prefix;
     match input {
-        Value::Float { val, .. } => format_f64(*val, true, span),
-        Value::Int { val, .. } => format_i64(*val, true, span),
-        Value::Filesize { val, .. } => format_i64(val.get(), true, span),
+        Value::Float { val, .. } => format_f64(*val, no_prefix, span),
+        Value::Int { val, .. } => format_i64(*val, no_prefix, span),
+        Value::Filesize { val, .. } => format_i64(val.get(), no_prefix, span),
         // Propagate errors by explicitly matching them before the final case.
         Value::Error { .. } => input.clone(),
         other => Value::error(
@@ -191,6 +300,60 @@ fn format_f64(num: f64, no_prefix: bool, span: Span) -> Value {
     )
 }
 
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Localized {
+    fn format(&self, number: Number) -> String {
+        let (negative, digits) = match number {
+            // Ints are formatted exactly when they aren't rounded or scaled
+            Number::Int(val) if self.decimals.is_none() && !self.percent => {
+                (val < 0, val.unsigned_abs().to_string())
+            }
+            Number::Int(val) => self.digits(val as f64),
+            Number::Float(val) => self.digits(val),
+        };
+
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+        let mut number = nu_utils::group_digits(integer, &self.locale);
+        if let Some(fraction) = fraction {
+            number.push_str(self.locale.decimal());
+            number.push_str(fraction);
+        }
+        if self.percent {
+            number.push('%');
+        }
+
+        let minus = if negative {
+            self.locale.minus_sign()
+        } else {
+            ""
+        };
+        match &self.currency {
+            Some(currency) if self.locale.decimal() == "," => format!("{minus}{number} {currency}"),
+            Some(currency) => format!("{minus}{currency}{number}"),
+            None => format!("{minus}{number}"),
+        }
+    }
+
+    /// Whether `val` is negative, and its rounded absolute value with a decimal point.
+    fn digits(&self, val: f64) -> (bool, String) {
+        let val = if self.percent { val * 100.0 } else { val };
+        let digits = match self.decimals {
+            Some(decimals) => format!("{:.*}", decimals, val.abs()),
+            None => val.abs().to_string(),
+        };
+        // Don't show a minus sign for numbers which are rounded to zero
+        let negative = val < 0.0 && digits.bytes().any(|b| matches!(b, b'1'..=b'9'));
+        (negative, digits)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
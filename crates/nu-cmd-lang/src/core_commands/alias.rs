@@ -16,6 +16,11 @@ impl Command for Alias {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("alias")
             .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .switch(
+                "parameters",
+                "replace placeholders like `{branch}` with the arguments of each call",
+                Some('p'),
+            )
             .required("name", SyntaxShape::String, "Name of the alias.")
             .required(
                 "initial_value",
@@ -49,10 +54,17 @@ impl Command for Alias {
     }
 
     fn examples(&self) -> Vec<Example<'_>> {
-        vec![Example {
-            description: "Alias ll to ls -l",
-            example: "alias ll = ls -l",
-            result: Some(Value::nothing(Span::test_data())),
-        }]
+        vec![
+            Example {
+                description: "Alias ll to ls -l",
+                example: "alias ll = ls -l",
+                result: Some(Value::nothing(Span::test_data())),
+            },
+            Example {
+                description: "Alias a command with a parameter, so `gco main` runs `git checkout main`",
+                example: "alias --parameters gco = git checkout {branch}",
+                result: Some(Value::nothing(Span::test_data())),
+            },
+        ]
     }
 }
@@ -14,6 +14,10 @@ impl Command for Attr {
             .input_output_types(vec![(Type::Nothing, Type::String)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Various attributes for custom commands."
     }
@@ -20,6 +20,10 @@ impl Command for AttrCategory {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for adding a category to custom commands."
     }
@@ -20,6 +20,10 @@ impl Command for AttrComplete {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for using another command as a completion source for all arguments."
     }
@@ -83,6 +87,10 @@ impl Command for AttrCompleteExternal {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for enabling use of the external completer for internal commands."
     }
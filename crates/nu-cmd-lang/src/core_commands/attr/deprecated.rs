@@ -44,6 +44,10 @@ impl Command for AttrDeprecated {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for marking a command or flag as deprecated."
     }
@@ -42,6 +42,10 @@ impl Command for AttrExample {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for adding examples to custom commands."
     }
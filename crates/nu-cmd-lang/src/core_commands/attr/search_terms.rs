@@ -16,6 +16,10 @@ impl Command for AttrSearchTerms {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Attribute for adding search terms to custom commands."
     }
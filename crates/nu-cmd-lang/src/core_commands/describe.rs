@@ -32,6 +32,10 @@ impl Command for Describe {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn is_const(&self) -> bool {
         true
     }
@@ -325,7 +329,7 @@ impl Description {
 }
 
 fn describe_value(value: Value, head: Span, engine_state: Option<&EngineState>) -> Value {
-    let Description::Record(record) = describe_value_inner(value, head, engine_state);
+    let (Description::Record(record), _) = describe_value_inner(value, head, engine_state);
     Value::record(record, head)
 }
 
@@ -337,9 +341,15 @@ fn describe_value_inner(
     mut value: Value,
     head: Span,
     engine_state: Option<&EngineState>,
-) -> Description {
-    let value_type = value.get_type().to_string();
-    match value {
+) -> (Description, Type) {
+    // Records and lists get their types from those of their values, so nested values aren't
+    // visited again for every level above them
+    let leaf_type = match &value {
+        Value::Record { .. } | Value::List { .. } => Type::Any,
+        _ => value.get_type(),
+    };
+    let value_type = leaf_type.to_string();
+    let description = match value {
         Value::Bool { val, .. } => Description::Record(record! {
             "type" => Value::string("bool", head),
             "detailed_type" => Value::string(value_type, head),
@@ -402,31 +412,41 @@ fn describe_value_inner(
         }),
         Value::Record { ref val, .. } => {
             let mut columns = val.clone().into_owned();
-            for (_, val) in &mut columns {
-                *val =
-                    describe_value_inner(std::mem::take(val), head, engine_state).into_value(head);
+            let mut types = Vec::with_capacity(columns.len());
+            for (name, val) in &mut columns {
+                let (description, ty) =
+                    describe_value_inner(std::mem::take(val), head, engine_state);
+                *val = description.into_value(head);
+                types.push((name.clone(), ty));
             }
+            let ty = Type::Record(types.into());
 
-            Description::Record(record! {
+            let description = Description::Record(record! {
                 "type" => Value::string("record", head),
-                "detailed_type" => Value::string(value_type, head),
-                "columns" => Value::record(columns.clone(), head),
+                "detailed_type" => Value::string(ty.to_string(), head),
+                "columns" => Value::record(columns, head),
                 "rust_type" => Value::string(type_of(&val), head),
-            })
+            });
+            return (description, ty);
         }
         Value::List { ref mut vals, .. } => {
+            let mut types = Vec::with_capacity(vals.len());
             for val in &mut *vals {
-                *val =
-                    describe_value_inner(std::mem::take(val), head, engine_state).into_value(head);
+                let (description, ty) =
+                    describe_value_inner(std::mem::take(val), head, engine_state);
+                *val = description.into_value(head);
+                types.push(ty);
             }
+            let ty = Type::of_list(types);
 
-            Description::Record(record! {
+            let description = Description::Record(record! {
                 "type" => Value::string("list", head),
-                "detailed_type" => Value::string(value_type, head),
+                "detailed_type" => Value::string(ty.to_string(), head),
                 "length" => Value::int(vals.len() as i64, head),
                 "rust_type" => Value::string(type_of(&vals), head),
                 "value" => value,
-            })
+            });
+            return (description, ty);
         }
         Value::Closure { ref val, .. } => {
             let block = engine_state.map(|engine_state| engine_state.get_block(val.block_id));
@@ -483,7 +503,8 @@ fn describe_value_inner(
                     Err(err) => Value::error(err, head),
                 }
         }),
-    }
+    };
+    (description, leaf_type)
 }
 
 fn metadata_to_value(metadata: Option<PipelineMetadata>, head: Span) -> Value {
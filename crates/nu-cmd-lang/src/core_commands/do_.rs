@@ -49,6 +49,10 @@ impl Command for Do {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
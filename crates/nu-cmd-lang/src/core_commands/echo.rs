@@ -19,6 +19,10 @@ impl Command for Echo {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn extra_description(&self) -> &str {
         r#"Unlike `print`, which prints unstructured text to stdout, `echo` is like an
 identity function and simply returns its arguments. When given no arguments,
@@ -25,6 +25,10 @@ impl Command for ErrorMake {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Create an error."
     }
@@ -16,6 +16,11 @@ impl Command for ExportAlias {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("export alias")
             .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .switch(
+                "parameters",
+                "replace placeholders like `{branch}` with the arguments of each call",
+                Some('p'),
+            )
             .required("name", SyntaxShape::String, "Name of the alias.")
             .required(
                 "initial_value",
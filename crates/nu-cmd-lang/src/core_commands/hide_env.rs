@@ -25,6 +25,10 @@ impl Command for HideEnv {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Hide environment variables in the current scope."
     }
@@ -19,6 +19,10 @@ impl Command for Ignore {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["silent", "quiet", "out-null"]
     }
@@ -30,6 +30,7 @@ mod module;
 mod mut_;
 pub(crate) mod overlay;
 mod return_;
+mod run_sandboxed;
 mod scope;
 mod try_;
 mod use_;
@@ -68,6 +69,7 @@ pub use module::Module;
 pub use mut_::Mut;
 pub use overlay::*;
 pub use return_::Return;
+pub use run_sandboxed::RunSandboxed;
 pub use scope::*;
 pub use try_::Try;
 pub use use_::Use;
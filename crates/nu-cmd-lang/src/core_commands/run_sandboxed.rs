@@ -0,0 +1,135 @@
+use nu_engine::{command_prelude::*, get_eval_block_with_early_return};
+use nu_protocol::engine::{Closure, Sandbox};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct RunSandboxed;
+
+impl Command for RunSandboxed {
+    fn name(&self) -> &str {
+        "run-sandboxed"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure in a sandbox, which denies writing files, using the network and running processes."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Commands which aren't allowed fail with an error instead of running. Allow them with the flags, which can't allow more than an outer sandbox does.
+
+Redirecting output to a file needs `--allow-write`. Plugin commands, background jobs and the config editor count as running processes.
+
+The output of the closure is collected before leaving the sandbox, so streams can't run outside of it."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("run-sandboxed")
+            .required("closure", SyntaxShape::Closure(None), "The closure to run.")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .switch(
+                "allow-write",
+                "allow creating, changing and deleting files",
+                None,
+            )
+            .switch("allow-network", "allow using the network", None)
+            .switch(
+                "allow-processes",
+                "allow running any external command and controlling processes",
+                None,
+            )
+            .named(
+                "allow-externals",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "external commands which are allowed to run",
+                None,
+            )
+            .named(
+                "allow-commands",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "commands which are allowed to run, whatever they do",
+                None,
+            )
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["restrict", "untrusted", "permission", "jail"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        caller_stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let closure: Closure = call.req(engine_state, caller_stack, 0)?;
+        let sandbox = Sandbox {
+            allow_write: call.has_flag(engine_state, caller_stack, "allow-write")?,
+            allow_network: call.has_flag(engine_state, caller_stack, "allow-network")?,
+            allow_processes: call.has_flag(engine_state, caller_stack, "allow-processes")?,
+            allow_externals: call
+                .get_flag(engine_state, caller_stack, "allow-externals")?
+                .unwrap_or_default(),
+            allow_commands: call
+                .get_flag(engine_state, caller_stack, "allow-commands")?
+                .unwrap_or_default(),
+        };
+        let sandbox = match &engine_state.sandbox {
+            Some(outer) => outer.restrict(&sandbox),
+            None => sandbox,
+        };
+
+        let mut engine_state = engine_state.clone();
+        engine_state.sandbox = Some(Arc::new(sandbox));
+
+        let mut callee_stack = caller_stack.captures_to_stack_preserve_out_dest(closure.captures);
+        let block = engine_state.get_block(closure.block_id).clone();
+        let eval_block_with_early_return = get_eval_block_with_early_return(&engine_state);
+
+        let output = eval_block_with_early_return(&engine_state, &mut callee_stack, &block, input)?
+            .body
+            .into_value(head)?;
+        Ok(output.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Code without effects runs as usual",
+                example: r#"run-sandboxed { 1 + 2 }"#,
+                result: Some(Value::test_int(3)),
+            },
+            Example {
+                description: "Evaluate a script which must not change anything",
+                example: r#"run-sandboxed { open data.json | get items | length }"#,
+                result: None,
+            },
+            Example {
+                description: "Allow writing files and running git, but nothing else",
+                example: r#"run-sandboxed --allow-write --allow-externals [git] { git log -1 | save last_commit.txt }"#,
+                result: None,
+            },
+            Example {
+                description: "Commands which aren't allowed fail",
+                example: r#"try { run-sandboxed { "hello" | save hello.txt } } catch { "denied" }"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_examples() {
+        use super::RunSandboxed;
+        use crate::test_examples;
+        test_examples(RunSandboxed {})
+    }
+}
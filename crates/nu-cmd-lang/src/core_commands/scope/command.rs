@@ -15,6 +15,10 @@ impl Command for Scope {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Commands for getting info about what is in scope."
     }
@@ -15,6 +15,10 @@ impl Command for ScopeCommands {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Output info on the commands in the current scope."
     }
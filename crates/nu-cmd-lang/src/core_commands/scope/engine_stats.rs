@@ -15,6 +15,10 @@ impl Command for ScopeEngineStats {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Output stats on the engine in the current state."
     }
@@ -15,6 +15,10 @@ impl Command for ScopeExterns {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Output info on the known externals in the current scope."
     }
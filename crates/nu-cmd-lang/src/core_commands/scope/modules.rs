@@ -15,6 +15,10 @@ impl Command for ScopeModules {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Output info on the modules in the current scope."
     }
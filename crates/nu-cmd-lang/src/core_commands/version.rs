@@ -58,6 +58,10 @@ impl Command for Version {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Display Nu version, and its build configuration."
     }
@@ -60,6 +60,7 @@ pub fn add_default_context(mut engine_state: EngineState) -> EngineState {
             Module,
             Mut,
             Return,
+            RunSandboxed,
             Scope,
             ScopeAliases,
             ScopeCommands,
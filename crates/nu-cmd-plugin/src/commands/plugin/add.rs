@@ -73,6 +73,10 @@ apparent the next time `nu` is next launched with that plugin registry file.
         ]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write, Effect::Process])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
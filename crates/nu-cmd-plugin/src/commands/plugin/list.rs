@@ -48,6 +48,10 @@ impl Command for PluginList {
             .category(Category::Plugin)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "List loaded and installed plugins."
     }
@@ -26,6 +26,10 @@ impl Command for PluginCommand {
             .category(Category::Plugin)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Commands for managing plugins."
     }
@@ -74,6 +74,10 @@ fixed with `plugin add`.
         ]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
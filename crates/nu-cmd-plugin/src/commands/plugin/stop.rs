@@ -21,6 +21,10 @@ impl Command for PluginStop {
             .category(Category::Plugin)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn description(&self) -> &str {
         "Stop an installed plugin if it was running."
     }
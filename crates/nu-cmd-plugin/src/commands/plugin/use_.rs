@@ -57,6 +57,10 @@ it was already previously registered with `plugin add`.
         CommandType::Keyword
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
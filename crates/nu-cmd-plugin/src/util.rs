@@ -24,7 +24,9 @@ fn get_plugin_registry_file_path(
     } else {
         engine_state
             .plugin_path
-            .clone()
+            .as_deref()
+            // Keep using the registry if `config migrate` moved it
+            .map(|path| nu_path::follow_state_file(path).into_owned())
             .ok_or_else(|| ShellError::GenericError {
                 error: "Plugin registry file not set".into(),
                 msg: "pass --plugin-config explicitly here".into(),
@@ -90,6 +92,13 @@ pub(crate) fn modify_plugin_file(
     // Do the operation
     operate(&mut contents)?;
 
+    // The default registry lives in the data directory, which may not exist yet
+    if custom_path.is_none()
+        && let Some(dir) = plugin_registry_file_path.parent()
+    {
+        fs::create_dir_all(dir).map_err(|err| IoError::new(err, file_span, dir.to_owned()))?;
+    }
+
     // Save the modified file on success
     contents.write_to(
         File::create(&plugin_registry_file_path)
@@ -0,0 +1,152 @@
+use super::struct_layout::{Endian, Layout};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct BytesDecodeStruct;
+
+impl Command for BytesDecodeStruct {
+    fn name(&self) -> &str {
+        "bytes decode-struct"
+    }
+
+    fn description(&self) -> &str {
+        "Decode binary data into a record, following a layout of fields."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The layout is a record of field names and types, in the order they're stored. The types are
+`u8` to `u64`, `i8` to `i64`, `f32`, `f64` and `bool`, optionally followed by `le` or `be`, and
+`bytes[N]`, `str[N]` and `pad[N]` for N bytes of binary, text and padding. A record is a nested
+struct.
+
+`[N]` after a number type decodes a list of N values. N can also be the name of an earlier
+field, for lengths which are stored in the data. `@N` places a field at offset N from the start
+of its struct."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["unpack", "parse", "binary", "header", "record"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bytes decode-struct")
+            .input_output_types(vec![
+                (Type::Binary, Type::record()),
+                (Type::Binary, Type::table()),
+            ])
+            .required(
+                "layout",
+                SyntaxShape::Record(vec![]),
+                "The names and types of the fields.",
+            )
+            .param(Endian::flag())
+            .switch(
+                "repeat",
+                "decode structs until the end of the data, and return a table",
+                Some('r'),
+            )
+            .category(Category::Bytes)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Decode a header with a big-endian version",
+                example: "0x[4e 55 00 00 01 02 ff ff] | bytes decode-struct {magic: 'str[4]', version: u16be, flags: i16}",
+                result: Some(Value::test_record(record! {
+                    "magic" => Value::test_string("NU"),
+                    "version" => Value::test_int(0x0102),
+                    "flags" => Value::test_int(-1),
+                })),
+            },
+            Example {
+                description: "Decode a list whose length is stored before it",
+                example: "0x[03 01 02 03] | bytes decode-struct {len: u8, items: 'u8[len]'}",
+                result: Some(Value::test_record(record! {
+                    "len" => Value::test_int(3),
+                    "items" => Value::test_list(vec![
+                        Value::test_int(1),
+                        Value::test_int(2),
+                        Value::test_int(3),
+                    ]),
+                })),
+            },
+            Example {
+                description: "Decode consecutive records into a table",
+                example: "0x[00 01 0a 00 02 14] | bytes decode-struct --repeat --endian big {id: u16, value: u8}",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "id" => Value::test_int(1),
+                        "value" => Value::test_int(10),
+                    }),
+                    Value::test_record(record! {
+                        "id" => Value::test_int(2),
+                        "value" => Value::test_int(20),
+                    }),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let layout: Spanned<Record> = call.req(engine_state, stack, 0)?;
+        let endian = Endian::from_call(engine_state, stack, call)?;
+        let repeat = call.has_flag(engine_state, stack, "repeat")?;
+        let layout = Layout::new(&layout.item, endian, layout.span)?;
+
+        let metadata = input.metadata();
+        let data = input.into_value(head)?;
+        let data = match &data {
+            Value::Binary { val, .. } => val,
+            Value::Error { error, .. } => return Err(*error.clone()),
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "binary".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: other.span(),
+                });
+            }
+        };
+
+        if !repeat {
+            let (record, _) = layout.decode(data, head)?;
+            return Ok(Value::record(record, head).into_pipeline_data_with_metadata(metadata));
+        }
+
+        let mut rows = vec![];
+        let mut rest = data.as_slice();
+        while !rest.is_empty() {
+            let (record, len) = layout.decode(rest, head)?;
+            if len == 0 {
+                return Err(ShellError::IncorrectValue {
+                    msg: "the layout takes no bytes, so it can't be repeated".into(),
+                    val_span: layout.span(),
+                    call_span: head,
+                });
+            }
+            rows.push(Value::record(record, head));
+            rest = &rest[len..];
+        }
+        Ok(Value::list(rows, head).into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(BytesDecodeStruct {})
+    }
+}
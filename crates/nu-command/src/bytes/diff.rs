@@ -0,0 +1,301 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{ListStream, shell_error::io::IoError};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufReader, Cursor, Read},
+};
+
+/// How many bytes are compared at once. Chunks which are equal are skipped without comparing
+/// them byte by byte.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The longest range which is returned. Longer ranges are split, so they don't have to fit into
+/// memory.
+const MAX_RANGE_LEN: usize = CHUNK_SIZE;
+
+#[derive(Clone)]
+pub struct BytesDiff;
+
+impl Command for BytesDiff {
+    fn name(&self) -> &str {
+        "bytes diff"
+    }
+
+    fn description(&self) -> &str {
+        "Compare two files or binary values, and return the ranges of bytes which differ."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Every range is a record with its offset, its length, and the bytes on the left and on the
+right. If one side is longer than the other, the rest of it is the last range, and the other side
+is empty there. Ranges longer than 64 KiB are split into several ranges.
+
+Files are read as they're compared, so large files don't have to fit into memory."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["compare", "cmp", "binary", "reproducible"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bytes diff")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("offset".into(), Type::Int),
+                        ("length".into(), Type::Int),
+                        ("left".into(), Type::Binary),
+                        ("right".into(), Type::Binary),
+                    ]
+                    .into(),
+                ),
+            )])
+            .required(
+                "left",
+                SyntaxShape::OneOf(vec![SyntaxShape::Binary, SyntaxShape::Filepath]),
+                "The first file or binary value.",
+            )
+            .required(
+                "right",
+                SyntaxShape::OneOf(vec![SyntaxShape::Binary, SyntaxShape::Filepath]),
+                "The second file or binary value.",
+            )
+            .category(Category::Bytes)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Find the bytes which differ between two values",
+                example: "bytes diff 0x[01 02 03 04 05] 0x[01 ff fe 04]",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "offset" => Value::test_int(1),
+                        "length" => Value::test_int(2),
+                        "left" => Value::test_binary(vec![0x02, 0x03]),
+                        "right" => Value::test_binary(vec![0xff, 0xfe]),
+                    }),
+                    Value::test_record(record! {
+                        "offset" => Value::test_int(4),
+                        "length" => Value::test_int(1),
+                        "left" => Value::test_binary(vec![0x05]),
+                        "right" => Value::test_binary(vec![]),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Check whether two builds are byte-for-byte identical",
+                example: "bytes diff build-1/app build-2/app | is-empty",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cwd = engine_state.cwd(Some(stack))?;
+        let mut open = |index| -> Result<Box<dyn Read + Send>, ShellError> {
+            let value: Value = call.req(engine_state, stack, index)?;
+            let span = value.span();
+            match value {
+                Value::Binary { val, .. } => Ok(Box::new(Cursor::new(val))),
+                Value::String { val, .. } => {
+                    let path = nu_path::expand_path_with(val, &cwd, true);
+                    let file = File::open(&path).map_err(|err| IoError::new(err, span, path))?;
+                    Ok(Box::new(BufReader::new(file)))
+                }
+                other => Err(ShellError::TypeMismatch {
+                    err_message: format!("expected a file or binary, found {}", other.get_type()),
+                    span,
+                }),
+            }
+        };
+        let diff = Diff {
+            left: open(0)?,
+            right: open(1)?,
+            offset: 0,
+            range: None,
+            ranges: VecDeque::new(),
+            finished: false,
+            span: head,
+        };
+        let signals = engine_state.signals().clone();
+        Ok(ListStream::new(diff, head, signals).into())
+    }
+}
+
+/// A range of bytes which differ.
+struct Range {
+    offset: u64,
+    left: Vec<u8>,
+    right: Vec<u8>,
+}
+
+impl Range {
+    fn into_value(self, span: Span) -> Value {
+        let length = self.left.len().max(self.right.len());
+        Value::record(
+            record! {
+                "offset" => Value::int(self.offset as i64, span),
+                "length" => Value::int(length as i64, span),
+                "left" => Value::binary(self.left, span),
+                "right" => Value::binary(self.right, span),
+            },
+            span,
+        )
+    }
+}
+
+/// An iterator over the ranges where two readers differ.
+struct Diff {
+    left: Box<dyn Read + Send>,
+    right: Box<dyn Read + Send>,
+    /// The offset of the next chunk.
+    offset: u64,
+    /// The range which is being compared, which may continue in the next chunk.
+    range: Option<Range>,
+    /// The ranges which are complete, but not returned yet.
+    ranges: VecDeque<Range>,
+    finished: bool,
+    span: Span,
+}
+
+impl Diff {
+    /// Compare the next chunks, and return whether there were any.
+    fn compare_chunk(&mut self) -> std::io::Result<bool> {
+        let left = read_chunk(&mut self.left)?;
+        let right = read_chunk(&mut self.right)?;
+        let len = left.len().max(right.len());
+        if len == 0 {
+            return Ok(false);
+        }
+
+        if left == right {
+            self.ranges.extend(self.range.take());
+        } else {
+            for i in 0..len {
+                let (l, r) = (left.get(i), right.get(i));
+                if l == r {
+                    self.ranges.extend(self.range.take());
+                    continue;
+                }
+                let range = self.range.get_or_insert_with(|| Range {
+                    offset: self.offset + i as u64,
+                    left: vec![],
+                    right: vec![],
+                });
+                range.left.extend(l);
+                range.right.extend(r);
+                if range.left.len().max(range.right.len()) >= MAX_RANGE_LEN {
+                    self.ranges.extend(self.range.take());
+                }
+            }
+        }
+        self.offset += len as u64;
+        Ok(true)
+    }
+}
+
+impl Iterator for Diff {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        loop {
+            if let Some(range) = self.ranges.pop_front() {
+                return Some(range.into_value(self.span));
+            }
+            if self.finished {
+                return self.range.take().map(|range| range.into_value(self.span));
+            }
+            match self.compare_chunk() {
+                Ok(true) => {}
+                Ok(false) => self.finished = true,
+                Err(err) => {
+                    self.finished = true;
+                    self.range = None;
+                    return Some(Value::error(
+                        IoError::new(err, self.span, None).into(),
+                        self.span,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Read up to [`CHUNK_SIZE`] bytes, fewer only at the end of `reader`.
+fn read_chunk(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    reader.take(CHUNK_SIZE as u64).read_to_end(&mut chunk)?;
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn diff(left: Vec<u8>, right: Vec<u8>) -> Diff {
+        Diff {
+            left: Box::new(Cursor::new(left)),
+            right: Box::new(Cursor::new(right)),
+            offset: 0,
+            range: None,
+            ranges: VecDeque::new(),
+            finished: false,
+            span: Span::test_data(),
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(BytesDiff {})
+    }
+
+    #[test]
+    fn ranges_continue_across_chunks() {
+        let left = vec![0; CHUNK_SIZE + 2];
+        let mut right = left.clone();
+        right[CHUNK_SIZE - 1] = 1;
+        right[CHUNK_SIZE] = 1;
+        let ranges = diff(left, right).collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![Value::test_record(record! {
+                "offset" => Value::test_int(CHUNK_SIZE as i64 - 1),
+                "length" => Value::test_int(2),
+                "left" => Value::test_binary(vec![0, 0]),
+                "right" => Value::test_binary(vec![1, 1]),
+            })]
+        );
+    }
+
+    #[test]
+    fn long_ranges_are_split() {
+        let len = MAX_RANGE_LEN * 2 + 1;
+        let offsets = diff(vec![0; len], vec![1; len])
+            .map(|range| {
+                range
+                    .get_data_by_key("offset")
+                    .and_then(|v| v.as_int().ok())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            vec![
+                Some(0),
+                Some(MAX_RANGE_LEN as i64),
+                Some(2 * MAX_RANGE_LEN as i64)
+            ]
+        );
+    }
+}
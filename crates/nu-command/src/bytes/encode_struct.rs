@@ -0,0 +1,104 @@
+use super::struct_layout::{Endian, Layout};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct BytesEncodeStruct;
+
+impl Command for BytesEncodeStruct {
+    fn name(&self) -> &str {
+        "bytes encode-struct"
+    }
+
+    fn description(&self) -> &str {
+        "Encode a record into binary data, following a layout of fields."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The layout is the same as for `bytes decode-struct`. Numbers have to fit into their fields,
+and `bytes[N]` and `str[N]` fields are padded with zero bytes up to their length. A table is
+encoded as its rows one after another."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["pack", "serialize", "binary", "header", "record"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bytes encode-struct")
+            .input_output_types(vec![
+                (Type::record(), Type::Binary),
+                (Type::table(), Type::Binary),
+            ])
+            .required(
+                "layout",
+                SyntaxShape::Record(vec![]),
+                "The names and types of the fields.",
+            )
+            .param(Endian::flag())
+            .category(Category::Bytes)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Encode a header with a big-endian version",
+                example: "{magic: NU, version: 258, flags: -1} | bytes encode-struct {magic: 'str[4]', version: u16be, flags: i16}",
+                result: Some(Value::test_binary(vec![
+                    0x4e, 0x55, 0x00, 0x00, 0x01, 0x02, 0xff, 0xff,
+                ])),
+            },
+            Example {
+                description: "Encode a table of records",
+                example: "[[id value]; [1 10] [2 20]] | bytes encode-struct --endian big {id: u16, value: u8}",
+                result: Some(Value::test_binary(vec![0x00, 0x01, 0x0a, 0x00, 0x02, 0x14])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let layout: Spanned<Record> = call.req(engine_state, stack, 0)?;
+        let endian = Endian::from_call(engine_state, stack, call)?;
+        let layout = Layout::new(&layout.item, endian, layout.span)?;
+
+        let metadata = input.metadata();
+        let mut out = vec![];
+        let mut encode = |value: &Value| match value {
+            Value::Record { val, .. } => layout.encode(val, &mut out, head),
+            Value::Error { error, .. } => Err(*error.clone()),
+            other => Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "record or table".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: other.span(),
+            }),
+        };
+        match input {
+            PipelineData::Value(value @ Value::Record { .. }, ..) => encode(&value)?,
+            input => {
+                for value in input.into_iter() {
+                    encode(&value)?;
+                }
+            }
+        }
+        Ok(Value::binary(out, head).into_pipeline_data_with_metadata(metadata))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(BytesEncodeStruct {})
+    }
+}
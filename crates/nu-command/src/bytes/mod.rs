@@ -3,6 +3,9 @@ mod at;
 mod build_;
 mod bytes_;
 mod collect;
+mod decode_struct;
+mod diff;
+mod encode_struct;
 mod ends_with;
 mod index_of;
 mod length;
@@ -11,12 +14,16 @@ mod replace;
 mod reverse;
 mod split;
 mod starts_with;
+mod struct_layout;
 
 pub use add::BytesAdd;
 pub use at::BytesAt;
 pub use build_::BytesBuild;
 pub use bytes_::Bytes;
 pub use collect::BytesCollect;
+pub use decode_struct::BytesDecodeStruct;
+pub use diff::BytesDiff;
+pub use encode_struct::BytesEncodeStruct;
 pub use ends_with::BytesEndsWith;
 pub use index_of::BytesIndexOf;
 pub use length::BytesLen;
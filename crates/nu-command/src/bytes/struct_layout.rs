@@ -0,0 +1,653 @@
+//! The layouts of `bytes decode-struct` and `bytes encode-struct`.
+//!
+//! A layout is a record of field names and types, in the order the fields are stored. A type is
+//! a string like `u32`, `i16be`, `f64[3]`, `str[count]` or `u8@16`, or a record for a nested
+//! struct:
+//!
+//! - `u8` to `u64`, `i8` to `i64`, `f32`, `f64` and `bool`, optionally followed by `le` or `be`
+//!   to override the endianness of the command
+//! - `bytes[N]` and `str[N]` for N bytes of binary or text, and `pad[N]` for N bytes which are
+//!   skipped
+//! - `[N]` after a number or bool type makes it a list of N values
+//! - `@N` at the end places the field at offset N from the start of its struct
+//!
+//! Counts are either numbers or the name of an earlier integer field.
+
+use nu_engine::command_prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    pub(super) fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Endian, ShellError> {
+        let native = if cfg!(target_endian = "little") {
+            Endian::Little
+        } else {
+            Endian::Big
+        };
+        match call.get_flag::<Spanned<String>>(engine_state, stack, "endian")? {
+            None => Ok(native),
+            Some(endian) => match endian.item.as_str() {
+                "native" => Ok(native),
+                "little" => Ok(Endian::Little),
+                "big" => Ok(Endian::Big),
+                _ => Err(ShellError::TypeMismatch {
+                    err_message: "Endian must be one of native, little, big".to_string(),
+                    span: endian.span,
+                }),
+            },
+        }
+    }
+
+    pub(super) fn flag() -> Flag {
+        Flag::new("endian")
+            .short('e')
+            .arg(SyntaxShape::String)
+            .desc("endianness of the fields, available options: native(default), little, big")
+            .completion(Completion::new_list(&["native", "little", "big"]))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Count {
+    Fixed(usize),
+    Field(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Int {
+        size: usize,
+        signed: bool,
+        endian: Option<Endian>,
+    },
+    Float {
+        size: usize,
+        endian: Option<Endian>,
+    },
+    Bool,
+    Bytes,
+    Str,
+    Pad,
+    Struct(Vec<Field>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    name: String,
+    kind: Kind,
+    count: Option<Count>,
+    offset: Option<usize>,
+}
+
+/// A parsed layout.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Layout {
+    fields: Vec<Field>,
+    endian: Endian,
+    span: Span,
+}
+
+impl Layout {
+    pub(super) fn new(layout: &Record, endian: Endian, span: Span) -> Result<Self, ShellError> {
+        Ok(Layout {
+            fields: parse_fields(layout, span)?,
+            endian,
+            span,
+        })
+    }
+
+    pub(super) fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Decode one struct from the start of `data`, and return it with the number of bytes it
+    /// takes.
+    pub(super) fn decode(&self, data: &[u8], head: Span) -> Result<(Record, usize), ShellError> {
+        let decoder = Decoder {
+            data,
+            endian: self.endian,
+            head,
+        };
+        let mut end = 0;
+        let record = decoder.decode(&self.fields, 0, &mut end)?;
+        Ok((record, end))
+    }
+
+    /// Append the encoding of `record` to `out`.
+    pub(super) fn encode(
+        &self,
+        record: &Record,
+        out: &mut Vec<u8>,
+        head: Span,
+    ) -> Result<(), ShellError> {
+        let encoder = Encoder {
+            endian: self.endian,
+            layout_span: self.span,
+            head,
+        };
+        let base = out.len();
+        encoder.encode(&self.fields, record, base, out)
+    }
+}
+
+fn invalid_layout(msg: String, span: Span) -> ShellError {
+    ShellError::GenericError {
+        error: "Invalid struct layout".into(),
+        msg,
+        span: Some(span),
+        help: Some(
+            "field types are like `u32`, `i16be`, `f64[3]`, `str[count]`, `u8@16` or records"
+                .into(),
+        ),
+        inner: vec![],
+    }
+}
+
+fn parse_fields(layout: &Record, span: Span) -> Result<Vec<Field>, ShellError> {
+    layout
+        .iter()
+        .map(|(name, ty)| {
+            let (kind, count, offset) = match ty {
+                Value::Record { val, .. } => (Kind::Struct(parse_fields(val, ty.span())?), None, None),
+                Value::String { val, .. } => parse_type(val)
+                    .map_err(|msg| invalid_layout(format!("field `{name}`: {msg}"), ty.span()))?,
+                other => {
+                    return Err(invalid_layout(
+                        format!(
+                            "the type of field `{name}` is a {}, not a string or record",
+                            other.get_type()
+                        ),
+                        other.span(),
+                    ));
+                }
+            };
+            if let Some(Count::Field(count)) = &count
+                && !layout.columns().take_while(|column| *column != name).any(|column| column == count)
+            {
+                return Err(invalid_layout(
+                    format!("the count of field `{name}` refers to `{count}`, which isn't an earlier field"),
+                    ty.span(),
+                ));
+            }
+            Ok(Field {
+                name: name.clone(),
+                kind,
+                count,
+                offset,
+            })
+        })
+        .collect::<Result<_, _>>()
+        .and_then(|fields: Vec<Field>| {
+            if fields.is_empty() {
+                Err(invalid_layout("the layout has no fields".into(), span))
+            } else {
+                Ok(fields)
+            }
+        })
+}
+
+fn parse_type(ty: &str) -> Result<(Kind, Option<Count>, Option<usize>), String> {
+    let (ty, offset) = match ty.split_once('@') {
+        Some((ty, offset)) => (
+            ty,
+            Some(
+                offset
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("`{offset}` isn't an offset"))?,
+            ),
+        ),
+        None => (ty, None),
+    };
+    let (base, count) = match ty.split_once('[') {
+        Some((base, rest)) => {
+            let count = rest
+                .strip_suffix(']')
+                .ok_or_else(|| format!("`{ty}` is missing a `]`"))?
+                .trim();
+            let count = match count.parse() {
+                Ok(count) => Count::Fixed(count),
+                Err(_) if !count.is_empty() => Count::Field(count.into()),
+                Err(_) => return Err(format!("`{ty}` is missing a count")),
+            };
+            (base.trim(), Some(count))
+        }
+        None => (ty.trim(), None),
+    };
+
+    let (number, endian) = match base {
+        _ if base.ends_with("le") => (&base[..base.len() - 2], Some(Endian::Little)),
+        _ if base.ends_with("be") => (&base[..base.len() - 2], Some(Endian::Big)),
+        _ => (base, None),
+    };
+    let kind = match number {
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" => Kind::Int {
+            size: number[1..].parse::<usize>().expect("the sizes are numbers") / 8,
+            signed: number.starts_with('i'),
+            endian,
+        },
+        "f32" | "f64" => Kind::Float {
+            size: number[1..].parse::<usize>().expect("the sizes are numbers") / 8,
+            endian,
+        },
+        _ => match base {
+            "bool" => Kind::Bool,
+            "bytes" => Kind::Bytes,
+            "str" => Kind::Str,
+            "pad" => Kind::Pad,
+            _ => return Err(format!("`{base}` isn't a type")),
+        },
+    };
+    if matches!(kind, Kind::Bytes | Kind::Str | Kind::Pad) && count.is_none() {
+        return Err(format!("`{base}` needs a length, like `{base}[4]`"));
+    }
+    Ok((kind, count, offset))
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    endian: Endian,
+    head: Span,
+}
+
+impl Decoder<'_> {
+    fn decode(
+        &self,
+        fields: &[Field],
+        base: usize,
+        cursor: &mut usize,
+    ) -> Result<Record, ShellError> {
+        let mut record = Record::new();
+        *cursor = base;
+        for field in fields {
+            if let Some(offset) = field.offset {
+                *cursor = base + offset;
+            }
+            let count = match &field.count {
+                None => None,
+                Some(Count::Fixed(count)) => Some(*count),
+                Some(Count::Field(name)) => {
+                    Some(count_from(&record, name, &field.name, self.head)?)
+                }
+            };
+            let value = match (&field.kind, count) {
+                (Kind::Struct(fields), _) => {
+                    Value::record(self.decode(fields, *cursor, cursor)?, self.head)
+                }
+                (Kind::Bytes, Some(len)) => {
+                    Value::binary(self.take(&field.name, cursor, len)?, self.head)
+                }
+                (Kind::Str, Some(len)) => {
+                    let bytes = self.take(&field.name, cursor, len)?;
+                    let text = String::from_utf8_lossy(bytes);
+                    Value::string(text.trim_end_matches('\0'), self.head)
+                }
+                (Kind::Pad, Some(len)) => {
+                    self.take(&field.name, cursor, len)?;
+                    continue;
+                }
+                (kind, None) => self.scalar(kind, &field.name, cursor)?,
+                (kind, Some(count)) => Value::list(
+                    (0..count)
+                        .map(|_| self.scalar(kind, &field.name, cursor))
+                        .collect::<Result<_, _>>()?,
+                    self.head,
+                ),
+            };
+            record.push(field.name.clone(), value);
+        }
+        Ok(record)
+    }
+
+    fn take(&self, field: &str, cursor: &mut usize, len: usize) -> Result<&[u8], ShellError> {
+        let start = *cursor;
+        let bytes = start
+            .checked_add(len)
+            .and_then(|end| self.data.get(start..end))
+            .ok_or_else(|| ShellError::GenericError {
+                error: "Not enough data".into(),
+                msg: format!(
+                    "field `{field}` needs {len} bytes at offset {start}, but the data is {} bytes long",
+                    self.data.len()
+                ),
+                span: Some(self.head),
+                help: None,
+                inner: vec![],
+            })?;
+        *cursor += len;
+        Ok(bytes)
+    }
+
+    fn scalar(&self, kind: &Kind, field: &str, cursor: &mut usize) -> Result<Value, ShellError> {
+        let span = self.head;
+        Ok(match *kind {
+            Kind::Int {
+                size,
+                signed,
+                endian,
+            } => {
+                let bits = read_uint(
+                    self.take(field, cursor, size)?,
+                    endian.unwrap_or(self.endian),
+                );
+                if signed {
+                    let shift = 64 - 8 * size as u32;
+                    Value::int(((bits << shift) as i64) >> shift, span)
+                } else {
+                    let val = i64::try_from(bits).map_err(|_| ShellError::GenericError {
+                        error: "Integer too large".into(),
+                        msg: format!("field `{field}` is {bits}, which doesn't fit into an int"),
+                        span: Some(span),
+                        help: Some("decode it as `i64` or `bytes[8]` instead".into()),
+                        inner: vec![],
+                    })?;
+                    Value::int(val, span)
+                }
+            }
+            Kind::Float { size, endian } => {
+                let bits = read_uint(
+                    self.take(field, cursor, size)?,
+                    endian.unwrap_or(self.endian),
+                );
+                match size {
+                    4 => Value::float(f32::from_bits(bits as u32).into(), span),
+                    _ => Value::float(f64::from_bits(bits), span),
+                }
+            }
+            Kind::Bool => Value::bool(self.take(field, cursor, 1)?[0] != 0, span),
+            Kind::Bytes | Kind::Str | Kind::Pad | Kind::Struct(_) => {
+                unreachable!("handled by `decode`")
+            }
+        })
+    }
+}
+
+/// The value of the earlier field `name`, which is the count of `field`.
+fn count_from(record: &Record, name: &str, field: &str, span: Span) -> Result<usize, ShellError> {
+    match record.get(name) {
+        Some(Value::Int { val, .. }) if *val >= 0 => Ok(*val as usize),
+        _ => Err(ShellError::GenericError {
+            error: "Invalid count".into(),
+            msg: format!("the count of `{field}` is `{name}`, which isn't an unsigned int"),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        }),
+    }
+}
+
+fn read_uint(bytes: &[u8], endian: Endian) -> u64 {
+    let fold = |bits: u64, byte: &u8| (bits << 8) | u64::from(*byte);
+    match endian {
+        Endian::Big => bytes.iter().fold(0, fold),
+        Endian::Little => bytes.iter().rev().fold(0, fold),
+    }
+}
+
+struct Encoder {
+    endian: Endian,
+    layout_span: Span,
+    head: Span,
+}
+
+impl Encoder {
+    fn encode(
+        &self,
+        fields: &[Field],
+        record: &Record,
+        base: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        for field in fields {
+            if let Some(offset) = field.offset {
+                let start = base + offset;
+                if start < out.len() {
+                    return Err(invalid_layout(
+                        format!(
+                            "field `{}` is at offset {offset}, which overlaps the fields before it",
+                            field.name
+                        ),
+                        self.layout_span,
+                    ));
+                }
+                out.resize(start, 0);
+            }
+            let count = match &field.count {
+                None => None,
+                Some(Count::Fixed(count)) => Some(*count),
+                Some(Count::Field(name)) => Some(count_from(record, name, &field.name, self.head)?),
+            };
+            if let (Kind::Pad, Some(len)) = (&field.kind, count) {
+                out.resize(out.len() + len, 0);
+                continue;
+            }
+            let value = record
+                .get(&field.name)
+                .ok_or_else(|| ShellError::CantFindColumn {
+                    col_name: field.name.clone(),
+                    span: Some(self.layout_span),
+                    src_span: self.head,
+                })?;
+
+            match (&field.kind, value) {
+                (Kind::Struct(fields), Value::Record { val, .. }) => {
+                    self.encode(fields, val, out.len(), out)?
+                }
+                (Kind::Bytes | Kind::Str, value) => {
+                    let bytes = match (&field.kind, value) {
+                        (Kind::Bytes, Value::Binary { val, .. }) => val.as_slice(),
+                        (Kind::Str, Value::String { val, .. }) => val.as_bytes(),
+                        (_, value) => return Err(self.mismatch(field, value)),
+                    };
+                    match count {
+                        Some(len) if bytes.len() > len => {
+                            return Err(ShellError::IncorrectValue {
+                                msg: format!(
+                                    "`{}` is {} bytes long, but the field has {len}",
+                                    field.name,
+                                    bytes.len()
+                                ),
+                                val_span: value.span(),
+                                call_span: self.head,
+                            });
+                        }
+                        Some(len) => {
+                            out.extend_from_slice(bytes);
+                            out.resize(out.len() + len - bytes.len(), 0);
+                        }
+                        None => out.extend_from_slice(bytes),
+                    }
+                }
+                (kind, Value::List { vals, .. }) if count.is_some() => {
+                    let count = count.unwrap_or_default();
+                    if vals.len() != count {
+                        return Err(ShellError::IncorrectValue {
+                            msg: format!(
+                                "`{}` has {} items, but the field has {count}",
+                                field.name,
+                                vals.len()
+                            ),
+                            val_span: value.span(),
+                            call_span: self.head,
+                        });
+                    }
+                    for val in vals {
+                        self.scalar(kind, field, val, out)?;
+                    }
+                }
+                (kind, value) if count.is_none() => self.scalar(kind, field, value, out)?,
+                (_, value) => return Err(self.mismatch(field, value)),
+            }
+        }
+        Ok(())
+    }
+
+    fn scalar(
+        &self,
+        kind: &Kind,
+        field: &Field,
+        value: &Value,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ShellError> {
+        let (bits, size, endian) = match (kind, value) {
+            (
+                Kind::Int {
+                    size,
+                    signed,
+                    endian,
+                },
+                Value::Int { val, .. },
+            ) => {
+                let bits = 8 * *size as u32;
+                let fits = if *signed {
+                    bits == 64 || matches!(*val >> (bits - 1), 0 | -1)
+                } else {
+                    *val >= 0 && (bits == 64 || *val >> bits == 0)
+                };
+                if !fits {
+                    return Err(ShellError::IncorrectValue {
+                        msg: format!("{val} is out of range for `{}`", type_name(*size, *signed)),
+                        val_span: value.span(),
+                        call_span: self.head,
+                    });
+                }
+                (*val as u64, *size, *endian)
+            }
+            (Kind::Float { size: 4, endian }, Value::Float { val, .. }) => {
+                (u64::from((*val as f32).to_bits()), 4, *endian)
+            }
+            (Kind::Float { size, endian }, Value::Float { val, .. }) => {
+                (val.to_bits(), *size, *endian)
+            }
+            (Kind::Float { size, endian }, Value::Int { val, .. }) => {
+                return self.scalar(
+                    &Kind::Float {
+                        size: *size,
+                        endian: *endian,
+                    },
+                    field,
+                    &Value::float(*val as f64, value.span()),
+                    out,
+                );
+            }
+            (Kind::Bool, Value::Bool { val, .. }) => (u64::from(*val), 1, None),
+            _ => return Err(self.mismatch(field, value)),
+        };
+        let bytes = bits.to_le_bytes();
+        let bytes = &bytes[..size];
+        match endian.unwrap_or(self.endian) {
+            Endian::Little => out.extend_from_slice(bytes),
+            Endian::Big => out.extend(bytes.iter().rev()),
+        }
+        Ok(())
+    }
+
+    fn mismatch(&self, field: &Field, value: &Value) -> ShellError {
+        ShellError::TypeMismatch {
+            err_message: format!(
+                "field `{}` can't be encoded from a {}",
+                field.name,
+                value.get_type()
+            ),
+            span: value.span(),
+        }
+    }
+}
+
+fn type_name(size: usize, signed: bool) -> String {
+    format!("{}{}", if signed { 'i' } else { 'u' }, size * 8)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nu_protocol::record;
+
+    fn layout(layout: Record) -> Layout {
+        Layout::new(&layout, Endian::Little, Span::test_data()).unwrap()
+    }
+
+    #[test]
+    fn types_are_parsed() {
+        assert_eq!(
+            parse_type("i16be[count]@4"),
+            Ok((
+                Kind::Int {
+                    size: 2,
+                    signed: true,
+                    endian: Some(Endian::Big)
+                },
+                Some(Count::Field("count".into())),
+                Some(4)
+            ))
+        );
+        assert!(parse_type("str").is_err());
+        assert!(parse_type("u24").is_err());
+    }
+
+    #[test]
+    fn structs_round_trip() {
+        let layout = layout(record! {
+            "magic" => Value::test_string("str[4]"),
+            "version" => Value::test_string("u16be"),
+            "count" => Value::test_string("u8"),
+            "values" => Value::test_string("i16[count]"),
+            "header" => Value::test_record(record! {
+                "flag" => Value::test_string("bool@1"),
+            }),
+        });
+        let data = [
+            b'N', b'U', 0, 0, 0x01, 0x02, 2, 0xff, 0xff, 0x10, 0x00, 0, 1,
+        ];
+        let (record, len) = layout.decode(&data, Span::test_data()).unwrap();
+        assert_eq!(len, data.len());
+        assert_eq!(
+            record,
+            record! {
+                "magic" => Value::test_string("NU"),
+                "version" => Value::test_int(0x0102),
+                "count" => Value::test_int(2),
+                "values" => Value::test_list(vec![Value::test_int(-1), Value::test_int(16)]),
+                "header" => Value::test_record(record! {
+                    "flag" => Value::test_bool(true),
+                }),
+            }
+        );
+
+        let mut out = vec![];
+        layout.encode(&record, &mut out, Span::test_data()).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn out_of_range_values_are_errors() {
+        let layout = layout(record! { "byte" => Value::test_string("i8") });
+        let mut out = vec![];
+        assert!(
+            layout
+                .encode(
+                    &record! { "byte" => Value::test_int(128) },
+                    &mut out,
+                    Span::test_data()
+                )
+                .is_err()
+        );
+        assert!(
+            layout
+                .encode(
+                    &record! { "byte" => Value::test_int(-128) },
+                    &mut out,
+                    Span::test_data()
+                )
+                .is_ok()
+        );
+    }
+}
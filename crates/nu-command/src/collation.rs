@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+
+/// The order of strings in the alphabet of a language, for sorting words like people expect.
+///
+/// Letters are compared without their accents and case first, so `é` sorts next to `e`
+/// rather than after `z`, except for the letters which a language has as letters of their own,
+/// like `ö` in Swedish or `ñ` in Spanish. The accents, and then the case, only decide between
+/// strings which are otherwise equal.
+///
+/// This covers the Latin letters of the Latin-1 and Latin Extended-A blocks; other characters
+/// are compared by their code point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Collation {
+    language: Language,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Language {
+    #[default]
+    Root,
+    /// `å`, `ä` and `ö` after `z`.
+    Swedish,
+    /// `æ`, `ø` and `å` after `z`.
+    Danish,
+    /// `ñ` after `n`.
+    Spanish,
+}
+
+/// The first private use character, which sorts after every letter we fold to.
+const AFTER: u32 = 0xE000;
+
+impl Collation {
+    /// The collation of the locale with the name `name`, like `de`, `sv-SE` or `es_ES.UTF-8`.
+    ///
+    /// Locales of languages without their own letters use the root collation.
+    pub fn from_locale(name: &str) -> Self {
+        let name = name.split('.').next().unwrap_or(name);
+        let language = name
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(name)
+            .to_ascii_lowercase();
+        let language = match language.as_str() {
+            "sv" | "fi" => Language::Swedish,
+            "da" | "nb" | "nn" | "no" => Language::Danish,
+            "es" => Language::Spanish,
+            _ => Language::Root,
+        };
+        Collation { language }
+    }
+
+    /// Compare two strings, with the digits in them compared by their numeric value if `natural`.
+    pub fn compare(&self, left: &str, right: &str, natural: bool) -> Ordering {
+        let compare = |left: &str, right: &str| {
+            if natural {
+                alphanumeric_sort::compare_str(left, right)
+            } else {
+                left.cmp(right)
+            }
+        };
+        compare(&self.primary_key(left), &self.primary_key(right))
+            .then_with(|| compare(&left.to_lowercase(), &right.to_lowercase()))
+            .then_with(|| compare(left, right))
+    }
+
+    /// Compare two strings like [`compare`](Self::compare), but ignoring their case.
+    pub fn compare_ignore_case(&self, left: &str, right: &str, natural: bool) -> Ordering {
+        self.compare(&left.to_lowercase(), &right.to_lowercase(), natural)
+    }
+
+    /// `text` in lowercase with the accents removed, and the letters of the language after the
+    /// letter they follow in its alphabet.
+    ///
+    /// A letter which follows `z` becomes `z` followed by a private use character, so that it's
+    /// greater than every word starting with `z`, but less than the letters after it.
+    fn primary_key(&self, text: &str) -> String {
+        let mut key = String::with_capacity(text.len());
+        for c in text.chars().flat_map(char::to_lowercase) {
+            if let Some((letter, rank)) = self.own_letter(c) {
+                key.push(letter);
+                key.push(char::from_u32(AFTER + rank).unwrap_or(char::MAX));
+            } else if let Some(base) = base_letters(c) {
+                key.push_str(base);
+            } else {
+                key.push(c);
+            }
+        }
+        key
+    }
+
+    /// The letter after which `c` comes in the alphabet, and its rank among the letters there,
+    /// if it's a letter of its own in the language.
+    fn own_letter(&self, c: char) -> Option<(char, u32)> {
+        match (self.language, c) {
+            (Language::Swedish, 'å') => Some(('z', 1)),
+            (Language::Swedish, 'ä' | 'æ') => Some(('z', 2)),
+            (Language::Swedish, 'ö' | 'ø') => Some(('z', 3)),
+            (Language::Danish, 'æ' | 'ä') => Some(('z', 1)),
+            (Language::Danish, 'ø' | 'ö') => Some(('z', 2)),
+            (Language::Danish, 'å') => Some(('z', 3)),
+            (Language::Spanish, 'ñ') => Some(('n', 1)),
+            _ => None,
+        }
+    }
+}
+
+/// The letters without accents for a lowercase Latin letter with accents, like `e` for `é`.
+fn base_letters(c: char) -> Option<&'static str> {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' | 'ĸ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' => "n",
+        'ŋ' => "ng",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(collation: Collation, words: &[&'static str]) -> Vec<&'static str> {
+        let mut words = words.to_vec();
+        words.sort_by(|a, b| collation.compare(a, b, false));
+        words
+    }
+
+    #[test]
+    fn accents_sort_with_their_letter() {
+        assert_eq!(
+            sorted(
+                Collation::default(),
+                &["zebra", "éclair", "eclair", "Éclair", "ecu"]
+            ),
+            ["eclair", "Éclair", "éclair", "ecu", "zebra"]
+        );
+    }
+
+    #[test]
+    fn letters_of_their_own() {
+        let words = ["öl", "zon", "ål", "ask", "äpple"];
+        assert_eq!(
+            sorted(Collation::from_locale("sv_SE.UTF-8"), &words),
+            ["ask", "zon", "ål", "äpple", "öl"]
+        );
+        assert_eq!(
+            sorted(Collation::from_locale("de-DE"), &words),
+            ["ål", "äpple", "ask", "öl", "zon"]
+        );
+        assert_eq!(
+            sorted(Collation::from_locale("es"), &["ño", "nz", "o"]),
+            ["nz", "ño", "o"]
+        );
+    }
+
+    #[test]
+    fn natural_and_case() {
+        let collation = Collation::default();
+        assert_eq!(
+            collation.compare("étape 10", "etape 9", true),
+            Ordering::Greater
+        );
+        assert_eq!(
+            collation.compare_ignore_case("Élan", "élan", false),
+            Ordering::Equal
+        );
+    }
+}
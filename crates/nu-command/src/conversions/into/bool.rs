@@ -144,7 +144,7 @@ fn into_bool(
     operate(action, args, input, call.head, engine_state.signals())
 }
 
-fn strict_string_to_boolean(s: &str, span: Span) -> Result<bool, ShellError> {
+pub(crate) fn strict_string_to_boolean(s: &str, span: Span) -> Result<bool, ShellError> {
     match s.trim().to_ascii_lowercase().as_str() {
         "true" => Ok(true),
         "false" => Ok(false),
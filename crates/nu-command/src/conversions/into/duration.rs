@@ -209,7 +209,7 @@ fn split_whitespace_indices(s: &str, span: Span) -> impl Iterator<Item = (&str,
     })
 }
 
-fn compound_to_duration(s: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn compound_to_duration(s: &str, span: Span) -> Result<i64, ShellError> {
     let mut duration_ns: i64 = 0;
 
     for (substring, substring_span) in split_whitespace_indices(s, span) {
@@ -1,8 +1,38 @@
-use nu_cmd_base::input_handler::{CellPathOnlyArgs, operate};
+use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
+use nu_protocol::{FilesizeUnit, SUPPORTED_FILESIZE_UNITS};
 
 use nu_utils::get_system_locale;
 
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+    units: Units,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+/// How to read the units of numbers and strings.
+#[derive(Clone, Copy)]
+pub(crate) struct Units {
+    /// The unit of numbers and of strings without one.
+    pub default: FilesizeUnit,
+    /// Read metric prefixes as binary ones.
+    pub binary: bool,
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Self {
+            default: FilesizeUnit::B,
+            binary: false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct IntoFilesize;
 
@@ -48,6 +78,17 @@ impl Command for IntoFilesize {
                 SyntaxShape::CellPath,
                 "For a data structure input, convert data at the given cell paths.",
             )
+            .named(
+                "unit",
+                SyntaxShape::String,
+                "the unit of numbers and of strings without a unit, like 'MiB'",
+                Some('u'),
+            )
+            .switch(
+                "binary",
+                "read metric units, like kB and MB, as binary ones, like KiB and MiB",
+                Some('b'),
+            )
             .category(Category::Conversions)
     }
 
@@ -55,8 +96,14 @@ impl Command for IntoFilesize {
         "Convert value to filesize."
     }
 
+    fn extra_description(&self) -> &str {
+        r#"Units are case-insensitive and the `B` can be left out, so `2k`, `2KB` and `2kB` are all
+2000 bytes, while `2Ki` or `2KiB` are 2048 bytes. Some tools show binary sizes with metric units,
+which can be read correctly with --binary."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["convert", "number", "bytes"]
+        vec!["convert", "number", "bytes", "mebibyte", "binary"]
     }
 
     fn run(
@@ -67,7 +114,19 @@ impl Command for IntoFilesize {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-        let args = CellPathOnlyArgs::from(cell_paths);
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let default = match call.get_flag::<Spanned<String>>(engine_state, stack, "unit")? {
+            Some(unit) => unit.item.parse().map_err(|_| ShellError::InvalidUnit {
+                supported_units: SUPPORTED_FILESIZE_UNITS.join(", "),
+                span: unit.span,
+            })?,
+            None => FilesizeUnit::B,
+        };
+        let binary = call.has_flag(engine_state, stack, "binary")?;
+        let args = Arguments {
+            cell_paths,
+            units: Units { default, binary },
+        };
         operate(action, args, input, call.head, engine_state.signals())
     }
 
@@ -112,17 +171,47 @@ impl Command for IntoFilesize {
                 example: "'-1KB' | into filesize",
                 result: Some(Value::test_filesize(-1000)),
             },
+            Example {
+                description: "Convert string with a binary unit to filesize",
+                example: "'1.5GiB' | into filesize",
+                result: Some(Value::test_filesize(1610612736)),
+            },
+            Example {
+                description: "Convert numbers of mebibytes to filesize",
+                example: "[1 '2'] | into filesize --unit MiB",
+                result: Some(Value::test_list(vec![
+                    Value::test_filesize(1048576),
+                    Value::test_filesize(2097152),
+                ])),
+            },
+            Example {
+                description: "Read a size from a tool which means binary units with metric ones",
+                example: "'4 MB' | into filesize --binary",
+                result: Some(Value::test_filesize(4194304)),
+            },
         ]
     }
 }
 
-fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
     let value_span = input.span();
+    let bytes = args.units.default.as_bytes();
     match input {
         Value::Filesize { .. } => input.clone(),
-        Value::Int { val, .. } => Value::filesize(*val, value_span),
-        Value::Float { val, .. } => Value::filesize(*val as i64, value_span),
-        Value::String { val, .. } => match i64_from_string(val, value_span) {
+        Value::Int { val, .. } => match val.checked_mul(bytes as i64) {
+            Some(val) => Value::filesize(val, value_span),
+            None => Value::error(
+                ShellError::CantConvert {
+                    to_type: "filesize".into(),
+                    from_type: "int".into(),
+                    span: value_span,
+                    help: Some("the file size is too large".into()),
+                },
+                value_span,
+            ),
+        },
+        Value::Float { val, .. } => Value::filesize((*val * bytes as f64) as i64, value_span),
+        Value::String { val, .. } => match filesize_from_string_with(val, args.units, value_span) {
             Ok(val) => Value::filesize(val, value_span),
             Err(error) => Value::error(error, value_span),
         },
@@ -139,7 +228,15 @@ fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
     }
 }
 
-fn i64_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn filesize_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
+    filesize_from_string_with(a_string, Units::default(), span)
+}
+
+pub(crate) fn filesize_from_string_with(
+    a_string: &str,
+    units: Units,
+    span: Span,
+) -> Result<i64, ShellError> {
     // Get the Locale so we know what the thousands separator is
     let locale = get_system_locale();
 
@@ -148,36 +245,64 @@ fn i64_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
     let no_comma_string = a_string.replace(locale.separator(), "");
     let clean_string = no_comma_string.trim();
 
-    // Handle negative file size
-    if let Some(stripped_negative_string) = clean_string.strip_prefix('-') {
-        match stripped_negative_string.parse::<bytesize::ByteSize>() {
-            Ok(n) => i64_from_byte_size(n, true, span),
-            Err(_) => Err(string_convert_error(span)),
-        }
-    } else if let Some(stripped_positive_string) = clean_string.strip_prefix('+') {
-        match stripped_positive_string.parse::<bytesize::ByteSize>() {
-            Ok(n) if stripped_positive_string.starts_with(|c: char| c.is_ascii_digit()) => {
-                i64_from_byte_size(n, false, span)
-            }
-            _ => Err(string_convert_error(span)),
+    let (number, unit) = clean_string.split_at(
+        clean_string
+            .find(|c: char| c.is_alphabetic())
+            .unwrap_or(clean_string.len()),
+    );
+    let number = number.trim_end();
+    // Only one sign, which is followed by a digit
+    let digits = number.strip_prefix(['-', '+']).unwrap_or(number);
+    if !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(string_convert_error(span));
+    }
+    let unit = match unit {
+        "" => units.default,
+        unit => parse_unit(unit, units.binary).ok_or_else(|| string_convert_error(span))?,
+    };
+
+    let bytes = unit.as_bytes() as i64;
+    if let Ok(n) = number.parse::<i64>() {
+        n.checked_mul(bytes)
+            .ok_or_else(|| string_convert_error(span))
+    } else if let Ok(n) = number.parse::<f64>() {
+        let n = n * bytes as f64;
+        if n.abs() < i64::MAX as f64 {
+            Ok(n as i64)
+        } else {
+            Err(string_convert_error(span))
         }
     } else {
-        match clean_string.parse::<bytesize::ByteSize>() {
-            Ok(n) => i64_from_byte_size(n, false, span),
-            Err(_) => Err(string_convert_error(span)),
-        }
+        Err(string_convert_error(span))
     }
 }
 
-fn i64_from_byte_size(
-    byte_size: bytesize::ByteSize,
-    is_negative: bool,
-    span: Span,
-) -> Result<i64, ShellError> {
-    match i64::try_from(byte_size.as_u64()) {
-        Ok(n) => Ok(if is_negative { -n } else { n }),
-        Err(_) => Err(string_convert_error(span)),
-    }
+/// The unit of a file size, like `kB`, `K` or `kib`. With `binary`, metric units are read as the
+/// binary ones with the same letter.
+fn parse_unit(unit: &str, binary: bool) -> Option<FilesizeUnit> {
+    let unit = unit.to_ascii_lowercase();
+    let unit = unit.strip_suffix('b').unwrap_or(&unit);
+    let (prefix, is_binary) = match unit.strip_suffix('i') {
+        Some("") => return None,
+        Some(prefix) => (prefix, true),
+        None => (unit, binary),
+    };
+    Some(match (prefix, is_binary) {
+        ("", _) => FilesizeUnit::B,
+        ("k", false) => FilesizeUnit::KB,
+        ("m", false) => FilesizeUnit::MB,
+        ("g", false) => FilesizeUnit::GB,
+        ("t", false) => FilesizeUnit::TB,
+        ("p", false) => FilesizeUnit::PB,
+        ("e", false) => FilesizeUnit::EB,
+        ("k", true) => FilesizeUnit::KiB,
+        ("m", true) => FilesizeUnit::MiB,
+        ("g", true) => FilesizeUnit::GiB,
+        ("t", true) => FilesizeUnit::TiB,
+        ("p", true) => FilesizeUnit::PiB,
+        ("e", true) => FilesizeUnit::EiB,
+        _ => return None,
+    })
 }
 
 fn string_convert_error(span: Span) -> ShellError {
@@ -199,4 +324,24 @@ mod test {
 
         test_examples(IntoFilesize {})
     }
+
+    #[test]
+    fn parses_metric_and_binary_units() {
+        let parse = |text: &str, binary: bool| {
+            let units = Units {
+                binary,
+                ..Units::default()
+            };
+            filesize_from_string_with(text, units, Span::test_data()).ok()
+        };
+
+        assert_eq!(parse("2k", false), Some(2000));
+        assert_eq!(parse("2 KB", false), Some(2000));
+        assert_eq!(parse("2kib", false), Some(2048));
+        assert_eq!(parse("2Ki", false), Some(2048));
+        assert_eq!(parse("2MB", true), Some(2 * 1024 * 1024));
+        assert_eq!(parse("-0.5 GiB", false), Some(-(1 << 29)));
+        assert_eq!(parse("2 bytes", false), None);
+        assert_eq!(parse("2 iB", false), None);
+    }
 }
@@ -1,5 +1,17 @@
-use nu_cmd_base::input_handler::{CellPathOnlyArgs, operate};
+use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
+use num_format::Locale;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+    locale: Option<Locale>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
 
 #[derive(Clone)]
 pub struct IntoFloat;
@@ -28,6 +40,12 @@ impl Command for IntoFloat {
                 SyntaxShape::CellPath,
                 "For a data structure input, convert data at the given cell paths.",
             )
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "parse strings with the separators of a locale, like 'de' or 'fr-CH', and allow percentages and currencies",
+                Some('l'),
+            )
             .allow_variants_without_examples(true)
             .category(Category::Conversions)
     }
@@ -48,7 +66,18 @@ impl Command for IntoFloat {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-        let args = CellPathOnlyArgs::from(cell_paths);
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let locale = call
+            .get_flag::<Spanned<String>>(engine_state, stack, "locale")?
+            .map(|name| {
+                nu_utils::locale_from_name(&name.item).ok_or_else(|| ShellError::IncorrectValue {
+                    msg: "unknown locale".into(),
+                    val_span: name.span,
+                    call_span: call.head,
+                })
+            })
+            .transpose()?;
+        let args = Arguments { cell_paths, locale };
         operate(action, args, input, call.head, engine_state.signals())
     }
 
@@ -79,18 +108,35 @@ impl Command for IntoFloat {
                 example: "true | into float",
                 result: Some(Value::test_float(1.0)),
             },
+            Example {
+                description: "Convert a number with German separators",
+                example: "'-1.234,5' | into float --locale de",
+                result: Some(Value::test_float(-1234.5)),
+            },
+            Example {
+                description: "Convert a price and a percentage",
+                example: "['$1,299.99' '12.5%'] | into float --locale en-US",
+                result: Some(Value::test_list(vec![
+                    Value::test_float(1299.99),
+                    Value::test_float(0.125),
+                ])),
+            },
         ]
     }
 }
 
-fn action(input: &Value, _args: &CellPathOnlyArgs, head: Span) -> Value {
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     let span = input.span();
     match input {
         Value::Float { .. } => input.clone(),
         Value::String { val: s, .. } => {
             let other = s.trim();
+            let parsed = match &args.locale {
+                Some(locale) => parse_localized(other, locale),
+                None => other.parse::<f64>(),
+            };
 
-            match other.parse::<f64>() {
+            match parsed {
                 Ok(x) => Value::float(x, head),
                 Err(reason) => Value::error(
                     ShellError::CantConvert {
@@ -125,6 +171,25 @@ fn action(input: &Value, _args: &CellPathOnlyArgs, head: Span) -> Value {
     }
 }
 
+/// Parse a number with the separators of `locale`, which can be a percentage or have a currency
+/// symbol or code before or after it, like `$1,299.99`, `12,5 %` or `1.234,50 EUR`.
+fn parse_localized(text: &str, locale: &Locale) -> Result<f64, std::num::ParseFloatError> {
+    let is_currency =
+        |c: char| !(c.is_ascii_digit() || c == '-' || c == '+' || c == '.' || c == ',');
+    let (text, percent) = match text.strip_suffix('%') {
+        Some(text) => (text, true),
+        None => (text, false),
+    };
+    let number = text
+        .trim_matches(|c: char| is_currency(c) && !locale.decimal().starts_with(c))
+        .replace(locale.minus_sign(), "-")
+        .replace(locale.separator(), "")
+        .replace(['\u{a0}', '\u{202f}', ' '], "")
+        .replace(locale.decimal(), ".");
+    let number = number.parse::<f64>()?;
+    Ok(if percent { number / 100.0 } else { number })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,7 +208,14 @@ mod tests {
         let word = Value::test_string("3.1415");
         let expected = Value::test_float(3.1415);
 
-        let actual = action(&word, &CellPathOnlyArgs::from(vec![]), Span::test_data());
+        let actual = action(
+            &word,
+            &Arguments {
+                cell_paths: None,
+                locale: None,
+            },
+            Span::test_data(),
+        );
         assert_eq!(actual, expected);
     }
 
@@ -153,7 +225,10 @@ mod tests {
 
         let actual = action(
             &invalid_str,
-            &CellPathOnlyArgs::from(vec![]),
+            &Arguments {
+                cell_paths: None,
+                locale: None,
+            },
             Span::test_data(),
         );
 
@@ -166,10 +241,25 @@ mod tests {
         let expected = Value::test_float(10.0);
         let actual = action(
             &input_int,
-            &CellPathOnlyArgs::from(vec![]),
+            &Arguments {
+                cell_paths: None,
+                locale: None,
+            },
             Span::test_data(),
         );
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn localized_string_to_float() {
+        let parse = |text: &str, locale: &str| {
+            parse_localized(text, &nu_utils::locale_from_name(locale).unwrap()).ok()
+        };
+
+        assert_eq!(parse("1\u{202f}234,5 €", "fr"), Some(1234.5));
+        assert_eq!(parse("12,5 %", "de"), Some(0.125));
+        assert_eq!(parse("-1,23,456.7 INR", "en-IN"), Some(-123456.7));
+        assert_eq!(parse("1,2,3", "de"), None);
+    }
 }
@@ -0,0 +1,166 @@
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+use nu_protocol::IpValue;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct IntoIp;
+
+impl Command for IntoIp {
+    fn name(&self) -> &str {
+        "into ip"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("into ip")
+            .input_output_types(vec![
+                (Type::String, Type::custom("ip")),
+                (Type::Int, Type::custom("ip")),
+                (Type::Binary, Type::custom("ip")),
+                (Type::custom("ip"), Type::custom("ip")),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::custom("ip"))),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, convert data at the given cell paths.",
+            )
+            .category(Category::Conversions)
+    }
+
+    fn description(&self) -> &str {
+        "Convert value to an IP address or network."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Strings are addresses like `10.0.0.1` or `::1`, or networks in CIDR notation like
+`10.0.0.0/8`. Integers are IPv4 addresses, and binary is the 4 bytes of an IPv4 address or the
+16 bytes of an IPv6 address.
+
+IP values compare numerically, so they sort like addresses instead of like strings. Addresses
+and networks are in the networks which contain them, which is tested with `in`, and integers can
+be added to addresses to get the next ones. The `ip` subcommands work with networks."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "convert", "address", "cidr", "subnet", "network", "ipv4", "ipv6",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cell_paths = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Convert a string to an IP address",
+                example: "'192.168.1.10' | into ip",
+                result: Some(
+                    IpValue::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)), None)
+                        .into_value(Span::test_data()),
+                ),
+            },
+            Example {
+                description: "Check whether an address is in a network",
+                example: "('10.1.2.3' | into ip) in ('10.0.0.0/8' | into ip)",
+                result: None,
+            },
+            Example {
+                description: "Sort addresses numerically",
+                example: "['10.0.0.10' '10.0.0.9'] | into ip | sort",
+                result: None,
+            },
+            Example {
+                description: "Get the next address",
+                example: "('10.0.0.255' | into ip) + 1",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, span: Span) -> Value {
+    let ip = match input {
+        Value::String { val, .. } => IpValue::parse(val).map_err(|msg| ShellError::CantConvert {
+            to_type: "ip".into(),
+            from_type: "string".into(),
+            span,
+            help: Some(msg),
+        }),
+        Value::Int { val, .. } => u32::try_from(*val)
+            .map(|val| IpValue::new(IpAddr::V4(Ipv4Addr::from(val)), None))
+            .map_err(|_| ShellError::CantConvert {
+                to_type: "ip".into(),
+                from_type: "int".into(),
+                span,
+                help: Some("IPv4 addresses are between 0 and 4294967295".into()),
+            }),
+        Value::Binary { val, .. } => match val.len() {
+            4 => Ok(IpAddr::V4(Ipv4Addr::from(
+                <[u8; 4]>::try_from(&val[..]).expect("4 bytes"),
+            ))),
+            16 => Ok(IpAddr::V6(Ipv6Addr::from(
+                <[u8; 16]>::try_from(&val[..]).expect("16 bytes"),
+            ))),
+            len => Err(ShellError::CantConvert {
+                to_type: "ip".into(),
+                from_type: "binary".into(),
+                span,
+                help: Some(format!("expected 4 or 16 bytes, not {len}")),
+            }),
+        }
+        .map(|addr| IpValue::new(addr, None)),
+        x if IpValue::try_from_value(x).is_some() => return x.clone(),
+        x => Err(ShellError::CantConvert {
+            to_type: "ip".into(),
+            from_type: x.get_type().to_string(),
+            span,
+            help: None,
+        }),
+    };
+    match ip {
+        Ok(ip) => ip.into_value(span),
+        Err(err) => Value::error(err, span),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(IntoIp {})
+    }
+}
@@ -8,6 +8,8 @@ mod filesize;
 mod float;
 mod glob;
 mod int;
+mod ip;
+mod path;
 mod record;
 mod string;
 mod value;
@@ -22,6 +24,13 @@ pub use filesize::IntoFilesize;
 pub use float::IntoFloat;
 pub use glob::IntoGlob;
 pub use int::IntoInt;
+pub use ip::IntoIp;
+pub use path::IntoPath;
 pub use record::IntoRecord;
 pub use string::IntoString;
 pub use value::IntoValue;
+
+pub(crate) use bool::strict_string_to_boolean;
+pub(crate) use duration::compound_to_duration;
+pub(crate) use filesize::filesize_from_string;
+pub(crate) use int::int_from_string;
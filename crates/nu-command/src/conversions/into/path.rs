@@ -0,0 +1,140 @@
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+use nu_protocol::PathValue;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct IntoPath;
+
+impl Command for IntoPath {
+    fn name(&self) -> &str {
+        "into path"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("into path")
+            .input_output_types(vec![
+                (Type::String, Type::custom("path")),
+                (Type::custom("path"), Type::custom("path")),
+                (Type::Binary, Type::custom("path")),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::custom("path"))),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, convert data at the given cell paths.",
+            )
+            .category(Category::Conversions)
+    }
+
+    fn description(&self) -> &str {
+        "Convert value to a path, which keeps the parts of it that aren't valid UTF-8."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Paths work with the `path` subcommands, and are passed to external commands as a single
+argument with their exact bytes. They can be joined with `/`.
+
+On Unix, binary is converted to a path with the same bytes, so file names which aren't valid
+UTF-8 can be created exactly."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["convert", "file", "filename"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cell_paths = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Convert a string to a path",
+                example: "'/home/viking' | into path",
+                result: Some(PathValue::new("/home/viking").into_value(Span::test_data())),
+            },
+            Example {
+                description: "Join paths with `/`",
+                example: "('/home' | into path) / viking",
+                result: None,
+            },
+            Example {
+                description: "Create a file whose name isn't valid UTF-8",
+                example: "0x[66 e9] | into path | ^touch $in",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, span: Span) -> Value {
+    match input {
+        Value::String { val, .. } => PathValue::new(val).into_value(span),
+        #[cfg(unix)]
+        Value::Binary { val, .. } => {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            PathValue::new(OsStr::from_bytes(val)).into_value(span)
+        }
+        #[cfg(not(unix))]
+        Value::Binary { val, .. } => match std::str::from_utf8(val) {
+            Ok(val) => PathValue::new(val).into_value(span),
+            Err(err) => Value::error(
+                ShellError::CantConvert {
+                    to_type: "path".into(),
+                    from_type: "binary".into(),
+                    span,
+                    help: Some(format!("the path has to be valid UTF-8 here: {err}")),
+                },
+                span,
+            ),
+        },
+        x if PathValue::try_from_value(x).is_some() => x.clone(),
+        x => Value::error(
+            ShellError::CantConvert {
+                to_type: "path".into(),
+                from_type: x.get_type().to_string(),
+                span,
+                help: None,
+            },
+            span,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(IntoPath {})
+    }
+}
@@ -39,6 +39,10 @@ impl Command for IntoSqliteDb {
             )
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -121,7 +125,7 @@ impl Table {
         };
 
         // create the sqlite database table
-        let conn = open_sqlite_db(&db_path, span)?;
+        let conn = open_sqlite_db(&db_path, false, span)?;
 
         Ok(Self { conn, table_name })
     }
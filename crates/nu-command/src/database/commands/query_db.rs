@@ -27,6 +27,10 @@ impl Command for QueryDb {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn description(&self) -> &str {
         "Query a SQLite database with SQL statements."
     }
@@ -17,6 +17,10 @@ impl Command for SchemaDb {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Show the schema of a SQLite database."
     }
@@ -30,6 +30,10 @@ pub struct SQLiteDatabase {
     // this understandably can't be serialized. think that's OK, I'm not aware of a
     // reason why a CustomValue would be serialized outside of a plugin
     signals: Signals,
+    /// Whether the database file is opened read-only, for databases opened in a sandbox which
+    /// can't write files
+    #[serde(skip)]
+    read_only: bool,
 }
 
 impl SQLiteDatabase {
@@ -37,9 +41,15 @@ impl SQLiteDatabase {
         Self {
             path: PathBuf::from(path),
             signals,
+            read_only: false,
         }
     }
 
+    /// Only open the database file for reading.
+    pub fn with_read_only(self, read_only: bool) -> Self {
+        Self { read_only, ..self }
+    }
+
     pub fn try_from_path(path: &Path, span: Span, signals: Signals) -> Result<Self, ShellError> {
         let mut file = File::open(path).map_err(|e| IoError::new(e, span, PathBuf::from(path)))?;
 
@@ -68,6 +78,7 @@ impl SQLiteDatabase {
                 Some(db) => Ok(Self {
                     path: db.path.clone(),
                     signals: db.signals.clone(),
+                    read_only: db.read_only,
                 }),
                 None => Err(ShellError::CantConvert {
                     to_type: "database".into(),
@@ -101,7 +112,7 @@ impl SQLiteDatabase {
         params: NuSqlParams,
         call_span: Span,
     ) -> Result<Value, ShellError> {
-        let conn = open_sqlite_db(&self.path, call_span)?;
+        let conn = open_sqlite_db(&self.path, self.read_only, call_span)?;
         let stream = run_sql_query(conn, sql, params, &self.signals)
             .map_err(|e| e.into_shell_error(sql.span, "Failed to query SQLite database"))?;
 
@@ -112,13 +123,14 @@ impl SQLiteDatabase {
         if self.path == PathBuf::from(MEMORY_DB) {
             open_connection_in_memory_custom()
         } else {
-            let conn = Connection::open(&self.path).map_err(|e| ShellError::GenericError {
-                error: "Failed to open SQLite database from open_connection".into(),
-                msg: e.to_string(),
-                span: None,
-                help: None,
-                inner: vec![],
-            })?;
+            let conn =
+                open_file(&self.path, self.read_only).map_err(|e| ShellError::GenericError {
+                    error: "Failed to open SQLite database from open_connection".into(),
+                    msg: e.to_string(),
+                    span: None,
+                    help: None,
+                    inner: vec![],
+                })?;
             conn.busy_handler(Some(SQLiteDatabase::sleeper))
                 .map_err(|e| ShellError::GenericError {
                     error: "Failed to set busy handler for SQLite database".into(),
@@ -362,7 +374,7 @@ impl CustomValue for SQLiteDatabase {
     }
 
     fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
-        let db = open_sqlite_db(&self.path, span)?;
+        let db = open_sqlite_db(&self.path, self.read_only, span)?;
         read_entire_sqlite_db(db, span, &self.signals)
             .map_err(|e| e.into_shell_error(span, "Failed to read from SQLite database"))
     }
@@ -394,7 +406,7 @@ impl CustomValue for SQLiteDatabase {
         _optional: bool,
         _casing: Casing,
     ) -> Result<Value, ShellError> {
-        let db = open_sqlite_db(&self.path, path_span)?;
+        let db = open_sqlite_db(&self.path, self.read_only, path_span)?;
         read_single_table(db, column_name, path_span, &self.signals)
             .map_err(|e| e.into_shell_error(path_span, "Failed to read from SQLite database"))
     }
@@ -408,12 +420,15 @@ impl CustomValue for SQLiteDatabase {
     }
 }
 
-pub fn open_sqlite_db(path: &Path, call_span: Span) -> Result<Connection, ShellError> {
+pub fn open_sqlite_db(
+    path: &Path,
+    read_only: bool,
+    call_span: Span,
+) -> Result<Connection, ShellError> {
     if path.to_string_lossy() == MEMORY_DB {
         open_connection_in_memory_custom()
     } else {
-        let path = path.to_string_lossy().to_string();
-        Connection::open(path).map_err(|err| ShellError::GenericError {
+        open_file(path, read_only).map_err(|err| ShellError::GenericError {
             error: "Failed to open SQLite database".into(),
             msg: err.to_string(),
             span: Some(call_span),
@@ -423,6 +438,20 @@ pub fn open_sqlite_db(path: &Path, call_span: Span) -> Result<Connection, ShellE
     }
 }
 
+/// Open the database file at `path`, which can't be written to if `read_only` is set.
+fn open_file(path: &Path, read_only: bool) -> Result<Connection, SqliteError> {
+    if read_only {
+        Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+    } else {
+        Connection::open(path)
+    }
+}
+
 fn run_sql_query(
     conn: Connection,
     sql: &Spanned<String>,
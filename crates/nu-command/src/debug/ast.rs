@@ -34,6 +34,10 @@ impl Command for Ast {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
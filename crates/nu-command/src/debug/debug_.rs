@@ -30,6 +30,10 @@ impl Command for Debug {
             )
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -14,6 +14,10 @@ impl Command for DebugEnv {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Show environment variables as external commands would get it."
     }
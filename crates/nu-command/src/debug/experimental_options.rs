@@ -26,6 +26,10 @@ impl Command for DebugExperimentalOptions {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Show all experimental options."
     }
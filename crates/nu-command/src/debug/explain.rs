@@ -28,6 +28,10 @@ impl Command for Explain {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -34,6 +34,10 @@ impl Command for DebugInfo {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -29,6 +29,10 @@ impl Command for Metadata {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn requires_ast_for_arguments(&self) -> bool {
         true
     }
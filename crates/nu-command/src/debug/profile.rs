@@ -46,6 +46,10 @@ impl Command for DebugProfile {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Profile pipeline elements in a closure."
     }
@@ -31,6 +31,10 @@ This command will bubble up any errors encountered when running the closure. The
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["timing", "timer", "benchmark", "measure"]
     }
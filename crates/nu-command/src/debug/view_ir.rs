@@ -30,6 +30,10 @@ impl Command for ViewIr {
             .category(Category::Debug)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View the compiled IR code for a block of code."
     }
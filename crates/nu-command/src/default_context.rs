@@ -97,9 +97,13 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
 
         // Misc
         bind_command! {
+            Import,
+            ImportDynamic,
             Panic,
             Source,
+            Translate,
             Tutor,
+            WatchVar,
         };
 
         // Path
@@ -191,6 +195,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
         bind_command! {
             Ansi,
             AnsiLink,
+            AnsiParse,
             AnsiStrip,
             Char,
             Decode,
@@ -207,6 +212,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             DetectColumns,
             DetectType,
             Parse,
+            Render,
             Split,
             SplitChars,
             SplitColumn,
@@ -229,12 +235,22 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             StrSubstring,
             StrTrim,
             StrUpcase,
+            StrWidth,
             Format,
             FormatDate,
             FormatDuration,
             FormatFilesize,
         };
 
+        #[cfg(feature = "uuid")]
+        bind_command! {
+            Uuid,
+            UuidParse,
+            UuidValidate,
+            Ulid,
+            UlidParse,
+        };
+
         // FileSystem
         #[cfg(feature = "os")]
         bind_command! {
@@ -250,9 +266,26 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Save,
             UTouch,
             Glob,
+            Search,
+            SearchFiles,
+            SearchText,
             Watch,
         };
 
+        #[cfg(all(unix, feature = "os"))]
+        bind_command! {
+            Fs,
+            FsChmod,
+            FsChown,
+        };
+
+        // Version control
+        #[cfg(feature = "vcs")]
+        bind_command! {
+            Vcs,
+            VcsStatus,
+        };
+
         // Platform
         #[cfg(feature = "os")]
         bind_command! {
@@ -290,13 +323,18 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
 
         // Formats
         bind_command! {
+            FormatRegister,
             From,
+            FromAuto,
             FromCsv,
             FromJson,
+            FromJwt,
+            FromMd,
             FromMsgpack,
             FromMsgpackz,
             FromNuon,
             FromOds,
+            FromPem,
             FromSsv,
             FromToml,
             FromTsv,
@@ -321,6 +359,13 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             ToYml,
         };
 
+        #[cfg(feature = "network")]
+        bind_command! {
+            Jwt,
+            JwtSign,
+            JwtVerify,
+        };
+
         // Viewers
         bind_command! {
             Griddle,
@@ -339,6 +384,8 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             IntoFloat,
             IntoFilesize,
             IntoInt,
+            IntoIp,
+            IntoPath,
             IntoRecord,
             IntoString,
             IntoGlob,
@@ -348,6 +395,12 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
 
         // Env
         bind_command! {
+            EnvMeta,
+            EnvExport,
+            EnvImport,
+            EnvTrust,
+            EnvUntrust,
+            EnvWatch,
             ExportEnv,
             LoadEnv,
             SourceEnv,
@@ -358,6 +411,16 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             ConfigMeta,
             ConfigReset,
             ConfigUseColors,
+            ConfigGet,
+            ConfigSet,
+            ConfigDiff,
+            ConfigValidate,
+            ConfigProfiles,
+            ConfigMigrate,
+        };
+        #[cfg(feature = "os")]
+        bind_command! {
+            EnvCapture,
         };
 
         // Math
@@ -366,6 +429,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             MathAbs,
             MathAvg,
             MathCeil,
+            MathEval,
             MathFloor,
             MathMax,
             MathMedian,
@@ -394,12 +458,19 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             BytesIndexOf,
             BytesCollect,
             BytesRemove,
-            BytesBuild
+            BytesBuild,
+            BytesDecodeStruct,
+            BytesDiff,
+            BytesEncodeStruct,
         }
 
         // Network
         #[cfg(feature = "network")]
         bind_command! {
+            Cert,
+            CertInspect,
+            Dns,
+            DnsResolve,
             Http,
             HttpDelete,
             HttpGet,
@@ -408,10 +479,16 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             HttpPost,
             HttpPut,
             HttpOptions,
+            NetworkPing,
             Port,
+            PortScan,
             VersionCheck,
         }
         bind_command! {
+            Network,
+            NetworkHosts,
+            NetworkInfo,
+            NetworkSubnets,
             Url,
             UrlBuildQuery,
             UrlSplitQuery,
@@ -430,6 +507,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             RandomDice,
             RandomFloat,
             RandomInt,
+            RandomUlid,
             RandomUuid,
             RandomBinary
         };
@@ -439,6 +517,7 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             Cal,
             Seq,
             SeqDate,
+            SeqCron,
             SeqChar,
             Generate,
         };
@@ -459,6 +538,10 @@ pub fn add_shell_command_context(mut engine_state: EngineState) -> EngineState {
             JobId,
             JobTag,
             Job,
+            Schedule,
+            ScheduleAdd,
+            ScheduleList,
+            ScheduleRemove,
         };
 
         #[cfg(not(target_family = "wasm"))]
@@ -21,6 +21,10 @@ impl Command for ConfigMeta {
             .input_output_types(vec![(Type::Nothing, Type::String)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Edit nushell configuration files."
     }
@@ -67,6 +71,9 @@ pub(super) fn start_editor(
     // Find the editor executable.
 
     let (editor_name, editor_args) = get_editor(engine_state, stack, call.head)?;
+    if let Some(sandbox) = &engine_state.sandbox {
+        sandbox.check_external(&editor_name, call.head)?;
+    }
     let paths = nu_engine::env::path_str(engine_state, stack, call.head)?;
     let cwd = engine_state.cwd(Some(stack))?;
     let editor_executable =
@@ -106,7 +113,7 @@ pub(super) fn start_editor(
     // Spawn the child process. On Unix, also put the child process to
     // foreground if we're in an interactive session.
     #[cfg(windows)]
-    let child = ForegroundChild::spawn(command);
+    let child = ForegroundChild::spawn(command, 0);
     #[cfg(unix)]
     let child = ForegroundChild::spawn(
         command,
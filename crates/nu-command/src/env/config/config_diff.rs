@@ -0,0 +1,104 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::Config;
+
+#[derive(Clone)]
+pub struct ConfigDiff;
+
+impl Command for ConfigDiff {
+    fn name(&self) -> &str {
+        "config diff"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Show the configuration options that differ from their defaults."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Nested records are compared option by option, lists are compared as a whole."
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Show every option changed from the default configuration",
+            example: "config diff",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let current = stack.get_config(engine_state).as_ref().clone();
+
+        let mut defaults = Vec::new();
+        flatten("", Config::default().into_value(head), &mut defaults);
+        let mut current_options = Vec::new();
+        flatten("", current.into_value(head), &mut current_options);
+
+        let mut rows = Vec::new();
+        for (path, value) in current_options {
+            let default = defaults
+                .iter()
+                .position(|(default_path, _)| *default_path == path)
+                .map(|idx| defaults.swap_remove(idx).1);
+
+            match default {
+                Some(default) if default == value => (),
+                default => rows.push(Value::record(
+                    record! {
+                        "path" => Value::string(path, head),
+                        "default" => default.unwrap_or(Value::nothing(head)),
+                        "current" => value,
+                    },
+                    head,
+                )),
+            }
+        }
+
+        // Options that only exist in the defaults, e.g. removed entries of a map-like option.
+        for (path, default) in defaults {
+            rows.push(Value::record(
+                record! {
+                    "path" => Value::string(path, head),
+                    "default" => default,
+                    "current" => Value::nothing(head),
+                },
+                head,
+            ));
+        }
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+/// Collect every leaf option of a config value together with its dotted path.
+fn flatten(prefix: &str, value: Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Record { val, .. } if !val.is_empty() => {
+            for (key, value) in val.into_owned() {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, out);
+            }
+        }
+        value => out.push((prefix.to_owned(), value)),
+    }
+}
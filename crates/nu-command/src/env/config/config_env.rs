@@ -26,6 +26,10 @@ impl Command for ConfigEnv {
         // TODO: Signature narrower than what run actually supports theoretically
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Edit nu environment configurations."
     }
@@ -15,6 +15,10 @@ impl Command for ConfigFlatten {
             .input_output_types(vec![(Type::Nothing, Type::record())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Show the current configuration in a flattened form."
     }
@@ -0,0 +1,67 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ConfigGet;
+
+impl Command for ConfigGet {
+    fn name(&self) -> &str {
+        "config get"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "path",
+                SyntaxShape::CellPath,
+                "The cell path of the config option to get.",
+            )
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Get the value of a configuration option."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Without a path this returns the whole configuration record, like `$env.config`."
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get the current table mode",
+                example: "config get table.mode",
+                result: None,
+            },
+            Example {
+                description: "Get the whole history configuration",
+                example: "config get history",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: Option<CellPath> = call.opt(engine_state, stack, 0)?;
+        let config = stack.get_config(engine_state).as_ref().clone();
+        let value = config.into_value(call.head);
+
+        let value = match path {
+            Some(path) => value.follow_cell_path(&path.members)?.into_owned(),
+            None => value,
+        };
+
+        Ok(value.into_pipeline_data())
+    }
+}
@@ -0,0 +1,148 @@
+use nu_engine::command_prelude::*;
+use std::path::Path;
+
+/// Files which older versions kept in the config directory, but which belong in the data
+/// directory.
+const STATE_FILES: &[&str] = &["history.txt", "history.sqlite3", "plugin.msgpackz"];
+
+/// The files SQLite keeps next to a database while it's open.
+///
+/// SQLite removes them when the last connection to the database is closed, so a database which
+/// still has them is in use, or wasn't closed cleanly, and can't be moved on its own.
+fn sqlite_side_files(name: &str) -> Vec<String> {
+    if name.ends_with(".sqlite3") {
+        vec![format!("{name}-wal"), format!("{name}-shm")]
+    } else {
+        vec![]
+    }
+}
+
+#[derive(Clone)]
+pub struct ConfigMigrate;
+
+impl Command for ConfigMigrate {
+    fn name(&self) -> &str {
+        "config migrate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .switch("dry-run", "only show which files would be moved", Some('d'))
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .category(Category::Env)
+    }
+
+    fn description(&self) -> &str {
+        "Move history and plugin registry files from the config directory to the data directory."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Nushell keeps its configuration in `$nu.default-config-dir` and state like the history and the plugin registry in `$nu.data-dir`. Files left in the config directory by older versions keep being used until they're migrated.
+A file which already exists in the data directory is never overwritten. A SQLite history isn't moved while its `-wal` or `-shm` files exist, since it's still open then. The history of the current session isn't moved either; run `nu --no-config-file --commands 'config migrate'` to move it. The plugin registry of the current session follows the move."#
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Show which files would be migrated",
+                example: "config migrate --dry-run",
+                result: None,
+            },
+            Example {
+                description: "Migrate the history and the plugin registry",
+                example: "config migrate",
+                result: None,
+            },
+        ]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let dry_run = call.has_flag(engine_state, stack, "dry-run")?;
+
+        let Some(config_dir) = nu_path::nu_config_dir() else {
+            return Err(ShellError::ConfigDirNotFound { span: head });
+        };
+        let Some(data_dir) = nu_path::nu_data_dir() else {
+            return Err(ShellError::GenericError {
+                error: "Could not find the data directory".into(),
+                msg: "data directory not found".into(),
+                span: Some(head),
+                help: Some("set $env.XDG_DATA_HOME to choose one".into()),
+                inner: vec![],
+            });
+        };
+
+        // On some platforms the config and data directories are the same.
+        if config_dir == data_dir {
+            return Ok(Value::list(vec![], head).into_pipeline_data());
+        }
+
+        // The interactive session keeps its history file open, and would write to it again
+        let session_history = engine_state
+            .is_interactive
+            .then(|| engine_state.history_config().and_then(|h| h.file_path()))
+            .flatten();
+
+        let mut rows = Vec::new();
+        for name in STATE_FILES {
+            let from = config_dir.join(name);
+            if from.symlink_metadata().is_err() {
+                continue;
+            }
+            let to = data_dir.join(name);
+            let side_files = sqlite_side_files(name);
+
+            let status = if to.symlink_metadata().is_ok() {
+                "skipped, destination exists"
+            } else if session_history.as_deref() == Some(from.as_std_path())
+                || side_files
+                    .iter()
+                    .any(|file| config_dir.join(file).symlink_metadata().is_ok())
+            {
+                "skipped, in use"
+            } else if dry_run {
+                "would move"
+            } else {
+                move_file(from.as_std_path(), to.as_std_path(), head)?;
+                "moved"
+            };
+
+            rows.push(Value::record(
+                record! {
+                    "file" => Value::string(*name, head),
+                    "from" => Value::string(from.to_string_lossy(), head),
+                    "to" => Value::string(to.to_string_lossy(), head),
+                    "status" => Value::string(status, head),
+                },
+                head,
+            ));
+        }
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+fn move_file(from: &Path, to: &Path, span: Span) -> Result<(), ShellError> {
+    if let Some(dir) = to.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| IoError::new(err, span, dir.to_owned()))?;
+    }
+
+    // Renaming fails across file systems, fall back to copying the file over.
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to).map_err(|err| IoError::new(err, span, from.to_owned()))?;
+        std::fs::remove_file(from).map_err(|err| IoError::new(err, span, from.to_owned()))?;
+    }
+
+    Ok(())
+}
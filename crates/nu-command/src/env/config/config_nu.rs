@@ -25,6 +25,10 @@ impl Command for ConfigNu {
             )
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Edit nu configurations."
     }
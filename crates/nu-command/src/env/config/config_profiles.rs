@@ -0,0 +1,121 @@
+use nu_engine::command_prelude::*;
+use nu_utils::{CONFIG_HOSTS_DIR, CONFIG_PROFILES_DIR};
+use sysinfo::System;
+
+#[derive(Clone)]
+pub struct ConfigProfiles;
+
+impl Command for ConfigProfiles {
+    fn name(&self) -> &str {
+        "config profiles"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "List the available config profiles."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"A profile is a directory under `$nu.default-config-dir/profiles` with its own `env.nu` and `config.nu`, selected with `nu --profile <name>` or `$env.NU_PROFILE`.
+Config files are layered with increasing precedence: the base files, the active profile, then `$nu.default-config-dir/hosts/<hostname>` for the current host."#
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "List the config profiles",
+                example: "config profiles",
+                result: None,
+            },
+            Example {
+                description: "Show the name of the active profile",
+                example: "$nu.profile",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let Some(config_dir) = nu_path::nu_config_dir() else {
+            return Err(ShellError::ConfigDirNotFound { span: head });
+        };
+
+        let active = engine_state.config_profile.as_deref();
+        let mut profiles = list_layers(config_dir.join(CONFIG_PROFILES_DIR).as_std_path(), head)?;
+        profiles.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut rows: Vec<Value> = profiles
+            .into_iter()
+            .map(|(name, path)| {
+                let is_active = active == Some(name.as_str());
+                layer_row(name, "profile", path, is_active, head)
+            })
+            .collect();
+
+        // The host layer always applies, so list it too if it exists.
+        if let Some(host) = System::host_name() {
+            let path = config_dir.join(CONFIG_HOSTS_DIR).join(&host);
+            if path.is_dir() {
+                rows.push(layer_row(
+                    host,
+                    "host",
+                    path.to_string_lossy().into_owned(),
+                    true,
+                    head,
+                ));
+            }
+        }
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+fn list_layers(dir: &std::path::Path, span: Span) -> Result<Vec<(String, String)>, ShellError> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let entries = std::fs::read_dir(dir).map_err(|err| IoError::new(err, span, dir.to_owned()))?;
+
+    let mut layers = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| IoError::new(err, span, dir.to_owned()))?;
+        let path = entry.path();
+        if path.is_dir() {
+            layers.push((
+                entry.file_name().to_string_lossy().into_owned(),
+                path.to_string_lossy().into_owned(),
+            ));
+        }
+    }
+
+    Ok(layers)
+}
+
+fn layer_row(name: String, kind: &str, path: String, active: bool, span: Span) -> Value {
+    Value::record(
+        record! {
+            "name" => Value::string(name, span),
+            "kind" => Value::string(kind, span),
+            "path" => Value::string(path, span),
+            "active" => Value::bool(active, span),
+        },
+        span,
+    )
+}
@@ -33,6 +33,10 @@ impl Command for ConfigReset {
         }]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
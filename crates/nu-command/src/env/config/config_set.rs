@@ -0,0 +1,83 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::Config;
+
+#[derive(Clone)]
+pub struct ConfigSet;
+
+impl Command for ConfigSet {
+    fn name(&self) -> &str {
+        "config set"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "path",
+                SyntaxShape::CellPath,
+                "The cell path of the config option to set.",
+            )
+            .required("value", SyntaxShape::Any, "The new value of the option.")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Set a configuration option, validating it against the config schema."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Unlike assigning to `$env.config` directly, an invalid value is rejected and leaves the current configuration untouched."
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Use the rounded table mode",
+                example: "config set table.mode rounded",
+                result: None,
+            },
+            Example {
+                description: "Increase the history size",
+                example: "config set history.max_size 1_000_000",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: CellPath = call.req(engine_state, stack, 0)?;
+        let new_value: Value = call.req(engine_state, stack, 1)?;
+
+        if path.members.is_empty() {
+            return Err(ShellError::IncorrectValue {
+                msg: "a config option path is required".into(),
+                val_span: call.arguments_span(),
+                call_span: call.head,
+            });
+        }
+
+        let old = stack.get_config(engine_state);
+        let mut value = old.as_ref().clone().into_value(call.head);
+        value.upsert_data_at_cell_path(&path.members, new_value)?;
+
+        // Validate against a scratch copy first, so that a rejected value doesn't leave the
+        // config half-updated the way a direct `$env.config` assignment would.
+        let mut config = Config::clone(&old);
+        config.update_from_value(&old, &value)?;
+
+        stack.add_env_var("config".into(), value);
+        stack.update_config(engine_state)?;
+
+        Ok(PipelineData::empty())
+    }
+}
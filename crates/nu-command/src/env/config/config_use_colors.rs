@@ -14,6 +14,10 @@ impl Command for ConfigUseColors {
             .input_output_type(Type::Nothing, Type::Bool)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Get the configuration for color output."
     }
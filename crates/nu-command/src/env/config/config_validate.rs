@@ -0,0 +1,148 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{Config, ConfigError, ConfigWarning};
+
+#[derive(Clone)]
+pub struct ConfigValidate;
+
+impl Command for ConfigValidate {
+    fn name(&self) -> &str {
+        "config validate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::record(), Type::table())])
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Check a config record against the config schema without applying it."
+    }
+
+    fn extra_description(&self) -> &str {
+        "Every problem is reported as a row with the option path, its severity and a message, instead of a single \"invalid config\" error. An empty table means the record would be accepted as `$env.config`."
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Check a config record before assigning it",
+                example: "{ table: { mode: rounded }, history: { max_size: -1 } } | config validate",
+                result: None,
+            },
+            Example {
+                description: "Lint config options stored in a file",
+                example: "open my-config.nuon | config validate",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let value = input.into_value(head)?;
+        if !matches!(value, Value::Record { .. }) {
+            return Err(ShellError::OnlySupportsThisInputType {
+                exp_input_type: "record".into(),
+                wrong_type: value.get_type().to_string(),
+                dst_span: head,
+                src_span: value.span(),
+            });
+        }
+
+        let old = stack.get_config(engine_state);
+        let mut config = Config::clone(&old);
+
+        let rows = match config.update_from_value(&old, &value) {
+            Ok(None) => vec![],
+            Ok(Some(ShellWarning::InvalidConfig { warnings })) => warnings
+                .into_iter()
+                .map(|warning| warning_row(warning, head))
+                .collect(),
+            Ok(Some(warning)) => vec![Value::record(
+                record! {
+                    "path" => Value::string("", head),
+                    "severity" => Value::string("warning", head),
+                    "message" => Value::string(warning.to_string(), head),
+                },
+                head,
+            )],
+            Err(ShellError::InvalidConfig { errors }) => errors
+                .into_iter()
+                .map(|error| error_row(error, head))
+                .collect(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+}
+
+fn error_row(error: ConfigError, head: Span) -> Value {
+    let (path, message) = match &error {
+        ConfigError::TypeMismatch {
+            path,
+            expected,
+            actual,
+            ..
+        } => (
+            path.clone(),
+            format!("expected {expected}, but got {actual}"),
+        ),
+        ConfigError::InvalidValue {
+            path,
+            valid,
+            actual,
+            ..
+        } => (path.clone(), format!("expected {valid}, but got {actual}")),
+        ConfigError::UnknownOption { path, .. } => (path.clone(), "unknown option".into()),
+        ConfigError::MissingRequiredColumn { path, column, .. } => {
+            (path.clone(), format!("requires a '{column}' column"))
+        }
+        ConfigError::Deprecated {
+            path, suggestion, ..
+        } => (
+            path.clone(),
+            format!("deprecated, please {suggestion} instead"),
+        ),
+        ConfigError::ShellError(err) => (String::new(), err.to_string()),
+    };
+    // Like in `config get` and `config diff`, the paths are relative to `$env.config`
+    let path = path
+        .strip_prefix("$env.config.")
+        .map_or(path.clone(), Into::into);
+
+    Value::record(
+        record! {
+            "path" => Value::string(path, head),
+            "severity" => Value::string("error", head),
+            "message" => Value::string(message, head),
+        },
+        head,
+    )
+}
+
+fn warning_row(warning: ConfigWarning, head: Span) -> Value {
+    let message = match &warning {
+        ConfigWarning::IncompatibleOptions { label, help, .. } => format!("{label}: {help}"),
+    };
+
+    Value::record(
+        record! {
+            "path" => Value::string("", head),
+            "severity" => Value::string("warning", head),
+            "message" => Value::string(message, head),
+        },
+        head,
+    )
+}
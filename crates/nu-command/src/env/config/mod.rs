@@ -1,13 +1,25 @@
 mod config_;
+mod config_diff;
 mod config_env;
 mod config_flatten;
+mod config_get;
+mod config_migrate;
 mod config_nu;
+mod config_profiles;
 mod config_reset;
+mod config_set;
 mod config_use_colors;
+mod config_validate;
 
 pub use config_::ConfigMeta;
+pub use config_diff::ConfigDiff;
 pub use config_env::ConfigEnv;
 pub use config_flatten::ConfigFlatten;
+pub use config_get::ConfigGet;
+pub use config_migrate::ConfigMigrate;
 pub use config_nu::ConfigNu;
+pub use config_profiles::ConfigProfiles;
 pub use config_reset::ConfigReset;
+pub use config_set::ConfigSet;
 pub use config_use_colors::ConfigUseColors;
+pub use config_validate::ConfigValidate;
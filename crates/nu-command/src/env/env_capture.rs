@@ -0,0 +1,167 @@
+use super::shell_syntax::{Dialect, dialect_flag, dialect_switches, parse_env};
+use nu_engine::{command_prelude::*, env_to_string, env_to_strings};
+
+use std::{borrow::Cow, process::Stdio};
+
+#[derive(Clone)]
+pub struct EnvCapture;
+
+impl Command for EnvCapture {
+    fn name(&self) -> &str {
+        "env capture"
+    }
+
+    fn description(&self) -> &str {
+        "Run a program which prints shell statements, and apply the environment changes they make."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"This is what `eval $(ssh-agent)` does in other shells: programs like ssh-agent, docker-machine env or conda shell.posix activate print the statements which set their variables, and they are read like with `env import` and applied like with `load-env`. Unset variables are hidden.
+The syntax the program prints is detected unless it's chosen with a switch. The program runs with the current environment, and its stderr and stdin are those of the shell, so it can still ask for input. Use `^program | env import` to only see the changes."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "eval",
+            "ssh-agent",
+            "docker-machine",
+            "conda",
+            "activate",
+            "source",
+        ]
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        let signature = Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+            .required(
+                "command",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::Any]),
+                "The external program to run.",
+            )
+            .rest(
+                "args",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::Any]),
+                "The arguments of the program.",
+            )
+            .allows_unknown_args();
+        dialect_switches(signature, "the program prints the syntax of").category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Start an ssh agent for this session",
+                example: "env capture ssh-agent -s",
+                result: None,
+            },
+            Example {
+                description: "Point docker at a machine, reading the fish syntax it prints",
+                example: "env capture docker-machine env --shell fish default --fish",
+                result: None,
+            },
+            Example {
+                description: "Activate a conda environment",
+                example: "env capture conda shell.posix activate base",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let dialect = dialect_flag(engine_state, stack, call)?;
+        let name: Value = call.req(engine_state, stack, 0)?;
+        let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
+
+        let name_span = name.span();
+        let name: Cow<str> = match &name {
+            Value::Glob { val, .. } | Value::String { val, .. } => Cow::Borrowed(val),
+            _ => Cow::Owned(name.clone().coerce_into_string()?),
+        };
+        if let Some(sandbox) = &engine_state.sandbox {
+            sandbox.check_external(&name, name_span)?;
+        }
+
+        let cwd = engine_state.cwd(Some(stack))?;
+        let paths = nu_engine::env::path_str(engine_state, stack, head)?;
+        let Some(executable) = crate::which(name.as_ref(), &paths, cwd.as_ref()) else {
+            return Err(crate::command_not_found(
+                &name,
+                name_span,
+                engine_state,
+                stack,
+                &cwd,
+            ));
+        };
+
+        let mut command = std::process::Command::new(executable);
+        command
+            .current_dir(cwd)
+            .env_clear()
+            .envs(env_to_strings(engine_state, stack)?)
+            .args(
+                crate::eval_external_arguments(engine_state, stack, args)?
+                    .into_iter()
+                    .map(|arg| arg.item),
+            )
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .stdout(Stdio::piped());
+        let output = command
+            .output()
+            .map_err(|err| ShellError::ExternalCommand {
+                label: "Can't run the program".into(),
+                help: err.to_string(),
+                span: name_span,
+            })?;
+        if let Some(exit_code) = output.status.code().and_then(std::num::NonZeroI32::new) {
+            return Err(ShellError::NonZeroExitCode {
+                exit_code,
+                span: name_span,
+            });
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let dialect = dialect.unwrap_or_else(|| Dialect::detect(&text));
+        let env = parse_env(&text, dialect, &|name| {
+            let value = stack.get_env_var(engine_state, name)?;
+            env_to_string(name, value, engine_state, stack).ok()
+        });
+
+        for (name, value) in env {
+            if ["FILE_PWD", "CURRENT_FILE", "PWD"].contains(&name.as_str()) {
+                continue;
+            }
+            if value.is_nothing() {
+                stack.remove_env_var(engine_state, &name);
+            } else {
+                stack.add_env_var(name, value.with_span(head));
+            }
+        }
+        Ok(PipelineData::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(EnvCapture {})
+    }
+}
@@ -0,0 +1,201 @@
+use super::shell_syntax::{Dialect, ExportValue, dialect_flag, dialect_switches, is_name};
+use nu_engine::{command_prelude::*, env_to_string, scope::ScopeData};
+
+#[derive(Clone)]
+pub struct EnvExport;
+
+impl Command for EnvExport {
+    fn name(&self) -> &str {
+        "env export"
+    }
+
+    fn description(&self) -> &str {
+        "Write environment variables and aliases in the syntax of another shell."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Without names, every environment variable which can be converted to a string is written, except for PWD and the variables of the current file. Lists like PATH are joined with `:`, or in PowerShell with the path separator of the platform, and are separate words in fish.
+The default syntax is POSIX, for sh, bash and zsh. Aliases are written as they are defined in nushell, so they only work in another shell when they run external commands.
+`env import` reads the output of this command back."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "bash",
+            "sh",
+            "fish",
+            "powershell",
+            "dotenv",
+            "shell",
+            "source",
+        ]
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        let signature = Signature::build(self.name())
+            .input_output_types(vec![
+                (Type::Nothing, Type::String),
+                (Type::record(), Type::String),
+            ])
+            .rest(
+                "names",
+                SyntaxShape::String,
+                "The environment variables to write (defaults to all of them).",
+            )
+            .switch("aliases", "also write the aliases in scope", Some('a'));
+        dialect_switches(signature, "write the syntax of").category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Write variables for a POSIX shell",
+                example: "{EDITOR: hx, PAGER: 'less -R'} | env export",
+                result: Some(Value::test_string(
+                    "export EDITOR='hx'\nexport PAGER='less -R'",
+                )),
+            },
+            Example {
+                description: "Write variables for fish, with a list as separate words",
+                example: "{GREETING: \"it's me\", PATH: [/usr/bin /bin]} | env export --fish",
+                result: Some(Value::test_string(
+                    "set -gx GREETING 'it\\'s me'\nset -gx PATH '/usr/bin' '/bin'",
+                )),
+            },
+            Example {
+                description: "Write variables for PowerShell",
+                example: "{EDITOR: hx} | env export --powershell",
+                result: Some(Value::test_string("$env:EDITOR = 'hx'")),
+            },
+            Example {
+                description: "Write a variable of the environment",
+                example: "with-env {EDITOR: hx} { env export EDITOR }",
+                result: Some(Value::test_string("export EDITOR='hx'")),
+            },
+            Example {
+                description: "Share some variables and the aliases with a bash session",
+                example: "env export EDITOR PATH --aliases | save -f ~/.nu-env.sh",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let names: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let aliases = call.has_flag(engine_state, stack, "aliases")?;
+        let dialect = dialect_flag(engine_state, stack, call)?.unwrap_or(Dialect::Posix);
+
+        let input_span = input.span().unwrap_or(head);
+        let env = match input.into_value(head)? {
+            Value::Record { val, .. } => val.into_owned(),
+            Value::Nothing { .. } => {
+                let mut env: Vec<_> = stack.get_env_vars(engine_state).into_iter().collect();
+                env.sort_by(|(a, _), (b, _)| a.cmp(b));
+                env.into_iter().collect()
+            }
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "record or nothing".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: input_span,
+                });
+            }
+        };
+
+        let mut lines = vec![];
+        if names.is_empty() {
+            for (name, value) in &env {
+                if ["PWD", "FILE_PWD", "CURRENT_FILE"].contains(&name.as_str()) || !is_name(name) {
+                    continue;
+                }
+                match export_value(name, value, engine_state, stack) {
+                    Ok(value) => lines.push(dialect.export(name, &value)),
+                    Err(ShellError::EnvVarNotAString { .. }) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        } else {
+            for name in names {
+                if !is_name(&name.item) {
+                    return Err(ShellError::IncorrectValue {
+                        msg: "the name of a variable must be letters, digits and underscores"
+                            .into(),
+                        val_span: name.span,
+                        call_span: head,
+                    });
+                }
+                let value =
+                    env.get(&name.item)
+                        .ok_or_else(|| ShellError::EnvVarNotFoundAtRuntime {
+                            envvar_name: name.item.clone(),
+                            span: name.span,
+                        })?;
+                let value = export_value(&name.item, value, engine_state, stack)?;
+                lines.push(dialect.export(&name.item, &value));
+            }
+        }
+
+        if aliases {
+            let mut scope_data = ScopeData::new(engine_state, stack);
+            scope_data.populate_decls();
+            for alias in scope_data.collect_aliases(head) {
+                let name = alias.get_data_by_key("name");
+                let expansion = alias.get_data_by_key("expansion");
+                match (name, expansion) {
+                    // Aliases like `ll` work everywhere, but not ones like `git co`
+                    (
+                        Some(Value::String { val: name, .. }),
+                        Some(Value::String { val: expansion, .. }),
+                    ) if is_name(&name.replace('-', "_")) => {
+                        let expansion = expansion.strip_prefix('^').unwrap_or(&expansion);
+                        lines.push(dialect.alias(&name, expansion));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Value::string(lines.join("\n"), head).into_pipeline_data())
+    }
+}
+
+/// The value of a variable as another shell sees it.
+fn export_value(
+    name: &str,
+    value: &Value,
+    engine_state: &EngineState,
+    stack: &Stack,
+) -> Result<ExportValue, ShellError> {
+    if let Value::List { vals, .. } = value {
+        let entries: Option<Vec<String>> =
+            vals.iter().map(|val| val.coerce_string().ok()).collect();
+        if let Some(entries) = entries {
+            return Ok(ExportValue::List(entries));
+        }
+    }
+    env_to_string(name, value, engine_state, stack).map(ExportValue::String)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::{WithEnv, test_examples_with_commands};
+
+        test_examples_with_commands(EnvExport {}, &[&WithEnv])
+    }
+}
@@ -0,0 +1,106 @@
+use super::shell_syntax::{Dialect, dialect_flag, dialect_switches, parse_env};
+use nu_engine::{command_prelude::*, env_to_string};
+
+#[derive(Clone)]
+pub struct EnvImport;
+
+impl Command for EnvImport {
+    fn name(&self) -> &str {
+        "env import"
+    }
+
+    fn description(&self) -> &str {
+        "Read the environment variables a script of another shell sets."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The statements which export or unset variables are read, like `export NAME=value`, `setenv NAME value`, `set -gx NAME value` and `$env:NAME = 'value'`. Everything else is skipped, so the script isn't run. Unset variables are null in the output.
+References like `$NAME` are replaced by what the script set before, or by the current environment. The syntax of the script is detected unless it's chosen with a switch.
+Use `load-env` to apply the output, or `env capture` to run a program and apply what it prints."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "bash",
+            "sh",
+            "fish",
+            "powershell",
+            "dotenv",
+            "shell",
+            "source",
+            "parse",
+        ]
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        let signature =
+            Signature::build(self.name()).input_output_types(vec![(Type::String, Type::record())]);
+        dialect_switches(signature, "read the syntax of").category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Read the variables of a POSIX script",
+                example: "\"export EDITOR=hx\\nexport PAGER='less -R'\" | env import",
+                result: Some(Value::test_record(record! {
+                    "EDITOR" => Value::test_string("hx"),
+                    "PAGER" => Value::test_string("less -R"),
+                })),
+            },
+            Example {
+                description: "Read the variables set by fish",
+                example: "'set -gx DOCKER_HOST \"tcp://10.0.0.2:2376\"; set -e DOCKER_CERT_PATH' | env import",
+                result: Some(Value::test_record(record! {
+                    "DOCKER_HOST" => Value::test_string("tcp://10.0.0.2:2376"),
+                    "DOCKER_CERT_PATH" => Value::test_nothing(),
+                })),
+            },
+            Example {
+                description: "Load the variables of a bash file",
+                example: "open --raw .env.sh | env import | load-env",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let dialect = dialect_flag(engine_state, stack, call)?;
+        let (text, _, metadata) = input.collect_string_strict(head)?;
+        let dialect = dialect.unwrap_or_else(|| Dialect::detect(&text));
+
+        let env = parse_env(&text, dialect, &|name| {
+            let value = stack.get_env_var(engine_state, name)?;
+            env_to_string(name, value, engine_state, stack).ok()
+        });
+        let env = env
+            .into_iter()
+            .map(|(name, value)| (name, value.with_span(head)))
+            .collect();
+        Ok(Value::record(env, head)
+            .into_pipeline_data_with_metadata(metadata.map(|md| md.with_content_type(None))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(EnvImport {})
+    }
+}
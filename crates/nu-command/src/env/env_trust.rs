@@ -0,0 +1,147 @@
+use nu_cmd_base::dir_env::{DIR_ENV_FILE, content_hash, find_dir_env_file, set_trusted};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct EnvTrust;
+
+impl Command for EnvTrust {
+    fn name(&self) -> &str {
+        "env trust"
+    }
+
+    fn description(&self) -> &str {
+        "Allow a `.nu-env` file to be loaded when entering its directory."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Without a path, the `.nu-env` file applying to the current directory is trusted. The hash of the file's current contents is recorded, so the file has to be trusted again after every change.
+Trusted files are stored in `trusted-env.json` inside the nushell data directory."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The `.nu-env` file, or the directory containing it.",
+            )
+            .category(Category::Env)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Trust the `.nu-env` file of the current directory",
+                example: "env trust",
+                result: None,
+            },
+            Example {
+                description: "Trust the `.nu-env` file of a project",
+                example: "env trust ~/projects/nushell",
+                result: None,
+            },
+        ]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let file = dir_env_file(engine_state, stack, call)?;
+        let contents =
+            std::fs::read(&file).map_err(|err| IoError::new(err, call.head, file.clone()))?;
+
+        set_trusted(&file, Some(&content_hash(&contents)))?;
+
+        Ok(PipelineData::empty())
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvUntrust;
+
+impl Command for EnvUntrust {
+    fn name(&self) -> &str {
+        "env untrust"
+    }
+
+    fn description(&self) -> &str {
+        "Stop loading a `.nu-env` file when entering its directory."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The `.nu-env` file, or the directory containing it.",
+            )
+            .category(Category::Env)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Revoke the trust of the `.nu-env` file of the current directory",
+            example: "env untrust",
+            result: None,
+        }]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let file = dir_env_file(engine_state, stack, call)?;
+
+        set_trusted(&file, None)?;
+
+        Ok(PipelineData::empty())
+    }
+}
+
+fn dir_env_file(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<PathBuf, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?;
+    let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+
+    match path {
+        Some(path) => {
+            let path = nu_path::expand_path_with(&path.item, &cwd, true);
+            if path.is_dir() {
+                Ok(path.join(DIR_ENV_FILE))
+            } else {
+                Ok(path)
+            }
+        }
+        None => find_dir_env_file(cwd.as_std_path()).ok_or_else(|| ShellError::GenericError {
+            error: format!("No {DIR_ENV_FILE} file found"),
+            msg: format!(
+                "neither the current directory nor its parents contain a {DIR_ENV_FILE} file"
+            ),
+            span: Some(call.head),
+            help: None,
+            inner: vec![],
+        }),
+    }
+}
@@ -0,0 +1,85 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::engine::{Closure, EnvWatch};
+
+#[derive(Clone)]
+pub struct EnvWatch;
+
+impl Command for EnvWatch {
+    fn name(&self) -> &str {
+        "env watch"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure whenever an environment variable changes."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure receives the previous and the new value of the variable, which is null while it isn't set. Changes from `load-env`, `$env.NAME = ...`, `cd` or overlay switches are all picked up.
+Unlike the `env_change` hooks, which run in the REPL before the next prompt is drawn, the closure runs right after the assignment or the command which changed the variable, in scripts too. Changes made by the closure itself don't run it again.
+A change inside a block or a custom command without `--env` runs the closure too, but the variable going back to its old value when it returns doesn't."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The name of the environment variable to watch.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any, SyntaxShape::Any])),
+                "The closure to run with the old and the new value.",
+            )
+            .category(Category::Env)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Remove duplicate entries whenever PATH changes",
+                example: "env watch PATH {|old, new| if ($new | uniq | length) != ($new | length) { $env.PATH = ($new | uniq) } }",
+                result: None,
+            },
+            Example {
+                description: "Report directory changes",
+                example: "env watch PWD {|old, new| print $'left ($old) for ($new)' }",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: String = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+
+        watch_env(engine_state, stack, name, closure);
+        Ok(PipelineData::empty())
+    }
+}
+
+/// Run `closure` whenever the environment variable `name` changes, which is also what
+/// `watch-var $env.NAME` does.
+pub(crate) fn watch_env(engine_state: &EngineState, stack: &Stack, name: String, closure: Closure) {
+    let initial = stack.get_env_var(engine_state, &name).cloned();
+    engine_state
+        .env_watches
+        .lock()
+        .expect("env watches lock is poisoned!")
+        .push(EnvWatch {
+            name,
+            closure,
+            initial,
+        });
+}
@@ -30,6 +30,10 @@ impl Command for LoadEnv {
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -1,15 +1,37 @@
 mod config;
+mod env_;
+#[cfg(feature = "os")]
+mod env_capture;
+mod env_export;
+mod env_import;
+mod env_trust;
+mod env_watch;
 mod export_env;
 mod load_env;
+mod shell_syntax;
 mod source_env;
 mod with_env;
 
+pub use config::ConfigDiff;
 pub use config::ConfigEnv;
 pub use config::ConfigFlatten;
+pub use config::ConfigGet;
 pub use config::ConfigMeta;
+pub use config::ConfigMigrate;
 pub use config::ConfigNu;
+pub use config::ConfigProfiles;
 pub use config::ConfigReset;
+pub use config::ConfigSet;
 pub use config::ConfigUseColors;
+pub use config::ConfigValidate;
+pub use env_::EnvMeta;
+#[cfg(feature = "os")]
+pub use env_capture::EnvCapture;
+pub use env_export::EnvExport;
+pub use env_import::EnvImport;
+pub use env_trust::{EnvTrust, EnvUntrust};
+pub use env_watch::EnvWatch;
+pub(crate) use env_watch::watch_env;
 pub use export_env::ExportEnv;
 pub use load_env::LoadEnv;
 pub use source_env::SourceEnv;
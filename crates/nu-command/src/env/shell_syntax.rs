@@ -0,0 +1,604 @@
+//! Reading and writing environment variables in the syntax of other shells.
+//!
+//! Only the statements which change the environment are understood: `export`, `unset`, `setenv`,
+//! `set -x` and `$env:NAME = ...`. Everything else, like the `echo` of `ssh-agent`, is skipped.
+
+use nu_engine::command_prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Dialect {
+    /// sh, bash, zsh and other POSIX shells
+    Posix,
+    /// csh and tcsh
+    Csh,
+    Fish,
+    PowerShell,
+}
+
+impl Dialect {
+    pub(crate) const FLAGS: [(&'static str, Dialect); 4] = [
+        ("posix", Dialect::Posix),
+        ("csh", Dialect::Csh),
+        ("fish", Dialect::Fish),
+        ("powershell", Dialect::PowerShell),
+    ];
+
+    /// Guess the dialect of a script from its first statement which sets a variable.
+    pub(crate) fn detect(text: &str) -> Dialect {
+        for line in text.lines() {
+            let line = line.trim_start();
+            let lower = line.to_ascii_lowercase();
+            if lower.starts_with("$env:")
+                || lower.starts_with("${env:")
+                || lower.starts_with("remove-item")
+            {
+                return Dialect::PowerShell;
+            } else if line.starts_with("setenv ") || line.starts_with("unsetenv ") {
+                return Dialect::Csh;
+            } else if line.starts_with("set -") {
+                return Dialect::Fish;
+            } else if line.starts_with("export ") || line.starts_with("unset ") {
+                return Dialect::Posix;
+            }
+        }
+        Dialect::Posix
+    }
+
+    /// The statement which sets `name` to `value`.
+    pub(crate) fn export(self, name: &str, value: &ExportValue) -> String {
+        let value = match (self, value) {
+            (Dialect::Fish, ExportValue::List(values)) => values
+                .iter()
+                .map(|value| self.quote(value))
+                .collect::<Vec<_>>()
+                .join(" "),
+            (_, value) => self.quote(&value.joined(self.separator())),
+        };
+        match self {
+            Dialect::Posix => format!("export {name}={value}"),
+            Dialect::Csh => format!("setenv {name} {value}"),
+            Dialect::Fish => format!("set -gx {name} {value}"),
+            Dialect::PowerShell => format!("$env:{name} = {value}"),
+        }
+    }
+
+    /// The statement which defines the alias `name`, running `expansion`.
+    pub(crate) fn alias(self, name: &str, expansion: &str) -> String {
+        match self {
+            Dialect::Posix => format!("alias {name}={}", self.quote(expansion)),
+            Dialect::Csh => format!("alias {name} {}", self.quote(expansion)),
+            Dialect::Fish => format!("alias {name} {}", self.quote(expansion)),
+            // `Set-Alias` can't pass arguments, so a function is the closest to an alias
+            Dialect::PowerShell => format!("function {name} {{ {expansion} @args }}"),
+        }
+    }
+
+    /// The separator of the entries of a list variable like PATH.
+    ///
+    /// POSIX shells use `:` even on Windows, like in MSYS2 and Cygwin, while PowerShell uses the
+    /// separator of the platform it runs on.
+    pub(crate) fn separator(self) -> char {
+        match self {
+            Dialect::Posix | Dialect::Csh | Dialect::Fish => ':',
+            Dialect::PowerShell if cfg!(windows) => ';',
+            Dialect::PowerShell => ':',
+        }
+    }
+
+    /// Quote `value` so that it's read back literally.
+    pub(crate) fn quote(self, value: &str) -> String {
+        match self {
+            Dialect::Posix | Dialect::Csh => format!("'{}'", value.replace('\'', r"'\''")),
+            Dialect::Fish => format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+            Dialect::PowerShell => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+}
+
+/// Add the `--posix`, `--csh`, `--fish` and `--powershell` switches to a signature.
+pub(crate) fn dialect_switches(signature: Signature, description: &str) -> Signature {
+    signature
+        .switch("posix", format!("{description} sh, bash or zsh"), None)
+        .switch("csh", format!("{description} csh or tcsh"), None)
+        .switch("fish", format!("{description} fish"), None)
+        .switch("powershell", format!("{description} PowerShell"), None)
+}
+
+/// The dialect of the `--posix`, `--csh`, `--fish` or `--powershell` switch of a call.
+pub(crate) fn dialect_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<Option<Dialect>, ShellError> {
+    let mut chosen = None;
+    for (flag, dialect) in Dialect::FLAGS {
+        if call.has_flag(engine_state, stack, flag)? {
+            if chosen.is_some() {
+                return Err(ShellError::IncompatibleParametersSingle {
+                    msg: "only one of --posix, --csh, --fish and --powershell can be used".into(),
+                    span: call.head,
+                });
+            }
+            chosen = Some(dialect);
+        }
+    }
+    Ok(chosen)
+}
+
+/// The value of an environment variable, before it's written in the syntax of a shell.
+pub(crate) enum ExportValue {
+    String(String),
+    /// The entries of a list variable like PATH, which are separate words in fish.
+    List(Vec<String>),
+}
+
+impl ExportValue {
+    fn joined(&self, separator: char) -> String {
+        match self {
+            ExportValue::String(value) => value.clone(),
+            ExportValue::List(values) => values.join(&separator.to_string()),
+        }
+    }
+}
+
+/// Whether `name` can be the name of a variable in every shell.
+pub(crate) fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Read the environment changes of a script.
+///
+/// Exported variables are set to a string, or to a list in fish when they have several words.
+/// Unset variables are null. `$NAME` is replaced by earlier values of the script or, before those,
+/// by `lookup`.
+pub(crate) fn parse_env(
+    text: &str,
+    dialect: Dialect,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Record {
+    let mut parser = Parser {
+        dialect,
+        lookup,
+        env: Record::new(),
+        locals: Vec::new(),
+    };
+    for statement in statements(text, dialect) {
+        parser.statement(statement);
+    }
+    parser.env
+}
+
+struct Parser<'a> {
+    dialect: Dialect,
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    env: Record,
+    /// Variables set without `export`, which an `export NAME` later in the script exports.
+    locals: Vec<(String, String)>,
+}
+
+impl Parser<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        match self.env.get(name) {
+            Some(Value::String { val, .. }) => Some(val.clone()),
+            Some(Value::List { vals, .. }) => Some(
+                vals.iter()
+                    .filter_map(|val| val.as_str().ok())
+                    .collect::<Vec<_>>()
+                    .join(":"),
+            ),
+            Some(_) => None,
+            None => self
+                .locals
+                .iter()
+                .rev()
+                .find(|(local, _)| local == name)
+                .map(|(_, value)| value.clone())
+                .or_else(|| (self.lookup)(name)),
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) {
+        if is_name(name) {
+            self.env.insert(name, value);
+        }
+    }
+
+    fn statement(&mut self, words: Vec<Word>) {
+        let words: Vec<String> = words.into_iter().map(|word| word.expand(self)).collect();
+        let Some((command, args)) = words.split_first() else {
+            return;
+        };
+        match (self.dialect, command.as_str()) {
+            (Dialect::Posix, "export") => self.export(args),
+            (Dialect::Posix, "declare" | "typeset")
+                if args
+                    .iter()
+                    .any(|arg| arg.starts_with('-') && arg.contains('x')) =>
+            {
+                self.export(args)
+            }
+            (Dialect::Posix, "unset") => {
+                for name in args.iter().filter(|arg| !arg.starts_with('-')) {
+                    self.set(name, Value::nothing(Span::unknown()));
+                }
+            }
+            (Dialect::Posix, _) if command.contains('=') => {
+                // Without a command, the assignments only set shell variables
+                if words.iter().all(|word| word.contains('=')) {
+                    for word in &words {
+                        if let Some((name, value)) = word.split_once('=') {
+                            self.locals.push((name.into(), value.into()));
+                        }
+                    }
+                }
+            }
+            (Dialect::Csh, "setenv") => {
+                if let Some(name) = args.first() {
+                    let value = args.get(1).cloned().unwrap_or_default();
+                    self.set(name, string(value));
+                }
+            }
+            (Dialect::Csh, "unsetenv") => {
+                for name in args {
+                    self.set(name, Value::nothing(Span::unknown()));
+                }
+            }
+            (Dialect::Fish, "set") => self.fish_set(args),
+            (Dialect::PowerShell, _) => self.powershell(&words),
+            _ => {}
+        }
+    }
+
+    fn export(&mut self, args: &[String]) {
+        for arg in args.iter().filter(|arg| !arg.starts_with('-')) {
+            match arg.split_once('=') {
+                Some((name, value)) => self.set(name, string(value.into())),
+                None => {
+                    if let Some(value) = self.get(arg) {
+                        self.set(arg, string(value));
+                    }
+                }
+            }
+        }
+    }
+
+    fn fish_set(&mut self, args: &[String]) {
+        let (flags, rest): (Vec<_>, Vec<_>) = args.iter().partition(|arg| arg.starts_with('-'));
+        let has = |short: char, long: &str| {
+            flags.iter().any(|flag| match flag.strip_prefix("--") {
+                Some(flag) => flag == long,
+                None => flag.contains(short),
+            })
+        };
+        let Some((name, values)) = rest.split_first() else {
+            return;
+        };
+        if has('e', "erase") {
+            for name in &rest {
+                self.set(name, Value::nothing(Span::unknown()));
+            }
+        } else if has('x', "export") {
+            let value = match values {
+                [value] => string(value.to_string()),
+                values => Value::list(
+                    values
+                        .iter()
+                        .map(|value| string(value.to_string()))
+                        .collect(),
+                    Span::unknown(),
+                ),
+            };
+            self.set(name, value);
+        }
+    }
+
+    fn powershell(&mut self, words: &[String]) {
+        let env_name = |word: &str| {
+            let word = word.strip_prefix('$').unwrap_or(word);
+            let word = word
+                .strip_prefix('{')
+                .and_then(|word| word.strip_suffix('}'))
+                .unwrap_or(word);
+            let (drive, name) = word.split_once(':')?;
+            drive
+                .eq_ignore_ascii_case("env")
+                .then(|| name.trim_start_matches('\\').to_string())
+        };
+        match words {
+            [command, args @ ..] if command.eq_ignore_ascii_case("remove-item") => {
+                for name in args.iter().filter_map(|arg| env_name(arg)) {
+                    self.set(&name, Value::nothing(Span::unknown()));
+                }
+            }
+            [target, rest @ ..] if target.starts_with('$') => {
+                // The `=` may or may not be surrounded by spaces
+                let (target, value) = match (target.split_once('='), rest) {
+                    (Some((target, value)), []) => (target, value.to_string()),
+                    (Some((target, "")), [value]) => (target, value.clone()),
+                    (None, [equals, value]) if equals == "=" => (target.as_str(), value.clone()),
+                    (None, [value]) if value.starts_with('=') => {
+                        (target.as_str(), value[1..].to_string())
+                    }
+                    _ => return,
+                };
+                if let Some(name) = env_name(target) {
+                    self.set(&name, string(value));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn string(value: String) -> Value {
+    Value::string(value, Span::unknown())
+}
+
+/// A word of a statement, made of literal text and variable references.
+#[derive(Debug, Default, PartialEq)]
+struct Word(Vec<Part>);
+
+#[derive(Debug, PartialEq)]
+enum Part {
+    Text(String),
+    Variable(String),
+}
+
+impl Word {
+    fn push(&mut self, char: char) {
+        match self.0.last_mut() {
+            Some(Part::Text(text)) => text.push(char),
+            _ => self.0.push(Part::Text(char.into())),
+        }
+    }
+
+    fn expand(self, parser: &Parser) -> String {
+        self.0
+            .into_iter()
+            .map(|part| match part {
+                Part::Text(text) => text,
+                Part::Variable(name) => parser.get(&name).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Split a script into statements of words, removing quotes and comments.
+fn statements(text: &str, dialect: Dialect) -> Vec<Vec<Word>> {
+    let mut statements = vec![];
+    let mut words = vec![];
+    let mut word: Option<Word> = None;
+    let mut chars = text.chars().peekable();
+    let escape = if dialect == Dialect::PowerShell {
+        '`'
+    } else {
+        '\\'
+    };
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\n' | ';' => {
+                words.extend(word.take());
+                if !words.is_empty() {
+                    statements.push(std::mem::take(&mut words));
+                }
+            }
+            char if char.is_whitespace() => words.extend(word.take()),
+            '#' if word.is_none() => while chars.next_if(|char| *char != '\n').is_some() {},
+            '\'' => {
+                let word = word.get_or_insert_default();
+                while let Some(char) = chars.next() {
+                    match (dialect, char) {
+                        (Dialect::PowerShell, '\'') if chars.next_if_eq(&'\'').is_some() => {
+                            word.push('\'')
+                        }
+                        (Dialect::Fish, '\\') => {
+                            match chars.next_if(|c| matches!(*c, '\\' | '\'')) {
+                                Some(char) => word.push(char),
+                                None => word.push('\\'),
+                            }
+                        }
+                        (_, '\'') => break,
+                        (_, char) => word.push(char),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_default();
+                while let Some(char) = chars.next() {
+                    match char {
+                        '"' if dialect == Dialect::PowerShell
+                            && chars.next_if_eq(&'"').is_some() =>
+                        {
+                            word.push('"')
+                        }
+                        '"' => break,
+                        char if char == escape => {
+                            match chars.next_if(|char| escapable(dialect, *char)) {
+                                Some('\n') => {}
+                                Some(char) => word.push(escaped(dialect, char)),
+                                None => word.push(escape),
+                            }
+                        }
+                        '$' => variable(&mut chars, dialect, word),
+                        char => word.push(char),
+                    }
+                }
+            }
+            char if char == escape => {
+                match chars.next() {
+                    // A line continuation
+                    Some('\n') => {}
+                    Some(char) => word.get_or_insert_default().push(escaped(dialect, char)),
+                    None => {}
+                }
+            }
+            '$' if dialect != Dialect::PowerShell => {
+                variable(&mut chars, dialect, word.get_or_insert_default())
+            }
+            char => word.get_or_insert_default().push(char),
+        }
+    }
+    words.extend(word);
+    if !words.is_empty() {
+        statements.push(words);
+    }
+    statements
+}
+
+/// Whether the escape character escapes `char` inside double quotes, instead of being literal.
+fn escapable(dialect: Dialect, char: char) -> bool {
+    match dialect {
+        Dialect::Posix | Dialect::Csh => matches!(char, '$' | '`' | '"' | '\\' | '\n'),
+        Dialect::Fish => matches!(char, '$' | '"' | '\\' | '\n'),
+        Dialect::PowerShell => true,
+    }
+}
+
+fn escaped(dialect: Dialect, char: char) -> char {
+    match (dialect, char) {
+        (Dialect::PowerShell, 'n') => '\n',
+        (Dialect::PowerShell, 't') => '\t',
+        (_, char) => char,
+    }
+}
+
+/// Read a variable reference after a `$`, like `$NAME`, `${NAME}` or, in PowerShell, `$env:NAME`.
+fn variable(chars: &mut std::iter::Peekable<std::str::Chars>, dialect: Dialect, word: &mut Word) {
+    let braced = chars.next_if_eq(&'{').is_some();
+    let mut name = String::new();
+    while let Some(char) = chars.next_if(|char| {
+        char.is_ascii_alphanumeric()
+            || *char == '_'
+            || (dialect == Dialect::PowerShell && *char == ':')
+    }) {
+        name.push(char);
+    }
+    if braced {
+        chars.next_if_eq(&'}');
+    }
+    let raw = name.clone();
+    let name = match dialect {
+        Dialect::PowerShell => match name.split_once(':') {
+            Some((drive, name)) if drive.eq_ignore_ascii_case("env") => Some(name.to_string()),
+            _ => None,
+        },
+        _ => Some(name).filter(|name| is_name(name)),
+    };
+    match name {
+        Some(name) => word.0.push(Part::Variable(name)),
+        // Not a variable of the environment, like `$1` or a PowerShell variable, so it's kept
+        None => {
+            word.push('$');
+            if braced {
+                word.push('{');
+            }
+            raw.chars().for_each(|char| word.push(char));
+            if braced {
+                word.push('}');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str) -> Record {
+        let dialect = Dialect::detect(text);
+        parse_env(text, dialect, &|name| {
+            (name == "PATH").then(|| "/usr/bin".to_string())
+        })
+    }
+
+    #[test]
+    fn detects_dialects() {
+        assert_eq!(Dialect::detect("export A=1"), Dialect::Posix);
+        assert_eq!(Dialect::detect("A=1; export A;"), Dialect::Posix);
+        assert_eq!(Dialect::detect("setenv A 1;"), Dialect::Csh);
+        assert_eq!(Dialect::detect("set -gx A 1;"), Dialect::Fish);
+        assert_eq!(Dialect::detect("$Env:A = \"1\""), Dialect::PowerShell);
+    }
+
+    #[test]
+    fn parses_ssh_agent() {
+        let env = parse(
+            "SSH_AUTH_SOCK=/tmp/ssh-XXXX/agent.1; export SSH_AUTH_SOCK;\nSSH_AGENT_PID=2; export SSH_AGENT_PID;\necho Agent pid 2;\n",
+        );
+        assert_eq!(
+            env,
+            Record::from_iter([
+                (
+                    "SSH_AUTH_SOCK".into(),
+                    string("/tmp/ssh-XXXX/agent.1".into())
+                ),
+                ("SSH_AGENT_PID".into(), string("2".into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn expands_variables() {
+        let env = parse("export A=\"/opt/bin:$PATH\"\nexport B='$A' C=${A}:x # comment");
+        assert_eq!(env.get("A"), Some(&string("/opt/bin:/usr/bin".into())));
+        assert_eq!(env.get("B"), Some(&string("$A".into())));
+        assert_eq!(env.get("C"), Some(&string("/opt/bin:/usr/bin:x".into())));
+    }
+
+    #[test]
+    fn parses_other_dialects() {
+        let env = parse("set -gx A 'it\\'s';\nset -x PATH /a /b;\nset -e B");
+        assert_eq!(env.get("A"), Some(&string("it's".into())));
+        assert_eq!(
+            env.get("PATH"),
+            Some(&Value::list(
+                vec![string("/a".into()), string("/b".into())],
+                Span::unknown()
+            ))
+        );
+        assert_eq!(env.get("B"), Some(&Value::nothing(Span::unknown())));
+
+        let env =
+            parse("$Env:DOCKER_HOST = \"tcp://1.2.3.4:2376\"\n$env:A='it''s'\nRemove-Item Env:\\B");
+        assert_eq!(
+            env.get("DOCKER_HOST"),
+            Some(&string("tcp://1.2.3.4:2376".into()))
+        );
+        assert_eq!(env.get("A"), Some(&string("it's".into())));
+        assert_eq!(env.get("B"), Some(&Value::nothing(Span::unknown())));
+
+        let env = parse("setenv A \"b c\";\nunsetenv B;");
+        assert_eq!(env.get("A"), Some(&string("b c".into())));
+    }
+
+    #[test]
+    fn exports_read_back() {
+        let value = "it's a \\ \"test\" $HOME";
+        for (_, dialect) in Dialect::FLAGS {
+            let script = dialect.export("A", &ExportValue::String(value.into()));
+            let env = parse_env(&script, dialect, &|_| None);
+            assert_eq!(env.get("A"), Some(&string(value.into())), "{script}");
+        }
+    }
+
+    #[test]
+    fn exports_lists_with_separator() {
+        let path = ExportValue::List(vec!["/a".into(), "/b".into()]);
+        assert_eq!(Dialect::Posix.export("PATH", &path), "export PATH='/a:/b'");
+        assert_eq!(Dialect::Csh.export("PATH", &path), "setenv PATH '/a:/b'");
+        assert_eq!(
+            Dialect::Fish.export("PATH", &path),
+            "set -gx PATH '/a' '/b'"
+        );
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        assert_eq!(
+            Dialect::PowerShell.export("PATH", &path),
+            format!("$env:PATH = '/a{separator}/b'")
+        );
+    }
+}
@@ -25,6 +25,10 @@ impl Command for WithEnv {
             .category(Category::Env)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Runs a block with an environment variable set."
     }
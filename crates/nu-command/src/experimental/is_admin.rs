@@ -19,6 +19,10 @@ impl Command for IsAdmin {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["root", "administrator", "superuser", "supervisor"]
     }
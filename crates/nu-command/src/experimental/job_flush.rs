@@ -26,6 +26,10 @@ If a message is received while this command is executing, it may also be discard
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec![]
     }
@@ -24,6 +24,10 @@ was instead spawned by main nushell execution thread."
             .input_output_types(vec![(Type::Nothing, Type::Int)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["self", "this", "my-id", "this-id"]
     }
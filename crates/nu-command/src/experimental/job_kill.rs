@@ -21,6 +21,10 @@ impl Command for JobKill {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["halt", "stop", "end", "close"]
     }
@@ -19,6 +19,10 @@ impl Command for JobList {
             .input_output_types(vec![(Type::Nothing, Type::table())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["background", "jobs"]
     }
@@ -55,6 +55,10 @@ in no particular order, regardless of the specified timeout parameter.
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["receive"]
     }
@@ -47,6 +47,10 @@ impl Command for JobSpawn {
         vec!["background", "bg", "&"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -73,6 +77,9 @@ impl Command for JobSpawn {
 
         job_state.exit_warning_given = Arc::new(AtomicBool::new(false));
 
+        // files imported by the job must not be loaded by the foreground
+        job_state.deferred_sources = Arc::default();
+
         let jobs = job_state.jobs.clone();
         let mut jobs = jobs.lock().expect("jobs lock is poisoned!");
 
@@ -25,6 +25,10 @@ impl Command for JobTag {
             .input_output_types(vec![(Type::Nothing, Type::Nothing)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["describe", "desc"]
     }
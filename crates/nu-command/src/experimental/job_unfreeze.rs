@@ -26,6 +26,10 @@ impl Command for JobUnfreeze {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["fg"]
     }
@@ -5,6 +5,10 @@ mod job_kill;
 mod job_list;
 mod job_spawn;
 mod job_tag;
+mod schedule;
+mod schedule_add;
+mod schedule_list;
+mod schedule_remove;
 
 #[cfg(all(unix, feature = "os"))]
 mod job_unfreeze;
@@ -23,6 +27,10 @@ pub use job_kill::JobKill;
 pub use job_list::JobList;
 pub use job_spawn::JobSpawn;
 pub use job_tag::JobTag;
+pub use schedule::Schedule;
+pub use schedule_add::ScheduleAdd;
+pub use schedule_list::ScheduleList;
+pub use schedule_remove::ScheduleRemove;
 
 #[cfg(not(target_family = "wasm"))]
 pub use job_flush::JobFlush;
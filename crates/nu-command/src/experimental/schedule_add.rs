@@ -0,0 +1,290 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::generators::cron::Cron;
+use chrono::{DateTime, Local, TimeDelta, TimeZone};
+use nu_engine::{ClosureEval, command_prelude::*};
+use nu_protocol::{
+    OutDest, Signals,
+    engine::{Closure, CurrentJob, Job, Mailbox, Redirection, Schedule, ScheduleRun, ThreadJob},
+};
+
+const CTRL_C_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct ScheduleAdd;
+
+impl Command for ScheduleAdd {
+    fn name(&self) -> &str {
+        "schedule add"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure in the background on a cron schedule or at an interval."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("schedule add")
+            .category(Category::Experimental)
+            .input_output_types(vec![(Type::Nothing, Type::Int)])
+            .required(
+                "schedule",
+                SyntaxShape::OneOf(vec![SyntaxShape::Duration, SyntaxShape::String]),
+                "A cron expression like '0 * * * *', or the duration between runs.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::DateTime])),
+                "The closure to run, with the time of the run.",
+            )
+            .named(
+                "name",
+                SyntaxShape::String,
+                "a name for the scheduled job",
+                Some('n'),
+            )
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure runs in a background job while the shell is running, so it can be seen
+with `job list` and stopped with `schedule remove` or `job kill`. It's given the time of its run,
+and its output is discarded. The cron expressions are those of `seq cron`. Runs are never
+concurrent: if a run takes longer than the interval, the next starts when it ends.
+
+The next run, the number of runs and the results of the recent ones are shown by `schedule list`.
+A script or `nu --commands` which schedules jobs keeps running while they remain with `nu --daemon`.
+
+This command returns the job id of the scheduled job."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cron", "timer", "periodic", "every", "background"]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+
+        let schedule: Value = call.req(engine_state, stack, 0)?;
+        let closure: Closure = call.req(engine_state, stack, 1)?;
+        let name: Option<String> = call.get_flag(engine_state, stack, "name")?;
+
+        let span = schedule.span();
+        let (when, description) = match &schedule {
+            Value::Duration { val, .. } if *val > 0 => (
+                When::Every(TimeDelta::nanoseconds(*val)),
+                format!(
+                    "every {}",
+                    schedule.to_expanded_string("", &engine_state.config)
+                ),
+            ),
+            Value::Duration { .. } => return Err(ShellError::NeedsPositiveValue { span }),
+            Value::String { val, .. } => {
+                let cron = Cron::parse(val).map_err(|msg| ShellError::GenericError {
+                    error: "Invalid cron expression".into(),
+                    msg,
+                    span: Some(span),
+                    help: None,
+                    inner: vec![],
+                })?;
+                (When::Cron(cron), val.clone())
+            }
+            other => {
+                return Err(ShellError::TypeMismatch {
+                    err_message: format!(
+                        "expected a duration or a string, not {}",
+                        other.get_type()
+                    ),
+                    span,
+                });
+            }
+        };
+        let job_stack = stack.clone();
+
+        let mut job_state = engine_state.clone();
+        job_state.is_interactive = false;
+
+        // like `job spawn`, the job has its own ctrl-c and process group state
+        let job_signals = Signals::new(Arc::new(AtomicBool::new(false)));
+        job_state.set_signals(job_signals.clone());
+        job_state.pipeline_externals_state = Arc::new((AtomicU32::new(0), AtomicU32::new(0)));
+        job_state.exit_warning_given = Arc::new(AtomicBool::new(false));
+
+        let jobs = job_state.jobs.clone();
+        let mut jobs = jobs.lock().expect("jobs lock is poisoned!");
+
+        let (send, recv) = mpsc::channel();
+
+        let tag = name.clone().unwrap_or_else(|| description.clone());
+        let thread_job = ThreadJob::new(job_signals.clone(), Some(tag), send);
+        let id = jobs.add_job(Job::Thread(thread_job.clone()));
+        job_state.current_job = CurrentJob {
+            id,
+            background_thread_job: Some(thread_job),
+            mailbox: Arc::new(Mutex::new(Mailbox::new(recv))),
+        };
+
+        job_state
+            .schedules
+            .lock()
+            .expect("schedules lock is poisoned!")
+            .insert(id, Schedule::new(name, description));
+
+        let result = thread::Builder::new()
+            .name(format!("scheduled job {}", id.get()))
+            .spawn(move || {
+                let mut stack = job_stack.reset_pipes();
+                let stack = stack.push_redirection(
+                    Some(Redirection::Pipe(OutDest::Null)),
+                    Some(Redirection::Pipe(OutDest::Null)),
+                );
+                let mut closure = ClosureEval::new_preserve_out_dest(&job_state, &stack, closure);
+
+                let mut next = when.next_after(Local::now());
+                while let Some(time) = next {
+                    if let Some(schedule) = job_state
+                        .schedules
+                        .lock()
+                        .expect("schedules lock is poisoned!")
+                        .get_mut(&id)
+                    {
+                        schedule.next_run = Some(time.fixed_offset());
+                    }
+
+                    if !wait_until(time, &job_signals) {
+                        break;
+                    }
+
+                    let start = Local::now();
+                    let timer = Instant::now();
+                    let result = closure
+                        .run_with_value(Value::date(start.fixed_offset(), head))
+                        .and_then(|data| data.drain());
+                    if job_signals.interrupted() {
+                        break;
+                    }
+
+                    if let Some(schedule) = job_state
+                        .schedules
+                        .lock()
+                        .expect("schedules lock is poisoned!")
+                        .get_mut(&id)
+                    {
+                        schedule.add_run(ScheduleRun {
+                            start: start.fixed_offset(),
+                            duration: timer.elapsed(),
+                            error: result.err().map(|err| err.to_string()),
+                        });
+                    }
+
+                    // Runs which took too long skip the times which have passed
+                    next = when.next_after(time);
+                    if next.is_some_and(|next| next < Local::now()) {
+                        next = when.next_after(Local::now());
+                    }
+                }
+
+                job_state
+                    .schedules
+                    .lock()
+                    .expect("schedules lock is poisoned!")
+                    .remove(&id);
+                job_state
+                    .jobs
+                    .lock()
+                    .expect("jobs lock is poisoned!")
+                    .remove_job(id);
+            });
+
+        match result {
+            Ok(_) => Ok(Value::int(id.get() as i64, head).into_pipeline_data()),
+            Err(err) => {
+                jobs.remove_job(id);
+                engine_state
+                    .schedules
+                    .lock()
+                    .expect("schedules lock is poisoned!")
+                    .remove(&id);
+                Err(ShellError::Io(IoError::new_with_additional_context(
+                    err,
+                    call.head,
+                    None,
+                    "Failed to spawn thread for scheduled job",
+                )))
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "schedule add '0 * * * *' { http get https://example.com/status | save --append status.log }",
+                description: "Save a status every hour, on the hour",
+                result: None,
+            },
+            Example {
+                example: "schedule add 15min --name backup { |time| ^rsync -a docs/ backup/ }",
+                description: "Run a backup every 15 minutes",
+                result: None,
+            },
+            Example {
+                example: "schedule add '0 9 * * mon-fri' { notify-send 'Stand-up' }",
+                description: "Send a notification on weekdays at 9 o'clock",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// When a scheduled job runs.
+enum When {
+    Cron(Cron),
+    Every(TimeDelta),
+}
+
+impl When {
+    fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            When::Every(interval) => after.checked_add_signed(*interval),
+            // Local times which are skipped by daylight saving time are skipped too
+            When::Cron(cron) => {
+                std::iter::successors(cron.next_after(after.naive_local()), |time| {
+                    cron.next_after(*time)
+                })
+                .take(4)
+                .find_map(|time| Local.from_local_datetime(&time).earliest())
+            }
+        }
+    }
+}
+
+/// Sleep until `time`, or return `false` if the job is killed before.
+fn wait_until(time: DateTime<Local>, signals: &Signals) -> bool {
+    loop {
+        if signals.interrupted() {
+            return false;
+        }
+        match (time - Local::now()).to_std() {
+            Ok(remaining) if !remaining.is_zero() => {
+                thread::sleep(CTRL_C_CHECK_INTERVAL.min(remaining))
+            }
+            _ => return true,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct ScheduleList;
+
+impl Command for ScheduleList {
+    fn name(&self) -> &str {
+        "schedule list"
+    }
+
+    fn description(&self) -> &str {
+        "List the scheduled jobs with their recent runs."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("schedule list")
+            .category(Category::Experimental)
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The `log` of each job has its most recent runs, up to 20, with their start, duration
+and error if they failed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cron", "jobs", "status", "logs"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+
+        let schedules = engine_state
+            .schedules
+            .lock()
+            .expect("schedules lock is poisoned!");
+
+        let mut schedules: Vec<_> = schedules.iter().collect();
+        schedules.sort_by_key(|(id, _)| id.get());
+
+        let values = schedules
+            .into_iter()
+            .map(|(id, schedule)| {
+                let log = schedule
+                    .log
+                    .iter()
+                    .map(|run| {
+                        Value::record(
+                            record! {
+                                "start" => Value::date(run.start, head),
+                                "duration" => Value::duration(
+                                    i64::try_from(run.duration.as_nanos()).unwrap_or(i64::MAX),
+                                    head,
+                                ),
+                                "error" => match &run.error {
+                                    Some(error) => Value::string(error, head),
+                                    None => Value::nothing(head),
+                                },
+                            },
+                            head,
+                        )
+                    })
+                    .collect();
+                let last_status = match schedule.log.back() {
+                    Some(run) if run.error.is_some() => Value::string("error", head),
+                    Some(_) => Value::string("ok", head),
+                    None => Value::nothing(head),
+                };
+
+                Value::record(
+                    record! {
+                        "id" => Value::int(id.get() as i64, head),
+                        "name" => match &schedule.name {
+                            Some(name) => Value::string(name, head),
+                            None => Value::nothing(head),
+                        },
+                        "schedule" => Value::string(&schedule.schedule, head),
+                        "next_run" => match schedule.next_run {
+                            Some(time) => Value::date(time, head),
+                            None => Value::nothing(head),
+                        },
+                        "runs" => Value::int(schedule.run_count as i64, head),
+                        "last_status" => last_status,
+                        "log" => Value::list(log, head),
+                    },
+                    head,
+                )
+            })
+            .collect::<Vec<Value>>();
+
+        Ok(Value::list(values, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "schedule list",
+                description: "List all scheduled jobs",
+                result: None,
+            },
+            Example {
+                example: "schedule list | where last_status == error | get log | flatten",
+                description: "Show the runs of the jobs whose last run failed",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,85 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::JobId;
+
+#[derive(Clone)]
+pub struct ScheduleRemove;
+
+impl Command for ScheduleRemove {
+    fn name(&self) -> &str {
+        "schedule remove"
+    }
+
+    fn description(&self) -> &str {
+        "Stop and remove a scheduled job."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("schedule remove")
+            .category(Category::Experimental)
+            .required("id", SyntaxShape::Int, "The id of the scheduled job.")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cron", "stop", "cancel", "delete"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+
+        let id_arg: Spanned<usize> = call.req(engine_state, stack, 0)?;
+        let id = JobId::new(id_arg.item);
+
+        let removed = engine_state
+            .schedules
+            .lock()
+            .expect("schedules lock is poisoned!")
+            .remove(&id);
+        if removed.is_none() {
+            return Err(JobError::NotFound {
+                span: id_arg.span,
+                id,
+            }
+            .into());
+        }
+
+        let mut jobs = engine_state.jobs.lock().expect("jobs lock is poisoned!");
+        if jobs.lookup(id).is_some() {
+            jobs.kill_and_remove(id).map_err(|err| {
+                ShellError::Io(IoError::new_internal(
+                    err,
+                    "Failed to kill the scheduled job",
+                    nu_protocol::location!(),
+                ))
+            })?;
+        }
+
+        Ok(Value::nothing(head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "let id = schedule add 1hr { ^make report }; schedule remove $id",
+                description: "Remove a newly scheduled job",
+                result: None,
+            },
+            Example {
+                example: "schedule list | where name == backup | each { schedule remove $in.id }",
+                description: "Remove the scheduled jobs named backup",
+                result: None,
+            },
+        ]
+    }
+}
@@ -29,6 +29,10 @@ impl Command for Cd {
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -70,6 +70,10 @@ impl Command for Du {
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -0,0 +1,244 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{NuGlob, PathValue, shell_error::io::IoError};
+use std::{
+    fs::Permissions,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone)]
+pub struct FsChmod;
+
+impl Command for FsChmod {
+    fn name(&self) -> &str {
+        "fs chmod"
+    }
+
+    fn description(&self) -> &str {
+        "Change the permissions of files and directories."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The mode is either octal, like `755`, or symbolic, like `u+x,go-w`.
+
+The files can be given as arguments, or piped in as a list of paths or a table with a `name`
+column, like the output of `ls`. The new mode of every file is returned."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["permissions", "mode", "coreutils"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fs chmod")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::List(Box::new(Type::String)), Type::table()),
+                (Type::table(), Type::table()),
+            ])
+            .required("mode", SyntaxShape::String, "The mode to set.")
+            .rest(
+                "paths",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::String]),
+                "The files to change.",
+            )
+            .switch(
+                "recursive",
+                "Also change the contents of directories",
+                Some('R'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mode: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let recursive = call.has_flag(engine_state, stack, "recursive")?;
+        let targets = targets(engine_state, stack, call, input, 1)?;
+
+        let mut rows = vec![];
+        for target in targets {
+            walk(
+                &target.item,
+                recursive,
+                target.span,
+                &mut |path, nested_link| {
+                    // There's no way to change the mode of a symlink itself, and following it could
+                    // change files outside of the directory
+                    if nested_link {
+                        return Ok(());
+                    }
+                    let metadata = std::fs::metadata(path)
+                        .map_err(|err| IoError::new(err, target.span, path.to_path_buf()))?;
+                    let new_mode =
+                        parse_mode(&mode.item, metadata.permissions().mode(), metadata.is_dir())
+                            .map_err(|msg| ShellError::IncorrectValue {
+                                msg,
+                                val_span: mode.span,
+                                call_span: head,
+                            })?;
+                    std::fs::set_permissions(path, Permissions::from_mode(new_mode))
+                        .map_err(|err| IoError::new(err, target.span, path.to_path_buf()))?;
+                    rows.push(Value::record(
+                        record! {
+                            "name" => Value::string(path.to_string_lossy(), head),
+                            "mode" => Value::string(umask::Mode::from(new_mode).to_string(), head),
+                        },
+                        head,
+                    ));
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Make a script executable",
+                example: "fs chmod +x build.sh",
+                result: None,
+            },
+            Example {
+                description: "Remove write permissions from everyone but the owner, for all TOML files",
+                example: "ls *.toml | fs chmod go-w",
+                result: None,
+            },
+            Example {
+                description: "Set the permissions of a directory and everything in it",
+                example: "fs chmod -R 750 private",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Apply an octal or symbolic `mode` to the permissions `current`.
+fn parse_mode(mode: &str, current: u32, is_dir: bool) -> Result<u32, String> {
+    if mode.starts_with(|c: char| c.is_ascii_digit()) {
+        uucore::mode::parse_numeric(current, mode, is_dir)
+    } else {
+        mode.split(',').try_fold(current, |perm, clause| {
+            uucore::mode::parse_symbolic(perm, clause, uucore::mode::get_umask(), is_dir)
+        })
+    }
+}
+
+/// Call `f` on `path`, and on everything in it if `recursive` is set.
+///
+/// Symlinks aren't followed into directories. `f` also gets whether the path is a symlink found
+/// in one of the directories, which commands shouldn't follow either, unlike the symlinks they were
+/// given.
+pub(super) fn walk(
+    path: &Path,
+    recursive: bool,
+    span: Span,
+    f: &mut dyn FnMut(&Path, bool) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    f(path, false)?;
+    let is_dir = path
+        .symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir());
+    if recursive && is_dir {
+        walk_dir(path, span, f)?;
+    }
+    Ok(())
+}
+
+fn walk_dir(
+    dir: &Path,
+    span: Span,
+    f: &mut dyn FnMut(&Path, bool) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| IoError::new(err, span, dir.to_path_buf()))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| IoError::new(err, span, dir.to_path_buf()))?;
+        let file_type = entry
+            .file_type()
+            .map_err(|err| IoError::new(err, span, entry.path()))?;
+        let path = entry.path();
+        f(&path, file_type.is_symlink())?;
+        if file_type.is_dir() {
+            walk_dir(&path, span, f)?;
+        }
+    }
+    Ok(())
+}
+
+/// The files a command should change: the paths in its rest arguments starting at `index`, with
+/// globs expanded, followed by the paths piped in.
+///
+/// The input can be paths, or records with a `name` column like the output of `ls`.
+pub(super) fn targets(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+    index: usize,
+) -> Result<Vec<Spanned<PathBuf>>, ShellError> {
+    let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+    let mut targets = vec![];
+
+    for pattern in call.rest::<Spanned<NuGlob>>(engine_state, stack, index)? {
+        let (_, paths) = nu_engine::glob_from(
+            &pattern,
+            &cwd,
+            call.head,
+            None,
+            engine_state.signals().clone(),
+        )?;
+        for path in paths {
+            targets.push(path?.into_spanned(pattern.span));
+        }
+    }
+
+    for value in input {
+        let span = value.span();
+        let path = match &value {
+            Value::Record { val, .. } => {
+                val.get("name")
+                    .and_then(path_of)
+                    .ok_or_else(|| ShellError::CantFindColumn {
+                        col_name: "name".into(),
+                        span: Some(call.head),
+                        src_span: span,
+                    })?
+            }
+            Value::Error { error, .. } => return Err(*error.clone()),
+            other => path_of(other).ok_or_else(|| ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string, path or record with a name".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: call.head,
+                src_span: span,
+            })?,
+        };
+        targets.push(nu_path::expand_path_with(path, &cwd, true).into_spanned(span));
+    }
+
+    if targets.is_empty() {
+        return Err(ShellError::MissingParameter {
+            param_name: "paths".into(),
+            span: call.head,
+        });
+    }
+    Ok(targets)
+}
+
+fn path_of(value: &Value) -> Option<PathBuf> {
+    match value {
+        Value::String { val, .. } => Some(PathBuf::from(val)),
+        other => PathValue::try_from_value(other).map(Path::to_path_buf),
+    }
+}
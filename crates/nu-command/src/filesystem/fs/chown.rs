@@ -0,0 +1,192 @@
+use super::chmod::{targets, walk};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use nu_utils::filesystem::users;
+use std::os::unix::fs::MetadataExt;
+
+#[derive(Clone)]
+pub struct FsChown;
+
+impl Command for FsChown {
+    fn name(&self) -> &str {
+        "fs chown"
+    }
+
+    fn description(&self) -> &str {
+        "Change the owner and group of files and directories."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The owner is given as `user`, `user:group` or `:group`, by name or by id.
+
+The files can be given as arguments, or piped in as a list of paths or a table with a `name`
+column, like the output of `ls`. The new owner and group of every file are returned."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["owner", "group", "chgrp", "coreutils"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fs chown")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::List(Box::new(Type::String)), Type::table()),
+                (Type::table(), Type::table()),
+            ])
+            .required(
+                "owner",
+                SyntaxShape::String,
+                "The new owner, as `user`, `user:group` or `:group`.",
+            )
+            .rest(
+                "paths",
+                SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::String]),
+                "The files to change.",
+            )
+            .switch(
+                "recursive",
+                "Also change the contents of directories",
+                Some('R'),
+            )
+            .switch(
+                "no-dereference",
+                "Change symbolic links instead of the files they refer to",
+                None,
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let owner: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let recursive = call.has_flag(engine_state, stack, "recursive")?;
+        let no_dereference = call.has_flag(engine_state, stack, "no-dereference")?;
+        let (uid, gid) = parse_owner(&owner)?;
+        let targets = targets(engine_state, stack, call, input, 1)?;
+
+        let mut rows = vec![];
+        for target in targets {
+            walk(
+                &target.item,
+                recursive,
+                target.span,
+                &mut |path, nested_link| {
+                    // Symlinks in directories are changed themselves, so files outside of the
+                    // directory aren't changed
+                    let no_dereference = no_dereference || nested_link;
+                    let result = if no_dereference {
+                        std::os::unix::fs::lchown(path, uid, gid)
+                    } else {
+                        std::os::unix::fs::chown(path, uid, gid)
+                    };
+                    result.map_err(|err| IoError::new(err, target.span, path.to_path_buf()))?;
+
+                    let metadata = if no_dereference {
+                        path.symlink_metadata()
+                    } else {
+                        path.metadata()
+                    }
+                    .map_err(|err| IoError::new(err, target.span, path.to_path_buf()))?;
+                    rows.push(Value::record(
+                        record! {
+                            "name" => Value::string(path.to_string_lossy(), head),
+                            "user" => user_value(metadata.uid(), head),
+                            "group" => group_value(metadata.gid(), head),
+                        },
+                        head,
+                    ));
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Give a file to another user",
+                example: "fs chown alice notes.txt",
+                result: None,
+            },
+            Example {
+                description: "Change the group of every file in a directory",
+                example: "fs chown -R :www public",
+                result: None,
+            },
+            Example {
+                description: "Change the owner of the files some user owns",
+                example: "ls -l | where user == bob | fs chown alice:staff",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Parse `user`, `user:group` or `:group` into ids.
+fn parse_owner(owner: &Spanned<String>) -> Result<(Option<u32>, Option<u32>), ShellError> {
+    let (user, group) = match owner.item.split_once(':') {
+        Some((user, group)) => (user, Some(group)),
+        None => (owner.item.as_str(), None),
+    };
+    let unknown = |kind: &str, name: &str| ShellError::IncorrectValue {
+        msg: format!("no {kind} named `{name}`"),
+        val_span: owner.span,
+        call_span: owner.span,
+    };
+
+    let uid = match user {
+        "" => None,
+        user => Some(match user.parse() {
+            Ok(uid) => uid,
+            Err(_) => users::get_user_by_name(user)
+                .ok_or_else(|| unknown("user", user))?
+                .uid
+                .as_raw(),
+        }),
+    };
+    let gid = match group {
+        None | Some("") => None,
+        Some(group) => Some(match group.parse() {
+            Ok(gid) => gid,
+            Err(_) => users::get_group_by_name(group)
+                .ok_or_else(|| unknown("group", group))?
+                .gid
+                .as_raw(),
+        }),
+    };
+
+    if uid.is_none() && gid.is_none() {
+        return Err(ShellError::IncorrectValue {
+            msg: "expected a user, a group, or both".into(),
+            val_span: owner.span,
+            call_span: owner.span,
+        });
+    }
+    Ok((uid, gid))
+}
+
+fn user_value(uid: u32, span: Span) -> Value {
+    match users::get_user_by_uid(uid.into()) {
+        Some(user) => Value::string(user.name, span),
+        None => Value::int(uid.into(), span),
+    }
+}
+
+fn group_value(gid: u32, span: Span) -> Value {
+    match users::get_group_by_gid(gid.into()) {
+        Some(group) => Value::string(group.name, span),
+        None => Value::int(gid.into(), span),
+    }
+}
@@ -0,0 +1,40 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Fs;
+
+impl Command for Fs {
+    fn name(&self) -> &str {
+        "fs"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fs")
+            .category(Category::FileSystem)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Commands to change the attributes of files."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"You must use one of the following subcommands. Using this command as-is will only produce this help message.
+
+They're named like the coreutils, which can still be run as externals like `^chmod`."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,7 @@
+mod chmod;
+mod chown;
+mod fs_;
+
+pub use chmod::FsChmod;
+pub use chown::FsChown;
+pub use fs_::Fs;
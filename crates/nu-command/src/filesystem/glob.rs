@@ -1,5 +1,6 @@
 use nu_engine::command_prelude::*;
-use nu_protocol::{ListStream, Signals};
+use nu_protocol::{ListStream, PathValue, Signals};
+use std::collections::HashSet;
 use wax::{Glob as WaxGlob, WalkBehavior, WalkEntry};
 
 #[derive(Clone)]
@@ -12,8 +13,9 @@ impl Command for Glob {
 
     fn signature(&self) -> Signature {
         Signature::build("glob")
-            .input_output_types(vec![(Type::Nothing, Type::List(Box::new(Type::String)))])
-            .required("glob", SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::GlobPattern]), "The glob expression.")
+            // Names which aren't valid UTF-8 are path values
+            .input_output_types(vec![(Type::Nothing, Type::list(Type::Any))])
+            .required("glob", SyntaxShape::OneOf(vec![SyntaxShape::List(Box::new(SyntaxShape::String)), SyntaxShape::String, SyntaxShape::GlobPattern]), "The glob expression, or a list of them.")
             .named(
                 "depth",
                 SyntaxShape::Int,
@@ -46,9 +48,18 @@ impl Command for Glob {
                 "Patterns to exclude from the search: `glob` will not walk the inside of directories matching the excluded patterns.",
                 Some('e'),
             )
+            .switch(
+                "gitignore",
+                "Skip hidden files and files ignored by .gitignore or .ignore files",
+                Some('g'),
+            )
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Creates a list of files and/or folders based on the glob pattern provided."
     }
@@ -119,11 +130,22 @@ impl Command for Glob {
                 example: r#"glob "**/*.txt" --follow-symlinks"#,
                 result: None,
             },
+            Example {
+                description: "Search for Rust and TOML files, leaving out the ones in target directories",
+                example: r#"glob ["**/*.rs" "**/*.toml" "!**/target/**"]"#,
+                result: None,
+            },
+            Example {
+                description: "Search for files like ripgrep would, skipping hidden and git-ignored files",
+                example: r#"glob "**/*" --gitignore"#,
+                result: None,
+            },
         ]
     }
 
     fn extra_description(&self) -> &str {
-        r#"For more glob pattern help, please refer to https://docs.rs/crate/wax/latest"#
+        r#"When given a list of patterns, the paths matching any of them are returned, except for the paths matching a pattern starting with `!`.
+For more glob pattern help, please refer to https://docs.rs/crate/wax/latest"#
     }
 
     fn run(
@@ -141,9 +163,10 @@ impl Command for Glob {
         let no_files = call.has_flag(engine_state, stack, "no-file")?;
         let no_symlinks = call.has_flag(engine_state, stack, "no-symlink")?;
         let follow_symlinks = call.has_flag(engine_state, stack, "follow-symlinks")?;
+        let respect_gitignore = call.has_flag(engine_state, stack, "gitignore")?;
         let paths_to_exclude: Option<Value> = call.get_flag(engine_state, stack, "exclude")?;
 
-        let (not_patterns, not_pattern_span): (Vec<String>, Span) = match paths_to_exclude {
+        let (mut not_patterns, not_pattern_span): (Vec<String>, Span) = match paths_to_exclude {
             None => (vec![], span),
             Some(f) => {
                 let pat_span = f.span();
@@ -157,22 +180,30 @@ impl Command for Glob {
             }
         };
 
-        let glob_pattern =
-            match glob_pattern_input {
-                Value::String { val, .. } | Value::Glob { val, .. } => val,
-                _ => return Err(ShellError::IncorrectValue {
-                    msg: "Incorrect glob pattern supplied to glob. Please use string or glob only."
+        let glob_patterns = match glob_pattern_input {
+            Value::String { val, .. } | Value::Glob { val, .. } => vec![val],
+            // in a list of patterns, the ones starting with `!` exclude paths
+            Value::List { vals, .. } => {
+                let mut glob_patterns = vec![];
+                for pattern in convert_patterns(&vals)? {
+                    match pattern.strip_prefix('!') {
+                        Some(negated) => not_patterns.push(negated.to_string()),
+                        None => glob_patterns.push(pattern),
+                    }
+                }
+                glob_patterns
+            }
+            _ => {
+                return Err(ShellError::IncorrectValue {
+                    msg: "Incorrect glob pattern supplied to glob. Please use string, glob or list of strings only."
                         .to_string(),
                     val_span: call.head,
                     call_span: glob_span,
-                }),
-            };
-
-        // paths starting with drive letters must be escaped on Windows
-        #[cfg(windows)]
-        let glob_pattern = patch_windows_glob_pattern(glob_pattern, glob_span)?;
+                });
+            }
+        };
 
-        if glob_pattern.is_empty() {
+        if glob_patterns.is_empty() || glob_patterns.iter().any(|p| p.is_empty()) {
             return Err(ShellError::GenericError {
                 error: "glob pattern must not be empty".into(),
                 msg: "glob pattern is empty".into(),
@@ -182,103 +213,114 @@ impl Command for Glob {
             });
         }
 
-        // below we have to check / instead of MAIN_SEPARATOR because glob uses / as separator
-        // using a glob like **\*.rs should fail because it's not a valid glob pattern
-        let folder_depth = if let Some(depth) = depth {
-            depth
-        } else if glob_pattern.contains("**") {
-            usize::MAX
-        } else if glob_pattern.contains('/') {
-            glob_pattern.split('/').count() + 1
-        } else {
-            1
-        };
-
-        let (prefix, glob) = match WaxGlob::new(&glob_pattern) {
-            Ok(p) => p.partition(),
-            Err(e) => {
-                return Err(ShellError::GenericError {
-                    error: "error with glob pattern".into(),
-                    msg: format!("{e}"),
-                    span: Some(glob_span),
-                    help: None,
-                    inner: vec![],
-                });
-            }
-        };
-
-        let path = engine_state.cwd_as_string(Some(stack))?;
-        let path = match nu_path::canonicalize_with(prefix, path) {
-            Ok(path) => path,
-            Err(e) if e.to_string().contains("os error 2") =>
-            // path we're trying to glob doesn't exist,
-            {
-                std::path::PathBuf::new() // user should get empty list not an error
-            }
-            Err(e) => {
-                return Err(ShellError::GenericError {
-                    error: "error in canonicalize".into(),
-                    msg: format!("{e}"),
-                    span: Some(glob_span),
-                    help: None,
-                    inner: vec![],
-                });
-            }
-        };
-
+        let cwd = engine_state.cwd_as_string(Some(stack))?;
         let link_behavior = match follow_symlinks {
             true => wax::LinkBehavior::ReadTarget,
             false => wax::LinkBehavior::ReadFile,
         };
+        let np: Vec<&str> = not_patterns.iter().map(|s| s as &str).collect();
+        let multiple_patterns = glob_patterns.len() > 1;
+
+        let mut walks = vec![];
+        for glob_pattern in glob_patterns {
+            // paths starting with drive letters must be escaped on Windows
+            #[cfg(windows)]
+            let glob_pattern = patch_windows_glob_pattern(glob_pattern, glob_span)?;
+
+            // below we have to check / instead of MAIN_SEPARATOR because glob uses / as separator
+            // using a glob like **\*.rs should fail because it's not a valid glob pattern
+            let folder_depth = if let Some(depth) = depth {
+                depth
+            } else if glob_pattern.contains("**") {
+                usize::MAX
+            } else if glob_pattern.contains('/') {
+                glob_pattern.split('/').count() + 1
+            } else {
+                1
+            };
+
+            let (prefix, glob) = match WaxGlob::new(&glob_pattern) {
+                Ok(p) => p.partition(),
+                Err(e) => {
+                    return Err(ShellError::GenericError {
+                        error: "error with glob pattern".into(),
+                        msg: format!("{e}"),
+                        span: Some(glob_span),
+                        help: None,
+                        inner: vec![],
+                    });
+                }
+            };
 
-        let result = if !not_patterns.is_empty() {
-            let np: Vec<&str> = not_patterns.iter().map(|s| s as &str).collect();
-            let glob_results = glob
+            let path = match nu_path::canonicalize_with(prefix, &cwd) {
+                Ok(path) => path,
+                Err(e) if e.to_string().contains("os error 2") =>
+                // path we're trying to glob doesn't exist,
+                {
+                    std::path::PathBuf::new() // user should get empty list not an error
+                }
+                Err(e) => {
+                    return Err(ShellError::GenericError {
+                        error: "error in canonicalize".into(),
+                        msg: format!("{e}"),
+                        span: Some(glob_span),
+                        help: None,
+                        inner: vec![],
+                    });
+                }
+            };
+
+            let walk = glob
                 .walk_with_behavior(
-                    path,
+                    &path,
                     WalkBehavior {
                         depth: folder_depth,
                         link: link_behavior,
                     },
                 )
-                .into_owned()
-                .not(np)
-                .map_err(|err| ShellError::GenericError {
-                    error: "error with glob's not pattern".into(),
-                    msg: format!("{err}"),
-                    span: Some(not_pattern_span),
-                    help: None,
-                    inner: vec![],
-                })?
-                .flatten();
-            glob_to_value(
-                engine_state.signals(),
-                glob_results,
-                no_dirs,
-                no_files,
-                no_symlinks,
-                span,
-            )
-        } else {
-            let glob_results = glob
-                .walk_with_behavior(
-                    path,
-                    WalkBehavior {
-                        depth: folder_depth,
-                        link: link_behavior,
-                    },
+                .into_owned();
+
+            let entries: Box<dyn Iterator<Item = WalkEntry<'static>> + Send> = if !np.is_empty() {
+                Box::new(
+                    walk.not(np.clone())
+                        .map_err(|err| ShellError::GenericError {
+                            error: "error with glob's not pattern".into(),
+                            msg: format!("{err}"),
+                            span: Some(not_pattern_span),
+                            help: None,
+                            inner: vec![],
+                        })?
+                        .flatten(),
                 )
-                .into_owned()
-                .flatten();
-            glob_to_value(
-                engine_state.signals(),
-                glob_results,
-                no_dirs,
-                no_files,
-                no_symlinks,
-                span,
-            )
-        };
+            } else {
+                Box::new(walk.flatten())
+            };
+
+            let entries: Box<dyn Iterator<Item = WalkEntry<'static>> + Send> = if respect_gitignore
+            {
+                let mut ignore = nu_glob::Ignore::new(&cwd).below(&path);
+                Box::new(entries.filter(move |entry| !ignore.is_ignored_below(&path, entry.path())))
+            } else {
+                entries
+            };
+
+            walks.push(entries);
+        }
+
+        let mut seen = HashSet::new();
+        let glob_results = walks.into_iter().flatten().filter(move |entry| {
+            // several patterns may match the same path
+            !multiple_patterns || seen.insert(entry.path().to_path_buf())
+        });
+
+        let result = glob_to_value(
+            engine_state.signals(),
+            glob_results,
+            no_dirs,
+            no_files,
+            no_symlinks,
+            span,
+        );
 
         Ok(result.into_pipeline_data(span, engine_state.signals().clone()))
     }
@@ -338,10 +380,7 @@ fn glob_to_value(
             || no_files && file_type.is_file()
             || no_symlinks && file_type.is_symlink())
         {
-            Some(Value::string(
-                entry.into_path().to_string_lossy().to_string(),
-                span,
-            ))
+            Some(PathValue::string_or_path(entry.into_path(), span))
         } else {
             None
         }
@@ -6,7 +6,7 @@ use nu_engine::{command_prelude::*, env::current_dir};
 use nu_glob::MatchOptions;
 use nu_path::{expand_path_with, expand_to_real_path};
 use nu_protocol::{
-    DataSource, NuGlob, PipelineMetadata, Signals,
+    DataSource, NuGlob, PathValue, PipelineMetadata, Signals,
     shell_error::{self, io::IoError},
 };
 use pathdiff::diff_paths;
@@ -26,16 +26,90 @@ pub struct Ls;
 #[derive(Clone, Copy)]
 struct Args {
     all: bool,
-    long: bool,
+    columns: Columns,
     short_names: bool,
     full_paths: bool,
     du: bool,
     directory: bool,
     use_mime_type: bool,
     use_threads: bool,
+    unsorted: bool,
     call_span: Span,
 }
 
+/// All columns `ls` can output, in the order they're output in.
+const COLUMNS: &[&str] = &[
+    "name",
+    "type",
+    "target",
+    "readonly",
+    "mode",
+    "num_links",
+    "inode",
+    "user",
+    "group",
+    "xattrs",
+    "acl",
+    "size",
+    "created",
+    "accessed",
+    "modified",
+];
+
+/// The columns shown without `--long`.
+const SHORT_COLUMNS: &[&str] = &["name", "type", "size", "modified"];
+
+/// The set of columns to output, as a bitset over [`COLUMNS`].
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct Columns(u16);
+
+impl Columns {
+    fn new(long: bool) -> Self {
+        if long {
+            Self((1 << COLUMNS.len()) - 1)
+        } else {
+            Self::from_names(SHORT_COLUMNS.iter().copied())
+        }
+    }
+
+    fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let bits = names
+            .into_iter()
+            .filter_map(|name| COLUMNS.iter().position(|column| *column == name))
+            .fold(0, |bits, index| bits | 1 << index);
+        Self(bits)
+    }
+
+    fn from_fields(fields: &[Spanned<String>]) -> Result<Self, ShellError> {
+        if let Some(field) = fields
+            .iter()
+            .find(|field| !COLUMNS.contains(&field.item.as_str()))
+        {
+            return Err(ShellError::InvalidValue {
+                valid: format!("one of {}", COLUMNS.join(", ")),
+                actual: field.item.clone(),
+                span: field.span,
+            });
+        }
+        // the name is always needed to identify the row
+        Ok(Self::from_names(
+            std::iter::once("name").chain(fields.iter().map(|field| field.item.as_str())),
+        ))
+    }
+
+    fn has(self, column: &str) -> bool {
+        COLUMNS
+            .iter()
+            .position(|c| *c == column)
+            .is_some_and(|index| self.0 & (1 << index) != 0)
+    }
+
+    /// Whether any of the columns needs the entry to be stat'ed.
+    fn needs_metadata(self) -> bool {
+        self != Self::from_names(["name"])
+    }
+}
+
 impl Command for Ls {
     fn name(&self) -> &str {
         "ls"
@@ -51,7 +125,11 @@ impl Command for Ls {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("ls")
-            .input_output_types(vec![(Type::Nothing, Type::table())])
+            // The name is a path value if it isn't valid UTF-8, and the other columns can be chosen
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table([("name".into(), Type::Any)].into()),
+            )])
             // LsGlobPattern is similar to string, it won't auto-expand
             // and we use it to track if the user input is quoted.
             .rest("pattern", SyntaxShape::OneOf(vec![SyntaxShape::GlobPattern, SyntaxShape::String]), "The glob pattern to use.")
@@ -79,9 +157,20 @@ impl Command for Ls {
             )
             .switch("mime-type", "Show mime-type in type column instead of 'file' (based on filenames only; files' contents are not examined)", Some('m'))
             .switch("threads", "Use multiple threads to list contents. Output will be non-deterministic.", Some('t'))
+            .switch("unsorted", "Stream entries in the order they are read instead of sorting them (faster for large directories)", Some('u'))
+            .named(
+                "fields",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Only output these columns, skipping the metadata lookups needed for the others",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -97,19 +186,27 @@ impl Command for Ls {
         let directory = call.has_flag(engine_state, stack, "directory")?;
         let use_mime_type = call.has_flag(engine_state, stack, "mime-type")?;
         let use_threads = call.has_flag(engine_state, stack, "threads")?;
+        let unsorted = call.has_flag(engine_state, stack, "unsorted")?;
+        let fields: Option<Vec<Spanned<String>>> = call.get_flag(engine_state, stack, "fields")?;
         let call_span = call.head;
         #[allow(deprecated)]
         let cwd = current_dir(engine_state, stack)?;
 
+        let columns = match fields {
+            Some(fields) => Columns::from_fields(&fields)?,
+            None => Columns::new(long),
+        };
+
         let args = Args {
             all,
-            long,
+            columns,
             short_names,
             full_paths,
             du,
             directory,
             use_mime_type,
             use_threads,
+            unsorted,
             call_span,
         };
 
@@ -206,6 +303,16 @@ impl Command for Ls {
                 example: "ls ...(glob **/*.{rs,toml})",
                 result: None,
             },
+            Example {
+                description: "List only the names and sizes of the files in a large directory, as soon as they're read",
+                example: "ls --unsorted --fields [size]",
+                result: None,
+            },
+            Example {
+                description: "List the directories in the current directory with their disk usage",
+                example: "ls --du --fields [type size] | where type == dir",
+                result: None,
+            },
             Example {
                 description: "List given paths and show directories themselves",
                 example: "['/path/to/directory' '/path/to/file'] | each {|| ls -D $in } | flatten",
@@ -237,19 +344,19 @@ fn ls_for_one_pattern(
         }
     }
 
-    let (tx, rx) = mpsc::channel();
-
     let Args {
         all,
-        long,
+        columns,
         short_names,
         full_paths,
         du,
         directory,
         use_mime_type,
         use_threads,
+        unsorted,
         call_span,
     } = args;
+    let sort = !use_threads && !unsorted;
     let pattern_arg = {
         if let Some(path) = pattern_arg {
             // it makes no sense to list an empty string.
@@ -277,6 +384,7 @@ fn ls_for_one_pattern(
     };
 
     let mut just_read_dir = false;
+    let pattern_given = pattern_arg.is_some();
     let p_tag: Span = pattern_arg.as_ref().map(|p| p.span).unwrap_or(call_span);
     let (pattern_arg, absolute_path) = match pattern_arg {
         Some(pat) => {
@@ -285,10 +393,7 @@ fn ls_for_one_pattern(
                 nu_path::expand_path_with(pat.item.as_ref(), &cwd, pat.item.is_expand());
             // Avoid checking and pushing "*" to the path when directory (do not show contents) flag is true
             if !directory && tmp_expanded.is_dir() {
-                if read_dir(tmp_expanded, p_tag, use_threads, signals.clone())?
-                    .next()
-                    .is_none()
-                {
+                if is_empty_dir(&tmp_expanded, p_tag)? {
                     return Ok(Value::test_nothing().into_pipeline_data());
                 }
                 just_read_dir = !(pat.item.is_expand() && nu_glob::is_glob(pat.item.as_ref()));
@@ -307,23 +412,25 @@ fn ls_for_one_pattern(
             // Avoid pushing "*" to the default path when directory (do not show contents) flag is true
             if directory {
                 (NuGlob::Expand(".".to_string()), false)
-            } else if read_dir(cwd.clone(), p_tag, use_threads, signals.clone())?
-                .next()
-                .is_none()
-            {
+            } else if is_empty_dir(&cwd, p_tag)? {
                 return Ok(Value::test_nothing().into_pipeline_data());
             } else {
-                (NuGlob::Expand("*".to_string()), false)
+                // reading the directory directly allows to stream its entries, unlike globbing `*`
+                just_read_dir = true;
+                (
+                    NuGlob::DoNotExpand(cwd.to_string_lossy().to_string()),
+                    false,
+                )
             }
         }
     };
 
-    let hidden_dir_specified = is_hidden_dir(pattern_arg.as_ref());
+    let hidden_dir_specified = pattern_given && is_hidden_dir(pattern_arg.as_ref());
 
     let path = pattern_arg.into_spanned(p_tag);
     let (prefix, paths) = if just_read_dir {
         let expanded = nu_path::expand_path_with(path.item.as_ref(), &cwd, path.item.is_expand());
-        let paths = read_dir(expanded.clone(), p_tag, use_threads, signals.clone())?;
+        let paths = read_dir(expanded.clone(), p_tag, sort, signals.clone())?;
         // just need to read the directory, so prefix is path itself.
         (Some(expanded), paths)
     } else {
@@ -356,113 +463,121 @@ fn ls_for_one_pattern(
 
     let signals_clone = signals.clone();
 
-    let pool = if use_threads {
-        let count = std::thread::available_parallelism()
-            .map_err(|err| {
-                IoError::new_with_additional_context(
-                    err,
-                    call_span,
-                    None,
-                    "Could not get available parallelism",
-                )
-            })?
-            .get();
-        create_pool(count)?
-    } else {
-        create_pool(1)?
+    let to_entry = move |x: Result<PathBuf, ShellError>| match x {
+        Ok(path) => {
+            let metadata = if columns.needs_metadata() {
+                std::fs::symlink_metadata(&path).ok()
+            } else {
+                None
+            };
+            let hidden_dir_clone = Arc::clone(&hidden_dirs);
+            let mut hidden_dir_mutex = hidden_dir_clone
+                .lock()
+                .expect("Unable to acquire lock for hidden_dirs");
+            if path_contains_hidden_folder(&path, &hidden_dir_mutex) {
+                return None;
+            }
+
+            if !all && !hidden_dir_specified && is_hidden_dir(&path) {
+                if path.is_dir() {
+                    hidden_dir_mutex.push(path);
+                    drop(hidden_dir_mutex);
+                }
+                return None;
+            }
+
+            let display_name = if short_names {
+                path.file_name().map(PathBuf::from)
+            } else if full_paths || absolute_path {
+                Some(path.clone())
+            } else if let Some(prefix) = &prefix {
+                if let Ok(remainder) = path.strip_prefix(prefix) {
+                    if directory {
+                        // When the path is the same as the cwd, path_diff should be "."
+                        let path_diff = if let Some(path_diff_not_dot) = diff_paths(&path, &cwd) {
+                            if path_diff_not_dot.as_os_str().is_empty() {
+                                PathBuf::from(".")
+                            } else {
+                                path_diff_not_dot
+                            }
+                        } else {
+                            path.clone()
+                        };
+
+                        Some(path_diff)
+                    } else {
+                        let new_prefix = if let Some(pfx) = diff_paths(prefix, &cwd) {
+                            pfx
+                        } else {
+                            prefix.to_path_buf()
+                        };
+
+                        Some(new_prefix.join(remainder))
+                    }
+                } else {
+                    Some(path.clone())
+                }
+            } else {
+                Some(path.clone())
+            }
+            .ok_or_else(|| ShellError::GenericError {
+                error: format!("Invalid file name: {:}", path.to_string_lossy()),
+                msg: "invalid file name".into(),
+                span: Some(call_span),
+                help: None,
+                inner: vec![],
+            });
+
+            match display_name {
+                Ok(name) => {
+                    let entry = dir_entry_dict(
+                        &path,
+                        &name,
+                        metadata.as_ref(),
+                        call_span,
+                        columns,
+                        du,
+                        &signals_clone,
+                        use_mime_type,
+                        args.full_paths,
+                    );
+                    match entry {
+                        Ok(value) => Some(value),
+                        Err(err) => Some(Value::error(err, call_span)),
+                    }
+                }
+                Err(err) => Some(Value::error(err, call_span)),
+            }
+        }
+        Err(err) => Some(Value::error(err, call_span)),
     };
 
+    if !use_threads {
+        // Build each row only when it is pulled from the stream, so metadata (and `--du`)
+        // is only looked up for the entries that are actually consumed.
+        return Ok(paths_peek
+            .filter_map(to_entry)
+            .into_pipeline_data(call_span, signals));
+    }
+
+    let count = std::thread::available_parallelism()
+        .map_err(|err| {
+            IoError::new_with_additional_context(
+                err,
+                call_span,
+                None,
+                "Could not get available parallelism",
+            )
+        })?
+        .get();
+    let pool = create_pool(count)?;
+    let (tx, rx) = mpsc::channel();
+
     pool.install(|| {
         rayon::spawn(move || {
             let result = paths_peek
                 .par_bridge()
-                .filter_map(move |x| match x {
-                    Ok(path) => {
-                        let metadata = std::fs::symlink_metadata(&path).ok();
-                        let hidden_dir_clone = Arc::clone(&hidden_dirs);
-                        let mut hidden_dir_mutex = hidden_dir_clone
-                            .lock()
-                            .expect("Unable to acquire lock for hidden_dirs");
-                        if path_contains_hidden_folder(&path, &hidden_dir_mutex) {
-                            return None;
-                        }
-
-                        if !all && !hidden_dir_specified && is_hidden_dir(&path) {
-                            if path.is_dir() {
-                                hidden_dir_mutex.push(path);
-                                drop(hidden_dir_mutex);
-                            }
-                            return None;
-                        }
-
-                        let display_name = if short_names {
-                            path.file_name().map(|os| os.to_string_lossy().to_string())
-                        } else if full_paths || absolute_path {
-                            Some(path.to_string_lossy().to_string())
-                        } else if let Some(prefix) = &prefix {
-                            if let Ok(remainder) = path.strip_prefix(prefix) {
-                                if directory {
-                                    // When the path is the same as the cwd, path_diff should be "."
-                                    let path_diff = if let Some(path_diff_not_dot) =
-                                        diff_paths(&path, &cwd)
-                                    {
-                                        let path_diff_not_dot = path_diff_not_dot.to_string_lossy();
-                                        if path_diff_not_dot.is_empty() {
-                                            ".".to_string()
-                                        } else {
-                                            path_diff_not_dot.to_string()
-                                        }
-                                    } else {
-                                        path.to_string_lossy().to_string()
-                                    };
-
-                                    Some(path_diff)
-                                } else {
-                                    let new_prefix = if let Some(pfx) = diff_paths(prefix, &cwd) {
-                                        pfx
-                                    } else {
-                                        prefix.to_path_buf()
-                                    };
-
-                                    Some(new_prefix.join(remainder).to_string_lossy().to_string())
-                                }
-                            } else {
-                                Some(path.to_string_lossy().to_string())
-                            }
-                        } else {
-                            Some(path.to_string_lossy().to_string())
-                        }
-                        .ok_or_else(|| ShellError::GenericError {
-                            error: format!("Invalid file name: {:}", path.to_string_lossy()),
-                            msg: "invalid file name".into(),
-                            span: Some(call_span),
-                            help: None,
-                            inner: vec![],
-                        });
-
-                        match display_name {
-                            Ok(name) => {
-                                let entry = dir_entry_dict(
-                                    &path,
-                                    &name,
-                                    metadata.as_ref(),
-                                    call_span,
-                                    long,
-                                    du,
-                                    &signals_clone,
-                                    use_mime_type,
-                                    args.full_paths,
-                                );
-                                match entry {
-                                    Ok(value) => Some(value),
-                                    Err(err) => Some(Value::error(err, call_span)),
-                                }
-                            }
-                            Err(err) => Some(Value::error(err, call_span)),
-                        }
-                    }
-                    Err(err) => Some(Value::error(err, call_span)),
-                })
+                .filter_map(to_entry)
                 .try_for_each(|stream| {
                     tx.send(stream).map_err(|e| ShellError::GenericError {
                         error: "Error streaming data".into(),
@@ -566,39 +681,47 @@ pub fn get_file_type(md: &std::fs::Metadata, display_name: &str, use_mime_type:
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn dir_entry_dict(
-    filename: &std::path::Path, // absolute path
-    display_name: &str,         // file name to be displayed
+    filename: &std::path::Path,     // absolute path
+    display_name: &std::path::Path, // file name to be displayed
     metadata: Option<&std::fs::Metadata>,
     span: Span,
-    long: bool,
+    columns: Columns,
     du: bool,
     signals: &Signals,
     use_mime_type: bool,
     full_symlink_target: bool,
 ) -> Result<Value, ShellError> {
     #[cfg(windows)]
-    if metadata.is_none() {
+    if metadata.is_none() && columns.needs_metadata() {
         return Ok(windows_helper::dir_entry_dict_windows_fallback(
             filename,
             display_name,
             span,
-            long,
+            columns,
         ));
     }
 
     let mut record = Record::new();
     let mut file_type = "unknown".to_string();
 
-    record.push("name", Value::string(display_name, span));
+    // Names which aren't valid UTF-8 are path values, so they still refer to the same file
+    record.push("name", PathValue::string_or_path(display_name, span));
 
     if let Some(md) = metadata {
-        file_type = get_file_type(md, display_name, use_mime_type);
-        record.push("type", Value::string(file_type.clone(), span));
-    } else {
-        record.push("type", Value::nothing(span));
+        file_type = get_file_type(md, &display_name.to_string_lossy(), use_mime_type);
+    }
+
+    if columns.has("type") {
+        if metadata.is_some() {
+            record.push("type", Value::string(file_type.clone(), span));
+        } else {
+            record.push("type", Value::nothing(span));
+        }
     }
 
-    if long && let Some(md) = metadata {
+    if columns.has("target")
+        && let Some(md) = metadata
+    {
         record.push(
             "target",
             if md.file_type().is_symlink() {
@@ -629,85 +752,132 @@ pub(crate) fn dir_entry_dict(
         )
     }
 
-    if long && let Some(md) = metadata {
-        record.push("readonly", Value::bool(md.permissions().readonly(), span));
+    if let Some(md) = metadata {
+        if columns.has("readonly") {
+            record.push("readonly", Value::bool(md.permissions().readonly(), span));
+        }
 
         #[cfg(unix)]
         {
             use nu_utils::filesystem::users;
             use std::os::unix::fs::MetadataExt;
 
-            let mode = md.permissions().mode();
-            record.push(
-                "mode",
-                Value::string(umask::Mode::from(mode).to_string(), span),
-            );
+            if columns.has("mode") {
+                let mode = md.permissions().mode();
+                record.push(
+                    "mode",
+                    Value::string(umask::Mode::from(mode).to_string(), span),
+                );
+            }
 
-            let nlinks = md.nlink();
-            record.push("num_links", Value::int(nlinks as i64, span));
+            if columns.has("num_links") {
+                let nlinks = md.nlink();
+                record.push("num_links", Value::int(nlinks as i64, span));
+            }
 
-            let inode = md.ino();
-            record.push("inode", Value::int(inode as i64, span));
+            if columns.has("inode") {
+                let inode = md.ino();
+                record.push("inode", Value::int(inode as i64, span));
+            }
 
-            record.push(
-                "user",
-                if let Some(user) = users::get_user_by_uid(md.uid().into()) {
-                    Value::string(user.name, span)
-                } else {
-                    Value::int(md.uid().into(), span)
-                },
-            );
+            if columns.has("user") {
+                record.push(
+                    "user",
+                    if let Some(user) = users::get_user_by_uid(md.uid().into()) {
+                        Value::string(user.name, span)
+                    } else {
+                        Value::int(md.uid().into(), span)
+                    },
+                );
+            }
 
-            record.push(
-                "group",
-                if let Some(group) = users::get_group_by_gid(md.gid().into()) {
-                    Value::string(group.name, span)
-                } else {
-                    Value::int(md.gid().into(), span)
-                },
-            );
+            if columns.has("group") {
+                record.push(
+                    "group",
+                    if let Some(group) = users::get_group_by_gid(md.gid().into()) {
+                        Value::string(group.name, span)
+                    } else {
+                        Value::int(md.gid().into(), span)
+                    },
+                );
+            }
         }
-    }
 
-    record.push(
-        "size",
-        if let Some(md) = metadata {
-            let zero_sized = file_type == "pipe"
-                || file_type == "socket"
-                || file_type == "char device"
-                || file_type == "block device";
-
-            if md.is_dir() {
-                if du {
-                    let params = DirBuilder::new(Span::new(0, 2), None, false, None, false);
-                    let dir_size = DirInfo::new(filename, &params, None, span, signals)?.get_size();
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        if columns.has("xattrs") || columns.has("acl") {
+            let xattrs = xattr_names(filename);
+            if columns.has("xattrs") {
+                record.push(
+                    "xattrs",
+                    match &xattrs {
+                        Some(names) => Value::list(
+                            names
+                                .iter()
+                                .map(|name| Value::string(name.clone(), span))
+                                .collect(),
+                            span,
+                        ),
+                        None => Value::nothing(span),
+                    },
+                );
+            }
+            if columns.has("acl") {
+                record.push(
+                    "acl",
+                    match &xattrs {
+                        Some(names) => Value::bool(
+                            names.iter().any(|name| ACL_XATTRS.contains(&name.as_str())),
+                            span,
+                        ),
+                        None => Value::nothing(span),
+                    },
+                );
+            }
+        }
+    }
 
-                    Value::filesize(dir_size as i64, span)
-                } else {
-                    let dir_size: u64 = md.len();
+    if columns.has("size") {
+        record.push(
+            "size",
+            if let Some(md) = metadata {
+                let zero_sized = file_type == "pipe"
+                    || file_type == "socket"
+                    || file_type == "char device"
+                    || file_type == "block device";
+
+                if md.is_dir() {
+                    if du {
+                        let params = DirBuilder::new(Span::new(0, 2), None, false, None, false);
+                        let dir_size =
+                            DirInfo::new(filename, &params, None, span, signals)?.get_size();
+
+                        Value::filesize(dir_size as i64, span)
+                    } else {
+                        let dir_size: u64 = md.len();
 
-                    Value::filesize(dir_size as i64, span)
-                }
-            } else if md.is_file() {
-                Value::filesize(md.len() as i64, span)
-            } else if md.file_type().is_symlink() {
-                if let Ok(symlink_md) = filename.symlink_metadata() {
-                    Value::filesize(symlink_md.len() as i64, span)
+                        Value::filesize(dir_size as i64, span)
+                    }
+                } else if md.is_file() {
+                    Value::filesize(md.len() as i64, span)
+                } else if md.file_type().is_symlink() {
+                    if let Ok(symlink_md) = filename.symlink_metadata() {
+                        Value::filesize(symlink_md.len() as i64, span)
+                    } else {
+                        Value::nothing(span)
+                    }
+                } else if zero_sized {
+                    Value::filesize(0, span)
                 } else {
                     Value::nothing(span)
                 }
-            } else if zero_sized {
-                Value::filesize(0, span)
             } else {
                 Value::nothing(span)
-            }
-        } else {
-            Value::nothing(span)
-        },
-    );
+            },
+        );
+    }
 
     if let Some(md) = metadata {
-        if long {
+        if columns.has("created") {
             record.push("created", {
                 let mut val = Value::nothing(span);
                 if let Ok(c) = md.created()
@@ -717,7 +887,9 @@ pub(crate) fn dir_entry_dict(
                 }
                 val
             });
+        }
 
+        if columns.has("accessed") {
             record.push("accessed", {
                 let mut val = Value::nothing(span);
                 if let Ok(a) = md.accessed()
@@ -729,30 +901,67 @@ pub(crate) fn dir_entry_dict(
             });
         }
 
-        record.push("modified", {
-            let mut val = Value::nothing(span);
-            if let Ok(m) = md.modified()
-                && let Some(local) = try_convert_to_local_date_time(m)
-            {
-                val = Value::date(local.with_timezone(local.offset()), span);
-            }
-            val
-        })
+        if columns.has("modified") {
+            record.push("modified", {
+                let mut val = Value::nothing(span);
+                if let Ok(m) = md.modified()
+                    && let Some(local) = try_convert_to_local_date_time(m)
+                {
+                    val = Value::date(local.with_timezone(local.offset()), span);
+                }
+                val
+            })
+        }
     } else {
-        if long {
-            record.push("created", Value::nothing(span));
-            record.push("accessed", Value::nothing(span));
+        for column in ["created", "accessed", "modified"] {
+            if columns.has(column) {
+                record.push(column, Value::nothing(span));
+            }
         }
-
-        record.push("modified", Value::nothing(span));
     }
 
     Ok(Value::record(record, span))
 }
 
+/// The extended attributes which hold access control lists.
+#[cfg(target_os = "linux")]
+const ACL_XATTRS: &[&str] = &["system.posix_acl_access", "system.posix_acl_default"];
+#[cfg(target_os = "macos")]
+const ACL_XATTRS: &[&str] = &["com.apple.system.Security"];
+
+/// The names of the extended attributes of `path`, without following symlinks.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn xattr_names(path: &Path) -> Option<Vec<String>> {
+    use nix::libc;
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let list = |buf: *mut libc::c_char, size: usize| {
+        // SAFETY: `path` is a valid C string and `buf` has room for `size` bytes, or is null with
+        // a size of 0 to ask for the size of the list.
+        #[cfg(target_os = "linux")]
+        let len = unsafe { libc::llistxattr(path.as_ptr(), buf, size) };
+        #[cfg(target_os = "macos")]
+        let len = unsafe { libc::listxattr(path.as_ptr(), buf, size, libc::XATTR_NOFOLLOW) };
+        usize::try_from(len).ok()
+    };
+
+    let size = list(std::ptr::null_mut(), 0)?;
+    let mut buf = vec![0u8; size];
+    // The list can grow in between, which is reported as an error, so this is just left out then
+    let len = list(buf.as_mut_ptr().cast(), buf.len())?;
+    buf.truncate(len);
+    Some(
+        buf.split(|byte| *byte == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect(),
+    )
+}
+
 // TODO: can we get away from local times in `ls`? internals might be cleaner if we worked in UTC
 // and left the conversion to local time to the display layer
-fn try_convert_to_local_date_time(t: SystemTime) -> Option<DateTime<Local>> {
+pub(crate) fn try_convert_to_local_date_time(t: SystemTime) -> Option<DateTime<Local>> {
     // Adapted from https://github.com/chronotope/chrono/blob/v0.4.19/src/datetime.rs#L755-L767.
     let (sec, nsec) = match t.duration_since(UNIX_EPOCH) {
         Ok(dur) => (dur.as_secs() as i64, dur.subsec_nanos()),
@@ -808,13 +1017,13 @@ mod windows_helper {
     /// https://github.com/rust-lang/rust/issues/96980
     pub fn dir_entry_dict_windows_fallback(
         filename: &Path,
-        display_name: &str,
+        display_name: &Path,
         span: Span,
-        long: bool,
+        columns: Columns,
     ) -> Value {
         let mut record = Record::new();
 
-        record.push("name", Value::string(display_name, span));
+        record.push("name", PathValue::string_or_path(display_name, span));
 
         let find_data = match find_first_file(filename, span) {
             Ok(fd) => fd,
@@ -827,12 +1036,14 @@ mod windows_helper {
             }
         };
 
-        record.push(
-            "type",
-            Value::string(get_file_type_windows_fallback(&find_data), span),
-        );
+        if columns.has("type") {
+            record.push(
+                "type",
+                Value::string(get_file_type_windows_fallback(&find_data), span),
+            );
+        }
 
-        if long {
+        if columns.has("target") {
             record.push(
                 "target",
                 if is_symlink(&find_data) {
@@ -845,7 +1056,9 @@ mod windows_helper {
                     Value::nothing(span)
                 },
             );
+        }
 
+        if columns.has("readonly") {
             record.push(
                 "readonly",
                 Value::bool(
@@ -855,10 +1068,13 @@ mod windows_helper {
             );
         }
 
-        let file_size = ((find_data.nFileSizeHigh as u64) << 32) | find_data.nFileSizeLow as u64;
-        record.push("size", Value::filesize(file_size as i64, span));
+        if columns.has("size") {
+            let file_size =
+                ((find_data.nFileSizeHigh as u64) << 32) | find_data.nFileSizeLow as u64;
+            record.push("size", Value::filesize(file_size as i64, span));
+        }
 
-        if long {
+        if columns.has("created") {
             record.push("created", {
                 let mut val = Value::nothing(span);
                 let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftCreationTime);
@@ -867,7 +1083,9 @@ mod windows_helper {
                 }
                 val
             });
+        }
 
+        if columns.has("accessed") {
             record.push("accessed", {
                 let mut val = Value::nothing(span);
                 let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftLastAccessTime);
@@ -878,14 +1096,16 @@ mod windows_helper {
             });
         }
 
-        record.push("modified", {
-            let mut val = Value::nothing(span);
-            let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftLastWriteTime);
-            if let Some(local) = unix_time_to_local_date_time(seconds_since_unix_epoch) {
-                val = Value::date(local.with_timezone(local.offset()), span);
-            }
-            val
-        });
+        if columns.has("modified") {
+            record.push("modified", {
+                let mut val = Value::nothing(span);
+                let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftLastWriteTime);
+                if let Some(local) = unix_time_to_local_date_time(seconds_since_unix_epoch) {
+                    val = Value::date(local.with_timezone(local.offset()), span);
+                }
+                val
+            });
+        }
 
         Value::record(record, span)
     }
@@ -963,11 +1183,19 @@ mod windows_helper {
     }
 }
 
+fn is_empty_dir(dir: &Path, span: Span) -> Result<bool, ShellError> {
+    let mut entries = dir
+        .read_dir()
+        .map_err(|err| IoError::new(err, span, dir.to_path_buf()))?;
+    Ok(entries.next().is_none())
+}
+
+/// Read the entries of a directory. Unless they're sorted, the entries are read lazily.
 #[allow(clippy::type_complexity)]
 fn read_dir(
     f: PathBuf,
     span: Span,
-    use_threads: bool,
+    sort: bool,
     signals: Signals,
 ) -> Result<Box<dyn Iterator<Item = Result<PathBuf, ShellError>> + Send>, ShellError> {
     let signals_clone = signals.clone();
@@ -980,7 +1208,7 @@ fn read_dir(
                 .map_err(|err| IoError::new(err, span, f.clone()))
                 .map_err(ShellError::from)
         });
-    if !use_threads {
+    if sort {
         let mut collected = items.collect::<Vec<_>>();
         signals.check(&span)?;
         collected.sort_by(|a, b| match (a, b) {
@@ -66,6 +66,10 @@ impl Command for Mktemp {
         ]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -1,30 +1,40 @@
 mod cd;
 mod du;
+#[cfg(unix)]
+mod fs;
 mod glob;
 mod ls;
 mod mktemp;
 mod open;
 mod rm;
 mod save;
+mod search;
 mod start;
 mod ucp;
 mod umkdir;
 mod umv;
 mod util;
 mod utouch;
+#[cfg(feature = "vcs")]
+mod vcs;
 mod watch;
 
 pub use self::open::Open;
 pub use cd::Cd;
 pub use du::Du;
+#[cfg(unix)]
+pub use fs::{Fs, FsChmod, FsChown};
 pub use glob::Glob;
 pub use ls::Ls;
 pub use mktemp::Mktemp;
 pub use rm::Rm;
 pub use save::Save;
+pub use search::{Search, SearchFiles, SearchText};
 pub use start::Start;
 pub use ucp::UCp;
 pub use umkdir::UMkdir;
 pub use umv::UMv;
 pub use utouch::UTouch;
+#[cfg(feature = "vcs")]
+pub use vcs::{Vcs, VcsStatus};
 pub use watch::Watch;
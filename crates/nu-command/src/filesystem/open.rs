@@ -1,9 +1,11 @@
+use crate::formats::registered_format;
 #[allow(deprecated)]
-use nu_engine::{command_prelude::*, current_dir, eval_call};
+use nu_engine::{ClosureEvalOnce, command_prelude::*, current_dir, eval_call};
 use nu_path::is_windows_device_path;
 use nu_protocol::{
-    DataSource, NuGlob, PipelineMetadata, ast,
+    DataSource, DeclId, ListStream, NuGlob, PipelineMetadata, ast,
     debugger::{WithDebug, WithoutDebug},
+    engine::Closure,
     shell_error::{self, io::IoError},
 };
 use std::{
@@ -17,6 +19,8 @@ use crate::database::SQLiteDatabase;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
+mod follow;
+
 #[derive(Clone)]
 pub struct Open;
 
@@ -30,7 +34,9 @@ impl Command for Open {
     }
 
     fn extra_description(&self) -> &str {
-        "Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope."
+        r#"Support to automatically parse files with an extension `.xyz` can be provided by a `from xyz` command in scope, or by a closure registered with `format register xyz`.
+
+With --follow, the lines which are appended to the file are streamed until ctrl-c, like with `tail -f`, starting from the end of the file. When the file is truncated, the lines are read again from its beginning, and when it's replaced, like by log rotation, the new file is followed. Each line of `.json`, `.jsonl`, `.ndjson` and `.nuon` files, and of formats registered with `format register`, is parsed on its own, and the lines of `.csv` and `.tsv` files are parsed with the header of the file. Other formats can only be followed as text with --raw."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -59,9 +65,18 @@ impl Command for Open {
                 "The file(s) to open.",
             )
             .switch("raw", "open file as raw binary", Some('r'))
+            .switch(
+                "follow",
+                "stream the lines appended to the file, like `tail -f`",
+                Some('f'),
+            )
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -70,6 +85,7 @@ impl Command for Open {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let raw = call.has_flag(engine_state, stack, "raw")?;
+        let follow = call.has_flag(engine_state, stack, "follow")?;
         let call_span = call.head;
         #[allow(deprecated)]
         let cwd = current_dir(engine_state, stack)?;
@@ -97,6 +113,7 @@ impl Command for Open {
         }
 
         let mut output = vec![];
+        let mut followed = None;
 
         for mut path in paths {
             //FIXME: `open` should not have to do this
@@ -156,13 +173,21 @@ impl Command for Open {
                     return Err(err.into());
                 } else {
                     #[cfg(feature = "sqlite")]
-                    if !raw {
+                    if !raw && !follow {
+                        let read_only = engine_state
+                            .sandbox
+                            .as_ref()
+                            .is_some_and(|sandbox| !sandbox.allow_write);
                         let res = SQLiteDatabase::try_from_path(
                             path,
                             arg_span,
                             engine_state.signals().clone(),
                         )
-                        .map(|db| db.into_value(call.head).into_pipeline_data());
+                        .map(|db| {
+                            db.with_read_only(read_only)
+                                .into_value(call.head)
+                                .into_pipeline_data()
+                        });
 
                         if res.is_ok() {
                             return res;
@@ -183,7 +208,32 @@ impl Command for Open {
                         )));
                     }
 
-                    let file = std::fs::File::open(path)
+                    if follow {
+                        if followed.is_some() {
+                            return Err(ShellError::GenericError {
+                                error: "Can only follow one file".into(),
+                                msg: "more than one file to follow".into(),
+                                span: Some(arg_span),
+                                help: None,
+                                inner: vec![],
+                            });
+                        }
+                        let format = follow::LineFormat::new(
+                            find_converter(engine_state, stack, path, raw),
+                            path,
+                            arg_span,
+                        )?;
+                        followed = Some(follow::Follow::new(
+                            engine_state,
+                            stack,
+                            path.to_path_buf(),
+                            format,
+                            call_span,
+                        )?);
+                        continue;
+                    }
+
+                    let file = std::fs::File::open(nu_path::long_path(path))
                         .map_err(|err| IoError::new(err, arg_span, PathBuf::from(path)))?;
 
                     // No content_type by default - Is added later if no converter is found
@@ -195,42 +245,16 @@ impl Command for Open {
                         }),
                     );
 
-                    let exts_opt: Option<Vec<String>> = if raw {
-                        None
-                    } else {
-                        let path_str = path
-                            .file_name()
-                            .unwrap_or(std::ffi::OsStr::new(path))
-                            .to_string_lossy()
-                            .to_lowercase();
-                        Some(extract_extensions(path_str.as_str()))
-                    };
-
-                    let converter = exts_opt.and_then(|exts| {
-                        exts.iter().find_map(|ext| {
-                            engine_state
-                                .find_decl(format!("from {ext}").as_bytes(), &[])
-                                .map(|id| (id, ext.to_string()))
-                        })
-                    });
+                    let converter = find_converter(engine_state, stack, path, raw);
 
                     match converter {
-                        Some((converter_id, ext)) => {
-                            let open_call = ast::Call {
-                                decl_id: converter_id,
-                                head: call_span,
-                                arguments: vec![],
-                                parser_info: HashMap::new(),
-                            };
-                            let command_output = if engine_state.is_debugging() {
-                                eval_call::<WithDebug>(engine_state, stack, &open_call, stream)
-                            } else {
-                                eval_call::<WithoutDebug>(engine_state, stack, &open_call, stream)
-                            };
+                        Some((converter, ext)) => {
+                            let command_output =
+                                run_converter(engine_state, stack, converter, stream, call_span);
                             output.push(command_output.map_err(|inner| {
                                     ShellError::GenericError{
                                         error: format!("Error while parsing as {ext}"),
-                                        msg: format!("Could not parse '{}' with `from {}`", path.display(), ext),
+                                        msg: format!("Could not parse '{}' as `.{}`", path.display(), ext),
                                         span: Some(arg_span),
                                         help: Some(format!("Check out `help from {}` or `help from` for more options or open raw data with `open --raw '{}'`", ext, path.display())),
                                         inner: vec![inner],
@@ -257,7 +281,16 @@ impl Command for Open {
             }
         }
 
-        if output.is_empty() {
+        if let Some(followed) = followed {
+            let path = followed.path().to_path_buf();
+            Ok(PipelineData::list_stream(
+                ListStream::new(followed, call_span, engine_state.signals().clone()),
+                PipelineMetadata {
+                    data_source: DataSource::FilePath(path),
+                    ..Default::default()
+                },
+            ))
+        } else if output.is_empty() {
             Ok(PipelineData::empty())
         } else if output.len() == 1 {
             Ok(output.remove(0))
@@ -291,6 +324,16 @@ impl Command for Open {
                 example: "open myfile.txt --raw | decode utf-8",
                 result: None,
             },
+            Example {
+                description: "Follow the records appended to a newline-delimited JSON log",
+                example: "open --follow app.ndjson | where level == error",
+                result: None,
+            },
+            Example {
+                description: "Follow the lines appended to a log file as text",
+                example: "open --follow --raw /var/log/syslog",
+                result: None,
+            },
             Example {
                 description: "Create a custom `from` parser to open newline-delimited JSON files with `open`",
                 example: r#"def "from ndjson" [] { from json -o }; open myfile.ndjson"#,
@@ -310,6 +353,101 @@ impl Command for Open {
     }
 }
 
+/// What parses a file with a known extension.
+#[derive(Clone)]
+enum Converter {
+    /// A `from` command.
+    Decl(DeclId),
+    /// A closure registered with `format register`.
+    Closure(Closure),
+}
+
+/// Extensions whose `from` command isn't used by `open`, because the files are mostly read as text.
+const TEXT_EXTENSIONS: &[&str] = &["md"];
+
+/// The converter of a file, from the first of its extensions which has one, unless it's opened raw.
+fn find_converter(
+    engine_state: &EngineState,
+    stack: &Stack,
+    path: &Path,
+    raw: bool,
+) -> Option<(Converter, String)> {
+    if raw {
+        return None;
+    }
+    let path_str = path
+        .file_name()
+        .unwrap_or(std::ffi::OsStr::new(path))
+        .to_string_lossy()
+        .to_lowercase();
+
+    // Formats registered with `format register` take precedence over commands
+    extract_extensions(path_str.as_str())
+        .iter()
+        .find_map(|ext| {
+            registered_format(engine_state, stack, ext)
+                .and_then(|format| format.from)
+                .map(|closure| (Converter::Closure(closure), ext.to_string()))
+                .or_else(|| {
+                    if TEXT_EXTENSIONS.contains(&ext.as_str()) {
+                        return None;
+                    }
+                    engine_state
+                        .find_decl(format!("from {ext}").as_bytes(), &[])
+                        .map(|id| (Converter::Decl(id), ext.to_string()))
+                })
+        })
+}
+
+fn run_converter(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    converter: Converter,
+    input: PipelineData,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    match converter {
+        Converter::Decl(converter_id) => {
+            let call = ast::Call {
+                decl_id: converter_id,
+                head: span,
+                arguments: vec![],
+                parser_info: HashMap::new(),
+            };
+            if engine_state.is_debugging() {
+                eval_call::<WithDebug>(engine_state, stack, &call, input)
+            } else {
+                eval_call::<WithoutDebug>(engine_state, stack, &call, input)
+            }
+        }
+        Converter::Closure(closure) => {
+            run_registered_format(engine_state, stack, closure, input, span)
+        }
+    }
+}
+
+/// Run the `from` closure of a registered format. Closures with a parameter get the collected
+/// contents as their argument, other closures get the stream as their input.
+fn run_registered_format(
+    engine_state: &EngineState,
+    stack: &Stack,
+    closure: Closure,
+    stream: PipelineData,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    let takes_argument = engine_state
+        .get_block(closure.block_id)
+        .signature
+        .num_positionals()
+        > 0;
+    let closure = ClosureEvalOnce::new(engine_state, stack, closure);
+    if takes_argument {
+        closure.run_with_value(stream.into_value(span)?)
+    } else {
+        closure.run_with_input(stream)
+    }
+}
+
 fn permission_denied(dir: impl AsRef<Path>) -> bool {
     match dir.as_ref().read_dir() {
         Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
@@ -0,0 +1,239 @@
+//! `open --follow`, which streams the lines appended to a file, like `tail -f`.
+
+use super::{Converter, run_converter};
+use nu_engine::command_prelude::*;
+use nu_protocol::{Signals, shell_error::io::IoError};
+use std::{
+    collections::VecDeque,
+    fs::{File, Metadata},
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How the appended lines are turned into values.
+pub(super) enum LineFormat {
+    /// The lines as strings.
+    Text,
+    /// Each line is converted on its own, like in NDJSON.
+    Each(Converter),
+    /// Each line is converted after the header line of the file, like in CSV.
+    WithHeader(Converter),
+}
+
+impl LineFormat {
+    /// The format of the lines of a file, from the converter that `open` found for it.
+    pub(super) fn new(
+        converter: Option<(Converter, String)>,
+        path: &Path,
+        span: Span,
+    ) -> Result<Self, ShellError> {
+        let Some((converter, ext)) = converter else {
+            return Ok(LineFormat::Text);
+        };
+        match (converter, ext.as_str()) {
+            (converter @ Converter::Decl(_), "csv" | "tsv") => {
+                Ok(LineFormat::WithHeader(converter))
+            }
+            (converter @ Converter::Decl(_), "json" | "jsonl" | "ndjson" | "nuon")
+            | (converter @ Converter::Closure(_), _) => Ok(LineFormat::Each(converter)),
+            (Converter::Decl(_), _) => Err(ShellError::GenericError {
+                error: format!("Can't follow a `.{ext}` file"),
+                msg: "the lines of this format can't be parsed on their own".into(),
+                span: Some(span),
+                help: Some(format!(
+                    "Follow the lines as text with `open --follow --raw '{}'`",
+                    path.display()
+                )),
+                inner: vec![],
+            }),
+        }
+    }
+}
+
+/// Which file a path points to, to notice when it's replaced, like by log rotation.
+#[cfg(unix)]
+type FileId = (u64, u64);
+#[cfg(not(unix))]
+type FileId = std::time::SystemTime;
+
+fn file_id(metadata: &Metadata) -> Option<FileId> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.created().ok()
+    }
+}
+
+/// The values of the lines appended to a file, until ctrl-c.
+pub(super) struct Follow {
+    path: PathBuf,
+    reader: BufReader<File>,
+    id: Option<FileId>,
+    position: u64,
+    /// The line which is being read, which can be written in several parts.
+    line: Vec<u8>,
+    format: LineFormat,
+    /// The first line of the file for [`LineFormat::WithHeader`].
+    header: Option<String>,
+    pending: VecDeque<Value>,
+    engine_state: EngineState,
+    stack: Stack,
+    signals: Signals,
+    span: Span,
+    done: bool,
+}
+
+impl Follow {
+    pub(super) fn new(
+        engine_state: &EngineState,
+        stack: &Stack,
+        path: PathBuf,
+        format: LineFormat,
+        span: Span,
+    ) -> Result<Self, ShellError> {
+        let io_error = |err| IoError::new(err, span, path.clone());
+        let file = File::open(nu_path::long_path(&path)).map_err(io_error)?;
+        let id = file.metadata().ok().as_ref().and_then(file_id);
+        let mut reader = BufReader::new(file);
+
+        // The header was written before the lines which are followed
+        let mut header = None;
+        if let LineFormat::WithHeader(_) = format {
+            let mut line = String::new();
+            if reader.read_line(&mut line).map_err(io_error)? > 0 && line.ends_with('\n') {
+                header = Some(line.trim_end_matches(['\n', '\r']).to_string());
+            }
+        }
+        let position = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+
+        Ok(Self {
+            path,
+            reader,
+            id,
+            position,
+            line: vec![],
+            format,
+            header,
+            pending: VecDeque::new(),
+            engine_state: engine_state.clone(),
+            stack: stack.clone(),
+            signals: engine_state.signals().clone(),
+            span,
+            done: false,
+        })
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Start from the beginning when the file is truncated, or reopen it when it's replaced.
+    fn reopen_if_changed(&mut self) -> std::io::Result<()> {
+        let metadata = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // The file can be missing for a moment while it's rotated
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        let id = file_id(&metadata);
+        if id != self.id {
+            self.reader = BufReader::new(File::open(nu_path::long_path(&self.path))?);
+            self.id = id;
+        } else if metadata.len() < self.position {
+            self.reader.seek(SeekFrom::Start(0))?;
+        } else {
+            return Ok(());
+        }
+
+        self.position = 0;
+        self.line.clear();
+        if let LineFormat::WithHeader(_) = self.format {
+            self.header = None;
+        }
+        Ok(())
+    }
+
+    /// Add the values of a whole line to the pending values.
+    fn parse(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        let (converter, text) = match &self.format {
+            LineFormat::Text => {
+                self.pending.push_back(Value::string(line, self.span));
+                return;
+            }
+            _ if line.trim().is_empty() => return,
+            LineFormat::Each(converter) => (converter, line.to_string()),
+            LineFormat::WithHeader(converter) => match &self.header {
+                Some(header) => (converter, format!("{header}\n{line}")),
+                None => {
+                    self.header = Some(line.to_string());
+                    return;
+                }
+            },
+        };
+
+        let input = Value::string(text, self.span).into_pipeline_data();
+        let output = run_converter(
+            &self.engine_state,
+            &mut self.stack,
+            converter.clone(),
+            input,
+            self.span,
+        )
+        .and_then(|data| data.into_value(self.span));
+        match output {
+            Ok(Value::List { vals, .. }) => self.pending.extend(vals),
+            Ok(value) => self.pending.push_back(value),
+            Err(err) => self.pending.push_back(Value::error(err, self.span)),
+        }
+    }
+}
+
+impl Iterator for Follow {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.pending.pop_front() {
+                return Some(value);
+            }
+            if self.done || self.signals.interrupted() {
+                return None;
+            }
+
+            let read = self.reader.read_until(b'\n', &mut self.line);
+            let result = match read {
+                Ok(0) => self.reopen_if_changed(),
+                Ok(n) => {
+                    self.position += n as u64;
+                    // Otherwise the rest of the line hasn't been written yet
+                    if self.line.ends_with(b"\n") {
+                        let line = std::mem::take(&mut self.line);
+                        self.parse(&line);
+                        continue;
+                    }
+                    Ok(())
+                }
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                self.done = true;
+                let err = IoError::new(err, self.span, self.path.clone());
+                return Some(Value::error(err.into(), self.span));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
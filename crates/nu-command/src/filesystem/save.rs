@@ -1,17 +1,22 @@
-use crate::progress_bar;
-use nu_engine::get_eval_block;
+use crate::{
+    formats::{registered_extension, registered_format},
+    progress_bar,
+};
+use indexmap::{IndexMap, map::Entry};
+use nu_engine::{ClosureEval, ClosureEvalOnce, get_eval_block};
 #[allow(deprecated)]
 use nu_engine::{command_prelude::*, current_dir};
 use nu_path::{expand_path_with, is_windows_device_path};
 use nu_protocol::{
-    ByteStreamSource, DataSource, OutDest, PipelineMetadata, Signals, ast,
-    byte_stream::copy_with_signals, process::ChildPipe, shell_error::io::IoError,
+    ByteStreamSource, DataSource, Filesize, FromValue, OutDest, PipelineMetadata, Signals, ast,
+    byte_stream::copy_with_signals, engine::Closure, process::ChildPipe, shell_error::io::IoError,
 };
 use std::{
     borrow::Cow,
     fs::File,
     io::{self, BufRead, BufReader, Read, Write},
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
     thread,
     time::{Duration, Instant},
 };
@@ -44,7 +49,7 @@ impl Command for Save {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("save")
             .input_output_types(vec![(Type::Any, Type::Nothing)])
-            .required("filename", SyntaxShape::Filepath, "The filename to use.")
+            .optional("filename", SyntaxShape::Filepath, "The filename to use.")
             .named(
                 "stderr",
                 SyntaxShape::Filepath,
@@ -55,9 +60,51 @@ impl Command for Save {
             .switch("append", "append input to the end of the file", Some('a'))
             .switch("force", "overwrite the destination", Some('f'))
             .switch("progress", "enable progress bar", Some('p'))
+            .switch(
+                "atomic",
+                "write to a temporary file and rename it to the filename when done, so the file is never partially written",
+                None,
+            )
+            .named(
+                "rotate-size",
+                SyntaxShape::Filesize,
+                "start a new file once the file is bigger than this, keeping the old ones as <filename>.1, <filename>.2 and so on",
+                None,
+            )
+            .named(
+                "rotate-every",
+                SyntaxShape::Duration,
+                "start a new file once the file has been written to for this long",
+                None,
+            )
+            .named(
+                "rotate-keep",
+                SyntaxShape::Int,
+                "how many rotated files to keep (default: 5)",
+                None,
+            )
+            .named(
+                "partition-by",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "save every item to the filename returned by this closure, instead of a single file",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
+    fn extra_description(&self) -> &str {
+        r#"With `--partition-by`, the items of the input are saved to the files named by the closure.
+Files with an extension which has a `to` command are written when the input ends, other files
+are written one item per line as the input arrives.
+
+Rotation is checked between the values or chunks written, so long running pipelines like
+`tail -f` can be saved to a log which doesn't grow forever."#
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -69,12 +116,72 @@ impl Command for Save {
         let append = call.has_flag(engine_state, stack, "append")?;
         let force = call.has_flag(engine_state, stack, "force")?;
         let progress = call.has_flag(engine_state, stack, "progress")?;
+        let atomic = call.has_flag(engine_state, stack, "atomic")?;
+        let rotation = Rotation::from_call(engine_state, stack, call)?;
+        let partition_by: Option<Spanned<Closure>> =
+            call.get_flag(engine_state, stack, "partition-by")?;
 
         let span = call.head;
         #[allow(deprecated)]
         let cwd = current_dir(engine_state, stack)?;
 
-        let path_arg = call.req::<Spanned<PathBuf>>(engine_state, stack, 0)?;
+        if atomic && append {
+            return Err(incompatible_flags(
+                call,
+                stack,
+                "append",
+                "appending can't be atomic",
+            ));
+        }
+        if atomic && rotation.is_some() {
+            let flag = match call.get_flag_span(stack, "rotate-size") {
+                Some(_) => "rotate-size",
+                None => "rotate-every",
+            };
+            return Err(incompatible_flags(
+                call,
+                stack,
+                flag,
+                "rotated files aren't replaced at once",
+            ));
+        }
+        let options = SaveOptions {
+            append,
+            force,
+            atomic,
+            rotation,
+        };
+
+        let path_arg = call.opt::<Spanned<PathBuf>>(engine_state, stack, 0)?;
+        let path_arg = match (path_arg, partition_by) {
+            (None, Some(closure)) => {
+                return save_partitioned(
+                    engine_state,
+                    stack,
+                    input,
+                    closure,
+                    raw,
+                    &options,
+                    &cwd,
+                    span,
+                );
+            }
+            (Some(path_arg), Some(closure)) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "a filename was given...".into(),
+                    left_span: path_arg.span,
+                    right_message: "...but the filenames are computed by `--partition-by`".into(),
+                    right_span: closure.span,
+                });
+            }
+            (Some(path_arg), None) => path_arg,
+            (None, None) => {
+                return Err(ShellError::MissingParameter {
+                    param_name: "filename".into(),
+                    span,
+                });
+            }
+        };
         let path = Spanned {
             item: expand_path_with(path_arg.item, &cwd, true),
             span: path_arg.span,
@@ -92,18 +199,18 @@ impl Command for Save {
             PipelineData::ByteStream(stream, metadata) => {
                 check_saving_to_source_file(metadata.as_ref(), &path, stderr_path.as_ref())?;
 
-                let (file, stderr_file) =
-                    get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                let (mut file, stderr_file) =
+                    get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
 
                 let size = stream.known_size();
                 let signals = engine_state.signals();
 
                 match stream.into_source() {
                     ByteStreamSource::Read(read) => {
-                        stream_to_file(read, size, signals, file, span, progress)?;
+                        stream_to_file(read, size, signals, &mut file, span, progress)?;
                     }
                     ByteStreamSource::File(source) => {
-                        stream_to_file(source, size, signals, file, span, progress)?;
+                        stream_to_file(source, size, signals, &mut file, span, progress)?;
                     }
                     #[cfg(feature = "os")]
                     ByteStreamSource::Child(mut child) => {
@@ -114,14 +221,14 @@ impl Command for Save {
                             signals: &Signals,
                             progress: bool,
                         ) -> Result<(), ShellError> {
-                            if let Some(file) = file {
+                            if let Some(mut file) = file {
                                 match stderr {
-                                    ChildPipe::Pipe(pipe) => {
-                                        stream_to_file(pipe, None, signals, file, span, progress)
-                                    }
-                                    ChildPipe::Tee(tee) => {
-                                        stream_to_file(tee, None, signals, file, span, progress)
-                                    }
+                                    ChildPipe::Pipe(pipe) => stream_to_file(
+                                        pipe, None, signals, &mut file, span, progress,
+                                    ),
+                                    ChildPipe::Tee(tee) => stream_to_file(
+                                        tee, None, signals, &mut file, span, progress,
+                                    ),
                                 }?
                             } else {
                                 match stderr {
@@ -159,12 +266,12 @@ impl Command for Save {
                                     .map_err(&from_io_error)?;
 
                                 let res = match stdout {
-                                    ChildPipe::Pipe(pipe) => {
-                                        stream_to_file(pipe, None, signals, file, span, progress)
-                                    }
-                                    ChildPipe::Tee(tee) => {
-                                        stream_to_file(tee, None, signals, file, span, progress)
-                                    }
+                                    ChildPipe::Pipe(pipe) => stream_to_file(
+                                        pipe, None, signals, &mut file, span, progress,
+                                    ),
+                                    ChildPipe::Tee(tee) => stream_to_file(
+                                        tee, None, signals, &mut file, span, progress,
+                                    ),
                                 };
                                 if let Some(h) = handler {
                                     h.join().map_err(|err| ShellError::ExternalCommand {
@@ -191,11 +298,19 @@ impl Command for Save {
                         child.wait()?;
                     }
                 }
+                file.finish().map_err(&from_io_error)?;
 
                 Ok(PipelineData::empty())
             }
             PipelineData::ListStream(ls, pipeline_metadata)
-                if raw || prepare_path(&path, append, force)?.0.extension().is_none() =>
+                if raw
+                    || (prepare_path(&path, append, force)?.0.extension().is_none()
+                        && content_type_extension(
+                            engine_state,
+                            stack,
+                            pipeline_metadata.as_ref(),
+                        )
+                        .is_none()) =>
             {
                 check_saving_to_source_file(
                     pipeline_metadata.as_ref(),
@@ -203,14 +318,14 @@ impl Command for Save {
                     stderr_path.as_ref(),
                 )?;
 
-                let (mut file, _) =
-                    get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                let (mut file, _) = get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
                 for val in ls {
-                    file.write_all(&value_to_bytes(val)?)
-                        .map_err(&from_io_error)?;
-                    file.write_all("\n".as_bytes()).map_err(&from_io_error)?;
+                    // Write the newline with the value, so rotation doesn't split lines
+                    let mut bytes = value_to_bytes(val)?;
+                    bytes.push(b'\n');
+                    file.write_all(&bytes).map_err(&from_io_error)?;
                 }
-                file.flush().map_err(&from_io_error)?;
+                file.finish().map_err(&from_io_error)?;
 
                 Ok(PipelineData::empty())
             }
@@ -226,7 +341,7 @@ impl Command for Save {
                 }
 
                 // Try to convert the input pipeline into another type if we know the extension
-                let ext = extract_extension(&input, &path.item, raw);
+                let ext = extract_extension(engine_state, stack, &input, &path.item, raw);
                 let converted = match ext {
                     None => input,
                     Some(ext) => convert_to_extension(engine_state, &ext, stack, input, span)?,
@@ -251,11 +366,10 @@ impl Command for Save {
                 let bytes = value_to_bytes(converted.into_value(span)?)?;
 
                 // Only open file after successful conversion
-                let (mut file, _) =
-                    get_files(engine_state, &path, stderr_path.as_ref(), append, force)?;
+                let (mut file, _) = get_files(engine_state, &path, stderr_path.as_ref(), &options)?;
 
                 file.write_all(&bytes).map_err(&from_io_error)?;
-                file.flush().map_err(&from_io_error)?;
+                file.finish().map_err(&from_io_error)?;
 
                 Ok(PipelineData::empty())
             }
@@ -289,6 +403,21 @@ impl Command for Save {
                 example: r#"do -i {} | save foo.txt --stderr bar.txt"#,
                 result: None,
             },
+            Example {
+                description: "Replace a config file without ever leaving it half written",
+                example: r#"open config.toml | upsert port 8080 | save --force --atomic config.toml"#,
+                result: None,
+            },
+            Example {
+                description: "Keep a log of a stream, starting a new file every 10 MB",
+                example: r#"tail -f /var/log/syslog | save --append --rotate-size 10MB syslog.txt"#,
+                result: None,
+            },
+            Example {
+                description: "Save records to a file per day",
+                example: r#"open events.json | save --partition-by {|event| $"events-($event.date).json" }"#,
+                result: None,
+            },
             Example {
                 description: "Show the extensions for which the `save` command will automatically serialize",
                 example: r#"scope commands
@@ -307,6 +436,15 @@ impl Command for Save {
     }
 }
 
+fn incompatible_flags(call: &Call, stack: &Stack, flag: &str, reason: &str) -> ShellError {
+    ShellError::IncompatibleParameters {
+        left_message: "can't use `--atomic`...".into(),
+        left_span: call.get_flag_span(stack, "atomic").unwrap_or(call.head),
+        right_message: format!("...with `--{flag}`, {reason}"),
+        right_span: call.get_flag_span(stack, flag).unwrap_or(call.head),
+    }
+}
+
 fn saving_to_source_file_error(dest: &Spanned<PathBuf>) -> ShellError {
     ShellError::GenericError {
         error: "pipeline input and output are the same file".into(),
@@ -345,15 +483,53 @@ fn check_saving_to_source_file(
 }
 
 /// Extract extension for conversion.
-fn extract_extension<'e>(input: &PipelineData, path: &'e Path, raw: bool) -> Option<Cow<'e, str>> {
+///
+/// Files without an extension are converted according to the content type in the metadata of
+/// the input, if there is a `to` command or a registered format for it.
+fn extract_extension<'e>(
+    engine_state: &EngineState,
+    stack: &Stack,
+    input: &PipelineData,
+    path: &'e Path,
+    raw: bool,
+) -> Option<Cow<'e, str>> {
     match (raw, input) {
         (true, _)
         | (_, PipelineData::ByteStream(..))
         | (_, PipelineData::Value(Value::String { .. }, ..)) => None,
-        _ => path.extension().map(|name| name.to_string_lossy()),
+        _ => path
+            .extension()
+            .map(|name| name.to_string_lossy())
+            .or_else(|| content_type_extension(engine_state, stack, input.metadata().as_ref())),
     }
 }
 
+/// The extension of the format for the content type in `metadata`.
+fn content_type_extension(
+    engine_state: &EngineState,
+    stack: &Stack,
+    metadata: Option<&PipelineMetadata>,
+) -> Option<Cow<'static, str>> {
+    let content_type = metadata?.content_type.as_deref()?;
+    // Ignore parameters like `; charset=utf-8`
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    if let Some(extension) = registered_extension(engine_state, stack, mime) {
+        return Some(Cow::Owned(extension));
+    }
+    Some(Cow::Borrowed(match mime {
+        "application/json" => "json",
+        "application/x-nuon" => "nuon",
+        "text/csv" => "csv",
+        "text/tab-separated-values" => "tsv",
+        "application/yaml" | "application/x-yaml" | "text/yaml" => "yaml",
+        "text/x-toml" | "application/toml" => "toml",
+        "application/xml" | "text/xml" => "xml",
+        "text/markdown" => "md",
+        "application/x-msgpack" => "msgpack",
+        _ => return None,
+    }))
+}
+
 /// Convert given data into content of file of specified extension if
 /// corresponding `to` command exists. Otherwise attempt to convert
 /// data to bytes as is
@@ -364,7 +540,12 @@ fn convert_to_extension(
     input: PipelineData,
     span: Span,
 ) -> Result<PipelineData, ShellError> {
-    if let Some(decl_id) = engine_state.find_decl(format!("to {extension}").as_bytes(), &[]) {
+    if let Some(closure) =
+        registered_format(engine_state, stack, extension).and_then(|format| format.to)
+    {
+        ClosureEvalOnce::new(engine_state, stack, closure).run_with_input(input)
+    } else if let Some(decl_id) = engine_state.find_decl(format!("to {extension}").as_bytes(), &[])
+    {
         let decl = engine_state.get_decl(decl_id);
         if let Some(block_id) = decl.block_id() {
             let block = engine_state.get_block(block_id);
@@ -434,9 +615,10 @@ fn open_file(
     span: Span,
     append: bool,
 ) -> Result<File, ShellError> {
+    let long_path = nu_path::long_path(path);
     let file: std::io::Result<File> = match (append, path.exists() || is_windows_device_path(path))
     {
-        (true, true) => std::fs::OpenOptions::new().append(true).open(path),
+        (true, true) => std::fs::OpenOptions::new().append(true).open(&long_path),
         _ => {
             // This is a temporary solution until `std::fs::File::create` is fixed on Windows (rust-lang/rust#134893)
             // A TOCTOU problem exists here, which may cause wrong error message to be shown
@@ -448,10 +630,10 @@ fn open_file(
                 )]
                 Err(std::io::ErrorKind::IsADirectory.into())
             } else {
-                std::fs::File::create(path)
+                std::fs::File::create(&long_path)
             }
             #[cfg(not(target_os = "windows"))]
-            std::fs::File::create(path)
+            std::fs::File::create(&long_path)
         }
     };
 
@@ -487,14 +669,210 @@ fn open_file(
     }
 }
 
+/// How `save` writes its files.
+struct SaveOptions {
+    append: bool,
+    force: bool,
+    atomic: bool,
+    rotation: Option<Rotation>,
+}
+
+/// When to start a new file, for `--rotate-size` and `--rotate-every`.
+#[derive(Clone)]
+struct Rotation {
+    size: Option<u64>,
+    every: Option<Duration>,
+    keep: usize,
+}
+
+impl Rotation {
+    fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Option<Self>, ShellError> {
+        let size: Option<Spanned<Filesize>> = call.get_flag(engine_state, stack, "rotate-size")?;
+        let every: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "rotate-every")?;
+        let keep: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "rotate-keep")?;
+        if size.is_none() && every.is_none() {
+            return Ok(None);
+        }
+
+        let positive = |value: Spanned<i64>, name: &str| {
+            u64::try_from(value.item)
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| ShellError::IncorrectValue {
+                    msg: format!("`--{name}` has to be positive"),
+                    val_span: value.span,
+                    call_span: call.head,
+                })
+        };
+        Ok(Some(Rotation {
+            size: size
+                .map(|size| positive(size.item.get().into_spanned(size.span), "rotate-size"))
+                .transpose()?,
+            every: every
+                .map(|every| positive(every, "rotate-every").map(Duration::from_nanos))
+                .transpose()?,
+            keep: keep
+                .map(|keep| positive(keep, "rotate-keep"))
+                .transpose()?
+                .map_or(5, |keep| keep as usize),
+        }))
+    }
+
+    fn is_due(&self, written: u64, opened: Instant) -> bool {
+        self.size.is_some_and(|size| written >= size)
+            || self.every.is_some_and(|every| opened.elapsed() >= every)
+    }
+
+    /// Move `path` to `path.1`, `path.1` to `path.2` and so on, dropping the oldest file.
+    fn shift(&self, path: &Path) -> io::Result<()> {
+        let numbered = |n: usize| {
+            let mut name = path.as_os_str().to_owned();
+            name.push(format!(".{n}"));
+            PathBuf::from(name)
+        };
+        for n in (1..self.keep).rev() {
+            match std::fs::rename(numbered(n), numbered(n + 1)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => (),
+            }
+        }
+        std::fs::rename(path, numbered(1))
+    }
+}
+
+/// The file `save` writes to, which takes care of `--atomic` and rotation.
+struct SaveFile {
+    file: File,
+    path: PathBuf,
+    /// The file written instead of `path` with `--atomic`, until it's renamed to `path`
+    temp_path: Option<PathBuf>,
+    rotation: Option<Rotation>,
+    written: u64,
+    opened: Instant,
+    /// Whether the last write ended a line, as files are only rotated between lines
+    at_line_start: bool,
+}
+
+impl SaveFile {
+    fn open(
+        engine_state: &EngineState,
+        path: &Path,
+        span: Span,
+        options: &SaveOptions,
+    ) -> Result<Self, ShellError> {
+        let (file, temp_path) = if options.atomic {
+            let temp_path = temp_path(path);
+            let file = open_file(engine_state, &temp_path, span, false)?;
+            // Keep the permissions of the file which is replaced
+            if let Ok(metadata) = path.metadata() {
+                let _ = file.set_permissions(metadata.permissions());
+            }
+            (file, Some(temp_path))
+        } else {
+            (open_file(engine_state, path, span, options.append)?, None)
+        };
+        let written = if options.append {
+            file.metadata().map_or(0, |metadata| metadata.len())
+        } else {
+            0
+        };
+        Ok(SaveFile {
+            file,
+            path: path.to_path_buf(),
+            temp_path,
+            rotation: options.rotation.clone(),
+            written,
+            opened: Instant::now(),
+            at_line_start: true,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(rotation) = &self.rotation {
+            self.file.flush()?;
+            rotation.shift(&self.path)?;
+            self.file = File::create(nu_path::long_path(&self.path))?;
+            self.written = 0;
+            self.opened = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn write_to_file(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.file.write(buf)?;
+        self.written += len as u64;
+        if len > 0 {
+            self.at_line_start = buf[len - 1] == b'\n';
+        }
+        Ok(len)
+    }
+
+    /// Flush the file, and move it into place if it's saved atomically.
+    fn finish(mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if let Some(temp_path) = &self.temp_path {
+            self.file.sync_all()?;
+            std::fs::rename(temp_path, &self.path)?;
+            self.temp_path = None;
+        }
+        Ok(())
+    }
+}
+
+impl Write for SaveFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self
+            .rotation
+            .as_ref()
+            .is_some_and(|rotation| rotation.is_due(self.written, self.opened))
+        {
+            match buf.iter().position(|byte| *byte == b'\n') {
+                _ if self.at_line_start => self.rotate()?,
+                // Finish the current line before starting a new file
+                Some(end) => return self.write_to_file(&buf[..=end]),
+                None => (),
+            }
+        }
+        self.write_to_file(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for SaveFile {
+    fn drop(&mut self) {
+        // The save failed, so the file which would have been replaced is kept
+        if let Some(temp_path) = &self.temp_path {
+            let _ = std::fs::remove_file(temp_path);
+        }
+    }
+}
+
+/// A hidden file next to `path`, to write to before renaming it to `path`.
+fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(
+        ".{name}.{}-{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
 /// Get output file and optional stderr file
 fn get_files(
     engine_state: &EngineState,
     path: &Spanned<PathBuf>,
     stderr_path: Option<&Spanned<PathBuf>>,
-    append: bool,
-    force: bool,
-) -> Result<(File, Option<File>), ShellError> {
+    options: &SaveOptions,
+) -> Result<(SaveFile, Option<File>), ShellError> {
+    let SaveOptions { append, force, .. } = *options;
     // First check both paths
     let (path, path_span) = prepare_path(path, append, force)?;
     let stderr_path_and_span = stderr_path
@@ -503,7 +881,7 @@ fn get_files(
         .transpose()?;
 
     // Only if both files can be used open and possibly truncate them
-    let file = open_file(engine_state, path, path_span, append)?;
+    let file = SaveFile::open(engine_state, path, path_span, options)?;
 
     let stderr_file = stderr_path_and_span
         .map(|(stderr_path, stderr_path_span)| {
@@ -524,11 +902,99 @@ fn get_files(
     Ok((file, stderr_file))
 }
 
+/// Save the items of `input` to the files named by `closure`, for `--partition-by`.
+#[allow(clippy::too_many_arguments)]
+fn save_partitioned(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    input: PipelineData,
+    closure: Spanned<Closure>,
+    raw: bool,
+    options: &SaveOptions,
+    cwd: &Path,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    /// The items saved to one file. Items are written as they arrive unless they have to be
+    /// converted all at once, by the `to` command for the extension of the file.
+    enum Partition {
+        Written(SaveFile),
+        Collected(Vec<Value>),
+    }
+
+    let mut partitions = IndexMap::<PathBuf, Partition>::new();
+    let mut eval = ClosureEval::new(engine_state, stack, closure.item);
+    for value in input {
+        if let Value::Error { error, .. } = value {
+            return Err(*error);
+        }
+        let path = PathBuf::from_value(eval.run_with_value(value.clone())?.into_value(span)?)?;
+        let path = expand_path_with(path, cwd, true);
+        let from_io_error = IoError::factory(span, path.as_path());
+
+        let partition = match partitions.entry(path.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = Spanned {
+                    item: entry.key().clone(),
+                    span: closure.span,
+                };
+                prepare_path(&path, options.append, options.force)?;
+                let converted = !raw
+                    && path.item.extension().is_some_and(|ext| {
+                        let ext = ext.to_string_lossy();
+                        registered_format(engine_state, stack, &ext)
+                            .is_some_and(|format| format.to.is_some())
+                            || engine_state
+                                .find_decl(format!("to {ext}").as_bytes(), &[])
+                                .is_some()
+                    });
+                entry.insert(if converted {
+                    Partition::Collected(vec![])
+                } else {
+                    Partition::Written(SaveFile::open(
+                        engine_state,
+                        &path.item,
+                        path.span,
+                        options,
+                    )?)
+                })
+            }
+        };
+        match partition {
+            Partition::Written(file) => {
+                let mut bytes = value_to_bytes(value)?;
+                bytes.push(b'\n');
+                file.write_all(&bytes).map_err(&from_io_error)?;
+            }
+            Partition::Collected(values) => values.push(value),
+        }
+    }
+    drop(eval);
+
+    for (path, partition) in partitions {
+        let from_io_error = IoError::factory(span, path.as_path());
+        match partition {
+            Partition::Written(file) => file.finish().map_err(&from_io_error)?,
+            Partition::Collected(values) => {
+                let ext = path.extension().unwrap_or_default().to_string_lossy();
+                let input = Value::list(values, span).into_pipeline_data();
+                let converted = convert_to_extension(engine_state, &ext, stack, input, span)?;
+                let bytes = value_to_bytes(converted.into_value(span)?)?;
+                let mut file = SaveFile::open(engine_state, &path, closure.span, options)?;
+                file.write_all(&bytes).map_err(&from_io_error)?;
+                file.finish().map_err(&from_io_error)?;
+            }
+        }
+    }
+
+    Ok(PipelineData::empty())
+}
+
 fn stream_to_file(
     source: impl Read,
     known_size: Option<u64>,
     signals: &Signals,
-    mut file: File,
+    file: &mut impl Write,
     span: Span,
     progress: bool,
 ) -> Result<(), ShellError> {
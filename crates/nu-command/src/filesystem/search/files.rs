@@ -0,0 +1,369 @@
+use super::walk::{Entry, WalkOptions, walk};
+use crate::filesystem::ls::{get_file_type, try_convert_to_local_date_time};
+use fancy_regex::Regex;
+use nu_engine::{ClosureEvalOnce, command_prelude::*};
+use nu_glob::{MatchOptions, Pattern};
+use nu_protocol::{Filesize, engine::Closure};
+use rayon::prelude::*;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+#[derive(Clone)]
+pub struct SearchFiles;
+
+impl Command for SearchFiles {
+    fn name(&self) -> &str {
+        "search files"
+    }
+
+    fn description(&self) -> &str {
+        "Recursively search for files by name, type, size and modification time."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Directories are walked in parallel, so the order of the results is not deterministic. Like `fd`, hidden files and files ignored by `.gitignore` or `.ignore` files are skipped unless `--hidden` or `--no-ignore` are given.
+With `--exec`, the closure is run on every match, with at most `--threads` closures running at the same time, and its results are returned instead of the matches."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["find", "fd", "locate", "recursive", "walk"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "The directories to search, the current directory by default.",
+            )
+            .named(
+                "name",
+                SyntaxShape::String,
+                "Glob pattern the file name has to match",
+                Some('n'),
+            )
+            .named(
+                "regex",
+                SyntaxShape::String,
+                "Regular expression the file name has to match",
+                Some('r'),
+            )
+            .named(
+                "type",
+                SyntaxShape::String,
+                "Type of the entries to find: file, dir or symlink",
+                Some('t'),
+            )
+            .named(
+                "min-size",
+                SyntaxShape::Filesize,
+                "Only find files at least this large",
+                None,
+            )
+            .named(
+                "max-size",
+                SyntaxShape::Filesize,
+                "Only find files at most this large",
+                None,
+            )
+            .named(
+                "changed-within",
+                SyntaxShape::OneOf(vec![SyntaxShape::Duration, SyntaxShape::DateTime]),
+                "Only find entries modified within this duration, or since this date",
+                None,
+            )
+            .named(
+                "changed-before",
+                SyntaxShape::OneOf(vec![SyntaxShape::Duration, SyntaxShape::DateTime]),
+                "Only find entries modified longer ago than this duration, or before this date",
+                None,
+            )
+            .named(
+                "max-depth",
+                SyntaxShape::Int,
+                "Don't descend further than this many directories",
+                Some('d'),
+            )
+            .switch("hidden", "Include hidden files and directories", Some('H'))
+            .switch(
+                "no-ignore",
+                "Don't skip files ignored by .gitignore or .ignore files",
+                Some('I'),
+            )
+            .named(
+                "exec",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Record(vec![])])),
+                "Closure to run on each match",
+                Some('x'),
+            )
+            .named(
+                "threads",
+                SyntaxShape::Int,
+                "The number of threads to use",
+                Some('j'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Find all Rust files below the current directory",
+                example: "search files --name '*.rs'",
+                result: None,
+            },
+            Example {
+                description: "Find large files in the home directory which changed in the last week",
+                example: "search files ~ --type file --min-size 100MB --changed-within 1wk",
+                result: None,
+            },
+            Example {
+                description: "Find test directories, two levels deep at most",
+                example: "search files --type dir --regex '^tests?$' --max-depth 2",
+                result: None,
+            },
+            Example {
+                description: "Count the lines of each TOML file, four files at a time",
+                example: "search files -n '*.toml' --threads 4 --exec {|f| {path: $f.path, lines: (open --raw $f.path | lines | length)} }",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let name: Option<Spanned<String>> = call.get_flag(engine_state, stack, "name")?;
+        let regex: Option<Spanned<String>> = call.get_flag(engine_state, stack, "regex")?;
+        let file_type: Option<Spanned<String>> = call.get_flag(engine_state, stack, "type")?;
+        let min_size: Option<Filesize> = call.get_flag(engine_state, stack, "min-size")?;
+        let max_size: Option<Filesize> = call.get_flag(engine_state, stack, "max-size")?;
+        let changed_within: Option<Value> = call.get_flag(engine_state, stack, "changed-within")?;
+        let changed_before: Option<Value> = call.get_flag(engine_state, stack, "changed-before")?;
+        let max_depth: Option<usize> = call.get_flag(engine_state, stack, "max-depth")?;
+        let hidden = call.has_flag(engine_state, stack, "hidden")?;
+        let no_ignore = call.has_flag(engine_state, stack, "no-ignore")?;
+        let exec: Option<Closure> = call.get_flag(engine_state, stack, "exec")?;
+        let threads: Option<usize> = call.get_flag(engine_state, stack, "threads")?;
+
+        let filter = Filter {
+            name: name
+                .map(|name| {
+                    Pattern::new(&name.item).map_err(|err| ShellError::IncorrectValue {
+                        msg: err.to_string(),
+                        val_span: name.span,
+                        call_span: head,
+                    })
+                })
+                .transpose()?,
+            regex: regex
+                .map(|regex| {
+                    Regex::new(&regex.item).map_err(|err| ShellError::IncorrectValue {
+                        msg: err.to_string(),
+                        val_span: regex.span,
+                        call_span: head,
+                    })
+                })
+                .transpose()?,
+            file_type: file_type.map(parse_file_type).transpose()?,
+            min_size: min_size.map(|size| size.get()),
+            max_size: max_size.map(|size| size.get()),
+            changed_after: changed_within
+                .map(|v| time_threshold(v, head))
+                .transpose()?,
+            changed_before: changed_before
+                .map(|v| time_threshold(v, head))
+                .transpose()?,
+        };
+
+        let roots = if paths.is_empty() {
+            vec![cwd.clone()]
+        } else {
+            paths
+                .iter()
+                .map(|path| nu_path::expand_path_with(&path.item, &cwd, true))
+                .collect()
+        };
+
+        let threads = match threads {
+            Some(threads) => threads,
+            None => std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+        };
+        let options = WalkOptions {
+            max_depth,
+            hidden,
+            respect_ignore: !no_ignore,
+            threads,
+        };
+
+        let matches = walk(
+            roots,
+            options,
+            engine_state.signals().clone(),
+            head,
+            move |entry| filter.matches(entry, &cwd, head),
+        )?;
+
+        let Some(closure) = exec else {
+            return Ok(matches
+                .into_iter()
+                .into_pipeline_data(head, engine_state.signals().clone()));
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|err| ShellError::GenericError {
+                error: "Error creating thread pool".into(),
+                msg: err.to_string(),
+                span: Some(head),
+                help: None,
+                inner: vec![],
+            })?;
+
+        let results = pool.install(|| {
+            matches
+                .into_iter()
+                .par_bridge()
+                .map(|value| {
+                    ClosureEvalOnce::new(engine_state, stack, closure.clone())
+                        .run_with_value(value)
+                        .and_then(|data| data.into_value(head))
+                        .unwrap_or_else(|err| Value::error(err, head))
+                })
+                .filter(|value| !value.is_nothing())
+                .collect::<Vec<_>>()
+        });
+
+        Ok(results.into_pipeline_data(head, engine_state.signals().clone()))
+    }
+}
+
+struct Filter {
+    name: Option<Pattern>,
+    regex: Option<Regex>,
+    file_type: Option<&'static str>,
+    min_size: Option<i64>,
+    max_size: Option<i64>,
+    changed_after: Option<SystemTime>,
+    changed_before: Option<SystemTime>,
+}
+
+impl Filter {
+    /// Return the row for a matching entry.
+    fn matches(&self, entry: &Entry, cwd: &Path, span: Span) -> Option<Value> {
+        let name = entry.path.file_name()?.to_string_lossy();
+
+        if let Some(pattern) = &self.name
+            && !pattern.matches_with(
+                &name,
+                MatchOptions {
+                    require_literal_separator: true,
+                    ..MatchOptions::default()
+                },
+            )
+        {
+            return None;
+        }
+        if let Some(regex) = &self.regex
+            && !regex.is_match(&name).unwrap_or(false)
+        {
+            return None;
+        }
+
+        let metadata = std::fs::symlink_metadata(&entry.path).ok()?;
+        let file_type = get_file_type(&metadata, &name, false);
+        if self.file_type.is_some_and(|expected| expected != file_type) {
+            return None;
+        }
+
+        let size = metadata.len() as i64;
+        if self.min_size.is_some_and(|min| size < min)
+            || self.max_size.is_some_and(|max| size > max)
+        {
+            return None;
+        }
+
+        let modified = metadata.modified().ok();
+        if let Some(after) = self.changed_after
+            && modified.is_none_or(|modified| modified < after)
+        {
+            return None;
+        }
+        if let Some(before) = self.changed_before
+            && modified.is_none_or(|modified| modified >= before)
+        {
+            return None;
+        }
+
+        let path = display_path(&entry.path, cwd);
+        Some(Value::record(
+            record! {
+                "path" => Value::string(path.to_string_lossy(), span),
+                "type" => Value::string(file_type, span),
+                "size" => Value::filesize(size, span),
+                "modified" => modified
+                    .and_then(try_convert_to_local_date_time)
+                    .map(|date| Value::date(date.fixed_offset(), span))
+                    .unwrap_or_else(|| Value::nothing(span)),
+            },
+            span,
+        ))
+    }
+}
+
+/// Paths below the current directory are shown relative to it.
+fn display_path(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn parse_file_type(file_type: Spanned<String>) -> Result<&'static str, ShellError> {
+    match file_type.item.as_str() {
+        "f" | "file" => Ok("file"),
+        "d" | "dir" | "directory" => Ok("dir"),
+        "l" | "symlink" => Ok("symlink"),
+        _ => Err(ShellError::InvalidValue {
+            valid: "file, dir or symlink".into(),
+            actual: file_type.item,
+            span: file_type.span,
+        }),
+    }
+}
+
+/// Convert a duration (relative to now) or a date into a point in time.
+fn time_threshold(value: Value, head: Span) -> Result<SystemTime, ShellError> {
+    let span = value.span();
+    match value {
+        Value::Duration { val, .. } => {
+            let ago = Duration::from_nanos(val.unsigned_abs());
+            Ok(SystemTime::now()
+                .checked_sub(ago)
+                .unwrap_or(SystemTime::UNIX_EPOCH))
+        }
+        Value::Date { val, .. } => Ok(val.into()),
+        _ => Err(ShellError::OnlySupportsThisInputType {
+            exp_input_type: "duration or date".into(),
+            wrong_type: value.get_type().to_string(),
+            dst_span: head,
+            src_span: span,
+        }),
+    }
+}
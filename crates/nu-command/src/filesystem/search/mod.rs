@@ -0,0 +1,8 @@
+mod files;
+mod search_;
+mod text;
+mod walk;
+
+pub use files::SearchFiles;
+pub use search_::Search;
+pub use text::SearchText;
@@ -0,0 +1,261 @@
+use super::walk::{WalkOptions, walk};
+use crate::{capture_column_names, captures_record};
+use fancy_regex::Regex;
+use nu_engine::command_prelude::*;
+use nu_glob::{MatchOptions, Pattern};
+use std::path::Path;
+
+/// Files with a NUL byte in this many leading bytes are considered binary, like ripgrep does.
+const BINARY_DETECTION_LEN: usize = 8 * 1024;
+
+#[derive(Clone)]
+pub struct SearchText;
+
+impl Command for SearchText {
+    fn name(&self) -> &str {
+        "search text"
+    }
+
+    fn description(&self) -> &str {
+        "Recursively search the contents of files for a regular expression."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Every match is returned as a record with the path of the file, the line number and the byte column (both starting at 1), the matched text, the whole line and the capture groups of the pattern. Unnamed capture groups are called `capture0`, `capture1`, and so on, like in `parse`.
+Files are searched in parallel, so the files' order is not deterministic, but the matches of each file are returned in order. Binary files, hidden files and files ignored by `.gitignore` or `.ignore` files are skipped unless `--hidden` or `--no-ignore` are given."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["grep", "rg", "ripgrep", "find", "regex"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::table())])
+            .required(
+                "pattern",
+                SyntaxShape::String,
+                "The regular expression to search for.",
+            )
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "The files or directories to search, the current directory by default.",
+            )
+            .switch(
+                "fixed-strings",
+                "Search for the pattern literally instead of as a regular expression",
+                Some('F'),
+            )
+            .switch("ignore-case", "Search case-insensitively", Some('i'))
+            .named(
+                "glob",
+                SyntaxShape::String,
+                "Only search files whose name matches this glob pattern",
+                Some('g'),
+            )
+            .named(
+                "max-depth",
+                SyntaxShape::Int,
+                "Don't descend further than this many directories",
+                Some('d'),
+            )
+            .switch("hidden", "Search hidden files and directories", Some('H'))
+            .switch(
+                "no-ignore",
+                "Don't skip files ignored by .gitignore or .ignore files",
+                Some('I'),
+            )
+            .named(
+                "threads",
+                SyntaxShape::Int,
+                "The number of threads to use",
+                Some('j'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Find the TODO comments in Rust files",
+                example: "search text 'TODO' --glob '*.rs'",
+                result: None,
+            },
+            Example {
+                description: "Count the matches per file",
+                example: "search text -i 'error' logs | group-by path | transpose path matches | update matches { length }",
+                result: None,
+            },
+            Example {
+                description: "List the versions of the dependencies in manifests using named captures",
+                example: r#"search text '^(?<name>[a-z_-]+) = "(?<version>[0-9.]+)"' --glob Cargo.toml | get captures"#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let pattern: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 1)?;
+        let fixed_strings = call.has_flag(engine_state, stack, "fixed-strings")?;
+        let ignore_case = call.has_flag(engine_state, stack, "ignore-case")?;
+        let glob: Option<Spanned<String>> = call.get_flag(engine_state, stack, "glob")?;
+        let max_depth: Option<usize> = call.get_flag(engine_state, stack, "max-depth")?;
+        let hidden = call.has_flag(engine_state, stack, "hidden")?;
+        let no_ignore = call.has_flag(engine_state, stack, "no-ignore")?;
+        let threads: Option<usize> = call.get_flag(engine_state, stack, "threads")?;
+
+        let mut regex = if fixed_strings {
+            fancy_regex::escape(&pattern.item).into_owned()
+        } else {
+            pattern.item
+        };
+        if ignore_case {
+            regex.insert_str(0, "(?i)");
+        }
+        let regex = Regex::new(&regex).map_err(|err| ShellError::GenericError {
+            error: "Error with regular expression".into(),
+            msg: err.to_string(),
+            span: Some(pattern.span),
+            help: None,
+            inner: vec![],
+        })?;
+        let glob = glob
+            .map(|glob| {
+                Pattern::new(&glob.item).map_err(|err| ShellError::IncorrectValue {
+                    msg: err.to_string(),
+                    val_span: glob.span,
+                    call_span: head,
+                })
+            })
+            .transpose()?;
+
+        let roots = if paths.is_empty() {
+            vec![cwd.clone()]
+        } else {
+            paths
+                .iter()
+                .map(|path| nu_path::expand_path_with(&path.item, &cwd, true))
+                .collect()
+        };
+        let options = WalkOptions {
+            max_depth,
+            hidden,
+            respect_ignore: !no_ignore,
+            threads: threads.unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|threads| threads.get())
+                    .unwrap_or(1)
+            }),
+        };
+        let searcher = Searcher { regex, glob, head };
+
+        let matches = walk(
+            roots,
+            options,
+            engine_state.signals().clone(),
+            head,
+            move |entry| {
+                if !entry.file_type.is_file() {
+                    return None;
+                }
+                let matches = searcher.search(&entry.path, &cwd);
+                (!matches.is_empty()).then_some(matches)
+            },
+        )?;
+
+        Ok(matches
+            .into_iter()
+            .flatten()
+            .into_pipeline_data(head, engine_state.signals().clone()))
+    }
+}
+
+struct Searcher {
+    regex: Regex,
+    glob: Option<Pattern>,
+    head: Span,
+}
+
+impl Searcher {
+    /// Return a row for every match in the file.
+    fn search(&self, path: &Path, cwd: &Path) -> Vec<Value> {
+        let span = self.head;
+
+        if let Some(glob) = &self.glob {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let options = MatchOptions {
+                require_literal_separator: true,
+                ..MatchOptions::default()
+            };
+            if !glob.matches_with(&name, options) {
+                return vec![];
+            }
+        }
+
+        let Ok(contents) = std::fs::read(path) else {
+            return vec![];
+        };
+        if contents[..contents.len().min(BINARY_DETECTION_LEN)].contains(&0) {
+            return vec![];
+        }
+        let contents = String::from_utf8_lossy(&contents);
+
+        let display_path = path.strip_prefix(cwd).unwrap_or(path).to_string_lossy();
+        let columns = capture_column_names(&self.regex);
+
+        let mut rows = vec![];
+        for (line_index, line) in contents.lines().enumerate() {
+            for captures in self.regex.captures_iter(line) {
+                let captures = match captures {
+                    Ok(captures) => captures,
+                    Err(err) => {
+                        rows.push(Value::error(
+                            ShellError::GenericError {
+                                error: "Error with regular expression captures".into(),
+                                msg: err.to_string(),
+                                span: Some(span),
+                                help: None,
+                                inner: vec![],
+                            },
+                            span,
+                        ));
+                        break;
+                    }
+                };
+                let Some(whole) = captures.get(0) else {
+                    continue;
+                };
+
+                let groups = captures_record(&columns, &captures, span);
+
+                rows.push(Value::record(
+                    record! {
+                        "path" => Value::string(display_path.clone(), span),
+                        "line" => Value::int(line_index as i64 + 1, span),
+                        "column" => Value::int(whole.start() as i64 + 1, span),
+                        "match" => Value::string(whole.as_str(), span),
+                        "text" => Value::string(line, span),
+                        "captures" => Value::record(groups, span),
+                    },
+                    span,
+                ));
+            }
+        }
+
+        rows
+    }
+}
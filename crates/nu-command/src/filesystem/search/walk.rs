@@ -0,0 +1,172 @@
+use nu_glob::IgnoreDir;
+use nu_protocol::{ShellError, Signals, Span};
+use std::{
+    fs::FileType,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+};
+
+/// How to walk the directory trees searched by the `search` commands.
+pub(super) struct WalkOptions {
+    pub max_depth: Option<usize>,
+    pub hidden: bool,
+    pub respect_ignore: bool,
+    pub threads: usize,
+}
+
+/// A file or directory found while walking.
+pub(super) struct Entry {
+    pub path: PathBuf,
+    pub file_type: FileType,
+}
+
+struct Walker<T, F> {
+    options: WalkOptions,
+    visit: F,
+    tx: mpsc::Sender<T>,
+    signals: Signals,
+    /// Set when the receiving end is gone or the walk was interrupted.
+    stopped: AtomicBool,
+}
+
+/// Walk the directory trees below `roots` in parallel, passing every entry to `visit`.
+///
+/// The walk happens in the background; the values returned by `visit` are sent through the
+/// returned channel as soon as they're produced, so their order is not deterministic. Roots which
+/// are files are visited themselves, and unreadable directories are skipped.
+pub(super) fn walk<T, F>(
+    roots: Vec<PathBuf>,
+    options: WalkOptions,
+    signals: Signals,
+    span: Span,
+    visit: F,
+) -> Result<mpsc::Receiver<T>, ShellError>
+where
+    T: Send + 'static,
+    F: Fn(&Entry) -> Option<T> + Send + Sync + 'static,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.threads)
+        .build()
+        .map_err(|err| ShellError::GenericError {
+            error: "Error creating thread pool".into(),
+            msg: err.to_string(),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        })?;
+
+    let (tx, rx) = mpsc::channel();
+    let walker = Arc::new(Walker {
+        options,
+        visit,
+        tx,
+        signals,
+        stopped: AtomicBool::new(false),
+    });
+
+    pool.spawn(move || {
+        rayon::scope(|scope| {
+            for root in roots {
+                match std::fs::symlink_metadata(&root) {
+                    Ok(metadata) if metadata.is_dir() => {
+                        let ignore = walker
+                            .options
+                            .respect_ignore
+                            .then(|| IgnoreDir::new(&root, walker.options.hidden));
+                        walk_dir(scope, root, 1, ignore, &walker)
+                    }
+                    Ok(metadata) => walker.emit(&Entry {
+                        path: root,
+                        file_type: metadata.file_type(),
+                    }),
+                    Err(_) => {}
+                }
+            }
+        })
+    });
+
+    Ok(rx)
+}
+
+impl<T, F> Walker<T, F>
+where
+    T: Send,
+    F: Fn(&Entry) -> Option<T> + Sync,
+{
+    fn stopped(&self) -> bool {
+        if self.signals.interrupted() {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    fn emit(&self, entry: &Entry) {
+        if let Some(value) = (self.visit)(entry)
+            && self.tx.send(value).is_err()
+        {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether `entry` is hidden or ignored by the rules of the directory it's in.
+    fn skip(&self, entry: &Entry, ignore: Option<&IgnoreDir>) -> bool {
+        match ignore {
+            Some(ignore) => ignore.is_ignored(&entry.path, entry.file_type.is_dir()),
+            None => {
+                !self.options.hidden
+                    && entry
+                        .path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            }
+        }
+    }
+}
+
+fn walk_dir<'scope, T, F>(
+    scope: &rayon::Scope<'scope>,
+    dir: PathBuf,
+    depth: usize,
+    ignore: Option<IgnoreDir>,
+    walker: &'scope Walker<T, F>,
+) where
+    T: Send,
+    F: Fn(&Entry) -> Option<T> + Sync,
+{
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if walker.stopped() {
+            return;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let entry = Entry {
+            path: entry.path(),
+            file_type,
+        };
+        if walker.skip(&entry, ignore.as_ref()) {
+            continue;
+        }
+
+        walker.emit(&entry);
+
+        if entry.file_type.is_dir()
+            && walker
+                .options
+                .max_depth
+                .is_none_or(|max_depth| depth < max_depth)
+        {
+            let ignore = ignore.as_ref().map(|ignore| ignore.child(&entry.path));
+            scope.spawn(move |scope| walk_dir(scope, entry.path, depth + 1, ignore, walker));
+        }
+    }
+}
@@ -29,6 +29,10 @@ impl Command for Start {
             .category(Category::FileSystem)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -73,6 +73,10 @@ impl Command for UTouch {
         "Creates one or more files."
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Write])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -0,0 +1,5 @@
+mod status;
+mod vcs_;
+
+pub use status::VcsStatus;
+pub use vcs_::Vcs;
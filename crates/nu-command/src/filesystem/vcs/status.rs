@@ -0,0 +1,306 @@
+use gix::{
+    bstr::BString,
+    status::{UntrackedFiles, index_worktree::iter::Summary},
+};
+use nu_engine::command_prelude::*;
+use nu_protocol::Signals;
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct VcsStatus;
+
+impl Command for VcsStatus {
+    fn name(&self) -> &str {
+        "vcs status"
+    }
+
+    fn description(&self) -> &str {
+        "Show the branch and the changes of a git repository, without running git."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The repository is read with gitoxide, so this works without git being installed and is fast enough to be used in prompts.
+`staged` lists the changes between `HEAD` and the index, `unstaged` the changes between the index and the working tree. Listing the files can take a while in large repositories; use `--no-files` to only get the branch, or `--no-untracked` to skip looking for untracked files."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "git", "gstat", "branch", "dirty", "ahead", "behind", "prompt",
+        ]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::record())])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "A path inside the repository, the current directory by default.",
+            )
+            .switch(
+                "no-files",
+                "Only read the branch and HEAD, without looking for changed files",
+                Some('n'),
+            )
+            .switch("no-untracked", "Don't look for untracked files", Some('u'))
+            .category(Category::FileSystem)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Show the status of the repository in the current directory",
+                example: "vcs status",
+                result: None,
+            },
+            Example {
+                description: "Show the branch and how far it is ahead of its upstream in a prompt",
+                example: "$env.PROMPT_COMMAND_RIGHT = {|| try { vcs status --no-files | $'($in.branch) ↑($in.ahead)' } }",
+                result: None,
+            },
+            Example {
+                description: "List the modified files which aren't staged yet",
+                example: "vcs status | get unstaged | where status == modified | get path",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let cwd = engine_state.cwd(Some(stack))?.into_std_path_buf();
+        let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let no_files = call.has_flag(engine_state, stack, "no-files")?;
+        let no_untracked = call.has_flag(engine_state, stack, "no-untracked")?;
+
+        let (path, path_span) = match path {
+            Some(path) => (nu_path::expand_path_with(&path.item, &cwd, true), path.span),
+            None => (cwd, head),
+        };
+        let repo = gix::discover(&path).map_err(|err| ShellError::GenericError {
+            error: "Not a git repository".into(),
+            msg: err.to_string(),
+            span: Some(path_span),
+            help: Some("vcs status only supports git repositories".into()),
+            inner: vec![],
+        })?;
+
+        let mut record = branch_status(&repo, head)?;
+        if !no_files {
+            let files = file_status(&repo, !no_untracked, engine_state.signals(), head)?;
+            record.extend(files);
+        }
+
+        Ok(Value::record(record, head).into_pipeline_data())
+    }
+}
+
+/// The repository root, HEAD, the branch and how it relates to its upstream.
+fn branch_status(repo: &gix::Repository, span: Span) -> Result<Record, ShellError> {
+    let head = repo.head().map_err(|err| git_error(err, span))?;
+    let head_id = head.id().map(|id| id.detach());
+    let branch = head.referent_name().map(|name| name.to_owned());
+
+    let upstream = match &branch {
+        Some(branch) => repo
+            .branch_remote_tracking_ref_name(branch.as_ref(), gix::remote::Direction::Fetch)
+            .transpose()
+            .map_err(|err| git_error(err, span))?
+            .map(|name| name.into_owned()),
+        None => None,
+    };
+    let upstream_id = match &upstream {
+        Some(upstream) => repo
+            .try_find_reference(upstream.as_ref())
+            .map_err(|err| git_error(err, span))?
+            .and_then(|mut reference| reference.peel_to_id().ok())
+            .map(|id| id.detach()),
+        None => None,
+    };
+    let ahead_behind = match (head_id, upstream_id) {
+        (Some(local), Some(upstream)) => Some((
+            count_commits(repo, local, upstream, span)?,
+            count_commits(repo, upstream, local, span)?,
+        )),
+        _ => None,
+    };
+
+    let optional_int = |count: Option<usize>| {
+        count
+            .map(|count| Value::int(count as i64, span))
+            .unwrap_or_else(|| Value::nothing(span))
+    };
+    let optional_string = |string: Option<String>| {
+        string
+            .map(|string| Value::string(string, span))
+            .unwrap_or_else(|| Value::nothing(span))
+    };
+
+    Ok(record! {
+        "root" => optional_string(repo.workdir().or_else(|| repo.path().parent()).map(path_string)),
+        "branch" => optional_string(branch.as_ref().map(|name| name.shorten().to_string())),
+        "head" => optional_string(head_id.map(|id| id.to_string())),
+        "detached" => Value::bool(head.is_detached(), span),
+        "upstream" => optional_string(upstream.as_ref().map(|name| name.shorten().to_string())),
+        "ahead" => optional_int(ahead_behind.map(|(ahead, _)| ahead)),
+        "behind" => optional_int(ahead_behind.map(|(_, behind)| behind)),
+        "state" => optional_string(repo.state().map(|state| state_name(state).to_string())),
+    })
+}
+
+/// Count the commits reachable from `from` which aren't reachable from `hidden`.
+fn count_commits(
+    repo: &gix::Repository,
+    from: gix::ObjectId,
+    hidden: gix::ObjectId,
+    span: Span,
+) -> Result<usize, ShellError> {
+    let walk = repo
+        .rev_walk([from])
+        .with_hidden([hidden])
+        .all()
+        .map_err(|err| git_error(err, span))?;
+    let mut count = 0;
+    for info in walk {
+        info.map_err(|err| git_error(err, span))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// The staged, unstaged, untracked and conflicting files.
+fn file_status(
+    repo: &gix::Repository,
+    untracked: bool,
+    signals: &Signals,
+    span: Span,
+) -> Result<Record, ShellError> {
+    let status = repo
+        .status(gix::progress::Discard)
+        .map_err(|err| git_error(err, span))?
+        .untracked_files(if untracked {
+            UntrackedFiles::Files
+        } else {
+            UntrackedFiles::None
+        })
+        .into_iter(Vec::<BString>::new())
+        .map_err(|err| git_error(err, span))?;
+
+    let mut staged = vec![];
+    let mut unstaged = vec![];
+    let mut untracked_files = vec![];
+    let mut conflicts = vec![];
+
+    for item in status {
+        signals.check(&span)?;
+        match item.map_err(|err| git_error(err, span))? {
+            gix::status::Item::TreeIndex(change) => {
+                use gix::diff::index::ChangeRef;
+
+                let (status, path, original) = match &change {
+                    ChangeRef::Addition { location, .. } => ("added", location.to_string(), None),
+                    ChangeRef::Deletion { location, .. } => ("deleted", location.to_string(), None),
+                    ChangeRef::Modification { location, .. } => {
+                        ("modified", location.to_string(), None)
+                    }
+                    ChangeRef::Rewrite {
+                        source_location,
+                        location,
+                        copy,
+                        ..
+                    } => (
+                        if *copy { "copied" } else { "renamed" },
+                        location.to_string(),
+                        Some(source_location.to_string()),
+                    ),
+                };
+                staged.push(change_row(status, path, original, span));
+            }
+            gix::status::Item::IndexWorktree(item) => {
+                let path = item.rela_path().to_string();
+                let status = match item.summary() {
+                    Some(Summary::Added) => {
+                        untracked_files.push(Value::string(path, span));
+                        continue;
+                    }
+                    Some(Summary::Conflict) => {
+                        conflicts.push(Value::string(path, span));
+                        continue;
+                    }
+                    Some(Summary::Removed) => "deleted",
+                    Some(Summary::Modified) => "modified",
+                    Some(Summary::TypeChange) => "type_changed",
+                    Some(Summary::Renamed) => "renamed",
+                    Some(Summary::Copied) => "copied",
+                    Some(Summary::IntentToAdd) => "intent_to_add",
+                    None => continue,
+                };
+                unstaged.push(change_row(status, path, None, span));
+            }
+        }
+    }
+
+    let dirty = !(staged.is_empty()
+        && unstaged.is_empty()
+        && untracked_files.is_empty()
+        && conflicts.is_empty());
+
+    Ok(record! {
+        "dirty" => Value::bool(dirty, span),
+        "staged" => Value::list(staged, span),
+        "unstaged" => Value::list(unstaged, span),
+        "untracked" => Value::list(untracked_files, span),
+        "conflicts" => Value::list(conflicts, span),
+    })
+}
+
+fn change_row(status: &str, path: String, original: Option<String>, span: Span) -> Value {
+    Value::record(
+        record! {
+            "status" => Value::string(status, span),
+            "path" => Value::string(path, span),
+            "original" => original
+                .map(|original| Value::string(original, span))
+                .unwrap_or_else(|| Value::nothing(span)),
+        },
+        span,
+    )
+}
+
+fn state_name(state: gix::state::InProgress) -> &'static str {
+    use gix::state::InProgress;
+
+    match state {
+        InProgress::ApplyMailbox => "apply_mailbox",
+        InProgress::ApplyMailboxRebase => "apply_mailbox_rebase",
+        InProgress::Bisect => "bisect",
+        InProgress::CherryPick | InProgress::CherryPickSequence => "cherry_pick",
+        InProgress::Merge => "merge",
+        InProgress::Rebase | InProgress::RebaseInteractive => "rebase",
+        InProgress::Revert | InProgress::RevertSequence => "revert",
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn git_error(err: impl std::fmt::Display, span: Span) -> ShellError {
+    ShellError::GenericError {
+        error: "Error reading git repository".into(),
+        msg: err.to_string(),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    }
+}
@@ -1,7 +1,7 @@
 use nu_engine::command_prelude::*;
 use nu_protocol::{ast::PathMember, casing::Casing};
 
-use crate::Comparator;
+use crate::{Collation, Comparator};
 
 #[derive(Clone)]
 pub struct Sort;
@@ -36,6 +36,12 @@ impl Command for Sort {
                 "Sort alphanumeric string-based values naturally (1, 9, 10, 99, 100, ...)",
                 Some('n'),
             )
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "Sort strings in the alphabetical order of a locale, like `de` or `sv-SE`, with accented letters next to their base letter",
+                None,
+            )
             .category(Category::Filters)
     }
 
@@ -125,6 +131,16 @@ impl Command for Sort {
                     "b" => Value::test_int(4),
                 })),
             },
+            Example {
+                description: "Sort words in the alphabetical order of Swedish",
+                example: "[zon öl Ål ask] | sort --locale sv",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("ask"),
+                    Value::test_string("zon"),
+                    Value::test_string("Ål"),
+                    Value::test_string("öl"),
+                ])),
+            },
         ]
     }
 
@@ -138,6 +154,9 @@ impl Command for Sort {
         let reverse = call.has_flag(engine_state, stack, "reverse")?;
         let insensitive = call.has_flag(engine_state, stack, "ignore-case")?;
         let natural = call.has_flag(engine_state, stack, "natural")?;
+        let collation = call
+            .get_flag::<String>(engine_state, stack, "locale")?
+            .map(|locale| Collation::from_locale(&locale));
         let sort_by_value = call.has_flag(engine_state, stack, "values")?;
         let metadata = input.metadata();
 
@@ -152,6 +171,7 @@ impl Command for Sort {
                     reverse,
                     insensitive,
                     natural,
+                    collation,
                 )?;
                 Value::record(record, span)
             }
@@ -175,9 +195,9 @@ impl Command for Sort {
                         .map(|members| CellPath { members })
                         .map(Comparator::CellPath)
                         .collect();
-                    crate::sort_by(&mut vec, columns, span, insensitive, natural)?;
+                    crate::sort_by(&mut vec, columns, span, insensitive, natural, collation)?;
                 } else {
-                    crate::sort(&mut vec, insensitive, natural)?;
+                    crate::sort(&mut vec, insensitive, natural, collation)?;
                 }
 
                 if reverse {
@@ -1,6 +1,6 @@
 use nu_engine::{ClosureEval, command_prelude::*};
 
-use crate::Comparator;
+use crate::{Collation, Comparator};
 
 #[derive(Clone)]
 pub struct SortBy;
@@ -40,6 +40,12 @@ impl Command for SortBy {
                 "Sort alphanumeric string-based data naturally (1, 9, 10, 99, 100, ...)",
                 Some('n'),
             )
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "Sort strings in the alphabetical order of a locale, like `de` or `sv-SE`, with accented letters next to their base letter",
+                None,
+            )
             .switch(
                 "custom",
                 "Use closures to specify a custom sort order, rather than to compute a comparison key",
@@ -133,6 +139,9 @@ impl Command for SortBy {
         let reverse = call.has_flag(engine_state, stack, "reverse")?;
         let insensitive = call.has_flag(engine_state, stack, "ignore-case")?;
         let natural = call.has_flag(engine_state, stack, "natural")?;
+        let collation = call
+            .get_flag::<String>(engine_state, stack, "locale")?
+            .map(|locale| Collation::from_locale(&locale));
         let custom = call.has_flag(engine_state, stack, "custom")?;
         let metadata = input.metadata();
         let mut vec: Vec<_> = input.into_iter_strict(head)?.collect();
@@ -164,7 +173,7 @@ impl Command for SortBy {
             })
             .collect::<Result<_, _>>()?;
 
-        crate::sort_by(&mut vec, comparators, head, insensitive, natural)?;
+        crate::sort_by(&mut vec, comparators, head, insensitive, natural, collation)?;
 
         if reverse {
             vec.reverse()
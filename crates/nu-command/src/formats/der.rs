@@ -0,0 +1,226 @@
+//! Decoding of PEM, and of the DER encoding of ASN.1 used by keys and certificates.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+pub(crate) const INTEGER: u8 = 0x02;
+pub(crate) const BIT_STRING: u8 = 0x03;
+pub(crate) const OID: u8 = 0x06;
+pub(crate) const SEQUENCE: u8 = 0x30;
+
+/// A block of PEM, like `-----BEGIN PUBLIC KEY-----` and the DER up to its end line.
+pub(crate) struct Pem {
+    pub label: String,
+    pub der: Vec<u8>,
+}
+
+/// The PEM blocks of `text`, skipping the text around them.
+pub(crate) fn pem_blocks(text: &str) -> Result<Vec<Pem>, String> {
+    let mut blocks = vec![];
+    let mut lines = text.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some(label) = line
+            .strip_prefix("-----BEGIN ")
+            .and_then(|rest| rest.strip_suffix("-----"))
+        else {
+            continue;
+        };
+        let end = format!("-----END {label}-----");
+        let mut base64 = String::new();
+        loop {
+            match lines.next() {
+                Some(line) if line == end => break,
+                // Headers of encrypted PEM, like `Proc-Type: 4,ENCRYPTED`
+                Some(line) if line.contains(':') => {
+                    return Err(format!("the {label} is encrypted"));
+                }
+                Some(line) => base64.push_str(line),
+                None => return Err(format!("the {label} has no end line")),
+            }
+        }
+        let der = STANDARD
+            .decode(&base64)
+            .map_err(|err| format!("the {label} is not valid base64: {err}"))?;
+        blocks.push(Pem {
+            label: label.into(),
+            der,
+        });
+    }
+    Ok(blocks)
+}
+
+/// An encoded ASN.1 value.
+#[derive(Clone, Copy)]
+pub(crate) struct Tlv<'a> {
+    pub tag: u8,
+    pub contents: &'a [u8],
+    /// The whole value, with its tag and length.
+    pub encoded: &'a [u8],
+}
+
+/// Reads the DER values in a buffer one after another.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The tag of the next value, without reading it.
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    pub fn read(&mut self) -> Result<Tlv<'a>, String> {
+        let [tag, first, rest @ ..] = self.data else {
+            return Err("the DER ends in the middle of a value".into());
+        };
+        if tag & 0x1f == 0x1f {
+            return Err("the DER has a tag above 30".into());
+        }
+        let (len, rest) = match *first {
+            len @ 0..=0x7f => (len as usize, rest),
+            0x81..=0x84 => {
+                let count = (first & 0x7f) as usize;
+                let (bytes, rest) = rest
+                    .split_at_checked(count)
+                    .ok_or("the DER ends in the middle of a length")?;
+                let len = bytes
+                    .iter()
+                    .fold(0usize, |len, byte| len << 8 | *byte as usize);
+                (len, rest)
+            }
+            _ => return Err("the DER has an unsupported length".into()),
+        };
+        let (contents, rest) = rest
+            .split_at_checked(len)
+            .ok_or("the DER ends in the middle of a value")?;
+        let encoded = &self.data[..self.data.len() - rest.len()];
+        self.data = rest;
+        Ok(Tlv {
+            tag: *tag,
+            contents,
+            encoded,
+        })
+    }
+
+    /// The contents of the next value, which must have the tag `tag`.
+    pub fn expect(&mut self, tag: u8) -> Result<&'a [u8], String> {
+        let tlv = self.read()?;
+        if tlv.tag == tag {
+            Ok(tlv.contents)
+        } else {
+            Err(format!(
+                "expected a DER value with tag {tag:#04x}, not {:#04x}",
+                tlv.tag
+            ))
+        }
+    }
+
+    /// The contents of the next value if it has the tag `tag`.
+    pub fn optional(&mut self, tag: u8) -> Result<Option<&'a [u8]>, String> {
+        if self.peek_tag() == Some(tag) {
+            self.expect(tag).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The bytes of a bit string, which must be a whole number of bytes.
+pub(crate) fn bit_string_bytes(contents: &[u8]) -> Result<&[u8], String> {
+    match contents {
+        [0, bytes @ ..] => Ok(bytes),
+        _ => Err("the DER has a bit string which isn't a whole number of bytes".into()),
+    }
+}
+
+/// The bytes of a positive integer, without the leading zero which keeps it from being negative.
+pub(crate) fn unsigned_integer(contents: &[u8]) -> &[u8] {
+    match contents {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        _ => contents,
+    }
+}
+
+/// An object identifier in dotted notation, like `1.2.840.113549.1.1.1`.
+pub(crate) fn oid_to_string(contents: &[u8]) -> String {
+    let mut arcs = vec![];
+    let mut arc: u64 = 0;
+    for byte in contents {
+        arc = arc << 7 | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = (arc / 40).min(2);
+                arcs.push(first);
+                arcs.push(arc - first * 40);
+            } else {
+                arcs.push(arc);
+            }
+            arc = 0;
+        }
+    }
+    arcs.iter()
+        .map(|arc| arc.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The algorithm, its parameters and the key of a SubjectPublicKeyInfo.
+pub(crate) fn subject_public_key_info<'a>(
+    spki: &'a [u8],
+) -> Result<(String, Option<Tlv<'a>>, &'a [u8]), String> {
+    let mut spki = Reader::new(spki);
+    let mut spki = Reader::new(spki.expect(SEQUENCE)?);
+    let mut algorithm = Reader::new(spki.expect(SEQUENCE)?);
+    let oid = oid_to_string(algorithm.expect(OID)?);
+    let parameters = (!algorithm.is_empty())
+        .then(|| algorithm.read())
+        .transpose()?;
+    let key = bit_string_bytes(spki.expect(BIT_STRING)?)?;
+    Ok((oid, parameters, key))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_object_identifiers() {
+        assert_eq!(
+            oid_to_string(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01]),
+            "1.2.840.113549.1.1.1"
+        );
+        assert_eq!(oid_to_string(&[0x2b, 0x65, 0x70]), "1.3.101.112");
+        assert_eq!(oid_to_string(&[0x55, 0x1d, 0x11]), "2.5.29.17");
+    }
+
+    #[test]
+    fn reads_long_lengths() {
+        let mut data = vec![SEQUENCE, 0x81, 200];
+        data.extend([7; 200]);
+        data.extend([INTEGER, 1, 5]);
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.expect(SEQUENCE).map(<[u8]>::len), Ok(200));
+        assert_eq!(reader.expect(INTEGER), Ok(&[5][..]));
+        assert!(reader.is_empty());
+        assert!(reader.read().is_err());
+    }
+
+    #[test]
+    fn reads_subject_public_key_infos() {
+        // An Ed25519 key, which has no algorithm parameters
+        let mut spki = vec![SEQUENCE, 42, SEQUENCE, 5, OID, 3, 0x2b, 0x65, 0x70];
+        spki.extend([BIT_STRING, 33, 0]);
+        spki.extend([9; 32]);
+        let (oid, parameters, key) = subject_public_key_info(&spki).unwrap();
+        assert_eq!(oid, "1.3.101.112");
+        assert!(parameters.is_none());
+        assert_eq!(key, &[9; 32][..]);
+    }
+}
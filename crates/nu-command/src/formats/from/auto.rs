@@ -0,0 +1,333 @@
+use super::{
+    delimited::{DelimitedReaderConfig, from_delimited_data},
+    json::convert_string_to_value,
+};
+use crate::{aligned_columns, detect_aligned_table};
+use csv::Trim;
+use nu_engine::command_prelude::*;
+use nu_protocol::PipelineMetadata;
+
+#[derive(Clone)]
+pub struct FromAuto;
+
+impl Command for FromAuto {
+    fn name(&self) -> &str {
+        "from auto"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from auto")
+            .input_output_types(vec![(Type::String, Type::Any)])
+            .named(
+                "threshold",
+                SyntaxShape::Number,
+                "how confident, from 0 to 1, the detection has to be to convert the text (defaults to $env.config.auto_parse.threshold)",
+                Some('t'),
+            )
+            .switch(
+                "detect",
+                "return the detected format and its confidence instead of converting the text",
+                Some('d'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Detect whether text is JSON, CSV, TSV or an aligned table, and parse it accordingly."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Text whose format can't be detected confidently enough is returned unchanged.
+With `$env.config.auto_parse.enabled`, this is done automatically for the output of external commands which is piped into commands that can't take text."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["sniff", "detect", "guess", "parse", "json", "csv", "table"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let threshold: Option<Spanned<f64>> = call.get_flag(engine_state, stack, "threshold")?;
+        let detect = call.has_flag(engine_state, stack, "detect")?;
+        let threshold = match threshold {
+            Some(threshold) if !(0.0..=1.0).contains(&threshold.item) => {
+                return Err(ShellError::InvalidValue {
+                    valid: "a number between 0 and 1".into(),
+                    actual: threshold.item.to_string(),
+                    span: threshold.span,
+                });
+            }
+            Some(threshold) => threshold.item,
+            None => stack.get_config(engine_state).auto_parse.threshold,
+        };
+
+        let metadata = input.metadata().map(|md| md.with_content_type(None));
+        let text = input.collect_string_strict(head)?.0;
+        let detected = sniff(&text);
+
+        if detect {
+            let (format, confidence) = detected
+                .map(|(format, confidence)| (format.name(), confidence))
+                .unwrap_or(("text", 1.0));
+            return Ok(Value::record(
+                record! {
+                    "format" => Value::string(format, head),
+                    "confidence" => Value::float(confidence, head),
+                },
+                head,
+            )
+            .into_pipeline_data());
+        }
+
+        match detected {
+            Some((format, confidence)) if confidence >= threshold => {
+                format.parse(engine_state, call, text, metadata)
+            }
+            _ => Ok(Value::string(text, head).into_pipeline_data_with_metadata(metadata)),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Parse JSON",
+                example: r#"'{"a": 1}' | from auto"#,
+                result: Some(Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                })),
+            },
+            Example {
+                description: "Parse CSV",
+                example: "\"name,size\\nfoo,1\\nbar,2\" | from auto",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "name" => Value::test_string("foo"),
+                        "size" => Value::test_int(1),
+                    }),
+                    Value::test_record(record! {
+                        "name" => Value::test_string("bar"),
+                        "size" => Value::test_int(2),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Text which doesn't look structured is returned unchanged",
+                example: "'just some text' | from auto",
+                result: Some(Value::test_string("just some text")),
+            },
+            Example {
+                description: "Show which format the output of an external command is detected as",
+                example: "^docker ps | from auto --detect",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    JsonLines,
+    Csv,
+    Tsv,
+    Table,
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::JsonLines => "json lines",
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+            Format::Table => "table",
+        }
+    }
+
+    fn parse(
+        self,
+        engine_state: &EngineState,
+        call: &Call,
+        text: String,
+        metadata: Option<PipelineMetadata>,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        match self {
+            Format::Json => Ok(
+                convert_string_to_value(&text, head)?.into_pipeline_data_with_metadata(metadata)
+            ),
+            Format::JsonLines => {
+                let values = non_empty_lines(&text)
+                    .map(|line| convert_string_to_value(line, head))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::list(values, head).into_pipeline_data_with_metadata(metadata))
+            }
+            Format::Csv | Format::Tsv => {
+                let config = DelimitedReaderConfig {
+                    separator: if self == Format::Csv { ',' } else { '\t' },
+                    comment: None,
+                    quote: '"',
+                    escape: None,
+                    noheaders: false,
+                    flexible: false,
+                    no_infer: false,
+                    trim: Trim::All,
+                };
+                from_delimited_data(
+                    config,
+                    Value::string(text, head).into_pipeline_data_with_metadata(metadata),
+                    head,
+                )
+            }
+            Format::Table => detect_aligned_table(
+                engine_state,
+                call,
+                Value::string(text, head).into_pipeline_data(),
+            ),
+        }
+    }
+}
+
+/// Detect the most likely format of `text`, and how confident that detection is.
+fn sniff(text: &str) -> Option<(Format, f64)> {
+    let lines: Vec<&str> = non_empty_lines(text).collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let trimmed = text.trim();
+    if (trimmed.starts_with('{') && trimmed.ends_with('}'))
+        || (trimmed.starts_with('[') && trimmed.ends_with(']'))
+    {
+        if convert_string_to_value(trimmed, Span::unknown()).is_ok() {
+            return Some((Format::Json, 1.0));
+        }
+        if lines.len() > 1 {
+            let objects = lines
+                .iter()
+                .filter(|line| {
+                    line.trim_start().starts_with('{')
+                        && convert_string_to_value(line, Span::unknown()).is_ok()
+                })
+                .count();
+            return Some((Format::JsonLines, objects as f64 / lines.len() as f64));
+        }
+    }
+
+    [
+        delimited_confidence(&lines, ',').map(|confidence| (Format::Csv, confidence)),
+        delimited_confidence(&lines, '\t').map(|confidence| (Format::Tsv, confidence)),
+        table_confidence(&lines).map(|confidence| (Format::Table, confidence)),
+    ]
+    .into_iter()
+    .flatten()
+    // the first format wins ties
+    .reduce(|best, other| if other.1 > best.1 { other } else { best })
+}
+
+/// The share of lines with as many fields as the header.
+fn delimited_confidence(lines: &[&str], separator: char) -> Option<f64> {
+    let header_fields = count_fields(lines[0], separator);
+    if lines.len() < 2 || header_fields < 2 {
+        return None;
+    }
+    let matching = lines[1..]
+        .iter()
+        .filter(|line| count_fields(line, separator) == header_fields)
+        .count();
+    Some(matching as f64 / (lines.len() - 1) as f64)
+}
+
+/// Count the fields of a delimited line, ignoring separators in quotes.
+fn count_fields(line: &str, separator: char) -> usize {
+    let mut quoted = false;
+    let mut fields = 1;
+    for c in line.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == separator && !quoted {
+            fields += 1;
+        }
+    }
+    fields
+}
+
+/// How well the lines fit aligned columns: the share of filled cells, weighted by the share of
+/// lines padded with multiple spaces, which sets tables apart from prose.
+fn table_confidence(lines: &[&str]) -> Option<f64> {
+    if lines.len() < 2 {
+        return None;
+    }
+    let starts = aligned_columns(lines);
+    if starts.len() < 2 {
+        return None;
+    }
+
+    let mut cells = 0;
+    let mut filled = 0;
+    for line in lines {
+        let chars: Vec<char> = line.chars().collect();
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts
+                .get(i + 1)
+                .copied()
+                .unwrap_or(chars.len())
+                .min(chars.len());
+            cells += 1;
+            if chars
+                .get(start.min(end)..end)
+                .is_some_and(|cell| cell.iter().any(|c| !c.is_whitespace()))
+            {
+                filled += 1;
+            }
+        }
+    }
+    let padded = lines
+        .iter()
+        .filter(|line| line.trim_end().contains("  ") || line.contains('\t'))
+        .count();
+
+    Some(filled as f64 / cells as f64 * padded as f64 / lines.len() as f64)
+}
+
+fn non_empty_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().filter(|line| !line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FromAuto {})
+    }
+
+    #[test]
+    fn sniffs_formats() {
+        let format = |text| sniff(text).map(|(format, _)| format.name());
+        assert_eq!(format("[1, 2]"), Some("json"));
+        assert_eq!(format("{\"a\": 1}\n{\"a\": 2}"), Some("json lines"));
+        assert_eq!(format("a\tb\n1\t2"), Some("tsv"));
+        assert_eq!(
+            format("NAME    STATUS\nweb     Running\ndb      Pending"),
+            Some("table")
+        );
+    }
+
+    #[test]
+    fn prose_is_not_a_table() {
+        let confidence = sniff("This is just text.\nIt has two lines.")
+            .map(|(_, confidence)| confidence)
+            .unwrap_or(0.0);
+        assert!(confidence < 0.9);
+    }
+}
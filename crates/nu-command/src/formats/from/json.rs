@@ -148,7 +148,8 @@ fn read_json_lines(
     ListStream::new(iter, span, signals)
 }
 
-fn convert_nujson_to_value(value: nu_json::Value, span: Span) -> Value {
+/// Convert a parsed JSON value, where integers larger than an `i64` become errors.
+pub fn convert_nujson_to_value(value: nu_json::Value, span: Span) -> Value {
     match value {
         nu_json::Value::Array(array) => Value::list(
             array
@@ -0,0 +1,529 @@
+use super::yaml::from_yaml_string_to_value;
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct FromMd;
+
+impl Command for FromMd {
+    fn name(&self) -> &str {
+        "from md"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from md")
+            .input_output_types(vec![(Type::String, Type::table())])
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Parse text as Markdown and create a table of its blocks."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each block is a record with a `type` and the fields of that type:
+  front_matter: data, the parsed YAML between the leading `---` lines
+  heading:      level, text
+  paragraph:    text
+  code:         language, code
+  table:        align, a list of left, center, right or null for each column, and rows
+  list:         ordered, items
+  quote:        text
+  rule:         no other fields
+
+Only blocks are parsed, the text of paragraphs, headings, list items and table cells keeps its
+inline Markdown. `to md` turns such a table back into a document. `open` keeps `.md` files as
+text, so they are parsed with `open file.md | from md`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["markdown", "front matter", "parse", "document"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Parse a heading and a paragraph",
+                example: r##""# Nushell\n\nA *new* type of shell." | from md"##,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "type" => Value::test_string("heading"),
+                        "level" => Value::test_int(1),
+                        "text" => Value::test_string("Nushell"),
+                    }),
+                    Value::test_record(record! {
+                        "type" => Value::test_string("paragraph"),
+                        "text" => Value::test_string("A *new* type of shell."),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Parse a table with aligned columns",
+                example: r#""| name | size |\n| :--- | ---: |\n| a | 1 |" | from md"#,
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "type" => Value::test_string("table"),
+                    "align" => Value::test_list(vec![
+                        Value::test_string("left"),
+                        Value::test_string("right"),
+                    ]),
+                    "rows" => Value::test_list(vec![Value::test_record(record! {
+                        "name" => Value::test_string("a"),
+                        "size" => Value::test_string("1"),
+                    })]),
+                })])),
+            },
+            Example {
+                description: "Get the front matter of a document",
+                example: "open post.md | from md | where type == front_matter | get 0.data",
+                result: None,
+            },
+            Example {
+                description: "Get the code blocks of a document",
+                example: "open README.md | from md | where type == code and language == nu | get code",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let (text, span, metadata) = input.collect_string_strict(head)?;
+        let blocks = parse_blocks(&text, head, span)?;
+        Ok(Value::list(blocks, head)
+            .into_pipeline_data_with_metadata(metadata.map(|md| md.with_content_type(None))))
+    }
+}
+
+fn parse_blocks(text: &str, head: Span, span: Span) -> Result<Vec<Value>, ShellError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut blocks = vec![];
+    let mut i = 0;
+
+    if let Some((data, end)) = front_matter(&lines, head, span) {
+        blocks.push(Value::record(
+            record! {
+                "type" => Value::string("front_matter", head),
+                "data" => data,
+            },
+            head,
+        ));
+        i = end;
+    }
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some((level, text)) = heading(trimmed) {
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("heading", head),
+                    "level" => Value::int(level as i64, head),
+                    "text" => Value::string(text, head),
+                },
+                head,
+            ));
+            i += 1;
+        } else if let Some((fence, language)) = code_fence(trimmed) {
+            let mut code = vec![];
+            i += 1;
+            while i < lines.len() && !closes_fence(lines[i], fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            // Skip the closing fence, an unclosed block goes to the end of the document
+            i += 1;
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("code", head),
+                    "language" => match language {
+                        Some(language) => Value::string(language, head),
+                        None => Value::nothing(head),
+                    },
+                    "code" => Value::string(code.join("\n"), head),
+                },
+                head,
+            ));
+        } else if is_rule(trimmed) {
+            blocks.push(Value::record(
+                record! { "type" => Value::string("rule", head) },
+                head,
+            ));
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut text = vec![];
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let quoted = &lines[i].trim_start()[1..];
+                text.push(quoted.strip_prefix(' ').unwrap_or(quoted));
+                i += 1;
+            }
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("quote", head),
+                    "text" => Value::string(text.join("\n"), head),
+                },
+                head,
+            ));
+        } else if let Some((ordered, _)) = list_item(trimmed) {
+            let mut items: Vec<String> = vec![];
+            while i < lines.len() {
+                let line = lines[i];
+                match list_item(line.trim_start()) {
+                    Some((item_ordered, text)) if item_ordered == ordered => {
+                        items.push(text.to_string());
+                    }
+                    // Indented lines continue the last item
+                    _ if !line.trim().is_empty() && line.starts_with([' ', '\t']) => {
+                        if let Some(item) = items.last_mut() {
+                            item.push('\n');
+                            item.push_str(line.trim());
+                        }
+                    }
+                    _ => break,
+                }
+                i += 1;
+            }
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("list", head),
+                    "ordered" => Value::bool(ordered, head),
+                    "items" => Value::list(
+                        items.into_iter().map(|item| Value::string(item, head)).collect(),
+                        head,
+                    ),
+                },
+                head,
+            ));
+        } else if let Some(align) = lines
+            .get(i + 1)
+            .filter(|_| line.contains('|'))
+            .and_then(|delimiter| table_delimiter(delimiter))
+        {
+            let headers = table_cells(line);
+            let mut rows = vec![];
+            i += 2;
+            while i < lines.len() && lines[i].contains('|') && !lines[i].trim().is_empty() {
+                let mut cells = table_cells(lines[i]).into_iter();
+                let row = headers
+                    .iter()
+                    .map(|header| {
+                        let cell = cells.next().unwrap_or_default();
+                        (header.clone(), Value::string(cell, head))
+                    })
+                    .collect();
+                rows.push(Value::record(row, head));
+                i += 1;
+            }
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("table", head),
+                    "align" => Value::list(
+                        align
+                            .into_iter()
+                            .map(|align| match align {
+                                Some(align) => Value::string(align, head),
+                                None => Value::nothing(head),
+                            })
+                            .collect(),
+                        head,
+                    ),
+                    "rows" => Value::list(rows, head),
+                },
+                head,
+            ));
+        } else {
+            let mut text = vec![line.trim()];
+            i += 1;
+            while i < lines.len() && !starts_block(&lines, i) {
+                text.push(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Value::record(
+                record! {
+                    "type" => Value::string("paragraph", head),
+                    "text" => Value::string(text.join("\n"), head),
+                },
+                head,
+            ));
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// The YAML between a leading `---` line and the next `---` or `...` line, and the index of the
+/// line after it.
+///
+/// A document can also start with a rule, so the lines are only front matter when they are a
+/// YAML mapping.
+fn front_matter(lines: &[&str], head: Span, span: Span) -> Option<(Value, usize)> {
+    if lines.first().map(|line| line.trim_end()) != Some("---") {
+        return None;
+    }
+    let end = lines
+        .iter()
+        .skip(1)
+        .position(|line| matches!(line.trim_end(), "---" | "..."))?
+        + 1;
+    match from_yaml_string_to_value(&lines[1..end].join("\n"), head, span) {
+        Ok(data @ Value::Record { .. }) => Some((data, end + 1)),
+        _ => None,
+    }
+}
+
+/// Whether the line at `i` ends a paragraph.
+fn starts_block(lines: &[&str], i: usize) -> bool {
+    let trimmed = lines[i].trim_start();
+    trimmed.is_empty()
+        || heading(trimmed).is_some()
+        || code_fence(trimmed).is_some()
+        || is_rule(trimmed)
+        || trimmed.starts_with('>')
+        || list_item(trimmed).is_some()
+        || (lines[i].contains('|')
+            && lines
+                .get(i + 1)
+                .and_then(|delimiter| table_delimiter(delimiter))
+                .is_some())
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    // A closing sequence of `#` is only one if there's a space before it
+    let text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    let text = if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        without_closing.trim_end()
+    } else {
+        text
+    };
+    Some((level, text))
+}
+
+/// The fence which opens a code block, and its language.
+fn code_fence(line: &str) -> Option<(&str, Option<&str>)> {
+    let fence_char = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = line.chars().take_while(|c| *c == fence_char).count();
+    if len < 3 {
+        return None;
+    }
+    let (fence, info) = line.split_at(len);
+    let language = info.split_whitespace().next();
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    Some((fence, language))
+}
+
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let line = line.trim();
+    line.len() >= fence.len() && line.chars().all(|c| fence.starts_with(c))
+}
+
+fn is_rule(line: &str) -> bool {
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    line.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|rule| line.chars().all(|c| c == *rule))
+}
+
+/// Whether the line is an item of an ordered list, and its text.
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some((false, text.trim_start()));
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let text = line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))?;
+    (1..=9)
+        .contains(&digits)
+        .then_some((true, text.trim_start()))
+}
+
+/// The alignments of the columns if `line` is the delimiter row of a table.
+fn table_delimiter(line: &str) -> Option<Vec<Option<&'static str>>> {
+    let cells = table_cells(line);
+    if cells.is_empty() {
+        return None;
+    }
+    cells
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Some("center"),
+                (true, false) => Some("left"),
+                (false, true) => Some("right"),
+                (false, false) => None,
+            })
+        })
+        .collect()
+}
+
+/// The cells of a table row, split at the pipes which aren't escaped.
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FromMd {})
+    }
+
+    fn parse(text: &str) -> Vec<Value> {
+        parse_blocks(text, Span::test_data(), Span::test_data()).expect("valid markdown")
+    }
+
+    #[test]
+    fn parses_front_matter() {
+        let blocks = parse("---\ntitle: Post\ntags: [a, b]\n---\n# Post");
+
+        assert_eq!(
+            blocks[0],
+            Value::test_record(record! {
+                "type" => Value::test_string("front_matter"),
+                "data" => Value::test_record(record! {
+                    "title" => Value::test_string("Post"),
+                    "tags" => Value::test_list(vec![
+                        Value::test_string("a"),
+                        Value::test_string("b"),
+                    ]),
+                }),
+            })
+        );
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn leading_rule_is_not_front_matter() {
+        let blocks = parse("---\nJust some text.\n\n---\n# Post");
+
+        assert_eq!(
+            blocks[0],
+            Value::test_record(record! { "type" => Value::test_string("rule") })
+        );
+        assert_eq!(blocks.len(), 4);
+    }
+
+    #[test]
+    fn parses_code_blocks() {
+        let blocks = parse("```nu\nls | where size > 1kb\n\n# not a heading\n```\n~~~\nplain\n~~~");
+
+        assert_eq!(
+            blocks,
+            vec![
+                Value::test_record(record! {
+                    "type" => Value::test_string("code"),
+                    "language" => Value::test_string("nu"),
+                    "code" => Value::test_string("ls | where size > 1kb\n\n# not a heading"),
+                }),
+                Value::test_record(record! {
+                    "type" => Value::test_string("code"),
+                    "language" => Value::test_nothing(),
+                    "code" => Value::test_string("plain"),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_table_with_escaped_pipes() {
+        let blocks = parse("a | b\n--- | :---:\n`x \\| y` | 2\n3");
+
+        assert_eq!(
+            blocks,
+            vec![
+                Value::test_record(record! {
+                    "type" => Value::test_string("table"),
+                    "align" => Value::test_list(vec![
+                        Value::test_nothing(),
+                        Value::test_string("center"),
+                    ]),
+                    "rows" => Value::test_list(vec![Value::test_record(record! {
+                        "a" => Value::test_string("`x | y`"),
+                        "b" => Value::test_string("2"),
+                    })]),
+                }),
+                Value::test_record(record! {
+                    "type" => Value::test_string("paragraph"),
+                    "text" => Value::test_string("3"),
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_lists_quotes_and_rules() {
+        let blocks = parse("- one\n  more\n- two\n\n1. first\n\n> quoted\n> text\n\n***");
+
+        assert_eq!(
+            blocks,
+            vec![
+                Value::test_record(record! {
+                    "type" => Value::test_string("list"),
+                    "ordered" => Value::test_bool(false),
+                    "items" => Value::test_list(vec![
+                        Value::test_string("one\nmore"),
+                        Value::test_string("two"),
+                    ]),
+                }),
+                Value::test_record(record! {
+                    "type" => Value::test_string("list"),
+                    "ordered" => Value::test_bool(true),
+                    "items" => Value::test_list(vec![Value::test_string("first")]),
+                }),
+                Value::test_record(record! {
+                    "type" => Value::test_string("quote"),
+                    "text" => Value::test_string("quoted\ntext"),
+                }),
+                Value::test_record(record! { "type" => Value::test_string("rule") }),
+            ]
+        );
+    }
+}
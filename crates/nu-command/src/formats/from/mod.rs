@@ -1,11 +1,15 @@
+mod auto;
 mod command;
 mod csv;
 mod delimited;
 mod json;
+mod jwt;
+mod md;
 mod msgpack;
 mod msgpackz;
 mod nuon;
 mod ods;
+mod pem;
 mod ssv;
 mod toml;
 mod tsv;
@@ -15,12 +19,20 @@ mod yaml;
 
 pub use self::csv::FromCsv;
 pub use self::toml::FromToml;
+pub use auto::FromAuto;
 pub use command::From;
-pub use json::FromJson;
+pub use json::{FromJson, convert_nujson_to_value};
+#[cfg(feature = "network")]
+pub(crate) use jwt::DecodedJwt;
+pub use jwt::FromJwt;
+pub use md::FromMd;
 pub use msgpack::FromMsgpack;
 pub use msgpackz::FromMsgpackz;
 pub use nuon::FromNuon;
 pub use ods::FromOds;
+pub use pem::FromPem;
+#[cfg(feature = "network")]
+pub(crate) use pem::pem_to_certificates;
 pub use ssv::FromSsv;
 pub use tsv::FromTsv;
 pub use xlsx::FromXlsx;
@@ -28,5 +40,4 @@ pub use xml::FromXml;
 pub use yaml::FromYaml;
 pub use yaml::FromYml;
 
-#[cfg(feature = "sqlite")]
 pub(crate) use json::convert_string_to_value as convert_json_string_to_value;
@@ -0,0 +1,88 @@
+use crate::formats::{certificate_to_value, der};
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct FromPem;
+
+impl Command for FromPem {
+    fn name(&self) -> &str {
+        "from pem"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from pem")
+            .input_output_types(vec![(Type::String, Type::table())])
+            .allow_variants_without_examples(true)
+            .category(Category::Formats)
+    }
+
+    fn description(&self) -> &str {
+        "Parse the X.509 certificates in PEM text into a table."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each certificate is a row with its subject, issuer, subject alternative names, validity and
+key. The expires_in column is the time until the certificate expires, which is negative when it
+has. The other blocks of the PEM, like private keys, are skipped."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["certificate", "x509", "tls", "ssl", "crt", "expiry"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let (text, span, metadata) = input.collect_string_strict(call.head)?;
+        let certificates = pem_to_certificates(&text, span)?;
+        Ok(Value::list(certificates, span)
+            .into_pipeline_data_with_metadata(metadata.map(|md| md.with_content_type(None))))
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "List the certificates of a bundle",
+                example: "open --raw /etc/ssl/certs/ca-certificates.crt | from pem | select subject not_after",
+                result: None,
+            },
+            Example {
+                description: "Find the certificates which expire within a month",
+                example: "glob **/*.pem | each { open --raw $in | from pem } | flatten | where expires_in < 30day",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The certificates of the PEM blocks labelled `CERTIFICATE`.
+pub(crate) fn pem_to_certificates(text: &str, span: Span) -> Result<Vec<Value>, ShellError> {
+    let invalid = |msg: String| ShellError::CantConvert {
+        to_type: "certificate".into(),
+        from_type: "string".into(),
+        span,
+        help: Some(msg),
+    };
+    der::pem_blocks(text)
+        .map_err(invalid)?
+        .into_iter()
+        .filter(|pem| matches!(pem.label.as_str(), "CERTIFICATE" | "TRUSTED CERTIFICATE"))
+        .map(|pem| certificate_to_value(&pem.der, span).map_err(invalid))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FromPem {})
+    }
+}
@@ -0,0 +1,559 @@
+use crate::formats::der::{self, INTEGER, OID, Reader, SEQUENCE};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use nu_protocol::{Record, ShellError, Span, Value};
+use ring::{
+    hmac,
+    rand::SystemRandom,
+    signature::{self, EcdsaKeyPair, Ed25519KeyPair, RsaKeyPair, UnparsedPublicKey},
+};
+
+const RSA: &str = "1.2.840.113549.1.1.1";
+const EC: &str = "1.2.840.10045.2.1";
+const P256: &str = "1.2.840.10045.3.1.7";
+const P384: &str = "1.3.132.0.34";
+const ED25519: &str = "1.3.101.112";
+
+/// The signature algorithms of JSON web tokens, named as in their `alg` header.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Algorithm {
+    HS256,
+    HS384,
+    HS512,
+    RS256,
+    RS384,
+    RS512,
+    PS256,
+    PS384,
+    PS512,
+    ES256,
+    ES384,
+    EdDSA,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 12] = [
+        Algorithm::HS256,
+        Algorithm::HS384,
+        Algorithm::HS512,
+        Algorithm::RS256,
+        Algorithm::RS384,
+        Algorithm::RS512,
+        Algorithm::PS256,
+        Algorithm::PS384,
+        Algorithm::PS512,
+        Algorithm::ES256,
+        Algorithm::ES384,
+        Algorithm::EdDSA,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::HS256 => "HS256",
+            Algorithm::HS384 => "HS384",
+            Algorithm::HS512 => "HS512",
+            Algorithm::RS256 => "RS256",
+            Algorithm::RS384 => "RS384",
+            Algorithm::RS512 => "RS512",
+            Algorithm::PS256 => "PS256",
+            Algorithm::PS384 => "PS384",
+            Algorithm::PS512 => "PS512",
+            Algorithm::ES256 => "ES256",
+            Algorithm::ES384 => "ES384",
+            Algorithm::EdDSA => "EdDSA",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)
+    }
+
+    /// The algorithm of an `--alg` argument.
+    pub fn from_arg(name: &str, span: Span) -> Result<Self, ShellError> {
+        Self::from_name(name).ok_or_else(|| ShellError::IncorrectValue {
+            msg: format!(
+                "unsupported algorithm, expected one of {}",
+                Self::ALL.map(Self::name).join(", ")
+            ),
+            val_span: span,
+            call_span: span,
+        })
+    }
+
+    fn hmac(self) -> Option<hmac::Algorithm> {
+        match self {
+            Algorithm::HS256 => Some(hmac::HMAC_SHA256),
+            Algorithm::HS384 => Some(hmac::HMAC_SHA384),
+            Algorithm::HS512 => Some(hmac::HMAC_SHA512),
+            _ => None,
+        }
+    }
+
+    fn rsa_signing(self) -> Option<&'static dyn signature::RsaEncoding> {
+        match self {
+            Algorithm::RS256 => Some(&signature::RSA_PKCS1_SHA256),
+            Algorithm::RS384 => Some(&signature::RSA_PKCS1_SHA384),
+            Algorithm::RS512 => Some(&signature::RSA_PKCS1_SHA512),
+            Algorithm::PS256 => Some(&signature::RSA_PSS_SHA256),
+            Algorithm::PS384 => Some(&signature::RSA_PSS_SHA384),
+            Algorithm::PS512 => Some(&signature::RSA_PSS_SHA512),
+            _ => None,
+        }
+    }
+
+    fn rsa_verification(self) -> Option<&'static signature::RsaParameters> {
+        match self {
+            Algorithm::RS256 => Some(&signature::RSA_PKCS1_2048_8192_SHA256),
+            Algorithm::RS384 => Some(&signature::RSA_PKCS1_2048_8192_SHA384),
+            Algorithm::RS512 => Some(&signature::RSA_PKCS1_2048_8192_SHA512),
+            Algorithm::PS256 => Some(&signature::RSA_PSS_2048_8192_SHA256),
+            Algorithm::PS384 => Some(&signature::RSA_PSS_2048_8192_SHA384),
+            Algorithm::PS512 => Some(&signature::RSA_PSS_2048_8192_SHA512),
+            _ => None,
+        }
+    }
+
+    /// The curve of the ECDSA algorithms.
+    fn curve(self) -> Option<Curve> {
+        match self {
+            Algorithm::ES256 => Some(Curve::P256),
+            Algorithm::ES384 => Some(Curve::P384),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Curve {
+    P256,
+    P384,
+}
+
+impl Curve {
+    fn from_oid(oid: &str) -> Result<Self, String> {
+        match oid {
+            P256 => Ok(Curve::P256),
+            P384 => Ok(Curve::P384),
+            _ => Err(format!("the elliptic curve {oid} is not supported")),
+        }
+    }
+
+    fn from_jwk(crv: &str) -> Result<Self, String> {
+        match crv {
+            "P-256" => Ok(Curve::P256),
+            "P-384" => Ok(Curve::P384),
+            _ => Err(format!("the elliptic curve {crv} is not supported")),
+        }
+    }
+
+    fn algorithm(self) -> Algorithm {
+        match self {
+            Curve::P256 => Algorithm::ES256,
+            Curve::P384 => Algorithm::ES384,
+        }
+    }
+
+    fn signing(self) -> &'static signature::EcdsaSigningAlgorithm {
+        match self {
+            Curve::P256 => &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            Curve::P384 => &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+        }
+    }
+
+    fn verification(self) -> &'static signature::EcdsaVerificationAlgorithm {
+        match self {
+            Curve::P256 => &signature::ECDSA_P256_SHA256_FIXED,
+            Curve::P384 => &signature::ECDSA_P384_SHA384_FIXED,
+        }
+    }
+}
+
+/// A key to check the signatures of tokens with.
+pub(super) struct VerifyingKey {
+    kind: PublicKey,
+    /// The `kid` of a JWK, which tokens name the key they're signed with by.
+    pub id: Option<String>,
+    /// The `alg` of a JWK, which is the only algorithm the key can be used with.
+    pub algorithm: Option<Algorithm>,
+}
+
+enum PublicKey {
+    Secret(Vec<u8>),
+    Rsa { n: Vec<u8>, e: Vec<u8> },
+    Ec { curve: Curve, point: Vec<u8> },
+    Ed25519(Vec<u8>),
+}
+
+impl VerifyingKey {
+    /// The keys of a `--key` argument, which is a secret, PEM, a JWK or a JWK set.
+    pub fn from_value(value: &Value) -> Result<Vec<Self>, String> {
+        match value {
+            Value::String { val, .. } if val.contains("-----BEGIN") => der::pem_blocks(val)?
+                .iter()
+                .map(|pem| Self::from_pem(&pem.label, &pem.der))
+                .collect(),
+            Value::String { val, .. } => Ok(vec![Self::secret(val.as_bytes().to_vec())]),
+            Value::Binary { val, .. } => Ok(vec![Self::secret(val.clone())]),
+            Value::Record { val, .. } => match val.get("keys") {
+                Some(Value::List { vals, .. }) => vals
+                    .iter()
+                    .filter_map(|jwk| match jwk {
+                        Value::Record { val, .. } => Some(Self::from_jwk(val)),
+                        _ => None,
+                    })
+                    // Skip the keys of unsupported types in a JWK set
+                    .filter(|key| !matches!(key, Err(err) if err.starts_with("unsupported")))
+                    .collect(),
+                _ => Self::from_jwk(val).map(|key| vec![key]),
+            },
+            other => Err(format!("a key can't be {}", other.get_type())),
+        }
+    }
+
+    fn secret(secret: Vec<u8>) -> Self {
+        Self {
+            kind: PublicKey::Secret(secret),
+            id: None,
+            algorithm: None,
+        }
+    }
+
+    fn from_pem(label: &str, der: &[u8]) -> Result<Self, String> {
+        let kind = match label {
+            "PUBLIC KEY" => public_key_info(der)?,
+            "RSA PUBLIC KEY" => rsa_public_key(der)?,
+            "CERTIFICATE" => public_key_info(certificate_public_key_info(der)?)?,
+            _ if label.ends_with("PRIVATE KEY") => {
+                return Err("tokens are verified with public keys, not private keys".into());
+            }
+            _ => return Err(format!("a {label} is not a key")),
+        };
+        Ok(Self {
+            kind,
+            id: None,
+            algorithm: None,
+        })
+    }
+
+    fn from_jwk(jwk: &Record) -> Result<Self, String> {
+        let field = |name: &str| match jwk.get(name) {
+            Some(Value::String { val, .. }) => Ok(val.as_str()),
+            _ => Err(format!("the JWK has no {name}")),
+        };
+        let bytes = |name: &str| {
+            URL_SAFE_NO_PAD
+                .decode(field(name)?)
+                .map_err(|err| format!("the {name} of the JWK is not valid base64url: {err}"))
+        };
+        let kind = match field("kty")? {
+            "oct" => PublicKey::Secret(bytes("k")?),
+            "RSA" => PublicKey::Rsa {
+                n: bytes("n")?,
+                e: bytes("e")?,
+            },
+            "EC" => {
+                let curve = Curve::from_jwk(field("crv")?)?;
+                let mut point = vec![0x04];
+                point.extend(bytes("x")?);
+                point.extend(bytes("y")?);
+                PublicKey::Ec { curve, point }
+            }
+            "OKP" if field("crv")? == "Ed25519" => PublicKey::Ed25519(bytes("x")?),
+            kty => return Err(format!("unsupported JWK type {kty}")),
+        };
+        Ok(Self {
+            kind,
+            id: field("kid").ok().map(String::from),
+            algorithm: field("alg").ok().and_then(Algorithm::from_name),
+        })
+    }
+
+    /// Whether the key is for the algorithm, so that a public key can't be used as an HMAC secret.
+    pub fn supports(&self, algorithm: Algorithm) -> bool {
+        let matches = match &self.kind {
+            PublicKey::Secret(_) => algorithm.hmac().is_some(),
+            PublicKey::Rsa { .. } => algorithm.rsa_verification().is_some(),
+            PublicKey::Ec { curve, .. } => algorithm.curve() == Some(*curve),
+            PublicKey::Ed25519(_) => algorithm == Algorithm::EdDSA,
+        };
+        matches && self.algorithm.is_none_or(|only| only == algorithm)
+    }
+
+    pub fn verify(&self, algorithm: Algorithm, message: &[u8], signature: &[u8]) -> bool {
+        match (&self.kind, algorithm.hmac(), algorithm.rsa_verification()) {
+            (PublicKey::Secret(secret), Some(hmac), _) => {
+                hmac::verify(&hmac::Key::new(hmac, secret), message, signature).is_ok()
+            }
+            (PublicKey::Rsa { n, e }, _, Some(parameters)) => signature::RsaPublicKeyComponents {
+                n: der::unsigned_integer(n),
+                e: der::unsigned_integer(e),
+            }
+            .verify(parameters, message, signature)
+            .is_ok(),
+            (PublicKey::Ec { curve, point }, ..) if algorithm.curve() == Some(*curve) => {
+                UnparsedPublicKey::new(curve.verification(), point)
+                    .verify(message, signature)
+                    .is_ok()
+            }
+            (PublicKey::Ed25519(key), ..) if algorithm == Algorithm::EdDSA => {
+                UnparsedPublicKey::new(&signature::ED25519, key)
+                    .verify(message, signature)
+                    .is_ok()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A key to sign tokens with.
+pub(super) enum SigningKey {
+    Secret(Vec<u8>),
+    Rsa(Box<RsaKeyPair>),
+    Ec { curve: Curve, pkcs8: Vec<u8> },
+    Ed25519(Box<Ed25519KeyPair>),
+}
+
+impl SigningKey {
+    /// The key of a `--key` argument, which is a secret or a PEM private key.
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String { val, .. } if val.contains("-----BEGIN") => {
+                let blocks = der::pem_blocks(val)?;
+                let [pem] = &blocks[..] else {
+                    return Err("expected one PEM private key".into());
+                };
+                Self::from_pem(&pem.label, &pem.der)
+            }
+            Value::String { val, .. } => Ok(SigningKey::Secret(val.as_bytes().to_vec())),
+            Value::Binary { val, .. } => Ok(SigningKey::Secret(val.clone())),
+            other => Err(format!("a key can't be {}", other.get_type())),
+        }
+    }
+
+    fn from_pem(label: &str, der: &[u8]) -> Result<Self, String> {
+        let rejected = |err: ring::error::KeyRejected| format!("the private key is invalid: {err}");
+        match label {
+            "PRIVATE KEY" => {
+                let mut info = Reader::new(der);
+                let mut info = Reader::new(info.expect(SEQUENCE)?);
+                info.expect(INTEGER)?;
+                let mut algorithm = Reader::new(info.expect(SEQUENCE)?);
+                match der::oid_to_string(algorithm.expect(OID)?).as_str() {
+                    RSA => RsaKeyPair::from_pkcs8(der)
+                        .map(|pair| SigningKey::Rsa(Box::new(pair)))
+                        .map_err(rejected),
+                    EC => {
+                        let curve = Curve::from_oid(&der::oid_to_string(algorithm.expect(OID)?))?;
+                        // Check the key now, instead of when signing
+                        EcdsaKeyPair::from_pkcs8(curve.signing(), der, &SystemRandom::new())
+                            .map_err(rejected)?;
+                        Ok(SigningKey::Ec {
+                            curve,
+                            pkcs8: der.to_vec(),
+                        })
+                    }
+                    ED25519 => Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+                        .map(|pair| SigningKey::Ed25519(Box::new(pair)))
+                        .map_err(rejected),
+                    oid => Err(format!("keys of the algorithm {oid} are not supported")),
+                }
+            }
+            "RSA PRIVATE KEY" => RsaKeyPair::from_der(der)
+                .map(|pair| SigningKey::Rsa(Box::new(pair)))
+                .map_err(rejected),
+            "EC PRIVATE KEY" => Err("EC private keys must be in PKCS #8, which `openssl pkcs8 -topk8 -nocrypt` converts them to".into()),
+            "ENCRYPTED PRIVATE KEY" => Err("the private key is encrypted".into()),
+            _ => Err(format!("a {label} is not a private key")),
+        }
+    }
+
+    /// The algorithm tokens are signed with when none is given.
+    pub fn default_algorithm(&self) -> Algorithm {
+        match self {
+            SigningKey::Secret(_) => Algorithm::HS256,
+            SigningKey::Rsa(_) => Algorithm::RS256,
+            SigningKey::Ec { curve, .. } => curve.algorithm(),
+            SigningKey::Ed25519(_) => Algorithm::EdDSA,
+        }
+    }
+
+    pub fn sign(&self, algorithm: Algorithm, message: &[u8]) -> Result<Vec<u8>, String> {
+        let unsupported = || {
+            format!(
+                "the key can't sign with {}, only with {}",
+                algorithm.name(),
+                self.default_algorithm().name()
+            )
+        };
+        let failed = |_| "the message can't be signed".to_string();
+        match self {
+            SigningKey::Secret(secret) => {
+                let hmac = algorithm.hmac().ok_or_else(unsupported)?;
+                Ok(hmac::sign(&hmac::Key::new(hmac, secret), message)
+                    .as_ref()
+                    .to_vec())
+            }
+            SigningKey::Rsa(pair) => {
+                let encoding = algorithm.rsa_signing().ok_or_else(unsupported)?;
+                let mut signature = vec![0; pair.public().modulus_len()];
+                pair.sign(encoding, &SystemRandom::new(), message, &mut signature)
+                    .map_err(failed)?;
+                Ok(signature)
+            }
+            SigningKey::Ec { curve, pkcs8 } => {
+                if algorithm.curve() != Some(*curve) {
+                    return Err(unsupported());
+                }
+                let rng = SystemRandom::new();
+                let pair = EcdsaKeyPair::from_pkcs8(curve.signing(), pkcs8, &rng)
+                    .map_err(|err| format!("the private key is invalid: {err}"))?;
+                Ok(pair.sign(&rng, message).map_err(failed)?.as_ref().to_vec())
+            }
+            SigningKey::Ed25519(pair) => {
+                if algorithm != Algorithm::EdDSA {
+                    return Err(unsupported());
+                }
+                Ok(pair.sign(message).as_ref().to_vec())
+            }
+        }
+    }
+
+    /// The public key of a private key, to check its signatures with.
+    #[cfg(test)]
+    fn verifying_key(&self) -> VerifyingKey {
+        use ring::signature::KeyPair;
+
+        let kind = match self {
+            SigningKey::Secret(secret) => PublicKey::Secret(secret.clone()),
+            SigningKey::Rsa(pair) => {
+                rsa_public_key(pair.public().as_ref()).expect("RSA public key")
+            }
+            SigningKey::Ec { curve, pkcs8 } => {
+                let pair = EcdsaKeyPair::from_pkcs8(curve.signing(), pkcs8, &SystemRandom::new())
+                    .expect("valid key");
+                PublicKey::Ec {
+                    curve: *curve,
+                    point: pair.public_key().as_ref().to_vec(),
+                }
+            }
+            SigningKey::Ed25519(pair) => PublicKey::Ed25519(pair.public_key().as_ref().to_vec()),
+        };
+        VerifyingKey {
+            kind,
+            id: None,
+            algorithm: None,
+        }
+    }
+}
+
+/// The key of a SubjectPublicKeyInfo, which is a `PUBLIC KEY` in PEM.
+fn public_key_info(der: &[u8]) -> Result<PublicKey, String> {
+    let (oid, parameters, key) = der::subject_public_key_info(der)?;
+    match oid.as_str() {
+        RSA => rsa_public_key(key),
+        EC => {
+            let curve = match parameters {
+                Some(parameters) if parameters.tag == OID => {
+                    Curve::from_oid(&der::oid_to_string(parameters.contents))?
+                }
+                _ => return Err("the elliptic curve key has no named curve".into()),
+            };
+            Ok(PublicKey::Ec {
+                curve,
+                point: key.to_vec(),
+            })
+        }
+        ED25519 => Ok(PublicKey::Ed25519(key.to_vec())),
+        oid => Err(format!("keys of the algorithm {oid} are not supported")),
+    }
+}
+
+/// The modulus and exponent of an RSAPublicKey, which is an `RSA PUBLIC KEY` in PEM.
+fn rsa_public_key(der: &[u8]) -> Result<PublicKey, String> {
+    let mut key = Reader::new(der);
+    let mut key = Reader::new(key.expect(SEQUENCE)?);
+    Ok(PublicKey::Rsa {
+        n: key.expect(INTEGER)?.to_vec(),
+        e: key.expect(INTEGER)?.to_vec(),
+    })
+}
+
+/// The SubjectPublicKeyInfo of an X.509 certificate.
+fn certificate_public_key_info(der: &[u8]) -> Result<&[u8], String> {
+    let mut certificate = Reader::new(der);
+    let mut certificate = Reader::new(certificate.expect(SEQUENCE)?);
+    let mut tbs = Reader::new(certificate.expect(SEQUENCE)?);
+    // The version is explicitly tagged with [0], and left out for version 1
+    tbs.optional(0xa0)?;
+    // The serial number, signature algorithm, issuer, validity and subject
+    tbs.expect(INTEGER)?;
+    for _ in 0..4 {
+        tbs.expect(SEQUENCE)?;
+    }
+    let spki = tbs.read()?;
+    if spki.tag != SEQUENCE {
+        return Err("the certificate has no public key".into());
+    }
+    Ok(spki.encoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signs_and_verifies_with_secrets() {
+        let key = SigningKey::from_value(&Value::test_string("secret")).unwrap();
+        assert!(key.sign(Algorithm::RS256, b"message").is_err());
+        let signature = key.sign(Algorithm::HS384, b"message").unwrap();
+        let verifying = key.verifying_key();
+        assert!(verifying.verify(Algorithm::HS384, b"message", &signature));
+        assert!(!verifying.verify(Algorithm::HS384, b"massage", &signature));
+        assert!(!verifying.supports(Algorithm::ES256));
+    }
+
+    #[test]
+    fn signs_and_verifies_with_generated_keys() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&signature::ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            .unwrap();
+        let ec = SigningKey::from_pem("PRIVATE KEY", pkcs8.as_ref()).unwrap();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let ed25519 = SigningKey::from_pem("PRIVATE KEY", pkcs8.as_ref()).unwrap();
+
+        for key in [ec, ed25519] {
+            let algorithm = key.default_algorithm();
+            let signature = key.sign(algorithm, b"message").unwrap();
+            let verifying = key.verifying_key();
+            assert!(verifying.supports(algorithm));
+            assert!(!verifying.supports(Algorithm::HS256));
+            assert!(verifying.verify(algorithm, b"message", &signature));
+            assert!(!verifying.verify(algorithm, b"massage", &signature));
+        }
+    }
+
+    #[test]
+    fn reads_json_web_keys() {
+        let jwk = Value::test_record(nu_protocol::record! {
+            "kty" => Value::test_string("oct"),
+            "kid" => Value::test_string("one"),
+            "alg" => Value::test_string("HS512"),
+            "k" => Value::test_string("c2VjcmV0"),
+        });
+        let keys = VerifyingKey::from_value(&Value::test_record(nu_protocol::record! {
+            "keys" => Value::test_list(vec![jwk]),
+        }))
+        .unwrap();
+        let [key] = &keys[..] else {
+            panic!("expected one key");
+        };
+        assert_eq!(key.id.as_deref(), Some("one"));
+        assert!(key.supports(Algorithm::HS512));
+        assert!(!key.supports(Algorithm::HS256));
+
+        let secret = SigningKey::from_value(&Value::test_string("secret")).unwrap();
+        let signature = secret.sign(Algorithm::HS512, b"message").unwrap();
+        assert!(key.verify(Algorithm::HS512, b"message", &signature));
+    }
+}
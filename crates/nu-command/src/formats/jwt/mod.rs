@@ -0,0 +1,8 @@
+mod jwt_;
+mod key;
+mod sign;
+mod verify;
+
+pub use jwt_::Jwt;
+pub use sign::JwtSign;
+pub use verify::JwtVerify;
@@ -1,6 +1,16 @@
+mod der;
 mod from;
+#[cfg(feature = "network")]
+mod jwt;
 mod nu_xml_format;
+mod register;
 mod to;
+mod x509;
 
 pub use from::*;
+#[cfg(feature = "network")]
+pub use jwt::*;
+pub use register::FormatRegister;
+pub(crate) use register::{registered_extension, registered_format};
 pub use to::*;
+pub(crate) use x509::certificate_to_value;
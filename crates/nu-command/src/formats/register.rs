@@ -0,0 +1,195 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::engine::Closure;
+
+/// The environment variable which holds the formats registered by `format register`.
+///
+/// It's a record from file extensions to records with `from` and `to` closures and a
+/// `content_type`, all optional.
+pub(crate) const NU_FORMATS: &str = "NU_FORMATS";
+
+/// A format from [`NU_FORMATS`].
+pub(crate) struct RegisteredFormat {
+    pub from: Option<Closure>,
+    pub to: Option<Closure>,
+}
+
+/// The format registered for `extension`, if there is one.
+///
+/// Entries which were edited into something else than closures are ignored, so that `open` and
+/// `save` fall back to the `from` and `to` commands.
+pub(crate) fn registered_format(
+    engine_state: &EngineState,
+    stack: &Stack,
+    extension: &str,
+) -> Option<RegisteredFormat> {
+    let formats = stack
+        .get_env_var(engine_state, NU_FORMATS)?
+        .as_record()
+        .ok()?;
+    let format = formats.get(extension.to_lowercase())?.as_record().ok()?;
+    let closure = |name| format.get(name).and_then(|v| v.as_closure().ok()).cloned();
+    Some(RegisteredFormat {
+        from: closure("from"),
+        to: closure("to"),
+    })
+}
+
+/// The extension which was registered for the MIME type `content_type`.
+pub(crate) fn registered_extension(
+    engine_state: &EngineState,
+    stack: &Stack,
+    content_type: &str,
+) -> Option<String> {
+    let formats = stack
+        .get_env_var(engine_state, NU_FORMATS)?
+        .as_record()
+        .ok()?;
+    formats.iter().find_map(|(extension, format)| {
+        let registered = format
+            .as_record()
+            .ok()?
+            .get("content_type")?
+            .as_str()
+            .ok()?;
+        registered
+            .eq_ignore_ascii_case(content_type)
+            .then(|| extension.clone())
+    })
+}
+
+#[derive(Clone)]
+pub struct FormatRegister;
+
+impl Command for FormatRegister {
+    fn name(&self) -> &str {
+        "format register"
+    }
+
+    fn description(&self) -> &str {
+        "Register closures which `open` and `save` use for files with an extension."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The `from` closure gets the contents of the file as its argument, or as its input if it
+has no parameters, and returns structured data. The `to` closure gets the data which is saved as
+its input, and returns text or binary. A registered format is used before a `from` or `to`
+command with the same name, including those of plugins.
+
+With `--content-type`, `save` also uses the format for data with that content type in its
+metadata, when the file has no extension.
+
+The formats are kept in `$env.NU_FORMATS`, so they're scoped like other environment variables
+and can be registered in the config."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["open", "save", "extension", "mime", "converter", "parser"]
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format register")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+            .required(
+                "extension",
+                SyntaxShape::String,
+                "The file extension, with or without the leading dot.",
+            )
+            .optional(
+                "from",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "The closure which parses the contents of a file.",
+            )
+            .named(
+                "to",
+                SyntaxShape::Closure(None),
+                "the closure which converts data into the contents of a file",
+                Some('t'),
+            )
+            .named(
+                "content-type",
+                SyntaxShape::String,
+                "the MIME type of the format",
+                Some('c'),
+            )
+            .category(Category::Formats)
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Parse log files when they're opened",
+                example: r#"format register .log {|text| $text | lines | parse "{level}: {message}" }; open app.log"#,
+                result: None,
+            },
+            Example {
+                description: "Register a format which can be opened and saved",
+                example: r#"format register ndjson {|| from json --objects } --to {|| each { to json --raw } | str join "\n" } --content-type application/x-ndjson"#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let extension: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let from: Option<Value> = call.opt(engine_state, stack, 1)?;
+        let to: Option<Value> = call.get_flag(engine_state, stack, "to")?;
+        let content_type: Option<Value> = call.get_flag(engine_state, stack, "content-type")?;
+
+        let name = extension.item.trim_start_matches('.').to_lowercase();
+        if name.is_empty() {
+            return Err(ShellError::IncorrectValue {
+                msg: "the extension can't be empty".into(),
+                val_span: extension.span,
+                call_span: head,
+            });
+        }
+        if from.is_none() && to.is_none() {
+            return Err(ShellError::GenericError {
+                error: "Nothing to register".into(),
+                msg: format!("no closures were given for `.{name}`"),
+                span: Some(head),
+                help: Some("Pass a closure for `open`, or one for `save` with `--to`".into()),
+                inner: vec![],
+            });
+        }
+
+        let mut formats = match stack.get_env_var(engine_state, NU_FORMATS) {
+            Some(Value::Record { val, .. }) => val.clone().into_owned(),
+            _ => Record::new(),
+        };
+        // Registering only one direction keeps the other one
+        let mut format = match formats.remove(&name) {
+            Some(Value::Record { val, .. }) => val.into_owned(),
+            _ => Record::new(),
+        };
+        for (key, value) in [("from", from), ("to", to), ("content_type", content_type)] {
+            if let Some(value) = value {
+                format.insert(key, value);
+            }
+        }
+        formats.push(name, Value::record(format, head));
+        stack.add_env_var(NU_FORMATS.into(), Value::record(formats, head));
+
+        Ok(PipelineData::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FormatRegister {})
+    }
+}
@@ -1,3 +1,4 @@
+use super::output_metadata;
 use csv::WriterBuilder;
 use nu_cmd_base::formats::to::delimited::merge_descriptors;
 use nu_protocol::{
@@ -91,12 +92,10 @@ pub fn to_delimited_data(
 ) -> Result<PipelineData, ShellError> {
     let mut input = input;
     let span = input.span().unwrap_or(head);
-    let metadata = Some(
-        input
-            .metadata()
-            .unwrap_or_default()
-            .with_content_type(content_type),
-    );
+    let metadata = Some(match content_type {
+        Some(content_type) => output_metadata(input.metadata(), content_type),
+        None => input.metadata().unwrap_or_default(),
+    });
 
     let separator = u8::try_from(separator.item).map_err(|_| ShellError::IncorrectValue {
         msg: "separator must be an ASCII character".into(),
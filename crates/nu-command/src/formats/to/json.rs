@@ -1,5 +1,6 @@
+use super::output_metadata;
 use nu_engine::command_prelude::*;
-use nu_protocol::{PipelineMetadata, ast::PathMember};
+use nu_protocol::ast::PathMember;
 
 #[derive(Clone)]
 pub struct ToJson;
@@ -56,6 +57,7 @@ impl Command for ToJson {
         let span = call.head;
         // allow ranges to expand and turn into array
         let input = input.try_expand_range()?;
+        let metadata = output_metadata(input.metadata(), mime::APPLICATION_JSON.as_ref());
         let value = input.into_value(span)?;
         let json_value = value_to_json_value(engine_state, &value, span, serialize_types)?;
 
@@ -72,11 +74,6 @@ impl Command for ToJson {
         match json_result {
             Ok(serde_json_string) => {
                 let res = Value::string(serde_json_string, span);
-                let metadata = PipelineMetadata {
-                    data_source: nu_protocol::DataSource::None,
-                    content_type: Some(mime::APPLICATION_JSON.to_string()),
-                    ..Default::default()
-                };
                 Ok(PipelineData::value(res, Some(metadata)))
             }
             _ => Err(ShellError::CantConvert {
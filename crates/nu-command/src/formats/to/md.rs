@@ -1,8 +1,10 @@
+use super::{output_metadata, yaml::value_to_yaml_value};
 use indexmap::IndexMap;
 use nu_cmd_base::formats::to::delimited::merge_descriptors;
 use nu_engine::command_prelude::*;
 use nu_protocol::{Config, ast::PathMember};
 use std::collections::HashSet;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Clone)]
 pub struct ToMd;
@@ -57,6 +59,11 @@ impl Command for ToMd {
         "Convert table into simple Markdown."
     }
 
+    fn extra_description(&self) -> &str {
+        r#"A table of blocks from `from md` is converted back into a document. The text of its blocks
+is already Markdown, so it isn't escaped, except for the pipes in the cells of tables."#
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -113,6 +120,13 @@ impl Command for ToMd {
                     "| a | b |\n| --- | --- |\n| p | &lt;p&gt;Welcome to nushell&lt;&#x2f;p&gt; |",
                 )),
             },
+            Example {
+                description: "Render a document with an aligned table",
+                example: r#"[{type: heading, level: 2, text: Sizes} {type: table, align: [left right], rows: [[name size]; [a "1 | 2"]]}] | to md"#,
+                result: Some(Value::test_string(
+                    "## Sizes\n\n| name | size |\n|:--- | ---:|\n| a | 1 \\| 2 |",
+                )),
+            },
         ]
     }
 
@@ -135,6 +149,7 @@ impl Command for ToMd {
         let config = stack.get_config(engine_state);
 
         to_md(
+            engine_state,
             input,
             ToMdOptions {
                 pretty,
@@ -150,18 +165,31 @@ impl Command for ToMd {
 }
 
 fn to_md(
+    engine_state: &EngineState,
     input: PipelineData,
     options: ToMdOptions,
     config: &Config,
     head: Span,
 ) -> Result<PipelineData, ShellError> {
     // text/markdown became a valid mimetype with rfc7763
-    let metadata = input
-        .metadata()
-        .unwrap_or_default()
-        .with_content_type(Some("text/markdown".into()));
+    let metadata = output_metadata(input.metadata(), "text/markdown");
+
+    let values: Vec<Value> = input.into_iter().collect();
+    if let Some(blocks) = values
+        .iter()
+        .map(Block::from_value)
+        .collect::<Option<Vec<_>>>()
+        .filter(|blocks| !blocks.is_empty())
+    {
+        return Ok(Value::string(
+            document(engine_state, &blocks, options.pretty, config, head)?,
+            head,
+        )
+        .into_pipeline_data_with_metadata(Some(metadata)));
+    }
 
-    let (grouped_input, single_list) = group_by(input, head, config);
+    let (grouped_input, single_list) =
+        group_by(Value::list(values, head).into_pipeline_data(), head, config);
     if options.per_element || single_list {
         return Ok(Value::string(
             grouped_input
@@ -210,6 +238,202 @@ fn to_md(
     .into_pipeline_data_with_metadata(Some(metadata)))
 }
 
+/// A block of a document, as parsed by `from md`.
+enum Block<'a> {
+    FrontMatter(&'a Value),
+    Heading(i64, &'a str),
+    Paragraph(&'a str),
+    Code(Option<&'a str>, &'a str),
+    Table(Vec<Option<&'a str>>, &'a [Value]),
+    List(bool, &'a [Value]),
+    Quote(&'a str),
+    Rule,
+}
+
+impl<'a> Block<'a> {
+    /// The block which `value` is, if it's a record with a known `type` and its fields.
+    fn from_value(value: &'a Value) -> Option<Self> {
+        let record = value.as_record().ok()?;
+        let kind = record.get("type")?.as_str().ok()?;
+        let fields: &[&str] = match kind {
+            "front_matter" => &["data"],
+            "heading" => &["level", "text"],
+            "paragraph" | "quote" => &["text"],
+            "code" => &["language", "code"],
+            "table" => &["align", "rows"],
+            "list" => &["ordered", "items"],
+            "rule" => &[],
+            _ => return None,
+        };
+        // Other tables can have a `type` column too, so further columns make an ordinary table
+        if record.iter().any(|(column, value)| {
+            column != "type" && !fields.contains(&column.as_str()) && !value.is_nothing()
+        }) {
+            return None;
+        }
+
+        let text = |name: &str| record.get(name)?.as_str().ok();
+        Some(match kind {
+            "front_matter" => Block::FrontMatter(record.get("data")?),
+            "heading" => Block::Heading(record.get("level")?.as_int().ok()?, text("text")?),
+            "paragraph" => Block::Paragraph(text("text")?),
+            "code" => Block::Code(text("language"), text("code")?),
+            "table" => Block::Table(
+                match record.get("align") {
+                    Some(Value::List { vals, .. }) => {
+                        vals.iter().map(|align| align.as_str().ok()).collect()
+                    }
+                    _ => vec![],
+                },
+                record.get("rows")?.as_list().ok()?,
+            ),
+            "list" => Block::List(
+                record
+                    .get("ordered")
+                    .and_then(|ordered| ordered.as_bool().ok())
+                    .unwrap_or(false),
+                record.get("items")?.as_list().ok()?,
+            ),
+            "quote" => Block::Quote(text("text")?),
+            "rule" => Block::Rule,
+            _ => return None,
+        })
+    }
+}
+
+fn document(
+    engine_state: &EngineState,
+    blocks: &[Block],
+    pretty: bool,
+    config: &Config,
+    head: Span,
+) -> Result<String, ShellError> {
+    let mut out = vec![];
+    for block in blocks {
+        out.push(match block {
+            Block::FrontMatter(data) => {
+                let yaml = value_to_yaml_value(engine_state, data, false)?;
+                let yaml = serde_yaml::to_string(&yaml).map_err(|_| ShellError::CantConvert {
+                    to_type: "YAML".into(),
+                    from_type: data.get_type().to_string(),
+                    span: head,
+                    help: None,
+                })?;
+                format!("---\n{yaml}---")
+            }
+            Block::Heading(level, text) => {
+                format!("{} {text}", "#".repeat((*level).clamp(1, 6) as usize))
+            }
+            Block::Paragraph(text) => text.to_string(),
+            Block::Code(language, code) => {
+                // The fence has to be longer than any run of backticks in the code
+                let mut fence = String::from("```");
+                while code.contains(&fence) {
+                    fence.push('`');
+                }
+                format!("{fence}{}\n{code}\n{fence}", language.unwrap_or_default())
+            }
+            Block::Table(align, rows) => aligned_table(align, rows, pretty, config),
+            Block::List(ordered, items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = if *ordered {
+                        format!("{}. ", i + 1)
+                    } else {
+                        String::from("- ")
+                    };
+                    let indent = format!("\n{}", " ".repeat(marker.len()));
+                    let item = item.to_expanded_string(", ", config);
+                    format!("{marker}{}", item.lines().collect::<Vec<_>>().join(&indent))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Quote(text) => text
+                .lines()
+                .map(|line| format!("> {line}").trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Rule => String::from("---"),
+        });
+    }
+    Ok(out.join("\n\n"))
+}
+
+/// A table with the alignments of a table block, whose cells are Markdown.
+fn aligned_table(align: &[Option<&str>], rows: &[Value], pretty: bool, config: &Config) -> String {
+    let headers = merge_descriptors(rows);
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            headers
+                .iter()
+                .map(|header| {
+                    row.get_data_by_key(header)
+                        .map(|cell| cell.to_expanded_string(", ", config))
+                        .unwrap_or_default()
+                        .replace('|', "\\|")
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            if pretty {
+                cells
+                    .iter()
+                    .map(|row| row[i].width())
+                    .chain([headers[i].width(), 3])
+                    .max()
+                    .unwrap_or(3)
+            } else {
+                3
+            }
+        })
+        .collect();
+    let alignment = |i: usize| align.get(i).copied().flatten();
+
+    let line = |row: &Vec<String>| {
+        let mut line = String::from("|");
+        for (i, cell) in row.iter().enumerate() {
+            let cell = if !pretty {
+                cell.clone()
+            } else {
+                match alignment(i) {
+                    Some("center") => get_centered_string(cell.clone(), widths[i], ' '),
+                    Some("right") => {
+                        format!(
+                            "{}{cell}",
+                            " ".repeat(widths[i].saturating_sub(cell.width()))
+                        )
+                    }
+                    _ => get_padded_string(cell.clone(), widths[i], ' '),
+                }
+            };
+            line.push_str(&format!(" {cell} |"));
+        }
+        line
+    };
+
+    let mut delimiter = String::from("|");
+    for (i, width) in widths.iter().enumerate() {
+        let (left, right) = match alignment(i) {
+            Some("left") => (':', ' '),
+            Some("center") => (':', ':'),
+            Some("right") => (' ', ':'),
+            _ => (' ', ' '),
+        };
+        delimiter.push(left);
+        delimiter.push_str(&"-".repeat(*width));
+        delimiter.push(right);
+        delimiter.push('|');
+    }
+
+    let mut lines = vec![line(&headers), delimiter];
+    lines.extend(cells.iter().map(line));
+    lines.join("\n")
+}
+
 fn escape_markdown_characters(input: String, escape_md: bool, for_table: bool) -> String {
     let mut output = String::with_capacity(input.len());
     for ch in input.chars() {
@@ -314,7 +538,7 @@ fn collect_headers(headers: &[String], escape_md: bool) -> (Vec<String>, Vec<usi
                 escape_md,
                 true,
             );
-            column_widths.push(escaped_header_string.len());
+            column_widths.push(escaped_header_string.width());
             escaped_headers.push(escaped_header_string);
         }
     } else {
@@ -381,7 +605,7 @@ fn table(
                         true,
                     );
 
-                    let new_column_width = escaped_string.len();
+                    let new_column_width = escaped_string.width();
                     escaped_row.push(escaped_string);
 
                     if column_widths[i] < new_column_width {
@@ -565,11 +789,7 @@ fn get_output_string(
 }
 
 fn get_centered_string(text: String, desired_length: usize, padding_character: char) -> String {
-    let total_padding = if text.len() > desired_length {
-        0
-    } else {
-        desired_length - text.len()
-    };
+    let total_padding = desired_length.saturating_sub(text.width());
 
     let repeat_left = total_padding / 2;
     let repeat_right = total_padding - repeat_left;
@@ -583,11 +803,7 @@ fn get_centered_string(text: String, desired_length: usize, padding_character: c
 }
 
 fn get_padded_string(text: String, desired_length: usize, padding_character: char) -> String {
-    let repeat_length = if text.len() > desired_length {
-        0
-    } else {
-        desired_length - text.len()
-    };
+    let repeat_length = desired_length.saturating_sub(text.width());
 
     format!(
         "{}{}",
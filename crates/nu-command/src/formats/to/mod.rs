@@ -26,4 +26,17 @@ pub use xml::ToXml;
 pub use yaml::{ToYaml, ToYml};
 
 #[cfg(any(feature = "network", feature = "sqlite"))]
-pub(crate) use json::value_to_json_value;
+pub use json::value_to_json_value;
+
+use nu_protocol::PipelineMetadata;
+
+/// The metadata of the output of a `to` command, which is the metadata of its input with the
+/// content type of the format. This keeps the keys scripts add with `metadata set --merge`.
+pub(crate) fn output_metadata(
+    metadata: Option<PipelineMetadata>,
+    content_type: impl Into<String>,
+) -> PipelineMetadata {
+    metadata
+        .unwrap_or_default()
+        .with_content_type(Some(content_type.into()))
+}
@@ -3,6 +3,7 @@
 
 use std::io;
 
+use super::output_metadata;
 use byteorder::{BigEndian, WriteBytesExt};
 use nu_engine::command_prelude::*;
 use nu_protocol::{Signals, Spanned, ast::PathMember, shell_error::io::IoError};
@@ -79,10 +80,7 @@ MessagePack: https://msgpack.org/
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let metadata = input
-            .metadata()
-            .unwrap_or_default()
-            .with_content_type(Some("application/x-msgpack".into()));
+        let metadata = output_metadata(input.metadata(), "application/x-msgpack");
 
         let value_span = input.span().unwrap_or(call.head);
         let value = input.into_value(value_span)?;
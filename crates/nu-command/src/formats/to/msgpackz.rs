@@ -3,7 +3,7 @@ use std::io::Write;
 use nu_engine::command_prelude::*;
 use nu_protocol::shell_error::io::IoError;
 
-use super::msgpack::write_value;
+use super::{msgpack::write_value, output_metadata};
 
 const BUFFER_SIZE: usize = 65536;
 const DEFAULT_QUALITY: u32 = 3; // 1 can be very bad
@@ -76,6 +76,7 @@ impl Command for ToMsgpackz {
             .transpose()?;
         let serialize_types = call.has_flag(engine_state, stack, "serialize")?;
 
+        let metadata = output_metadata(input.metadata(), "application/x-msgpackz");
         let value_span = input.span().unwrap_or(call.head);
         let value = input.into_value(value_span)?;
         let mut out_buf = vec![];
@@ -98,6 +99,6 @@ impl Command for ToMsgpackz {
             .map_err(|err| IoError::new(err, call.head, None))?;
         drop(out);
 
-        Ok(Value::binary(out_buf, call.head).into_pipeline_data())
+        Ok(Value::binary(out_buf, call.head).into_pipeline_data_with_metadata(Some(metadata)))
     }
 }
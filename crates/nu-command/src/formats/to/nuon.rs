@@ -1,3 +1,4 @@
+use super::output_metadata;
 use nu_engine::command_prelude::*;
 
 #[derive(Clone)]
@@ -47,10 +48,7 @@ impl Command for ToNuon {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let metadata = input
-            .metadata()
-            .unwrap_or_default()
-            .with_content_type(Some("application/x-nuon".into()));
+        let metadata = output_metadata(input.metadata(), "application/x-nuon");
 
         let serialize_types = call.has_flag(engine_state, stack, "serialize")?;
         let style = if call.has_flag(engine_state, stack, "raw")? {
@@ -1,3 +1,4 @@
+use super::output_metadata;
 use chrono::Datelike;
 use chrono_humanize::HumanTime;
 use nu_engine::command_prelude::*;
@@ -50,10 +51,11 @@ impl Command for ToText {
         let no_newline = call.has_flag(engine_state, stack, "no-newline")?;
         let serialize_types = call.has_flag(engine_state, stack, "serialize")?;
         let input = input.try_expand_range()?;
+        let metadata = input.metadata();
 
         match input {
             PipelineData::Empty => Ok(Value::string(String::new(), head)
-                .into_pipeline_data_with_metadata(update_metadata(None))),
+                .into_pipeline_data_with_metadata(update_metadata(metadata))),
             PipelineData::Value(value, ..) => {
                 let add_trailing = !no_newline
                     && match &value {
@@ -65,10 +67,8 @@ impl Command for ToText {
                 if add_trailing {
                     str.push_str(LINE_ENDING);
                 }
-                Ok(
-                    Value::string(str, head)
-                        .into_pipeline_data_with_metadata(update_metadata(None)),
-                )
+                Ok(Value::string(str, head)
+                    .into_pipeline_data_with_metadata(update_metadata(metadata)))
             }
             PipelineData::ListStream(stream, meta) => {
                 let span = stream.span();
@@ -232,11 +232,7 @@ fn local_into_string(
 }
 
 fn update_metadata(metadata: Option<PipelineMetadata>) -> Option<PipelineMetadata> {
-    metadata
-        .map(|md| md.with_content_type(Some(mime::TEXT_PLAIN.to_string())))
-        .or_else(|| {
-            Some(PipelineMetadata::default().with_content_type(Some(mime::TEXT_PLAIN.to_string())))
-        })
+    Some(output_metadata(metadata, mime::TEXT_PLAIN.as_ref()))
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use super::output_metadata;
 use chrono::{DateTime, Datelike, FixedOffset, Timelike};
 use nu_engine::command_prelude::*;
 use nu_protocol::{PipelineMetadata, ast::PathMember};
@@ -130,11 +131,7 @@ fn toml_into_pipeline_data(
     span: Span,
     metadata: Option<PipelineMetadata>,
 ) -> Result<PipelineData, ShellError> {
-    let new_md = Some(
-        metadata
-            .unwrap_or_default()
-            .with_content_type(Some("text/x-toml".into())),
-    );
+    let new_md = Some(output_metadata(metadata, "text/x-toml"));
 
     match toml::to_string_pretty(&toml_value) {
         Ok(serde_toml_string) => {
@@ -1,3 +1,4 @@
+use super::output_metadata;
 use crate::formats::nu_xml_format::{COLUMN_ATTRS_NAME, COLUMN_CONTENT_NAME, COLUMN_TAG_NAME};
 use indexmap::IndexMap;
 use nu_engine::command_prelude::*;
@@ -128,10 +129,7 @@ impl Job {
     }
 
     fn run(mut self, input: PipelineData, head: Span) -> Result<PipelineData, ShellError> {
-        let metadata = input
-            .metadata()
-            .unwrap_or_default()
-            .with_content_type(Some("application/xml".into()));
+        let metadata = output_metadata(input.metadata(), "application/xml");
         let value = input.into_value(head)?;
 
         self.write_xml_entry(value, true).and_then(|_| {
@@ -1,3 +1,4 @@
+use super::output_metadata;
 use nu_engine::command_prelude::*;
 use nu_protocol::ast::PathMember;
 
@@ -175,11 +176,8 @@ fn to_yaml(
     head: Span,
     serialize_types: bool,
 ) -> Result<PipelineData, ShellError> {
-    let metadata = input
-        .metadata()
-        .unwrap_or_default()
-        // Per RFC-9512, application/yaml should be used
-        .with_content_type(Some("application/yaml".into()));
+    // Per RFC-9512, application/yaml should be used
+    let metadata = output_metadata(input.metadata(), "application/yaml");
     let value = input.into_value(head)?;
 
     let yaml_value = value_to_yaml_value(engine_state, &value, serialize_types)?;
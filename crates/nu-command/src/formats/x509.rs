@@ -0,0 +1,301 @@
+//! Decoding of X.509 certificates into records, for `from pem` and `cert inspect`.
+
+use super::der::{self, INTEGER, OID, Reader, SEQUENCE};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use nu_protocol::{Span, Value, record};
+use sha2::{Digest, Sha256};
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const SUBJECT_ALT_NAME: &str = "2.5.29.17";
+const BASIC_CONSTRAINTS: &str = "2.5.29.19";
+
+/// A certificate as a record of its subject, issuer, validity and key.
+pub(crate) fn certificate_to_value(der: &[u8], span: Span) -> Result<Value, String> {
+    let mut certificate = Reader::new(der);
+    let mut certificate = Reader::new(certificate.expect(SEQUENCE)?);
+    let mut tbs = Reader::new(certificate.expect(SEQUENCE)?);
+
+    // The version is explicitly tagged with [0], and left out for version 1
+    let version = match tbs.optional(0xa0)? {
+        Some(version) => Reader::new(version)
+            .expect(INTEGER)?
+            .last()
+            .map_or(1, |version| *version as i64 + 1),
+        None => 1,
+    };
+    let serial = der::unsigned_integer(tbs.expect(INTEGER)?);
+    let signature_algorithm = algorithm_name(Reader::new(tbs.expect(SEQUENCE)?).expect(OID)?);
+    let issuer = name(tbs.expect(SEQUENCE)?)?;
+    let mut validity = Reader::new(tbs.expect(SEQUENCE)?);
+    let not_before = time(&mut validity)?;
+    let not_after = time(&mut validity)?;
+    let subject = name(tbs.expect(SEQUENCE)?)?;
+    let key = public_key(tbs.read()?.encoded)?;
+    // The unique identifiers of version 2
+    tbs.optional(0x81)?;
+    tbs.optional(0x82)?;
+
+    let mut alt_names = vec![];
+    let mut is_ca = false;
+    if let Some(extensions) = tbs.optional(0xa3)? {
+        let mut extensions = Reader::new(Reader::new(extensions).expect(SEQUENCE)?);
+        while !extensions.is_empty() {
+            let mut extension = Reader::new(extensions.expect(SEQUENCE)?);
+            let id = der::oid_to_string(extension.expect(OID)?);
+            // Whether the extension is critical
+            extension.optional(0x01)?;
+            let value = extension.expect(0x04)?;
+            match id.as_str() {
+                SUBJECT_ALT_NAME => alt_names = subject_alt_names(value)?,
+                BASIC_CONSTRAINTS => {
+                    let mut constraints = Reader::new(Reader::new(value).expect(SEQUENCE)?);
+                    is_ca = constraints
+                        .optional(0x01)?
+                        .is_some_and(|ca| ca.first().is_some_and(|ca| *ca != 0));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let expires_in = not_after.signed_duration_since(Utc::now());
+    Ok(Value::record(
+        record! {
+            "subject" => Value::string(subject, span),
+            "issuer" => Value::string(issuer, span),
+            "subject_alt_names" => Value::list(
+                alt_names.into_iter().map(|name| Value::string(name, span)).collect(),
+                span,
+            ),
+            "not_before" => Value::date(not_before, span),
+            "not_after" => Value::date(not_after, span),
+            "expires_in" => Value::duration(
+                expires_in.num_nanoseconds().unwrap_or(if expires_in.num_seconds() < 0 {
+                    i64::MIN
+                } else {
+                    i64::MAX
+                }),
+                span,
+            ),
+            "is_ca" => Value::bool(is_ca, span),
+            "serial" => Value::string(hex(serial), span),
+            "key" => Value::string(key, span),
+            "signature_algorithm" => Value::string(signature_algorithm, span),
+            "version" => Value::int(version, span),
+            "fingerprint" => Value::string(hex(&Sha256::digest(der)), span),
+        },
+        span,
+    ))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A distinguished name in the notation of RFC 4514, like `CN=example.com, O=Example, C=US`.
+fn name(contents: &[u8]) -> Result<String, String> {
+    let mut rdns = Reader::new(contents);
+    let mut parts = vec![];
+    while !rdns.is_empty() {
+        let mut attributes = Reader::new(rdns.expect(0x31)?);
+        while !attributes.is_empty() {
+            let mut attribute = Reader::new(attributes.expect(SEQUENCE)?);
+            let id = der::oid_to_string(attribute.expect(OID)?);
+            let value = string(attribute.read()?)?;
+            let name = match id.as_str() {
+                "2.5.4.3" => "CN",
+                "2.5.4.5" => "serialNumber",
+                "2.5.4.6" => "C",
+                "2.5.4.7" => "L",
+                "2.5.4.8" => "ST",
+                "2.5.4.9" => "STREET",
+                "2.5.4.10" => "O",
+                "2.5.4.11" => "OU",
+                "0.9.2342.19200300.100.1.1" => "UID",
+                "0.9.2342.19200300.100.1.25" => "DC",
+                "1.2.840.113549.1.9.1" => "emailAddress",
+                _ => id.as_str(),
+            };
+            parts.push(format!("{name}={value}"));
+        }
+    }
+    // RFC 4514 starts with the most specific part, which is last in the DER
+    parts.reverse();
+    Ok(parts.join(", "))
+}
+
+fn string(tlv: der::Tlv<'_>) -> Result<String, String> {
+    match tlv.tag {
+        // UTF8String, PrintableString, TeletexString, IA5String and VisibleString
+        0x0c | 0x13 | 0x14 | 0x16 | 0x1a => Ok(String::from_utf8_lossy(tlv.contents).into()),
+        // BMPString, which is UTF-16
+        0x1e => {
+            let units: Vec<u16> = tlv
+                .contents
+                .chunks_exact(2)
+                .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                .collect();
+            Ok(String::from_utf16_lossy(&units))
+        }
+        tag => Err(format!(
+            "the certificate has a name with the type {tag:#04x}"
+        )),
+    }
+}
+
+fn time(validity: &mut Reader<'_>) -> Result<DateTime<FixedOffset>, String> {
+    let tlv = validity.read()?;
+    let text =
+        std::str::from_utf8(tlv.contents).map_err(|_| "the certificate has an invalid time")?;
+    let text = match tlv.tag {
+        // UTCTime, whose two digit years are from 1950 to 2049
+        0x17 => {
+            let century = if text.get(..2).is_some_and(|year| year < "50") {
+                "20"
+            } else {
+                "19"
+            };
+            format!("{century}{text}")
+        }
+        // GeneralizedTime
+        0x18 => text.into(),
+        _ => return Err("the certificate has an invalid time".into()),
+    };
+    NaiveDateTime::parse_from_str(&text, "%Y%m%d%H%M%SZ")
+        .map(|time| time.and_utc().fixed_offset())
+        .map_err(|_| format!("the certificate has an invalid time {text}"))
+}
+
+/// The names of the subject alternative name extension, like DNS names and IP addresses.
+fn subject_alt_names(value: &[u8]) -> Result<Vec<String>, String> {
+    let mut names = Reader::new(Reader::new(value).expect(SEQUENCE)?);
+    let mut alt_names = vec![];
+    while !names.is_empty() {
+        let name = names.read()?;
+        match name.tag {
+            // rfc822Name, dNSName and uniformResourceIdentifier
+            0x81 | 0x82 | 0x86 => {
+                alt_names.push(String::from_utf8_lossy(name.contents).into_owned())
+            }
+            // iPAddress
+            0x87 => {
+                let ip = match name.contents.len() {
+                    4 => IpAddr::V4(Ipv4Addr::from(
+                        <[u8; 4]>::try_from(name.contents).expect("4 bytes"),
+                    )),
+                    16 => IpAddr::V6(Ipv6Addr::from(
+                        <[u8; 16]>::try_from(name.contents).expect("16 bytes"),
+                    )),
+                    _ => continue,
+                };
+                alt_names.push(ip.to_string());
+            }
+            // Other names, X.400 addresses, directory names and registered IDs
+            _ => {}
+        }
+    }
+    Ok(alt_names)
+}
+
+/// The algorithm and size of a SubjectPublicKeyInfo, like `RSA 2048` or `EC P-256`.
+fn public_key(spki: &[u8]) -> Result<String, String> {
+    let (oid, parameters, key) = der::subject_public_key_info(spki)?;
+    Ok(match oid.as_str() {
+        "1.2.840.113549.1.1.1" => {
+            let mut key = Reader::new(Reader::new(key).expect(SEQUENCE)?);
+            let modulus = der::unsigned_integer(key.expect(INTEGER)?);
+            format!("RSA {}", modulus.len() * 8)
+        }
+        "1.2.840.10045.2.1" => {
+            let curve = parameters
+                .filter(|parameters| parameters.tag == OID)
+                .map(|parameters| der::oid_to_string(parameters.contents));
+            match curve.as_deref() {
+                Some("1.2.840.10045.3.1.7") => "EC P-256".into(),
+                Some("1.3.132.0.34") => "EC P-384".into(),
+                Some("1.3.132.0.35") => "EC P-521".into(),
+                Some(curve) => format!("EC {curve}"),
+                None => "EC".into(),
+            }
+        }
+        "1.3.101.112" => "Ed25519".into(),
+        "1.3.101.113" => "Ed448".into(),
+        _ => oid,
+    })
+}
+
+fn algorithm_name(oid: &[u8]) -> String {
+    let oid = der::oid_to_string(oid);
+    match oid.as_str() {
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption".into(),
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS".into(),
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption".into(),
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption".into(),
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption".into(),
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256".into(),
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384".into(),
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512".into(),
+        "1.3.101.112" => "Ed25519".into(),
+        "1.3.101.113" => "Ed448".into(),
+        _ => oid,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A self-signed certificate from
+    /// `openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:P-256 -subj /C=US/O=Nushell/CN=example.com`.
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIB3TCCAYOgAwIBAgIEEjSrzTAKBggqhkjOPQQDAjA1MQswCQYDVQQGEwJVUzEQ
+MA4GA1UECgwHTnVzaGVsbDEUMBIGA1UEAwwLZXhhbXBsZS5jb20wHhcNMjYxMDE0
+MTMzNTE4WhcNMzYxMDExMTMzNTE4WjA1MQswCQYDVQQGEwJVUzEQMA4GA1UECgwH
+TnVzaGVsbDEUMBIGA1UEAwwLZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggqhkjO
+PQMBBwNCAAR5Ynp8Vw9Cq6fPM3nG8z2+AoPZWkLMaxL8qK9MTHIGcCFbjxl+n96T
+llpKsk38jToycYjNT3gM1L+WFkJqES0xo4GAMH4wHQYDVR0OBBYEFEQuGs7sGZ8n
+L5vjaqSZzlVa0p73MB8GA1UdIwQYMBaAFEQuGs7sGZ8nL5vjaqSZzlVa0p73MA8G
+A1UdEwEB/wQFMAMBAf8wKwYDVR0RBCQwIoILZXhhbXBsZS5jb22CDSouZXhhbXBs
+ZS5jb22HBH8AAAEwCgYIKoZIzj0EAwIDSAAwRQIgZsCboJTF3+SvKsL5nRaKB2bb
+DGnqwk4DDHzQVhGn8G0CIQCyS57Aq3AZvudMnVgK5ffxR3XO09N+aYPLhWj2a/vz
+GA==
+-----END CERTIFICATE-----";
+
+    #[test]
+    fn decodes_certificates() {
+        let pem = der::pem_blocks(CERTIFICATE).unwrap();
+        let certificate = certificate_to_value(&pem[0].der, Span::test_data()).unwrap();
+        let field = |name: &str| certificate.get_data_by_key(name).unwrap();
+
+        assert_eq!(
+            field("subject"),
+            Value::test_string("CN=example.com, O=Nushell, C=US")
+        );
+        assert_eq!(field("issuer"), field("subject"));
+        assert_eq!(
+            field("subject_alt_names"),
+            Value::test_list(vec![
+                Value::test_string("example.com"),
+                Value::test_string("*.example.com"),
+                Value::test_string("127.0.0.1"),
+            ])
+        );
+        assert_eq!(
+            field("not_after"),
+            Value::test_date(DateTime::parse_from_rfc3339("2036-10-11T13:35:18Z").unwrap())
+        );
+        assert_eq!(field("is_ca"), Value::test_bool(true));
+        assert_eq!(field("serial"), Value::test_string("1234abcd"));
+        assert_eq!(field("key"), Value::test_string("EC P-256"));
+        assert_eq!(
+            field("signature_algorithm"),
+            Value::test_string("ecdsa-with-SHA256")
+        );
+        assert_eq!(field("version"), Value::test_int(3));
+        assert_eq!(
+            field("fingerprint"),
+            Value::test_string("52fc1c894c85d566e9ef1120fdf403242c597524beaebfce6c4b2cac284b9d4b")
+        );
+    }
+}
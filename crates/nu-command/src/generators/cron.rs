@@ -0,0 +1,251 @@
+//! Cron expressions, as in `crontab(5)`, for `seq cron` and the scheduler.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// The maximum number of days that are searched for the next time of a schedule, so that
+/// expressions which never match, like `0 0 30 2 *`, don't loop forever.
+const MAX_SEARCH_DAYS: i64 = 366 * 30;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression with the five fields `minute hour day-of-month month day-of-week`.
+///
+/// Fields are `*`, numbers, ranges like `1-5`, steps like `*/15` or `0-30/10`, and lists of
+/// those separated by commas. Months and weekdays can also be names like `jan` or `mon`, and
+/// weekdays can be `tue#2` for the second Tuesday of the month. Like in most crons, a day
+/// matches if either the day of month or the day of week matches when both are restricted, and a
+/// field starting with `*`, like `*/2`, isn't restricted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Cron {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    /// For each weekday, bit 0 for every one of the month and bit `n` for the `n`th one.
+    weekdays: [u8; 7],
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Cron {
+    pub(crate) fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day month weekday), found {}",
+                fields.len()
+            ));
+        };
+
+        let mut weekdays = [0; 7];
+        for item in weekday.split(',') {
+            let (range, nth) = match item.split_once('#') {
+                Some((range, nth)) => match nth.parse::<u8>() {
+                    Ok(nth @ 1..=5) => (range, nth),
+                    _ => return Err(format!("invalid weekday occurrence '{nth}'")),
+                },
+                None => (item, 0),
+            };
+            // 7 is also Sunday
+            let bits = parse_field(range, 0, 7, &WEEKDAYS)?;
+            for (day, weekday) in weekdays.iter_mut().enumerate() {
+                if bits & (1 << day) != 0 || (day == 0 && bits & (1 << 7) != 0) {
+                    *weekday |= 1 << nth;
+                }
+            }
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[])?,
+            hours: parse_field(hour, 0, 23, &[])? as u32,
+            days: parse_field(day, 1, 31, &[])? as u32,
+            months: parse_field(month, 1, 12, &MONTHS)? as u16,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+
+    /// Whether the schedule runs on `date`.
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let nth = (date.day() - 1) / 7 + 1;
+        let weekday =
+            self.weekdays[date.weekday().num_days_from_sunday() as usize] & (1 | (1 << nth)) != 0;
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// The first time of the schedule at or after `from`, which is rounded up to a whole minute.
+    pub(crate) fn first_from(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut from = from.with_nanosecond(0)?;
+        if from.second() != 0 {
+            from = from.with_second(0)? + Duration::minutes(1);
+        }
+
+        let mut date = from.date();
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                let earliest = if date == from.date() {
+                    from.time()
+                } else {
+                    NaiveTime::MIN
+                };
+                let time = (earliest.hour()..24)
+                    .filter(|hour| self.hours & (1 << hour) != 0)
+                    .flat_map(|hour| (0..60).map(move |minute| (hour, minute)))
+                    .filter(|(_, minute)| self.minutes & (1 << minute) != 0)
+                    .filter_map(|(hour, minute)| NaiveTime::from_hms_opt(hour, minute, 0))
+                    .find(|time| *time >= earliest);
+                if let Some(time) = time {
+                    return Some(date.and_time(time));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// The first time of the schedule strictly after `after`.
+    pub(crate) fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let next_minute = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        self.first_from(next_minute)
+    }
+}
+
+/// Parse a field into a bit set of its values between `min` and `max`.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let lower = text.to_ascii_lowercase();
+        let value = match names.iter().position(|name| *name == lower) {
+            // Months start at 1 and weekdays at 0, like their `min`
+            Some(index) => index as u32 + min,
+            None => text
+                .parse()
+                .map_err(|_| format!("invalid value '{text}'"))?,
+        };
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!("'{text}' is not between {min} and {max}"))
+        }
+    };
+
+    let mut bits = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("invalid step '{step}'")),
+            },
+            None => (item, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            // `5/10` means from 5 to the end
+            None if step > 1 => (value(range)?, max),
+            None => {
+                let value = value(range)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            return Err(format!("invalid range '{range}'"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn times(expression: &str, from: &str, count: usize) -> Vec<String> {
+        let cron = Cron::parse(expression).unwrap();
+        std::iter::successors(cron.first_from(at(from)), |time| cron.next_after(*time))
+            .take(count)
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn steps_and_ranges() {
+        assert_eq!(
+            times("*/20 9-10 * * *", "2024-03-01 10:30", 3),
+            ["2024-03-01 10:40", "2024-03-02 09:00", "2024-03-02 09:20"]
+        );
+    }
+
+    #[test]
+    fn weekday_names_and_occurrences() {
+        // 2024-03-01 is a Friday
+        assert_eq!(
+            times("0 9 * * MON,fri", "2024-03-01 09:00", 3),
+            ["2024-03-01 09:00", "2024-03-04 09:00", "2024-03-08 09:00"]
+        );
+        assert_eq!(
+            times("30 18 * * tue#2", "2024-03-01 00:00", 2),
+            ["2024-03-12 18:30", "2024-04-09 18:30"]
+        );
+    }
+
+    #[test]
+    fn day_of_month_or_weekday() {
+        assert_eq!(
+            times("0 0 1 * sun", "2024-03-01 12:00", 3),
+            ["2024-03-03 00:00", "2024-03-10 00:00", "2024-03-17 00:00"]
+        );
+        assert_eq!(
+            times("@yearly", "2024-03-01 00:00", 1),
+            ["2025-01-01 00:00"]
+        );
+    }
+
+    #[test]
+    fn star_steps_are_not_restrictions() {
+        // Both have to match then, so these are the Mondays on odd days
+        assert_eq!(
+            times("0 0 */2 * mon", "2024-03-01 12:00", 3),
+            ["2024-03-11 00:00", "2024-03-25 00:00", "2024-04-01 00:00"]
+        );
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(Cron::parse("* * * *").is_err());
+        assert!(Cron::parse("60 * * * *").is_err());
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("0 0 * * mon#6").is_err());
+        assert_eq!(
+            Cron::parse("0 0 30 2 *")
+                .unwrap()
+                .first_from(at("2024-01-01 00:00")),
+            None
+        );
+    }
+}
@@ -1,11 +1,14 @@
 mod cal;
+pub(crate) mod cron;
 mod generate;
 mod seq;
 mod seq_char;
+mod seq_cron;
 mod seq_date;
 
 pub use cal::Cal;
 pub use generate::Generate;
 pub use seq::Seq;
 pub use seq_char::SeqChar;
+pub use seq_cron::SeqCron;
 pub use seq_date::SeqDate;
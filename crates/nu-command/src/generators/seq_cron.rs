@@ -0,0 +1,153 @@
+use super::cron::Cron;
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use nu_engine::command_prelude::*;
+use nu_protocol::ListStream;
+
+#[derive(Clone)]
+pub struct SeqCron;
+
+impl Command for SeqCron {
+    fn name(&self) -> &str {
+        "seq cron"
+    }
+
+    fn description(&self) -> &str {
+        "Print the times of a cron schedule."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The expression has the five fields `minute hour day-of-month month day-of-week` of
+crontab(5). Fields are `*`, numbers, ranges like `1-5`, steps like `*/15` and lists like `1,15`.
+Months and weekdays can be names like `jan` or `mon`, and `tue#2` is the second Tuesday of the
+month. `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` are also understood.
+
+The times are in the local time zone. Without --end-date or --take, the stream of times is
+endless."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("seq cron")
+            .input_output_types(vec![(Type::Nothing, Type::List(Box::new(Type::Date)))])
+            .required(
+                "expression",
+                SyntaxShape::String,
+                "The cron expression, like '0 9 * * mon-fri'.",
+            )
+            .named(
+                "begin-date",
+                SyntaxShape::DateTime,
+                "the first time which can be printed (defaults to now)",
+                Some('b'),
+            )
+            .named(
+                "end-date",
+                SyntaxShape::DateTime,
+                "the last time which can be printed",
+                Some('e'),
+            )
+            .named(
+                "take",
+                SyntaxShape::Int,
+                "the number of times to print",
+                Some('t'),
+            )
+            .category(Category::Generators)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["schedule", "crontab", "recurring", "calendar"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Print the next 10 times of 9 o'clock on Mondays",
+                example: "seq cron '0 9 * * MON' --take 10",
+                result: None,
+            },
+            Example {
+                description: "Print the second Tuesdays of the months of 2024",
+                example: "seq cron '0 0 * * tue#2' --begin-date 2024-01-01 --end-date 2024-12-31 | format date '%F'",
+                result: None,
+            },
+            Example {
+                description: "Print the first two times of a schedule which runs every minute",
+                example: "seq cron '* * * * *' --begin-date 2024-06-01T12:00:00Z --take 2",
+                result: Some(Value::test_list(vec![
+                    Value::test_date(Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap().into()),
+                    Value::test_date(Utc.with_ymd_and_hms(2024, 6, 1, 12, 1, 0).unwrap().into()),
+                ])),
+            },
+            Example {
+                description: "Print the quarter hours of an afternoon",
+                example: "seq cron '*/15 13-17 * * *' --begin-date 2024-06-01T00:00:00 --take 20",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let expression: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let begin: Option<DateTime<FixedOffset>> =
+            call.get_flag(engine_state, stack, "begin-date")?;
+        let end: Option<DateTime<FixedOffset>> = call.get_flag(engine_state, stack, "end-date")?;
+        let take: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "take")?;
+
+        let cron = Cron::parse(&expression.item).map_err(|msg| ShellError::GenericError {
+            error: "Invalid cron expression".into(),
+            msg,
+            span: Some(expression.span),
+            help: None,
+            inner: vec![],
+        })?;
+        let take = take
+            .map(|take| {
+                usize::try_from(take.item)
+                    .map_err(|_| ShellError::NeedsPositiveValue { span: take.span })
+            })
+            .transpose()?;
+
+        // The schedule is in local time, so that it follows daylight saving time
+        let begin = begin
+            .map(|begin| begin.with_timezone(&Local))
+            .unwrap_or_else(Local::now)
+            .naive_local();
+        let end = end.map(|end| end.with_timezone(&Local).naive_local());
+
+        let times =
+            std::iter::successors(cron.first_from(begin), move |time| cron.next_after(*time))
+                .take_while(move |time| end.is_none_or(|end| *time <= end))
+                .filter_map(local_datetime)
+                .take(take.unwrap_or(usize::MAX))
+                .map(move |time| Value::date(time, head));
+
+        Ok(ListStream::new(times, head, engine_state.signals().clone()).into())
+    }
+}
+
+/// The local time of `time`, or nothing if it's skipped by daylight saving time.
+fn local_datetime(time: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.fixed_offset())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SeqCron {})
+    }
+}
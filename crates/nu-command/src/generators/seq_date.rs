@@ -1,6 +1,6 @@
-use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, Weekday};
 use nu_engine::command_prelude::*;
-use nu_protocol::FromValue;
+use nu_protocol::{FromValue, Signals};
 
 use std::fmt::Write;
 
@@ -59,9 +59,18 @@ impl Command for SeqDate {
                 Some('p'),
             )
             .switch("reverse", "print dates in reverse", Some('r'))
+            .switch(
+                "business-days",
+                "skip Saturdays and Sundays, and count only weekdays with --days and --periods",
+                Some('w'),
+            )
             .category(Category::Generators)
     }
 
+    fn extra_description(&self) -> &str {
+        "For schedules like every Monday at 9 o'clock, use `seq cron`."
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -181,6 +190,20 @@ impl Command for SeqDate {
                     Span::test_data(),
                 )),
             },
+            Example {
+                description: "print the next 5 business days from Friday, January 3rd, 2020",
+                example: "seq date --begin-date '2020-01-03' --days 5 --business-days",
+                result: Some(Value::list(
+                    vec![
+                        Value::test_string("2020-01-03"),
+                        Value::test_string("2020-01-06"),
+                        Value::test_string("2020-01-07"),
+                        Value::test_string("2020-01-08"),
+                        Value::test_string("2020-01-09"),
+                    ],
+                    Span::test_data(),
+                )),
+            },
         ]
     }
 
@@ -224,6 +247,7 @@ impl Command for SeqDate {
         let days: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "days")?;
         let periods: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "periods")?;
         let reverse = call.has_flag(engine_state, stack, "reverse")?;
+        let business_days = call.has_flag(engine_state, stack, "business-days")?;
 
         let out_format = match output_format {
             Some(s) => Some(Value::string(s.item, s.span)),
@@ -268,6 +292,8 @@ impl Command for SeqDate {
             day_count,
             period_count,
             rev,
+            business_days,
+            engine_state.signals(),
             call.head,
         )?
         .into_pipeline_data())
@@ -294,6 +320,8 @@ pub fn run_seq_dates(
     day_count: Option<Value>,
     period_count: Option<Value>,
     reverse: bool,
+    business_days: bool,
+    signals: &Signals,
     call_span: Span,
 ) -> Result<Value, ShellError> {
     let today = Local::now().naive_local();
@@ -392,8 +420,21 @@ pub fn run_seq_dates(
         periods_to_output *= -1;
     }
 
-    // --days is ignored when --periods is set
-    if periods_to_output != 0 {
+    // The number of business days can't be computed ahead, so they are counted while stepping
+    let mut limit = None;
+    if business_days && (periods_to_output != 0 || days_to_output != 0) {
+        let count = if periods_to_output != 0 {
+            periods_to_output
+        } else {
+            days_to_output
+        };
+        limit = Some(count.unsigned_abs() as usize);
+        end_date = if count > 0 {
+            NaiveDateTime::MAX
+        } else {
+            NaiveDateTime::MIN
+        };
+    } else if periods_to_output != 0 {
         end_date = periods_to_output
             .checked_sub(1)
             .and_then(|val| val.checked_mul(step_size.abs()))
@@ -443,8 +484,40 @@ pub fn run_seq_dates(
         });
     }
 
+    let is_weekend = |date: NaiveDateTime| matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+    // Whole weeks keep the weekday, so they would step over the weekend until the end of time
+    if business_days
+        && step_size.num_nanoseconds().unwrap_or(1) % (7 * NANOSECONDS_IN_DAY) == 0
+        && is_weekend(next)
+    {
+        return Err(ShellError::GenericError {
+            error: "No business days in the sequence".into(),
+            msg: "the increment is whole weeks and the first date is on a weekend".into(),
+            span: Some(call_span),
+            help: None,
+            inner: vec![],
+        });
+    }
+
     let mut ret = vec![];
     loop {
+        signals.check(&call_span)?;
+        if business_days && is_weekend(next) {
+            next = next
+                .checked_add_signed(step_size)
+                .ok_or_else(|| ShellError::GenericError {
+                    error: "date overflow".into(),
+                    msg: "adding the increment overflowed".into(),
+                    span: Some(call_span),
+                    help: None,
+                    inner: vec![],
+                })?;
+            if is_out_of_range(next) {
+                break;
+            }
+            continue;
+        }
+
         let mut date_string = String::new();
         match write!(date_string, "{}", next.format(&out_format)) {
             Ok(_) => {}
@@ -459,6 +532,9 @@ pub fn run_seq_dates(
             }
         }
         ret.push(Value::string(date_string, call_span));
+        if limit.is_some_and(|limit| ret.len() >= limit) {
+            break;
+        }
         if let Some(n) = next.checked_add_signed(step_size) {
             next = n;
         } else {
@@ -26,6 +26,10 @@ impl Command for Help {
             .category(Category::Core)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Display help information about different parts of Nushell."
     }
@@ -19,6 +19,10 @@ impl Command for HelpEscapes {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
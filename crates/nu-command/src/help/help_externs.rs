@@ -31,6 +31,10 @@ impl Command for HelpExterns {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -38,6 +38,10 @@ are also available in the current scope. Commands/aliases that were imported und
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -21,6 +21,10 @@ impl Command for HelpOperators {
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -24,6 +24,10 @@ https://www.nushell.sh/lang-guide/chapters/pipelines.html"#
             .allow_variants_without_examples(true)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -2,6 +2,7 @@
 #![doc = include_str!("../README.md")]
 mod bytes;
 mod charting;
+mod collation;
 mod conversions;
 mod date;
 mod debug;
@@ -33,10 +34,12 @@ mod stor;
 mod strings;
 #[cfg(feature = "os")]
 mod system;
+mod ulid;
 mod viewers;
 
 pub use bytes::*;
 pub use charting::*;
+pub use collation::*;
 pub use conversions::*;
 pub use date::*;
 pub use debug::*;
@@ -0,0 +1,894 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use nu_engine::command_prelude::*;
+use nu_protocol::Signals;
+use num_traits::{One, ToPrimitive, Zero};
+use std::{ops::Range, str::FromStr};
+
+/// Numbers can't have more digits than this, before or after the point, so an expression can't
+/// use up all memory.
+const MAX_DIGITS: u64 = 10_000;
+
+/// How deeply an expression can be nested, so parsing and evaluating it doesn't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Halves are rounded away from zero, by `round` and by `--precision`.
+const ROUNDING: RoundingMode = RoundingMode::HalfUp;
+
+#[derive(Clone)]
+pub struct MathEval;
+
+impl Command for MathEval {
+    fn name(&self) -> &str {
+        "math eval"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math eval")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::record(), Type::Any),
+                (Type::table(), Type::List(Box::new(Type::Any))),
+            ])
+            .required(
+                "expression",
+                SyntaxShape::String,
+                "The expression to evaluate.",
+            )
+            .optional(
+                "variables",
+                SyntaxShape::Record(vec![]),
+                "Values of the variables in the expression.",
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "return the result as a string, rounded to this many decimal places",
+                Some('p'),
+            )
+            .category(Category::Math)
+    }
+
+    fn description(&self) -> &str {
+        "Evaluate a math expression written in standard notation."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Expressions use `+`, `-`, `*`, `/`, `%` and `^` (or `**`) with the usual precedence, and
+parentheses. The functions are abs, sqrt, exp, ln, log (with an optional base, 10 by default),
+log2, pow, sin, cos, tan, asin, acos, atan, atan2, sinh, cosh, tanh, floor, ceil, round (with
+optional digits), min and max. The constants are pi, tau and e.
+
+Variables are taken from the record argument, and from the input record. For a table, the
+expression is evaluated for every row.
+
+Numbers are exact decimals, so `0.1 + 0.2` is `0.3`, and division is precise to 100 digits.
+Results with more than 10000 digits are an error, and powers which would have that many are
+computed with floats. Functions other than the arithmetic ones are computed with floats.
+
+`round` and `--precision` round halves away from zero, so `round(2.5)` is 3."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "calculate",
+            "calculator",
+            "formula",
+            "expression",
+            "arithmetic",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let expression: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let variables: Record = call.opt(engine_state, stack, 1)?.unwrap_or_default();
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+        let precision = match precision {
+            Some(precision) if precision.item < 0 || precision.item as u64 > MAX_DIGITS => {
+                return Err(ShellError::IncorrectValue {
+                    msg: format!("the precision has to be between 0 and {MAX_DIGITS}"),
+                    val_span: precision.span,
+                    call_span: head,
+                });
+            }
+            precision => precision.map(|precision| precision.item),
+        };
+
+        let expr = Parser::new(&expression.item)
+            .parse()
+            .map_err(|err| err.into_shell_error(&expression))?;
+        let signals = engine_state.signals().clone();
+        let evaluate = move |value: Value| {
+            let record = match &value {
+                Value::Record { val, .. } => Some(&**val),
+                Value::Nothing { .. } => None,
+                Value::Error { .. } => return value,
+                other => {
+                    return Value::error(
+                        ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "record or table".into(),
+                            wrong_type: other.get_type().to_string(),
+                            dst_span: head,
+                            src_span: other.span(),
+                        },
+                        head,
+                    );
+                }
+            };
+            let scope = Scope {
+                input: record,
+                variables: &variables,
+                signals: &signals,
+            };
+            match expr.eval(&scope) {
+                Ok(num) => num.into_value(precision, head),
+                Err(err) => match signals.check(&head) {
+                    Err(interrupted) => Value::error(interrupted, head),
+                    Ok(()) => Value::error(err.into_shell_error(&expression), head),
+                },
+            }
+        };
+
+        match input {
+            PipelineData::Empty => Ok(evaluate(Value::nothing(head)).into_pipeline_data()),
+            PipelineData::Value(value @ (Value::Record { .. } | Value::Nothing { .. }), ..) => {
+                Ok(evaluate(value).into_pipeline_data())
+            }
+            input => input.map(evaluate, engine_state.signals()),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Evaluate an expression",
+                example: "math eval '2 * (3 + 4) ^ 2'",
+                result: Some(Value::test_int(98)),
+            },
+            Example {
+                description: "Use variables from a record",
+                example: "math eval 'sqrt(x^2 + y^2)' {x: 3, y: 4}",
+                result: Some(Value::test_float(5.0)),
+            },
+            Example {
+                description: "Use the columns of the input record as variables",
+                example: "{width: 3, height: 4} | math eval 'width * height'",
+                result: Some(Value::test_int(12)),
+            },
+            Example {
+                description: "Evaluate a formula for every row of a table",
+                example: "[[price qty]; [1.5 2] [0.1 3]] | math eval 'price * qty'",
+                result: Some(Value::test_list(vec![
+                    Value::test_float(3.0),
+                    Value::test_float(0.3),
+                ])),
+            },
+            Example {
+                description: "Compute with more digits than a float has",
+                example: "math eval '1 / 3' --precision 30",
+                result: Some(Value::test_string("0.333333333333333333333333333333")),
+            },
+        ]
+    }
+}
+
+/// An error in an expression, with the range of it which caused the error.
+struct EvalError {
+    msg: String,
+    range: Range<usize>,
+}
+
+impl EvalError {
+    fn new(msg: impl Into<String>, range: Range<usize>) -> Self {
+        EvalError {
+            msg: msg.into(),
+            range,
+        }
+    }
+
+    fn into_shell_error(self, expression: &Spanned<String>) -> ShellError {
+        // Point at the part of the expression if it's written out as a literal, with or
+        // without quotes
+        let literal_len = expression.span.end - expression.span.start;
+        let span = match literal_len.checked_sub(expression.item.len()) {
+            Some(quotes @ (0 | 2)) => Span::new(
+                expression.span.start + quotes / 2 + self.range.start,
+                expression.span.start + quotes / 2 + self.range.end,
+            ),
+            _ => expression.span,
+        };
+        ShellError::GenericError {
+            error: "Invalid math expression".into(),
+            msg: self.msg,
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        }
+    }
+}
+
+/// A number, which is an int if it was computed from ints without division.
+#[derive(Debug, Clone, PartialEq)]
+struct Num {
+    val: BigDecimal,
+    int: bool,
+}
+
+impl Num {
+    /// A number, unless it has too many digits.
+    fn checked(val: BigDecimal, int: bool, range: &Range<usize>) -> Result<Num, EvalError> {
+        if val.digits() > MAX_DIGITS || val.fractional_digit_count().unsigned_abs() > MAX_DIGITS {
+            return Err(EvalError::new(
+                format!("the number has more than {MAX_DIGITS} digits"),
+                range.clone(),
+            ));
+        }
+        Ok(Num { val, int })
+    }
+
+    fn float(val: f64, range: &Range<usize>) -> Result<Num, EvalError> {
+        // The shortest representation of the float, so 0.1 is 0.1 and not the closest binary
+        // fraction to it
+        Ok(Num {
+            val: BigDecimal::from_str(&val.to_string())
+                .map_err(|_| EvalError::new(format!("the result is {val}"), range.clone()))?,
+            int: false,
+        })
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.val.to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        self.val.is_integer().then(|| self.val.to_i64()).flatten()
+    }
+
+    fn into_value(self, precision: Option<i64>, span: Span) -> Value {
+        match precision {
+            Some(digits) => Value::string(
+                self.val.with_scale_round(digits, ROUNDING).to_string(),
+                span,
+            ),
+            None => match self.to_i64() {
+                Some(val) if self.int => Value::int(val, span),
+                _ => Value::float(self.to_f64(), span),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Num(Num),
+    Var(String, Range<usize>),
+    Neg(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>, Range<usize>),
+    /// Operators of the same precedence, like `1 + 2 - 3`, which are evaluated from left to
+    /// right without nesting them.
+    Chain(Box<Expr>, Vec<(char, Expr, Range<usize>)>),
+    Call(String, Vec<Expr>, Range<usize>),
+}
+
+struct Scope<'a> {
+    input: Option<&'a Record>,
+    variables: &'a Record,
+    signals: &'a Signals,
+}
+
+impl Scope<'_> {
+    /// Stop evaluating if the evaluation was interrupted.
+    fn check_signals(&self, range: &Range<usize>) -> Result<(), EvalError> {
+        match self.signals.interrupted() {
+            true => Err(EvalError::new("interrupted", range.clone())),
+            false => Ok(()),
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, scope: &Scope) -> Result<Num, EvalError> {
+        match self {
+            Expr::Num(num) => Ok(num.clone()),
+            Expr::Var(name, range) => {
+                let value = scope
+                    .variables
+                    .get(name)
+                    .or_else(|| scope.input.and_then(|input| input.get(name)));
+                match value {
+                    Some(Value::Int { val, .. }) => Ok(Num {
+                        val: (*val).into(),
+                        int: true,
+                    }),
+                    Some(Value::Float { val, .. }) => Num::float(*val, range),
+                    Some(other) => Err(EvalError::new(
+                        format!("`{name}` is a {}, not a number", other.get_type()),
+                        range.clone(),
+                    )),
+                    None => match name.as_str() {
+                        "pi" => Num::float(std::f64::consts::PI, range),
+                        "tau" => Num::float(std::f64::consts::TAU, range),
+                        "e" => Num::float(std::f64::consts::E, range),
+                        _ => Err(EvalError::new(
+                            format!("`{name}` isn't defined"),
+                            range.clone(),
+                        )),
+                    },
+                }
+            }
+            Expr::Neg(expr) => {
+                let num = expr.eval(scope)?;
+                Ok(Num {
+                    val: -num.val,
+                    int: num.int,
+                })
+            }
+            Expr::Binary(op, lhs, rhs, range) => {
+                scope.check_signals(range)?;
+                binary(*op, lhs.eval(scope)?, rhs.eval(scope)?, range)
+            }
+            Expr::Chain(first, rest) => {
+                rest.iter()
+                    .try_fold(first.eval(scope)?, |lhs, (op, rhs, range)| {
+                        scope.check_signals(range)?;
+                        binary(*op, lhs, rhs.eval(scope)?, range)
+                    })
+            }
+            Expr::Call(name, args, range) => {
+                scope.check_signals(range)?;
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(scope))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(name, args, range)
+            }
+        }
+    }
+}
+
+fn binary(op: char, lhs: Num, rhs: Num, range: &Range<usize>) -> Result<Num, EvalError> {
+    let int = lhs.int && rhs.int;
+    let val = match op {
+        '+' => lhs.val + rhs.val,
+        '-' => lhs.val - rhs.val,
+        '*' => lhs.val * rhs.val,
+        '/' | '%' if rhs.val.is_zero() => {
+            return Err(EvalError::new("division by zero", range.clone()));
+        }
+        '/' => {
+            return Ok(Num {
+                val: lhs.val / rhs.val,
+                int: false,
+            });
+        }
+        '%' => lhs.val % rhs.val,
+        '^' => return pow(lhs, rhs, range),
+        _ => unreachable!("the parser only creates these operators"),
+    };
+    Num::checked(val, int, range)
+}
+
+/// `lhs` to the power of `rhs`, which is exact for integer powers unless the result would have
+/// too many digits.
+fn pow(lhs: Num, rhs: Num, range: &Range<usize>) -> Result<Num, EvalError> {
+    let exponent = rhs.to_i64().filter(|exponent| {
+        lhs.val
+            .digits()
+            .checked_mul(exponent.unsigned_abs())
+            .is_some_and(|digits| digits <= MAX_DIGITS)
+    });
+    let Some(exponent) = exponent else {
+        return Num::float(lhs.to_f64().powf(rhs.to_f64()), range);
+    };
+    if exponent < 0 && lhs.val.is_zero() {
+        return Err(EvalError::new("division by zero", range.clone()));
+    }
+
+    let mut result = BigDecimal::one();
+    let mut base = lhs.val;
+    let mut rest = exponent.unsigned_abs();
+    while rest > 0 {
+        if rest & 1 == 1 {
+            result *= &base;
+        }
+        base = &base * &base;
+        rest >>= 1;
+    }
+    if exponent < 0 {
+        result = BigDecimal::one() / result;
+    }
+    Num::checked(result, lhs.int && rhs.int && exponent >= 0, range)
+}
+
+fn call(name: &str, args: Vec<Num>, range: &Range<usize>) -> Result<Num, EvalError> {
+    let arity = |min: usize, max: usize| {
+        if args.len() < min || args.len() > max {
+            let expected = match (min, max) {
+                (1, 1) => "1 argument".into(),
+                (min, max) if min == max => format!("{min} arguments"),
+                (min, max) => format!("{min} or {max} arguments"),
+            };
+            Err(EvalError::new(
+                format!("`{name}` takes {expected}, not {}", args.len()),
+                range.clone(),
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    let float = |f: fn(f64) -> f64| -> Result<Num, EvalError> {
+        arity(1, 1)?;
+        let val = f(args[0].to_f64());
+        if val.is_nan() {
+            return Err(EvalError::new(
+                format!("`{name}` isn't defined for {}", args[0].val),
+                range.clone(),
+            ));
+        }
+        Num::float(val, range)
+    };
+
+    match name {
+        "sin" => float(f64::sin),
+        "cos" => float(f64::cos),
+        "tan" => float(f64::tan),
+        "asin" => float(f64::asin),
+        "acos" => float(f64::acos),
+        "atan" => float(f64::atan),
+        "sinh" => float(f64::sinh),
+        "cosh" => float(f64::cosh),
+        "tanh" => float(f64::tanh),
+        "exp" => float(f64::exp),
+        "ln" => float(f64::ln),
+        "log2" => float(f64::log2),
+        "atan2" => {
+            arity(2, 2)?;
+            Num::float(args[0].to_f64().atan2(args[1].to_f64()), range)
+        }
+        "log" => {
+            arity(1, 2)?;
+            let base = args.get(1).map_or(10.0, Num::to_f64);
+            let val = match base {
+                10.0 => args[0].to_f64().log10(),
+                _ => args[0].to_f64().log(base),
+            };
+            if val.is_nan() || val.is_infinite() {
+                return Err(EvalError::new(
+                    format!("`log` isn't defined for {} in base {base}", args[0].val),
+                    range.clone(),
+                ));
+            }
+            Num::float(val, range)
+        }
+        "sqrt" => {
+            arity(1, 1)?;
+            let val = args[0].val.sqrt().ok_or_else(|| {
+                EvalError::new(
+                    format!("`sqrt` isn't defined for {}", args[0].val),
+                    range.clone(),
+                )
+            })?;
+            Ok(Num { val, int: false })
+        }
+        "pow" => {
+            arity(2, 2)?;
+            let mut args = args.into_iter();
+            let (lhs, rhs) = (args.next(), args.next());
+            pow(lhs.expect("checked"), rhs.expect("checked"), range)
+        }
+        "abs" => {
+            arity(1, 1)?;
+            Ok(Num {
+                val: args[0].val.abs(),
+                int: args[0].int,
+            })
+        }
+        "floor" | "ceil" => {
+            arity(1, 1)?;
+            let mode = match name {
+                "floor" => RoundingMode::Floor,
+                _ => RoundingMode::Ceiling,
+            };
+            Ok(Num {
+                val: args[0].val.with_scale_round(0, mode),
+                int: true,
+            })
+        }
+        "round" => {
+            arity(1, 2)?;
+            let digits = match args.get(1) {
+                Some(digits) => digits
+                    .to_i64()
+                    .filter(|digits| digits.unsigned_abs() <= MAX_DIGITS)
+                    .ok_or_else(|| {
+                        EvalError::new(
+                            format!(
+                                "the digits of `round` have to be an int between -{MAX_DIGITS} and {MAX_DIGITS}"
+                            ),
+                            range.clone(),
+                        )
+                    })?,
+                None => 0,
+            };
+            Ok(Num {
+                val: args[0].val.with_scale_round(digits, ROUNDING),
+                int: digits <= 0,
+            })
+        }
+        "min" | "max" => {
+            if args.is_empty() {
+                return Err(EvalError::new(
+                    format!("`{name}` takes at least 1 argument"),
+                    range.clone(),
+                ));
+            }
+            let pick = |a: Num, b: Num| match (name, a.val < b.val) {
+                ("min", true) | ("max", false) => a,
+                _ => b,
+            };
+            Ok(args.into_iter().reduce(pick).expect("checked above"))
+        }
+        _ => Err(EvalError::new(
+            format!("`{name}` isn't a function"),
+            range.clone(),
+        )),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(BigDecimal, bool),
+    Ident(String),
+    Op(char),
+    Open,
+    Close,
+    Comma,
+    End,
+}
+
+/// A recursive descent parser for expressions.
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+    token: Token,
+    range: Range<usize>,
+    /// How deeply the expression which is parsed is nested.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            source,
+            pos: 0,
+            token: Token::End,
+            range: 0..0,
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Expr, EvalError> {
+        self.advance()?;
+        let expr = self.sum()?;
+        match self.token {
+            Token::End => Ok(expr),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn unexpected(&self) -> EvalError {
+        match self.token {
+            Token::End => EvalError::new("unexpected end of the expression", self.range.clone()),
+            _ => EvalError::new(
+                format!("unexpected `{}`", &self.source[self.range.clone()]),
+                self.range.clone(),
+            ),
+        }
+    }
+
+    /// Go one level deeper into the expression.
+    fn enter(&mut self) -> Result<(), EvalError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(EvalError::new(
+                format!("the expression is nested more than {MAX_DEPTH} levels deep"),
+                self.range.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Read the next token.
+    fn advance(&mut self) -> Result<(), EvalError> {
+        let rest = &self.source[self.pos..];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+        let rest = &self.source[start..];
+        let mut chars = rest.chars();
+        let (token, len) = match chars.next() {
+            None => (Token::End, 0),
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let mut len = rest
+                    .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+                    .unwrap_or(rest.len());
+                // An exponent, like 1e-3
+                if let Some(exponent) = rest[len..].strip_prefix(['e', 'E']) {
+                    let sign = usize::from(exponent.starts_with(['+', '-']));
+                    let digits = exponent[sign..]
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(exponent.len() - sign);
+                    if digits > 0 {
+                        len += 1 + sign + digits;
+                    }
+                }
+                let text = rest[..len].replace('_', "");
+                let val = BigDecimal::from_str(&text).map_err(|_| {
+                    EvalError::new(format!("`{text}` isn't a number"), start..start + len)
+                })?;
+                let int = !text.contains(['.', 'e', 'E']);
+                let num = Num::checked(val, int, &(start..start + len))?;
+                (Token::Num(num.val, num.int), len)
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (Token::Ident(rest[..len].into()), len)
+            }
+            Some('*') if rest.starts_with("**") => (Token::Op('^'), 2),
+            Some(c @ ('+' | '-' | '*' | '/' | '%' | '^')) => (Token::Op(c), 1),
+            Some('(') => (Token::Open, 1),
+            Some(')') => (Token::Close, 1),
+            Some(',') => (Token::Comma, 1),
+            Some(c) => {
+                return Err(EvalError::new(
+                    format!("unexpected `{c}`"),
+                    start..start + c.len_utf8(),
+                ));
+            }
+        };
+        self.token = token;
+        self.range = start..start + len;
+        self.pos = start + len;
+        Ok(())
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[char],
+        operand: fn(&mut Self) -> Result<Expr, EvalError>,
+    ) -> Result<Expr, EvalError> {
+        let first = operand(self)?;
+        let mut rest = vec![];
+        while let Token::Op(op) = self.token
+            && ops.contains(&op)
+        {
+            let range = self.range.clone();
+            self.advance()?;
+            rest.push((op, operand(self)?, range));
+        }
+        match rest.is_empty() {
+            true => Ok(first),
+            false => Ok(Expr::Chain(Box::new(first), rest)),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expr, EvalError> {
+        self.binary(&['+', '-'], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, EvalError> {
+        self.binary(&['*', '/', '%'], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Expr, EvalError> {
+        match self.token {
+            Token::Op('-') => {
+                self.enter()?;
+                self.advance()?;
+                let expr = self.unary()?;
+                self.depth -= 1;
+                Ok(Expr::Neg(Box::new(expr)))
+            }
+            Token::Op('+') => {
+                self.enter()?;
+                self.advance()?;
+                let expr = self.unary()?;
+                self.depth -= 1;
+                Ok(expr)
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// Powers are right associative, and bind tighter than negation, so `-2^2` is -4.
+    fn power(&mut self) -> Result<Expr, EvalError> {
+        let base = self.primary()?;
+        if self.token != Token::Op('^') {
+            return Ok(base);
+        }
+        let range = self.range.clone();
+        self.enter()?;
+        self.advance()?;
+        let exponent = self.unary()?;
+        self.depth -= 1;
+        Ok(Expr::Binary('^', Box::new(base), Box::new(exponent), range))
+    }
+
+    fn primary(&mut self) -> Result<Expr, EvalError> {
+        let range = self.range.clone();
+        match std::mem::replace(&mut self.token, Token::End) {
+            Token::Num(val, int) => {
+                self.advance()?;
+                Ok(Expr::Num(Num { val, int }))
+            }
+            Token::Ident(name) => {
+                self.advance()?;
+                if self.token != Token::Open {
+                    return Ok(Expr::Var(name, range));
+                }
+                self.enter()?;
+                self.advance()?;
+                let mut args = vec![];
+                if self.token != Token::Close {
+                    args.push(self.sum()?);
+                    while self.token == Token::Comma {
+                        self.advance()?;
+                        args.push(self.sum()?);
+                    }
+                }
+                let end = self.range.end;
+                self.expect_close()?;
+                self.depth -= 1;
+                Ok(Expr::Call(name, args, range.start..end))
+            }
+            Token::Open => {
+                self.enter()?;
+                self.advance()?;
+                let expr = self.sum()?;
+                self.expect_close()?;
+                self.depth -= 1;
+                Ok(expr)
+            }
+            token => {
+                self.token = token;
+                Err(self.unexpected())
+            }
+        }
+    }
+
+    fn expect_close(&mut self) -> Result<(), EvalError> {
+        match self.token {
+            Token::Close => self.advance(),
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn eval(expression: &str, variables: Record) -> Result<Value, String> {
+        let expr = Parser::new(expression).parse().map_err(|err| err.msg)?;
+        let scope = Scope {
+            input: None,
+            variables: &variables,
+            signals: &Signals::empty(),
+        };
+        expr.eval(&scope)
+            .map(|num| num.into_value(None, Span::test_data()))
+            .map_err(|err| err.msg)
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(MathEval {})
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3", Record::new()), Ok(Value::test_int(7)));
+        assert_eq!(eval("-2^2", Record::new()), Ok(Value::test_int(-4)));
+        assert_eq!(eval("2^3^2", Record::new()), Ok(Value::test_int(512)));
+        assert_eq!(eval("2 ** -1", Record::new()), Ok(Value::test_float(0.5)));
+        assert_eq!(
+            eval("(1 + 2) * 3 % 4", Record::new()),
+            Ok(Value::test_int(1))
+        );
+    }
+
+    #[test]
+    fn decimals_are_exact() {
+        assert_eq!(eval("0.1 + 0.2", Record::new()), Ok(Value::test_float(0.3)));
+        assert_eq!(eval("1e3 / 8", Record::new()), Ok(Value::test_float(125.0)));
+    }
+
+    #[test]
+    fn functions_and_variables() {
+        let variables = record! { "x" => Value::test_int(100) };
+        assert_eq!(
+            eval("log(x)", variables.clone()),
+            Ok(Value::test_float(2.0))
+        );
+        assert_eq!(eval("max(1, x, 3)", variables), Ok(Value::test_int(100)));
+        assert_eq!(
+            eval("round(pi * 10, 1)", Record::new()),
+            Ok(Value::test_float(31.4))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(eval("1 / 0", Record::new()).is_err());
+        assert!(eval("y + 1", Record::new()).is_err());
+        assert!(eval("sin(1, 2)", Record::new()).is_err());
+        assert!(eval("(1 + 2", Record::new()).is_err());
+        assert!(eval("1 +", Record::new()).is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let nested = format!(
+            "{}1{}",
+            "(".repeat(MAX_DEPTH + 1),
+            ")".repeat(MAX_DEPTH + 1)
+        );
+        assert!(eval(&nested, Record::new()).is_err());
+        assert!(eval(&"-".repeat(100_000), Record::new()).is_err());
+        assert_eq!(
+            eval(&vec!["1"; 100_000].join(" + "), Record::new()),
+            Ok(Value::test_int(100_000))
+        );
+
+        let nested = format!("{}1{}", "(".repeat(100), ")".repeat(100));
+        assert_eq!(eval(&nested, Record::new()), Ok(Value::test_int(1)));
+    }
+
+    #[test]
+    fn huge_numbers_are_limited() {
+        assert!(eval("2^1000", Record::new()).is_ok());
+        assert!(eval("10^5000", Record::new()).is_ok());
+        assert!(eval("10^5000 * 10^5000", Record::new()).is_err());
+        assert!(eval("1e100000", Record::new()).is_err());
+        assert!(eval("1e9999 * 1e9999", Record::new()).is_err());
+        assert!(eval("round(1, 100000)", Record::new()).is_err());
+        // Too many digits for an exact power, so it's computed with floats
+        assert_eq!(
+            eval("9^100000", Record::new()),
+            Err("the result is inf".into())
+        );
+    }
+
+    #[test]
+    fn halves_are_rounded_away_from_zero() {
+        assert_eq!(eval("round(2.5)", Record::new()), Ok(Value::test_int(3)));
+        assert_eq!(eval("round(-2.5)", Record::new()), Ok(Value::test_int(-3)));
+        assert_eq!(
+            Num::float(0.125, &(0..0))
+                .map(|num| num.into_value(Some(2), Span::test_data()))
+                .ok(),
+            Some(Value::test_string("0.13"))
+        );
+    }
+
+    #[test]
+    fn interrupted() {
+        let signals = Signals::new(std::sync::Arc::new(true.into()));
+        let expr = Parser::new("1 + 2").parse().map_err(|err| err.msg);
+        let scope = Scope {
+            input: None,
+            variables: &Record::new(),
+            signals: &signals,
+        };
+        let result = expr.and_then(|expr| expr.eval(&scope).map_err(|err| err.msg));
+        assert_eq!(result, Err("interrupted".into()));
+    }
+}
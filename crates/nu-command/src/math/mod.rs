@@ -1,6 +1,7 @@
 mod abs;
 mod avg;
 mod ceil;
+mod eval;
 mod floor;
 mod log;
 pub mod math_;
@@ -20,6 +21,7 @@ mod variance;
 pub use abs::MathAbs;
 pub use avg::MathAvg;
 pub use ceil::MathCeil;
+pub use eval::MathEval;
 pub use floor::MathFloor;
 pub use math_::MathCommand as Math;
 pub use max::MathMax;
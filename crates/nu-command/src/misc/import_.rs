@@ -0,0 +1,44 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Import;
+
+impl Command for Import {
+    fn name(&self) -> &str {
+        "import"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("import")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for loading code at runtime."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Files with constant paths are loaded with the `source` and `use` keywords instead.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["source", "use", "load"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,130 @@
+use nu_engine::{command_prelude::*, find_in_dirs_env};
+use nu_protocol::shell_error::{self, io::IoError};
+use nu_utils::escape_quote_string;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct ImportDynamic;
+
+impl Command for ImportDynamic {
+    fn name(&self) -> &str {
+        "import dynamic"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("import dynamic")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+            .required(
+                "path",
+                SyntaxShape::String,
+                "The script or module file, which can be computed at runtime.",
+            )
+            .switch(
+                "module",
+                "import the file as a module with all its exports, like `use <path> *`",
+                Some('m'),
+            )
+            .category(Category::Core)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Source a script or use a module whose path is only known at runtime."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"`source` and `use` are parser keywords, so their paths have to be constants. This command
+instead finds the file when it runs, relative to the current directory or `$env.NU_LIB_DIRS`, and
+queues it. The file is loaded at the top level after the current command line, hook, config file
+or script has been evaluated, as if it had been sourced from there. Its definitions and environment
+changes are then available to everything which runs afterwards, but not to the rest of the code
+which called `import dynamic`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["source", "use", "runtime", "load", "plugin", "profile"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Load the scripts of all installed packages",
+                example: r#"ls ($nu.data-dir | path join packages *.nu) | each { import dynamic $in.name }"#,
+                result: None,
+            },
+            Example {
+                description: "Use the commands of a module chosen by an environment variable",
+                example: r#"import dynamic --module $"profiles/($env.PROFILE).nu""#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let module = call.has_flag(engine_state, stack, "module")?;
+
+        // The file would be loaded after the sandboxed code has returned, outside of the sandbox
+        if engine_state.sandbox.is_some() {
+            return Err(ShellError::SandboxDenied {
+                command: self.name().into(),
+                effect: "load files after leaving the sandbox".into(),
+                span: call.head,
+                help: "use `source` or `use` with a constant path instead".into(),
+            });
+        }
+        // Nothing evaluates the files queued by a background job
+        if engine_state.is_background_job() {
+            return Err(ShellError::GenericError {
+                error: "Can't import files in a background job".into(),
+                msg: "the job has no top level to load the file into".into(),
+                span: Some(call.head),
+                help: Some("use `source` or `use` with a constant path instead".into()),
+                inner: vec![],
+            });
+        }
+
+        let Some(file) = find_in_dirs_env(&path.item, engine_state, stack, None)? else {
+            return Err(ShellError::Io(IoError::new(
+                shell_error::io::ErrorKind::FileNotFound,
+                path.span,
+                PathBuf::from(path.item),
+            )));
+        };
+        let file = escape_quote_string(&file.to_string_lossy());
+        let source = if module {
+            format!("use {file} *")
+        } else {
+            format!("source {file}")
+        };
+        engine_state
+            .deferred_sources
+            .lock()
+            .expect("deferred sources mutex")
+            .push(source);
+
+        Ok(PipelineData::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(ImportDynamic {})
+    }
+}
@@ -1,7 +1,15 @@
+mod import_;
+mod import_dynamic;
 mod panic;
 mod source;
+mod translate;
 mod tutor;
+mod watch_var;
 
+pub use import_::Import;
+pub use import_dynamic::ImportDynamic;
 pub use panic::Panic;
 pub use source::Source;
+pub use translate::Translate;
 pub use tutor::Tutor;
+pub use watch_var::WatchVar;
@@ -1,6 +1,6 @@
 use nu_engine::{command_prelude::*, get_eval_block_with_early_return};
 use nu_path::{canonicalize_with, is_windows_device_path};
-use nu_protocol::{BlockId, engine::CommandType, shell_error::io::IoError};
+use nu_protocol::{BlockId, VarId, engine::CommandType, shell_error::io::IoError};
 
 /// Source a file for environment variables.
 #[derive(Clone)]
@@ -19,6 +19,11 @@ impl Command for Source {
                 SyntaxShape::OneOf(vec![SyntaxShape::Filepath, SyntaxShape::Nothing]),
                 "The filepath to the script file to source (`null` for no-op).",
             )
+            .rest(
+                "args",
+                SyntaxShape::Any,
+                "Arguments for the script after `--`, which it gets as the list `$argv`.",
+            )
             .category(Category::Core)
     }
 
@@ -45,13 +50,18 @@ impl Command for Source {
         if call.get_parser_info(stack, "noop").is_some() {
             return Ok(PipelineData::empty());
         }
-        // Note: two hidden positionals are used here that are injected by the parser:
+        // Note: three hidden positionals are used here that are injected by the parser:
         // 1. The block_id that corresponded to the 0th position
         // 2. The block_id_name that corresponded to the file name at the 0th position
+        // 3. The argv_var_id of the `$argv` variable, which holds the rest of the arguments
         let block_id: i64 = call.req_parser_info(engine_state, stack, "block_id")?;
         let block_id_name: String = call.req_parser_info(engine_state, stack, "block_id_name")?;
         let block_id = BlockId::new(block_id as usize);
         let block = engine_state.get_block(block_id).clone();
+        let argv_var_id: i64 = call.req_parser_info(engine_state, stack, "argv_var_id")?;
+        let argv_var_id = VarId::new(argv_var_id as usize);
+        let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
+        stack.add_var(argv_var_id, Value::list(args, call.head));
         let cwd = engine_state.cwd_as_string(Some(stack))?;
         let pb = std::path::PathBuf::from(block_id_name);
         let parent = pb.parent().unwrap_or(std::path::Path::new(""));
@@ -97,6 +107,7 @@ impl Command for Source {
         } else {
             stack.remove_env_var(engine_state, "CURRENT_FILE");
         }
+        stack.remove_var(argv_var_id);
 
         return_result
     }
@@ -113,6 +124,11 @@ impl Command for Source {
                 example: r#"source ./foo.nu; say-hi"#,
                 result: None,
             },
+            Example {
+                description: "Runs setup.nu with arguments, which it gets in `$argv`",
+                example: r#"source setup.nu -- --env prod"#,
+                result: None,
+            },
             Example {
                 description: "Sourcing `null` is a no-op.",
                 example: r#"source null"#,
@@ -0,0 +1,110 @@
+mod posix;
+
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+
+#[derive(Clone)]
+pub struct Translate;
+
+impl Command for Translate {
+    fn name(&self) -> &str {
+        "translate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("translate")
+            .input_output_types(vec![
+                (Type::Nothing, Type::String),
+                (Type::String, Type::String),
+            ])
+            .allow_variants_without_examples(true)
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "The script to translate, instead of the input.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Translate a POSIX shell script into nushell, as a start for porting it."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Variables, exports, PATH changes, if, for, while and functions, the tests of `[`, pipes,
+redirections, `&&` and `||`, command substitutions and arithmetic are translated. Statements
+without a close equivalent, like case, trap and eval, are kept as comments after a TODO marker.
+
+External commands which have the name of a nushell command, like ls and sort, are called with
+`^` to keep their behavior. The script's arguments become those of a main function."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "bash", "sh", "zsh", "convert", "port", "migrate", "dotfiles",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+
+        let script = match path {
+            Some(path) => {
+                let cwd = engine_state.cwd(Some(stack))?;
+                let file = nu_path::expand_path_with(&path.item, &cwd, true);
+                std::fs::read_to_string(&file).map_err(|err| IoError::new(err, path.span, file))?
+            }
+            None => input.collect_string_strict(head)?.0,
+        };
+
+        let is_command = |name: &str| engine_state.find_decl(name.as_bytes(), &[]).is_some();
+        Ok(Value::string(posix::translate(&script, &is_command), head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Translate a PATH change",
+                example: r#"'export PATH="$HOME/bin:$PATH"' | translate"#,
+                result: Some(Value::test_string(
+                    r#"$env.PATH = ($env.PATH | prepend $"($env.HOME)/bin")"#,
+                )),
+            },
+            Example {
+                description: "Translate a loop",
+                example: "'for f in *.log; do gzip \"$f\"; done' | translate",
+                result: Some(Value::test_string(
+                    "for f in (glob *.log) {\n    gzip $f\n}",
+                )),
+            },
+            Example {
+                description: "Port a script to a file",
+                example: "translate deploy.sh | save deploy.nu",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Translate {})
+    }
+}
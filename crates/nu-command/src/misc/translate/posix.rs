@@ -0,0 +1,1611 @@
+//! A best-effort translation of POSIX shell scripts into nushell.
+//!
+//! The script is split into words and operators like a shell does, and each statement is
+//! translated on its own. What has no close equivalent is kept as a comment after a TODO marker,
+//! so the output always parses, and shows what's left to do by hand.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    /// Literal text, and whether it was quoted, so that globs in it are literal
+    Text(String, bool),
+    /// `$NAME`, `${NAME}`, or a special parameter like `$1` or `$@`
+    Variable(String),
+    /// `$(...)` or a command in backquotes
+    Command(String),
+    /// `$((...))`
+    Arithmetic(String),
+    /// An expansion without an equivalent, like `${NAME%suffix}`
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Word(Vec<Part>);
+
+impl Word {
+    fn push(&mut self, char: char, quoted: bool) {
+        match self.0.last_mut() {
+            Some(Part::Text(text, was_quoted)) if *was_quoted == quoted => text.push(char),
+            _ => self.0.push(Part::Text(char.into(), quoted)),
+        }
+    }
+
+    /// The parts of a word, without the empty text which quotes leave.
+    fn parts(&self) -> Vec<&Part> {
+        self.0
+            .iter()
+            .filter(|part| !matches!(part, Part::Text(text, _) if text.is_empty()))
+            .collect()
+    }
+
+    /// The text of a word without expansions.
+    fn text(&self) -> Option<String> {
+        self.0
+            .iter()
+            .map(|part| match part {
+                Part::Text(text, _) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is(&self, text: &str) -> bool {
+        matches!(self.0.as_slice(), [Part::Text(word, false)] if word == text)
+    }
+
+    fn has_glob(&self) -> bool {
+        self.0
+            .iter()
+            .any(|part| matches!(part, Part::Text(text, false) if text.contains(['*', '?', '['])))
+    }
+
+    /// The name and value of an assignment like `NAME=value`.
+    fn assignment(&self) -> Option<(String, Word)> {
+        let Some(Part::Text(first, false)) = self.0.first() else {
+            return None;
+        };
+        let (name, value) = first.split_once('=')?;
+        if !is_name(name) {
+            return None;
+        }
+        let mut parts = self.0[1..].to_vec();
+        if !value.is_empty() {
+            parts.insert(0, Part::Text(value.into(), false));
+        }
+        Some((name.into(), Word(parts)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(Word),
+    Operator(&'static str),
+    Comment(String),
+}
+
+impl Token {
+    fn is_word(&self, text: &str) -> bool {
+        matches!(self, Token::Word(word) if word.is(text))
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Split a script into tokens, and the line each of them is on.
+fn lex(text: &str) -> Vec<(Token, usize)> {
+    let mut tokens: Vec<(Token, usize)> = vec![];
+    let mut word: Option<Word> = None;
+    let mut line = 0;
+    let mut chars = text.chars().peekable();
+
+    fn flush(tokens: &mut Vec<(Token, usize)>, word: &mut Option<Word>, line: usize) {
+        if let Some(word) = word.take() {
+            tokens.push((Token::Word(word), line));
+        }
+    }
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\n' => {
+                flush(&mut tokens, &mut word, line);
+                tokens.push((Token::Operator("\n"), line));
+                line += 1;
+                heredocs(&mut tokens, &mut chars, &mut line);
+            }
+            ' ' | '\t' | '\r' => flush(&mut tokens, &mut word, line),
+            '\\' => match chars.next() {
+                Some('\n') => line += 1,
+                Some(char) => word.get_or_insert_default().push(char, true),
+                None => {}
+            },
+            '#' if word.is_none() => {
+                let mut comment = String::new();
+                while let Some(char) = chars.next_if(|char| *char != '\n') {
+                    comment.push(char);
+                }
+                tokens.push((Token::Comment(comment), line));
+            }
+            '\'' => {
+                let word = word.get_or_insert_default();
+                // An empty string is still a word
+                word.0.push(Part::Text(String::new(), true));
+                for char in chars.by_ref() {
+                    match char {
+                        '\'' => break,
+                        '\n' => {
+                            line += 1;
+                            word.push(char, true);
+                        }
+                        char => word.push(char, true),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_default();
+                word.0.push(Part::Text(String::new(), true));
+                while let Some(char) = chars.next() {
+                    match char {
+                        '"' => break,
+                        '\\' => match chars.next_if(|char| matches!(*char, '$' | '`' | '"' | '\\'))
+                        {
+                            Some(char) => word.push(char, true),
+                            None => word.push('\\', true),
+                        },
+                        '$' => dollar(&mut chars, word, true),
+                        '`' => word.0.push(Part::Command(backquoted(&mut chars))),
+                        '\n' => {
+                            line += 1;
+                            word.push(char, true);
+                        }
+                        char => word.push(char, true),
+                    }
+                }
+            }
+            '`' => word
+                .get_or_insert_default()
+                .0
+                .push(Part::Command(backquoted(&mut chars))),
+            '$' => dollar(&mut chars, word.get_or_insert_default(), false),
+            ';' => {
+                flush(&mut tokens, &mut word, line);
+                let operator = if chars.next_if_eq(&';').is_some() {
+                    ";;"
+                } else {
+                    ";"
+                };
+                tokens.push((Token::Operator(operator), line));
+            }
+            '|' => {
+                flush(&mut tokens, &mut word, line);
+                let operator = if chars.next_if_eq(&'|').is_some() {
+                    "||"
+                } else {
+                    "|"
+                };
+                tokens.push((Token::Operator(operator), line));
+            }
+            '&' => {
+                flush(&mut tokens, &mut word, line);
+                let operator = if chars.next_if_eq(&'&').is_some() {
+                    "&&"
+                } else if chars.next_if_eq(&'>').is_some() {
+                    "&>"
+                } else {
+                    "&"
+                };
+                tokens.push((Token::Operator(operator), line));
+            }
+            '>' => {
+                // `2>` redirects stderr, and `1>` is the same as `>`
+                let stderr = word.as_ref().is_some_and(|word| word.is("2"));
+                if stderr || word.as_ref().is_some_and(|word| word.is("1")) {
+                    word = None;
+                }
+                flush(&mut tokens, &mut word, line);
+                let operator = if chars.next_if_eq(&'>').is_some() {
+                    if stderr { "2>>" } else { ">>" }
+                } else if chars.next_if_eq(&'&').is_some() {
+                    match chars.next() {
+                        Some('1') if stderr => "2>&1",
+                        Some('2') if !stderr => ">&2",
+                        _ => ">&",
+                    }
+                } else if stderr {
+                    "2>"
+                } else {
+                    ">"
+                };
+                tokens.push((Token::Operator(operator), line));
+            }
+            '<' => {
+                flush(&mut tokens, &mut word, line);
+                let operator = if chars.next_if_eq(&'<').is_some() {
+                    if chars.next_if_eq(&'-').is_some() {
+                        "<<-"
+                    } else {
+                        "<<"
+                    }
+                } else {
+                    "<"
+                };
+                tokens.push((Token::Operator(operator), line));
+            }
+            '(' | ')' => {
+                flush(&mut tokens, &mut word, line);
+                let operator = if char == '(' { "(" } else { ")" };
+                tokens.push((Token::Operator(operator), line));
+            }
+            char => word.get_or_insert_default().push(char, false),
+        }
+    }
+    flush(&mut tokens, &mut word, line);
+    tokens
+}
+
+/// Read the bodies of the here-documents of the line which just ended, in place of their
+/// delimiters.
+fn heredocs(
+    tokens: &mut [(Token, usize)],
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    line: &mut usize,
+) {
+    let start = tokens[..tokens.len() - 1]
+        .iter()
+        .rposition(|(token, _)| *token == Token::Operator("\n"))
+        .map_or(0, |newline| newline + 1);
+    for index in start..tokens.len() - 1 {
+        let strip_tabs = match tokens[index].0 {
+            Token::Operator("<<") => false,
+            Token::Operator("<<-") => true,
+            _ => continue,
+        };
+        let Some((Token::Word(delimiter), _)) = tokens.get(index + 1) else {
+            continue;
+        };
+        let Some(delimiter) = delimiter.text() else {
+            continue;
+        };
+        let mut body = String::new();
+        loop {
+            let mut text = String::new();
+            let mut ended = true;
+            for char in chars.by_ref() {
+                if char == '\n' {
+                    ended = false;
+                    break;
+                }
+                text.push(char);
+            }
+            *line += 1;
+            let text = if strip_tabs {
+                text.trim_start_matches('\t')
+            } else {
+                &text
+            };
+            if text == delimiter || (ended && text.is_empty()) {
+                break;
+            }
+            body.push_str(text);
+            body.push('\n');
+            if ended {
+                break;
+            }
+        }
+        tokens[index + 1].0 = Token::Word(Word(vec![Part::Text(body, true)]));
+    }
+}
+
+fn backquoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut command = String::new();
+    while let Some(char) = chars.next() {
+        match char {
+            '`' => break,
+            '\\' => command.extend(chars.next()),
+            char => command.push(char),
+        }
+    }
+    command
+}
+
+/// Read an expansion after a `$`.
+fn dollar(chars: &mut std::iter::Peekable<std::str::Chars>, word: &mut Word, quoted: bool) {
+    if chars.next_if_eq(&'(').is_some() {
+        if chars.next_if_eq(&'(').is_some() {
+            let expression = until_closing(chars, 2);
+            word.0.push(Part::Arithmetic(
+                expression.strip_suffix(')').unwrap_or(&expression).into(),
+            ));
+        } else {
+            word.0.push(Part::Command(until_closing(chars, 1)));
+        }
+    } else if chars.next_if_eq(&'{').is_some() {
+        let mut name = String::new();
+        for char in chars.by_ref() {
+            if char == '}' {
+                break;
+            }
+            name.push(char);
+        }
+        if is_name(&name) || is_special(&name) {
+            word.0.push(Part::Variable(name));
+        } else {
+            word.0.push(Part::Unsupported(format!("${{{name}}}")));
+        }
+    } else if let Some(char) = chars
+        .next_if(|char| char.is_ascii_digit() || matches!(*char, '@' | '*' | '#' | '?' | '$' | '!'))
+    {
+        word.0.push(Part::Variable(char.into()));
+    } else {
+        let mut name = String::new();
+        while let Some(char) = chars.next_if(|char| char.is_ascii_alphanumeric() || *char == '_') {
+            name.push(char);
+        }
+        if name.is_empty() {
+            word.push('$', quoted);
+        } else {
+            word.0.push(Part::Variable(name));
+        }
+    }
+}
+
+fn is_special(name: &str) -> bool {
+    name.chars().all(|char| char.is_ascii_digit()) && !name.is_empty()
+        || matches!(name, "@" | "*" | "#" | "?" | "$" | "!")
+}
+
+/// Read up to the parenthesis which closes `depth` open ones, skipping quoted parentheses.
+fn until_closing(chars: &mut std::iter::Peekable<std::str::Chars>, mut depth: usize) -> String {
+    let mut text = String::new();
+    let mut quote = None;
+    for char in chars.by_ref() {
+        match (quote, char) {
+            (None, '\'' | '"') => quote = Some(char),
+            (Some(open), char) if open == char => quote = None,
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        text.push(char);
+    }
+    text
+}
+
+/// A statement, from one `;` or newline to the next.
+struct Statement {
+    tokens: Vec<Token>,
+    lines: (usize, usize),
+}
+
+fn statements(tokens: Vec<(Token, usize)>) -> Vec<Statement> {
+    let mut statements = vec![];
+    let mut current = vec![];
+    let mut lines = (0, 0);
+    for (token, line) in tokens {
+        if current.is_empty() {
+            lines.0 = line;
+        }
+        match token {
+            Token::Operator(";" | "\n" | ";;") => {
+                if !current.is_empty() {
+                    statements.push(Statement {
+                        tokens: std::mem::take(&mut current),
+                        lines,
+                    });
+                }
+            }
+            Token::Comment(_) => {
+                if !current.is_empty() {
+                    statements.push(Statement {
+                        tokens: std::mem::take(&mut current),
+                        lines,
+                    });
+                }
+                statements.push(Statement {
+                    tokens: vec![token],
+                    lines: (line, line),
+                });
+            }
+            token => {
+                current.push(token);
+                lines.1 = line;
+            }
+        }
+    }
+    if !current.is_empty() {
+        statements.push(Statement {
+            tokens: current,
+            lines,
+        });
+    }
+    statements
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Block {
+    If,
+    Loop,
+    Function,
+    /// `{ ...; }`, which is translated to its statements
+    Group,
+    /// `( ... )`, translated to `do { ... }` which keeps changes of the environment inside
+    Subshell,
+}
+
+/// Whether a word is a value of an expression, or an argument of a command where it can be bare.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Value,
+    Argument,
+}
+
+type Translated = Result<String, String>;
+
+/// Translate a POSIX shell script into nushell. `is_command` tells if nushell has a command with
+/// a name, which is then called with `^` to run the external command like the script does.
+pub(crate) fn translate(script: &str, is_command: &dyn Fn(&str) -> bool) -> String {
+    let statements = statements(lex(script));
+    let mut translator = Translator {
+        source: script.lines().collect(),
+        is_command,
+        output: vec![],
+        depth: 0,
+        blocks: vec![],
+        assignments: HashMap::new(),
+        exported: HashSet::new(),
+        functions: HashSet::new(),
+        declared: HashSet::new(),
+        uses_args: false,
+        case: None,
+        commented_until: None,
+    };
+    translator.scan(&statements);
+    for statement in &statements {
+        translator.statement(statement);
+    }
+    while let Some(block) = translator.blocks.pop() {
+        translator.depth = translator.depth.saturating_sub(1);
+        if block != Block::Group {
+            translator.emit("} # TODO: the script doesn't close this block".into());
+        }
+    }
+
+    if let Some((start, _)) = translator.case {
+        let end = translator.source.len().saturating_sub(1);
+        translator.todo("case without esac", (start, end));
+    }
+
+    let mut output = translator.output;
+    if translator.uses_args {
+        output = std::iter::once("def main [...args] {".to_string())
+            .chain(output.into_iter().map(|line| {
+                if line.is_empty() {
+                    line
+                } else {
+                    format!("    {line}")
+                }
+            }))
+            .chain(std::iter::once("}".to_string()))
+            .collect();
+    }
+    output.join("\n")
+}
+
+struct Translator<'a> {
+    source: Vec<&'a str>,
+    is_command: &'a dyn Fn(&str) -> bool,
+    output: Vec<String>,
+    depth: usize,
+    blocks: Vec<Block>,
+    /// How many times each shell variable is assigned, to choose between `let` and `mut`
+    assignments: HashMap<String, usize>,
+    /// The variables which are exported somewhere, which are then environment variables
+    exported: HashSet<String>,
+    functions: HashSet<String>,
+    declared: HashSet<String>,
+    /// Whether the script uses its arguments outside of functions
+    uses_args: bool,
+    /// The first line and the nesting of a `case` statement, which is kept as a comment
+    case: Option<(usize, usize)>,
+    /// The last line of the script which is in a TODO comment
+    commented_until: Option<usize>,
+}
+
+impl Translator<'_> {
+    /// Find the assignments, exports and functions of the script ahead of translating it.
+    fn scan(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            let words: Vec<&Word> = statement
+                .tokens
+                .iter()
+                .filter_map(|token| match token {
+                    Token::Word(word) => Some(word),
+                    _ => None,
+                })
+                .collect();
+            match words.first().and_then(|word| word.text()).as_deref() {
+                Some("export") => {
+                    for word in &words[1..] {
+                        let name = match word.assignment() {
+                            Some((name, _)) => name,
+                            None => word.text().unwrap_or_default(),
+                        };
+                        self.exported.insert(name);
+                    }
+                }
+                Some("function") => {
+                    if let Some(name) = words.get(1).and_then(|word| word.text()) {
+                        self.functions.insert(name);
+                    }
+                }
+                Some("for") => {
+                    if let Some(name) = words.get(1).and_then(|word| word.text()) {
+                        *self.assignments.entry(name).or_default() += 2;
+                    }
+                }
+                Some("read") => {
+                    for name in words[1..].iter().filter_map(|word| word.text()) {
+                        if is_name(&name) {
+                            *self.assignments.entry(name).or_default() += 1;
+                        }
+                    }
+                }
+                Some("local" | "readonly") => {
+                    for (name, _) in words[1..].iter().filter_map(|word| word.assignment()) {
+                        *self.assignments.entry(name).or_default() += 1;
+                    }
+                }
+                _ => {
+                    for (name, _) in words.iter().map_while(|word| word.assignment()) {
+                        *self.assignments.entry(name).or_default() += 1;
+                    }
+                }
+            }
+            if let [
+                Token::Word(name),
+                Token::Operator("("),
+                Token::Operator(")"),
+                ..,
+            ] = statement.tokens.as_slice()
+            {
+                self.functions.extend(name.text());
+            }
+        }
+    }
+
+    fn emit(&mut self, line: String) {
+        if line.is_empty() {
+            self.output.push(line);
+        } else {
+            self.output
+                .push(format!("{}{line}", "    ".repeat(self.depth)));
+        }
+    }
+
+    /// Keep the lines of a statement as a comment after the reason it isn't translated.
+    fn todo(&mut self, reason: &str, lines: (usize, usize)) {
+        self.emit(format!("# TODO: {reason}"));
+        let start = match self.commented_until {
+            Some(until) if until >= lines.0 => until + 1,
+            _ => lines.0,
+        };
+        for line in start..=lines.1 {
+            if let Some(text) = self.source.get(line) {
+                self.emit(format!("# {}", text.trim()));
+            }
+        }
+        self.commented_until = Some(lines.1.max(self.commented_until.unwrap_or_default()));
+    }
+
+    fn open(&mut self, block: Block, header: Option<String>) {
+        if let Some(header) = header {
+            self.emit(header);
+            self.depth += 1;
+        }
+        self.blocks.push(block);
+    }
+
+    fn close(&mut self, expected: &[Block], lines: (usize, usize)) {
+        match self.blocks.last() {
+            Some(block) if expected.contains(block) => {
+                let block = self.blocks.pop().expect("checked above");
+                if block != Block::Group {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.emit("}".into());
+                }
+            }
+            _ => self.todo("this closes a block which isn't open", lines),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        let tokens = statement.tokens.as_slice();
+        let lines = statement.lines;
+
+        if let Some((start, nesting)) = self.case {
+            let nesting = if tokens.first().is_some_and(|token| token.is_word("case")) {
+                nesting + 1
+            } else if tokens.first().is_some_and(|token| token.is_word("esac")) {
+                nesting - 1
+            } else {
+                nesting
+            };
+            if nesting == 0 {
+                self.case = None;
+                self.todo("case has no direct equivalent, use match", (start, lines.1));
+            } else {
+                self.case = Some((start, nesting));
+            }
+            return;
+        }
+
+        let first = match tokens.first() {
+            Some(Token::Comment(comment)) => {
+                if lines.0 != 0 || !comment.starts_with('!') {
+                    self.emit(format!("#{comment}"));
+                }
+                return;
+            }
+            Some(Token::Word(word)) => word.text(),
+            Some(Token::Operator(_)) => None,
+            None => return,
+        };
+
+        match (first.as_deref(), tokens) {
+            (Some("then" | "do"), [_, rest @ ..]) => self.rest(rest, lines),
+            (Some("if"), [_, condition @ ..]) => {
+                let condition = self.condition_or_todo(condition, lines);
+                self.open(Block::If, Some(format!("if {condition} {{")));
+            }
+            (Some("elif"), [_, condition @ ..]) => {
+                let condition = self.condition_or_todo(condition, lines);
+                if self.blocks.last() == Some(&Block::If) {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.emit(format!("}} else if {condition} {{"));
+                    self.depth += 1;
+                } else {
+                    self.todo("elif without if", lines);
+                }
+            }
+            (Some("else"), [_, rest @ ..]) => {
+                if self.blocks.last() == Some(&Block::If) {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.emit("} else {".into());
+                    self.depth += 1;
+                    self.rest(rest, lines);
+                } else {
+                    self.todo("else without if", lines);
+                }
+            }
+            (Some("fi"), [_, rest @ ..]) => {
+                self.close(&[Block::If], lines);
+                self.trailing(rest, lines);
+            }
+            (Some("done"), [_, rest @ ..]) => {
+                self.close(&[Block::Loop], lines);
+                self.trailing(rest, lines);
+            }
+            (Some("for"), [_, rest @ ..]) => {
+                let header = self.for_header(rest).unwrap_or_else(|reason| {
+                    self.todo(&reason, lines);
+                    "for _ in [] {".into()
+                });
+                self.open(Block::Loop, Some(header));
+                if let Some(position) = rest.iter().position(|token| token.is_word("do")) {
+                    self.rest(&rest[position + 1..], lines);
+                }
+            }
+            (Some(keyword @ ("while" | "until")), [_, condition @ ..]) => {
+                let condition = self.condition_or_todo(condition, lines);
+                let header = if keyword == "while" {
+                    format!("while {condition} {{")
+                } else {
+                    format!("while not {condition} {{")
+                };
+                self.open(Block::Loop, Some(header));
+            }
+            (Some("case"), _) => self.case = Some((lines.0, 1)),
+            (Some("function"), [_, Token::Word(name), rest @ ..]) => {
+                let rest = match rest {
+                    [Token::Operator("("), Token::Operator(")"), rest @ ..] => rest,
+                    rest => rest,
+                };
+                self.function(name, rest, lines);
+            }
+            (
+                _,
+                [
+                    Token::Word(name),
+                    Token::Operator("("),
+                    Token::Operator(")"),
+                    rest @ ..,
+                ],
+            ) => self.function(name, rest, lines),
+            (Some("{"), [_, rest @ ..]) => {
+                self.open(Block::Group, None);
+                self.rest(rest, lines);
+            }
+            (Some("}"), [_, rest @ ..]) => {
+                self.close(&[Block::Function, Block::Group], lines);
+                self.trailing(rest, lines);
+            }
+            (None, [Token::Operator("("), rest @ ..]) => {
+                self.open(Block::Subshell, Some("do {".into()));
+                self.rest(rest, lines);
+            }
+            (None, [Token::Operator(")"), rest @ ..]) => {
+                self.close(&[Block::Subshell], lines);
+                self.trailing(rest, lines);
+            }
+            _ => self.simple(tokens, lines),
+        }
+    }
+
+    /// Translate what follows a keyword like `then` or `do` on the same statement.
+    fn rest(&mut self, tokens: &[Token], lines: (usize, usize)) {
+        if !tokens.is_empty() {
+            self.statement(&Statement {
+                tokens: tokens.to_vec(),
+                lines,
+            });
+        }
+    }
+
+    /// Redirections or pipes after the end of a block aren't translated.
+    fn trailing(&mut self, tokens: &[Token], lines: (usize, usize)) {
+        if !tokens.is_empty() {
+            self.todo("the output of the block is redirected", lines);
+        }
+    }
+
+    /// Translate a statement which isn't a compound command, closing a subshell or brace group
+    /// which ends with it.
+    fn simple(&mut self, tokens: &[Token], lines: (usize, usize)) {
+        let (tokens, closes) = match tokens {
+            [rest @ .., Token::Operator(")")] if self.blocks.contains(&Block::Subshell) => {
+                (rest, Some(Block::Subshell))
+            }
+            [rest @ .., last] if last.is_word("}") && !rest.is_empty() => {
+                (rest, Some(Block::Function))
+            }
+            tokens => (tokens, None),
+        };
+        let (tokens, background) = match tokens {
+            [rest @ .., Token::Operator("&")] => (rest, true),
+            tokens => (tokens, false),
+        };
+        match self.and_or(tokens) {
+            Ok(line) if background => self.emit(format!("job spawn {{ {line} }}")),
+            Ok(line) => {
+                for line in line.lines() {
+                    self.emit(line.into());
+                }
+            }
+            Err(reason) => self.todo(&reason, lines),
+        }
+        match closes {
+            Some(Block::Subshell) => self.close(&[Block::Subshell], lines),
+            Some(_) => self.close(&[Block::Function, Block::Group], lines),
+            None => {}
+        }
+    }
+
+    fn function(&mut self, name: &Word, rest: &[Token], lines: (usize, usize)) {
+        let Some(name) = name.text() else {
+            return self.todo("the name of the function isn't literal", lines);
+        };
+        self.functions.insert(name.clone());
+        self.open(Block::Function, Some(format!("def {name} [...args] {{")));
+        match rest {
+            [open, rest @ ..] if open.is_word("{") => self.rest(rest, lines),
+            _ => {}
+        }
+    }
+
+    fn for_header(&mut self, tokens: &[Token]) -> Translated {
+        let words = words(
+            tokens
+                .split(|token| token.is_word("do"))
+                .next()
+                .unwrap_or_default(),
+        )?;
+        let (name, items) = match words.as_slice() {
+            [name] => (name, None),
+            [name, keyword, items @ ..] if keyword.is("in") => (name, Some(items)),
+            _ => return Err("the for loop isn't `for NAME in WORDS`".into()),
+        };
+        let name = name
+            .text()
+            .filter(|name| is_name(name))
+            .ok_or("the name of the loop variable isn't literal")?;
+        self.declared.insert(name.clone());
+
+        let items = match items {
+            None => self.arguments(),
+            Some([word]) if word.has_glob() => {
+                format!("(glob {})", self.word(word, Mode::Argument)?)
+            }
+            Some([word]) if matches!(word.0.as_slice(), [Part::Command(_)]) => {
+                let command = self.word(word, Mode::Value)?;
+                format!("{} | lines)", command.strip_suffix(')').unwrap_or(&command))
+            }
+            Some([word]) if matches!(word.0.as_slice(), [Part::Variable(name)] if !is_special(name)) =>
+            {
+                format!("({} | split row ' ')", self.word(word, Mode::Value)?)
+            }
+            Some(items) => {
+                let items = items
+                    .iter()
+                    .map(|word| match word.parts().as_slice() {
+                        [Part::Variable(name)] if matches!(name.as_str(), "@" | "*") => {
+                            Ok(format!("...{}", self.arguments()))
+                        }
+                        _ if word.has_glob() => {
+                            Ok(format!("...(glob {})", self.word(word, Mode::Argument)?))
+                        }
+                        _ => self.word(word, Mode::Value),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("[{}]", items.join(" "))
+            }
+        };
+        Ok(format!("for {name} in {items} {{"))
+    }
+
+    fn condition_or_todo(&mut self, tokens: &[Token], lines: (usize, usize)) -> String {
+        self.condition(tokens).unwrap_or_else(|reason| {
+            self.todo(&format!("{reason}, so the condition is false"), lines);
+            "false".into()
+        })
+    }
+
+    /// Translate the condition of an `if` or `while`, which is a command that succeeds or fails.
+    fn condition(&mut self, tokens: &[Token]) -> Translated {
+        match tokens {
+            [negate, rest @ ..] if negate.is_word("!") => {
+                Ok(format!("not {}", self.condition(rest)?))
+            }
+            _ => {
+                let words = words(tokens).ok();
+                match words.as_deref() {
+                    Some([first, test @ .., last]) if first.is("[") && last.is("]") => {
+                        Ok(format!("({})", self.test(test)?))
+                    }
+                    Some([first, test @ .., last]) if first.is("[[") && last.is("]]") => {
+                        Ok(format!("({})", self.test(test)?))
+                    }
+                    Some([first, test @ ..]) if first.is("test") => {
+                        Ok(format!("({})", self.test(test)?))
+                    }
+                    Some([word]) if word.is("true") || word.is(":") => Ok("true".into()),
+                    Some([word]) if word.is("false") => Ok("false".into()),
+                    _ => {
+                        let command = self.and_or(tokens)?;
+                        Ok(format!("((do {{ {command} }} | complete).exit_code == 0)"))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Translate the expression of `test`, `[` or `[[`.
+    fn test(&mut self, words: &[Word]) -> Translated {
+        let position = |operators: &[&str]| {
+            words
+                .iter()
+                .position(|word| operators.iter().any(|operator| word.is(operator)))
+        };
+        if let Some(or) = position(&["-o", "||"]) {
+            return Ok(format!(
+                "({}) or ({})",
+                self.test(&words[..or])?,
+                self.test(&words[or + 1..])?
+            ));
+        }
+        if let Some(and) = position(&["-a", "&&"]) {
+            // `-a` is also the old spelling of `-e`
+            if and > 0 {
+                return Ok(format!(
+                    "({}) and ({})",
+                    self.test(&words[..and])?,
+                    self.test(&words[and + 1..])?
+                ));
+            }
+        }
+        match words {
+            [negate, rest @ ..] if negate.is("!") => Ok(format!("not ({})", self.test(rest)?)),
+            [operator, operand] => {
+                let operand = self.word(operand, Mode::Value)?;
+                match operator.text().as_deref() {
+                    Some("-e" | "-a") => Ok(format!("{operand} | path exists")),
+                    Some("-f") => Ok(format!("({operand} | path type) == file")),
+                    Some("-d") => Ok(format!("({operand} | path type) == dir")),
+                    Some("-L" | "-h") => Ok(format!("({operand} | path type) == symlink")),
+                    Some("-z") => Ok(format!("{operand} | is-empty")),
+                    Some("-n") => Ok(format!("{operand} | is-not-empty")),
+                    Some(operator) => Err(format!("test {operator} has no direct equivalent")),
+                    None => Err("the test operator isn't literal".into()),
+                }
+            }
+            [left, operator, right] => {
+                let Some(operator) = operator.text() else {
+                    return Err("the test operator isn't literal".into());
+                };
+                let compare = match operator.as_str() {
+                    "=" | "==" if right.has_glob() => {
+                        return Err("comparing with a glob has no direct equivalent".into());
+                    }
+                    "=" | "==" => "==",
+                    "!=" => "!=",
+                    "=~" => "=~",
+                    "-eq" => "==",
+                    "-ne" => "!=",
+                    "-lt" => "<",
+                    "-le" => "<=",
+                    "-gt" => ">",
+                    "-ge" => ">=",
+                    operator => return Err(format!("test {operator} has no direct equivalent")),
+                };
+                if operator.starts_with('-') {
+                    Ok(format!(
+                        "{} {compare} {}",
+                        self.integer(left)?,
+                        self.integer(right)?
+                    ))
+                } else {
+                    Ok(format!(
+                        "{} {compare} {}",
+                        self.word(left, Mode::Value)?,
+                        self.word(right, Mode::Value)?
+                    ))
+                }
+            }
+            [operand] => Ok(format!(
+                "{} | is-not-empty",
+                self.word(operand, Mode::Value)?
+            )),
+            _ => Err("the test expression is too complex".into()),
+        }
+    }
+
+    fn integer(&mut self, word: &Word) -> Translated {
+        match word.text() {
+            Some(text) if text.parse::<i64>().is_ok() => Ok(text),
+            _ => Ok(format!("({} | into int)", self.word(word, Mode::Value)?)),
+        }
+    }
+
+    /// Translate commands joined by `&&` and `||`.
+    fn and_or(&mut self, tokens: &[Token]) -> Translated {
+        let mut pipelines = vec![];
+        let mut operators = vec![];
+        let mut start = 0;
+        for (index, token) in tokens.iter().enumerate() {
+            if let Token::Operator(operator @ ("&&" | "||")) = token {
+                pipelines.push(&tokens[start..index]);
+                operators.push(*operator);
+                start = index + 1;
+            }
+        }
+        pipelines.push(&tokens[start..]);
+
+        let mut translated = self.pipeline(pipelines[0]);
+        for (operator, pipeline) in operators.into_iter().zip(&pipelines[1..]) {
+            let left = pipelines[0];
+            let right = self.pipeline(pipeline)?;
+            // `[ -f file ] && source file` is an if statement
+            translated = match (operator, self.is_test(left).then(|| self.condition(left))) {
+                ("&&", Some(condition)) if pipelines.len() == 2 => {
+                    Ok(format!("if {} {{ {right} }}", condition?))
+                }
+                ("||", Some(condition)) if pipelines.len() == 2 => {
+                    Ok(format!("if not {} {{ {right} }}", condition?))
+                }
+                // A failing command is an error, which stops the script like with `set -e`
+                ("&&", _) => Ok(format!("{}\n{right}", translated?)),
+                _ => Ok(format!("try {{ {} }} catch {{ {right} }}", translated?)),
+            };
+        }
+        translated
+    }
+
+    fn is_test(&self, tokens: &[Token]) -> bool {
+        tokens
+            .first()
+            .is_some_and(|token| token.is_word("[") || token.is_word("[[") || token.is_word("test"))
+    }
+
+    /// Translate commands joined by pipes.
+    fn pipeline(&mut self, tokens: &[Token]) -> Translated {
+        if tokens.is_empty() {
+            return Err("an empty command".into());
+        }
+        if self.is_test(tokens) {
+            return Err("a test outside of a condition".into());
+        }
+        let commands: Vec<&[Token]> = tokens
+            .split(|token| *token == Token::Operator("|"))
+            .collect();
+        let mut translated = String::new();
+        for (index, command) in commands.iter().enumerate() {
+            let (command, pipe) = self.command(command)?;
+            translated.push_str(&command);
+            if index + 1 < commands.len() {
+                translated.push_str(pipe);
+            }
+        }
+        Ok(translated)
+    }
+
+    /// Translate a command and its redirections. The pipe to the next command is `o+e>|` when
+    /// stderr is redirected to stdout.
+    fn command(&mut self, tokens: &[Token]) -> Result<(String, &'static str), String> {
+        let mut words = vec![];
+        let mut redirections = vec![];
+        let mut stderr_to_stdout = false;
+        let mut stdout_to_stderr = false;
+        let mut tokens = tokens.iter();
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Word(word) => words.push(word.clone()),
+                Token::Operator("2>&1") => stderr_to_stdout = true,
+                Token::Operator(">&2") => stdout_to_stderr = true,
+                Token::Operator(
+                    operator @ (">" | ">>" | "2>" | "2>>" | "&>" | "<" | "<<" | "<<-"),
+                ) => match tokens.next() {
+                    Some(Token::Word(target)) => redirections.push((*operator, target.clone())),
+                    _ => return Err(format!("{operator} isn't followed by a file")),
+                },
+                Token::Operator(operator) => {
+                    return Err(format!("`{operator}` has no direct equivalent"));
+                }
+                Token::Comment(_) => {}
+            }
+        }
+
+        let assignments = words
+            .iter()
+            .map_while(|word| word.assignment())
+            .collect::<Vec<_>>();
+        let words = &words[assignments.len()..];
+        if words.is_empty() {
+            if !redirections.is_empty() {
+                return Err("a redirection without a command".into());
+            }
+            let lines = assignments
+                .into_iter()
+                .map(|(name, value)| self.assign(&name, &value))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok((lines.join("\n"), " | "));
+        }
+
+        let mut command = self.call(words, stdout_to_stderr)?;
+        if !assignments.is_empty() {
+            let env = assignments
+                .iter()
+                .map(|(name, value)| Ok(format!("{name}: {}", self.word(value, Mode::Value)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            command = format!("with-env {{{}}} {{ {command} }}", env.join(", "));
+        }
+        if stdout_to_stderr && !words[0].is("echo") {
+            return Err("redirecting stdout to stderr has no direct equivalent".into());
+        }
+
+        let mut stdout = None;
+        let mut stderr = None;
+        for (operator, target) in &redirections {
+            match *operator {
+                "<" => {
+                    let file = self.word(target, Mode::Argument)?;
+                    command = format!("open --raw {file} | {command}");
+                }
+                "<<" | "<<-" => {
+                    let text = self.word(target, Mode::Value)?;
+                    command = format!("{text} | {command}");
+                }
+                ">" | ">>" | "&>" => stdout = Some((*operator, target)),
+                _ => stderr = Some((*operator, target)),
+            }
+        }
+        let null = |target: &Word| target.is("/dev/null");
+        let both = stderr_to_stdout || stdout.is_some_and(|(operator, _)| operator == "&>");
+        match (stdout, stderr) {
+            (Some((_, target)), _) if both && null(target) => command.push_str(" o+e>| ignore"),
+            (Some((operator, target)), _) if both => {
+                let append = if operator == ">>" { ">>" } else { ">" };
+                command.push_str(&format!(
+                    " o+e{append} {}",
+                    self.word(target, Mode::Argument)?
+                ));
+            }
+            (stdout, stderr) => {
+                if let Some((operator, target)) = stderr {
+                    let append = if operator == "2>>" { ">>" } else { ">" };
+                    command.push_str(&format!(
+                        " e{append} {}",
+                        self.word(target, Mode::Argument)?
+                    ));
+                }
+                match stdout {
+                    Some((_, target)) if null(target) => command.push_str(" | ignore"),
+                    Some((operator, target)) => {
+                        let append = if operator == ">>" { ">>" } else { ">" };
+                        command.push_str(&format!(
+                            " o{append} {}",
+                            self.word(target, Mode::Argument)?
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let pipe = if stderr_to_stdout { " o+e>| " } else { " | " };
+        Ok((command, pipe))
+    }
+
+    /// Translate a command with its arguments.
+    fn call(&mut self, words: &[Word], to_stderr: bool) -> Translated {
+        let Some(name) = words[0].text() else {
+            return Err("the name of the command isn't literal".into());
+        };
+        let args = &words[1..];
+        match name.as_str() {
+            "export" | "local" | "readonly" | "declare" | "typeset" => {
+                let mut lines = vec![];
+                for arg in args {
+                    if let Some((name, value)) = arg.assignment() {
+                        lines.push(self.assign(&name, &value)?);
+                    } else if arg.text().is_some_and(|text| text.starts_with('-')) {
+                        return Err(format!("{name} with options has no direct equivalent"));
+                    }
+                    // `export NAME` alone needs nothing, as every assignment of NAME already sets
+                    // the environment variable
+                }
+                Ok(lines.join("\n"))
+            }
+            "unset" => {
+                let names = args
+                    .iter()
+                    .filter_map(|arg| arg.text())
+                    .filter(|arg| !arg.starts_with('-'))
+                    .collect::<Vec<_>>();
+                if names.iter().any(|name| self.is_local(name)) {
+                    return Err("shell variables can't be unset".into());
+                }
+                Ok(names
+                    .iter()
+                    .map(|name| format!("hide-env -i {name}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            "echo" => {
+                let (flags, args) = match args {
+                    [flag, args @ ..] if flag.is("-n") => (" -n", args),
+                    [flag, ..] if flag.is("-e") => {
+                        return Err("echo -e has no direct equivalent".into());
+                    }
+                    args => ("", args),
+                };
+                let stderr = if to_stderr { " -e" } else { "" };
+                let mut text = Word::default();
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        text.push(' ', true);
+                    }
+                    text.0.extend(arg.0.iter().cloned().map(|part| match part {
+                        // The words are joined, so a glob would be literal
+                        Part::Text(text, _) => Part::Text(text, true),
+                        part => part,
+                    }));
+                }
+                if text.0.is_empty() {
+                    text.0.push(Part::Text(String::new(), true));
+                }
+                Ok(format!(
+                    "print{flags}{stderr} {}",
+                    self.word(&text, Mode::Value)?
+                ))
+            }
+            "cd" | "exit" | "return" => {
+                let mut call = name.clone();
+                for arg in args {
+                    call.push(' ');
+                    call.push_str(&self.word(arg, Mode::Argument)?);
+                }
+                Ok(call)
+            }
+            "source" | "." => match args {
+                [file] => Ok(format!(
+                    "open --raw {} | env import | load-env # TODO: only the variables it exports are loaded",
+                    self.word(file, Mode::Argument)?
+                )),
+                _ => Err("source with arguments has no direct equivalent".into()),
+            },
+            "alias" => {
+                let mut lines = vec![];
+                for arg in args {
+                    let (name, expansion) = arg
+                        .assignment()
+                        .and_then(|(name, expansion)| Some((name, expansion.text()?)))
+                        .ok_or("the alias isn't literal")?;
+                    let statements = statements(lex(&expansion));
+                    let [statement] = statements.as_slice() else {
+                        return Err("the alias runs several commands".into());
+                    };
+                    lines.push(format!(
+                        "alias {name} = {}",
+                        self.and_or(&statement.tokens)?
+                    ));
+                }
+                Ok(lines.join("\n"))
+            }
+            "set"
+                if args.iter().all(|arg| {
+                    arg.text()
+                        .is_some_and(|arg| arg.starts_with(['-', '+']) || arg == "pipefail")
+                }) =>
+            {
+                Ok("# nushell stops at the first error, like `set -e`".into())
+            }
+            ":" => Ok(String::new()),
+            "read" => {
+                let mut prompt = None;
+                let mut names = vec![];
+                let mut args = args.iter();
+                while let Some(arg) = args.next() {
+                    match arg.text().as_deref() {
+                        Some("-p") => prompt = args.next(),
+                        Some("-r") => {}
+                        Some(name) if is_name(name) => names.push(name.to_string()),
+                        _ => return Err("read with options has no direct equivalent".into()),
+                    }
+                }
+                let input = match prompt {
+                    Some(prompt) => format!("(input {})", self.word(prompt, Mode::Value)?),
+                    None => "(input)".into(),
+                };
+                match names.as_slice() {
+                    [name] => self.assign_expression(name, input),
+                    _ => Err("read into several variables has no direct equivalent".into()),
+                }
+            }
+            "command" => match args {
+                [flag, name] if flag.is("-v") => {
+                    Ok(format!("which {}", self.word(name, Mode::Argument)?))
+                }
+                [name, ..] if !name.text().unwrap_or_default().starts_with('-') => {
+                    self.call(args, to_stderr)
+                }
+                _ => Err("command with options has no direct equivalent".into()),
+            },
+            "printf" | "eval" | "exec" | "trap" | "shift" | "getopts" | "wait" | "let" | "set"
+            | "select" | "builtin" | "[[" => Err(format!("{name} has no direct equivalent")),
+            _ => {
+                let mut call = if !self.functions.contains(&name) && (self.is_command)(&name) {
+                    format!("^{name}")
+                } else {
+                    name.clone()
+                };
+                for arg in args {
+                    call.push(' ');
+                    match arg.parts().as_slice() {
+                        [Part::Variable(name)] if matches!(name.as_str(), "@" | "*") => {
+                            call.push_str(&format!("...{}", self.arguments()));
+                        }
+                        _ => call.push_str(&self.word(arg, Mode::Argument)?),
+                    }
+                }
+                Ok(call)
+            }
+        }
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        !self.exported.contains(name)
+            && (self.declared.contains(name) || self.assignments.contains_key(name))
+    }
+
+    fn assign(&mut self, name: &str, value: &Word) -> Translated {
+        if !self.is_local(name) {
+            return self.assign_env(name, value);
+        }
+        let value = self.word(value, Mode::Value)?;
+        self.assign_expression(name, value)
+    }
+
+    fn assign_expression(&mut self, name: &str, value: String) -> Translated {
+        if self.exported.contains(name) {
+            return Ok(format!("$env.{name} = {value}"));
+        }
+        if self.declared.insert(name.into()) {
+            let keyword = if self.assignments.get(name).copied().unwrap_or_default() > 1 {
+                "mut"
+            } else {
+                "let"
+            };
+            Ok(format!("{keyword} {name} = {value}"))
+        } else {
+            Ok(format!("${name} = {value}"))
+        }
+    }
+
+    /// Set an environment variable, adding to PATH like `PATH=$PATH:dir` as a list.
+    fn assign_env(&mut self, name: &str, value: &Word) -> Translated {
+        let parts: Vec<Part> = value.parts().into_iter().cloned().collect();
+        let parts = parts.as_slice();
+        let is_self = |part: &Part| matches!(part, Part::Variable(variable) if variable == name);
+        if name.ends_with("PATH") {
+            match parts {
+                [first, Part::Text(text, _), rest @ ..]
+                    if is_self(first) && text.starts_with(':') =>
+                {
+                    let mut added = vec![Part::Text(text[1..].into(), true)];
+                    added.extend(rest.iter().cloned());
+                    let added = self.word(&Word(added), Mode::Value)?;
+                    return Ok(format!("$env.{name} = ($env.{name} | append {added})"));
+                }
+                [rest @ .., Part::Text(text, _), last] if is_self(last) && text.ends_with(':') => {
+                    let mut added = rest.to_vec();
+                    added.push(Part::Text(text[..text.len() - 1].into(), true));
+                    let added = self.word(&Word(added), Mode::Value)?;
+                    return Ok(format!("$env.{name} = ($env.{name} | prepend {added})"));
+                }
+                _ => {}
+            }
+        }
+        Ok(format!("$env.{name} = {}", self.word(value, Mode::Value)?))
+    }
+
+    /// The arguments of the script or of the current function.
+    fn arguments(&mut self) -> String {
+        if !self.blocks.contains(&Block::Function) {
+            self.uses_args = true;
+        }
+        "$args".into()
+    }
+
+    fn variable(&mut self, name: &str) -> Translated {
+        match name {
+            "0" => Ok("$env.CURRENT_FILE".into()),
+            "@" | "*" => Ok(format!("({} | str join ' ')", self.arguments())),
+            "#" => Ok(format!("({} | length)", self.arguments())),
+            "?" => Ok("$env.LAST_EXIT_CODE".into()),
+            "$" => Ok("$nu.pid".into()),
+            "!" => Err("$! has no direct equivalent, use job spawn".into()),
+            _ if name.chars().all(|char| char.is_ascii_digit()) => {
+                let index = name.parse::<usize>().map_err(|err| err.to_string())? - 1;
+                Ok(format!("{}.{index}?", self.arguments()))
+            }
+            name if self.is_local(name) => Ok(format!("${name}")),
+            name => Ok(format!("$env.{name}")),
+        }
+    }
+
+    /// Translate a command in `$(...)`.
+    fn substitution(&mut self, command: &str) -> Translated {
+        let statements = statements(lex(command));
+        match statements.as_slice() {
+            [statement] => Ok(format!("({})", self.and_or(&statement.tokens)?)),
+            _ => Err("the command substitution runs several commands".into()),
+        }
+    }
+
+    fn arithmetic(&mut self, expression: &str) -> Translated {
+        let mut translated = String::new();
+        let mut chars = expression.chars().peekable();
+        while let Some(char) = chars.next() {
+            match char {
+                char if char.is_ascii_alphanumeric() || char == '_' || char == '$' => {
+                    let mut name: String = if char == '$' {
+                        String::new()
+                    } else {
+                        char.into()
+                    };
+                    while let Some(char) =
+                        chars.next_if(|char| char.is_ascii_alphanumeric() || *char == '_')
+                    {
+                        name.push(char);
+                    }
+                    if name.parse::<i64>().is_ok() {
+                        translated.push_str(&name);
+                    } else if is_name(&name) || is_special(&name) {
+                        translated.push_str(&format!("({} | into int)", self.variable(&name)?));
+                    } else {
+                        return Err("the arithmetic expression is too complex".into());
+                    }
+                }
+                '/' => translated.push_str("//"),
+                '%' => translated.push_str("mod"),
+                '*' if chars.next_if_eq(&'*').is_some() => translated.push_str("**"),
+                '+' | '-' | '*' | '(' | ')' | ' ' => translated.push(char),
+                _ => return Err("the arithmetic expression is too complex".into()),
+            }
+        }
+        Ok(format!("({})", translated.trim()))
+    }
+
+    /// Translate a word. Bare words are only kept as arguments, where they're strings.
+    fn word(&mut self, word: &Word, mode: Mode) -> Translated {
+        match word.parts().as_slice() {
+            [Part::Variable(name)] => self.variable(name),
+            [Part::Command(command)] => self.substitution(command),
+            [Part::Arithmetic(expression)] => self.arithmetic(expression),
+            _ => {
+                if let Some(text) = word.text() {
+                    let quoted = word
+                        .0
+                        .iter()
+                        .any(|part| matches!(part, Part::Text(_, true)));
+                    let bare = !quoted
+                        && ((mode == Mode::Argument && is_bare(&text))
+                            || text.parse::<i64>().is_ok());
+                    return Ok(if bare {
+                        text
+                    } else if !quoted && text.starts_with('~') {
+                        format!("({} | path expand)", quote(&text))
+                    } else {
+                        quote(&text)
+                    });
+                }
+                let mut interpolation = String::from("$\"");
+                for part in &word.0 {
+                    let expression = match part {
+                        Part::Text(text, _) => {
+                            for char in text.chars() {
+                                match char {
+                                    '\\' | '"' | '(' | ')' => {
+                                        interpolation.push('\\');
+                                        interpolation.push(char);
+                                    }
+                                    '\n' => interpolation.push_str("\\n"),
+                                    char => interpolation.push(char),
+                                }
+                            }
+                            continue;
+                        }
+                        Part::Variable(name) => self.variable(name)?,
+                        Part::Command(command) => self.substitution(command)?,
+                        Part::Arithmetic(expression) => self.arithmetic(expression)?,
+                        Part::Unsupported(expansion) => {
+                            return Err(format!("{expansion} has no direct equivalent"));
+                        }
+                    };
+                    if expression.starts_with('(') {
+                        interpolation.push_str(&expression);
+                    } else {
+                        interpolation.push_str(&format!("({expression})"));
+                    }
+                }
+                interpolation.push('"');
+                Ok(interpolation)
+            }
+        }
+    }
+}
+
+fn words(tokens: &[Token]) -> Result<Vec<Word>, String> {
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::Word(word) => Ok(word.clone()),
+            Token::Operator(operator) => Err(format!("`{operator}` has no direct equivalent")),
+            Token::Comment(_) => Err("a comment in a command".into()),
+        })
+        .collect()
+}
+
+/// Whether a word can be written as a bare word in nushell.
+fn is_bare(text: &str) -> bool {
+    // Like `5s` or `1kb`, which would be durations and file sizes
+    let unit = text.starts_with(|char: char| char.is_ascii_digit()) && text.parse::<i64>().is_err();
+    !text.is_empty()
+        && !unit
+        && text.chars().all(|char| {
+            char.is_ascii_alphanumeric()
+                || matches!(
+                    char,
+                    '_' | '.' | '/' | '~' | '-' | '+' | '=' | ':' | '@' | '%' | ',' | '*' | '?'
+                )
+        })
+}
+
+fn quote(text: &str) -> String {
+    if text.contains(['\'', '\n']) {
+        format!(
+            "\"{}\"",
+            text.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        )
+    } else {
+        format!("'{text}'")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(script: &str, expected: &str) {
+        let is_command = |name: &str| matches!(name, "ls" | "mkdir" | "sort");
+        assert_eq!(translate(script, &is_command), expected);
+    }
+
+    #[test]
+    fn translates_variables() {
+        check(
+            "#!/bin/sh\nNAME=world\nexport GREETING=\"hello $NAME\"\necho \"$GREETING from $HOME\"",
+            "let NAME = 'world'\n$env.GREETING = $\"hello ($NAME)\"\nprint $\"($env.GREETING) from ($env.HOME)\"",
+        );
+    }
+
+    #[test]
+    fn translates_path_changes() {
+        check(
+            "export PATH=\"$HOME/bin:$PATH\"\nexport PATH=$PATH:/opt/bin",
+            "$env.PATH = ($env.PATH | prepend $\"($env.HOME)/bin\")\n$env.PATH = ($env.PATH | append '/opt/bin')",
+        );
+    }
+
+    #[test]
+    fn translates_blocks() {
+        check(
+            "for f in *.txt; do\n  if [ -f \"$f\" ]; then\n    echo found $f\n  else\n    mkdir -p out\n  fi\ndone",
+            "for f in (glob *.txt) {\n    if (($f | path type) == file) {\n        print $\"found ($f)\"\n    } else {\n        ^mkdir -p out\n    }\n}",
+        );
+    }
+
+    #[test]
+    fn translates_functions_and_arguments() {
+        check(
+            "greet() {\n  echo \"hi $1\"\n}\ngreet \"$@\"",
+            "def main [...args] {\n    def greet [...args] {\n        print $\"hi ($args.0?)\"\n    }\n    greet ...$args\n}",
+        );
+    }
+
+    #[test]
+    fn translates_redirections_and_operators() {
+        check(
+            "ls -l > out.txt 2>&1\ncd /tmp && ls | sort\n[ -d build ] || mkdir build\ngrep -q x f 2>/dev/null",
+            "^ls -l o+e> out.txt\ncd /tmp\n^ls | ^sort\nif not (($build | path type) == dir) { ^mkdir build }\ngrep -q x f e> /dev/null"
+                .replace("$build", "'build'")
+                .as_str(),
+        );
+    }
+
+    #[test]
+    fn translates_substitutions() {
+        check(
+            "count=$(ls | wc -l)\necho $((count + 1))",
+            "let count = (^ls | wc -l)\nprint (($count | into int) + 1)",
+        );
+    }
+
+    #[test]
+    fn marks_untranslated_statements() {
+        check(
+            "case $1 in\n  a) echo a;;\nesac\ntrap 'rm -f x' EXIT",
+            "# TODO: case has no direct equivalent, use match\n# case $1 in\n# a) echo a;;\n# esac\n# TODO: trap has no direct equivalent\n# trap 'rm -f x' EXIT",
+        );
+    }
+
+    #[test]
+    fn reads_heredocs() {
+        check(
+            "cat <<EOF\nhello $NAME\nEOF\necho done",
+            "\"hello $NAME\\n\" | cat\nprint 'done'",
+        );
+    }
+}
@@ -27,6 +27,10 @@ impl Command for Tutor {
             .category(Category::Misc)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Run the tutorial. To begin, run: tutor."
     }
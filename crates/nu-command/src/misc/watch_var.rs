@@ -0,0 +1,149 @@
+use crate::env::watch_env;
+use nu_engine::command_prelude::*;
+use nu_protocol::{
+    ENV_VARIABLE_ID,
+    ast::{Expr, PathMember},
+    engine::VarWatch,
+};
+
+#[derive(Clone)]
+pub struct WatchVar;
+
+impl Command for WatchVar {
+    fn name(&self) -> &str {
+        "watch-var"
+    }
+
+    fn description(&self) -> &str {
+        "Run a closure whenever a mutable variable or an environment variable changes."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The closure receives the previous and the new value of the variable. For a mutable variable, it runs in the REPL before the next prompt is drawn, after the `env_change` hooks, so it sees every change made by the commands before.
+Watching `$env.NAME` is the same as `env watch NAME`, whose closure runs right after the variable changes. An environment variable which isn't set yet is watched with `$env.NAME?`. Only variables declared with `mut` can be watched otherwise, since the others never change."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["hook", "reactive", "observe", "change", "mut"]
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+            .required(
+                "variable",
+                SyntaxShape::Any,
+                "The mutable variable, or the `$env.NAME` variable to watch.",
+            )
+            .required(
+                "closure",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any, SyntaxShape::Any])),
+                "The closure to run with the old and the new value.",
+            )
+            .category(Category::Misc)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn requires_ast_for_arguments(&self) -> bool {
+        true
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Report the changes of a counter",
+                example: "mut count = 0; watch-var $count {|old, new| print $'count went from ($old) to ($new)' }",
+                result: None,
+            },
+            Example {
+                description: "Report directory changes",
+                example: "watch-var $env.PWD {|old, new| print $'left ($old) for ($new)' }",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let closure: Value = call.req(engine_state, stack, 1)?;
+        let Some(variable) = call.positional_nth(stack, 0) else {
+            return Err(ShellError::MissingParameter {
+                param_name: "variable".into(),
+                span: head,
+            });
+        };
+        let span = variable.span;
+
+        let (var_id, tail) = match &variable.expr {
+            Expr::Var(var_id) => (*var_id, &[][..]),
+            Expr::FullCellPath(path) => match &path.head.expr {
+                Expr::Var(var_id) => (*var_id, &path.tail[..]),
+                _ => return Err(not_a_variable(span)),
+            },
+            _ => return Err(not_a_variable(span)),
+        };
+
+        match tail {
+            [] if var_id == ENV_VARIABLE_ID => Err(ShellError::IncorrectValue {
+                msg: "watch one environment variable, like `$env.PATH`".into(),
+                val_span: span,
+                call_span: head,
+            }),
+            [PathMember::String { val, .. }] if var_id == ENV_VARIABLE_ID => {
+                watch_env(engine_state, stack, val.clone(), closure.into_closure()?);
+                Ok(PipelineData::empty())
+            }
+            [] if engine_state.get_var(var_id).mutable => {
+                let last = stack.get_var(var_id, span).ok();
+                engine_state
+                    .var_watches
+                    .lock()
+                    .expect("var watches lock is poisoned!")
+                    .push(VarWatch {
+                        var_id,
+                        closure,
+                        last,
+                    });
+                Ok(PipelineData::empty())
+            }
+            [] => Err(ShellError::IncorrectValue {
+                msg: "only variables declared with `mut` can change".into(),
+                val_span: span,
+                call_span: head,
+            }),
+            _ => Err(not_a_variable(span)),
+        }
+    }
+}
+
+fn not_a_variable(span: Span) -> ShellError {
+    ShellError::GenericError {
+        error: "Can't watch this expression".into(),
+        msg: "expected a variable like `$count` or `$env.NAME`".into(),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(WatchVar {})
+    }
+}
@@ -0,0 +1,44 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Cert;
+
+impl Command for Cert {
+    fn name(&self) -> &str {
+        "cert"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("cert")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Network)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with X.509 certificates."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The certificates in PEM files are also parsed with `from pem`.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["certificate", "x509", "tls", "ssl"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
@@ -0,0 +1,333 @@
+use crate::formats::{certificate_to_value, pem_to_certificates};
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+#[derive(Clone)]
+pub struct CertInspect;
+
+impl Command for CertInspect {
+    fn name(&self) -> &str {
+        "cert inspect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("cert inspect")
+            .input_output_types(vec![
+                (Type::Nothing, Type::table()),
+                (Type::String, Type::table()),
+                (Type::Binary, Type::table()),
+            ])
+            .allow_variants_without_examples(true)
+            .optional(
+                "address",
+                SyntaxShape::String,
+                "The host and port of a TLS server, like example.com:443.",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the server (defaults to 10sec)",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Get the certificates of a TLS server, or of PEM or DER input."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The certificates of a server are the chain it sends, starting with its own certificate. The
+certificates aren't verified, so that expired and self-signed certificates can be inspected too.
+The port defaults to 443.
+
+Without an address, the input is the certificates in PEM text, like with `from pem`, or a
+certificate in DER."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "certificate",
+            "x509",
+            "tls",
+            "ssl",
+            "https",
+            "expiry",
+            "openssl",
+        ]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let address: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: timeout.span });
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => Duration::from_secs(10),
+        };
+
+        let certificates = match address {
+            Some(address) => server_certificates(&address, timeout, head)?
+                .iter()
+                .map(|der| {
+                    certificate_to_value(der, head).unwrap_or_else(|msg| {
+                        Value::error(invalid_certificate(msg, address.span), head)
+                    })
+                })
+                .collect(),
+            None => {
+                let value = input.into_value(head)?;
+                let span = value.span();
+                match value {
+                    Value::String { val, .. } => pem_to_certificates(&val, span)?,
+                    Value::Binary { val, .. } => vec![
+                        certificate_to_value(&val, head)
+                            .map_err(|msg| invalid_certificate(msg, span))?,
+                    ],
+                    Value::Nothing { .. } => {
+                        return Err(ShellError::MissingParameter {
+                            param_name: "address".into(),
+                            span: head,
+                        });
+                    }
+                    other => {
+                        return Err(ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "string or binary".into(),
+                            wrong_type: other.get_type().to_string(),
+                            dst_span: head,
+                            src_span: span,
+                        });
+                    }
+                }
+            }
+        };
+        Ok(Value::list(certificates, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get the certificate chain of a web server",
+                example: "cert inspect nushell.sh",
+                result: None,
+            },
+            Example {
+                description: "Check which servers have certificates expiring within two weeks",
+                example: "[a.example.com b.example.com:8443] | each {|host| cert inspect $host | first | insert host $host } | where expires_in < 14day",
+                result: None,
+            },
+            Example {
+                description: "Inspect a certificate in DER",
+                example: "open --raw server.der | cert inspect",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn invalid_certificate(msg: String, span: Span) -> ShellError {
+    ShellError::CantConvert {
+        to_type: "certificate".into(),
+        from_type: "binary".into(),
+        span,
+        help: Some(msg),
+    }
+}
+
+/// The host and port of an address like `example.com`, `example.com:8443` or `[::1]:443`.
+fn host_and_port(address: &str) -> Option<(&str, u16)> {
+    if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        return match rest.strip_prefix(':') {
+            Some(port) => Some((host, port.parse().ok()?)),
+            None if rest.is_empty() => Some((host, 443)),
+            None => None,
+        };
+    }
+    match address.rsplit_once(':') {
+        // IPv6 addresses without brackets have no port
+        Some((host, _)) if host.contains(':') => Some((address, 443)),
+        Some((host, port)) => Some((host, port.parse().ok()?)),
+        None => Some((address, 443)),
+    }
+}
+
+/// The DER of the certificates a server sends in a TLS handshake.
+fn server_certificates(
+    address: &Spanned<String>,
+    timeout: Duration,
+    head: Span,
+) -> Result<Vec<Vec<u8>>, ShellError> {
+    let (host, port) = host_and_port(&address.item).ok_or_else(|| ShellError::IncorrectValue {
+        msg: "expected an address like example.com:443".into(),
+        val_span: address.span,
+        call_span: head,
+    })?;
+    let socket_address = (host, port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| ShellError::GenericError {
+            error: "Unknown host".into(),
+            msg: "the host name can't be resolved".into(),
+            span: Some(address.span),
+            help: None,
+            inner: vec![],
+        })?;
+    let io_error = |err: std::io::Error| ShellError::from(IoError::new(err, address.span, None));
+    let stream = TcpStream::connect_timeout(&socket_address, timeout).map_err(io_error)?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .map_err(io_error)?;
+
+    handshake(host, stream).map_err(|msg| ShellError::GenericError {
+        error: "TLS handshake failed".into(),
+        msg,
+        span: Some(address.span),
+        help: None,
+        inner: vec![],
+    })
+}
+
+#[cfg(feature = "rustls-tls")]
+fn handshake(host: &str, mut stream: TcpStream) -> Result<Vec<Vec<u8>>, String> {
+    use rustls::{ClientConfig, ClientConnection, pki_types::ServerName};
+    use std::sync::Arc;
+
+    let provider = super::tls::CRYPTO_PROVIDER
+        .get()
+        .map_err(|err| err.to_string())?;
+    let config = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AnyCertificate(provider)))
+        .with_no_client_auth();
+    let name = ServerName::try_from(host.to_string()).map_err(|err| err.to_string())?;
+    let mut connection =
+        ClientConnection::new(Arc::new(config), name).map_err(|err| err.to_string())?;
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut stream)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(connection
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .map(|certificate| certificate.to_vec())
+        .collect())
+}
+
+/// Accepts every certificate, while still checking that the server has the key of its certificate.
+#[cfg(feature = "rustls-tls")]
+#[derive(Debug)]
+struct AnyCertificate(std::sync::Arc<rustls::crypto::CryptoProvider>);
+
+#[cfg(feature = "rustls-tls")]
+impl rustls::client::danger::ServerCertVerifier for AnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// With native TLS only the certificate of the server is available, and not the rest of its chain.
+#[cfg(feature = "native-tls")]
+fn handshake(host: &str, stream: TcpStream) -> Result<Vec<Vec<u8>>, String> {
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()
+        .map_err(|err| err.to_string())?;
+    let stream = connector
+        .connect(host, stream)
+        .map_err(|err| err.to_string())?;
+    let certificate = stream.peer_certificate().map_err(|err| err.to_string())?;
+    certificate
+        .map(|certificate| certificate.to_der())
+        .transpose()
+        .map_err(|err| err.to_string())
+        .map(|certificate| certificate.into_iter().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(CertInspect {})
+    }
+
+    #[test]
+    fn splits_addresses() {
+        assert_eq!(host_and_port("example.com"), Some(("example.com", 443)));
+        assert_eq!(
+            host_and_port("example.com:8443"),
+            Some(("example.com", 8443))
+        );
+        assert_eq!(host_and_port("[::1]:8443"), Some(("::1", 8443)));
+        assert_eq!(host_and_port("::1"), Some(("::1", 443)));
+        assert_eq!(host_and_port("example.com:https"), None);
+    }
+}
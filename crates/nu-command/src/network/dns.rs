@@ -0,0 +1,517 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket},
+    time::Duration,
+};
+
+/// The record types which can be queried, with their codes in DNS messages.
+const RECORD_TYPES: [(&str, u16); 7] = [
+    ("A", 1),
+    ("NS", 2),
+    ("CNAME", 5),
+    ("PTR", 12),
+    ("MX", 15),
+    ("TXT", 16),
+    ("AAAA", 28),
+];
+
+#[derive(Clone)]
+pub struct DnsResolve;
+
+impl Command for DnsResolve {
+    fn name(&self) -> &str {
+        "dns resolve"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("dns resolve")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("name".into(), Type::String),
+                        ("type".into(), Type::String),
+                        ("ttl".into(), Type::Duration),
+                        ("value".into(), Type::String),
+                    ]
+                    .into(),
+                ),
+            )])
+            .allow_variants_without_examples(true)
+            .required(
+                "name",
+                SyntaxShape::String,
+                "The domain name, or an IP address for a reverse lookup.",
+            )
+            .named(
+                "type",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "the record types to look up: a, aaaa, cname, mx, ns, ptr or txt (defaults to a and aaaa)",
+                Some('t'),
+            )
+            .named(
+                "server",
+                SyntaxShape::String,
+                "the address of the name server, like 1.1.1.1 or 1.1.1.1:53",
+                Some('s'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for an answer (defaults to 5sec)",
+                None,
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Look up the DNS records of a domain name."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The records are those of the answer of the name server, with their time to live. MX records
+also have their priority. Answers can have records of other types, like the CNAME records of
+aliases. The name server is the first one of /etc/resolv.conf, unless --server is given."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["dig", "nslookup", "host", "lookup", "network"]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let types: Option<Vec<Spanned<String>>> = call.get_flag(engine_state, stack, "type")?;
+        let server: Option<Spanned<String>> = call.get_flag(engine_state, stack, "server")?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: timeout.span });
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => Duration::from_secs(5),
+        };
+        let server = match server {
+            Some(server) => parse_server(&server.item).ok_or(ShellError::IncorrectValue {
+                msg: "expected an IP address, optionally with a port".into(),
+                val_span: server.span,
+                call_span: head,
+            })?,
+            None => system_name_server().ok_or_else(|| ShellError::GenericError {
+                error: "No name server found".into(),
+                msg: "there's no name server in /etc/resolv.conf".into(),
+                span: Some(head),
+                help: Some("Use --server to give a name server".into()),
+                inner: vec![],
+            })?,
+        };
+
+        // IP addresses are looked up in the reverse zones
+        let (query, default_types) = match name.item.parse::<IpAddr>() {
+            Ok(ip) => (reverse_name(ip), vec![12]),
+            Err(_) => (name.item.trim_end_matches('.').to_string(), vec![1, 28]),
+        };
+        let types = match types {
+            Some(types) => types
+                .iter()
+                .map(|ty| {
+                    RECORD_TYPES
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(&ty.item))
+                        .map(|(_, code)| *code)
+                        .ok_or_else(|| ShellError::IncorrectValue {
+                            msg: format!(
+                                "unknown record type, expected one of {}",
+                                RECORD_TYPES.map(|(name, _)| name.to_lowercase()).join(", ")
+                            ),
+                            val_span: ty.span,
+                            call_span: head,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => default_types,
+        };
+
+        let mut records = vec![];
+        for ty in types {
+            let message = exchange(&build_query(&query, ty, name.span)?, server, timeout)
+                .map_err(|err| IoError::new(err, head, None))?;
+            let answers = parse_response(&message).map_err(|msg| ShellError::GenericError {
+                error: "DNS lookup failed".into(),
+                msg,
+                span: Some(name.span),
+                help: None,
+                inner: vec![],
+            })?;
+            for answer in answers {
+                // The answers of both A and AAAA queries can have the same CNAME records
+                if !records.contains(&answer) {
+                    records.push(answer);
+                }
+            }
+        }
+
+        let records = records
+            .into_iter()
+            .map(|answer| answer.into_value(head))
+            .collect();
+        Ok(Value::list(records, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Look up the addresses of a domain",
+                example: "dns resolve nushell.sh",
+                result: None,
+            },
+            Example {
+                description: "Get the mail servers of a domain, by priority",
+                example: "dns resolve gmail.com --type [mx] | sort-by priority | get value",
+                result: None,
+            },
+            Example {
+                description: "Look up the name of an address with a given name server",
+                example: "dns resolve 1.1.1.1 --server 8.8.8.8",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// A record of the answer section of a response.
+#[derive(PartialEq)]
+struct Answer {
+    name: String,
+    ty: u16,
+    ttl: u32,
+    value: String,
+    priority: Option<u16>,
+}
+
+impl Answer {
+    fn into_value(self, span: Span) -> Value {
+        let ty = RECORD_TYPES
+            .iter()
+            .find(|(_, code)| *code == self.ty)
+            .map_or("?", |(name, _)| *name);
+        let mut record = record! {
+            "name" => Value::string(self.name, span),
+            "type" => Value::string(ty, span),
+            "ttl" => Value::duration(self.ttl as i64 * 1_000_000_000, span),
+            "value" => Value::string(self.value, span),
+        };
+        if let Some(priority) = self.priority {
+            record.push("priority", Value::int(priority as i64, span));
+        }
+        Value::record(record, span)
+    }
+}
+
+fn parse_server(server: &str) -> Option<SocketAddr> {
+    server
+        .parse()
+        .ok()
+        .or_else(|| Some(SocketAddr::new(server.parse().ok()?, 53)))
+}
+
+/// The first name server of `/etc/resolv.conf`.
+fn system_name_server() -> Option<SocketAddr> {
+    let conf = std::fs::read_to_string("/etc/resolv.conf").ok()?;
+    conf.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        if words.next()? != "nameserver" {
+            return None;
+        }
+        // Link-local IPv6 addresses can have a zone like `%eth0`, which isn't supported
+        let ip = words.next()?.parse().ok()?;
+        Some(SocketAddr::new(ip, 53))
+    })
+}
+
+/// The name of the PTR record of an address, like `4.3.2.1.in-addr.arpa`.
+fn reverse_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, d] = ip.octets();
+            format!("{d}.{c}.{b}.{a}.in-addr.arpa")
+        }
+        IpAddr::V6(ip) => {
+            let mut name: String = ip
+                .octets()
+                .iter()
+                .rev()
+                .map(|byte| format!("{:x}.{:x}.", byte & 0xf, byte >> 4))
+                .collect();
+            name.push_str("ip6.arpa");
+            name
+        }
+    }
+}
+
+fn build_query(name: &str, ty: u16, span: Span) -> Result<Vec<u8>, ShellError> {
+    let invalid = |msg: &str| ShellError::IncorrectValue {
+        msg: msg.into(),
+        val_span: span,
+        call_span: span,
+    };
+
+    // A random id, so that spoofed answers can't guess it
+    let id: u16 = rand::random();
+    let mut message = vec![];
+    message.extend(id.to_be_bytes());
+    // Recursion desired, and one question
+    message.extend([0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid("not a valid domain name"));
+        }
+        message.push(label.len() as u8);
+        message.extend(label.as_bytes());
+    }
+    message.push(0);
+    if message.len() > 12 + 255 {
+        return Err(invalid("the domain name is too long"));
+    }
+    message.extend(ty.to_be_bytes());
+    // The IN class
+    message.extend([0, 1]);
+    Ok(message)
+}
+
+/// Send a query over UDP, or over TCP when the response doesn't fit in a datagram.
+fn exchange(query: &[u8], server: SocketAddr, timeout: Duration) -> std::io::Result<Vec<u8>> {
+    let local = match server {
+        SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some(timeout))?;
+    socket.connect(server)?;
+    socket.send(query)?;
+
+    let mut response = [0; 512];
+    let response = loop {
+        let len = socket.recv(&mut response)?;
+        // Late answers of a previous query are skipped
+        if len >= 12 && response[..2] == query[..2] {
+            break &response[..len];
+        }
+    };
+    // Truncated
+    if response[2] & 0x02 == 0 {
+        return Ok(response.to_vec());
+    }
+
+    let mut stream = TcpStream::connect_timeout(&server, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.write_all(&(query.len() as u16).to_be_bytes())?;
+    stream.write_all(query)?;
+    let mut len = [0; 2];
+    stream.read_exact(&mut len)?;
+    let mut response = vec![0; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut response)?;
+    Ok(response)
+}
+
+/// Read the answers of a response, with the records of unknown types left out.
+fn parse_response(message: &[u8]) -> Result<Vec<Answer>, String> {
+    let mut reader = Reader { message, pos: 12 };
+    if message.len() < 12 {
+        return Err("the response is too short".into());
+    }
+    match message[3] & 0x0f {
+        0 => {}
+        2 => return Err("the name server failed".into()),
+        3 => return Err("the domain doesn't exist".into()),
+        5 => return Err("the name server refused the query".into()),
+        code => return Err(format!("the response has the error code {code}")),
+    }
+    let questions = u16::from_be_bytes([message[4], message[5]]);
+    let answers = u16::from_be_bytes([message[6], message[7]]);
+
+    for _ in 0..questions {
+        reader.name()?;
+        reader.take(4)?;
+    }
+
+    let mut records = vec![];
+    for _ in 0..answers {
+        let name = reader.name()?;
+        let ty = reader.u16()?;
+        reader.u16()?;
+        let ttl = reader.u32()?;
+        let len = reader.u16()? as usize;
+        let end = reader.pos + len;
+
+        let (value, priority) = match ty {
+            1 => match reader.take(len)? {
+                &[a, b, c, d] => (Ipv4Addr::new(a, b, c, d).to_string(), None),
+                _ => return Err("invalid A record".into()),
+            },
+            28 => match <[u8; 16]>::try_from(reader.take(len)?) {
+                Ok(octets) => (Ipv6Addr::from(octets).to_string(), None),
+                Err(_) => return Err("invalid AAAA record".into()),
+            },
+            2 | 5 | 12 => (reader.name()?, None),
+            15 => {
+                let priority = reader.u16()?;
+                (reader.name()?, Some(priority))
+            }
+            16 => {
+                let mut text = vec![];
+                while reader.pos < end {
+                    let len = reader.u8()? as usize;
+                    text.extend(reader.take(len)?);
+                }
+                (String::from_utf8_lossy(&text).into_owned(), None)
+            }
+            _ => {
+                reader.take(len)?;
+                continue;
+            }
+        };
+        if reader.pos != end {
+            return Err("invalid record length".into());
+        }
+        records.push(Answer {
+            name,
+            ty,
+            ttl,
+            value,
+            priority,
+        });
+    }
+    Ok(records)
+}
+
+struct Reader<'a> {
+    message: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .message
+            .get(self.pos..self.pos + len)
+            .ok_or("the response is truncated")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes([
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+        ]))
+    }
+
+    /// Read a domain name, whose end can be a pointer to a name earlier in the message.
+    fn name(&mut self) -> Result<String, String> {
+        let mut labels: Vec<String> = vec![];
+        let mut pos = self.pos;
+        let mut end = None;
+        // Pointers go backwards, so there can't be more of them than bytes
+        for _ in 0..self.message.len() {
+            let len = *self.message.get(pos).ok_or("the response is truncated")? as usize;
+            if len & 0xc0 == 0xc0 {
+                let low = *self
+                    .message
+                    .get(pos + 1)
+                    .ok_or("the response is truncated")?;
+                end.get_or_insert(pos + 2);
+                let target = (len & 0x3f) << 8 | low as usize;
+                if target >= pos {
+                    return Err("invalid name pointer".into());
+                }
+                pos = target;
+            } else if len == 0 {
+                self.pos = end.unwrap_or(pos + 1);
+                return Ok(if labels.is_empty() {
+                    ".".into()
+                } else {
+                    labels.join(".")
+                });
+            } else {
+                let label = self
+                    .message
+                    .get(pos + 1..pos + 1 + len)
+                    .ok_or("the response is truncated")?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                pos += 1 + len;
+            }
+        }
+        Err("invalid name".into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(DnsResolve {})
+    }
+
+    #[test]
+    fn parses_compressed_answers() {
+        let mut message = build_query("example.com", 15, Span::test_data()).unwrap();
+        // A response with one answer
+        message[2] = 0x81;
+        message[3] = 0x80;
+        message[7] = 1;
+        // A pointer to the name of the question, type MX, class IN, TTL 300
+        message.extend([0xc0, 12, 0, 15, 0, 1, 0, 0, 1, 44, 0, 9, 0, 10]);
+        // `mail` and a pointer to `example.com`
+        message.extend([4, b'm', b'a', b'i', b'l', 0xc0, 12]);
+
+        let answers = parse_response(&message).unwrap();
+        assert!(
+            answers
+                == [Answer {
+                    name: "example.com".into(),
+                    ty: 15,
+                    ttl: 300,
+                    value: "mail.example.com".into(),
+                    priority: Some(10),
+                }]
+        );
+    }
+
+    #[test]
+    fn reverse_names() {
+        assert_eq!(
+            reverse_name("192.0.2.1".parse().unwrap()),
+            "1.2.0.192.in-addr.arpa"
+        );
+        assert!(reverse_name("2001:db8::1".parse().unwrap()).starts_with("1.0.0.0.0.0.0.0."));
+    }
+}
@@ -105,6 +105,10 @@ impl Command for HttpDelete {
         vec!["network", "request", "curl", "wget"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
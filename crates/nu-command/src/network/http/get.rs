@@ -99,6 +99,10 @@ impl Command for HttpGet {
         ]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
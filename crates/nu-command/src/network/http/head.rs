@@ -83,6 +83,10 @@ impl Command for HttpHead {
         vec!["network", "request", "curl", "wget", "headers", "header"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -98,6 +98,10 @@ impl Command for Http {
             .category(Category::Network)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Various commands for working with http methods."
     }
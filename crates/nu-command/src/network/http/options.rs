@@ -75,6 +75,10 @@ impl Command for HttpOptions {
         vec!["network", "fetch", "pull", "request", "curl", "wget"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -100,6 +100,10 @@ impl Command for HttpPatch {
         vec!["network", "send", "push"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -104,6 +104,10 @@ impl Command for HttpPost {
         vec!["network", "send", "push"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
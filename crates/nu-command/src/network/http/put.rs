@@ -104,6 +104,10 @@ impl Command for HttpPut {
         vec!["network", "send", "push"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -0,0 +1,93 @@
+use super::ip_input;
+use nu_engine::command_prelude::*;
+use nu_protocol::{IpValue, ListStream};
+
+#[derive(Clone)]
+pub struct NetworkHosts;
+
+impl Command for NetworkHosts {
+    fn name(&self) -> &str {
+        "network hosts"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("network hosts")
+            .input_output_types(vec![
+                (Type::custom("ip"), Type::List(Box::new(Type::custom("ip")))),
+                (Type::String, Type::List(Box::new(Type::custom("ip")))),
+            ])
+            .allow_variants_without_examples(true)
+            .category(Category::Network)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "List the addresses of the hosts of a network."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The network and broadcast addresses of IPv4 networks are left out, except for /31 and /32
+networks, which don't have them. The addresses are streamed, so the first ones of large IPv6
+networks can be taken."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["addresses", "cidr", "subnet", "range", "iterate"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let network = ip_input(&input.into_value(head)?, head)?;
+
+        let (mut first, mut last) = network.range();
+        if network.addr.is_ipv4() && network.prefix_len() < 31 {
+            first += 1;
+            last -= 1;
+        }
+        let hosts = (first..=last)
+            .map(move |bits| IpValue::new(network.with_bits(bits), None).into_value(head));
+
+        Ok(ListStream::new(hosts, head, engine_state.signals().clone()).into())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let ip = |text| {
+            IpValue::parse(text)
+                .expect("valid address")
+                .into_value(Span::test_data())
+        };
+        vec![
+            Example {
+                description: "List the hosts of a small network",
+                example: "'192.168.1.0/30' | network hosts",
+                result: Some(Value::test_list(vec![ip("192.168.1.1"), ip("192.168.1.2")])),
+            },
+            Example {
+                description: "Find the unused addresses of a network",
+                example: "let used = open hosts.csv | get address | into ip; '10.0.0.0/24' | network hosts | where $it not-in $used",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetworkHosts {})
+    }
+}
@@ -0,0 +1,145 @@
+use super::ip_input;
+use nu_engine::command_prelude::*;
+use nu_protocol::IpValue;
+
+use std::net::IpAddr;
+
+#[derive(Clone)]
+pub struct NetworkInfo;
+
+impl Command for NetworkInfo {
+    fn name(&self) -> &str {
+        "network info"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("network info")
+            .input_output_types(vec![
+                (Type::custom("ip"), Type::record()),
+                (Type::String, Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .category(Category::Network)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Get the network, netmask, range and kind of an IP address or network."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The first and last addresses are those of the hosts, so they leave out the network and
+broadcast addresses of IPv4 networks. The size is the number of addresses of the network, which
+is a float for IPv6 networks which are too large for an int."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "cidr",
+            "netmask",
+            "broadcast",
+            "subnet",
+            "private",
+            "calculator",
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let ip = ip_input(&input.into_value(head)?, head)?;
+        Ok(info(ip, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let ip = |text| {
+            IpValue::parse(text)
+                .expect("valid address")
+                .into_value(Span::test_data())
+        };
+        vec![
+            Example {
+                description: "Get the network and range of an address",
+                example: "'192.168.1.10/24' | network info",
+                result: Some(Value::test_record(record! {
+                    "address" => ip("192.168.1.10"),
+                    "network" => ip("192.168.1.0/24"),
+                    "prefix" => Value::test_int(24),
+                    "netmask" => ip("255.255.255.0"),
+                    "first" => ip("192.168.1.1"),
+                    "last" => ip("192.168.1.254"),
+                    "size" => Value::test_int(256),
+                    "version" => Value::test_int(4),
+                    "private" => Value::test_bool(true),
+                    "loopback" => Value::test_bool(false),
+                    "broadcast" => ip("192.168.1.255"),
+                })),
+            },
+            Example {
+                description: "Keep the private addresses of a list",
+                example: "['10.1.2.3' '8.8.8.8' 'fd00::1'] | where { network info | get private }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn info(ip: IpValue, span: Span) -> Value {
+    let ip_value = |addr: IpAddr, prefix| IpValue::new(addr, prefix).into_value(span);
+    let max = if ip.addr.is_ipv4() { 32 } else { 128 };
+    let (first, last) = ip.range();
+    let mask = !(last - first) & u128::MAX.checked_shr(128 - max as u32).unwrap_or(0);
+    let (first_host, last_host) = if ip.addr.is_ipv4() && ip.prefix_len() < 31 {
+        (first + 1, last - 1)
+    } else {
+        (first, last)
+    };
+    let size = (last - first).checked_add(1);
+    let size = match size.and_then(|size| i64::try_from(size).ok()) {
+        Some(size) => Value::int(size, span),
+        None => Value::float(size.map_or(2f64.powi(128), |size| size as f64), span),
+    };
+
+    let private = match ip.addr {
+        IpAddr::V4(addr) => addr.is_private(),
+        // Unique local addresses, fc00::/7
+        IpAddr::V6(addr) => addr.segments()[0] & 0xfe00 == 0xfc00,
+    };
+
+    let mut record = record! {
+        "address" => ip_value(ip.addr, None),
+        "network" => ip_value(ip.with_bits(first), Some(ip.prefix_len())),
+        "prefix" => Value::int(ip.prefix_len() as i64, span),
+        "netmask" => ip_value(ip.with_bits(mask), None),
+        "first" => ip_value(ip.with_bits(first_host), None),
+        "last" => ip_value(ip.with_bits(last_host), None),
+        "size" => size,
+        "version" => Value::int(if ip.addr.is_ipv4() { 4 } else { 6 }, span),
+        "private" => Value::bool(private, span),
+        "loopback" => Value::bool(ip.addr.is_loopback(), span),
+    };
+    if ip.addr.is_ipv4() {
+        record.push("broadcast", ip_value(ip.with_bits(last), None));
+    }
+    Value::record(record, span)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetworkInfo {})
+    }
+}
@@ -0,0 +1,30 @@
+mod hosts;
+mod info;
+mod subnets;
+
+pub use hosts::NetworkHosts;
+pub use info::NetworkInfo;
+pub use subnets::NetworkSubnets;
+
+use nu_protocol::{IpValue, ShellError, Span, Value};
+
+/// The IP value of the input of a `network` subcommand, which can also be a string.
+fn ip_input(value: &Value, head: Span) -> Result<IpValue, ShellError> {
+    match value {
+        Value::String { val, .. } => IpValue::parse(val).map_err(|msg| ShellError::CantConvert {
+            to_type: "ip".into(),
+            from_type: "string".into(),
+            span: value.span(),
+            help: Some(msg),
+        }),
+        Value::Error { error, .. } => Err(*error.clone()),
+        other => {
+            IpValue::try_from_value(other).ok_or_else(|| ShellError::OnlySupportsThisInputType {
+                exp_input_type: "ip or string".into(),
+                wrong_type: other.get_type().to_string(),
+                dst_span: head,
+                src_span: other.span(),
+            })
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::ip_input;
+use nu_engine::command_prelude::*;
+use nu_protocol::{IpValue, ListStream};
+
+#[derive(Clone)]
+pub struct NetworkSubnets;
+
+impl Command for NetworkSubnets {
+    fn name(&self) -> &str {
+        "network subnets"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("network subnets")
+            .input_output_types(vec![
+                (Type::custom("ip"), Type::List(Box::new(Type::custom("ip")))),
+                (Type::String, Type::List(Box::new(Type::custom("ip")))),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "prefix",
+                SyntaxShape::Int,
+                "The prefix length of the subnets.",
+            )
+            .category(Category::Network)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Split a network into subnets of a prefix length."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cidr", "split", "divide", "iterate"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let prefix: Spanned<i64> = call.req(engine_state, stack, 0)?;
+        let network = ip_input(&input.into_value(head)?, head)?;
+
+        let max = if network.addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match u8::try_from(prefix.item) {
+            Ok(item) if (network.prefix_len()..=max).contains(&item) => item,
+            _ => {
+                return Err(ShellError::IncorrectValue {
+                    msg: format!(
+                        "the prefix length has to be between {} and {max}",
+                        network.prefix_len()
+                    ),
+                    val_span: prefix.span,
+                    call_span: head,
+                });
+            }
+        };
+
+        let (first, last) = network.range();
+        let step = 1u128.checked_shl((max - prefix) as u32).unwrap_or(0);
+        let subnets = std::iter::successors(Some(first), move |bits| {
+            bits.checked_add(step)
+                .filter(|bits| *bits <= last && step > 0)
+        })
+        .map(move |bits| IpValue::new(network.with_bits(bits), Some(prefix)).into_value(head));
+
+        Ok(ListStream::new(subnets, head, engine_state.signals().clone()).into())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let ip = |text| {
+            IpValue::parse(text)
+                .expect("valid network")
+                .into_value(Span::test_data())
+        };
+        vec![
+            Example {
+                description: "Split a network into four",
+                example: "'10.0.0.0/24' | network subnets 26",
+                result: Some(Value::test_list(vec![
+                    ip("10.0.0.0/26"),
+                    ip("10.0.0.64/26"),
+                    ip("10.0.0.128/26"),
+                    ip("10.0.0.192/26"),
+                ])),
+            },
+            Example {
+                description: "Get the first /64 networks of an IPv6 allocation",
+                example: "'2001:db8::/48' | network subnets 64 | first 3",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetworkSubnets {})
+    }
+}
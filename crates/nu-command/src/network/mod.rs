@@ -1,19 +1,47 @@
 #[cfg(feature = "network")]
+mod cert_;
+#[cfg(feature = "network")]
+mod cert_inspect;
+#[cfg(feature = "network")]
+mod dns;
+#[cfg(feature = "network")]
+mod dns_;
+#[cfg(feature = "network")]
 mod http;
+mod ip;
+mod network_;
+#[cfg(feature = "network")]
+mod ping;
 #[cfg(feature = "network")]
 mod port;
 #[cfg(feature = "network")]
+mod port_scan;
+#[cfg(feature = "network")]
 pub mod tls;
 mod url;
 #[cfg(feature = "network")]
 mod version_check;
 
+#[cfg(feature = "network")]
+pub use self::cert_::Cert;
+#[cfg(feature = "network")]
+pub use self::cert_inspect::CertInspect;
+#[cfg(feature = "network")]
+pub use self::dns::DnsResolve;
+#[cfg(feature = "network")]
+pub use self::dns_::Dns;
 #[cfg(feature = "network")]
 pub use self::http::*;
+pub use self::ip::*;
 pub use self::url::*;
 
+pub use network_::Network;
+#[cfg(feature = "network")]
+pub use ping::NetworkPing;
 #[cfg(feature = "network")]
 pub use port::Port;
+#[cfg(feature = "network")]
+pub use port_scan::PortScan;
 
 #[cfg(feature = "network")]
 pub use version_check::VersionCheck;
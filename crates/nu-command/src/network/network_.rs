@@ -0,0 +1,45 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Network;
+
+impl Command for Network {
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("network")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Network)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with networks, IP addresses and network hosts."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Ports are probed with `port scan`, names are looked up with `dns resolve`, and IP addresses
+and networks are made from strings with `into ip`.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["ip", "address", "cidr", "subnet", "host", "latency"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
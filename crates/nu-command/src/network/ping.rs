@@ -0,0 +1,341 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{ListStream, Signals, shell_error::io::IoError};
+use socket2::{Domain, Protocol, Socket, Type as SocketType};
+
+use std::{
+    io::{ErrorKind, Read},
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+const CTRL_C_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct NetworkPing;
+
+impl Command for NetworkPing {
+    fn name(&self) -> &str {
+        "network ping"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("network ping")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("seq".into(), Type::Int),
+                        ("address".into(), Type::String),
+                        ("protocol".into(), Type::String),
+                        ("latency".into(), Type::Duration),
+                    ]
+                    .into(),
+                ),
+            )])
+            .allow_variants_without_examples(true)
+            .required("host", SyntaxShape::String, "The host name or address.")
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "the number of pings (defaults to pinging until ctrl-c)",
+                Some('c'),
+            )
+            .named(
+                "interval",
+                SyntaxShape::Duration,
+                "the time between pings (defaults to 1sec)",
+                Some('i'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for each reply (defaults to 1sec)",
+                Some('t'),
+            )
+            .named(
+                "port",
+                SyntaxShape::Int,
+                "ping by connecting to this TCP port instead of with ICMP",
+                Some('p'),
+            )
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Measure the round trip time to a host."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The pings are ICMP echo requests, or TCP connections with --port. The latency is nothing
+for the pings without a reply before the timeout.
+
+ICMP pings use unprivileged ICMP sockets, which Linux only allows for the groups in
+`net.ipv4.ping_group_range`. TCP pings work everywhere, and through firewalls which drop ICMP."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["icmp", "latency", "rtt", "tcping"]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let host: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let count: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "count")?;
+        let interval = duration_flag(engine_state, stack, call, "interval")?;
+        let timeout = duration_flag(engine_state, stack, call, "timeout")?;
+        let port: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "port")?;
+
+        let count = count
+            .map(|count| {
+                u64::try_from(count.item)
+                    .map_err(|_| ShellError::NeedsPositiveValue { span: count.span })
+            })
+            .transpose()?;
+        let port = port
+            .map(|port| {
+                u16::try_from(port.item).map_err(|_| ShellError::IncorrectValue {
+                    msg: "expected a port between 0 and 65535".into(),
+                    val_span: port.span,
+                    call_span: head,
+                })
+            })
+            .transpose()?;
+
+        let address = (host.item.as_str(), port.unwrap_or(0))
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| ShellError::GenericError {
+                error: "Unknown host".into(),
+                msg: "the host name can't be resolved".into(),
+                span: Some(host.span),
+                help: None,
+                inner: vec![],
+            })?;
+
+        let pinger =
+            match port {
+                Some(_) => Pinger::Tcp(address),
+                None => Pinger::Icmp(icmp_socket(address.ip()).map_err(|err| {
+                    ShellError::GenericError {
+                        error: "Can't send ICMP pings".into(),
+                        msg: err.to_string(),
+                        span: Some(head),
+                        help: Some("Use --port to ping with TCP connections instead".into()),
+                        inner: vec![],
+                    }
+                })?),
+            };
+
+        let signals = engine_state.signals().clone();
+        let interval = interval.unwrap_or(Duration::from_secs(1));
+        let timeout = timeout.unwrap_or(Duration::from_secs(1));
+        let pings = (0..count.unwrap_or(u64::MAX)).map_while(move |seq| {
+            if seq > 0 && !sleep(interval, &signals) {
+                return None;
+            }
+            let record = |latency: Option<Duration>| {
+                Value::record(
+                    record! {
+                        "seq" => Value::int(seq as i64, head),
+                        "address" => Value::string(address.ip().to_string(), head),
+                        "protocol" => Value::string(pinger.protocol(), head),
+                        "latency" => latency.map_or(Value::nothing(head), |latency| {
+                            Value::duration(latency.as_nanos() as i64, head)
+                        }),
+                    },
+                    head,
+                )
+            };
+            Some(match pinger.ping(seq as u16, timeout) {
+                Ok(latency) => record(latency),
+                Err(err) => Value::error(IoError::new(err, head, None).into(), head),
+            })
+        });
+
+        Ok(ListStream::new(pings, head, engine_state.signals().clone()).into())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Ping a host until ctrl-c",
+                example: "network ping nushell.sh",
+                result: None,
+            },
+            Example {
+                description: "Get the average latency of 10 pings",
+                example: "network ping 1.1.1.1 --count 10 --interval 200ms | get latency | compact | math avg",
+                result: None,
+            },
+            Example {
+                description: "Ping a web server by connecting to its HTTPS port",
+                example: "network ping example.com --port 443 --count 3",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn duration_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &str,
+) -> Result<Option<Duration>, ShellError> {
+    let duration: Option<Spanned<i64>> = call.get_flag(engine_state, stack, name)?;
+    duration
+        .map(|duration| {
+            u64::try_from(duration.item)
+                .map(Duration::from_nanos)
+                .map_err(|_| ShellError::NeedsPositiveValue {
+                    span: duration.span,
+                })
+        })
+        .transpose()
+}
+
+/// Sleep for `duration`, or return `false` if interrupted before.
+fn sleep(duration: Duration, signals: &Signals) -> bool {
+    let end = Instant::now() + duration;
+    loop {
+        if signals.interrupted() {
+            return false;
+        }
+        match end.checked_duration_since(Instant::now()) {
+            Some(remaining) if !remaining.is_zero() => {
+                thread::sleep(CTRL_C_CHECK_INTERVAL.min(remaining))
+            }
+            _ => return true,
+        }
+    }
+}
+
+enum Pinger {
+    Icmp(Socket),
+    Tcp(SocketAddr),
+}
+
+impl Pinger {
+    fn protocol(&self) -> &'static str {
+        match self {
+            Pinger::Icmp(_) => "icmp",
+            Pinger::Tcp(_) => "tcp",
+        }
+    }
+
+    /// The round trip time of a ping, or nothing without a reply before the timeout.
+    fn ping(&self, seq: u16, timeout: Duration) -> std::io::Result<Option<Duration>> {
+        match self {
+            Pinger::Icmp(socket) => icmp_echo(socket, seq, timeout),
+            Pinger::Tcp(address) => {
+                let start = Instant::now();
+                match TcpStream::connect_timeout(address, timeout) {
+                    Ok(_) => Ok(Some(start.elapsed())),
+                    Err(err) if err.kind() == ErrorKind::TimedOut => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }
+        }
+    }
+}
+
+/// An ICMP datagram socket, which unlike raw sockets doesn't need privileges.
+fn icmp_socket(ip: IpAddr) -> std::io::Result<Socket> {
+    let socket = match ip {
+        IpAddr::V4(_) => Socket::new(Domain::IPV4, SocketType::DGRAM, Some(Protocol::ICMPV4)),
+        IpAddr::V6(_) => Socket::new(Domain::IPV6, SocketType::DGRAM, Some(Protocol::ICMPV6)),
+    }?;
+    socket.connect(&SocketAddr::new(ip, 0).into())?;
+    Ok(socket)
+}
+
+fn icmp_echo(socket: &Socket, seq: u16, timeout: Duration) -> std::io::Result<Option<Duration>> {
+    let v6 = socket.peer_addr()?.is_ipv6();
+    let (request, reply) = if v6 { (128, 129) } else { (8, 0) };
+
+    // The identifier is set by the kernel for datagram sockets
+    let mut packet = vec![request, 0, 0, 0, 0, 0];
+    packet.extend(seq.to_be_bytes());
+    packet.extend(b"nushell ping".repeat(4));
+    // The kernel computes the checksum of ICMPv6
+    if !v6 {
+        let sum = checksum(&packet);
+        packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+
+    let start = Instant::now();
+    socket.send(&packet)?;
+    let mut buffer = [0; 1500];
+    loop {
+        let Some(remaining) = timeout
+            .checked_sub(start.elapsed())
+            .filter(|d| !d.is_zero())
+        else {
+            return Ok(None);
+        };
+        socket.set_read_timeout(Some(remaining))?;
+        let len = match (&*socket).read(&mut buffer) {
+            Ok(len) => len,
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        // Some systems, like macOS, give the IPv4 header too
+        let mut message = &buffer[..len];
+        if !v6 && message.first().is_some_and(|byte| byte >> 4 == 4) {
+            let header_len = (message[0] & 0x0f) as usize * 4;
+            message = message.get(header_len..).unwrap_or_default();
+        }
+        // Replies to earlier pings are skipped
+        if message.len() >= 8 && message[0] == reply && message[6..8] == seq.to_be_bytes() {
+            return Ok(Some(start.elapsed()));
+        }
+    }
+}
+
+/// The internet checksum of RFC 1071.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair.get(1).copied().unwrap_or(0)]) as u32)
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(NetworkPing {})
+    }
+
+    #[test]
+    fn computes_checksums() {
+        // The example of RFC 1071
+        assert_eq!(
+            checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]),
+            !0xddf2
+        );
+    }
+}
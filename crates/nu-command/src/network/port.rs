@@ -31,6 +31,10 @@ impl Command for Port {
         vec!["network", "http"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -0,0 +1,263 @@
+use nu_engine::command_prelude::*;
+use nu_protocol::{Range, Signals};
+
+use std::{
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The most connections at the same time, as each of them has a thread.
+const MAX_CONCURRENCY: i64 = 1024;
+
+#[derive(Clone)]
+pub struct PortScan;
+
+impl Command for PortScan {
+    fn name(&self) -> &str {
+        "port scan"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("port scan")
+            .input_output_types(vec![(
+                Type::Nothing,
+                Type::Table(
+                    [
+                        ("port".into(), Type::Int),
+                        ("open".into(), Type::Bool),
+                        ("latency".into(), Type::Duration),
+                    ]
+                    .into(),
+                ),
+            )])
+            .allow_variants_without_examples(true)
+            .required("host", SyntaxShape::String, "The host name or address.")
+            .rest(
+                "ports",
+                SyntaxShape::OneOf(vec![SyntaxShape::Int, SyntaxShape::Range]),
+                "The ports to check, or ranges of ports.",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for each connection (defaults to 1sec)",
+                Some('t'),
+            )
+            .named(
+                "concurrency",
+                SyntaxShape::Int,
+                "the maximum number of connections at the same time, up to 1024 (defaults to 64)",
+                Some('c'),
+            )
+            .switch("open", "only list the open ports", Some('o'))
+            .category(Category::Network)
+    }
+
+    fn description(&self) -> &str {
+        "Check which TCP ports of a host accept connections."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"A port is open when a TCP connection to it succeeds before the timeout. The latency is the
+time it took to connect, and nothing for the ports which aren't open. The ports are listed in
+order, even though they're checked at the same time."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["nmap", "nc", "netcat", "connect", "network"]
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let host: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let port_args: Vec<Value> = call.rest(engine_state, stack, 1)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let concurrency: Option<Spanned<i64>> =
+            call.get_flag(engine_state, stack, "concurrency")?;
+        let only_open = call.has_flag(engine_state, stack, "open")?;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue { span: timeout.span });
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => Duration::from_secs(1),
+        };
+        let concurrency = match concurrency {
+            Some(concurrency) if concurrency.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue {
+                    span: concurrency.span,
+                });
+            }
+            Some(concurrency) if concurrency.item > MAX_CONCURRENCY => {
+                return Err(ShellError::IncorrectValue {
+                    msg: format!("at most {MAX_CONCURRENCY} connections at the same time"),
+                    val_span: concurrency.span,
+                    call_span: head,
+                });
+            }
+            Some(concurrency) => concurrency.item as usize,
+            None => 64,
+        };
+        if port_args.is_empty() {
+            return Err(ShellError::MissingParameter {
+                param_name: "ports".into(),
+                span: head,
+            });
+        }
+
+        let mut ports = vec![];
+        for arg in port_args {
+            let span = arg.span();
+            let numbers: Box<dyn Iterator<Item = i64>> = match arg {
+                Value::Int { val, .. } => Box::new(std::iter::once(val)),
+                Value::Range { val, .. } => match *val {
+                    // Unbounded ranges like `1024..` go up to the last port
+                    Range::IntRange(range) => Box::new(
+                        range
+                            .into_range_iter(engine_state.signals().clone())
+                            .take_while(|number| *number <= u16::MAX as i64),
+                    ),
+                    Range::FloatRange(_) => {
+                        return Err(ShellError::UnsupportedInput {
+                            msg: "float range".into(),
+                            input: "value originates from here".into(),
+                            msg_span: head,
+                            input_span: span,
+                        });
+                    }
+                },
+                other => {
+                    return Err(ShellError::TypeMismatch {
+                        err_message: format!("expected a port, not {}", other.get_type()),
+                        span,
+                    });
+                }
+            };
+            for number in numbers {
+                let port = u16::try_from(number).map_err(|_| ShellError::IncorrectValue {
+                    msg: format!("{number} is not a port between 0 and 65535"),
+                    val_span: span,
+                    call_span: head,
+                })?;
+                ports.push(port);
+            }
+        }
+        ports.sort_unstable();
+        ports.dedup();
+
+        let ip = resolve(&host.item).ok_or_else(|| ShellError::GenericError {
+            error: "Unknown host".into(),
+            msg: "the host name can't be resolved".into(),
+            span: Some(host.span),
+            help: None,
+            inner: vec![],
+        })?;
+
+        let latencies = scan(ip, &ports, timeout, concurrency, engine_state.signals());
+        engine_state.signals().check(&head)?;
+
+        let rows = ports
+            .into_iter()
+            .zip(latencies)
+            .filter(|(_, latency)| !only_open || latency.is_some())
+            .map(|(port, latency)| {
+                Value::record(
+                    record! {
+                        "port" => Value::int(port as i64, head),
+                        "open" => Value::bool(latency.is_some(), head),
+                        "latency" => latency.map_or(Value::nothing(head), |latency| {
+                            Value::duration(latency.as_nanos() as i64, head)
+                        }),
+                    },
+                    head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Check the web ports of a host",
+                example: "port scan example.com 80 443",
+                result: None,
+            },
+            Example {
+                description: "List the open ports below 1024 of a local server",
+                example: "port scan 192.168.1.10 1..1023 --open --timeout 200ms",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn resolve(host: &str) -> Option<IpAddr> {
+    (host, 0)
+        .to_socket_addrs()
+        .ok()?
+        .next()
+        .map(|address| address.ip())
+}
+
+/// The time it took to connect to each of the ports, with at most `concurrency` connections at
+/// the same time.
+fn scan(
+    ip: IpAddr,
+    ports: &[u16],
+    timeout: Duration,
+    concurrency: usize,
+    signals: &Signals,
+) -> Vec<Option<Duration>> {
+    let next = AtomicUsize::new(0);
+    let latencies = Mutex::new(vec![None; ports.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.min(ports.len()) {
+            scope.spawn(|| {
+                while !signals.interrupted() {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(port) = ports.get(index) else {
+                        break;
+                    };
+                    let start = Instant::now();
+                    // Errors are closed, filtered or unreachable ports
+                    let latency = TcpStream::connect_timeout(&SocketAddr::new(ip, *port), timeout)
+                        .ok()
+                        .map(|_| start.elapsed());
+                    latencies.lock().expect("latencies lock is poisoned!")[index] = latency;
+                }
+            });
+        }
+    });
+
+    latencies.into_inner().expect("latencies lock is poisoned!")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(PortScan {})
+    }
+}
@@ -22,6 +22,10 @@ impl Command for UrlBuildQuery {
             .category(Category::Network)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Converts record or table into query string applying percent-encoding."
     }
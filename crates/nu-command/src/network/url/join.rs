@@ -16,6 +16,10 @@ impl Command for UrlJoin {
             .category(Category::Network)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Converts a record to url."
     }
@@ -28,6 +28,10 @@ impl Command for UrlParse {
             .category(Category::Network)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Parses a url."
     }
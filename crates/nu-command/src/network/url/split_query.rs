@@ -19,6 +19,10 @@ impl Command for UrlSplitQuery {
             .category(Category::Network)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Converts query string into table applying percent-decoding."
     }
@@ -36,6 +36,10 @@ impl Command for VersionCheck {
         }]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Network])
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
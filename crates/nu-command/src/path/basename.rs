@@ -21,6 +21,7 @@ impl Command for PathBasename {
         Signature::build("path basename")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::String),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::String)),
@@ -32,6 +33,7 @@ impl Command for PathBasename {
                 "Return original path with basename replaced by this string",
                 Some('r'),
             )
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
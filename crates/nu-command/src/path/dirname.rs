@@ -1,7 +1,7 @@
 use super::PathSubcommandArguments;
 use nu_engine::command_prelude::*;
 use nu_protocol::engine::StateWorkingSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 struct Arguments {
     replace: Option<Spanned<String>>,
@@ -22,6 +22,7 @@ impl Command for PathDirname {
         Signature::build("path dirname")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::custom("path")),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::String)),
@@ -39,6 +40,7 @@ impl Command for PathDirname {
                 "Number of directories to walk up",
                 Some('n'),
             )
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
@@ -68,7 +70,7 @@ impl Command for PathDirname {
             return Err(ShellError::PipelineEmpty { dst_span: head });
         }
         input.map(
-            move |value| super::operate(&get_dirname, &args, value, head),
+            move |value| super::operate_path(&get_dirname, &args, value, head),
             engine_state.signals(),
         )
     }
@@ -90,7 +92,7 @@ impl Command for PathDirname {
             return Err(ShellError::PipelineEmpty { dst_span: head });
         }
         input.map(
-            move |value| super::operate(&get_dirname, &args, value, head),
+            move |value| super::operate_path(&get_dirname, &args, value, head),
             working_set.permanent().signals(),
         )
     }
@@ -154,7 +156,7 @@ impl Command for PathDirname {
     }
 }
 
-fn get_dirname(path: &Path, span: Span, args: &Arguments) -> Value {
+fn get_dirname(path: &Path, args: &Arguments) -> PathBuf {
     let num_levels = args.num_levels.as_ref().map_or(1, |val| *val);
 
     let mut dirname = path;
@@ -169,7 +171,7 @@ fn get_dirname(path: &Path, span: Span, args: &Arguments) -> Value {
         }
     }
 
-    let path = match args.replace {
+    match args.replace {
         Some(ref newdir) => {
             let remainder = path.strip_prefix(dirname).unwrap_or(dirname);
             if !remainder.as_os_str().is_empty() {
@@ -179,9 +181,7 @@ fn get_dirname(path: &Path, span: Span, args: &Arguments) -> Value {
             }
         }
         None => dirname.to_path_buf(),
-    };
-
-    Value::string(path.to_string_lossy(), span)
+    }
 }
 
 #[cfg(test)]
@@ -24,12 +24,14 @@ impl Command for PathExists {
         Signature::build("path exists")
             .input_output_types(vec![
                 (Type::String, Type::Bool),
+                (Type::custom("path"), Type::Bool),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::Bool)),
                 ),
             ])
             .switch("no-symlink", "Do not resolve symbolic links", Some('n'))
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
@@ -28,6 +28,7 @@ impl Command for PathExpand {
         Signature::build("path expand")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::String),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::String)),
@@ -39,6 +40,7 @@ impl Command for PathExpand {
                 Some('s'),
             )
             .switch("no-symlink", "Do not resolve symbolic links", Some('n'))
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
@@ -21,6 +21,7 @@ impl Command for PathJoin {
         Signature::build("path join")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::custom("path")),
                 (Type::List(Box::new(Type::String)), Type::String),
                 (Type::record(), Type::String),
                 (Type::table(), Type::List(Box::new(Type::String))),
@@ -30,6 +31,7 @@ impl Command for PathJoin {
                 SyntaxShape::String,
                 "Path to append to the input.",
             )
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
@@ -187,18 +189,22 @@ fn handle_value(v: Value, args: &Arguments, head: Span) -> Value {
         Value::String { ref val, .. } => join_single(Path::new(val), head, args),
         Value::Record { val, .. } => join_record(&val, head, span, args),
         Value::List { vals, .. } => join_list(&vals, head, span, args),
+        Value::Custom { .. } => super::operate_path(&join_path, args, v, head),
 
         _ => super::handle_invalid_values(v, head),
     }
 }
 
 fn join_single(path: &Path, head: Span, args: &Arguments) -> Value {
+    Value::string(join_path(path, args).to_string_lossy(), head)
+}
+
+fn join_path(path: &Path, args: &Arguments) -> PathBuf {
     let mut result = path.to_path_buf();
     for path_to_append in &args.append {
         result.push(&path_to_append.item)
     }
-
-    Value::string(result.to_string_lossy(), head)
+    result
 }
 
 fn join_list(parts: &[Value], head: Span, span: Span, args: &Arguments) -> Value {
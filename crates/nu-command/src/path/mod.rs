@@ -22,8 +22,8 @@ pub use self_::PathSelf;
 pub use split::PathSplit;
 pub use r#type::PathType;
 
-use nu_protocol::{ShellError, Span, Value};
-use std::path::Path as StdPath;
+use nu_protocol::{PathValue, ShellError, Span, Value};
+use std::path::{Path as StdPath, PathBuf};
 
 #[cfg(windows)]
 const ALLOWED_COLUMNS: [&str; 4] = ["prefix", "parent", "stem", "extension"];
@@ -40,7 +40,29 @@ where
     let span = v.span();
     match v {
         Value::String { val, .. } => cmd(StdPath::new(&val), span, args),
-        _ => handle_invalid_values(v, name),
+        _ => match PathValue::try_from_value(&v) {
+            Some(path) => cmd(path, span, args),
+            None => handle_invalid_values(v, name),
+        },
+    }
+}
+
+/// Like [`operate`], for subcommands which return a path. Path values stay path values, so the
+/// segments which aren't valid UTF-8 are kept.
+fn operate_path<F, A>(cmd: &F, args: &A, v: Value, name: Span) -> Value
+where
+    F: Fn(&StdPath, &A) -> PathBuf + Send + Sync + 'static,
+    A: PathSubcommandArguments + Send + Sync + 'static,
+{
+    let span = v.span();
+    match v {
+        Value::String { val, .. } => {
+            Value::string(cmd(StdPath::new(&val), args).to_string_lossy(), span)
+        }
+        _ => match PathValue::try_from_value(&v) {
+            Some(path) => PathValue::new(cmd(path, args)).into_value(span),
+            None => handle_invalid_values(v, name),
+        },
     }
 }
 
@@ -21,6 +21,7 @@ impl Command for PathParse {
         Signature::build("path parse")
             .input_output_types(vec![
                 (Type::String, Type::record()),
+                (Type::custom("path"), Type::record()),
                 (Type::List(Box::new(Type::String)), Type::table()),
             ])
             .named(
@@ -29,6 +30,7 @@ impl Command for PathParse {
                 "Manually supply the extension (without the dot)",
                 Some('e'),
             )
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
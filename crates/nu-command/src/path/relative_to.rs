@@ -22,6 +22,7 @@ impl Command for PathRelativeTo {
         Signature::build("path relative-to")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::String),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::String)),
@@ -32,6 +33,7 @@ impl Command for PathRelativeTo {
                 SyntaxShape::String,
                 "Parent shared with the input path.",
             )
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
@@ -19,11 +19,13 @@ impl Command for PathSplit {
         Signature::build("path split")
             .input_output_types(vec![
                 (Type::String, Type::List(Box::new(Type::String))),
+                (Type::custom("path"), Type::List(Box::new(Type::String))),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::List(Box::new(Type::String)))),
                 ),
             ])
+            .allow_variants_without_examples(true)
             .category(Category::Path)
     }
 
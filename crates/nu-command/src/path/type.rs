@@ -22,6 +22,7 @@ impl Command for PathType {
         Signature::build("path type")
             .input_output_types(vec![
                 (Type::String, Type::String),
+                (Type::custom("path"), Type::String),
                 (
                     Type::List(Box::new(Type::String)),
                     Type::List(Box::new(Type::String)),
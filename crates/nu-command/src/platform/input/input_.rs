@@ -68,6 +68,10 @@ impl Command for Input {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -44,6 +44,10 @@ impl Command for InputListen {
             )])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Listen for user interface event."
     }
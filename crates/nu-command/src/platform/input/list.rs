@@ -55,6 +55,10 @@ impl Command for InputList {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Interactive list selection."
     }
@@ -18,6 +18,10 @@ impl Command for IsTerminal {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Check if stdin, stdout, or stderr is a terminal."
     }
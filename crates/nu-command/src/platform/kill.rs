@@ -43,6 +43,10 @@ impl Command for Kill {
         vec!["stop", "end", "close"]
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -27,6 +27,10 @@ impl Command for Sleep {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["delay", "wait", "timer"]
     }
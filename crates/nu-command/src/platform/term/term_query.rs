@@ -61,6 +61,10 @@ The `prefix` is not included in the output."
             )
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn examples(&self) -> Vec<Example<'_>> {
         vec![
             Example {
@@ -519,6 +519,10 @@ impl Command for ULimit {
         sig
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[Effect::Process])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
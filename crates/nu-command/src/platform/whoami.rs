@@ -20,6 +20,10 @@ impl Command for Whoami {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["username", "coreutils"]
     }
@@ -6,6 +6,7 @@ mod dice;
 mod float;
 mod int;
 mod random_;
+mod ulid;
 mod uuid;
 
 pub use self::binary::RandomBinary;
@@ -14,5 +15,6 @@ pub use self::chars::RandomChars;
 pub use self::dice::RandomDice;
 pub use self::float::RandomFloat;
 pub use self::int::RandomInt;
+pub use self::ulid::RandomUlid;
 pub use self::uuid::RandomUuid;
 pub use random_::Random;
@@ -0,0 +1,109 @@
+use crate::ulid::encode;
+use nu_engine::command_prelude::*;
+
+use std::{
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const RANDOM_BITS: u32 = 80;
+
+/// The time and random bits of the last ULID, to keep ULIDs of the same millisecond in order.
+static LAST: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+#[derive(Clone)]
+pub struct RandomUlid;
+
+impl Command for RandomUlid {
+    fn name(&self) -> &str {
+        "random ulid"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random ulid")
+            .category(Category::Random)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .allow_variants_without_examples(true)
+    }
+
+    fn description(&self) -> &str {
+        "Generate a random ULID string."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"ULIDs start with the time at which they're generated, so they sort in the order in which
+they were generated, even within the same millisecond. Use `ulid parse` to get the time back."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["generate", "sortable", "uuid", "identifier"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as u64)
+            .unwrap_or_default();
+        Ok(Value::string(encode(next(millis)), call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Generate a random ULID",
+                example: "random ulid",
+                result: None,
+            },
+            Example {
+                description: "Generate ULIDs for rows of a table",
+                example: "[[name]; [a] [b]] | insert id { random ulid }",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The bits of the next ULID at `millis`, whose random part is the last one plus one when the
+/// last ULID was generated in the same millisecond or later.
+fn next(millis: u64) -> u128 {
+    let mask = (1 << RANDOM_BITS) - 1;
+    let mut last = LAST.lock().expect("ulid lock is poisoned!");
+    let (millis, random) = if millis <= last.0 {
+        match last.1.checked_add(1).filter(|random| *random <= mask) {
+            Some(random) => (last.0, random),
+            None => (last.0 + 1, rand::random::<u128>() & mask),
+        }
+    } else {
+        (millis, rand::random::<u128>() & mask)
+    };
+    *last = (millis, random);
+    // The timestamp has 48 bits
+    ((millis as u128 & 0xffff_ffff_ffff) << RANDOM_BITS) | random
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(RandomUlid {})
+    }
+
+    #[test]
+    fn ulids_of_the_same_millisecond_are_in_order() {
+        let first = next(1_469_922_850_259);
+        let second = next(1_469_922_850_259);
+        assert!(second > first);
+        assert!(encode(second) > encode(first));
+        assert_eq!(encode(0), "00000000000000000000000000");
+    }
+}
@@ -24,6 +24,10 @@ impl Command for DateFormat {
             .category(Category::Removed)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Removed command: use `format date` instead."
     }
@@ -21,6 +21,10 @@ impl Command for LetEnv {
             .category(Category::Removed)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "`let-env FOO = ...` has been removed, use `$env.FOO = ...` instead."
     }
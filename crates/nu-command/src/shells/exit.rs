@@ -19,6 +19,10 @@ impl Command for Exit {
             .category(Category::Shells)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Exit Nu."
     }
@@ -1,3 +1,4 @@
+use crate::Collation;
 use nu_engine::ClosureEval;
 use nu_protocol::{PipelineData, Record, ShellError, Span, Value, ast::CellPath};
 use nu_utils::IgnoreCaseExt;
@@ -18,13 +19,19 @@ pub enum Comparator {
 /// Sort has the following invariants, in order of precedence:
 /// - Null values (Nothing type) are always sorted to the end.
 /// - For natural sort, numeric values (numeric strings, ints, and floats) appear first, sorted by numeric value
-/// - Values appear by order of `Value`'s `PartialOrd`.
+/// - Strings are compared with `collation` if there's one, or by their code points otherwise.
+/// - Values appear by order of [`Value::total_cmp`], which orders even the values `PartialOrd` can't.
 /// - Sorting for values with equal ordering is stable.
 ///
 /// Generally, values of different types are ordered by order of appearance in the `Value` enum.
 /// However, this is not always the case. For example, ints and floats will be grouped together since
 /// `Value`'s `PartialOrd` defines a non-decreasing ordering between non-decreasing integers and floats.
-pub fn sort(vec: &mut [Value], insensitive: bool, natural: bool) -> Result<(), ShellError> {
+pub fn sort(
+    vec: &mut [Value],
+    insensitive: bool,
+    natural: bool,
+    collation: Option<Collation>,
+) -> Result<(), ShellError> {
     // allow the comparator function to indicate error
     // by mutating this option captured by the closure,
     // since sort_by closure must be infallible
@@ -36,7 +43,7 @@ pub fn sort(vec: &mut [Value], insensitive: bool, natural: bool) -> Result<(), S
             return Ordering::Equal;
         }
 
-        compare_values(a, b, insensitive, natural).unwrap_or_else(|err| {
+        compare_values(a, b, insensitive, natural, collation).unwrap_or_else(|err| {
             compare_err.get_or_insert(err);
             Ordering::Equal
         })
@@ -56,6 +63,7 @@ pub fn sort_by(
     head_span: Span,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
 ) -> Result<(), ShellError> {
     if comparators.is_empty() {
         return Err(ShellError::GenericError {
@@ -80,6 +88,7 @@ pub fn sort_by(
             head_span,
             insensitive,
             natural,
+            collation,
             &mut compare_err,
         )
     });
@@ -100,6 +109,7 @@ pub fn sort_record(
     reverse: bool,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
 ) -> Result<Record, ShellError> {
     let mut input_pairs: Vec<(String, Value)> = record.into_iter().collect();
 
@@ -115,13 +125,13 @@ pub fn sort_record(
                 return Ordering::Equal;
             }
 
-            compare_values(&a.1, &b.1, insensitive, natural).unwrap_or_else(|err| {
+            compare_values(&a.1, &b.1, insensitive, natural, collation).unwrap_or_else(|err| {
                 compare_err.get_or_insert(err);
                 Ordering::Equal
             })
         });
     } else {
-        input_pairs.sort_by(|a, b| compare_strings(&a.0, &b.0, insensitive, natural));
+        input_pairs.sort_by(|a, b| compare_strings(&a.0, &b.0, insensitive, natural, collation));
     };
 
     if let Some(err) = compare_err {
@@ -135,6 +145,7 @@ pub fn sort_record(
     Ok(input_pairs.into_iter().collect())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn compare_by(
     left: &Value,
     right: &Value,
@@ -142,6 +153,7 @@ pub fn compare_by(
     span: Span,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
     error: &mut Option<ShellError>,
 ) -> Ordering {
     // we've already hit an error, bail out now
@@ -151,10 +163,10 @@ pub fn compare_by(
     for cmp in comparators.iter_mut() {
         let result = match cmp {
             Comparator::CellPath(cell_path) => {
-                compare_cell_path(left, right, cell_path, insensitive, natural)
+                compare_cell_path(left, right, cell_path, insensitive, natural, collation)
             }
             Comparator::KeyClosure(closure) => {
-                compare_key_closure(left, right, closure, span, insensitive, natural)
+                compare_key_closure(left, right, closure, span, insensitive, natural, collation)
             }
             Comparator::CustomClosure(closure) => {
                 compare_custom_closure(left, right, closure, span)
@@ -199,6 +211,7 @@ pub fn compare_values(
     right: &Value,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
 ) -> Result<Ordering, ShellError> {
     if should_string_compare(left, right, natural) {
         Ok(compare_strings(
@@ -206,13 +219,20 @@ pub fn compare_values(
             &right.coerce_str()?,
             insensitive,
             natural,
+            collation,
         ))
     } else {
-        Ok(left.partial_cmp(right).unwrap_or(Ordering::Equal))
+        Ok(left.total_cmp(right))
     }
 }
 
-pub fn compare_strings(left: &str, right: &str, insensitive: bool, natural: bool) -> Ordering {
+pub fn compare_strings(
+    left: &str,
+    right: &str,
+    insensitive: bool,
+    natural: bool,
+    collation: Option<Collation>,
+) -> Ordering {
     fn compare_inner<T>(left: T, right: T, natural: bool) -> Ordering
     where
         T: AsRef<str> + Ord,
@@ -224,6 +244,14 @@ pub fn compare_strings(left: &str, right: &str, insensitive: bool, natural: bool
         }
     }
 
+    if let Some(collation) = collation {
+        return if insensitive {
+            collation.compare_ignore_case(left, right, natural)
+        } else {
+            collation.compare(left, right, natural)
+        };
+    }
+
     // only allocate a String if necessary for case folding
     if insensitive {
         compare_inner(left.to_folded_case(), right.to_folded_case(), natural)
@@ -238,10 +266,11 @@ pub fn compare_cell_path(
     cell_path: &CellPath,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
 ) -> Result<Ordering, ShellError> {
     let left = left.follow_cell_path(&cell_path.members)?;
     let right = right.follow_cell_path(&cell_path.members)?;
-    compare_values(&left, &right, insensitive, natural)
+    compare_values(&left, &right, insensitive, natural, collation)
 }
 
 pub fn compare_key_closure(
@@ -251,6 +280,7 @@ pub fn compare_key_closure(
     span: Span,
     insensitive: bool,
     natural: bool,
+    collation: Option<Collation>,
 ) -> Result<Ordering, ShellError> {
     let left_key = closure_eval
         .run_with_value(left.clone())?
@@ -258,7 +288,7 @@ pub fn compare_key_closure(
     let right_key = closure_eval
         .run_with_value(right.clone())?
         .into_value(span)?;
-    compare_values(&left_key, &right_key, insensitive, natural)
+    compare_values(&left_key, &right_key, insensitive, natural, collation)
 }
 
 pub fn compare_custom_closure(
@@ -28,6 +28,10 @@ impl Command for StorCreate {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Create a table in the in-memory sqlite database."
     }
@@ -29,6 +29,10 @@ impl Command for StorDelete {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Delete a table or specified rows in the in-memory sqlite database."
     }
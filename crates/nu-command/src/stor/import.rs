@@ -23,6 +23,10 @@ impl Command for StorImport {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Import a sqlite database file into the in-memory sqlite database."
     }
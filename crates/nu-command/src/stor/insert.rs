@@ -37,6 +37,10 @@ impl Command for StorInsert {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Insert information into a specified table in the in-memory sqlite database."
     }
@@ -20,6 +20,10 @@ impl Command for StorOpen {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Opens the in-memory sqlite database."
     }
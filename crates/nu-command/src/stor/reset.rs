@@ -17,6 +17,10 @@ impl Command for StorReset {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Reset the in-memory database by dropping all tables."
     }
@@ -42,6 +42,10 @@ impl Command for StorUpdate {
             .category(Category::Database)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Update information in a specified table in the in-memory sqlite database."
     }
@@ -553,6 +553,10 @@ impl Command for Ansi {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn is_const(&self) -> bool {
         true
     }
@@ -35,6 +35,10 @@ impl Command for AnsiLink {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Add a link (using OSC 8 escape sequence) to the given string."
     }
@@ -1,7 +1,9 @@
 mod ansi_;
 mod link;
+mod parse;
 mod strip;
 
 pub use ansi_::Ansi;
 pub use link::AnsiLink;
+pub use parse::AnsiParse;
 pub use strip::AnsiStrip;
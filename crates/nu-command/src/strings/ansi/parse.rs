@@ -0,0 +1,345 @@
+use nu_engine::command_prelude::*;
+
+#[derive(Clone)]
+pub struct AnsiParse;
+
+impl Command for AnsiParse {
+    fn name(&self) -> &str {
+        "ansi parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ansi parse")
+            .input_output_types(vec![(
+                Type::String,
+                Type::Table(
+                    [
+                        ("text".into(), Type::String),
+                        ("style".into(), Type::record()),
+                    ]
+                    .into(),
+                ),
+            )])
+            .category(Category::Platform)
+    }
+
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
+    fn description(&self) -> &str {
+        "Split a string with ANSI escape sequences into pieces of text with their style."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The styles are records like those which `ansi` takes, with only the `fg`, `bg` and `attr`
+which are set, so the text can be styled again with `ansi $piece.style`. Colors are names for the
+16 standard colors and `#rrggbb` otherwise. Escape sequences which don't change the style, like
+those which move the cursor, are dropped."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["color", "style", "sgr", "escape", "spans"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Parse colored text",
+                example: r#"$"(ansi red)error(ansi reset): (ansi { fg: '#00ff00' attr: b })ok" | ansi parse"#,
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "text" => Value::test_string("error"),
+                        "style" => Value::test_record(record! {
+                            "fg" => Value::test_string("red"),
+                        }),
+                    }),
+                    Value::test_record(record! {
+                        "text" => Value::test_string(": "),
+                        "style" => Value::test_record(record! {}),
+                    }),
+                    Value::test_record(record! {
+                        "text" => Value::test_string("ok"),
+                        "style" => Value::test_record(record! {
+                            "fg" => Value::test_string("#00ff00"),
+                            "attr" => Value::test_string("b"),
+                        }),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Keep only the red lines of the output of a command, with their colors",
+                example: r#"^cargo build --color always e>| lines | where { ansi parse | any { $in.style.fg? == red } }"#,
+                result: None,
+            },
+            Example {
+                description: "Style the pieces of text again after changing them",
+                example: r#"$"(ansi green)ok" | ansi parse | update text { str upcase } | each { $"(ansi $in.style)($in.text)(ansi reset)" } | str join"#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let (text, ..) = input.collect_string_strict(head)?;
+        let pieces = parse_ansi(&text)
+            .into_iter()
+            .map(|(text, style)| {
+                Value::record(
+                    record! {
+                        "text" => Value::string(text, head),
+                        "style" => style.into_value(head),
+                    },
+                    head,
+                )
+            })
+            .collect();
+        Ok(Value::list(pieces, head).into_pipeline_data())
+    }
+}
+
+/// The attributes in the order and with the letters of the `attr` of styles.
+const ATTRIBUTES: [char; 8] = ['l', 'b', 'd', 'h', 'i', 'r', 's', 'u'];
+
+#[derive(Clone, Default, PartialEq)]
+struct Style {
+    fg: Option<String>,
+    bg: Option<String>,
+    attributes: [bool; 8],
+}
+
+impl Style {
+    fn set(&mut self, attribute: char, on: bool) {
+        if let Some(i) = ATTRIBUTES.iter().position(|a| *a == attribute) {
+            self.attributes[i] = on;
+        }
+    }
+
+    /// Apply the parameters of an SGR sequence, `ESC [ ... m`.
+    fn apply(&mut self, params: &str) {
+        // Empty parameters, like in `ESC [ m`, are zeros
+        let mut params = params
+            .split([';', ':'])
+            .map(|param| param.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Style::default(),
+                1 => self.set('b', true),
+                2 => self.set('d', true),
+                3 => self.set('i', true),
+                4 | 21 => self.set('u', true),
+                5 | 6 => self.set('l', true),
+                7 => self.set('r', true),
+                8 => self.set('h', true),
+                9 => self.set('s', true),
+                22 => {
+                    self.set('b', false);
+                    self.set('d', false);
+                }
+                23 => self.set('i', false),
+                24 => self.set('u', false),
+                25 => self.set('l', false),
+                27 => self.set('r', false),
+                28 => self.set('h', false),
+                29 => self.set('s', false),
+                30..=37 => self.fg = Some(color_name(param - 30)),
+                90..=97 => self.fg = Some(color_name(param - 90 + 8)),
+                40..=47 => self.bg = Some(color_name(param - 40)),
+                100..=107 => self.bg = Some(color_name(param - 100 + 8)),
+                38 => self.fg = extended_color(&mut params),
+                48 => self.bg = extended_color(&mut params),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                _ => {}
+            }
+        }
+    }
+
+    fn into_value(self, span: Span) -> Value {
+        let mut record = Record::new();
+        if let Some(fg) = self.fg {
+            record.push("fg", Value::string(fg, span));
+        }
+        if let Some(bg) = self.bg {
+            record.push("bg", Value::string(bg, span));
+        }
+        let attr: String = ATTRIBUTES
+            .iter()
+            .zip(self.attributes)
+            .filter_map(|(letter, on)| on.then_some(*letter))
+            .collect();
+        if !attr.is_empty() {
+            record.push("attr", Value::string(attr, span));
+        }
+        Value::record(record, span)
+    }
+}
+
+/// The color of `38;5;n` or `38;2;r;g;b`, and the same for backgrounds.
+fn extended_color(params: &mut impl Iterator<Item = u8>) -> Option<String> {
+    match params.next()? {
+        5 => Some(indexed_color(params.next()?)),
+        2 => Some(rgb(params.next()?, params.next()?, params.next()?)),
+        _ => None,
+    }
+}
+
+/// The names of the 16 standard colors, in the same form as `$env.config.color_config`.
+fn color_name(index: u8) -> String {
+    const NAMES: [&str; 16] = [
+        "black",
+        "red",
+        "green",
+        "yellow",
+        "blue",
+        "purple",
+        "cyan",
+        "light_gray",
+        "dark_gray",
+        "light_red",
+        "light_green",
+        "light_yellow",
+        "light_blue",
+        "light_purple",
+        "light_cyan",
+        "white",
+    ];
+    NAMES[index as usize].into()
+}
+
+/// A color of the 256 color palette.
+fn indexed_color(index: u8) -> String {
+    match index {
+        0..=15 => color_name(index),
+        16..=231 => {
+            let level = |i: u8| if i == 0 { 0 } else { 55 + i * 40 };
+            let i = index - 16;
+            rgb(level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let grey = 8 + (index - 232) * 10;
+            rgb(grey, grey, grey)
+        }
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// The pieces of `text` with the same style. Empty pieces are left out and neighbors with the
+/// same style are merged.
+fn parse_ansi(text: &str) -> Vec<(String, Style)> {
+    let mut pieces: Vec<(String, Style)> = vec![];
+    let mut style = Style::default();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    let mut push = |current: &mut String, style: &Style| {
+        if current.is_empty() {
+            return;
+        }
+        match pieces.last_mut() {
+            Some((text, last)) if last == style => text.push_str(current),
+            _ => pieces.push((current.clone(), style.clone())),
+        }
+        current.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            current.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters, intermediate bytes and a final byte
+            Some('[') => {
+                let mut params = String::new();
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        if c == 'm' {
+                            push(&mut current, &style);
+                            style.apply(&params);
+                        }
+                        break;
+                    }
+                    params.push(c);
+                }
+            }
+            // OSC, like hyperlinks: ends with BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            // Other escape sequences are two characters
+            _ => {}
+        }
+    }
+    push(&mut current, &style);
+
+    pieces
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(AnsiParse {})
+    }
+
+    fn styles(text: &str) -> Vec<(String, Value)> {
+        parse_ansi(text)
+            .into_iter()
+            .map(|(text, style)| (text, style.into_value(Span::test_data())))
+            .collect()
+    }
+
+    #[test]
+    fn parses_extended_colors_and_resets() {
+        assert_eq!(
+            styles("\x1b[1;38;5;196mA\x1b[22;48;2;1;2;3mB\x1b[mC"),
+            vec![
+                (
+                    "A".into(),
+                    Value::test_record(record! {
+                        "fg" => Value::test_string("#ff0000"),
+                        "attr" => Value::test_string("b"),
+                    })
+                ),
+                (
+                    "B".into(),
+                    Value::test_record(record! {
+                        "fg" => Value::test_string("#ff0000"),
+                        "bg" => Value::test_string("#010203"),
+                    })
+                ),
+                ("C".into(), Value::test_record(record! {})),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_other_sequences_and_merges_pieces() {
+        assert_eq!(
+            styles("\x1b[31ma\x1b[2Kb\x1b]8;;https://nushell.sh\x1b\\c\x1b]8;;\x07\x1b[31md"),
+            vec![(
+                "abcd".into(),
+                Value::test_record(record! { "fg" => Value::test_string("red") })
+            )]
+        );
+    }
+}
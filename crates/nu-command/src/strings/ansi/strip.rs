@@ -1,12 +1,8 @@
-use std::sync::Arc;
-
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
-use nu_protocol::Config;
 
 struct Arguments {
     cell_paths: Option<Vec<CellPath>>,
-    config: Arc<Config>,
 }
 
 impl CmdArgument for Arguments {
@@ -40,10 +36,18 @@ impl Command for AnsiStrip {
             .category(Category::Platform)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Strip ANSI escape sequences from a string."
     }
 
+    fn extra_description(&self) -> &str {
+        "The strings in lists and records are stripped too. Other values are kept as they are."
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -53,8 +57,7 @@ impl Command for AnsiStrip {
     ) -> Result<PipelineData, ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
-        let config = stack.get_config(engine_state);
-        let args = Arguments { cell_paths, config };
+        let args = Arguments { cell_paths };
         operate(action, args, input, call.head, engine_state.signals())
     }
 
@@ -73,6 +76,14 @@ impl Command for AnsiStrip {
                     "exclamation" => Value::test_bool(false)
                 })),
             },
+            Example {
+                description: "Strip ANSI escape sequences from all the columns of a table",
+                example: r#"[[name size]; [$'(ansi green)nu' 10]] | ansi strip"#,
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "name" => Value::test_string("nu"),
+                    "size" => Value::test_int(10)
+                })])),
+            },
             Example {
                 description: "Strip ANSI escape sequences from multiple table columns",
                 example: r#"[[language feature]; [$'(ansi red)rust' $'(ansi i)safety']] | ansi strip language feature"#,
@@ -85,24 +96,33 @@ impl Command for AnsiStrip {
     }
 }
 
-fn action(input: &Value, args: &Arguments, _span: Span) -> Value {
+fn action(input: &Value, _args: &Arguments, _span: Span) -> Value {
+    strip_value(input)
+}
+
+/// Strip the escape sequences of a string, and of the strings nested in records and lists.
+fn strip_value(input: &Value) -> Value {
     let span = input.span();
     match input {
         Value::String { val, .. } => {
             Value::string(nu_utils::strip_ansi_likely(val).to_string(), span)
         }
-        other => {
-            // Fake stripping ansi for other types and just show the abbreviated string
-            // instead of showing an error message
-            Value::string(other.to_abbreviated_string(&args.config), span)
-        }
+        Value::Record { val, .. } => Value::record(
+            val.iter()
+                .map(|(col, val)| (col.clone(), strip_value(val)))
+                .collect(),
+            span,
+        ),
+        Value::List { vals, .. } => Value::list(vals.iter().map(strip_value).collect(), span),
+        // Numbers, dates and the like can't contain escape sequences, so they keep their type
+        other => other.clone(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{AnsiStrip, Arguments, action};
-    use nu_protocol::{Span, Value, engine::EngineState};
+    use nu_protocol::{Span, Value, record};
 
     #[test]
     fn examples_work_as_expected() {
@@ -119,10 +139,25 @@ mod tests {
 
         let args = Arguments {
             cell_paths: vec![].into(),
-            config: EngineState::new().get_config().clone(),
         };
 
         let actual = action(&input_string, &args, Span::test_data());
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_stripping_nested_values() {
+        let input = Value::test_record(record! {
+            "tags" => Value::test_list(vec![Value::test_string("\u{1b}[31mred\u{1b}[0m")]),
+            "count" => Value::test_int(1),
+        });
+        let expected = Value::test_record(record! {
+            "tags" => Value::test_list(vec![Value::test_string("red")]),
+            "count" => Value::test_int(1),
+        });
+
+        let args = Arguments { cell_paths: None };
+
+        assert_eq!(action(&input, &args, Span::test_data()), expected);
+    }
 }
@@ -34,6 +34,25 @@ impl Command for DetectColumns {
                 "detect columns by guessing width, it may be useful if default one doesn't work",
                 None,
             )
+            .switch(
+                "aligned",
+                "detect columns from the character positions which are blank in every line, for aligned output with spaces in its values",
+                Some('a'),
+            )
+            .named(
+                "widths",
+                SyntaxShape::OneOf(vec![
+                    SyntaxShape::List(Box::new(SyntaxShape::Int)),
+                    SyntaxShape::String,
+                ]),
+                "split each line into columns of these widths (a list or comma-separated string); the last column extends to the end of the line",
+                Some('w'),
+            )
+            .switch(
+                "infer-types",
+                "convert columns whose values all look like numbers, booleans, file sizes or dates",
+                Some('t'),
+            )
             .category(Category::Strings)
     }
 
@@ -41,6 +60,11 @@ impl Command for DetectColumns {
         "Attempt to automatically split text into multiple columns."
     }
 
+    fn extra_description(&self) -> &str {
+        r#"By default, lines are split on whitespace, and values are matched to the headers they overlap if a line has a different number of values than the header.
+For aligned output of Unix tools, --aligned places column boundaries where every line is blank, and --widths splits lines at fixed positions, so values may contain spaces. With --infer-types, columns are converted like by `detect type` if all their values have the same type; columns which also contain other values stay strings."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["split", "tabular"]
     }
@@ -85,6 +109,44 @@ none             8150224         4   8150220   1% /mnt/c' | detect columns --gue
                 example: "$'c1 c2 c3 c4 c5(char nl)a b c d e' | detect columns --combine-columns 2.. ",
                 result: None,
             },
+            Example {
+                description: "Split fixed-width columns, keeping spaces in values",
+                example: r"
+'NAME      STATUS   AGE
+web app   Running  5
+db        Pending  12' | detect columns --widths [10 9 3] --infer-types",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "NAME" => Value::test_string("web app"),
+                        "STATUS" => Value::test_string("Running"),
+                        "AGE" => Value::test_int(5),
+                    }),
+                    Value::test_record(record! {
+                        "NAME" => Value::test_string("db"),
+                        "STATUS" => Value::test_string("Pending"),
+                        "AGE" => Value::test_int(12),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Detect the columns of aligned output from the blank character positions",
+                example: r"
+'Filesystem     Size  Mounted on
+/dev/sda1      20G   /
+tmpfs          512M  /run/user' | detect columns --aligned",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "Filesystem" => Value::test_string("/dev/sda1"),
+                        "Size" => Value::test_string("20G"),
+                        "Mounted on" => Value::test_string("/"),
+                    }),
+                    Value::test_record(record! {
+                        "Filesystem" => Value::test_string("tmpfs"),
+                        "Size" => Value::test_string("512M"),
+                        "Mounted on" => Value::test_string("/run/user"),
+                    }),
+                ])),
+            },
             Example {
                 description: "Parse external ls command and combine columns for datetime",
                 example: "^ls -lh | detect columns --no-headers --skip 1 --combine-columns 5..7",
@@ -107,6 +169,8 @@ none             8150224         4   8150220   1% /mnt/c' | detect columns --gue
         let num_rows_to_skip: Option<usize> = call.get_flag(engine_state, stack, "skip")?;
         let noheader = call.has_flag(engine_state, stack, "no-headers")?;
         let range: Option<Range> = call.get_flag(engine_state, stack, "combine-columns")?;
+        let widths: Option<Value> = call.get_flag(engine_state, stack, "widths")?;
+        let infer_types = call.has_flag(engine_state, stack, "infer-types")?;
         let config = stack.get_config(engine_state);
 
         let args = Arguments {
@@ -116,10 +180,20 @@ none             8150224         4   8150220   1% /mnt/c' | detect columns --gue
             config,
         };
 
-        if call.has_flag(engine_state, stack, "guess")? {
+        let output = if let Some(widths) = widths {
+            fixed_width(engine_state, call, input, args, Some(parse_widths(widths)?))
+        } else if call.has_flag(engine_state, stack, "aligned")? {
+            fixed_width(engine_state, call, input, args, None)
+        } else if call.has_flag(engine_state, stack, "guess")? {
             guess_width(engine_state, call, input, args)
         } else {
             detect_columns(engine_state, call, input, args)
+        }?;
+
+        if infer_types {
+            infer_column_types(output, call.head)
+        } else {
+            Ok(output)
         }
     }
 
@@ -132,6 +206,8 @@ none             8150224         4   8150220   1% /mnt/c' | detect columns --gue
         let num_rows_to_skip: Option<usize> = call.get_flag_const(working_set, "skip")?;
         let noheader = call.has_flag_const(working_set, "no-headers")?;
         let range: Option<Range> = call.get_flag_const(working_set, "combine-columns")?;
+        let widths: Option<Value> = call.get_flag_const(working_set, "widths")?;
+        let infer_types = call.has_flag_const(working_set, "infer-types")?;
         let config = working_set.get_config().clone();
 
         let args = Arguments {
@@ -141,10 +217,21 @@ none             8150224         4   8150220   1% /mnt/c' | detect columns --gue
             config,
         };
 
-        if call.has_flag_const(working_set, "guess")? {
-            guess_width(working_set.permanent(), call, input, args)
+        let engine_state = working_set.permanent();
+        let output = if let Some(widths) = widths {
+            fixed_width(engine_state, call, input, args, Some(parse_widths(widths)?))
+        } else if call.has_flag_const(working_set, "aligned")? {
+            fixed_width(engine_state, call, input, args, None)
+        } else if call.has_flag_const(working_set, "guess")? {
+            guess_width(engine_state, call, input, args)
         } else {
-            detect_columns(working_set.permanent(), call, input, args)
+            detect_columns(engine_state, call, input, args)
+        }?;
+
+        if infer_types {
+            infer_column_types(output, call.head)
+        } else {
+            Ok(output)
         }
     }
 }
@@ -332,6 +419,244 @@ fn detect_columns(
     }
 }
 
+fn parse_widths(widths: Value) -> Result<Vec<usize>, ShellError> {
+    let span = widths.span();
+    let invalid = |actual: String| ShellError::InvalidValue {
+        valid: "positive column widths, as a list or separated by commas".into(),
+        actual,
+        span,
+    };
+    let widths = match widths {
+        Value::String { val, .. } => val
+            .split(',')
+            .map(|width| {
+                width
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| invalid(val.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Value::List { vals, .. } => vals
+            .into_iter()
+            .map(|width| {
+                let width = width.as_int()?;
+                usize::try_from(width).map_err(|_| invalid(width.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        value => {
+            return Err(ShellError::TypeMismatch {
+                err_message: format!("expected list<int> or string, got {}", value.get_type()),
+                span,
+            });
+        }
+    };
+
+    if widths.is_empty() || widths.contains(&0) {
+        return Err(invalid(format!("{widths:?}")));
+    }
+    Ok(widths)
+}
+
+/// Split aligned text into a table with typed columns, like `detect columns --aligned --infer-types`.
+pub(crate) fn detect_aligned_table(
+    engine_state: &EngineState,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let args = Arguments {
+        num_rows_to_skip: None,
+        noheader: false,
+        range: None,
+        config: engine_state.get_config().clone(),
+    };
+    let table = fixed_width(engine_state, call, input, args, None)?;
+    infer_column_types(table, call.head)
+}
+
+/// Find where the columns of aligned lines start, see [`aligned_column_starts`].
+pub(crate) fn aligned_columns(lines: &[&str]) -> Vec<usize> {
+    let lines: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    aligned_column_starts(&lines)
+}
+
+/// Split lines into columns at fixed character positions.
+///
+/// The positions are given by `widths`, or if there are none, placed between the runs of
+/// character positions which are blank in every line.
+fn fixed_width(
+    engine_state: &EngineState,
+    call: &Call,
+    input: PipelineData,
+    args: Arguments,
+    widths: Option<Vec<usize>>,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let input = input.collect_string("", &args.config)?;
+    let lines: Vec<Vec<char>> = input
+        .lines()
+        .skip(args.num_rows_to_skip.unwrap_or_default())
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+
+    let starts = match widths {
+        Some(widths) => widths
+            .iter()
+            .scan(0, |start, width| {
+                let column_start = *start;
+                *start += width;
+                Some(column_start)
+            })
+            .collect(),
+        None => aligned_column_starts(&lines),
+    };
+
+    let mut rows = lines.iter().map(|line| split_at_starts(line, &starts));
+    let headers: Vec<String> = if args.noheader {
+        (0..starts.len()).map(|i| format!("column{i}")).collect()
+    } else {
+        match rows.next() {
+            Some(header) => header
+                .into_iter()
+                .enumerate()
+                .map(|(i, name)| name.unwrap_or_else(|| format!("column{i}")))
+                .collect(),
+            None => return Ok(PipelineData::empty()),
+        }
+    };
+
+    if headers.iter().duplicates().next().is_some() {
+        return Err(ShellError::ColumnDetectionFailure {
+            bad_value: head,
+            failure_site: head,
+        });
+    }
+
+    Ok(rows
+        .map(|row| {
+            let record = headers
+                .iter()
+                .cloned()
+                .zip(row)
+                .map(|(header, value)| {
+                    let value = value
+                        .map(|value| Value::string(value, head))
+                        .unwrap_or_else(|| Value::nothing(head));
+                    (header, value)
+                })
+                .collect();
+            match &args.range {
+                Some(range) => merge_record(record, range, head),
+                None => Value::record(record, head),
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_pipeline_data(head, engine_state.signals().clone()))
+}
+
+/// Find where columns start, which is wherever a run of non-blank character positions starts.
+fn aligned_column_starts(lines: &[Vec<char>]) -> Vec<usize> {
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let blank: Vec<bool> = (0..width)
+        .map(|i| {
+            lines
+                .iter()
+                .all(|line| line.get(i).is_none_or(|c| c.is_whitespace()))
+        })
+        .collect();
+
+    (0..width)
+        .filter(|&i| !blank[i] && (i == 0 || blank[i - 1]))
+        .collect()
+}
+
+/// Split a line into the trimmed text between `starts`, where empty columns are `None`.
+fn split_at_starts(line: &[char], starts: &[usize]) -> Vec<Option<String>> {
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(line.len());
+            let start = start.min(line.len());
+            let end = end.clamp(start, line.len());
+            let text: String = line[start..end].iter().collect();
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_string())
+        })
+        .collect()
+}
+
+/// Convert the columns of a table whose values all have the same type, as detected by `detect type`.
+fn infer_column_types(output: PipelineData, head: Span) -> Result<PipelineData, ShellError> {
+    let mut rows = match output.into_value(head)? {
+        Value::List { vals, .. } => vals,
+        value => return Ok(value.into_pipeline_data()),
+    };
+    let columns: Vec<String> = rows
+        .iter()
+        .filter_map(|row| row.as_record().ok())
+        .flat_map(|record| record.columns().cloned())
+        .unique()
+        .collect();
+
+    for column in columns {
+        let mut converted = Vec::with_capacity(rows.len());
+        let mut column_type = None;
+        let mut consistent = true;
+        for row in &rows {
+            let value = match row.as_record().ok().and_then(|record| record.get(&column)) {
+                Some(value @ Value::String { .. }) => {
+                    let span = value.span();
+                    let detected = super::detect_type::process(value.clone(), false, span)?;
+                    let detected = if matches!(detected, Value::String { .. }) {
+                        infer_filesize(value, span).unwrap_or(detected)
+                    } else {
+                        detected
+                    };
+                    Some(detected)
+                }
+                _ => None,
+            };
+            if let Some(value) = &value {
+                let ty = value.get_type();
+                if ty == Type::String || column_type.as_ref().is_some_and(|t| *t != ty) {
+                    consistent = false;
+                    break;
+                }
+                column_type = Some(ty);
+            }
+            converted.push(value);
+        }
+
+        if !consistent || column_type.is_none() {
+            continue;
+        }
+        for (row, value) in rows.iter_mut().zip(converted) {
+            if let (Value::Record { val, .. }, Some(value)) = (row, value) {
+                val.to_mut().insert(&column, value);
+            }
+        }
+    }
+
+    Ok(Value::list(rows, head).into_pipeline_data())
+}
+
+/// File sizes like `4.0K` or `512M` in the output of Unix tools.
+fn infer_filesize(value: &Value, span: Span) -> Option<Value> {
+    let text = value.as_str().ok()?.trim();
+    let unit_start = text.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    if unit_start == 0 || !text[unit_start..].chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let text = match &text[unit_start..] {
+        unit @ ("K" | "M" | "G" | "T" | "P") => format!("{}{unit}iB", &text[..unit_start]),
+        _ => text.to_string(),
+    };
+    crate::conversions::into::filesize_from_string(&text, span)
+        .ok()
+        .map(|size| Value::filesize(size, span))
+}
+
 pub fn find_columns(input: &str) -> Vec<Spanned<String>> {
     let mut chars = input.char_indices().peekable();
     let mut output = vec![];
@@ -127,7 +127,11 @@ impl Command for DetectType {
 
 // This function will check if a value matches a regular expression for a particular datatype.
 // If it does, it will convert the value to that datatype.
-fn process(val: Value, display_as_filesize: bool, span: Span) -> Result<Value, ShellError> {
+pub(super) fn process(
+    val: Value,
+    display_as_filesize: bool,
+    span: Span,
+) -> Result<Value, ShellError> {
     // step 1: convert value to string
     let val_str = val.coerce_str().unwrap_or_default();
 
@@ -1,11 +1,13 @@
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::command_prelude::*;
 use nu_protocol::{
-    FilesizeFormatter, FilesizeUnit, SUPPORTED_FILESIZE_UNITS, engine::StateWorkingSet,
+    FilesizeFormatter, FilesizeUnitFormat, SUPPORTED_FILESIZE_UNITS, ast::PathMember,
+    casing::Casing, engine::StateWorkingSet,
 };
 
 struct Arguments {
-    unit: FilesizeUnit,
+    unit: FilesizeUnitFormat,
+    precision: Option<usize>,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -31,16 +33,28 @@ impl Command for FormatFilesize {
                 (Type::record(), Type::record()),
             ])
             .allow_variants_without_examples(true)
-            .required(
+            .optional(
                 "format value",
                 SyntaxShape::String,
-                "The format into which convert the file sizes.",
+                "The unit into which convert the file sizes, or a column with --unit.",
             )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
                 "For a data structure input, format filesizes at the given cell paths.",
             )
+            .named(
+                "unit",
+                SyntaxShape::String,
+                "the unit, or 'metric' or 'binary' for the largest fitting one of each size",
+                Some('u'),
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "the number of decimals",
+                Some('p'),
+            )
             .category(Category::Strings)
     }
 
@@ -48,6 +62,15 @@ impl Command for FormatFilesize {
         "Converts a column of filesizes to some specified format."
     }
 
+    fn extra_description(&self) -> &str {
+        r#"The unit is either the first argument, or --unit so that all the arguments are cell paths.
+Without a unit, the one of `$env.config.filesize.unit` is used. Without cell paths, all the file
+sizes in a table or record are formatted, and other values are kept.
+
+File sizes only hold a number of bytes, so the unit isn't kept through arithmetic. Format the
+result instead, like `1MiB + 512KiB | format filesize MiB`."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["convert", "display", "pattern", "human readable"]
     }
@@ -63,10 +86,13 @@ impl Command for FormatFilesize {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let unit = parse_filesize_unit(call.req::<Spanned<String>>(engine_state, stack, 0)?)?;
-        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
-        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
-        let arg = Arguments { unit, cell_paths };
+        let arg = arguments(
+            call.opt(engine_state, stack, 0)?,
+            call.rest(engine_state, stack, 1)?,
+            call.get_flag(engine_state, stack, "unit")?,
+            call.get_flag(engine_state, stack, "precision")?,
+            stack.get_config(engine_state).filesize.unit,
+        )?;
         operate(
             format_value_impl,
             arg,
@@ -82,10 +108,13 @@ impl Command for FormatFilesize {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let unit = parse_filesize_unit(call.req_const::<Spanned<String>>(working_set, 0)?)?;
-        let cell_paths: Vec<CellPath> = call.rest_const(working_set, 1)?;
-        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
-        let arg = Arguments { unit, cell_paths };
+        let arg = arguments(
+            call.opt_const(working_set, 0)?,
+            call.rest_const(working_set, 1)?,
+            call.get_flag_const(working_set, "unit")?,
+            call.get_flag_const(working_set, "precision")?,
+            working_set.get_config().filesize.unit,
+        )?;
         operate(
             format_value_impl,
             arg,
@@ -112,11 +141,69 @@ impl Command for FormatFilesize {
                 example: "4GB | format filesize MB",
                 result: Some(Value::test_string("4000 MB")),
             },
+            Example {
+                description: "Convert all the file sizes of a table to MiB with one decimal",
+                example: "[[name size]; [a 1MB] [b 3MiB]] | format filesize --unit MiB --precision 1",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "name" => Value::test_string("a"),
+                        "size" => Value::test_string("0.9 MiB"),
+                    }),
+                    Value::test_record(record! {
+                        "name" => Value::test_string("b"),
+                        "size" => Value::test_string("3.0 MiB"),
+                    }),
+                ])),
+            },
+            Example {
+                description: "Convert the size column to the largest fitting binary units",
+                example: "ls | format filesize --unit binary size",
+                result: None,
+            },
         ]
     }
 }
 
-fn parse_filesize_unit(format: Spanned<String>) -> Result<FilesizeUnit, ShellError> {
+fn arguments(
+    format: Option<Spanned<String>>,
+    mut cell_paths: Vec<CellPath>,
+    unit: Option<Spanned<String>>,
+    precision: Option<Spanned<i64>>,
+    default_unit: FilesizeUnitFormat,
+) -> Result<Arguments, ShellError> {
+    let unit = match (unit, format) {
+        (Some(unit), format) => {
+            // With --unit, the first argument is a column
+            if let Some(column) = format {
+                let member = PathMember::string(column.item, false, Casing::Sensitive, column.span);
+                cell_paths.insert(
+                    0,
+                    CellPath {
+                        members: vec![member],
+                    },
+                );
+            }
+            parse_filesize_unit(unit)?
+        }
+        (None, Some(format)) => parse_filesize_unit(format)?,
+        (None, None) => default_unit,
+    };
+    let precision = precision
+        .map(|precision| {
+            usize::try_from(precision.item).map_err(|_| ShellError::NeedsPositiveValue {
+                span: precision.span,
+            })
+        })
+        .transpose()?;
+    let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+    Ok(Arguments {
+        unit,
+        precision,
+        cell_paths,
+    })
+}
+
+fn parse_filesize_unit(format: Spanned<String>) -> Result<FilesizeUnitFormat, ShellError> {
     format.item.parse().map_err(|_| ShellError::InvalidUnit {
         supported_units: SUPPORTED_FILESIZE_UNITS.join(", "),
         span: format.span,
@@ -128,10 +215,25 @@ fn format_value_impl(val: &Value, arg: &Arguments, span: Span) -> Value {
     match val {
         Value::Filesize { val, .. } => FilesizeFormatter::new()
             .unit(arg.unit)
+            .precision(arg.precision)
             .format(*val)
             .to_string()
             .into_value(span),
         Value::Error { .. } => val.clone(),
+        // Without cell paths, tables and records are formatted as a whole
+        Value::Record { val: record, .. } if arg.cell_paths.is_none() => Value::record(
+            record
+                .iter()
+                .map(|(col, val)| (col.clone(), format_nested(val, arg, span)))
+                .collect(),
+            value_span,
+        ),
+        Value::List { vals, .. } if arg.cell_paths.is_none() => Value::list(
+            vals.iter()
+                .map(|val| format_nested(val, arg, span))
+                .collect(),
+            value_span,
+        ),
         _ => Value::error(
             ShellError::OnlySupportsThisInputType {
                 exp_input_type: "filesize".into(),
@@ -144,6 +246,16 @@ fn format_value_impl(val: &Value, arg: &Arguments, span: Span) -> Value {
     }
 }
 
+/// Format the file sizes in a value of a table or record, and keep the other values.
+fn format_nested(val: &Value, arg: &Arguments, span: Span) -> Value {
+    match val {
+        Value::Filesize { .. } | Value::Record { .. } | Value::List { .. } => {
+            format_value_impl(val, arg, span)
+        }
+        _ => val.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
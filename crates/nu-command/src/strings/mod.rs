@@ -8,10 +8,15 @@ mod encode_decode;
 mod format;
 mod guess_width;
 mod parse;
+mod render;
 mod split;
 mod str_;
+#[cfg(feature = "uuid")]
+mod uuid;
 
-pub use ansi::{Ansi, AnsiLink, AnsiStrip};
+#[cfg(feature = "uuid")]
+pub use self::uuid::{Ulid, UlidParse, Uuid, UuidParse, UuidValidate};
+pub use ansi::{Ansi, AnsiLink, AnsiParse, AnsiStrip};
 pub use base::{
     DecodeBase32, DecodeBase32Hex, DecodeBase64, DecodeHex, EncodeBase32, EncodeBase32Hex,
     EncodeBase64, EncodeHex,
@@ -23,12 +28,13 @@ pub use detect_type::*;
 pub use encode_decode::*;
 pub use format::*;
 pub use parse::*;
+pub use render::Render;
 pub use split::*;
 pub use str_::*;
 
 use nu_engine::CallExt;
 use nu_protocol::{
-    ShellError,
+    Record, ShellError, Span, Value,
     engine::{Call, EngineState, Stack, StateWorkingSet},
 };
 
@@ -61,8 +67,14 @@ pub fn grapheme_flags(
             span: call.head,
         })?
     }
-    // Grapheme cluster usage is decided by the non-default -g flag
-    Ok(g_flag)
+    // Grapheme cluster usage is decided by the -g flag, or by the config if no unit was given
+    if g_flag {
+        return Ok(true);
+    }
+    let other_flag = call.has_flag(engine_state, stack, "utf-8-bytes")?
+        || call.has_flag(engine_state, stack, "code-points")?
+        || call.has_flag(engine_state, stack, "chars")?;
+    Ok(!other_flag && stack.get_config(engine_state).strings.grapheme_clusters)
 }
 
 // Const version of grapheme_flags
@@ -83,5 +95,44 @@ pub fn grapheme_flags_const(
             span: call.head,
         })?
     }
-    Ok(g_flag)
+    if g_flag {
+        return Ok(true);
+    }
+    let other_flag = call.has_flag_const(working_set, "utf-8-bytes")?
+        || call.has_flag_const(working_set, "code-points")?
+        || call.has_flag_const(working_set, "chars")?;
+    Ok(!other_flag && working_set.get_config().strings.grapheme_clusters)
+}
+
+/// The column names for the capture groups of `regex`, like in `parse`: named groups keep their
+/// name and unnamed groups are called `capture0`, `capture1`, and so on.
+pub(crate) fn capture_column_names(regex: &fancy_regex::Regex) -> Vec<String> {
+    regex
+        .capture_names()
+        .skip(1)
+        .enumerate()
+        .map(|(i, name)| {
+            name.map(String::from)
+                .unwrap_or_else(|| format!("capture{i}"))
+        })
+        .collect()
+}
+
+/// The capture groups of a match as a record with the given `columns`, see
+/// [`capture_column_names`]. Groups which didn't take part in the match are `nothing`.
+pub(crate) fn captures_record(
+    columns: &[String],
+    captures: &fancy_regex::Captures,
+    span: Span,
+) -> Record {
+    columns
+        .iter()
+        .zip(captures.iter().skip(1))
+        .map(|(column, group)| {
+            let value = group
+                .map(|group| Value::string(group.as_str(), span))
+                .unwrap_or(Value::nothing(span));
+            (column.clone(), value)
+        })
+        .collect()
 }
@@ -1,7 +1,13 @@
+use crate::{
+    conversions::into::{
+        compound_to_duration, filesize_from_string, int_from_string, strict_string_to_boolean,
+    },
+    parse_date_from_string,
+};
 use fancy_regex::{Captures, Regex, RegexBuilder};
 use nu_engine::command_prelude::*;
 use nu_protocol::{ListStream, Signals, engine::StateWorkingSet};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 #[derive(Clone)]
 pub struct Parse;
@@ -20,7 +26,11 @@ impl Command for Parse {
     }
 
     fn extra_description(&self) -> &str {
-        "The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression."
+        r#"The parse command always uses regular expressions even when you use a simple pattern. If a simple pattern is supplied, parse will transform that pattern into a regular expression.
+
+The columns of a simple pattern can be annotated with a type, like `{size:filesize}`, to convert the captured text into that type. The supported types are int, float, bool, filesize, duration, datetime and string.
+
+Normally, each line of the input is matched separately. With --multiline, the pattern is matched against the whole input instead, so a pattern spanning multiple lines can extract a record from every block of lines matching it. Each line of a simple pattern then has to match whole lines of the input."#
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -37,6 +47,11 @@ impl Command for Parse {
                 "set the max backtrack limit for regex",
                 Some('b'),
             )
+            .switch(
+                "multiline",
+                "match the pattern against the whole input instead of each line, so it can span multiple lines",
+                Some('m'),
+            )
             .allow_variants_without_examples(true)
             .category(Category::Strings)
     }
@@ -102,6 +117,29 @@ impl Command for Parse {
                     "capture0" => Value::test_string("b"),
                 })])),
             },
+            Example {
+                description: "Parse a string into typed columns",
+                example: "\"README.md 2kB 48\" | parse \"{name} {size:filesize} {lines:int}\"",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "name" => Value::test_string("README.md"),
+                    "size" => Value::test_filesize(2000),
+                    "lines" => Value::test_int(48),
+                })])),
+            },
+            Example {
+                description: "Parse records spanning multiple lines",
+                example: "\"id: 1\\nuser: alice\\n\\nid: 2\\nuser: bob\\n\" | parse --multiline \"id: {id:int}\\nuser: {user}\"",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "id" => Value::test_int(1),
+                        "user" => Value::test_string("alice"),
+                    }),
+                    Value::test_record(record! {
+                        "id" => Value::test_int(2),
+                        "user" => Value::test_string("bob"),
+                    }),
+                ])),
+            },
             Example {
                 description: "Parse a string with a manually set fancy-regex backtrack limit",
                 example: "\"hi there\" | parse --backtrack 1500000 \"{foo} {bar}\"",
@@ -129,7 +167,16 @@ impl Command for Parse {
         let backtrack_limit: usize = call
             .get_flag(engine_state, stack, "backtrack")?
             .unwrap_or(1_000_000); // 1_000_000 is fancy_regex default
-        operate(engine_state, pattern, regex, backtrack_limit, call, input)
+        let multiline = call.has_flag(engine_state, stack, "multiline")?;
+        operate(
+            engine_state,
+            pattern,
+            regex,
+            backtrack_limit,
+            multiline,
+            call,
+            input,
+        )
     }
 
     fn run_const(
@@ -143,11 +190,13 @@ impl Command for Parse {
         let backtrack_limit: usize = call
             .get_flag_const(working_set, "backtrack")?
             .unwrap_or(1_000_000);
+        let multiline = call.has_flag_const(working_set, "multiline")?;
         operate(
             working_set.permanent(),
             pattern,
             regex,
             backtrack_limit,
+            multiline,
             call,
             input,
         )
@@ -159,6 +208,7 @@ fn operate(
     pattern: Spanned<String>,
     regex: bool,
     backtrack_limit: usize,
+    multiline: bool,
     call: &Call,
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
@@ -167,10 +217,10 @@ fn operate(
     let pattern_item = pattern.item;
     let pattern_span = pattern.span;
 
-    let item_to_parse = if regex {
-        pattern_item
+    let (item_to_parse, types) = if regex {
+        (pattern_item, HashMap::new())
     } else {
-        build_regex(&pattern_item, pattern_span)?
+        build_regex(&pattern_item, multiline, pattern_span)?
     };
 
     let regex = RegexBuilder::new(&item_to_parse)
@@ -189,11 +239,24 @@ fn operate(
         .skip(1)
         .enumerate()
         .map(|(i, name)| {
-            name.map(String::from)
-                .unwrap_or_else(|| format!("capture{i}"))
+            let name = name
+                .map(String::from)
+                .unwrap_or_else(|| format!("capture{i}"));
+            let ty = types.get(&name).copied();
+            Column { name, ty }
         })
         .collect::<Vec<_>>();
 
+    if multiline {
+        let text = collect_text(input, head)?;
+        let captures = regex
+            .captures_iter(&text)
+            .map(|captures| captures_to_value(captures, &columns, head))
+            .collect::<Result<_, _>>()?;
+
+        return Ok(Value::list(captures, head).into_pipeline_data());
+    }
+
     match input {
         PipelineData::Empty => Ok(PipelineData::empty()),
         PipelineData::Value(value, ..) => match value {
@@ -276,8 +339,102 @@ fn operate(
     }
 }
 
-fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
-    let mut output = "(?s)\\A".to_string();
+/// The type a column of a simple pattern is converted into.
+#[derive(Clone, Copy)]
+enum CaptureType {
+    Int,
+    Float,
+    Bool,
+    Filesize,
+    Duration,
+    Datetime,
+    String,
+}
+
+impl CaptureType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(Self::Int),
+            "float" => Some(Self::Float),
+            "bool" => Some(Self::Bool),
+            "filesize" => Some(Self::Filesize),
+            "duration" => Some(Self::Duration),
+            "datetime" | "date" => Some(Self::Datetime),
+            "string" => Some(Self::String),
+            _ => None,
+        }
+    }
+
+    fn convert(self, text: &str, span: Span) -> Result<Value, ShellError> {
+        let cant_convert = |to_type: &str| ShellError::CantConvert {
+            to_type: to_type.into(),
+            from_type: "string".into(),
+            span,
+            help: Some(format!("the captured text was '{text}'")),
+        };
+        Ok(match self {
+            Self::Int => Value::int(int_from_string(text, span)?, span),
+            Self::Float => Value::float(
+                text.trim().parse().map_err(|_| cant_convert("float"))?,
+                span,
+            ),
+            Self::Bool => Value::bool(strict_string_to_boolean(text, span)?, span),
+            Self::Filesize => Value::filesize(filesize_from_string(text, span)?, span),
+            Self::Duration => Value::duration(compound_to_duration(text.trim(), span)?, span),
+            Self::Datetime => match parse_date_from_string(text.trim(), span) {
+                Ok(date) => Value::date(date, span),
+                Err(_) => return Err(cant_convert("datetime")),
+            },
+            Self::String => Value::string(text, span),
+        })
+    }
+}
+
+struct Column {
+    name: String,
+    ty: Option<CaptureType>,
+}
+
+/// Collect the whole input into one string, to match a multi-line pattern against it.
+fn collect_text(input: PipelineData, head: Span) -> Result<String, ShellError> {
+    match input {
+        PipelineData::Empty => Ok(String::new()),
+        PipelineData::ByteStream(stream, ..) => stream.into_string(),
+        input => {
+            let mut lines = vec![];
+            for value in input {
+                let span = value.span();
+                match value {
+                    Value::String { val, .. } => lines.push(val),
+                    Value::Error { error, .. } => return Err(*error),
+                    value => {
+                        return Err(ShellError::OnlySupportsThisInputType {
+                            exp_input_type: "string".into(),
+                            wrong_type: value.get_type().to_string(),
+                            dst_span: head,
+                            src_span: span,
+                        });
+                    }
+                }
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+/// Turn a simple pattern into a regular expression, and return the types of the typed columns.
+fn build_regex(
+    input: &str,
+    multiline: bool,
+    span: Span,
+) -> Result<(String, HashMap<String, CaptureType>), ShellError> {
+    // in multi-line mode, columns can't span lines and the pattern matches whole lines
+    let mut output = if multiline {
+        "(?m)^".to_string()
+    } else {
+        "(?s)\\A".to_string()
+    };
+    let mut types = HashMap::new();
 
     let mut loop_input = input.chars().peekable();
     loop {
@@ -314,6 +471,20 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
             }
         }
 
+        if let Some((name, ty)) = column.rsplit_once(':') {
+            let ty = CaptureType::from_name(ty.trim()).ok_or_else(|| ShellError::IncorrectValue {
+                msg: format!(
+                    "unknown type `{ty}` for column `{name}`, expected int, float, bool, filesize, duration, datetime or string"
+                ),
+                val_span: span,
+                call_span: span,
+            })?;
+            column = name.to_string();
+            if column != "_" {
+                types.insert(column.clone(), ty);
+            }
+        }
+
         if !column.is_empty() {
             output.push_str("(?");
             if column == "_" {
@@ -333,14 +504,14 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
         }
     }
 
-    output.push_str("\\z");
-    Ok(output)
+    output.push_str(if multiline { "$" } else { "\\z" });
+    Ok((output, types))
 }
 
 struct ParseIter<I: Iterator<Item = Result<String, ShellError>>> {
     captures: VecDeque<Value>,
     regex: Regex,
-    columns: Vec<String>,
+    columns: Vec<Column>,
     iter: I,
     span: Span,
     signals: Signals,
@@ -383,7 +554,7 @@ impl<I: Iterator<Item = Result<String, ShellError>>> Iterator for ParseIter<I> {
 
 fn captures_to_value(
     captures: Result<Captures, fancy_regex::Error>,
-    columns: &[String],
+    columns: &[Column],
     span: Span,
 ) -> Result<Value, ShellError> {
     let captures = captures.map_err(|err| ShellError::GenericError {
@@ -398,12 +569,14 @@ fn captures_to_value(
         .iter()
         .zip(captures.iter().skip(1))
         .map(|(column, match_)| {
-            let match_value = match_
-                .map(|m| Value::string(m.as_str(), span))
-                .unwrap_or(Value::nothing(span));
-            (column.clone(), match_value)
+            let match_value = match (match_, column.ty) {
+                (Some(m), Some(ty)) => ty.convert(m.as_str(), span)?,
+                (Some(m), None) => Value::string(m.as_str(), span),
+                (None, _) => Value::nothing(span),
+            };
+            Ok((column.name.clone(), match_value))
         })
-        .collect();
+        .collect::<Result<_, ShellError>>()?;
 
     Ok(Value::record(record, span))
 }
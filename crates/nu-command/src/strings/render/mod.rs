@@ -0,0 +1,121 @@
+mod template;
+
+use nu_engine::command_prelude::*;
+use nu_protocol::shell_error::io::IoError;
+use template::Template;
+
+#[derive(Clone)]
+pub struct Render;
+
+impl Command for Render {
+    fn name(&self) -> &str {
+        "render"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("render")
+            .input_output_types(vec![(Type::Any, Type::String)])
+            .required(
+                "template",
+                SyntaxShape::String,
+                "The template file, or the template itself with --inline.",
+            )
+            .switch(
+                "inline",
+                "use the argument as the template instead of as the path of a file",
+                Some('i'),
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Render a Jinja-like template with the input as its context."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"The fields of a record input are variables of the template, and the whole input is also
+the variable `input`, so a table can be looped over with `{% for row in input %}`.
+
+  {{ value }}                      insert a value, fields are accessed with `value.field.0`
+  {% if value %}, {% elif value %}, {% else %}, {% endif %}
+  {% for item in list %}           `item` and `loop.index`, `loop.first` and `loop.last`
+  {% for key, value in record %}   {% else %} for empty loops, {% endfor %}
+  {% set name = value %}
+  {# comment #}                    `{%-` and `-%}` trim the whitespace before or after a tag
+
+Values can be compared with `==`, `!=`, `<`, `>`, `<=`, `>=` and `in`, combined with `and`, `or`
+and `not`, and joined as text with `~`. Filters are applied with `|`:
+  upper, lower, trim, capitalize, length, first, last, join(separator), round(precision),
+  default(value), date(format) with the format of `format date`, and filesize(unit) which
+  shows file sizes like `$env.config.filesize` without a unit."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["template", "jinja", "tera", "report", "generate"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Render a template with the fields of a record",
+                example: r#"{name: nu, stars: 3} | render --inline "{{ name | upper }} has {{ stars }} stars""#,
+                result: Some(Value::test_string("NU has 3 stars")),
+            },
+            Example {
+                description: "Render a list from a table",
+                example: r#"[[name size]; [a 1kB] [b 2MB]] | render -i "{% for file in input %}{{ file.name }}: {{ file.size | filesize('kB') }}{% if not loop.last %}, {% endif %}{% endfor %}""#,
+                result: Some(Value::test_string("a: 1 kB, b: 2000 kB")),
+            },
+            Example {
+                description: "Generate a report with a template file",
+                example: "ls | render report.html.j2 | save report.html",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let template: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let inline = call.has_flag(engine_state, stack, "inline")?;
+
+        let source = if inline {
+            template.item
+        } else {
+            let cwd = engine_state.cwd(Some(stack))?;
+            let path = nu_path::expand_path_with(&template.item, &cwd, true);
+            std::fs::read_to_string(&path).map_err(|err| IoError::new(err, template.span, path))?
+        };
+
+        let context = input.into_value(head)?;
+        let config = stack.get_config(engine_state);
+        Template::parse(&source)
+            .and_then(|parsed| parsed.render(&context, &config, head))
+            .map(|rendered| Value::string(rendered, head).into_pipeline_data())
+            .map_err(|err| ShellError::GenericError {
+                error: "Couldn't render template".into(),
+                msg: format!("line {}: {}", err.line, err.msg),
+                span: Some(template.span),
+                help: None,
+                inner: vec![],
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Render {})
+    }
+}
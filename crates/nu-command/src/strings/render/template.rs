@@ -0,0 +1,869 @@
+//! A small Jinja-like template language.
+//!
+//! Templates have `{{ expression }}` substitutions, `{% if %}`, `{% for %}` and `{% set %}` tags
+//! and `{# comments #}`. A `-` at the inside of a delimiter, like `{%- endfor %}`, trims the
+//! whitespace on that side of it.
+
+use chrono::format::{Item, StrftimeItems};
+use nu_protocol::{Config, Filesize, FilesizeFormatter, FilesizeUnit, Record, Span, Value};
+use std::cmp::Ordering;
+
+/// An error in a template, with the line where it happened.
+#[derive(Debug)]
+pub(super) struct TemplateError {
+    pub msg: String,
+    pub line: usize,
+}
+
+type Result<T> = std::result::Result<T, TemplateError>;
+
+fn error<T>(msg: impl Into<String>, line: usize) -> Result<T> {
+    Err(TemplateError {
+        msg: msg.into(),
+        line,
+    })
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Expr(&'a str, usize),
+    Tag(&'a str, usize),
+}
+
+enum Node {
+    Text(String),
+    Expr(Expr, usize),
+    If(Vec<(Expr, Vec<Node>)>, Vec<Node>, usize),
+    For {
+        vars: Vec<String>,
+        iterable: Expr,
+        body: Vec<Node>,
+        empty: Vec<Node>,
+        line: usize,
+    },
+    Set(String, Expr, usize),
+}
+
+enum Expr {
+    Literal(Value),
+    Path(String, Vec<PathPart>),
+    Filter(Box<Expr>, String, Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, &'static str, Box<Expr>),
+    Concat(Box<Expr>, Box<Expr>),
+}
+
+enum PathPart {
+    Key(String),
+    Index(usize),
+}
+
+/// A parsed template.
+pub(super) struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut tokens = tokenize(source)?.into_iter();
+        let (nodes, end) = parse_nodes(&mut tokens, &[])?;
+        if let Some((tag, line)) = end {
+            return error(format!("unexpected `{tag}`"), line);
+        }
+        Ok(Template { nodes })
+    }
+
+    /// Render the template with the fields of `context` and the whole `input` as variables.
+    pub fn render(&self, context: &Value, config: &Config, span: Span) -> Result<String> {
+        let mut vars = vec![("input".to_string(), context.clone())];
+        if let Value::Record { val, .. } = context {
+            vars.extend(
+                val.iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        }
+        let mut renderer = Renderer { vars, config, span };
+        let mut out = String::new();
+        renderer.render(&self.nodes, &mut out)?;
+        Ok(out)
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut pos = 0;
+    let mut trim_next = false;
+
+    loop {
+        let rest = &source[pos..];
+        let start = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| rest.find(open))
+            .min();
+        let text = &rest[..start.unwrap_or(rest.len())];
+        let text = if trim_next { text.trim_start() } else { text };
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        let Some(start) = start else {
+            break;
+        };
+
+        let line = source[..pos + start].matches('\n').count() + 1;
+        let (open, close) = match &rest[start..start + 2] {
+            "{{" => ("{{", "}}"),
+            "{%" => ("{%", "%}"),
+            _ => ("{#", "#}"),
+        };
+        let inner_start = start + 2;
+        let Some(len) = rest[inner_start..].find(close) else {
+            return error(format!("`{open}` isn't closed with `{close}`"), line);
+        };
+        let inner = &rest[inner_start..inner_start + len];
+        pos += inner_start + len + 2;
+
+        let inner = match inner.strip_prefix('-') {
+            Some(inner) => {
+                if let Some(Token::Text(text)) = tokens.last_mut() {
+                    *text = text.trim_end();
+                }
+                inner
+            }
+            None => inner,
+        };
+        let inner = match inner.strip_suffix('-') {
+            Some(inner) => {
+                trim_next = true;
+                inner
+            }
+            None => {
+                trim_next = false;
+                inner
+            }
+        };
+        match open {
+            "{{" => tokens.push(Token::Expr(inner.trim(), line)),
+            "{%" => tokens.push(Token::Tag(inner.trim(), line)),
+            _ => {}
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// The tag which ends a block of nodes, like `endfor`, with its line.
+type EndTag<'a> = (&'a str, usize);
+
+/// Parse nodes until one of the tags in `ends`, which is returned with its line.
+fn parse_nodes<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+    ends: &[&str],
+) -> Result<(Vec<Node>, Option<EndTag<'a>>)> {
+    let mut nodes = vec![];
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.to_string())),
+            Token::Expr(expr, line) => nodes.push(Node::Expr(parse_expr(expr, line)?, line)),
+            Token::Tag(tag, line) => {
+                let (keyword, rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                let rest = rest.trim();
+                if ends.contains(&keyword) {
+                    return Ok((nodes, Some((tag, line))));
+                }
+                match keyword {
+                    "if" => nodes.push(parse_if(tokens, rest, line)?),
+                    "for" => nodes.push(parse_for(tokens, rest, line)?),
+                    "set" => {
+                        let Some((name, value)) = rest.split_once('=') else {
+                            return error("expected `set <name> = <value>`", line);
+                        };
+                        nodes.push(Node::Set(
+                            name.trim().to_string(),
+                            parse_expr(value, line)?,
+                            line,
+                        ));
+                    }
+                    _ => return error(format!("unexpected `{tag}`"), line),
+                }
+            }
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn parse_if<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+    condition: &str,
+    line: usize,
+) -> Result<Node> {
+    let mut branches = vec![];
+    let mut condition = parse_expr(condition, line)?;
+    loop {
+        let (body, end) = parse_nodes(tokens, &["elif", "else", "endif"])?;
+        branches.push((condition, body));
+        match end {
+            Some((end, end_line)) if end.starts_with("elif") => {
+                condition = parse_expr(&end["elif".len()..], end_line)?;
+            }
+            Some(("else", _)) => {
+                let (otherwise, end) = parse_nodes(tokens, &["endif"])?;
+                if end.is_none() {
+                    return error("`if` isn't closed with `endif`", line);
+                }
+                return Ok(Node::If(branches, otherwise, line));
+            }
+            Some(_) => return Ok(Node::If(branches, vec![], line)),
+            None => return error("`if` isn't closed with `endif`", line),
+        }
+    }
+}
+
+fn parse_for<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+    header: &str,
+    line: usize,
+) -> Result<Node> {
+    let Some((vars, iterable)) = header.split_once(" in ") else {
+        return error("expected `for <name> in <value>`", line);
+    };
+    let vars: Vec<String> = vars.split(',').map(|var| var.trim().to_string()).collect();
+    if vars.len() > 2 || vars.iter().any(|var| !is_identifier(var)) {
+        return error(
+            format!("invalid loop variables `{}`", vars.join(", ")),
+            line,
+        );
+    }
+    let iterable = parse_expr(iterable, line)?;
+
+    let (body, end) = parse_nodes(tokens, &["else", "endfor"])?;
+    let empty = match end {
+        Some(("else", _)) => {
+            let (empty, end) = parse_nodes(tokens, &["endfor"])?;
+            if end.is_none() {
+                return error("`for` isn't closed with `endfor`", line);
+            }
+            empty
+        }
+        Some(_) => vec![],
+        None => return error("`for` isn't closed with `endfor`", line),
+    };
+    Ok(Node::For {
+        vars,
+        iterable,
+        body,
+        empty,
+        line,
+    })
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprToken {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 13] = [
+    "==", "!=", "<=", ">=", "<", ">", "|", ".", "(", ")", ",", "~", "-",
+];
+
+fn tokenize_expr(source: &str, line: usize) -> Result<Vec<ExprToken>> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => string.push('\n'),
+                        Some((_, 't')) => string.push('\t'),
+                        Some((_, other)) => string.push(other),
+                        None => break,
+                    },
+                    Some((_, end)) if end == c => break,
+                    Some((_, other)) => string.push(other),
+                    None => return error("unclosed string", line),
+                }
+            }
+            tokens.push(ExprToken::Str(string));
+        } else if c.is_ascii_digit() {
+            let mut end = i;
+            while let Some(&(j, c)) = chars.peek() {
+                // A dot after an index in a path isn't a decimal point
+                let is_decimal_point = c == '.'
+                    && !matches!(tokens.last(), Some(ExprToken::Symbol(".")))
+                    && source[j + 1..].starts_with(|c: char| c.is_ascii_digit());
+                if !c.is_ascii_digit() && !is_decimal_point {
+                    break;
+                }
+                end = j + c.len_utf8();
+                chars.next();
+            }
+            let number = &source[i..end];
+            tokens.push(match number.parse() {
+                Ok(int) => ExprToken::Int(int),
+                Err(_) => match number.parse() {
+                    Ok(float) => ExprToken::Float(float),
+                    Err(_) => return error(format!("invalid number `{number}`"), line),
+                },
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = i;
+            while let Some(&(j, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_' || c == '-') {
+                    break;
+                }
+                end = j + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(ExprToken::Ident(source[i..end].to_string()));
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| source[i..].starts_with(**s)) {
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            tokens.push(ExprToken::Symbol(symbol));
+        } else {
+            return error(format!("unexpected `{c}`"), line);
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_expr(source: &str, line: usize) -> Result<Expr> {
+    let tokens = tokenize_expr(source, line)?;
+    let mut parser = ExprParser {
+        tokens,
+        pos: 0,
+        line,
+    };
+    let expr = parser.or()?;
+    match parser.tokens.get(parser.pos) {
+        Some(token) => error(format!("unexpected {token:?} in `{}`", source.trim()), line),
+        None => Ok(expr),
+    }
+}
+
+struct ExprParser {
+    tokens: Vec<ExprToken>,
+    pos: usize,
+    line: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&ExprToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<ExprToken> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &ExprToken) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.eat(&ExprToken::Ident(keyword.into()))
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.not()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.eat_keyword("not") {
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else {
+            self.compare()
+        }
+    }
+
+    fn compare(&mut self) -> Result<Expr> {
+        let lhs = self.concat()?;
+        let operator = match self.peek() {
+            Some(ExprToken::Symbol(op @ ("==" | "!=" | "<" | ">" | "<=" | ">="))) => *op,
+            Some(ExprToken::Ident(op)) if op == "in" => "in",
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        Ok(Expr::Compare(
+            Box::new(lhs),
+            operator,
+            Box::new(self.concat()?),
+        ))
+    }
+
+    fn concat(&mut self) -> Result<Expr> {
+        let mut expr = self.filtered()?;
+        while self.eat(&ExprToken::Symbol("~")) {
+            expr = Expr::Concat(Box::new(expr), Box::new(self.filtered()?));
+        }
+        Ok(expr)
+    }
+
+    fn filtered(&mut self) -> Result<Expr> {
+        let mut expr = self.primary()?;
+        while self.eat(&ExprToken::Symbol("|")) {
+            let Some(ExprToken::Ident(name)) = self.advance() else {
+                return error("expected the name of a filter after `|`", self.line);
+            };
+            let mut args = vec![];
+            if self.eat(&ExprToken::Symbol("(")) && !self.eat(&ExprToken::Symbol(")")) {
+                loop {
+                    args.push(self.or()?);
+                    if self.eat(&ExprToken::Symbol(")")) {
+                        break;
+                    }
+                    if !self.eat(&ExprToken::Symbol(",")) {
+                        return error(
+                            format!("expected `,` or `)` after argument of `{name}`"),
+                            self.line,
+                        );
+                    }
+                }
+            }
+            expr = Expr::Filter(Box::new(expr), name, args);
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let span = Span::unknown();
+        match self.advance() {
+            Some(ExprToken::Str(string)) => Ok(Expr::Literal(Value::string(string, span))),
+            Some(ExprToken::Int(int)) => Ok(Expr::Literal(Value::int(int, span))),
+            Some(ExprToken::Float(float)) => Ok(Expr::Literal(Value::float(float, span))),
+            Some(ExprToken::Symbol("-")) => match self.advance() {
+                Some(ExprToken::Int(int)) => Ok(Expr::Literal(Value::int(-int, span))),
+                Some(ExprToken::Float(float)) => Ok(Expr::Literal(Value::float(-float, span))),
+                _ => error("expected a number after `-`", self.line),
+            },
+            Some(ExprToken::Symbol("(")) => {
+                let expr = self.or()?;
+                if !self.eat(&ExprToken::Symbol(")")) {
+                    return error("expected `)`", self.line);
+                }
+                Ok(expr)
+            }
+            Some(ExprToken::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::bool(true, span))),
+                "false" => Ok(Expr::Literal(Value::bool(false, span))),
+                "none" | "null" => Ok(Expr::Literal(Value::nothing(span))),
+                _ => {
+                    let mut path = vec![];
+                    while self.eat(&ExprToken::Symbol(".")) {
+                        path.push(match self.advance() {
+                            Some(ExprToken::Ident(key)) => PathPart::Key(key),
+                            Some(ExprToken::Int(index)) if index >= 0 => {
+                                PathPart::Index(index as usize)
+                            }
+                            _ => {
+                                return error(
+                                    format!("expected a field after `{name}.`"),
+                                    self.line,
+                                );
+                            }
+                        });
+                    }
+                    Ok(Expr::Path(name, path))
+                }
+            },
+            Some(token) => error(format!("unexpected {token:?}"), self.line),
+            None => error("expected a value", self.line),
+        }
+    }
+}
+
+struct Renderer<'a> {
+    vars: Vec<(String, Value)>,
+    config: &'a Config,
+    span: Span,
+}
+
+impl Renderer<'_> {
+    fn render(&mut self, nodes: &[Node], out: &mut String) -> Result<()> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Expr(expr, line) => {
+                    let value = self.eval(expr, *line)?;
+                    out.push_str(&self.display(&value));
+                }
+                Node::If(branches, otherwise, line) => {
+                    let mut taken = None;
+                    for (condition, body) in branches {
+                        if is_truthy(&self.eval(condition, *line)?) {
+                            taken = Some(body);
+                            break;
+                        }
+                    }
+                    self.render(taken.unwrap_or(otherwise), out)?;
+                }
+                Node::For {
+                    vars,
+                    iterable,
+                    body,
+                    empty,
+                    line,
+                } => {
+                    let iterations: Vec<Vec<Value>> = match self.eval(iterable, *line)? {
+                        Value::List { vals, .. } if vars.len() == 1 => {
+                            vals.into_iter().map(|val| vec![val]).collect()
+                        }
+                        Value::Record { val, .. } => val
+                            .into_owned()
+                            .into_iter()
+                            .map(|(key, value)| vec![Value::string(key, self.span), value])
+                            .collect(),
+                        Value::Nothing { .. } => vec![],
+                        other => {
+                            return error(
+                                format!(
+                                    "can't loop over {} with {} variables",
+                                    other.get_type(),
+                                    vars.len()
+                                ),
+                                *line,
+                            );
+                        }
+                    };
+                    if iterations.is_empty() {
+                        self.render(empty, out)?;
+                    }
+
+                    let len = iterations.len();
+                    let scope = self.vars.len();
+                    for (i, values) in iterations.into_iter().enumerate() {
+                        let loop_record = self.loop_record(i, len);
+                        self.vars.truncate(scope);
+                        self.vars.push(("loop".into(), loop_record));
+                        self.vars.extend(vars.iter().cloned().zip(values));
+                        self.render(body, out)?;
+                    }
+                    self.vars.truncate(scope);
+                }
+                Node::Set(name, expr, line) => {
+                    let value = self.eval(expr, *line)?;
+                    self.vars.push((name.clone(), value));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn loop_record(&self, i: usize, len: usize) -> Value {
+        let span = self.span;
+        let mut record = Record::new();
+        record.push("index", Value::int(i as i64 + 1, span));
+        record.push("index0", Value::int(i as i64, span));
+        record.push("first", Value::bool(i == 0, span));
+        record.push("last", Value::bool(i + 1 == len, span));
+        record.push("length", Value::int(len as i64, span));
+        Value::record(record, span)
+    }
+
+    fn display(&self, value: &Value) -> String {
+        match value {
+            Value::Nothing { .. } => String::new(),
+            value => value.to_expanded_string(", ", self.config),
+        }
+    }
+
+    fn eval(&self, expr: &Expr, line: usize) -> Result<Value> {
+        let span = self.span;
+        Ok(match expr {
+            Expr::Literal(value) => value.clone().with_span(span),
+            Expr::Path(name, path) => {
+                let Some((_, value)) = self.vars.iter().rev().find(|(var, _)| var == name) else {
+                    return error(format!("`{name}` isn't defined"), line);
+                };
+                let mut value = value;
+                for part in path {
+                    let next = match (part, value) {
+                        (PathPart::Key(key), Value::Record { val, .. }) => val.get(key),
+                        (PathPart::Index(index), Value::List { vals, .. }) => vals.get(*index),
+                        _ => None,
+                    };
+                    value = match next {
+                        Some(next) => next,
+                        None => {
+                            let part = match part {
+                                PathPart::Key(key) => key.clone(),
+                                PathPart::Index(index) => index.to_string(),
+                            };
+                            return error(format!("`{name}` has no `{part}`"), line);
+                        }
+                    };
+                }
+                value.clone()
+            }
+            Expr::Filter(value, name, args) if name == "default" => {
+                let fallback = match args.first() {
+                    Some(arg) => self.eval(arg, line)?,
+                    None => Value::string("", span),
+                };
+                match self.eval(value, line) {
+                    Ok(value) if !value.is_nothing() => value,
+                    _ => fallback,
+                }
+            }
+            Expr::Filter(value, name, args) => {
+                let value = self.eval(value, line)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg, line))
+                    .collect::<Result<Vec<_>>>()?;
+                self.filter(name, value, &args)
+                    .or_else(|msg| error(format!("`{name}`: {msg}"), line))?
+            }
+            Expr::Not(value) => Value::bool(!is_truthy(&self.eval(value, line)?), span),
+            Expr::And(lhs, rhs) => {
+                let lhs = self.eval(lhs, line)?;
+                if is_truthy(&lhs) {
+                    self.eval(rhs, line)?
+                } else {
+                    lhs
+                }
+            }
+            Expr::Or(lhs, rhs) => {
+                let lhs = self.eval(lhs, line)?;
+                if is_truthy(&lhs) {
+                    lhs
+                } else {
+                    self.eval(rhs, line)?
+                }
+            }
+            Expr::Compare(lhs, operator, rhs) => {
+                let (lhs, rhs) = (self.eval(lhs, line)?, self.eval(rhs, line)?);
+                let result = match *operator {
+                    "in" => match &rhs {
+                        Value::List { vals, .. } => vals.contains(&lhs),
+                        Value::Record { val, .. } => val.contains(self.display(&lhs)),
+                        rhs => self.display(rhs).contains(&self.display(&lhs)),
+                    },
+                    operator => {
+                        let ordering = lhs.partial_cmp(&rhs);
+                        match operator {
+                            "==" => ordering == Some(Ordering::Equal),
+                            "!=" => ordering != Some(Ordering::Equal),
+                            "<" => ordering == Some(Ordering::Less),
+                            ">" => ordering == Some(Ordering::Greater),
+                            "<=" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                            _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                        }
+                    }
+                };
+                Value::bool(result, span)
+            }
+            Expr::Concat(lhs, rhs) => {
+                let (lhs, rhs) = (self.eval(lhs, line)?, self.eval(rhs, line)?);
+                Value::string(self.display(&lhs) + &self.display(&rhs), span)
+            }
+        })
+    }
+
+    fn filter(
+        &self,
+        name: &str,
+        value: Value,
+        args: &[Value],
+    ) -> std::result::Result<Value, String> {
+        let span = self.span;
+        let string_arg = |i: usize| match args.get(i) {
+            Some(arg) => arg.coerce_string().map(Some).map_err(|err| err.to_string()),
+            None => Ok(None),
+        };
+        Ok(match name {
+            "upper" => Value::string(self.display(&value).to_uppercase(), span),
+            "lower" => Value::string(self.display(&value).to_lowercase(), span),
+            "trim" => Value::string(self.display(&value).trim(), span),
+            "capitalize" => {
+                let string = self.display(&value);
+                let mut chars = string.chars();
+                let capitalized = match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                };
+                Value::string(capitalized, span)
+            }
+            "length" => Value::int(
+                match &value {
+                    Value::List { vals, .. } => vals.len(),
+                    Value::Record { val, .. } => val.len(),
+                    value => self.display(value).chars().count(),
+                } as i64,
+                span,
+            ),
+            "first" | "last" => {
+                let Value::List { vals, .. } = &value else {
+                    return Err(format!("expected a list, not {}", value.get_type()));
+                };
+                let item = if name == "first" {
+                    vals.first()
+                } else {
+                    vals.last()
+                };
+                item.cloned().unwrap_or(Value::nothing(span))
+            }
+            "join" => {
+                let Value::List { vals, .. } = &value else {
+                    return Err(format!("expected a list, not {}", value.get_type()));
+                };
+                let separator = string_arg(0)?.unwrap_or_default();
+                let items: Vec<String> = vals.iter().map(|val| self.display(val)).collect();
+                Value::string(items.join(&separator), span)
+            }
+            "round" => {
+                let precision = match args.first() {
+                    Some(Value::Int { val, .. }) => *val as i32,
+                    Some(other) => {
+                        return Err(format!("expected an int, not {}", other.get_type()));
+                    }
+                    None => 0,
+                };
+                let number = match value {
+                    Value::Int { val, .. } => val as f64,
+                    Value::Float { val, .. } => val,
+                    other => return Err(format!("expected a number, not {}", other.get_type())),
+                };
+                let factor = 10f64.powi(precision);
+                Value::float((number * factor).round() / factor, span)
+            }
+            "date" => {
+                let Value::Date { val, .. } = value else {
+                    return Err(format!("expected a date, not {}", value.get_type()));
+                };
+                let format = string_arg(0)?.unwrap_or_else(|| "%Y-%m-%d".into());
+                let items: Vec<Item> = StrftimeItems::new(&format).collect();
+                if items.contains(&Item::Error) {
+                    return Err(format!("invalid format `{format}`"));
+                }
+                Value::string(val.format_with_items(items.into_iter()).to_string(), span)
+            }
+            "filesize" => {
+                let filesize = match value {
+                    Value::Filesize { val, .. } => val,
+                    Value::Int { val, .. } => Filesize::new(val),
+                    other => return Err(format!("expected a filesize, not {}", other.get_type())),
+                };
+                let formatted = match string_arg(0)? {
+                    Some(unit) => {
+                        let unit: FilesizeUnit =
+                            unit.parse().map_err(|_| format!("invalid unit `{unit}`"))?;
+                        FilesizeFormatter::new().unit(unit).format(filesize)
+                    }
+                    None => self.config.filesize.format(filesize),
+                };
+                Value::string(formatted.to_string(), span)
+            }
+            _ => return Err("unknown filter".into()),
+        })
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool { val, .. } => *val,
+        Value::Int { val, .. } => *val != 0,
+        Value::Float { val, .. } => *val != 0.0,
+        Value::String { val, .. } => !val.is_empty(),
+        Value::List { vals, .. } => !vals.is_empty(),
+        Value::Record { val, .. } => !val.is_empty(),
+        Value::Nothing { .. } | Value::Error { .. } => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nu_protocol::record;
+
+    fn render(template: &str, context: Value) -> String {
+        Template::parse(template)
+            .and_then(|template| template.render(&context, &Config::default(), Span::test_data()))
+            .unwrap_or_else(|err| panic!("line {}: {}", err.line, err.msg))
+    }
+
+    #[test]
+    fn loops_and_conditions() {
+        let context = Value::test_record(record! {
+            "items" => Value::test_list(vec![
+                Value::test_record(record! {
+                    "name" => Value::test_string("a"),
+                    "done" => Value::test_bool(true),
+                }),
+                Value::test_record(record! {
+                    "name" => Value::test_string("b"),
+                    "done" => Value::test_bool(false),
+                }),
+            ]),
+        });
+        let template = "{% for item in items -%}
+{{ loop.index }}. {{ item.name | upper }}{% if not item.done %} (todo){% endif %}
+{% endfor %}";
+
+        assert_eq!(render(template, context), "1. A\n2. B (todo)\n");
+    }
+
+    #[test]
+    fn records_defaults_and_set() {
+        let context = Value::test_record(record! {
+            "env" => Value::test_record(record! {
+                "HOME" => Value::test_string("/home/nu"),
+            }),
+        });
+        let template = "{% set sep = '=' %}{% for key, value in env %}{{ key ~ sep ~ value }}{% endfor %} {{ missing | default('none') }}";
+
+        assert_eq!(render(template, context), "HOME=/home/nu none");
+    }
+
+    #[test]
+    fn filesize_filters() {
+        let context = Value::test_record(record! {
+            "size" => Value::test_filesize(Filesize::new(1_500_000)),
+        });
+
+        assert_eq!(render("{{ size | filesize('kB') }}", context), "1500 kB");
+    }
+
+    #[test]
+    fn errors_have_lines() {
+        let err = Template::parse("line 1\n{% if x %}\nno end").err();
+
+        assert_eq!(err.map(|err| err.line), Some(2));
+    }
+}
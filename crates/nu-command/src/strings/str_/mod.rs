@@ -12,6 +12,7 @@ mod starts_with;
 mod stats;
 mod substring;
 mod trim;
+mod width;
 
 pub use case::*;
 pub use contains::StrContains;
@@ -27,3 +28,4 @@ pub use starts_with::StrStartsWith;
 pub use stats::StrStats;
 pub use substring::StrSubstring;
 pub use trim::StrTrim;
+pub use width::StrWidth;
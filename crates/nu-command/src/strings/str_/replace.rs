@@ -1,3 +1,4 @@
+use crate::{capture_column_names, captures_record};
 use fancy_regex::{Captures, NoExpand, Regex};
 use nu_cmd_base::input_handler::{CmdArgument, operate};
 use nu_engine::{ClosureEval, command_prelude::*};
@@ -16,6 +17,7 @@ struct Arguments {
     literal_replace: bool,
     no_regex: bool,
     multiline: bool,
+    named_captures: bool,
 }
 
 impl CmdArgument for Arguments {
@@ -70,6 +72,11 @@ impl Command for StrReplace {
                 "multi-line regex mode (implies --regex): ^ and $ match begin/end of line; equivalent to (?m)",
                 Some('m'),
             )
+            .switch(
+                "named-captures",
+                "pass the capture groups to the replacement closure as a single record (implies --regex)",
+                None,
+            )
             .allow_variants_without_examples(true)
             .category(Category::Strings)
     }
@@ -85,6 +92,9 @@ The replacement can be a a string, possibly containing references to numbered (`
 named capture groups (`$name`), or it can be closure that is invoked for each match.
 In the latter case, the closure is invoked with the entire match as its input and any capture
 groups as its argument. It must return a string that will be used as a replacement for the match.
+With `--named-captures`, the closure instead gets a single record of all capture groups as its
+argument. Named groups use their name as key, unnamed groups are called `capture0`, `capture1` and
+so on, like in `parse`.
 "#
     }
 
@@ -123,8 +133,10 @@ groups as its argument. It must return a string that will be used as a replaceme
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 2)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
         let literal_replace = call.has_flag(engine_state, stack, "no-expand")?;
+        let named_captures = call.has_flag(engine_state, stack, "named-captures")?;
         let no_regex = !call.has_flag(engine_state, stack, "regex")?
-            && !call.has_flag(engine_state, stack, "multiline")?;
+            && !call.has_flag(engine_state, stack, "multiline")?
+            && !named_captures;
         let multiline = call.has_flag(engine_state, stack, "multiline")?;
 
         let args = Arguments {
@@ -135,6 +147,7 @@ groups as its argument. It must return a string that will be used as a replaceme
             literal_replace,
             no_regex,
             multiline,
+            named_captures,
         };
         operate(action, args, input, call.head, engine_state.signals())
     }
@@ -150,8 +163,10 @@ groups as its argument. It must return a string that will be used as a replaceme
         let cell_paths: Vec<CellPath> = call.rest_const(working_set, 2)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
         let literal_replace = call.has_flag_const(working_set, "no-expand")?;
+        let named_captures = call.has_flag_const(working_set, "named-captures")?;
         let no_regex = !call.has_flag_const(working_set, "regex")?
-            && !call.has_flag_const(working_set, "multiline")?;
+            && !call.has_flag_const(working_set, "multiline")?
+            && !named_captures;
         let multiline = call.has_flag_const(working_set, "multiline")?;
 
         let args = Arguments {
@@ -162,6 +177,7 @@ groups as its argument. It must return a string that will be used as a replaceme
             literal_replace,
             no_regex,
             multiline,
+            named_captures,
         };
         operate(
             action,
@@ -251,6 +267,16 @@ groups as its argument. It must return a string that will be used as a replaceme
                     "string: \"abc\" backslash: \\ newline:\nend",
                 )),
             },
+            Example {
+                description: "Double all numbers using a closure and named capture groups",
+                example: r#"'width: 20px, height: 35px' | str replace -a --named-captures '(?<n>[0-9]+)px' {|c| $"($c.n | into int | $in * 2)px" }"#,
+                result: Some(Value::test_string("width: 40px, height: 70px")),
+            },
+            Example {
+                description: "Rewrite dates using a closure and named capture groups",
+                example: r#"'2024-03-15' | str replace --named-captures '(?<y>\d+)-(?<m>\d+)-(?<d>\d+)' {|date| $"($date.d).($date.m).($date.y)" }"#,
+                result: Some(Value::test_string("15.03.2024")),
+            },
         ]
     }
 }
@@ -264,6 +290,7 @@ fn action(
         literal_replace,
         no_regex,
         multiline,
+        named_captures,
         ..
     }: &Arguments,
     head: Span,
@@ -349,13 +376,22 @@ fn action(
                         //       and all commands that use it?
                         let mut closure_eval = closure.item.clone();
                         let mut first_error: Option<ShellError> = None;
+                        let columns = named_captures.then(|| capture_column_names(&re));
                         let replacer = |caps: &Captures| {
-                            for capture in caps.iter().skip(1) {
-                                let arg = match capture {
-                                    Some(m) => Value::string(m.as_str().to_string(), head),
-                                    None => Value::nothing(head),
-                                };
-                                closure_eval.add_arg(arg);
+                            match &columns {
+                                Some(columns) => {
+                                    let record = captures_record(columns, caps, head);
+                                    closure_eval.add_arg(Value::record(record, head));
+                                }
+                                None => {
+                                    for capture in caps.iter().skip(1) {
+                                        let arg = match capture {
+                                            Some(m) => Value::string(m.as_str().to_string(), head),
+                                            None => Value::nothing(head),
+                                        };
+                                        closure_eval.add_arg(arg);
+                                    }
+                                }
                             }
                             let value = match caps.get(0) {
                                 Some(m) => Value::string(m.as_str().to_string(), head),
@@ -428,9 +464,9 @@ mod tests {
 
     #[test]
     fn test_examples() {
-        use crate::test_examples;
+        use crate::{IntoInt, test_examples_with_commands};
 
-        test_examples(StrReplace {})
+        test_examples_with_commands(StrReplace {}, &[&IntoInt])
     }
 
     #[test]
@@ -445,6 +481,7 @@ mod tests {
             all: false,
             no_regex: false,
             multiline: false,
+            named_captures: false,
         };
 
         let actual = action(&word, &options, Span::test_data());
@@ -0,0 +1,180 @@
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+use nu_protocol::AmbiguousWidth;
+
+use unicode_width::UnicodeWidthStr;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+    wide_ambiguous: bool,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct StrWidth;
+
+impl Command for StrWidth {
+    fn name(&self) -> &str {
+        "str width"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str width")
+            .input_output_types(vec![
+                (Type::String, Type::Int),
+                (Type::List(Box::new(Type::String)), Type::List(Box::new(Type::Int))),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .named(
+                "ambiguous-width",
+                SyntaxShape::String,
+                "how to count characters with an ambiguous width: narrow, wide or locale (overrides the config)",
+                Some('a'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, replace strings at the given cell paths with their width.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Output the number of terminal columns needed to display strings."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"ANSI escape sequences don't take up any space, and for strings with multiple lines the width of the widest line is returned.
+East Asian wide characters and most emoji take up two columns. Characters like "°" or "±" are displayed with one column in most locales but two columns in CJK locales; this is decided by the `$env.config.strings.ambiguous_width` setting or the --ambiguous-width flag."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["columns", "display", "length", "size", "unicode", "cjk"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let ambiguous_width =
+            match call.get_flag::<Spanned<String>>(engine_state, stack, "ambiguous-width")? {
+                Some(flag) => flag.item.parse::<AmbiguousWidth>().map_err(|valid| {
+                    ShellError::InvalidValue {
+                        valid: valid.into(),
+                        actual: flag.item.clone(),
+                        span: flag.span,
+                    }
+                })?,
+                None => stack.get_config(engine_state).strings.ambiguous_width,
+            };
+        let wide_ambiguous = match ambiguous_width {
+            AmbiguousWidth::Narrow => false,
+            AmbiguousWidth::Wide => true,
+            AmbiguousWidth::Locale => is_cjk_locale(engine_state, stack),
+        };
+
+        let args = Arguments {
+            cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+            wide_ambiguous,
+        };
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Return the display width of a string",
+                example: "'hello' | str width",
+                result: Some(Value::test_int(5)),
+            },
+            Example {
+                description: "Wide characters take up two columns",
+                example: "'ほげ ふが' | str width",
+                result: Some(Value::test_int(9)),
+            },
+            Example {
+                description: "ANSI escape sequences are ignored",
+                example: "$'(ansi green)hello(ansi reset)' | str width",
+                result: Some(Value::test_int(5)),
+            },
+            Example {
+                description: "Count characters with an ambiguous width as wide, like in CJK locales",
+                example: "'±5°' | str width --ambiguous-width wide",
+                result: Some(Value::test_int(5)),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, arg: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => Value::int(width(val, arg.wide_ambiguous) as i64, head),
+        Value::Error { .. } => input.clone(),
+        _ => Value::error(
+            ShellError::OnlySupportsThisInputType {
+                exp_input_type: "string".into(),
+                wrong_type: input.get_type().to_string(),
+                dst_span: head,
+                src_span: input.span(),
+            },
+            head,
+        ),
+    }
+}
+
+fn width(string: &str, wide_ambiguous: bool) -> usize {
+    nu_utils::strip_ansi_unlikely(string)
+        .lines()
+        .map(|line| {
+            if wide_ambiguous {
+                line.width_cjk()
+            } else {
+                line.width()
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Whether the locale in the environment is a Chinese, Japanese or Korean one, where characters
+/// with an ambiguous width are usually displayed wide.
+fn is_cjk_locale(engine_state: &EngineState, stack: &Stack) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| stack.get_env_var(engine_state, name))
+        .filter_map(|value| value.as_str().ok())
+        .find(|locale| !locale.is_empty())
+        .is_some_and(|locale| {
+            ["zh", "ja", "ko"]
+                .iter()
+                .any(|language| locale.starts_with(language))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn widest_line_is_used() {
+        assert_eq!(width("a\nほげ\nabc", false), 4);
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(StrWidth {})
+    }
+}
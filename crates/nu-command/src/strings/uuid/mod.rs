@@ -0,0 +1,11 @@
+mod parse;
+mod ulid_;
+mod ulid_parse;
+mod uuid_;
+mod validate;
+
+pub use parse::UuidParse;
+pub use ulid_::Ulid;
+pub use ulid_parse::UlidParse;
+pub use uuid_::Uuid;
+pub use validate::UuidValidate;
@@ -0,0 +1,180 @@
+use chrono::DateTime;
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct UuidParse;
+
+impl Command for UuidParse {
+    fn name(&self) -> &str {
+        "uuid parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uuid parse")
+            .input_output_types(vec![
+                (Type::String, Type::record()),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::record())),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, parse the UUIDs at the given cell paths.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Parse a UUID into its version, variant and timestamp."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"UUIDs can be hyphenated, without hyphens, in braces or URNs like `urn:uuid:...`, and
+are returned hyphenated in lowercase. The timestamp is the time at which version 1, 6 and 7
+UUIDs were generated, and nothing for the other versions."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["guid", "version", "timestamp", "decode"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Get the time at which a version 7 UUID was generated",
+                example: "'01912d68-783e-7a03-8467-5661c1243ad4' | uuid parse",
+                result: Some(Value::test_record(record! {
+                    "uuid" => Value::test_string("01912d68-783e-7a03-8467-5661c1243ad4"),
+                    "version" => Value::test_int(7),
+                    "variant" => Value::test_string("rfc4122"),
+                    "timestamp" => Value::test_date(
+                        DateTime::parse_from_rfc3339("2024-08-07T15:15:06.942Z").expect("valid date")
+                    ),
+                })),
+            },
+            Example {
+                description: "Normalize UUIDs in any format",
+                example: "'{67E55044-10B1-426F-9247-BB680E5FE0C8}' | uuid parse",
+                result: Some(Value::test_record(record! {
+                    "uuid" => Value::test_string("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+                    "version" => Value::test_int(4),
+                    "variant" => Value::test_string("rfc4122"),
+                    "timestamp" => Value::test_nothing(),
+                })),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, head: Span) -> Value {
+    let span = input.span();
+    let text = match input {
+        Value::String { val, .. } => val,
+        Value::Error { .. } => return input.clone(),
+        other => {
+            return Value::error(
+                ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: span,
+                },
+                head,
+            );
+        }
+    };
+    match uuid::Uuid::try_parse(text.trim()) {
+        Ok(uuid) => uuid_record(uuid, head),
+        Err(err) => Value::error(
+            ShellError::CantConvert {
+                to_type: "uuid".into(),
+                from_type: "string".into(),
+                span,
+                help: Some(err.to_string()),
+            },
+            head,
+        ),
+    }
+}
+
+fn uuid_record(uuid: uuid::Uuid, span: Span) -> Value {
+    // The variant is in the high bits of the 9th byte
+    let variant = match uuid.as_bytes()[8] {
+        0x00..=0x7f => "ncs",
+        0x80..=0xbf => "rfc4122",
+        0xc0..=0xdf => "microsoft",
+        _ => "future",
+    };
+    let timestamp = uuid
+        .get_timestamp()
+        .and_then(|timestamp| {
+            let (secs, nanos) = timestamp.to_unix();
+            DateTime::from_timestamp(secs as i64, nanos)
+        })
+        .map_or(Value::nothing(span), |time| {
+            Value::date(time.fixed_offset(), span)
+        });
+
+    Value::record(
+        record! {
+            "uuid" => Value::string(uuid.hyphenated().to_string(), span),
+            "version" => Value::int(uuid.get_version_num() as i64, span),
+            "variant" => Value::string(variant, span),
+            "timestamp" => timestamp,
+        },
+        span,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(UuidParse {})
+    }
+
+    #[test]
+    fn version_1_timestamps() {
+        let uuid = uuid::Uuid::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+        let record = uuid_record(uuid, Span::test_data());
+        assert_eq!(
+            record.get_data_by_key("timestamp"),
+            Some(Value::test_date(
+                DateTime::parse_from_rfc3339("2022-02-22T19:22:22Z").unwrap()
+            ))
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use nu_engine::{command_prelude::*, get_full_help};
+
+#[derive(Clone)]
+pub struct Ulid;
+
+impl Command for Ulid {
+    fn name(&self) -> &str {
+        "ulid"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ulid")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Various commands for working with ULIDs."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"ULIDs are generated with `random ulid`.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["uuid", "sortable", "identifier"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::string(get_full_help(self, engine_state, stack), call.head).into_pipeline_data())
+    }
+}
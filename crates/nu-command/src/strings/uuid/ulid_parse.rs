@@ -0,0 +1,142 @@
+use chrono::DateTime;
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+
+use crate::ulid::{decode, encode};
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct UlidParse;
+
+impl Command for UlidParse {
+    fn name(&self) -> &str {
+        "ulid parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ulid parse")
+            .input_output_types(vec![
+                (Type::String, Type::record()),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::record())),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, parse the ULIDs at the given cell paths.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Parse a ULID into its timestamp and the UUID with the same bits."
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"ULIDs are 26 characters of Crockford's base32, whose first 10 are the time at which they
+were generated, so they sort by time. They're case-insensitive, and are returned in uppercase.
+ULIDs are generated with `random ulid`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["uuid", "timestamp", "decode", "sortable"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            description: "Get the time at which a ULID was generated",
+            example: "'01ARZ3NDEKTSV4RRFFQ69G5FAV' | ulid parse",
+            result: Some(Value::test_record(record! {
+                "ulid" => Value::test_string("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+                "timestamp" => Value::test_date(
+                    DateTime::parse_from_rfc3339("2016-07-30T23:54:10.259Z").expect("valid date")
+                ),
+                "uuid" => Value::test_string("01563e3a-b5d3-d676-4c61-efb99302bd5b"),
+            })),
+        }]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, head: Span) -> Value {
+    let span = input.span();
+    let text = match input {
+        Value::String { val, .. } => val,
+        Value::Error { .. } => return input.clone(),
+        other => {
+            return Value::error(
+                ShellError::OnlySupportsThisInputType {
+                    exp_input_type: "string".into(),
+                    wrong_type: other.get_type().to_string(),
+                    dst_span: head,
+                    src_span: span,
+                },
+                head,
+            );
+        }
+    };
+    match decode(text.trim()) {
+        Ok(bits) => {
+            let millis = (bits >> 80) as i64;
+            let timestamp = DateTime::from_timestamp_millis(millis)
+                .map_or(Value::nothing(head), |time| {
+                    Value::date(time.fixed_offset(), head)
+                });
+            Value::record(
+                record! {
+                    "ulid" => Value::string(encode(bits), head),
+                    "timestamp" => timestamp,
+                    "uuid" => Value::string(uuid::Uuid::from_u128(bits).hyphenated().to_string(), head),
+                },
+                head,
+            )
+        }
+        Err(msg) => Value::error(
+            ShellError::CantConvert {
+                to_type: "ulid".into(),
+                from_type: "string".into(),
+                span,
+                help: Some(msg),
+            },
+            head,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(UlidParse {})
+    }
+}
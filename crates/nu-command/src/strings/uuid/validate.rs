@@ -0,0 +1,130 @@
+use nu_cmd_base::input_handler::{CmdArgument, operate};
+use nu_engine::command_prelude::*;
+
+struct Arguments {
+    version: Option<usize>,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct UuidValidate;
+
+impl Command for UuidValidate {
+    fn name(&self) -> &str {
+        "uuid validate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uuid validate")
+            .input_output_types(vec![
+                (Type::String, Type::Bool),
+                (
+                    Type::List(Box::new(Type::String)),
+                    Type::List(Box::new(Type::Bool)),
+                ),
+                (Type::table(), Type::table()),
+                (Type::record(), Type::record()),
+            ])
+            .allow_variants_without_examples(true)
+            .named(
+                "version",
+                SyntaxShape::Int,
+                "only accept UUIDs of this version",
+                Some('v'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, check the strings at the given cell paths.",
+            )
+            .category(Category::Strings)
+    }
+
+    fn description(&self) -> &str {
+        "Check whether strings are UUIDs."
+    }
+
+    fn extra_description(&self) -> &str {
+        "The UUIDs can be in any of the formats which `uuid parse` accepts. Values which aren't strings aren't UUIDs."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["guid", "check", "is"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let version: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "version")?;
+        let version = version
+            .map(|version| match usize::try_from(version.item) {
+                Ok(item @ 0..=15) => Ok(item),
+                _ => Err(ShellError::IncorrectValue {
+                    msg: "UUID versions are between 0 and 15".into(),
+                    val_span: version.span,
+                    call_span: call.head,
+                }),
+            })
+            .transpose()?;
+        let cell_paths = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            version,
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.signals())
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                description: "Check whether a string is a UUID",
+                example: "'67e55044-10b1-426f-9247-bb680e5fe0c8' | uuid validate",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                description: "Check whether strings are version 7 UUIDs",
+                example: "['01912d68-783e-7a03-8467-5661c1243ad4' '67e55044-10b1-426f-9247-bb680e5fe0c8' 'not a uuid'] | uuid validate --version 7",
+                result: Some(Value::test_list(vec![
+                    Value::test_bool(true),
+                    Value::test_bool(false),
+                    Value::test_bool(false),
+                ])),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    let valid = match input {
+        Value::String { val, .. } => uuid::Uuid::try_parse(val.trim()).is_ok_and(|uuid| {
+            args.version
+                .is_none_or(|version| uuid.get_version_num() == version)
+        }),
+        Value::Error { .. } => return input.clone(),
+        _ => false,
+    };
+    Value::bool(valid, head)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(UuidValidate {})
+    }
+}
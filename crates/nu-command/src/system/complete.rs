@@ -15,6 +15,10 @@ impl Command for Complete {
             .input_output_types(vec![(Type::Any, Type::record())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Capture the outputs and exit code from an external piped in command in a nushell table."
     }
@@ -22,6 +22,10 @@ impl Command for Exec {
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Execute a command, replacing or exiting the current process, depending on platform."
     }
@@ -55,6 +59,10 @@ On Windows based systems, Nushell will wait for the command to finish and then e
             _ => Cow::Owned(name.clone().coerce_into_string()?),
         };
 
+        if let Some(sandbox) = &engine_state.sandbox {
+            sandbox.check_external(&name_str, name.span())?;
+        }
+
         // Find the absolute path to the executable. If the command is not
         // found, display a helpful error message.
         let executable = {
@@ -28,6 +28,10 @@ impl Command for Ps {
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about system processes."
     }
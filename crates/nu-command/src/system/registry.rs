@@ -14,6 +14,10 @@ impl Command for Registry {
             .input_output_types(vec![(Type::Nothing, Type::String)])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Various commands for interacting with the system registry (Windows only)."
     }
@@ -43,6 +43,10 @@ impl Command for RegistryQuery {
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Query the Windows registry."
     }
@@ -2,7 +2,7 @@ use nu_cmd_base::hook::eval_hook;
 use nu_engine::{command_prelude::*, env_to_strings};
 use nu_path::{AbsolutePath, dots::expand_ndots_safe, expand_tilde};
 use nu_protocol::{
-    ByteStream, NuGlob, OutDest, Signals, UseAnsiColoring, did_you_mean,
+    ByteStream, NuGlob, OutDest, PathValue, SignalAction, Signals, UseAnsiColoring, did_you_mean,
     process::{ChildProcess, PostWaitCallback},
     shell_error::io::IoError,
 };
@@ -49,6 +49,10 @@ If you create a custom command with this name, that will be used instead."#
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -84,6 +88,10 @@ If you create a custom command with this name, that will be used instead."#
             _ => Cow::Owned(name.clone().coerce_into_string()?),
         };
 
+        if let Some(sandbox) = &engine_state.sandbox {
+            sandbox.check_external(&name_str, name.span())?;
+        }
+
         let expanded_name = match &name {
             // Expand tilde and ndots on the name if it's a bare string / glob (#13000)
             Value::Glob { no_expand, .. } if !*no_expand => {
@@ -221,6 +229,11 @@ If you create a custom command with this name, that will be used instead."#
                 && matches!(stdout, OutDest::Inherit | OutDest::Print)
             {
                 command.stdout(Stdio::null());
+            } else if engine_state.is_json_server
+                && matches!(stdout, OutDest::Inherit | OutDest::Print)
+            {
+                // The stdout of `nu --serve-json` only has its responses
+                command.stdout(std::io::stderr());
             } else {
                 command.stdout(
                     Stdio::try_from(stdout).map_err(|err| IoError::new(err, call.head, None))?,
@@ -254,6 +267,11 @@ If you create a custom command with this name, that will be used instead."#
                     Some(PipelineData::byte_stream(stream, metadata))
                 }
             },
+            // The stdin of `nu --serve-json` has its requests, which externals mustn't read
+            PipelineData::Empty if engine_state.is_json_server => {
+                command.stdin(Stdio::null());
+                None
+            }
             PipelineData::Empty => {
                 command.stdin(Stdio::inherit());
                 None
@@ -264,13 +282,17 @@ If you create a custom command with this name, that will be used instead."#
             }
         };
 
+        if let Some(limits) = engine_state.signals().limits() {
+            limits.add_process(call.head)?;
+        }
+
         // Log the command we're about to run in case it's useful for debugging purposes.
         log::trace!("run-external spawning: {command:?}");
 
         // Spawn the child process. On Unix, also put the child process to
         // foreground if we're in an interactive session.
         #[cfg(windows)]
-        let child = ForegroundChild::spawn(command);
+        let child = ForegroundChild::spawn(command, 0);
         #[cfg(unix)]
         let child = ForegroundChild::spawn(
             command,
@@ -318,21 +340,40 @@ If you create a custom command with this name, that will be used instead."#
 
         let child_pid = child.pid();
 
+        // Interrupt the child when nushell is interrupted, until it exits. This matters when the
+        // interrupt doesn't come from the terminal, or the child isn't in the foreground.
+        let interrupt_guard = engine_state
+            .signal_handlers
+            .as_ref()
+            .map(|handlers| {
+                let interrupter = child.interrupter();
+                handlers.register(Box::new(move |action| {
+                    if action == SignalAction::Interrupt {
+                        interrupter.interrupt();
+                    }
+                }))
+            })
+            .transpose()?;
+        let job_control = PostWaitCallback::for_job_control(
+            engine_state,
+            Some(child_pid),
+            executable
+                .as_path()
+                .file_name()
+                .and_then(|it| it.to_str())
+                .map(|it| it.to_string()),
+        );
+
         // Wrap the output into a `PipelineData::byte_stream`.
         let mut child = ChildProcess::new(
             child,
             merged_stream,
             matches!(stderr, OutDest::Pipe),
             call.head,
-            Some(PostWaitCallback::for_job_control(
-                engine_state,
-                Some(child_pid),
-                executable
-                    .as_path()
-                    .file_name()
-                    .and_then(|it| it.to_str())
-                    .map(|it| it.to_string()),
-            )),
+            Some(PostWaitCallback::new(move |status| {
+                drop(interrupt_guard);
+                (job_control.0)(status);
+            })),
         )?;
 
         if matches!(stdout, OutDest::Pipe | OutDest::PipeSeparate)
@@ -391,8 +432,14 @@ pub fn eval_external_arguments(
                 .into_iter()
                 .map(|s| s.into_spanned(span)),
             ),
-            other => args
-                .push(OsString::from(coerce_into_string(engine_state, other)?).into_spanned(span)),
+            // Paths are passed as a single argument with their exact bytes, so the external
+            // command gets them even if they aren't valid UTF-8 or contain spaces
+            other => match PathValue::try_from_value(&other) {
+                Some(path) => args.push(path.as_os_str().to_os_string().into_spanned(span)),
+                None => args.push(
+                    OsString::from(coerce_into_string(engine_state, other)?).into_spanned(span),
+                ),
+            },
         }
     }
     Ok(args)
@@ -422,6 +469,16 @@ fn expand_glob(
     span: Span,
     signals: Signals,
 ) -> Result<Vec<OsString>, ShellError> {
+    // Brace sets expand to one argument per alternative, like in Bash. Only arguments which are
+    // globs anyway are expanded, so that e.g. `--format={h,s}` reaches the command unchanged.
+    if nu_glob::is_glob(arg) && nu_glob::has_braces(arg) {
+        let mut result = vec![];
+        for alternative in nu_glob::expand_braces(arg) {
+            result.extend(expand_glob(&alternative, cwd, span, signals.clone())?);
+        }
+        return Ok(result);
+    }
+
     // For an argument that isn't a glob, just do the `expand_tilde`
     // and `expand_ndots` expansion
     if !nu_glob::is_glob(arg) {
@@ -772,6 +829,19 @@ mod test {
             let expected = &["[*.txt"];
             assert_eq!(actual, expected);
 
+            let actual = expand_glob("{a,c}.txt", cwd, Span::unknown(), Signals::empty()).unwrap();
+            let expected = &["{a,c}.txt"];
+            assert_eq!(actual, expected);
+
+            let actual =
+                expand_glob("[ac].{txt,md}", cwd, Span::unknown(), Signals::empty()).unwrap();
+            let expected = &["a.txt", "[ac].md"];
+            assert_eq!(actual, expected);
+
+            let actual = expand_glob("*.{txt,md}", cwd, Span::unknown(), Signals::empty()).unwrap();
+            let expected = &["a.txt", "b.txt", "*.md"];
+            assert_eq!(actual, expected);
+
             let actual = expand_glob("~/foo.txt", cwd, Span::unknown(), Signals::empty()).unwrap();
             let home = dirs::home_dir().expect("failed to get home dir");
             let expected: Vec<OsString> = vec![home.join("foo.txt").into()];
@@ -17,6 +17,10 @@ impl Command for SysDisks {
             .input_output_types(vec![(Type::Nothing, Type::table())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about the system disks."
     }
@@ -18,6 +18,10 @@ impl Command for SysHost {
             .input_output_types(vec![(Type::Nothing, Type::record())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about the system host."
     }
@@ -16,6 +16,10 @@ impl Command for SysMem {
             .input_output_types(vec![(Type::Nothing, Type::record())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about the system memory."
     }
@@ -17,6 +17,10 @@ impl Command for SysNet {
             .input_output_types(vec![(Type::Nothing, Type::table())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about the system network interfaces."
     }
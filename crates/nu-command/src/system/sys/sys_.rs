@@ -15,6 +15,10 @@ impl Command for Sys {
             .input_output_types(vec![(Type::Nothing, Type::record())])
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "View information about the system."
     }
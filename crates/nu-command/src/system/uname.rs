@@ -16,6 +16,10 @@ impl Command for UName {
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Print certain system information using uutils/coreutils uname."
     }
@@ -23,6 +23,10 @@ impl Command for Which {
             .category(Category::System)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn description(&self) -> &str {
         "Finds a program file, alias or custom command. If `application` is not provided, all deduplicated commands will be returned."
     }
@@ -0,0 +1,63 @@
+//! ULIDs in Crockford's base32, for `random ulid` and `ulid parse`.
+
+/// The digits of Crockford's base32, in which ULIDs are written.
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The 26 digits of the 128 bits of a ULID.
+pub(crate) fn encode(bits: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD[(bits >> (i * 5)) as usize & 0x1f] as char)
+        .collect()
+}
+
+/// The 128 bits of a ULID, which is case-insensitive.
+pub(crate) fn decode(text: &str) -> Result<u128, String> {
+    if text.len() != 26 {
+        return Err(format!("a ULID has 26 characters, not {}", text.len()));
+    }
+    let mut bits: u128 = 0;
+    for (i, c) in text.chars().enumerate() {
+        // Crockford's base32 reads I and L as 1, and O as 0
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = CROCKFORD
+            .iter()
+            .position(|digit| *digit as char == c)
+            .ok_or_else(|| format!("'{c}' is not a base32 digit"))?;
+        // The first digit only has 3 bits, as a ULID has 128
+        if i == 0 && digit > 7 {
+            return Err("the timestamp of the ULID is too large".into());
+        }
+        bits = bits << 5 | digit as u128;
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_lowercase_and_ambiguous_digits() {
+        assert_eq!(
+            decode("01arz3ndektsv4rrffq69g5fav"),
+            decode("01ARZ3NDEKTSV4RRFFQ69G5FAV")
+        );
+        assert_eq!(decode("0000000000000000000000000O"), Ok(0));
+        assert!(decode("81ARZ3NDEKTSV4RRFFQ69G5FAV").is_err());
+        assert!(decode("01ARZ3NDEKTSV4RRFFQ69G5FAU").is_err());
+    }
+
+    #[test]
+    fn encodes_what_it_decodes() {
+        assert_eq!(encode(0), "00000000000000000000000000");
+        assert_eq!(
+            decode("01ARZ3NDEKTSV4RRFFQ69G5FAV").map(encode),
+            Ok("01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string())
+        );
+    }
+}
@@ -46,6 +46,10 @@ impl Command for Griddle {
             .category(Category::Viewers)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn extra_description(&self) -> &str {
         r#"grid was built to give a concise gridded layout for ls. however,
 it determines what to put in the grid by looking for a column named
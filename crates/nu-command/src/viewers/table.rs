@@ -2,7 +2,14 @@
 //        overall reduce the redundant calls to StyleComputer etc.
 //        the goal is to configure it once...
 
-use std::{collections::VecDeque, io::Read, path::PathBuf, str::FromStr, time::Duration};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 
 use lscolors::{LsColors, Style};
 use url::Url;
@@ -13,8 +20,8 @@ use nu_engine::{command_prelude::*, env_to_string};
 use nu_path::form::Absolute;
 use nu_pretty_hex::HexConfig;
 use nu_protocol::{
-    ByteStream, Config, DataSource, ListStream, PipelineMetadata, Signals, TableMode,
-    ValueIterator, shell_error::io::IoError,
+    ByteStream, ByteStreamSource, Config, DataSource, ListStream, PathValue, PipelineMetadata,
+    Signals, TableMode, ValueIterator, shell_error::io::IoError,
 };
 use nu_table::{
     CollapsedTable, ExpandedTable, JustTable, NuTable, StringResult, TableOpts, TableOutput,
@@ -104,6 +111,10 @@ impl Command for Table {
             .category(Category::Viewers)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -298,7 +309,9 @@ fn get_table_view(args: &CLIArgs) -> TableView {
 fn get_cli_args(call: &Call<'_>, state: &EngineState, stack: &mut Stack) -> ShellResult<CLIArgs> {
     let width: Option<i64> = call.get_flag(state, stack, "width")?;
     let expand: bool = call.has_flag(state, stack, "expand")?;
-    let expand_limit: Option<usize> = call.get_flag(state, stack, "expand-deep")?;
+    let expand_limit: Option<usize> = call
+        .get_flag(state, stack, "expand-deep")?
+        .or_else(|| stack.get_config(state).table.expand_depth);
     let expand_flatten: bool = call.has_flag(state, stack, "flatten")?;
     let expand_flatten_separator: Option<String> =
         call.get_flag(state, stack, "flatten-separator")?;
@@ -421,11 +434,28 @@ impl<'a> CmdInput<'a> {
 
 fn handle_table_command(mut input: CmdInput<'_>) -> ShellResult<PipelineData> {
     let span = input.data.span().unwrap_or(input.call.head);
+    // Opened files which aren't text are printed as hex too, reading only as much as is printed
+    let binary_file = match &mut input.data {
+        PipelineData::ByteStream(stream, _) if stream.type_() == ByteStreamType::Unknown => {
+            match stream.source_mut() {
+                ByteStreamSource::File(file) => {
+                    is_binary_file(file).map_err(|err| IoError::new(err, span, None))?
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    };
     match input.data {
         // Binary streams should behave as if they really are `binary` data, and printed as hex
-        PipelineData::ByteStream(stream, _) if stream.type_() == ByteStreamType::Binary => Ok(
-            PipelineData::byte_stream(pretty_hex_stream(stream, input.call.head), None),
-        ),
+        PipelineData::ByteStream(stream, _)
+            if binary_file || stream.type_() == ByteStreamType::Binary =>
+        {
+            Ok(PipelineData::byte_stream(
+                pretty_hex_stream(stream, input.call.head),
+                None,
+            ))
+        }
         PipelineData::ByteStream(..) => Ok(input.data),
         PipelineData::Value(Value::Binary { val, .. }, ..) => {
             let signals = input.engine_state.signals().clone();
@@ -457,7 +487,9 @@ fn handle_table_command(mut input: CmdInput<'_>) -> ShellResult<PipelineData> {
             Err(*error)
         }
         PipelineData::Value(Value::Custom { val, .. }, ..) => {
-            let base_pipeline = val.to_base_value(span)?.into_pipeline_data();
+            let config = input.stack.get_config(input.engine_state);
+            let base_pipeline =
+                PathValue::display_base_value(&*val, span, &config)?.into_pipeline_data();
             Table.run(input.engine_state, input.stack, input.call, base_pipeline)
         }
         PipelineData::Value(Value::Range { val, .. }, metadata) => {
@@ -471,6 +503,16 @@ fn handle_table_command(mut input: CmdInput<'_>) -> ShellResult<PipelineData> {
     }
 }
 
+/// Whether the start of `file` isn't UTF-8. The file is read from the same position afterwards.
+fn is_binary_file(file: &mut File) -> std::io::Result<bool> {
+    let start = file.stream_position()?;
+    let mut head = Vec::with_capacity(1024);
+    file.by_ref().take(1024).read_to_end(&mut head)?;
+    file.seek(SeekFrom::Start(start))?;
+    // A character may be cut off at the end
+    Ok(std::str::from_utf8(&head).is_err_and(|err| err.error_len().is_some()))
+}
+
 fn pretty_hex_stream(stream: ByteStream, span: Span) -> ByteStream {
     let mut cfg = HexConfig {
         // We are going to render the title manually first
@@ -641,8 +683,7 @@ fn build_table_batch(
         let span = val.span();
 
         if let Value::Custom { val: custom, .. } = val {
-            *val = custom
-                .to_base_value(span)
+            *val = PathValue::display_base_value(&**custom, span, opts.config)
                 .or_else(|err| Result::<_, ShellError>::Ok(Value::error(err, span)))
                 .expect("error converting custom value to base value")
         }
@@ -155,3 +155,53 @@ fn export_alias_with_overlay_use_works() {
     let actual = nu!("export alias teapot = overlay use");
     assert!(actual.err.is_empty())
 }
+
+#[test]
+fn alias_with_parameter() {
+    let actual =
+        nu!(r#"alias --parameters greet = echo hello {name} '!'; greet world | str join ' '"#);
+    assert_eq!(actual.out, "hello world !");
+}
+
+#[test]
+fn alias_with_parameter_passes_extra_arguments() {
+    let actual = nu!(r#"alias -p pick = echo {first} b; pick a c d | str join ' '"#);
+    assert_eq!(actual.out, "a b c d");
+}
+
+#[test]
+fn alias_with_missing_parameter() {
+    let actual = nu!(r#"alias --parameters greet = echo hello {name}; greet"#);
+    assert!(actual.err.contains("missing name"));
+}
+
+#[test]
+fn alias_with_block_of_command_is_not_parameterized() {
+    let actual = nu!(r#"alias each-print = each {print}; [a b] | each-print | length"#);
+    assert!(actual.err.is_empty());
+}
+
+#[test]
+fn alias_with_block_is_not_parameterized_without_flag() {
+    let actual = nu!(r#"
+        alias each-x = each {x}
+        scope aliases | where name == each-x | get expansion.0
+    "#);
+    assert_eq!(actual.out, "each {x}");
+}
+
+#[test]
+fn alias_with_closure_argument() {
+    let actual = nu!(r#"alias double = each {|x| $x * 2}; [1 2] | double | to nuon"#);
+    assert_eq!(actual.out, "[2, 4]");
+}
+
+#[test]
+fn export_alias_with_parameter() {
+    let actual = nu!(r#"
+        module git { export alias --parameters co = echo checkout {branch} }
+        use git co
+        co main | str join ' '
+    "#);
+    assert_eq!(actual.out, "checkout main");
+}
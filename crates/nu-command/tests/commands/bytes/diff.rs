@@ -0,0 +1,29 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn diffs_files() {
+    Playground::setup("bytes_diff_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[]);
+        std::fs::write(dirs.test().join("a.bin"), b"\x00\x01\x02\x03").unwrap();
+        std::fs::write(dirs.test().join("b.bin"), b"\x00\xff\x02\x03\x04").unwrap();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "bytes diff a.bin b.bin | each { [$in.offset $in.length] } | to nuon"
+        );
+        assert_eq!(actual.out, "[[1, 1], [4, 1]]");
+    })
+}
+
+#[test]
+fn identical_files_have_no_ranges() {
+    Playground::setup("bytes_diff_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[]);
+        std::fs::write(dirs.test().join("a.bin"), b"same").unwrap();
+        std::fs::write(dirs.test().join("b.bin"), b"same").unwrap();
+
+        let actual = nu!(cwd: dirs.test(), "bytes diff a.bin b.bin | is-empty");
+        assert_eq!(actual.out, "true");
+    })
+}
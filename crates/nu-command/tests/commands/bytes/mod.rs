@@ -1,2 +1,3 @@
 mod at;
 mod collect;
+mod diff;
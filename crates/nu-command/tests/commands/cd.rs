@@ -285,7 +285,7 @@ fn cd_permission_denied_folder() {
         let actual = nu!(
             cwd: dirs.test(),
             "
-                chmod -x banned
+                fs chmod a-x banned
                 cd banned
             "
         );
@@ -293,7 +293,7 @@ fn cd_permission_denied_folder() {
         nu!(
             cwd: dirs.test(),
             "
-                chmod +x banned
+                fs chmod a+x banned
                 rm banned
             "
         );
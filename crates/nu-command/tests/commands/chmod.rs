@@ -0,0 +1,67 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+use std::os::unix::fs::PermissionsExt;
+
+#[test]
+fn sets_octal_and_symbolic_modes() {
+    Playground::setup("chmod_test_modes", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("script.sh")]);
+
+        let actual = nu!(cwd: dirs.test(), "fs chmod 600 script.sh | get mode.0");
+        assert_eq!(actual.out, "rw-------");
+
+        let actual = nu!(cwd: dirs.test(), "fs chmod u+x,g+r script.sh | get mode.0");
+        assert_eq!(actual.out, "rwxr-----");
+
+        let mode = dirs
+            .test()
+            .join("script.sh")
+            .metadata()
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o740);
+    })
+}
+
+#[test]
+fn changes_files_piped_from_ls() {
+    Playground::setup("chmod_test_ls", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt"), EmptyFile("b.txt"), EmptyFile("c.md")]);
+
+        let actual = nu!(cwd: dirs.test(), "ls *.txt | fs chmod 644 | length");
+        assert_eq!(actual.out, "2");
+    })
+}
+
+#[test]
+fn rejects_invalid_modes() {
+    Playground::setup("chmod_test_invalid", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "fs chmod u+q a.txt");
+        assert!(!actual.err.is_empty());
+    })
+}
+
+#[test]
+fn recursive_doesnt_follow_symlinks() {
+    Playground::setup("chmod_test_symlinks", |dirs, sandbox| {
+        sandbox
+            .within("dir")
+            .with_files(&[EmptyFile("inner.txt")])
+            .within("")
+            .with_files(&[EmptyFile("outside.txt")]);
+        let outside = dirs.test().join("outside.txt");
+        std::fs::set_permissions(&outside, std::fs::Permissions::from_mode(0o644)).unwrap();
+        std::os::unix::fs::symlink(&outside, dirs.test().join("dir/link")).unwrap();
+
+        let actual =
+            nu!(cwd: dirs.test(), "fs chmod -R 700 dir | get name | path basename | to nuon");
+        assert_eq!(actual.out, r#"[dir, "inner.txt"]"#);
+
+        let mode = outside.metadata().unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o644);
+    })
+}
@@ -0,0 +1,45 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+use std::os::unix::fs::MetadataExt;
+
+#[test]
+fn changes_group_to_own_group() {
+    Playground::setup("chown_test_group", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt")]);
+        let gid = dirs.test().join("a.txt").metadata().unwrap().gid();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("fs chown :{gid} a.txt | get name | path basename | to nuon")
+        );
+        assert_eq!(actual.out, r#"["a.txt"]"#);
+    })
+}
+
+#[test]
+fn recursive_changes_symlinks_instead_of_following_them() {
+    Playground::setup("chown_test_symlinks", |dirs, sandbox| {
+        sandbox.within("dir").with_files(&[EmptyFile("inner.txt")]);
+        // Following the dangling link would fail
+        std::os::unix::fs::symlink(dirs.test().join("missing"), dirs.test().join("dir/link"))
+            .unwrap();
+        let uid = dirs.test().join("dir").metadata().unwrap().uid();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            format!("fs chown -R {uid} dir | get name | path basename | sort | to nuon")
+        );
+        assert_eq!(actual.out, r#"[dir, "inner.txt", link]"#);
+    })
+}
+
+#[test]
+fn rejects_unknown_users() {
+    Playground::setup("chown_test_unknown", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "fs chown no_such_user_for_sure a.txt");
+        assert!(actual.err.contains("no user named"));
+    })
+}
@@ -0,0 +1,123 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn config_get_follows_path() {
+    let actual = nu!("$env.config.table.mode = 'light'; config get table.mode");
+
+    assert_eq!(actual.out, "light");
+}
+
+#[test]
+fn config_set_updates_option() {
+    let actual = nu!("config set table.mode compact; $env.config.table.mode");
+
+    assert_eq!(actual.out, "compact");
+}
+
+#[test]
+fn config_set_rejects_invalid_value() {
+    let actual = nu!(
+        "$env.config.table.mode = 'light'; try { config set table.mode nope }; $env.config.table.mode"
+    );
+
+    assert_eq!(actual.out, "light");
+}
+
+#[test]
+fn config_diff_reports_changed_option() {
+    let actual = nu!(
+        "$env.config.float_precision = 7; config diff | where path == float_precision | get 0.current"
+    );
+
+    assert_eq!(actual.out, "7");
+}
+
+#[test]
+fn config_validate_lists_errors() {
+    let actual = nu!(
+        "{ float_precision: nope, not_an_option: 1 } | config validate | get path | str join ','"
+    );
+
+    assert_eq!(actual.out, "float_precision,not_an_option");
+}
+
+#[test]
+fn config_validate_accepts_valid_record() {
+    let actual = nu!("{ table: { mode: rounded } } | config validate | is-empty");
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn no_profile_by_default() {
+    let actual = nu!("$nu.profile | describe");
+
+    assert_eq!(actual.out, "nothing");
+}
+
+#[test]
+fn config_migrate_moves_state_files() {
+    Playground::setup("config_migrate_moves_state_files", |dirs, sandbox| {
+        sandbox
+            .mkdir("config/nushell")
+            .mkdir("data")
+            .with_files(&[EmptyFile("config/nushell/history.txt")]);
+
+        let envs = vec![
+            (
+                "XDG_CONFIG_HOME".to_string(),
+                dirs.test().join("config").display().to_string(),
+            ),
+            (
+                "XDG_DATA_HOME".to_string(),
+                dirs.test().join("data").display().to_string(),
+            ),
+        ];
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: envs,
+            "config migrate | get status | str join ','"
+        );
+
+        assert_eq!(actual.out, "moved");
+        assert!(!dirs.test().join("config/nushell/history.txt").exists());
+        assert!(dirs.test().join("data/nushell/history.txt").exists());
+    })
+}
+
+#[test]
+fn config_migrate_skips_open_sqlite_history() {
+    Playground::setup(
+        "config_migrate_skips_open_sqlite_history",
+        |dirs, sandbox| {
+            sandbox.mkdir("config/nushell").mkdir("data").with_files(&[
+                EmptyFile("config/nushell/history.sqlite3"),
+                EmptyFile("config/nushell/history.sqlite3-wal"),
+            ]);
+
+            let envs = vec![
+                (
+                    "XDG_CONFIG_HOME".to_string(),
+                    dirs.test().join("config").display().to_string(),
+                ),
+                (
+                    "XDG_DATA_HOME".to_string(),
+                    dirs.test().join("data").display().to_string(),
+                ),
+            ];
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                envs: envs,
+                "config migrate | get status | str join ','"
+            );
+
+            assert_eq!(actual.out, "skipped, in use");
+            assert!(dirs.test().join("config/nushell/history.sqlite3").exists());
+            assert!(!dirs.test().join("data/nushell/history.sqlite3").exists());
+        },
+    )
+}
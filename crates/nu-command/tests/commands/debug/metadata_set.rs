@@ -1,4 +1,5 @@
 use nu_test_support::nu;
+use rstest::rstest;
 
 #[test]
 fn errors_on_conflicting_metadata_flags() {
@@ -78,3 +79,41 @@ fn custom_metadata_preserved_through_collect() {
 
     assert_eq!(actual.out, "custom_value");
 }
+
+#[rstest]
+#[case::csv("csv")]
+#[case::json("json")]
+#[case::md("md")]
+#[case::msgpack("msgpack")]
+#[case::msgpackz("msgpackz")]
+#[case::nuon("nuon")]
+#[case::text("text")]
+#[case::toml("toml")]
+#[case::tsv("tsv")]
+#[case::yaml("yaml")]
+fn to_keeps_arbitrary_metadata(#[case] format: &str) {
+    let actual = nu!(format!(
+        r#"
+    {{a: 1}}
+    | metadata set --merge {{custom_key: "custom_value"}}
+    | to {format}
+    | metadata
+    | get custom_key
+    "#
+    ));
+
+    assert_eq!(actual.out, "custom_value");
+}
+
+#[test]
+fn to_xml_keeps_arbitrary_metadata() {
+    let actual = nu!(r#"
+    {tag: a, attributes: {}, content: []}
+    | metadata set --merge {custom_key: "custom_value"}
+    | to xml
+    | metadata
+    | get custom_key
+    "#);
+
+    assert_eq!(actual.out, "custom_value");
+}
@@ -102,3 +102,39 @@ fn detect_columns_may_fail() {
         nu!(r#""meooooow cat\nkitty kitty woof" | try { detect columns } catch { "failed" }"#);
     assert_eq!(out.out, "failed");
 }
+
+#[test]
+fn detect_columns_with_widths() {
+    let actual = nu!(r#"
+        "PID  COMMAND     CPU\n1    init        0.0\n242  my server   12.5"
+        | detect columns --widths "5,12,4" --infer-types
+        | to nuon
+    "#);
+
+    assert_eq!(
+        actual.out,
+        r#"[[PID, COMMAND, CPU]; [1, init, 0.0], [242, "my server", 12.5]]"#
+    );
+}
+
+#[test]
+fn detect_columns_aligned_handles_ragged_values() {
+    let actual = nu!(r#"
+        "USER   SIZE  DESCRIPTION\nroot   4.0K  super user\nalice  12M   \nbob    1.5G  a b c"
+        | detect columns --aligned --infer-types
+        | update SIZE { into int }
+        | to nuon
+    "#);
+
+    assert_eq!(
+        actual.out,
+        r#"[[USER, SIZE, DESCRIPTION]; [root, 4096, "super user"], [alice, 12582912, null], [bob, 1610612736, "a b c"]]"#
+    );
+}
+
+#[test]
+fn detect_columns_rejects_invalid_widths() {
+    let actual = nu!(r#""a b" | detect columns --widths "3,x""#);
+
+    assert!(actual.err.contains("positive column widths"));
+}
@@ -0,0 +1,82 @@
+use nu_test_support::nu;
+
+#[test]
+fn env_export_writes_the_chosen_variables() {
+    let actual = nu!("$env.FOO = 'a b'; $env.BAR = 1; env export FOO");
+
+    assert_eq!(actual.out, "export FOO='a b'");
+}
+
+#[test]
+fn env_export_round_trips_through_env_import() {
+    for shell in ["posix", "csh", "fish", "powershell"] {
+        let actual = nu!(&format!(
+            r#"let vars = {{A: "it's", B: 'back\slash', C: '$HOME "quoted"'}}; $vars | env export --{shell} | env import --{shell} | $in == $vars"#
+        ));
+
+        assert_eq!(actual.out, "true", "{shell}");
+    }
+}
+
+#[test]
+fn env_export_rejects_several_shells() {
+    let actual = nu!("{A: 1} | env export --fish --posix");
+
+    assert!(actual.err.contains("only one of"));
+}
+
+#[test]
+fn env_export_requires_existing_variables() {
+    let actual = nu!("env export NOT_A_VARIABLE_FOR_SURE");
+
+    assert!(actual.err.contains("not found"));
+}
+
+#[test]
+fn env_export_writes_aliases() {
+    let actual = nu!(
+        "alias ll = ^ls -l; {} | env export --aliases | lines | where $it starts-with 'alias ll' | first"
+    );
+
+    assert_eq!(actual.out, "alias ll='ls -l'");
+}
+
+#[test]
+fn env_import_reads_ssh_agent_output() {
+    let actual = nu!(r#"
+        "SSH_AUTH_SOCK=/tmp/ssh-abc/agent.41; export SSH_AUTH_SOCK;\nSSH_AGENT_PID=42; export SSH_AGENT_PID;\necho Agent pid 42;"
+        | env import | load-env
+        $env.SSH_AGENT_PID
+    "#);
+
+    assert_eq!(actual.out, "42");
+}
+
+#[test]
+fn env_import_expands_current_variables() {
+    let actual =
+        nu!(r#"$env.BASE = "/opt"; 'export TOOL_HOME="$BASE/tool"' | env import | get TOOL_HOME"#);
+
+    assert_eq!(actual.out, "/opt/tool");
+}
+
+#[cfg(unix)]
+#[test]
+fn env_capture_applies_printed_exports() {
+    let actual = nu!(r#"
+        $env.OLD_VAR = "old"
+        env capture sh -c "echo 'EXTRA=/tmp/x; export EXTRA;'; echo 'unset OLD_VAR;'; echo 'echo done;'"
+        [$env.EXTRA ('OLD_VAR' in $env)] | to nuon
+    "#);
+
+    assert_eq!(actual.out, "[/tmp/x, false]");
+}
+
+#[cfg(unix)]
+#[test]
+fn env_capture_fails_with_the_program() {
+    let actual = nu!("env capture sh -c 'echo export A=1; exit 3'; $env.A?");
+
+    assert_eq!(actual.status.code(), Some(3));
+    assert_eq!(actual.out, "");
+}
@@ -0,0 +1,48 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn env_trust_records_hash_of_file() {
+    Playground::setup("env_trust_test_1", |dirs, sandbox| {
+        sandbox
+            .mkdir("data")
+            .with_files(&[FileWithContent(".nu-env", "$env.FOO = 'bar'")]);
+        let data_dir = dirs.test().join("data");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir.display().to_string())],
+            "env trust; open data/nushell/trusted-env.json | length"
+        );
+
+        assert_eq!(actual.out, "1");
+    })
+}
+
+#[test]
+fn env_untrust_removes_file() {
+    Playground::setup("env_trust_test_2", |dirs, sandbox| {
+        sandbox
+            .mkdir("data")
+            .with_files(&[FileWithContent(".nu-env", "$env.FOO = 'bar'")]);
+        let data_dir = dirs.test().join("data");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            envs: vec![("XDG_DATA_HOME".to_string(), data_dir.display().to_string())],
+            "env trust; env untrust; open data/nushell/trusted-env.json | length"
+        );
+
+        assert_eq!(actual.out, "0");
+    })
+}
+
+#[test]
+fn env_trust_without_nu_env_file_fails() {
+    Playground::setup("env_trust_test_3", |dirs, _| {
+        let actual = nu!(cwd: dirs.test(), "env trust");
+
+        assert!(actual.err.contains("No .nu-env file found"));
+    })
+}
@@ -0,0 +1,83 @@
+use nu_test_support::nu;
+
+#[test]
+fn env_watch_runs_closure_when_variable_changes() {
+    let actual = nu!(
+        "$env.FOO = 1; env watch FOO {|old, new| $env.SEEN = $'($old)->($new)' }; $env.FOO = 2; $env.SEEN"
+    );
+
+    assert_eq!(actual.out, "1->2");
+}
+
+#[test]
+fn env_watch_sees_load_env_and_unset_variables() {
+    let actual = nu!(
+        "env watch FOO {|old, new| $env.SEEN = ($env.SEEN? | default [] | append $'($old | to nuon)->($new)') }; load-env {FOO: a}; hide-env FOO; $env.SEEN | str join ','"
+    );
+
+    assert_eq!(actual.out, "null->a,\"a\"->");
+}
+
+#[test]
+fn env_watch_skips_unchanged_variables() {
+    let actual = nu!(
+        "$env.FOO = 1; env watch FOO {|old, new| $env.SEEN = $new }; $env.FOO = 1; $env.SEEN? | default nothing"
+    );
+
+    assert_eq!(actual.out, "nothing");
+}
+
+#[test]
+fn env_watch_ignores_changes_of_its_closure() {
+    let actual = nu!(
+        "env watch FOO {|old, new| $env.FOO = $new + 1; $env.RUNS = ($env.RUNS? | default 0) + 1 }; $env.FOO = 1; [$env.FOO $env.RUNS] | str join ','"
+    );
+
+    assert_eq!(actual.out, "2,1");
+}
+
+#[test]
+fn env_watch_sees_scoped_changes_without_their_revert() {
+    let actual = nu!(
+        "$env.FOO = a; env watch FOO {|old, new| print -n $'($old)->($new);' }; def scoped [] { $env.FOO = b }; scoped; $env.FOO = c"
+    );
+
+    assert_eq!(actual.out, "a->b;a->c;");
+}
+
+#[test]
+fn env_watch_sees_reverts_in_the_same_scope() {
+    let actual = nu!(
+        "$env.FOO = a; env watch FOO {|old, new| print -n $'($old)->($new);' }; do { $env.FOO = b; $env.FOO = a }; $env.FOO = c"
+    );
+
+    assert_eq!(actual.out, "a->b;b->a;a->c;");
+}
+
+#[test]
+fn env_watch_runs_once_for_changes_kept_by_env_commands() {
+    let actual = nu!(
+        "$env.FOO = a; env watch FOO {|old, new| print -n $'($old)->($new);' }; def --env set-foo [] { $env.FOO = b }; set-foo; $env.FOO = c"
+    );
+
+    assert_eq!(actual.out, "a->b;b->c;");
+}
+
+#[test]
+fn env_watch_runs_on_every_thread() {
+    let actual = nu!(
+        "env watch FOO {|old, new| sleep 50ms; print -n $new }; 1..4 | par-each {|i| $env.FOO = $i } | ignore"
+    );
+
+    let mut seen: Vec<_> = actual.out.chars().collect();
+    seen.sort();
+    assert_eq!(seen, ['1', '2', '3', '4']);
+}
+
+#[test]
+fn env_watch_requires_closure() {
+    let actual = nu!("env watch FOO 3");
+
+    assert!(actual.err.contains("nu::parser::parse_mismatch"));
+    assert!(actual.err.contains("expected block, closure or record"));
+}
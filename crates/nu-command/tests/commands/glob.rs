@@ -1,4 +1,4 @@
-use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
 use nu_test_support::nu;
 use nu_test_support::playground::Playground;
 use rstest::rstest;
@@ -204,3 +204,78 @@ fn glob_follow_symlinks() {
         );
     })
 }
+
+#[test]
+fn glob_list_with_negation() {
+    Playground::setup("glob_list_with_negation", |dirs, sandbox| {
+        sandbox.with_files(&[
+            EmptyFile("a.rs"),
+            EmptyFile("b.rs"),
+            EmptyFile("Cargo.toml"),
+            EmptyFile("README.md"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"glob ["*.rs" "*.toml" "*.{rs,md}" "!b.rs"] | path basename | sort | to nuon"#,
+        );
+
+        assert_eq!(actual.out, r#"["Cargo.toml", "README.md", "a.rs"]"#);
+    })
+}
+
+#[test]
+fn glob_respects_gitignore() {
+    Playground::setup("glob_respects_gitignore", |dirs, sandbox| {
+        sandbox.mkdir(".git").mkdir("target").with_files(&[
+            FileWithContent(".gitignore", "target/\n*.log\n"),
+            EmptyFile("main.rs"),
+            EmptyFile("debug.log"),
+            EmptyFile("target/app"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "glob '**/*' --gitignore --no-dir | path basename | to nuon",
+        );
+
+        assert_eq!(actual.out, r#"["main.rs"]"#);
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn glob_keeps_non_utf8_names() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    Playground::setup("glob_test_non_utf8", |dirs, _| {
+        std::fs::write(dirs.test().join(OsStr::from_bytes(b"caf\xe9.txt")), "")
+            .expect("file should be created");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "glob *.txt | each { [($in | describe) ($in | path exists)] } | to nuon"
+        );
+        assert_eq!(actual.out, r#"[[path, true]]"#);
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn glob_shows_non_utf8_names_in_path_display_style() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    Playground::setup("glob_test_path_display", |dirs, _| {
+        std::fs::write(dirs.test().join(OsStr::from_bytes(b"caf\xe9.txt")), "")
+            .expect("file should be created");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "$env.config.path_display = 'escaped'; glob *.txt | get 0 | path basename"
+        );
+        assert_eq!(actual.out, r"caf\xE9.txt");
+
+        let actual = nu!(cwd: dirs.test(), "glob *.txt | get 0 | path basename");
+        assert_eq!(actual.out, "caf\u{FFFD}.txt");
+    })
+}
@@ -0,0 +1,52 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn import_dynamic_runs_after_the_command_line() {
+    Playground::setup("import_dynamic_runs_after", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("setup.nu", "print loaded")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"let file = "setup" + ".nu"; import dynamic $file; print before"#);
+
+        assert_eq!(actual.out, "beforeloaded");
+    })
+}
+
+#[test]
+fn import_dynamic_module() {
+    Playground::setup("import_dynamic_module", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "spam.nu",
+            "export-env { print used }; export def greet [] { 'spam' }",
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), "import dynamic --module spam.nu; print before");
+
+        assert_eq!(actual.out, "beforeused");
+        assert!(actual.err.is_empty());
+    })
+}
+
+#[test]
+fn import_dynamic_missing_file() {
+    let actual = nu!("import dynamic does-not-exist.nu");
+
+    assert!(actual.err.contains("not found"));
+}
+
+#[test]
+fn import_dynamic_in_background_job() {
+    Playground::setup("import_dynamic_in_background_job", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("setup.nu", "print loaded")]);
+
+        let actual = nu!(cwd: dirs.test(), "
+            job spawn {
+                try { import dynamic setup.nu; 'queued' } catch { 'refused' } | job send 0
+            }
+            job recv --timeout 5sec
+        ");
+
+        assert_eq!(actual.out, "refused");
+    })
+}
@@ -131,3 +131,31 @@ fn invalid_str() {
 
     assert!(actual.err.contains("can't convert string to filesize"));
 }
+
+#[test]
+fn binary_str() {
+    let actual = nu!("'1.5 GiB' | into filesize | $in == 1.5GiB");
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn binary_flag() {
+    let actual = nu!("'2 mb' | into filesize --binary | $in == 2MiB");
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn unit_flag() {
+    let actual = nu!("[7 '7' '7kB'] | into filesize --unit KiB | $in == [7KiB 7KiB 7kB]");
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn invalid_unit() {
+    let actual = nu!("'1 kilo' | into filesize");
+
+    assert!(actual.err.contains("can't convert string to filesize"));
+}
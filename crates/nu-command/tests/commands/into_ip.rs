@@ -0,0 +1,24 @@
+use nu_test_support::nu;
+
+#[test]
+fn into_ip_tests_containment_in_networks() {
+    let actual = nu!(
+        "let network = '10.0.0.0/8' | into ip; [(('10.1.2.3' | into ip) in $network) (('11.0.0.1' | into ip) in $network)] | to nuon"
+    );
+
+    assert_eq!(actual.out, "[true, false]");
+}
+
+#[test]
+fn into_ip_sorts_numerically() {
+    let actual = nu!("['10.0.0.10' '10.0.0.9'] | into ip | sort | into string | to nuon");
+
+    assert_eq!(actual.out, r#"["10.0.0.9", "10.0.0.10"]"#);
+}
+
+#[test]
+fn into_ip_adds_to_addresses() {
+    let actual = nu!("('10.0.0.255' | into ip) + 1 | into string");
+
+    assert_eq!(actual.out, "10.0.1.0");
+}
@@ -415,7 +415,29 @@ fn list_all_columns() {
             "ls -l | columns | to md"
         );
         let expected = {
-            #[cfg(unix)]
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            {
+                [
+                    "name",
+                    "type",
+                    "target",
+                    "readonly",
+                    "mode",
+                    "num_links",
+                    "inode",
+                    "user",
+                    "group",
+                    "xattrs",
+                    "acl",
+                    "size",
+                    "created",
+                    "accessed",
+                    "modified",
+                ]
+                .join("")
+            }
+
+            #[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
             {
                 [
                     "name",
@@ -562,10 +584,10 @@ fn list_directory_contains_invalid_utf8() {
 
             std::fs::create_dir_all(path).expect("failed to create directory");
 
-            let actual = nu!(cwd: cwd, "ls");
+            let actual =
+                nu!(cwd: cwd, "ls | get name.0 | [($in | describe) ($in | path exists)] | to nuon");
 
-            assert!(actual.out.contains("warning: get non-utf8 filename"));
-            assert!(actual.err.contains("No matches found for"));
+            assert_eq!(actual.out, "[path, true]");
         },
     )
 }
@@ -780,3 +802,49 @@ fn consistent_list_order() {
         assert_eq!(no_arg.out, with_arg.out);
     })
 }
+
+#[test]
+fn fields_selects_columns() {
+    Playground::setup("ls_test_fields", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("los.txt"), EmptyFile("tres.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --fields [size inode] | columns | to nuon"
+        );
+
+        #[cfg(unix)]
+        assert_eq!(actual.out, "[name, inode, size]");
+        #[cfg(windows)]
+        assert_eq!(actual.out, "[name, size]");
+    })
+}
+
+#[test]
+fn fields_rejects_unknown_column() {
+    Playground::setup("ls_test_fields_unknown", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("los.txt")]);
+
+        let actual = nu!(cwd: dirs.test(), "ls --fields [colour]");
+
+        assert!(actual.err.contains("colour"));
+    })
+}
+
+#[test]
+fn unsorted_lists_all_entries() {
+    Playground::setup("ls_test_unsorted", |dirs, sandbox| {
+        sandbox.with_files(&[
+            EmptyFile("los.txt"),
+            EmptyFile("tres.txt"),
+            EmptyFile("amigos.txt"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --unsorted --fields [] | get name | sort | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["amigos.txt", "los.txt", "tres.txt"]"#);
+    })
+}
@@ -0,0 +1,33 @@
+use nu_test_support::nu;
+
+#[test]
+fn evaluates_every_row_of_a_table() {
+    let actual = nu!("[[x]; [1] [2]] | math eval 'x ^ 2 + 1' | to nuon");
+
+    assert_eq!(actual.out, "[2, 5]")
+}
+
+#[test]
+fn precision_rounds_halves_away_from_zero() {
+    let actual = nu!("math eval '-5 / 8' --precision 2");
+
+    assert_eq!(actual.out, "-0.63")
+}
+
+#[test]
+fn precision_is_limited() {
+    let actual = nu!("math eval '1 / 3' --precision 1000000");
+
+    assert!(
+        actual
+            .err
+            .contains("the precision has to be between 0 and 10000")
+    );
+}
+
+#[test]
+fn errors_point_into_the_expression() {
+    let actual = nu!("math eval '1 + y'");
+
+    assert!(actual.err.contains("`y` isn't defined"));
+}
@@ -8,10 +8,15 @@ mod break_;
 mod bytes;
 mod cal;
 mod cd;
+#[cfg(unix)]
+mod chmod;
+#[cfg(unix)]
+mod chown;
 mod chunk_by;
 mod chunks;
 mod compact;
 mod complete;
+mod config;
 mod config_env_default;
 mod config_nu_default;
 mod continue_;
@@ -29,6 +34,9 @@ mod du;
 mod each;
 mod echo;
 mod empty;
+mod env_export;
+mod env_trust;
+mod env_watch;
 mod error_make;
 mod every;
 mod exec;
@@ -51,6 +59,7 @@ mod headers;
 mod help;
 mod histogram;
 mod ignore;
+mod import_dynamic;
 mod insert;
 mod inspect;
 mod interleave;
@@ -58,6 +67,7 @@ mod into_datetime;
 mod into_duration;
 mod into_filesize;
 mod into_int;
+mod into_ip;
 mod join;
 mod last;
 mod length;
@@ -88,17 +98,22 @@ mod redirection;
 mod reduce;
 mod reject;
 mod rename;
+mod render;
 mod return_;
 mod reverse;
 mod rm;
 mod roll;
 mod rotate;
 mod run_external;
+mod run_sandboxed;
 mod save;
+mod schedule;
+mod search;
 mod select;
 mod semicolon;
 mod seq;
 mod seq_char;
+mod seq_cron;
 mod seq_date;
 mod skip;
 mod slice;
@@ -114,11 +129,13 @@ mod take;
 mod tee;
 mod terminal;
 mod to_text;
+mod translate;
 mod transpose;
 mod try_;
 mod ucp;
 #[cfg(unix)]
 mod ulimit;
+mod watch_var;
 mod window;
 
 mod debug;
@@ -132,6 +149,8 @@ mod upsert;
 mod url;
 mod use_;
 mod utouch;
+#[cfg(feature = "vcs")]
+mod vcs;
 mod where_;
 mod which;
 mod while_;
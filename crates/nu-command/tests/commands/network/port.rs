@@ -51,3 +51,45 @@ fn port_out_of_range() {
 
     assert!(actual.err.contains("can't convert usize to u16"));
 }
+
+#[test]
+fn port_scan_finds_open_and_closed_ports() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let open_port = listener.local_addr().unwrap().port();
+    let closed_port = {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+        listener.local_addr().unwrap().port()
+    };
+
+    let actual = nu!(format!(
+        "port scan 127.0.0.1 {open_port} {closed_port} | where open | get port | to nuon"
+    ));
+
+    assert_eq!(actual.out, format!("[{open_port}]"));
+}
+
+#[test]
+fn port_scan_rejects_invalid_ports() {
+    let actual = nu!("port scan 127.0.0.1 70000");
+
+    assert!(actual.err.contains("is not a port"));
+}
+
+#[test]
+fn port_scan_finds_open_port() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener.local_addr().unwrap().port();
+
+    let actual = nu!(format!(
+        "port scan 127.0.0.1 {port} --open | get port | to nuon"
+    ));
+
+    assert_eq!(actual.out, format!("[{port}]"));
+}
+
+#[test]
+fn port_scan_limits_concurrency() {
+    let actual = nu!("port scan 127.0.0.1 1..2 --concurrency 100000");
+
+    assert!(actual.err.contains("at most 1024"), "{}", actual.err);
+}
@@ -430,3 +430,139 @@ fn test_metadata_without_raw() {
         assert!(result.out.contains("appveyor.yml"));
     })
 }
+
+#[test]
+fn open_uses_registered_format() {
+    Playground::setup("open_test_registered_format", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("app.log", "INFO: started\nWARN: slow\n")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            format register .log {|text| $text | lines | parse "{level}: {message}" }
+            open app.log | get level | str join ","
+        "#);
+
+        assert_eq!(actual.out, "INFO,WARN");
+    })
+}
+
+#[test]
+fn open_raw_ignores_registered_format() {
+    Playground::setup("open_test_registered_format_raw", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("app.log", "INFO: started")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            format register log {|| lines | length }
+            open --raw app.log
+        "#);
+
+        assert_eq!(actual.out, "INFO: started");
+    })
+}
+
+#[test]
+fn open_follow_rejects_formats_without_lines() {
+    Playground::setup("open_test_follow_yaml", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("config.yaml", "a: 1\n")]);
+
+        let actual = nu!(cwd: dirs.test(), "open --follow config.yaml");
+
+        assert!(actual.err.contains("Can't follow a `.yaml` file"));
+    })
+}
+
+#[test]
+fn open_follow_only_follows_one_file() {
+    Playground::setup("open_test_follow_two_files", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.log"), EmptyFile("b.log")]);
+
+        let actual = nu!(cwd: dirs.test(), "open --follow a.log b.log");
+
+        assert!(actual.err.contains("Can only follow one file"));
+    })
+}
+
+#[test]
+fn binary_files_are_shown_as_hex() {
+    Playground::setup("open_test_binary_hex", |dirs, _| {
+        nu!(cwd: dirs.test(), "0x[00 ff fe 41] | save data.bin");
+
+        let actual = nu!(cwd: dirs.test(), "open data.bin | table | ansi strip");
+        assert!(actual.out.contains("00 ff fe 41"), "{}", actual.out);
+
+        let actual = nu!(cwd: dirs.test(), "open data.bin | describe");
+        assert_eq!(actual.out, "byte stream");
+    })
+}
+
+#[test]
+fn open_md_is_text() {
+    Playground::setup("open_md_is_text", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("doc.md", "# Title\n")]);
+
+        let actual = nu!(cwd: dirs.test(), "open doc.md | collect | describe");
+
+        assert_eq!(actual.out, "string");
+    })
+}
+
+#[test]
+fn open_follow_streams_appended_lines() {
+    Playground::setup("open_test_follow_appended", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("app.log", "old\n")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            job spawn { sleep 500ms; "one\ntw" | save -a app.log; sleep 300ms; "o\nthree\n" | save -a app.log }
+            open --follow app.log | first 3 | str join ','
+        "#);
+
+        assert_eq!(actual.out, "one,two,three");
+    })
+}
+
+#[test]
+fn open_follow_reads_truncated_file_again() {
+    Playground::setup("open_test_follow_truncated", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("app.log")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            job spawn { sleep 500ms; "a long line\n" | save -a app.log; sleep 500ms; "new\n" | save -f app.log }
+            open --follow app.log | first 2 | str join ','
+        "#);
+
+        assert_eq!(actual.out, "a long line,new");
+    })
+}
+
+#[test]
+fn open_follow_follows_rotated_file() {
+    Playground::setup("open_test_follow_rotated", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("app.log")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            job spawn {
+                sleep 500ms
+                "before\n" | save -a app.log
+                sleep 500ms
+                mv app.log app.log.1
+                "after\n" | save app.log
+            }
+            open --follow app.log | first 2 | str join ','
+        "#);
+
+        assert_eq!(actual.out, "before,after");
+    })
+}
+
+#[test]
+fn open_follow_parses_csv_with_header() {
+    Playground::setup("open_test_follow_csv", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("data.csv", "name,size\na,1\n")]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            job spawn { sleep 500ms; "b,2\nc,3\n" | save -a data.csv }
+            open --follow data.csv | first 2 | to nuon
+        "#);
+
+        assert_eq!(actual.out, "[[name, size]; [b, 2], [c, 3]]");
+    })
+}
@@ -91,6 +91,54 @@ mod simple {
             r#"[{"level":"INFO","entry":"all is well"},{"level":"ERROR","entry":"something bad happened"}]"#
         );
     }
+
+    #[test]
+    fn converts_typed_columns() {
+        let actual = nu!(r#"
+            "3 1.5 true 2KiB 1hr 30min"
+            | parse "{count:int} {ratio:float} {ok:bool} {size:filesize} {took:duration}"
+            | first
+            | values
+            | each { describe }
+            | str join ","
+        "#);
+
+        assert_eq!(actual.out, "int,float,bool,filesize,duration");
+    }
+
+    #[test]
+    fn errors_on_unknown_column_type() {
+        let actual = nu!(r#""a" | parse "{name:color}""#);
+
+        assert!(actual.err.contains("unknown type `color`"));
+    }
+
+    #[test]
+    fn errors_on_failed_conversion() {
+        let actual = nu!(r#""abc" | parse "{count:int}""#);
+
+        assert!(actual.err.contains("Can't convert"));
+    }
+
+    #[test]
+    fn extracts_records_spanning_multiple_lines() {
+        Playground::setup("parse_test_multiline_1", |dirs, sandbox| {
+            sandbox.with_files(&[Stub::FileWithContent(
+                "services.log",
+                "service: web\nstatus: up\nuptime: 3day\n\nservice: db\nstatus: down\nuptime: 0sec\n",
+            )]);
+
+            let actual = nu!(cwd: dirs.test(), r#"
+                open services.log
+                | parse --multiline "service: {name}\nstatus: {status}\nuptime: {uptime:duration}"
+                | where uptime > 1day
+                | get name
+                | to nuon
+            "#);
+
+            assert_eq!(actual.out, "[web]");
+        })
+    }
 }
 
 mod regex {
@@ -111,3 +111,13 @@ fn const_path_dirname() {
     let actual = nu!("const name = ('spam/eggs.txt' | path dirname); $name");
     assert_eq!(actual.out, "spam");
 }
+
+#[cfg(unix)]
+#[test]
+fn keeps_non_utf8_segments_of_path_values() {
+    let actual = nu!(r#"
+        0x[66 e9 2f 61] | into path | path dirname | ^printf '%s' $in | into binary | encode hex
+    "#);
+
+    assert_eq!(actual.out, "66E9");
+}
@@ -49,3 +49,12 @@ fn const_path_join() {
     let expected = join_path_sep(&["spam", "eggs.txt"]);
     assert_eq!(actual.out, expected);
 }
+
+#[test]
+fn joins_path_values_into_path_values() {
+    let actual = nu!(cwd: "tests", r#"
+        '/some/dir' | into path | path join spam.txt | describe
+    "#);
+
+    assert_eq!(actual.out, "path");
+}
@@ -40,3 +40,13 @@ fn const_path_relative_to() {
     let actual = nu!("'/home/viking' | path relative-to '/home'");
     assert_eq!(actual.out, "viking");
 }
+
+#[test]
+fn paths_and_strings_are_equal_from_both_sides() {
+    let actual = nu!(r#"
+        let path = 'a' | into path
+        [('a' in [$path]) ($path in ['a']) ([$path] | where {|x| 'a' == $x } | length)] | to nuon
+    "#);
+
+    assert_eq!(actual.out, "[true, true, 1]");
+}
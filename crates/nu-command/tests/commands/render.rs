@@ -0,0 +1,31 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn render_template_file() {
+    Playground::setup("render_template_file", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "report.j2",
+            "{% for row in input -%}\n{{ row.name }}={{ row.count | default(0) }};\n{%- endfor %}",
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), "[{name: a, count: 2} {name: b}] | render report.j2");
+
+        assert_eq!(actual.out, "a=2;b=0;");
+    })
+}
+
+#[test]
+fn render_date_filter() {
+    let actual = nu!(r#"{when: 2024-03-05} | render -i "{{ when | date('%d.%m.%Y') }}""#);
+
+    assert_eq!(actual.out, "05.03.2024");
+}
+
+#[test]
+fn render_reports_the_line_of_errors() {
+    let actual = nu!(r#"{} | render -i "ok\n{{ missing }}""#);
+
+    assert!(actual.err.contains("line 2: `missing` isn't defined"));
+}
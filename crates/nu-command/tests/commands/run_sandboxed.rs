@@ -0,0 +1,158 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn writing_files_is_denied() {
+    Playground::setup("run_sandboxed_test_1", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed { 'hello' | save hello.txt }"
+        );
+
+        assert!(actual.err.contains("nu::shell::sandbox_denied"));
+        assert!(!dirs.test().join("hello.txt").exists());
+    })
+}
+
+#[test]
+fn writing_files_can_be_allowed() {
+    Playground::setup("run_sandboxed_test_2", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "run-sandboxed --allow-write { 'hello' | save hello.txt }"
+        );
+
+        assert!(dirs.test().join("hello.txt").exists());
+    })
+}
+
+#[test]
+fn commands_can_be_allowed_by_name() {
+    Playground::setup("run_sandboxed_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("a.txt"), EmptyFile("b.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed --allow-commands [rm] { rm a.txt }; run-sandboxed { rm b.txt }"
+        );
+
+        assert!(!dirs.test().join("a.txt").exists());
+        assert!(dirs.test().join("b.txt").exists());
+        assert!(actual.err.contains("can't write files"));
+    })
+}
+
+#[test]
+fn externals_are_denied_unless_allowed() {
+    let actual = nu!("run-sandboxed { nu --testbin cococo hello }");
+    assert!(actual.err.contains("nu::shell::sandbox_denied"));
+
+    let actual = nu!("run-sandboxed --allow-externals [nu] { nu --testbin cococo hello }");
+    assert_eq!(actual.out, "hello");
+}
+
+#[test]
+fn network_is_denied() {
+    let actual = nu!("run-sandboxed { http get http://localhost:1 }");
+    assert!(actual.err.contains("can't use the network"));
+}
+
+#[test]
+fn reading_is_allowed() {
+    let actual = nu!("run-sandboxed { [1 2 3] | math sum }");
+    assert_eq!(actual.out, "6");
+}
+
+#[test]
+fn inner_sandbox_cant_allow_more() {
+    Playground::setup("run_sandboxed_test_4", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed { run-sandboxed --allow-write { 'hello' | save hello.txt } }"
+        );
+
+        assert!(actual.err.contains("nu::shell::sandbox_denied"));
+        assert!(!dirs.test().join("hello.txt").exists());
+    })
+}
+
+#[test]
+fn sandbox_is_left_after_the_closure() {
+    Playground::setup("run_sandboxed_test_5", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "run-sandboxed { 1 }; 'hello' | save hello.txt"
+        );
+
+        assert!(dirs.test().join("hello.txt").exists());
+    })
+}
+
+#[test]
+fn redirection_to_files_is_denied() {
+    Playground::setup("run_sandboxed_test_6", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed { nu --testbin cococo hello o> out.txt }"
+        );
+        assert!(actual.err.contains("nu::shell::sandbox_denied"));
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed --allow-externals [nu] { nu --testbin cococo hello o+e> out.txt }"
+        );
+        assert!(actual.err.contains("redirection can't write files"));
+        assert!(!dirs.test().join("out.txt").exists());
+    })
+}
+
+#[test]
+fn config_editor_is_denied() {
+    let actual = nu!("$env.EDITOR = 'nu'; run-sandboxed { config nu }");
+    assert!(actual.err.contains("nu::shell::sandbox_denied"));
+}
+
+#[test]
+fn background_jobs_are_denied() {
+    let actual = nu!("run-sandboxed { schedule add 1sec { 'hello' | save hello.txt } }");
+    assert!(actual.err.contains("can't run processes"));
+
+    let actual = nu!("run-sandboxed { job spawn { 'hello' | save hello.txt } }");
+    assert!(actual.err.contains("can't run processes"));
+}
+
+#[test]
+fn import_dynamic_is_denied() {
+    Playground::setup("run_sandboxed_test_7", |dirs, sandbox| {
+        sandbox.with_files(&[EmptyFile("script.nu")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "run-sandboxed { import dynamic script.nu }"
+        );
+        assert!(actual.err.contains("nu::shell::sandbox_denied"));
+    })
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_databases_are_read_only() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "run-sandboxed { open sample.db | columns | length }"
+    );
+    assert_eq!(actual.out, "3");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "run-sandboxed { open sample.db | query db 'DELETE FROM ints' }"
+    );
+    assert!(actual.err.contains("can't write files"));
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "run-sandboxed --allow-commands ['query db'] { open sample.db | query db 'DELETE FROM ints' }"
+    );
+    assert!(actual.err.contains("readonly"));
+}
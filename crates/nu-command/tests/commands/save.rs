@@ -555,3 +555,119 @@ fn force_save_to_dir() {
 
     assert!(actual.err.contains("nu::shell::io::is_a_directory"));
 }
+
+#[test]
+fn save_atomic_replaces_the_file() {
+    Playground::setup("save_test_atomic", |dirs, sandbox| {
+        sandbox.with_files(&[Stub::FileWithContent("config.txt", "old")]);
+
+        let actual = nu!(cwd: dirs.test(), "'new' | save --force --atomic config.txt");
+        assert!(actual.err.is_empty());
+
+        assert_eq!(file_contents(dirs.test().join("config.txt")), "new");
+        // only the saved file is left
+        let actual = nu!(cwd: dirs.test(), "ls -a | length");
+        assert_eq!(actual.out, "1");
+    })
+}
+
+#[test]
+fn save_atomic_with_append_fails() {
+    Playground::setup("save_test_atomic_append", |dirs, _| {
+        let actual = nu!(cwd: dirs.test(), "'new' | save --append --atomic log.txt");
+        assert!(actual.err.contains("--atomic"));
+    })
+}
+
+#[test]
+fn save_append_rotates_by_size() {
+    Playground::setup("save_test_rotate", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "[aaaa bbbb cccc dddd eeee] | each { $in } | save --raw --append --rotate-size 8B --rotate-keep 1 log.txt"
+        );
+
+        assert_eq!(file_contents(dirs.test().join("log.txt")), "eeee\n");
+        assert_eq!(file_contents(dirs.test().join("log.txt.1")), "cccc\ndddd\n");
+        assert!(!dirs.test().join("log.txt.2").exists());
+    })
+}
+
+#[test]
+fn save_rotates_between_lines() {
+    Playground::setup("save_test_rotate_lines", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            r#"["aaaaaa" "\nbb\n"] | each { into binary } | bytes collect | save --rotate-size 4B log.txt"#
+        );
+
+        assert_eq!(file_contents(dirs.test().join("log.txt")), "bb\n");
+        assert_eq!(file_contents(dirs.test().join("log.txt.1")), "aaaaaa\n");
+    })
+}
+
+#[test]
+fn save_rotate_size_has_to_be_positive() {
+    Playground::setup("save_test_rotate_zero", |dirs, _| {
+        let actual = nu!(cwd: dirs.test(), "'a' | save --rotate-size 0B log.txt");
+
+        assert!(actual.err.contains("has to be positive"));
+        assert!(!dirs.test().join("log.txt").exists());
+    })
+}
+
+#[test]
+fn save_partition_by_writes_a_file_per_key() {
+    Playground::setup("save_test_partition", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            r#"[[day, n]; [mon, 1] [tue, 2] [mon, 3]] | save --partition-by {|row| $"($row.day).csv" }"#
+        );
+
+        assert_eq!(
+            file_contents(dirs.test().join("mon.csv")),
+            "day,n\nmon,1\nmon,3\n"
+        );
+        assert_eq!(file_contents(dirs.test().join("tue.csv")), "day,n\ntue,2\n");
+    })
+}
+
+#[test]
+fn save_without_extension_uses_content_type() {
+    Playground::setup("save_test_content_type", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            "{a: 1} | metadata set --content-type application/json | save out"
+        );
+
+        let actual = nu!(cwd: dirs.test(), "open out --raw | from json | get a");
+        assert_eq!(actual.out, "1");
+    })
+}
+
+#[test]
+fn save_uses_registered_format() {
+    Playground::setup("save_test_registered_format", |dirs, _| {
+        nu!(cwd: dirs.test(), r#"
+            format register log --to {|| each { $"($in.level): ($in.message)" } | str join "\n" }
+            [[level, message]; [INFO, started] [WARN, slow]] | save app.log
+        "#);
+
+        assert_eq!(
+            file_contents(dirs.test().join("app.log")),
+            "INFO: started\nWARN: slow"
+        );
+    })
+}
+
+#[test]
+fn save_uses_registered_content_type() {
+    Playground::setup("save_test_registered_content_type", |dirs, _| {
+        nu!(cwd: dirs.test(), r#"
+            format register tally --to {|| length | into string } --content-type text/x-tally
+            [a b c] | metadata set --content-type text/x-tally | save out
+        "#);
+
+        assert_eq!(file_contents(dirs.test().join("out")), "3");
+    })
+}
@@ -0,0 +1,41 @@
+use nu_test_support::nu;
+
+#[test]
+fn schedule_runs_closure_with_its_time() {
+    let actual = nu!(r#"
+        schedule add 10ms { |time| $time | describe | job send 0 }
+        job recv --timeout 10sec"#);
+
+    assert_eq!(actual.out, "datetime");
+}
+
+#[test]
+fn schedule_list_and_remove() {
+    let actual = nu!(r#"
+        let id = schedule add --name report '0 9 * * mon-fri' { }
+        let listed = schedule list | select name schedule runs
+        schedule remove $id
+        [$listed (schedule list | length) (job list | length)] | to nuon"#);
+
+    assert_eq!(
+        actual.out,
+        r#"[[[name, schedule, runs]; [report, "0 9 * * mon-fri", 0]], 0, 0]"#
+    );
+}
+
+#[test]
+fn schedule_records_failed_runs() {
+    let actual = nu!(r#"
+        schedule add 10ms { error make { msg: boom } }
+        sleep 500ms
+        schedule list | get 0 | [$in.last_status ($in.runs > 0)] | to nuon"#);
+
+    assert_eq!(actual.out, "[error, true]");
+}
+
+#[test]
+fn schedule_invalid_cron() {
+    let actual = nu!("schedule add '0 25 * * *' { }");
+
+    assert!(actual.err.contains("Invalid cron expression"));
+}
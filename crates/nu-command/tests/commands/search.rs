@@ -0,0 +1,148 @@
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn search_files_by_name() {
+    Playground::setup("search_files_test_1", |dirs, sandbox| {
+        sandbox.mkdir("src").mkdir("src/nested").with_files(&[
+            EmptyFile("Cargo.toml"),
+            EmptyFile("src/lib.rs"),
+            EmptyFile("src/nested/mod.rs"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --name '*.rs' | get path | path basename | sort | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["lib.rs", "mod.rs"]"#);
+    })
+}
+
+#[test]
+fn search_files_by_type_and_depth() {
+    Playground::setup("search_files_test_2", |dirs, sandbox| {
+        sandbox
+            .mkdir("a")
+            .mkdir("a/b")
+            .mkdir("a/b/c")
+            .with_files(&[EmptyFile("a/file.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --type dir --max-depth 2 | get path | path basename | sort | to nuon"
+        );
+
+        assert_eq!(actual.out, "[a, b]");
+    })
+}
+
+#[test]
+fn search_files_respects_gitignore_and_hidden() {
+    Playground::setup("search_files_test_3", |dirs, sandbox| {
+        sandbox.mkdir(".git").with_files(&[
+            FileWithContent(".gitignore", "*.log\n"),
+            EmptyFile("kept.txt"),
+            EmptyFile("skipped.log"),
+            EmptyFile(".hidden.txt"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --type file | get path | to nuon"
+        );
+        assert_eq!(actual.out, r#"["kept.txt"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --type file --no-ignore --hidden --name '*.log' | get path | to nuon"
+        );
+        assert_eq!(actual.out, r#"["skipped.log"]"#);
+    })
+}
+
+#[test]
+fn search_files_by_size() {
+    Playground::setup("search_files_test_4", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("small.txt", "a"),
+            FileWithContent("large.txt", "a lot more content"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --min-size 5B | get path | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["large.txt"]"#);
+    })
+}
+
+#[test]
+fn search_files_exec_runs_closure_per_match() {
+    Playground::setup("search_files_test_5", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("one.txt", "1"),
+            FileWithContent("two.txt", "2"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search files --exec {|f| open $f.path | into int } | math sum"
+        );
+
+        assert_eq!(actual.out, "3");
+    })
+}
+
+#[test]
+fn search_text_returns_structured_matches() {
+    Playground::setup("search_text_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("a.txt", "first line\nversion = 1.2\n"),
+            FileWithContent("b.md", "no match here\n"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"search text 'version = (?<v>[0-9.]+)' | first | [$in.path $in.line $in.column $in.captures.v] | to nuon"#
+        );
+
+        assert_eq!(actual.out, r#"["a.txt", 2, 1, "1.2"]"#);
+    })
+}
+
+#[test]
+fn search_text_fixed_strings_and_glob() {
+    Playground::setup("search_text_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("a.rs", "let x = a.b(c);\n"),
+            FileWithContent("a.txt", "let x = a.b(c);\n"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search text --fixed-strings 'a.b(c)' --glob '*.rs' | get path | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["a.rs"]"#);
+    })
+}
+
+#[test]
+fn search_text_skips_binary_files() {
+    Playground::setup("search_text_test_3", |dirs, sandbox| {
+        sandbox.with_files(&[
+            FileWithContent("text.txt", "needle\n"),
+            FileWithContent("binary.bin", "needle\0\n"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "search text needle | get path | to nuon"
+        );
+
+        assert_eq!(actual.out, r#"["text.txt"]"#);
+    })
+}
@@ -0,0 +1,39 @@
+use nu_test_support::nu;
+
+// The times are local, so the tests don't depend on the hour of the day
+
+#[test]
+fn weekdays_of_a_schedule() {
+    let actual = nu!(
+        "seq cron '0 0 * * mon-fri' --begin-date 2024-03-02T12:00:00Z --take 5 | format date '%a' | str join ','"
+    );
+
+    assert_eq!(actual.out, "Mon,Tue,Wed,Thu,Fri");
+}
+
+#[test]
+fn times_until_the_end_date() {
+    let actual = nu!(
+        "seq cron '0 * * * *' --begin-date 2024-03-02T12:10:00Z --end-date 2024-03-02T15:10:00Z | length"
+    );
+
+    assert_eq!(actual.out, "3");
+}
+
+#[test]
+fn endless_without_limits() {
+    let actual = nu!("seq cron '* * * * *' | first 3 | length");
+
+    assert_eq!(actual.out, "3");
+}
+
+#[test]
+fn fails_on_invalid_expression() {
+    let actual = nu!("seq cron '60 * * * *'");
+
+    assert!(
+        actual.err.contains("Invalid cron expression"),
+        "{}",
+        actual.err
+    );
+}
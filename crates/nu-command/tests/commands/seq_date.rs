@@ -17,3 +17,28 @@ fn fails_when_increment_not_integer_or_duration() {
             .contains("expected one of a list of accepted shapes: [Duration, Int]")
     )
 }
+
+#[test]
+fn business_days_skip_weekends() {
+    let actual = nu!("seq date --begin-date 2020-01-03 --days 5 --business-days | str join ','");
+
+    assert_eq!(
+        actual.out,
+        "2020-01-03,2020-01-06,2020-01-07,2020-01-08,2020-01-09"
+    );
+}
+
+#[test]
+fn business_days_in_reverse() {
+    let actual =
+        nu!("seq date --begin-date 2020-01-06 --days 2 --business-days --reverse | str join ','");
+
+    assert_eq!(actual.out, "2020-01-06,2020-01-03");
+}
+
+#[test]
+fn business_days_fail_for_weeks_from_a_weekend() {
+    let actual = nu!("seq date --begin-date 2020-01-04 --increment 7day --days 3 --business-days");
+
+    assert!(actual.err.contains("No business days"), "{}", actual.err);
+}
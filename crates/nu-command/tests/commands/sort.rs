@@ -56,6 +56,29 @@ fn sort_natural() {
     assert_eq!(actual.out, r#"["1", "2", "3", "4", "5", "10", "100"]"#);
 }
 
+#[test]
+fn sort_nan_after_numbers() {
+    let actual = nu!("[NaN 2 1.5 NaN 1] | sort | to nuon");
+
+    assert_eq!(actual.out, "[1, 1.5, 2, NaN, NaN]");
+}
+
+#[test]
+fn sort_locale() {
+    let actual = nu!("[zon öl Ål ask] | sort --locale sv_SE.UTF-8 | to nuon");
+    assert_eq!(actual.out, r#"[ask, zon, Ål, öl]"#);
+
+    let actual = nu!("[zon öl Ål ask] | sort --locale de | to nuon");
+    assert_eq!(actual.out, r#"[Ål, ask, öl, zon]"#);
+}
+
+#[test]
+fn sort_locale_natural() {
+    let actual = nu!("['étape 10' 'etape 9' 'Étape 1'] | sort --natural --locale fr | to nuon");
+
+    assert_eq!(actual.out, r#"["Étape 1", "etape 9", "étape 10"]"#);
+}
+
 #[test]
 fn sort_record_natural() {
     let actual = nu!("{10:0,99:0,1:0,9:0,100:0} | sort -n | to nuon");
@@ -107,3 +130,18 @@ fn sort_empty() {
 
     assert_eq!(actual.out, "[]");
 }
+
+#[test]
+fn sort_strings_and_paths() {
+    let actual = nu!(r#"
+        ["d", ("c" | into path), "b", ("a" | into path), "e"]
+        | sort
+        | each { $"($in | describe) ($in)" }
+        | to nuon
+    "#);
+
+    assert_eq!(
+        actual.out,
+        r#"["path a", "string b", "path c", "string d", "string e"]"#
+    );
+}
@@ -110,3 +110,15 @@ fn fail_on_non_iterator() {
 
     assert!(actual.err.contains("command doesn't support"));
 }
+
+#[test]
+fn sort_by_locale() {
+    let actual = nu!("
+        [[name]; [Zoë] [Émile] [Eva] [Åsa]]
+        | sort-by name --locale sv
+        | get name
+        | to nuon
+    ");
+
+    assert_eq!(actual.out, "[Émile, Eva, Zoë, Åsa]");
+}
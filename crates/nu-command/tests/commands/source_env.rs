@@ -349,3 +349,80 @@ fn use_after_source_should_not_error() {
         assert!(actual.err.is_empty());
     })
 }
+
+#[test]
+fn source_passes_arguments_as_argv() {
+    Playground::setup("source_passes_arguments_as_argv", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent("setup.nu", "$argv | str join ','")]);
+
+        let actual = nu!(cwd: dirs.test(), "source setup.nu -- --env prod 3");
+
+        assert_eq!(actual.out, "--env,prod,3");
+    })
+}
+
+#[test]
+fn source_rejects_arguments_before_double_dash() {
+    Playground::setup(
+        "source_rejects_arguments_before_double_dash",
+        |dirs, sandbox| {
+            sandbox.with_files(&[FileWithContent("setup.nu", "$argv | length")]);
+
+            let actual = nu!(cwd: dirs.test(), "source setup.nu --evn prod");
+            assert!(actual.err.contains("--evn"), "{}", actual.err);
+
+            let actual = nu!(cwd: dirs.test(), "source setup.nu prod");
+            assert!(actual.err.contains("go after `--`"), "{}", actual.err);
+        },
+    )
+}
+
+#[test]
+fn source_argv_is_not_visible_after_the_file() {
+    Playground::setup(
+        "source_argv_is_not_visible_after_the_file",
+        |dirs, sandbox| {
+            sandbox.with_files(&[FileWithContent("setup.nu", "print ($argv | length)")]);
+
+            let actual = nu!(cwd: dirs.test(), "source setup.nu -- a; $argv");
+            assert!(actual.err.contains("Variable not found"), "{}", actual.err);
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                "def f [...argv] { source setup.nu -- a b; $argv | length }; f x"
+            );
+            assert_eq!(actual.out, "21");
+        },
+    )
+}
+
+#[test]
+fn source_without_arguments_has_empty_argv() {
+    Playground::setup(
+        "source_without_arguments_has_empty_argv",
+        |dirs, sandbox| {
+            sandbox.with_files(&[FileWithContent("setup.nu", "$argv | length")]);
+
+            let actual = nu!(cwd: dirs.test(), "source setup.nu");
+
+            assert_eq!(actual.out, "0");
+        },
+    )
+}
+
+#[test]
+fn source_in_module_is_relative_to_module() {
+    Playground::setup("source_in_module_is_relative_to_module", |dirs, sandbox| {
+        sandbox.mkdir("lib").with_files(&[
+            FileWithContent("lib/helper.nu", "def helper [] { 'helped' }"),
+            FileWithContent(
+                "lib/spam.nu",
+                "export def greet [] { source helper.nu; helper }",
+            ),
+        ]);
+
+        let actual = nu!(cwd: dirs.test(), "use lib/spam.nu; spam greet");
+
+        assert_eq!(actual.out, "helped");
+    })
+}
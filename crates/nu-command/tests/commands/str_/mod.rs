@@ -237,6 +237,28 @@ fn find_and_replaces_regex_with_closure() {
     assert_eq!(actual.out, "source tr from string");
 }
 
+#[test]
+fn find_and_replaces_regex_with_named_captures() {
+    let actual = nu!(r#"
+         'price: 10, tax: 2'
+         | str replace -a --named-captures '(?<key>[a-z]+): (?<amount>[0-9]+)' {|c|
+           $"($c.key | str upcase)=($c.amount | into int | $in * 3)"
+         }
+     "#);
+
+    assert_eq!(actual.out, "PRICE=30, TAX=6");
+}
+
+#[test]
+fn find_and_replaces_with_unnamed_captures_record() {
+    let actual = nu!(r#"
+         'a-b'
+         | str replace --named-captures '(.)-(?<second>.)' {|c| $"($c.second)-($c.capture0)" }
+     "#);
+
+    assert_eq!(actual.out, "b-a");
+}
+
 #[test]
 fn find_and_replaces_closure_error() {
     let actual = nu!(r#"
@@ -470,3 +492,23 @@ fn test_redirection_trim() {
 
     assert_eq!(actual.out, "7");
 }
+
+#[test]
+fn grapheme_clusters_default_from_config() {
+    let actual = nu!(r#"
+        $env.config.strings.grapheme_clusters = true
+        [('🇯🇵ほげ' | str length) ('🇯🇵ほげ' | str length --chars) ('🇯🇵ほげ' | split chars | length)] | to nuon
+    "#);
+
+    assert_eq!(actual.out, "[3, 4, 3]");
+}
+
+#[test]
+fn str_width_ambiguous_width_from_config() {
+    let actual = nu!(r#"
+        $env.config.strings.ambiguous_width = "wide"
+        [('±5°' | str width) ('±5°' | str width --ambiguous-width narrow)] | to nuon
+    "#);
+
+    assert_eq!(actual.out, "[5, 3]");
+}
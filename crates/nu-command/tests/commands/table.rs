@@ -343,6 +343,22 @@ fn table_expand_deep_1() {
     );
 }
 
+#[test]
+fn table_expand_depth_from_config() {
+    let actual = nu!(
+        "$env.config.table.expand_depth = 0; [[a b, c]; [1 2 3] [4 5 [1 2 [1 2 3]]]] | table --width=80 --expand"
+    );
+    assert_eq!(
+        actual.out,
+        "╭───┬───┬───┬────────────────╮\
+         │ # │ a │ b │       c        │\
+         ├───┼───┼───┼────────────────┤\
+         │ 0 │ 1 │ 2 │              3 │\
+         │ 1 │ 4 │ 5 │ [list 3 items] │\
+         ╰───┴───┴───┴────────────────╯"
+    );
+}
+
 #[test]
 fn table_expand_flatten_0() {
     let actual =
@@ -0,0 +1,31 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn translated_script_parses() {
+    let actual = nu!(r#"
+        "NAME=world\nexport GREETING=\"hello $NAME\"\nif [ -n \"$GREETING\" ]; then\n  echo \"$GREETING\"\nfi\ncase $1 in\n  *) exit 1;;\nesac"
+        | translate
+        | nu-check
+    "#);
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn translates_a_file() {
+    Playground::setup("translate_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContent(
+            "script.sh",
+            "#!/bin/sh\nset -e\ntrap 'rm -f tmp' EXIT\n",
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), "translate script.sh");
+
+        assert_eq!(
+            actual.out,
+            "# nushell stops at the first error, like `set -e`# TODO: trap has no direct equivalent# trap 'rm -f tmp' EXIT"
+        );
+    });
+}
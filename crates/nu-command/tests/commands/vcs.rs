@@ -0,0 +1,53 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+/// Set up the bare minimum of a git repository without any commits.
+fn unborn_repository(sandbox: &mut Playground) {
+    sandbox
+        .mkdir(".git/objects")
+        .mkdir(".git/refs/heads")
+        .with_files(&[
+            FileWithContent(".git/HEAD", "ref: refs/heads/main\n"),
+            FileWithContent(
+                ".git/config",
+                "[core]\n\trepositoryformatversion = 0\n\tbare = false\n",
+            ),
+        ]);
+}
+
+#[test]
+fn vcs_status_reads_unborn_branch() {
+    Playground::setup("vcs_status_test_1", |dirs, sandbox| {
+        unborn_repository(sandbox);
+
+        let actual = nu!(cwd: dirs.test(), "
+            vcs status --no-files | $'($in.branch) ($in.head == null) ($in.detached) ($in.ahead == null)'
+        ");
+
+        assert_eq!(actual.out, "main true false true");
+    })
+}
+
+#[test]
+fn vcs_status_lists_untracked_files() {
+    Playground::setup("vcs_status_test_2", |dirs, sandbox| {
+        unborn_repository(sandbox);
+        sandbox.with_files(&[FileWithContent("notes.txt", "hello")]);
+
+        let actual = nu!(cwd: dirs.test(), "
+            vcs status | $'($in.dirty) ($in.untracked | str join)'
+        ");
+
+        assert_eq!(actual.out, "true notes.txt");
+    })
+}
+
+#[test]
+fn vcs_status_outside_of_repository_fails() {
+    Playground::setup("vcs_status_test_3", |dirs, _| {
+        let actual = nu!(cwd: dirs.test(), "vcs status");
+
+        assert!(actual.err.contains("Not a git repository"));
+    })
+}
@@ -0,0 +1,39 @@
+use nu_test_support::{nu, nu_repl_code};
+
+#[test]
+fn watch_var_runs_closure_when_variable_changes() {
+    let actual = nu!(nu_repl_code(&[
+        "mut count = 1",
+        "watch-var $count {|old, new| $env.SEEN = $'($old)->($new)' }",
+        "$count = 2",
+        "$env.SEEN",
+    ]));
+
+    assert_eq!(actual.out, "1->2");
+}
+
+#[test]
+fn watch_var_skips_unchanged_variables() {
+    let actual = nu!(nu_repl_code(&[
+        "mut count = 1",
+        "watch-var $count {|old, new| $env.SEEN = $new }",
+        "$count = 1",
+        "$env.SEEN? | default nothing",
+    ]));
+
+    assert_eq!(actual.out, "nothing");
+}
+
+#[test]
+fn watch_var_on_env_runs_closure_when_variable_changes() {
+    let actual = nu!("watch-var $env.FOO? {|old, new| $env.SEEN = $new }; $env.FOO = 1; $env.SEEN");
+
+    assert_eq!(actual.out, "1");
+}
+
+#[test]
+fn watch_var_rejects_immutable_variables() {
+    let actual = nu!("let count = 1; watch-var $count {|old, new| }");
+
+    assert!(actual.err.contains("mut"));
+}
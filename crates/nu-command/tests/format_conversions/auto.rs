@@ -0,0 +1,70 @@
+use nu_test_support::fs::Stub::FileWithContentToBeTrimmed;
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn from_auto_parses_aligned_table() {
+    let actual = nu!(r#"
+        "NAME    STATUS    RESTARTS\nweb     Running   0\ndb      Pending   12"
+        | from auto
+        | get 1.RESTARTS
+    "#);
+
+    assert_eq!(actual.out, "12");
+}
+
+#[test]
+fn from_auto_keeps_text_below_threshold() {
+    let actual = nu!(r#"
+        "a,b\n1,2\n3\n4" | from auto --threshold 0.9 | describe
+    "#);
+
+    assert_eq!(actual.out, "string");
+}
+
+#[test]
+fn from_auto_rejects_invalid_threshold() {
+    let actual = nu!(r#"'{"a": 1}' | from auto --threshold 2"#);
+
+    assert!(actual.err.contains("between 0 and 1"));
+}
+
+#[test]
+fn auto_parse_converts_external_output() {
+    Playground::setup("auto_parse_test_1", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContentToBeTrimmed(
+            "services.csv",
+            r#"
+                name,port
+                web,80
+                db,5432
+            "#,
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            $env.config.auto_parse.enabled = true
+            nu --testbin meow services.csv | where name == db | get 0.port
+        "#);
+
+        assert_eq!(actual.out, "5432");
+    })
+}
+
+#[test]
+fn auto_parse_is_opt_in() {
+    Playground::setup("auto_parse_test_2", |dirs, sandbox| {
+        sandbox.with_files(&[FileWithContentToBeTrimmed(
+            "services.csv",
+            r#"
+                name,port
+                web,80
+            "#,
+        )]);
+
+        let actual = nu!(cwd: dirs.test(), r#"
+            nu --testbin meow services.csv | where name == web
+        "#);
+
+        assert!(actual.err.contains("only_supports_this_input_type"));
+    })
+}
@@ -0,0 +1,52 @@
+use nu_test_support::nu;
+
+#[test]
+fn signed_tokens_verify_with_their_secret() {
+    let actual = nu!(r#"
+        {sub: alice admin: true}
+        | jwt sign secret --alg HS512 --header {kid: main}
+        | jwt verify secret
+        | [$in.header.alg $in.header.kid $in.claims.admin]
+        | to nuon
+    "#);
+
+    assert_eq!(actual.out, r#"["HS512", main, true]"#);
+}
+
+#[test]
+fn tokens_with_another_secret_are_invalid() {
+    let actual = nu!("{sub: alice} | jwt sign secret | jwt verify other");
+
+    assert!(actual.err.contains("Invalid JWT signature"));
+}
+
+#[test]
+fn expired_tokens_are_invalid() {
+    let actual = nu!("{sub: alice exp: 1} | jwt sign secret | jwt verify secret");
+
+    assert!(actual.err.contains("the token expired at 1970-01-01"));
+
+    let actual = nu!(
+        "{sub: alice exp: 1} | jwt sign secret | jwt verify secret --ignore-time | get claims.sub"
+    );
+
+    assert_eq!(actual.out, "alice");
+}
+
+#[test]
+fn secrets_only_verify_hmac_tokens() {
+    let actual = nu!("{sub: alice} | jwt sign secret | jwt verify secret --alg RS256");
+
+    assert!(
+        actual
+            .err
+            .contains("the token is signed with HS256, not RS256")
+    );
+}
+
+#[test]
+fn from_jwt_decodes_without_verifying() {
+    let actual = nu!("{sub: alice} | jwt sign secret | from jwt | get claims.sub");
+
+    assert_eq!(actual.out, "alice");
+}
@@ -75,3 +75,53 @@ fn md_combined() {
         "# Nu top meals| dish  || ----- || Arepa || Taco  || Pizza |"
     );
 }
+
+#[test]
+fn from_md_to_md_round_trip() {
+    let document = "---\ntitle: Post\n---\n\n# Post\n\nSome *text*\nin a paragraph.\n\n| name |     size |\n|:---- | --------:|\n| a    | `1 \\| 2` |\n\n```nu\nls | first\n```\n\n1. one\n2. two\n\n> quoted";
+
+    let actual = nu!(format!(
+        "'{document}' | from md | to md --pretty | $in == '{document}'"
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn from_md_front_matter() {
+    let actual = nu!(r#"
+        "---\ntags: [nu, shell]\n---\ntext" | from md | get 0.data.tags.1
+    "#);
+
+    assert_eq!(actual.out, "shell");
+}
+
+#[test]
+fn from_md_leading_rule() {
+    let actual = nu!(r#"
+        "---\nJust text.\n\n---\nmore" | from md | get type | str join ","
+    "#);
+
+    assert_eq!(actual.out, "rule,paragraph,rule,paragraph");
+}
+
+#[test]
+fn to_md_table_with_type_column() {
+    let actual = nu!(r#"
+        [[type text]; [paragraph a] [paragraph b]] | insert size 1 | to md
+    "#);
+
+    assert_eq!(
+        actual.out,
+        "| type | text | size || --- | --- | --- || paragraph | a | 1 || paragraph | b | 1 |"
+    );
+}
+
+#[test]
+fn to_md_pads_by_display_width() {
+    let actual = nu!(r#"
+        [{type: table, align: [right left], rows: [[a b]; [日本 x]]}] | to md --pretty
+    "#);
+
+    assert_eq!(actual.out, "|    a | b   || ----:|:--- || 日本 | x   |");
+}
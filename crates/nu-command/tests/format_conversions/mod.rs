@@ -1,11 +1,14 @@
+mod auto;
 mod csv;
 mod html;
 mod json;
+mod jwt;
 mod markdown;
 mod msgpack;
 mod msgpackz;
 mod nuon;
 mod ods;
+mod pem;
 mod ssv;
 mod toml;
 mod tsv;
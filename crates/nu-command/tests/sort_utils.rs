@@ -17,7 +17,7 @@ fn test_sort_basic() {
         Value::test_string("baz"),
     ];
 
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -43,7 +43,7 @@ fn test_sort_nothing() {
         Value::test_string("bar"),
     ];
 
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -69,7 +69,7 @@ fn test_sort_nothing() {
         .filter(|item| item == &&Value::test_nothing())
         .count();
 
-    assert!(sort(&mut values, false, false).is_ok());
+    assert!(sort(&mut values, false, false, None).is_ok());
 
     // check if the last `nulls` values of the sorted list are indeed null
     assert_eq!(&values[(nulls - 1)..], vec![Value::test_nothing(); nulls])
@@ -90,7 +90,7 @@ fn test_sort_natural_basic() {
         Value::test_string("99"),
     ];
 
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -107,7 +107,7 @@ fn test_sort_natural_basic() {
         ]
     );
 
-    assert!(sort(&mut list, false, true).is_ok());
+    assert!(sort(&mut list, false, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -142,7 +142,7 @@ fn test_sort_natural_mixed_types() {
         Value::test_string("10"),
     ];
 
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -161,7 +161,7 @@ fn test_sort_natural_mixed_types() {
         ]
     );
 
-    assert!(sort(&mut list, false, true).is_ok());
+    assert!(sort(&mut list, false, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -199,8 +199,8 @@ fn test_sort_natural_no_numeric_values() {
     ];
     let mut natural = normal.clone();
 
-    assert!(sort(&mut normal, false, false).is_ok());
-    assert!(sort(&mut natural, false, true).is_ok());
+    assert!(sort(&mut normal, false, false, None).is_ok());
+    assert!(sort(&mut natural, false, true, None).is_ok());
     assert_eq!(normal, natural);
 }
 
@@ -241,7 +241,7 @@ fn test_sort_natural_type_order() {
         Value::test_string("tango"),
     ];
 
-    assert!(sort(&mut list, false, true).is_ok());
+    assert!(sort(&mut list, false, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -288,7 +288,7 @@ fn test_sort_natural_type_order() {
         Value::test_float(3.0),
         Value::test_string("foobar"),
     ];
-    assert!(sort(&mut list, false, true).is_ok());
+    assert!(sort(&mut list, false, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -332,7 +332,7 @@ fn test_sort_insensitive() {
 
     // sensitive + non-natural
     list = source.clone();
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -349,7 +349,7 @@ fn test_sort_insensitive() {
 
     // sensitive + natural
     list = source.clone();
-    assert!(sort(&mut list, false, true).is_ok());
+    assert!(sort(&mut list, false, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -366,7 +366,7 @@ fn test_sort_insensitive() {
 
     // insensitive + non-natural
     list = source.clone();
-    assert!(sort(&mut list, true, false).is_ok());
+    assert!(sort(&mut list, true, false, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -383,7 +383,7 @@ fn test_sort_insensitive() {
 
     // insensitive + natural
     list = source.clone();
-    assert!(sort(&mut list, true, true).is_ok());
+    assert!(sort(&mut list, true, true, None).is_ok());
     assert_eq!(
         list,
         vec![
@@ -417,7 +417,7 @@ fn test_sort_record_keys() {
         "echo" => Value::test_int(123),
     };
 
-    let sorted = sort_record(record, false, false, false, false).unwrap();
+    let sorted = sort_record(record, false, false, false, false, None).unwrap();
     assert_record_eq(
         sorted,
         record! {
@@ -461,7 +461,7 @@ fn test_sort_record_values() {
     };
 
     // non-natural sort
-    let sorted = sort_record(record.clone(), true, false, false, false).unwrap();
+    let sorted = sort_record(record.clone(), true, false, false, false, None).unwrap();
     assert_record_eq(
         sorted,
         record! {
@@ -479,7 +479,7 @@ fn test_sort_record_values() {
     );
 
     // natural sort
-    let sorted = sort_record(record.clone(), true, false, false, true).unwrap();
+    let sorted = sort_record(record.clone(), true, false, false, true, None).unwrap();
     assert_record_eq(
         sorted,
         record! {
@@ -532,19 +532,20 @@ fn test_sort_equivalent() {
         }],
     });
 
-    assert!(sort(&mut list, false, false).is_ok());
+    assert!(sort(&mut list, false, false, None).is_ok());
     assert!(
         sort_by(
             &mut table,
             vec![comparator],
             Span::test_data(),
             false,
-            false
+            false,
+            None
         )
         .is_ok()
     );
 
-    let record_sorted = sort_record(record.clone(), true, false, false, false).unwrap();
+    let record_sorted = sort_record(record.clone(), true, false, false, false, None).unwrap();
     let record_vals: Vec<Value> = record_sorted.into_iter().map(|pair| pair.1).collect();
 
     let table_vals: Vec<Value> = table
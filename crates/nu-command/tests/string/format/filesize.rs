@@ -13,3 +13,19 @@ fn format_duration_with_invalid_unit() {
 
     assert!(actual.err.contains("invalid_unit"));
 }
+
+#[test]
+fn format_filesize_unit_flag_with_columns() {
+    let actual = nu!(r#"{a: 1500kB, b: 2kB} | format filesize --unit MB a | to nuon"#);
+
+    assert_eq!("{a: \"1.5 MB\", b: 2000b}", actual.out);
+}
+
+#[test]
+fn format_filesize_precision_on_table() {
+    let actual = nu!(
+        r#"[[name size]; [a 1MiB] [b 1500KiB]] | format filesize --unit MiB --precision 2 | get size | str join ' '"#
+    );
+
+    assert_eq!("1.00 MiB 1.46 MiB", actual.out);
+}
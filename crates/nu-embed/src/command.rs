@@ -0,0 +1,48 @@
+use nu_engine::CallExt;
+use nu_protocol::{
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape, Type,
+    Value,
+    engine::{Call, Command, EngineState, Stack},
+};
+use std::sync::Arc;
+
+/// The function of a command added with [`Engine::add_fn_command`](crate::Engine::add_fn_command).
+type CommandFn = dyn Fn(Vec<Value>, Value, Span) -> Result<Value, ShellError> + Send + Sync;
+
+/// A command which calls a Rust function with its positional arguments and input.
+#[derive(Clone)]
+pub(crate) struct FnCommand {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) run: Arc<CommandFn>,
+}
+
+impl Command for FnCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(&self.name)
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .rest("args", SyntaxShape::Any, "The arguments of the command.")
+            .category(Category::Custom("embedded".into()))
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let args: Vec<Value> = call.rest(engine_state, stack, 0)?;
+        let input = input.into_value(head)?;
+        Ok((self.run)(args, input, head)?.into_pipeline_data())
+    }
+}
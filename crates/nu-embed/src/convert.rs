@@ -0,0 +1,112 @@
+use nu_command::{convert_nujson_to_value, value_to_json_value};
+use nu_protocol::{ShellError, Span, Value, engine::EngineState};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Convert a Rust value into a Nushell [`Value`], through its [`Serialize`] implementation.
+///
+/// The conversion is the one of `from json`: structs and maps become records, sequences become
+/// lists, and unit and `None` become `null`. Integers which don't fit into an `i64` are errors.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, ShellError> {
+    let json = nu_json::to_value(value).map_err(|err| ShellError::CantConvert {
+        to_type: "value".into(),
+        from_type: std::any::type_name::<T>().into(),
+        span: Span::unknown(),
+        help: Some(err.to_string()),
+    })?;
+    let mut value = convert_nujson_to_value(json, Span::unknown());
+    value.recurse_mut(&mut |value| match value {
+        Value::Error { error, .. } => Err(*error.clone()),
+        _ => Ok(()),
+    })?;
+    Ok(value)
+}
+
+/// Convert a Nushell [`Value`] into a Rust value, through its [`Deserialize`](serde::Deserialize)
+/// implementation.
+///
+/// The conversion is the one of `to json`: records become maps or structs, and lists become
+/// sequences. Filesizes are converted to their number of bytes, durations to their number of
+/// nanoseconds, dates to strings, binary to sequences of bytes and ranges to `null`. Closures
+/// can't be converted.
+pub fn from_value<T: DeserializeOwned>(value: &Value) -> Result<T, ShellError> {
+    // The engine state is only used to show closures, which aren't converted
+    let json = value_to_json_value(&EngineState::new(), value, value.span(), false)?;
+    nu_json::from_value(json).map_err(|err| ShellError::CantConvert {
+        to_type: std::any::type_name::<T>().into(),
+        from_type: value.get_type().to_string(),
+        span: value.span(),
+        help: Some(err.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nu_protocol::record;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        retries: u32,
+        ratio: f64,
+        tags: Vec<String>,
+        parent: Option<Box<Config>>,
+    }
+
+    #[test]
+    fn structs_round_trip() {
+        let config = Config {
+            name: "web".into(),
+            retries: 3,
+            ratio: 0.5,
+            tags: vec!["a".into(), "b".into()],
+            parent: None,
+        };
+        let value = to_value(&config).unwrap();
+        assert_eq!(
+            value,
+            Value::test_record(record! {
+                "name" => Value::test_string("web"),
+                "retries" => Value::test_int(3),
+                "ratio" => Value::test_float(0.5),
+                "tags" => Value::test_list(vec![Value::test_string("a"), Value::test_string("b")]),
+                "parent" => Value::test_nothing(),
+            })
+        );
+        assert_eq!(from_value::<Config>(&value).unwrap(), config);
+    }
+
+    #[test]
+    fn nushell_types_become_plain_data() {
+        let value = Value::test_record(record! {
+            "size" => Value::test_filesize(1024),
+            "timeout" => Value::test_duration(1_000_000_000),
+        });
+        let map: BTreeMap<String, i64> = from_value(&value).unwrap();
+        assert_eq!(map["size"], 1024);
+        assert_eq!(map["timeout"], 1_000_000_000);
+    }
+
+    #[test]
+    fn large_integers_are_errors() {
+        assert_eq!(
+            to_value(&(i64::MAX as u64)).unwrap(),
+            Value::test_int(i64::MAX)
+        );
+        assert!(matches!(
+            to_value(&vec![u64::MAX]),
+            Err(ShellError::CantConvert { .. })
+        ));
+    }
+
+    #[test]
+    fn mismatched_types_are_errors() {
+        let value = Value::test_string("not a number");
+        assert!(matches!(
+            from_value::<u32>(&value),
+            Err(ShellError::CantConvert { .. })
+        ));
+    }
+}
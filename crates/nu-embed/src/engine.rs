@@ -0,0 +1,389 @@
+use crate::command::FnCommand;
+use nu_engine::{ClosureEvalOnce, eval_block};
+use nu_parser::parse;
+use nu_protocol::{
+    IntoPipelineData, LabeledError, PipelineData, ShellError, Signals, Span, Type, Value,
+    debugger::WithoutDebug,
+    engine::{Command, EngineState, Stack, StateWorkingSet},
+    format_cli_error,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, atomic::AtomicBool},
+};
+
+/// A Nushell engine, which evaluates source code and closures.
+///
+/// Everything evaluated by the same engine shares its definitions, variables and environment, the
+/// same way as the lines entered in the REPL do.
+pub struct Engine {
+    engine_state: EngineState,
+    stack: Stack,
+    entry_num: usize,
+}
+
+/// Configures a new [`Engine`].
+///
+/// # Example
+///
+/// ```rust
+/// # use nu_embed::{Engine, ShellError, Span, Value};
+/// # fn main() -> Result<(), ShellError> {
+/// let mut engine = Engine::builder()
+///     .std_lib(false)
+///     .env_var("GREETING", Value::string("hello", Span::unknown()))
+///     .build()?;
+/// assert_eq!(engine.eval("$env.GREETING")?.as_str()?, "hello");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EngineBuilder {
+    std_lib: bool,
+    inherit_env: bool,
+    cwd: Option<PathBuf>,
+    env_vars: Vec<(String, Value)>,
+    interrupt: Option<Arc<AtomicBool>>,
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        Self {
+            std_lib: true,
+            inherit_env: false,
+            cwd: None,
+            env_vars: vec![],
+            interrupt: None,
+        }
+    }
+}
+
+impl EngineBuilder {
+    /// Whether the standard library can be used with `use std/...`. On by default, if the `std`
+    /// feature is enabled.
+    pub fn std_lib(mut self, std_lib: bool) -> Self {
+        self.std_lib = std_lib;
+        self
+    }
+
+    /// Whether to start with the environment variables of the current process. Off by default, so
+    /// the environment only has what the application sets.
+    pub fn inherit_env(mut self, inherit_env: bool) -> Self {
+        self.inherit_env = inherit_env;
+        self
+    }
+
+    /// The working directory (`$env.PWD`) of the engine. Defaults to the working directory of the
+    /// current process.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Set an environment variable.
+    pub fn env_var(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.env_vars.push((name.into(), value));
+        self
+    }
+
+    /// Interrupt whatever the engine is evaluating when `interrupt` is set, like pressing ctrl-c
+    /// in the REPL does.
+    pub fn interrupt(mut self, interrupt: Arc<AtomicBool>) -> Self {
+        self.interrupt = Some(interrupt);
+        self
+    }
+
+    /// Create the engine.
+    pub fn build(self) -> Result<Engine, ShellError> {
+        let engine_state = nu_cmd_lang::create_default_context();
+        #[cfg(feature = "commands")]
+        let engine_state = nu_command::add_shell_command_context(engine_state);
+        let mut engine_state = engine_state;
+        let mut stack = Stack::new().collect_value();
+
+        if let Some(interrupt) = self.interrupt {
+            engine_state.set_signals(Signals::new(interrupt));
+        }
+
+        if self.inherit_env {
+            for (name, value) in std::env::vars() {
+                engine_state.add_env_var(name, Value::string(value, Span::unknown()));
+            }
+        }
+        let cwd = match self.cwd {
+            Some(cwd) => cwd,
+            None => std::env::current_dir().map_err(|err| ShellError::GenericError {
+                error: "Couldn't get the current working directory".into(),
+                msg: err.to_string(),
+                span: None,
+                help: Some("set the working directory with `EngineBuilder::cwd`".into()),
+                inner: vec![],
+            })?,
+        };
+        engine_state.add_env_var(
+            "PWD".into(),
+            Value::string(cwd.to_string_lossy(), Span::unknown()),
+        );
+        for (name, value) in self.env_vars {
+            engine_state.add_env_var(name, value);
+        }
+        nu_engine::convert_env_values(&mut engine_state, &mut stack)?;
+
+        if self.std_lib {
+            #[cfg(feature = "std")]
+            nu_std::load_standard_library(&mut engine_state).map_err(|err| {
+                ShellError::GenericError {
+                    error: "Couldn't load the standard library".into(),
+                    msg: err.to_string(),
+                    span: None,
+                    help: None,
+                    inner: vec![],
+                }
+            })?;
+        }
+
+        engine_state.generate_nu_constant();
+
+        Ok(Engine {
+            engine_state,
+            stack,
+            entry_num: 1,
+        })
+    }
+}
+
+impl Engine {
+    /// Create an engine with the default settings, see [`EngineBuilder`].
+    pub fn new() -> Result<Engine, ShellError> {
+        Self::builder().build()
+    }
+
+    /// Configure a new engine.
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    /// Evaluate some Nushell source code, and return the value of its last pipeline.
+    pub fn eval(&mut self, source: &str) -> Result<Value, ShellError> {
+        self.eval_pipeline(source, PipelineData::empty())
+    }
+
+    /// Evaluate some Nushell source code with `input` piped into it, and return the value of its
+    /// last pipeline.
+    ///
+    /// Streams, like the output of external commands, are collected into a value.
+    pub fn eval_with(&mut self, source: &str, input: Value) -> Result<Value, ShellError> {
+        self.eval_pipeline(source, input.into_pipeline_data())
+    }
+
+    fn eval_pipeline(&mut self, source: &str, input: PipelineData) -> Result<Value, ShellError> {
+        let mut working_set = StateWorkingSet::new(&self.engine_state);
+        let fname = format!("entry #{}", self.entry_num);
+        self.entry_num += 1;
+
+        let block = parse(&mut working_set, Some(&fname), source.as_bytes(), false);
+
+        let error = if working_set.parse_errors.is_empty() {
+            None
+        } else {
+            // ShellError doesn't have ParseError, use LabeledError to contain it.
+            let mut error = LabeledError::new("Failed to parse the source code");
+            error.inner.extend(
+                working_set
+                    .parse_errors
+                    .iter()
+                    .map(LabeledError::from_diagnostic),
+            );
+            Some(ShellError::LabeledError(error.into()))
+        };
+
+        // Merge the delta even if there are errors, so the errors can be rendered with the spans
+        // pointing into the source.
+        self.engine_state.merge_delta(working_set.render())?;
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        eval_block::<WithoutDebug>(&self.engine_state, &mut self.stack, &block, input)?
+            .body
+            .into_value(Span::unknown())
+    }
+
+    /// Call a closure, which was returned by an earlier evaluation, with positional arguments.
+    pub fn call_closure(
+        &mut self,
+        closure: &Value,
+        args: impl IntoIterator<Item = Value>,
+    ) -> Result<Value, ShellError> {
+        let closure = closure.as_closure()?.clone();
+        args.into_iter()
+            .fold(
+                ClosureEvalOnce::new(&self.engine_state, &self.stack, closure),
+                |eval, arg| eval.add_arg(arg),
+            )
+            .run_with_input(PipelineData::empty())?
+            .into_value(Span::unknown())
+    }
+
+    /// Set the variable `$name`, so the code evaluated after can use it.
+    pub fn set_var(&mut self, name: &str, value: Value) -> Result<(), ShellError> {
+        let var_name = format!("${name}").into_bytes();
+        let mut working_set = StateWorkingSet::new(&self.engine_state);
+        let var_id = match working_set.find_variable(&var_name) {
+            Some(var_id) => var_id,
+            None => working_set.add_variable(var_name, Span::unknown(), Type::Any, false),
+        };
+        self.engine_state.merge_delta(working_set.render())?;
+        self.stack.add_var(var_id, value);
+        Ok(())
+    }
+
+    /// Get the value of the variable `$name`, if it's defined.
+    pub fn get_var(&self, name: &str) -> Option<Value> {
+        let working_set = StateWorkingSet::new(&self.engine_state);
+        let var_id = working_set.find_variable(format!("${name}").as_bytes())?;
+        self.stack.get_var(var_id, Span::unknown()).ok()
+    }
+
+    /// Set an environment variable.
+    pub fn set_env(&mut self, name: &str, value: Value) {
+        self.stack.add_env_var(name.into(), value);
+    }
+
+    /// Get the value of an environment variable, if it's set.
+    pub fn get_env(&self, name: &str) -> Option<Value> {
+        self.stack.get_env_var(&self.engine_state, name).cloned()
+    }
+
+    /// Make a command implemented in Rust available to the code evaluated after.
+    pub fn add_command(&mut self, command: impl Command + 'static) -> Result<(), ShellError> {
+        let mut working_set = StateWorkingSet::new(&self.engine_state);
+        working_set.add_decl(Box::new(command));
+        self.engine_state.merge_delta(working_set.render())
+    }
+
+    /// Make a command, which calls `run` with its positional arguments, its input and the span of
+    /// the call, available to the code evaluated after.
+    ///
+    /// The command accepts any arguments and input. Unlike [`add_command`](Engine::add_command),
+    /// this doesn't need implementing the [`Command`] trait of `nu-protocol`.
+    pub fn add_fn_command(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        run: impl Fn(Vec<Value>, Value, Span) -> Result<Value, ShellError> + Send + Sync + 'static,
+    ) -> Result<(), ShellError> {
+        self.add_command(FnCommand {
+            name: name.into(),
+            description: description.into(),
+            run: Arc::new(run),
+        })
+    }
+
+    /// Render an error the way Nushell prints it, with the source code it points to.
+    pub fn format_error(&self, error: &ShellError) -> String {
+        let working_set = StateWorkingSet::new(&self.engine_state);
+        format_cli_error(&working_set, error, None)
+    }
+
+    /// The state of the engine.
+    ///
+    /// This gives full access to the internals of Nushell, which aren't covered by the version of
+    /// this crate.
+    pub fn engine_state(&self) -> &EngineState {
+        &self.engine_state
+    }
+
+    /// The mutable state of the engine, see [`engine_state`](Engine::engine_state).
+    pub fn engine_state_mut(&mut self) -> &mut EngineState {
+        &mut self.engine_state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn engine() -> Engine {
+        Engine::builder()
+            .std_lib(false)
+            .cwd(std::env::temp_dir())
+            .build()
+            .expect("engine should be created")
+    }
+
+    #[test]
+    fn definitions_are_kept_between_evaluations() {
+        let mut engine = engine();
+        engine.eval("def double [x: int] { $x * 2 }").unwrap();
+        engine.eval("let base = 20").unwrap();
+        assert_eq!(engine.eval("double $base").unwrap(), Value::test_int(40));
+    }
+
+    #[test]
+    fn input_is_piped_into_the_source() {
+        let mut engine = engine();
+        let input = Value::test_list(vec![Value::test_int(1), Value::test_int(2)]);
+        assert_eq!(
+            engine.eval_with("$in | length", input).unwrap(),
+            Value::test_int(2)
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_returned() {
+        let mut engine = engine();
+        assert!(engine.eval("let = ").is_err());
+        // the engine can still be used after an error
+        assert_eq!(engine.eval("1 + 1").unwrap(), Value::test_int(2));
+    }
+
+    #[test]
+    fn variables_and_environment_can_be_set() {
+        let mut engine = engine();
+        engine.set_var("answer", Value::test_int(42)).unwrap();
+        engine.set_env("NAME", Value::test_string("nu"));
+        assert_eq!(
+            engine.eval("$'($env.NAME) ($answer)'").unwrap(),
+            Value::test_string("nu 42")
+        );
+
+        engine.eval("$env.NAME = 'shell'").unwrap();
+        assert_eq!(engine.get_env("NAME"), Some(Value::test_string("shell")));
+        assert_eq!(engine.get_var("answer"), Some(Value::test_int(42)));
+        assert_eq!(engine.get_var("missing"), None);
+    }
+
+    #[test]
+    fn fn_commands_get_their_arguments_and_input() {
+        let mut engine = engine();
+        engine
+            .add_fn_command(
+                "sum",
+                "Add the input and the arguments",
+                |args, input, span| {
+                    let sum = args
+                        .iter()
+                        .chain([&input])
+                        .map(Value::as_int)
+                        .sum::<Result<i64, _>>()?;
+                    Ok(Value::int(sum, span))
+                },
+            )
+            .unwrap();
+        assert_eq!(engine.eval("1 | sum 2 3").unwrap(), Value::test_int(6));
+        assert!(engine.eval("sum foo").is_err());
+    }
+
+    #[test]
+    fn closures_can_be_called() {
+        let mut engine = engine();
+        let closure = engine.eval("{|a, b| $a + $b }").unwrap();
+        assert_eq!(
+            engine
+                .call_closure(&closure, [Value::test_int(1), Value::test_int(2)])
+                .unwrap(),
+            Value::test_int(3)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Embed [Nushell](https://nushell.sh) as a scripting language in Rust applications.
+//!
+//! An [`Engine`] keeps the definitions, variables and environment of everything it evaluated, like
+//! a REPL session. Values are converted from and to Rust types with [`to_value`] and
+//! [`from_value`], through their [`serde`] implementations.
+//!
+//! # Example
+//!
+//! ```rust
+//! use nu_embed::{Engine, ShellError, from_value, to_value};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Server {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! # fn main() -> Result<(), ShellError> {
+//! let mut engine = Engine::new()?;
+//!
+//! // Evaluate some configuration
+//! let value = engine.eval("{host: localhost, port: (8000 + 80)}")?;
+//! let server: Server = from_value(&value)?;
+//! assert_eq!(server, Server { host: "localhost".into(), port: 8080 });
+//!
+//! // Pass values in, and get the results of closures out
+//! engine.set_var("server", to_value(&server)?)?;
+//! let closure = engine.eval("{|prefix| $'($prefix)($server.host):($server.port)' }")?;
+//! let url = engine.call_closure(&closure, [to_value("http://")?])?;
+//! assert_eq!(url.as_str()?, "http://localhost:8080");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Stability
+//!
+//! Only the items defined in this crate, like [`Engine`], [`to_value`] and [`from_value`], are
+//! covered by its version. The types re-exported from `nu-protocol`, including [`Value`],
+//! [`Record`], [`Span`] and [`ShellError`], are the same as in the rest of Nushell and can change
+//! with any of its releases, like its other crates. The
+//! [`engine_state`](Engine::engine_state) escape hatch gives full access to the internals.
+mod command;
+mod convert;
+mod engine;
+
+pub use convert::{from_value, to_value};
+pub use engine::{Engine, EngineBuilder};
+
+pub use nu_protocol::{
+    IntoValue, Record, ShellError, Span, Value,
+    engine::{Command, EngineState, Stack},
+    record,
+};
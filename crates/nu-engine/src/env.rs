@@ -1,9 +1,9 @@
-use crate::ClosureEvalOnce;
+use crate::{ClosureEvalOnce, get_eval_block_with_early_return, redirect_env};
 use nu_path::canonicalize_with;
 use nu_protocol::{
-    ShellError, Span, Type, Value, VarId,
+    PipelineData, ShellError, Span, Type, Value, VarId,
     ast::Expr,
-    engine::{Call, EngineState, Stack, StateWorkingSet},
+    engine::{Call, Closure, EngineState, Stack, StateWorkingSet},
     shell_error::io::{IoError, IoErrorExt, NotFound},
 };
 use std::{
@@ -429,3 +429,92 @@ fn ensure_path(engine_state: &EngineState, stack: &mut Stack) -> Option<ShellErr
 
     error
 }
+
+/// Run the closures of `env watch` whose environment variable changed since `stack` last checked
+/// them.
+///
+/// This is called after every environment assignment and command, which are what set and remove
+/// environment variables or switch overlays, and only checks the watched variables if
+/// [`Stack::env_changed`] is set. The values are kept per stack, so a change which is undone when
+/// a command without `--env` returns is only seen by the stack of that command.
+pub fn eval_env_watches(engine_state: &EngineState, stack: &mut Stack) -> Result<(), ShellError> {
+    if stack.notifying_env_watches || !std::mem::take(&mut stack.env_changed) {
+        return Ok(());
+    }
+
+    // Copy the watches, so that the closures can add watches themselves
+    let watches = engine_state
+        .env_watches
+        .lock()
+        .expect("env watches lock is poisoned!")
+        .clone();
+    let changes: Vec<_> = watches
+        .iter()
+        .enumerate()
+        .filter_map(|(index, watch)| {
+            let current = stack.get_env_var(engine_state, &watch.name);
+            let last = stack.env_watch_values.get(index).unwrap_or(&watch.initial);
+            (current != last.as_ref()).then(|| {
+                (
+                    watch.closure.clone(),
+                    last.clone().unwrap_or_default(),
+                    current.cloned().unwrap_or_default(),
+                )
+            })
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    stack.notifying_env_watches = true;
+    let result = changes
+        .into_iter()
+        .try_for_each(|(closure, before, after)| {
+            eval_env_watch(engine_state, stack, closure, before, after)
+        });
+    stack.notifying_env_watches = false;
+
+    // The changes made by the closures themselves don't run them again
+    stack.env_watch_values = watches
+        .iter()
+        .map(|watch| stack.get_env_var(engine_state, &watch.name).cloned())
+        .collect();
+    stack.env_changed = false;
+
+    result
+}
+
+/// Run a closure of `env watch` with the old and the new value, keeping the environment it sets.
+fn eval_env_watch(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    closure: Closure,
+    before: Value,
+    after: Value,
+) -> Result<(), ShellError> {
+    let block = engine_state.get_block(closure.block_id);
+    let mut callee_stack = stack.captures_to_stack(closure.captures);
+    for (position, value) in [before, after].into_iter().enumerate() {
+        if let Some(var_id) = block
+            .signature
+            .get_positional(position)
+            .and_then(|var| var.var_id)
+        {
+            callee_stack.add_var(var_id, value);
+        }
+    }
+
+    let eval_block_with_early_return = get_eval_block_with_early_return(engine_state);
+    eval_block_with_early_return(
+        engine_state,
+        &mut callee_stack,
+        block,
+        PipelineData::empty(),
+    )?
+    .body
+    .drain()?;
+    redirect_env(engine_state, stack, &callee_stack);
+
+    Ok(())
+}
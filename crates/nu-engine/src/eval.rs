@@ -158,6 +158,9 @@ pub fn eval_call<D: DebugContext>(
 
         result
     } else {
+        if let Some(sandbox) = &engine_state.sandbox {
+            sandbox.check_command(decl, call.head)?;
+        }
         // We pass caller_stack here with the knowledge that internal commands
         // are going to be specifically looking for global state in the stack
         // rather than any local state.
@@ -185,6 +188,11 @@ pub fn redirect_env(engine_state: &EngineState, caller_stack: &mut Stack, callee
 
     // set config to callee config, to capture any updates to that
     caller_stack.config.clone_from(&callee_stack.config);
+
+    // the callee already ran the closures of `env watch` for the changes it made
+    caller_stack
+        .env_watch_values
+        .clone_from(&callee_stack.env_watch_values);
 }
 
 fn eval_external(
@@ -20,6 +20,7 @@ use nu_utils::IgnoreCaseExt;
 
 use crate::{
     ENV_CONVERSIONS, convert_env_vars, eval::is_automatic_env_var, eval_block_with_early_return,
+    eval_env_watches,
 };
 
 pub fn eval_ir_block<D: DebugContext>(
@@ -222,6 +223,12 @@ fn eval_ir_block_impl<D: DebugContext>(
                 pc += 1;
             }
             Ok(InstructionResult::Branch(next_pc)) => {
+                // Loops branch backwards, so check for an interrupt there, even if the body of the
+                // loop doesn't call any command which would. This doesn't branch to an error
+                // handler, so a `try` in the loop can't keep it running.
+                if next_pc <= pc {
+                    ctx.engine_state.signals().check(span)?;
+                }
                 pc = next_pc;
             }
             Ok(InstructionResult::Return(reg_id)) => return Ok(ctx.take_reg(reg_id)),
@@ -432,6 +439,7 @@ fn eval_instruction<D: DebugContext>(
                 if update_conversions {
                     convert_env_vars(ctx.stack, ctx.engine_state, &value)?;
                 }
+                eval_env_watches(ctx.engine_state, ctx.stack)?;
                 Ok(Continue)
             } else {
                 Err(ShellError::AutomaticEnvVarSetManually {
@@ -612,6 +620,8 @@ fn eval_instruction<D: DebugContext>(
             }
             #[cfg(not(feature = "os"))]
             ctx.put_reg(*src_dst, PipelineExecutionData { body: result });
+            // Commands like `load-env`, `cd` and `overlay use` change the environment
+            eval_env_watches(ctx.engine_state, ctx.stack)?;
             Ok(Continue)
         }
         Instruction::StringAppend { src_dst, val } => {
@@ -1109,6 +1119,14 @@ fn eval_call<D: DebugContext>(
             // If the decl is a custom command
             let block = engine_state.get_block(block_id);
 
+            let input = auto_parse_input(
+                engine_state,
+                &mut caller_stack,
+                input,
+                &block.signature,
+                head,
+            )?;
+
             // check types after acquiring block to avoid unnecessarily cloning Signature
             check_input_types(&input, &block.signature, head)?;
 
@@ -1140,7 +1158,12 @@ fn eval_call<D: DebugContext>(
 
             result
         } else {
-            check_input_types(&input, &decl.signature(), head)?;
+            if let Some(sandbox) = &engine_state.sandbox {
+                sandbox.check_command(decl, head)?;
+            }
+            let signature = decl.signature();
+            let input = auto_parse_input(engine_state, &mut caller_stack, input, &signature, head)?;
+            check_input_types(&input, &signature, head)?;
             // FIXME: precalculate this and save it somewhere
             let span = Span::merge_many(
                 std::iter::once(head).chain(
@@ -1380,6 +1403,33 @@ fn check_assignment_type(val: Value, target_ty: &Type) -> Result<Value, ShellErr
     }
 }
 
+/// Convert the output of an external command with `from auto` if `$env.config.auto_parse` is
+/// enabled and the command it's piped into can't take it as text.
+fn auto_parse_input(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    input: PipelineData,
+    signature: &Signature,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    #[cfg(feature = "os")]
+    if let PipelineData::ByteStream(stream, ..) = &input
+        && matches!(stream.source(), nu_protocol::ByteStreamSource::Child(_))
+        && stack.get_config(engine_state).auto_parse.enabled
+        && check_input_types(&input, signature, head).is_err()
+        && let Some(decl_id) = engine_state.find_decl(b"from auto", &[])
+    {
+        let call = nu_protocol::ast::Call::new(head);
+        return engine_state
+            .get_decl(decl_id)
+            .run(engine_state, stack, &(&call).into(), input);
+    }
+    #[cfg(not(feature = "os"))]
+    let _ = (engine_state, stack, signature, head);
+
+    Ok(input)
+}
+
 /// Type check pipeline input against command's input types
 fn check_input_types(
     input: &PipelineData,
@@ -1610,6 +1660,9 @@ enum RedirectionStream {
 
 /// Open a file for redirection
 fn open_file(ctx: &EvalContext<'_>, path: &Value, append: bool) -> Result<Arc<File>, ShellError> {
+    if let Some(sandbox) = &ctx.engine_state.sandbox {
+        sandbox.check_redirection(path.span())?;
+    }
     let path_expanded =
         expand_path_with(path.as_str()?, ctx.engine_state.cwd(Some(ctx.stack))?, true);
     let mut options = File::options();
@@ -1713,4 +1766,9 @@ fn redirect_env(engine_state: &EngineState, caller_stack: &mut Stack, callee_sta
 
     // set config to callee config, to capture any updates to that
     caller_stack.config.clone_from(&callee_stack.config);
+
+    // the callee already ran the closures of `env watch` for the changes it made
+    caller_stack
+        .env_watch_values
+        .clone_from(&callee_stack.env_watch_values);
 }
@@ -0,0 +1,105 @@
+use super::ViewCommand;
+use crate::{
+    nu_common::collect_input,
+    views::{Orientation, RecordView, ViewConfig},
+};
+use anyhow::{Result, bail};
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Hide columns of the data being explored. The columns are shown again when going back with
+/// <Esc>.
+#[derive(Debug, Default, Clone)]
+pub struct HideCmd {
+    columns: Vec<String>,
+}
+
+impl HideCmd {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const NAME: &'static str = "hide";
+}
+
+impl ViewCommand for HideCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.columns = args.split_whitespace().map(String::from).collect();
+        if self.columns.is_empty() {
+            bail!("expected the names of the columns to hide");
+        }
+
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        let is_record = matches!(value, Value::Record { .. });
+
+        let (columns, data) = collect_input(value)?;
+        if let Some(missing) = self.columns.iter().find(|name| !columns.contains(name)) {
+            bail!("there is no column `{missing}`");
+        }
+
+        let keep: Vec<bool> = columns
+            .iter()
+            .map(|name| !self.columns.contains(name))
+            .collect();
+        let columns = retain(columns, &keep);
+        let data = data.into_iter().map(|row| retain(row, &keep)).collect();
+
+        let mut view = RecordView::new(columns, data, config.explore_config.clone());
+
+        if is_record {
+            view.set_top_layer_orientation(Orientation::Left);
+        }
+
+        Ok(view)
+    }
+}
+
+fn retain<T>(values: Vec<T>, keep: &[bool]) -> Vec<T> {
+    values
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(value, keep)| keep.then_some(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hides_columns() {
+        let mut cmd = HideCmd::new();
+        cmd.parse(" b  c").unwrap();
+        assert_eq!(cmd.columns, ["b", "c"]);
+
+        let keep = [true, false, false, true];
+        assert_eq!(retain(vec!["a", "b", "c", "d"], &keep), ["a", "d"]);
+    }
+
+    #[test]
+    fn needs_columns() {
+        assert!(HideCmd::new().parse("  ").is_err());
+    }
+}
@@ -9,6 +9,7 @@ use nu_protocol::{
 
 mod expand;
 mod help;
+mod hide;
 mod nu;
 mod quit;
 mod table;
@@ -16,6 +17,7 @@ mod r#try;
 
 pub use expand::ExpandCmd;
 pub use help::HelpCmd;
+pub use hide::HideCmd;
 pub use nu::NuCmd;
 pub use quit::QuitCmd;
 pub use table::TableCmd;
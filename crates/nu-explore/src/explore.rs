@@ -46,6 +46,10 @@ impl Command for Explore {
             .category(Category::Viewers)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn extra_description(&self) -> &str {
         r#"Press `:` then `h` to get a help menu."#
     }
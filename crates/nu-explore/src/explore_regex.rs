@@ -65,6 +65,10 @@ impl Command for ExploreRegex {
             .category(Category::Viewers)
     }
 
+    fn effects(&self) -> Option<&'static [Effect]> {
+        Some(&[])
+    }
+
     fn extra_description(&self) -> &str {
         r#"Press `Ctrl-Q` to quit and provide constructed regular expression as the output."#
     }
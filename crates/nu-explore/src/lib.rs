@@ -9,7 +9,7 @@ mod registry;
 mod views;
 
 use anyhow::Result;
-use commands::{ExpandCmd, HelpCmd, NuCmd, QuitCmd, TableCmd, TryCmd};
+use commands::{ExpandCmd, HelpCmd, HideCmd, NuCmd, QuitCmd, TableCmd, TryCmd};
 use crossterm::terminal::size;
 pub use default_context::add_explore_context;
 pub use explore::Explore;
@@ -17,7 +17,7 @@ use explore::ExploreConfig;
 use explore_regex::ExploreRegex;
 use nu_common::{collect_pipeline, has_simple_value};
 use nu_protocol::{
-    PipelineData, Value,
+    ByteStreamSource, PipelineData, Value,
     engine::{EngineState, Stack},
 };
 use pager::{Page, Pager, PagerConfig};
@@ -95,14 +95,19 @@ fn help_view() -> Option<Page> {
 }
 
 fn binary_view(input: PipelineData, config: &ExploreConfig) -> Result<Page> {
-    let data = match input {
-        PipelineData::Value(Value::Binary { val, .. }, _) => val,
-        PipelineData::ByteStream(bs, _) => bs.into_bytes()?,
+    let view = match input {
+        PipelineData::Value(Value::Binary { val, .. }, _) => BinaryView::new(val, config),
+        // Files are paged from disk, so they don't have to fit into memory
+        PipelineData::ByteStream(bs, _) if matches!(bs.source(), ByteStreamSource::File(_)) => {
+            match bs.into_source() {
+                ByteStreamSource::File(file) => BinaryView::from_file(file, config)?,
+                _ => unreachable!("checked above"),
+            }
+        }
+        PipelineData::ByteStream(bs, _) => BinaryView::new(bs.into_bytes()?, config),
         _ => unreachable!("checked beforehand"),
     };
 
-    let view = BinaryView::new(data, config);
-
     Ok(Page::new(view, true))
 }
 
@@ -117,6 +122,7 @@ fn create_command_registry() -> CommandRegistry {
 fn create_commands(registry: &mut CommandRegistry) {
     registry.register_command_view(NuCmd::new(), true);
     registry.register_command_view(TableCmd::new(), true);
+    registry.register_command_view(HideCmd::new(), true);
 
     registry.register_command_view(ExpandCmd::new(), false);
     registry.register_command_view(TryCmd::new(), false);
@@ -33,10 +33,6 @@ impl<'a> BinaryWidget<'a> {
         }
     }
 
-    pub fn count_lines(&self) -> usize {
-        self.data.len() / self.count_elements()
-    }
-
     pub fn count_elements(&self) -> usize {
         self.opts.count_segments * self.opts.segment_size
     }
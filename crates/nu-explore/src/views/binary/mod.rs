@@ -8,6 +8,11 @@ use nu_protocol::{
     engine::{EngineState, Stack},
 };
 use ratatui::layout::Rect;
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    sync::Arc,
+};
 
 use crate::{
     explore::ExploreConfig,
@@ -27,12 +32,64 @@ use super::{Layout, View, ViewConfig, cursor::CursorMoveHandler, cursor::WindowC
 /// Not finished; many aspects are still WIP.
 #[derive(Debug, Clone)]
 pub struct BinaryView {
-    data: Vec<u8>,
+    data: BinaryData,
     // HACK: we are only using the vertical dimension of the cursor, should we use a plain old WindowCursor?
     cursor: WindowCursor2D,
     settings: Settings,
 }
 
+/// The data of a [`BinaryView`].
+#[derive(Debug, Clone)]
+enum BinaryData {
+    Memory(Vec<u8>),
+    /// A file which is read a page at a time, so files of any size can be viewed.
+    File {
+        file: Arc<File>,
+        start: u64,
+        len: usize,
+        page: Vec<u8>,
+        page_offset: usize,
+    },
+}
+
+impl BinaryData {
+    fn len(&self) -> usize {
+        match self {
+            BinaryData::Memory(data) => data.len(),
+            BinaryData::File { len, .. } => *len,
+        }
+    }
+
+    /// The data from `offset`, at least `len` bytes of it unless the data ends before.
+    fn window(&mut self, offset: usize, len: usize) -> io::Result<&[u8]> {
+        match self {
+            BinaryData::Memory(data) => Ok(&data[offset.min(data.len())..]),
+            BinaryData::File {
+                file,
+                start,
+                len: file_len,
+                page,
+                page_offset,
+            } => {
+                let len = len.min(file_len.saturating_sub(offset));
+                let cached = offset >= *page_offset && offset + len <= *page_offset + page.len();
+                if !cached {
+                    // Read a few screens at once, so scrolling doesn't read the file every time
+                    let read_len = len.saturating_mul(4);
+                    let read_offset = offset.saturating_sub(len);
+                    let mut file = file.as_ref();
+                    file.seek(SeekFrom::Start(*start + read_offset as u64))?;
+                    page.clear();
+                    file.take(read_len as u64).read_to_end(page)?;
+                    *page_offset = read_offset;
+                }
+                // The file may have become shorter since it was opened
+                Ok(page.get(offset - *page_offset..).unwrap_or_default())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct Settings {
     opts: BinarySettings,
@@ -41,9 +98,28 @@ struct Settings {
 
 impl BinaryView {
     pub fn new(data: Vec<u8>, cfg: &ExploreConfig) -> Self {
+        Self::with_data(BinaryData::Memory(data), cfg)
+    }
+
+    /// Create a view of the rest of `file`, which reads only the parts that are shown.
+    pub fn from_file(mut file: File, cfg: &ExploreConfig) -> io::Result<Self> {
+        let start = file.stream_position()?;
+        let len = file.metadata()?.len().saturating_sub(start);
+        let data = BinaryData::File {
+            file: Arc::new(file),
+            start,
+            len: usize::try_from(len).unwrap_or(usize::MAX),
+            page: vec![],
+            page_offset: 0,
+        };
+        Ok(Self::with_data(data, cfg))
+    }
+
+    fn with_data(data: BinaryData, cfg: &ExploreConfig) -> Self {
         let settings = settings_from_config(cfg);
-        // There's gotta be a nicer way of doing this than creating a widget just to count lines
-        let count_rows = BinaryWidget::new(&data, settings.opts, Default::default()).count_lines();
+        let count_elements =
+            BinaryWidget::new(&[], settings.opts, Default::default()).count_elements();
+        let count_rows = data.len() / count_elements;
 
         Self {
             data,
@@ -55,7 +131,7 @@ impl BinaryView {
 
 impl View for BinaryView {
     fn draw(&mut self, f: &mut Frame, area: Rect, _cfg: ViewConfig<'_>, _layout: &mut Layout) {
-        let widget = create_binary_widget(self);
+        let widget = create_binary_widget(self, area.height as usize);
         f.render_widget(widget, area);
     }
 
@@ -98,12 +174,16 @@ impl CursorMoveHandler for BinaryView {
     }
 }
 
-fn create_binary_widget(v: &BinaryView) -> BinaryWidget<'_> {
+fn create_binary_widget(v: &mut BinaryView, height: usize) -> BinaryWidget<'_> {
     let start_line = v.cursor.window_origin().row;
     let count_elements =
         BinaryWidget::new(&[], v.settings.opts, Default::default()).count_elements();
     let index = start_line * count_elements;
-    let data = &v.data[index..];
+    // A file that can't be read anymore is shown as empty
+    let data = v
+        .data
+        .window(index, height * count_elements)
+        .unwrap_or_default();
 
     let mut w = BinaryWidget::new(data, v.settings.opts, v.settings.style.clone());
     w.set_row_offset(index);
@@ -0,0 +1,86 @@
+/*
+ * The C API of Nushell, implemented by the `nu-ffi` crate.
+ *
+ * Engines and values are opaque handles, released with `nu_engine_free` and `nu_value_free`.
+ * Strings returned by the library, including error messages, are released with `nu_string_free`.
+ * Functions which can fail take an optional `char **error`, which is set to the error message when
+ * they fail; they then return `NULL` or `false`.
+ *
+ * An engine must only be used by one thread at a time.
+ */
+#ifndef NUSHELL_H
+#define NUSHELL_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct NuEngine NuEngine;
+typedef struct NuValue NuValue;
+
+/*
+ * A command implemented by the application.
+ *
+ * `args` is a list of the positional arguments and `input` is the value piped into the command;
+ * both are owned by Nushell. The callback sets `*output` to a value it created, which is then owned
+ * by Nushell, or leaves it `NULL` to return nothing. It returns `true` on success. On failure it
+ * returns `false` and can set `*error` to a message created with `nu_string_new`, which is then
+ * owned and released by Nushell.
+ */
+typedef bool (*NuCommandCallback)(void *user_data, const NuValue *args, const NuValue *input,
+                                  NuValue **output, char **error);
+
+/* Create an engine with the commands and the standard library of Nushell. */
+NuEngine *nu_engine_new(char **error);
+
+/* Release an engine. Does nothing if `engine` is `NULL`. */
+void nu_engine_free(NuEngine *engine);
+
+/*
+ * Evaluate `source`, with `input` piped into it if it isn't `NULL`, and return the value of its
+ * last pipeline. Definitions and variables are kept for the next evaluations with the same engine.
+ */
+NuValue *nu_eval(NuEngine *engine, const char *source, const NuValue *input, char **error);
+
+/*
+ * Call a closure, which was returned by an earlier evaluation, with the items of the list `args`
+ * as positional arguments. `args` can be `NULL` to pass none.
+ */
+NuValue *nu_call_closure(NuEngine *engine, const NuValue *closure, const NuValue *args,
+                         char **error);
+
+/* Set the variable `$name` for the code evaluated after. The value is copied. */
+bool nu_engine_set_var(NuEngine *engine, const char *name, const NuValue *value, char **error);
+
+/*
+ * Register a command implemented by the application. `user_data` is passed to every call of
+ * `callback`, which can happen on other threads than the one which registered it.
+ */
+bool nu_engine_add_command(NuEngine *engine, const char *name, const char *description,
+                           NuCommandCallback callback, void *user_data, char **error);
+
+/* Create a value from JSON. Objects become records and arrays become lists. */
+NuValue *nu_value_from_json(const char *json, char **error);
+
+/* Convert a value to JSON. Fails for values which can't be represented in JSON, like closures. */
+char *nu_value_to_json(const NuValue *value, char **error);
+
+/* The type of a value, like `int` or `record<name: string>`. */
+char *nu_value_type(const NuValue *value);
+
+/* Release a value. Does nothing if `value` is `NULL`. */
+void nu_value_free(NuValue *value);
+
+/* Copy `string` into a string owned by the library, like the errors of command callbacks. */
+char *nu_string_new(const char *string);
+
+/* Release a string returned by the library. Does nothing if `string` is `NULL`. */
+void nu_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NUSHELL_H */
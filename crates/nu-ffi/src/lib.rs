@@ -0,0 +1,526 @@
+//! A C API for embedding [Nushell](https://nushell.sh), over [`nu_embed`].
+//!
+//! The declarations for C are in `include/nushell.h`. All functions take and return pointers:
+//!
+//! - Engines and values are opaque handles, released with [`nu_engine_free`] and
+//!   [`nu_value_free`].
+//! - Strings returned by this library, including error messages, are released with
+//!   [`nu_string_free`].
+//! - Functions which can fail take an optional `char **error`, which is set to the error message
+//!   when they fail. They then return `NULL` or `false`.
+//!
+//! Panics are caught at the boundary and reported as errors.
+//!
+//! A value or engine is live from its creation until it's released.
+
+use nu_embed::{Engine, ShellError, Span, Value};
+use std::{
+    ffi::{CStr, CString, c_char, c_void},
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr,
+};
+
+/// An embedded Nushell engine.
+pub struct NuEngine(Engine);
+
+/// A Nushell value.
+pub struct NuValue(Value);
+
+/// A command implemented by the application.
+///
+/// `args` is a list of the positional arguments and `input` is the value piped into the command.
+/// The callback sets `*output` to a value it created, which is then owned by Nushell, or leaves it
+/// `NULL` to return nothing. It returns `true` on success.
+///
+/// On failure it returns `false`, and can set `*error` to a message created with
+/// [`nu_string_new`]. The message is then owned by Nushell, which releases it once it has been
+/// copied, so the application must not use or release it after the callback returns.
+pub type NuCommandCallback = unsafe extern "C" fn(
+    user_data: *mut c_void,
+    args: *const NuValue,
+    input: *const NuValue,
+    output: *mut *mut NuValue,
+    error: *mut *mut c_char,
+) -> bool;
+
+/// Create an engine with the commands and the standard library of Nushell.
+///
+/// Returns `NULL` if it couldn't be created.
+///
+/// # Safety
+///
+/// `error` has to be `NULL` or point to a `char *` which can be written.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_engine_new(error: *mut *mut c_char) -> *mut NuEngine {
+    unsafe {
+        ffi(error, || {
+            let engine = Engine::new().map_err(|err| err.to_string())?;
+            Ok(Box::into_raw(Box::new(NuEngine(engine))))
+        })
+        .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// Release an engine. Does nothing if `engine` is `NULL`.
+///
+/// # Safety
+///
+/// `engine` has to be `NULL` or an engine created by [`nu_engine_new`] which wasn't released yet.
+/// It can't be used after this.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_engine_free(engine: *mut NuEngine) {
+    if !engine.is_null() {
+        drop(unsafe { Box::from_raw(engine) });
+    }
+}
+
+/// Evaluate `source`, with `input` piped into it if it isn't `NULL`, and return the value of its
+/// last pipeline.
+///
+/// Definitions and variables are kept for the next evaluations with the same engine. Returns `NULL`
+/// if parsing or evaluating failed, with the error rendered like Nushell prints it.
+///
+/// # Safety
+///
+/// `engine` has to be a live engine which isn't used by another thread at the same time, `source`
+/// a NUL-terminated string, `input` `NULL` or a live value, and `error` `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_eval(
+    engine: *mut NuEngine,
+    source: *const c_char,
+    input: *const NuValue,
+    error: *mut *mut c_char,
+) -> *mut NuValue {
+    unsafe {
+        ffi(error, || {
+            let engine = &mut engine.as_mut().ok_or("engine is NULL")?.0;
+            let source = str_arg(source, "source")?;
+            let result = match input.as_ref() {
+                Some(input) => engine.eval_with(source, input.0.clone()),
+                None => engine.eval(source),
+            };
+            let value = result.map_err(|err| engine.format_error(&err))?;
+            Ok(Box::into_raw(Box::new(NuValue(value))))
+        })
+        .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// Call a closure, which was returned by an earlier evaluation, with the items of the list `args`
+/// as positional arguments. `args` can be `NULL` to pass none.
+///
+/// # Safety
+///
+/// `engine` has to be a live engine which isn't used by another thread at the same time,
+/// `closure` a live value, `args` `NULL` or a live value, and `error` `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_call_closure(
+    engine: *mut NuEngine,
+    closure: *const NuValue,
+    args: *const NuValue,
+    error: *mut *mut c_char,
+) -> *mut NuValue {
+    unsafe {
+        ffi(error, || {
+            let engine = &mut engine.as_mut().ok_or("engine is NULL")?.0;
+            let closure = &closure.as_ref().ok_or("closure is NULL")?.0;
+            let args = match args.as_ref() {
+                Some(args) => args.0.as_list().map_err(|err| err.to_string())?.to_vec(),
+                None => vec![],
+            };
+            let value = engine
+                .call_closure(closure, args)
+                .map_err(|err| engine.format_error(&err))?;
+            Ok(Box::into_raw(Box::new(NuValue(value))))
+        })
+        .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// Set the variable `$name` for the code evaluated after. The value is copied.
+///
+/// # Safety
+///
+/// `engine` has to be a live engine which isn't used by another thread at the same time, `name` a
+/// NUL-terminated string, `value` a live value, and `error` `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_engine_set_var(
+    engine: *mut NuEngine,
+    name: *const c_char,
+    value: *const NuValue,
+    error: *mut *mut c_char,
+) -> bool {
+    unsafe {
+        ffi(error, || {
+            let engine = &mut engine.as_mut().ok_or("engine is NULL")?.0;
+            let name = str_arg(name, "name")?;
+            let value = &value.as_ref().ok_or("value is NULL")?.0;
+            engine
+                .set_var(name, value.clone())
+                .map_err(|err| err.to_string())
+        })
+        .is_some()
+    }
+}
+
+/// Register a command implemented by the application, see [`NuCommandCallback`].
+///
+/// `user_data` is passed to every call of `callback`. The callback can be called from other threads
+/// than the one which registered it, for example by `par-each`.
+///
+/// # Safety
+///
+/// `engine` has to be a live engine which isn't used by another thread at the same time, `name` a
+/// NUL-terminated string, `description` `NULL` or a NUL-terminated string, and `error` `NULL` or
+/// writable. `callback` and `user_data` have to stay usable, from any thread, for as long as the
+/// engine lives.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_engine_add_command(
+    engine: *mut NuEngine,
+    name: *const c_char,
+    description: *const c_char,
+    callback: Option<NuCommandCallback>,
+    user_data: *mut c_void,
+    error: *mut *mut c_char,
+) -> bool {
+    unsafe {
+        ffi(error, || {
+            let engine = &mut engine.as_mut().ok_or("engine is NULL")?.0;
+            let name = str_arg(name, "name")?.to_string();
+            let description = if description.is_null() {
+                ""
+            } else {
+                str_arg(description, "description")?
+            };
+            let command = ForeignCommand {
+                name: name.clone(),
+                callback: callback.ok_or("callback is NULL")?,
+                user_data: UserData(user_data),
+            };
+            engine
+                .add_fn_command(name, description, move |args, input, span| {
+                    command.call(args, input, span)
+                })
+                .map_err(|err| err.to_string())
+        })
+        .is_some()
+    }
+}
+
+/// Create a value from JSON. Objects become records and arrays become lists.
+///
+/// # Safety
+///
+/// `json` has to be a NUL-terminated string, and `error` `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_value_from_json(
+    json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut NuValue {
+    unsafe {
+        ffi(error, || {
+            let json: serde_json::Value =
+                serde_json::from_str(str_arg(json, "json")?).map_err(|err| err.to_string())?;
+            let value = nu_embed::to_value(&json).map_err(|err| err.to_string())?;
+            Ok(Box::into_raw(Box::new(NuValue(value))))
+        })
+        .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// Convert a value to JSON, like `to json --raw` does.
+///
+/// Fails for values which can't be represented in JSON, like closures.
+///
+/// # Safety
+///
+/// `value` has to be a live value, and `error` `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_value_to_json(
+    value: *const NuValue,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    unsafe {
+        ffi(error, || {
+            let value = &value.as_ref().ok_or("value is NULL")?.0;
+            let json: serde_json::Value =
+                nu_embed::from_value(value).map_err(|err| err.to_string())?;
+            Ok(to_c_string(json.to_string()))
+        })
+        .unwrap_or(ptr::null_mut())
+    }
+}
+
+/// The type of a value, like `int` or `record<name: string>`.
+///
+/// # Safety
+///
+/// `value` has to be `NULL` or a live value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_value_type(value: *const NuValue) -> *mut c_char {
+    match unsafe { value.as_ref() } {
+        Some(value) => to_c_string(value.0.get_type().to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Release a value. Does nothing if `value` is `NULL`.
+///
+/// # Safety
+///
+/// `value` has to be `NULL` or a value created by this library which wasn't released yet. It
+/// can't be used after this.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_value_free(value: *mut NuValue) {
+    if !value.is_null() {
+        drop(unsafe { Box::from_raw(value) });
+    }
+}
+
+/// Copy `string` into a string owned by this library, which is released with [`nu_string_free`].
+///
+/// Command callbacks create their error messages with this. Returns `NULL` if `string` is `NULL`.
+///
+/// # Safety
+///
+/// `string` has to be `NULL` or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_string_new(string: *const c_char) -> *mut c_char {
+    if string.is_null() {
+        ptr::null_mut()
+    } else {
+        unsafe { CStr::from_ptr(string) }.to_owned().into_raw()
+    }
+}
+
+/// Release a string returned by this library. Does nothing if `string` is `NULL`.
+///
+/// # Safety
+///
+/// `string` has to be `NULL` or a string returned by this library which wasn't released yet. It
+/// can't be used after this.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nu_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Run `f`, catching panics, and set `*error` if it fails.
+unsafe fn ffi<T>(error: *mut *mut c_char, f: impl FnOnce() -> Result<T, String>) -> Option<T> {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into());
+        Err(format!("nushell panicked: {msg}"))
+    });
+    match result {
+        Ok(value) => Some(value),
+        Err(msg) => {
+            if let Some(error) = unsafe { error.as_mut() } {
+                *error = to_c_string(msg);
+            }
+            None
+        }
+    }
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{name} is NULL"));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| format!("{name} isn't valid UTF-8"))
+}
+
+fn to_c_string(string: String) -> *mut c_char {
+    // interior NUL bytes can't be represented, so they're dropped
+    CString::new(string.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// The pointer given to [`nu_engine_add_command`]. The application is responsible for it being
+/// usable from any thread.
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// A command registered with [`nu_engine_add_command`].
+struct ForeignCommand {
+    name: String,
+    callback: NuCommandCallback,
+    user_data: UserData,
+}
+
+impl ForeignCommand {
+    fn call(&self, args: Vec<Value>, input: Value, span: Span) -> Result<Value, ShellError> {
+        let args = NuValue(Value::list(args, span));
+        let input = NuValue(input);
+
+        let mut output = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let ok =
+            unsafe { (self.callback)(self.user_data.0, &args, &input, &mut output, &mut error) };
+        let output = if output.is_null() {
+            Value::nothing(span)
+        } else {
+            unsafe { Box::from_raw(output) }.0
+        };
+        // the message was created with `nu_string_new`, so it's ours to release
+        let error = if error.is_null() {
+            None
+        } else {
+            Some(unsafe { CString::from_raw(error) })
+        };
+
+        if !ok {
+            return Err(ShellError::GenericError {
+                error: format!("{} failed", self.name),
+                msg: error
+                    .map(|error| error.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "the command returned an error".into()),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            });
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn c(string: &str) -> CString {
+        CString::new(string).unwrap()
+    }
+
+    unsafe fn json(value: *const NuValue) -> String {
+        unsafe {
+            let json = nu_value_to_json(value, ptr::null_mut());
+            let string = CStr::from_ptr(json).to_string_lossy().into_owned();
+            nu_string_free(json);
+            string
+        }
+    }
+
+    #[test]
+    fn eval_and_convert_to_json() {
+        unsafe {
+            let engine = nu_engine_new(ptr::null_mut());
+            assert!(!engine.is_null());
+
+            let input = nu_value_from_json(c(r#"[1, 2, 3]"#).as_ptr(), ptr::null_mut());
+            let source = c("$in | each { $in * 2 } | {doubled: $in}");
+            let value = nu_eval(engine, source.as_ptr(), input, ptr::null_mut());
+            assert_eq!(json(value), r#"{"doubled":[2,4,6]}"#);
+
+            nu_value_free(value);
+            nu_value_free(input);
+            nu_engine_free(engine);
+        }
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        unsafe {
+            let engine = nu_engine_new(ptr::null_mut());
+            let mut error = ptr::null_mut();
+            let value = nu_eval(
+                engine,
+                c("error make {msg: boom}").as_ptr(),
+                ptr::null(),
+                &mut error,
+            );
+            assert!(value.is_null());
+            assert!(CStr::from_ptr(error).to_string_lossy().contains("boom"));
+
+            nu_string_free(error);
+            nu_engine_free(engine);
+        }
+    }
+
+    unsafe extern "C" fn add(
+        user_data: *mut c_void,
+        args: *const NuValue,
+        _input: *const NuValue,
+        output: *mut *mut NuValue,
+        error: *mut *mut c_char,
+    ) -> bool {
+        let offset = unsafe { *(user_data as *const i64) };
+        let Ok(args) = (unsafe { &(*args).0 }).as_list() else {
+            unsafe { *error = nu_string_new(c"expected a list of arguments".as_ptr()) };
+            return false;
+        };
+        let sum = args.iter().filter_map(|arg| arg.as_int().ok()).sum::<i64>() + offset;
+        unsafe { *output = Box::into_raw(Box::new(NuValue(Value::test_int(sum)))) };
+        true
+    }
+
+    #[test]
+    fn commands_call_back() {
+        unsafe {
+            let engine = nu_engine_new(ptr::null_mut());
+            let mut offset = 100i64;
+            assert!(nu_engine_add_command(
+                engine,
+                c("add").as_ptr(),
+                c("Add numbers to an offset").as_ptr(),
+                Some(add),
+                &mut offset as *mut i64 as *mut c_void,
+                ptr::null_mut(),
+            ));
+
+            let value = nu_eval(engine, c("add 1 2").as_ptr(), ptr::null(), ptr::null_mut());
+            assert_eq!(json(value), "103");
+
+            nu_value_free(value);
+            nu_engine_free(engine);
+        }
+    }
+
+    unsafe extern "C" fn fail(
+        _user_data: *mut c_void,
+        _args: *const NuValue,
+        _input: *const NuValue,
+        _output: *mut *mut NuValue,
+        error: *mut *mut c_char,
+    ) -> bool {
+        // the message only lives on the stack of the callback, like the header allows
+        let message = CString::new("the answer is 42").unwrap();
+        unsafe { *error = nu_string_new(message.as_ptr()) };
+        false
+    }
+
+    #[test]
+    fn command_errors_are_reported() {
+        unsafe {
+            let engine = nu_engine_new(ptr::null_mut());
+            assert!(nu_engine_add_command(
+                engine,
+                c("fail").as_ptr(),
+                ptr::null(),
+                Some(fail),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ));
+
+            let mut error = ptr::null_mut();
+            let value = nu_eval(engine, c("fail").as_ptr(), ptr::null(), &mut error);
+            assert!(value.is_null());
+            let message = CStr::from_ptr(error).to_string_lossy().into_owned();
+            assert!(message.contains("fail failed"), "{message}");
+            assert!(message.contains("the answer is 42"), "{message}");
+
+            nu_string_free(error);
+            nu_engine_free(engine);
+        }
+    }
+}
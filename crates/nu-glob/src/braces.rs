@@ -0,0 +1,109 @@
+/// Expand brace sets in a glob pattern into the list of patterns they stand for.
+///
+/// `{a,b}` is replaced with each of its comma separated alternatives, so `*.{rs,toml}` becomes
+/// `*.rs` and `*.toml`. Brace sets may be nested, and patterns with several sets expand to every
+/// combination of alternatives. Braces without a top-level comma, unbalanced braces and braces
+/// inside `[...]` are kept literally.
+///
+/// ```rust
+/// use nu_glob::expand_braces;
+///
+/// assert_eq!(expand_braces("*.{rs,toml}"), vec!["*.rs", "*.toml"]);
+/// assert_eq!(expand_braces("{a,b{c,d}}x"), vec!["ax", "bcx", "bdx"]);
+/// assert_eq!(expand_braces("{a,b}{1,2}"), vec!["a1", "a2", "b1", "b2"]);
+/// assert_eq!(expand_braces("foo{}"), vec!["foo{}"]);
+/// assert_eq!(expand_braces("[{,}]"), vec!["[{,}]"]);
+/// ```
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some((open, close, commas)) = find_brace_set(pattern) else {
+        return vec![pattern.to_string()];
+    };
+
+    let prefix = &pattern[..open];
+    let suffix = &pattern[close + 1..];
+
+    let mut start = open + 1;
+    let mut alternatives = Vec::with_capacity(commas.len() + 1);
+    for comma in commas.into_iter().chain(std::iter::once(close)) {
+        alternatives.push(&pattern[start..comma]);
+        start = comma + 1;
+    }
+
+    alternatives
+        .into_iter()
+        .flat_map(|alternative| expand_braces(&format!("{prefix}{alternative}{suffix}")))
+        .collect()
+}
+
+/// Returns whether the pattern contains a brace set which [`expand_braces`] would expand.
+pub fn has_braces(pattern: &str) -> bool {
+    find_brace_set(pattern).is_some()
+}
+
+/// Find the first expandable brace set, returning the byte offsets of its braces and of its
+/// top-level commas.
+fn find_brace_set(pattern: &str) -> Option<(usize, usize, Vec<usize>)> {
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => i = skip_char_class(bytes, i),
+            b'{' => {
+                if let Some((close, commas)) = match_brace(bytes, i)
+                    && !commas.is_empty()
+                {
+                    return Some((i, close, commas));
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Find the closing brace matching the one at `open`, and the commas which belong to it.
+fn match_brace(bytes: &[u8], open: usize) -> Option<(usize, Vec<usize>)> {
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut i = open + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => {
+                i = skip_char_class(bytes, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some((i, commas)),
+            b'}' => depth -= 1,
+            b',' if depth == 0 => commas.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Skip a `[...]` character class starting at `open`, following the rules of `Pattern::new`:
+/// a `]` right after `[` or `[!` is part of the class. Returns the index after the class, or
+/// after the `[` if the class is not closed.
+fn skip_char_class(bytes: &[u8], open: usize) -> usize {
+    let mut i = open + 1;
+    if bytes.get(i) == Some(&b'!') {
+        i += 1;
+    }
+    // the first character is always part of the class
+    i += 1;
+
+    match bytes
+        .get(i..)
+        .and_then(|rest| rest.iter().position(|&b| b == b']'))
+    {
+        Some(j) => i + j + 1,
+        None => open + 1,
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{MatchOptions, Pattern};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The files which list ignore rules, in the order they're applied.
+const IGNORE_FILES: &[&str] = &[".gitignore", ".ignore"];
+
+/// Skips hidden files and files excluded by `.gitignore` and `.ignore` files, similar to
+/// ripgrep's default filtering.
+///
+/// Ignore files are read from the directory of a path and its parents, up to the root of the git
+/// repository containing it, or up to the root of the walk outside of a git repository. Rules of
+/// deeper files take precedence, and within one file the last matching rule wins, so `!` rules
+/// can re-include files. The ignore files are only read once per directory, and are matched with
+/// [`IgnoreDir`].
+#[derive(Debug)]
+pub struct Ignore {
+    cwd: PathBuf,
+    root: PathBuf,
+    dirs: HashMap<PathBuf, IgnoreDir>,
+    include_hidden: bool,
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Whether the rule contains a separator, and so is matched against the path relative to
+    /// the ignore file instead of the file name.
+    anchored: bool,
+}
+
+impl Ignore {
+    /// Create an empty filter, which resolves relative paths against `cwd`, the absolute current
+    /// directory of the caller. Ignore files are read when they're first needed.
+    ///
+    /// The walk is assumed to start at `cwd`, see [`Ignore::below`].
+    pub fn new(cwd: impl Into<PathBuf>) -> Self {
+        let cwd = cwd.into();
+        Self {
+            root: cwd.clone(),
+            cwd,
+            dirs: HashMap::new(),
+            include_hidden: false,
+        }
+    }
+
+    /// Set the directory the walk starts at. Outside of a git repository, only the ignore files
+    /// of `root` and the directories below it are read.
+    pub fn below(mut self, root: impl AsRef<Path>) -> Self {
+        self.root = self.cwd.join(root);
+        self.dirs.clear();
+        self
+    }
+
+    /// Only skip files excluded by ignore files, but not hidden files.
+    pub fn include_hidden(mut self) -> Self {
+        self.include_hidden = true;
+        self.dirs.clear();
+        self
+    }
+
+    /// Returns whether `path`, which may be a directory, is hidden or ignored.
+    ///
+    /// Only the last component of `path` is checked, so paths inside of an ignored directory
+    /// aren't necessarily ignored themselves. Use [`Ignore::is_ignored_below`] for that.
+    pub fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let path = self.cwd.join(path);
+        match path.parent() {
+            Some(dir) => self.dir(dir).is_ignored(&path, is_dir),
+            None => false,
+        }
+    }
+
+    /// Returns whether `path`, or any of its parents below `root`, is hidden or ignored.
+    pub fn is_ignored_below(&mut self, root: &Path, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return self.is_ignored(path, crate::is_dir(path));
+        };
+
+        let mut current = root.to_path_buf();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            current.push(component);
+            let is_dir = components.peek().is_some() || crate::is_dir(&current);
+            if self.is_ignored(&current, is_dir) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// The rules for the entries of `dir`, an absolute path.
+    fn dir(&mut self, dir: &Path) -> IgnoreDir {
+        if let Some(ignore) = self.dirs.get(dir) {
+            return ignore.clone();
+        }
+        let ignore = match dir.parent() {
+            Some(parent) if dir != self.root && dir.starts_with(&self.root) => {
+                self.dir(parent).child(dir)
+            }
+            _ => IgnoreDir::new(dir, self.include_hidden),
+        };
+        self.dirs.insert(dir.to_path_buf(), ignore.clone());
+        ignore
+    }
+}
+
+/// The ignore rules which apply to the entries of one directory, for walking directory trees.
+///
+/// This is what matches the rules of [`Ignore`], but it doesn't need to be shared mutably: a
+/// walker keeps the `IgnoreDir` of the directory it's reading, and [`IgnoreDir::child`] reads the
+/// ignore files of a subdirectory while sharing the rules of its parents.
+#[derive(Debug, Clone)]
+pub struct IgnoreDir {
+    inner: Arc<IgnoreDirInner>,
+}
+
+#[derive(Debug)]
+struct IgnoreDirInner {
+    dir: PathBuf,
+    rules: Vec<IgnoreRule>,
+    /// The rules of the parent directory, unless this is the root of a git repository or of the
+    /// walk.
+    parent: Option<IgnoreDir>,
+    include_hidden: bool,
+}
+
+impl IgnoreDir {
+    /// Read the rules for the entries of `dir`, the absolute path the walk starts at, from its
+    /// ignore files and those of its parents up to the root of the git repository containing it.
+    /// Outside of a git repository, only the ignore files of `dir` itself are read.
+    pub fn new(dir: &Path, include_hidden: bool) -> Self {
+        let Some(repo) = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+        else {
+            return Self::with_parent(dir, None, include_hidden);
+        };
+
+        let below_repo: Vec<_> = dir
+            .ancestors()
+            .take_while(|ancestor| *ancestor != repo)
+            .collect();
+        below_repo.into_iter().rev().fold(
+            Self::with_parent(repo, None, include_hidden),
+            |parent, ancestor| Self::with_parent(ancestor, Some(parent), include_hidden),
+        )
+    }
+
+    /// Read the rules for the entries of the subdirectory `dir`.
+    pub fn child(&self, dir: &Path) -> Self {
+        let parent = (!dir.join(".git").exists()).then(|| self.clone());
+        Self::with_parent(dir, parent, self.inner.include_hidden)
+    }
+
+    fn with_parent(dir: &Path, parent: Option<IgnoreDir>, include_hidden: bool) -> Self {
+        Self {
+            inner: Arc::new(IgnoreDirInner {
+                dir: dir.to_path_buf(),
+                rules: read_rules(dir),
+                parent,
+                include_hidden,
+            }),
+        }
+    }
+
+    /// Returns whether `path`, an entry of this directory which may be a directory itself, is
+    /// hidden or ignored.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        if !self.inner.include_hidden && name.starts_with('.') && name != "." && name != ".." {
+            return true;
+        }
+
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+
+        let mut current = Some(self);
+        while let Some(ignore) = current {
+            let Ok(relative) = path.strip_prefix(&ignore.inner.dir) else {
+                break;
+            };
+            if let Some(rule) = ignore.inner.rules.iter().rev().find(|rule| {
+                (is_dir || !rule.dir_only)
+                    && if rule.anchored {
+                        rule.pattern.matches_path_with(relative, options)
+                    } else {
+                        rule.pattern.matches_with(name, options)
+                    }
+            }) {
+                return !rule.negated;
+            }
+            current = ignore.inner.parent.as_ref();
+        }
+
+        false
+    }
+}
+
+fn read_rules(dir: &Path) -> Vec<IgnoreRule> {
+    IGNORE_FILES
+        .iter()
+        .filter_map(|file| fs::read_to_string(dir.join(file)).ok())
+        .flat_map(|contents| {
+            contents
+                .lines()
+                .filter_map(IgnoreRule::parse)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+impl IgnoreRule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        // a leading `**/` matches in all directories, just like a pattern without separator
+        let (anchored, line) = match line.strip_prefix("**/") {
+            Some(rest) if !rest.contains('/') => (false, rest),
+            _ => (anchored, line),
+        };
+
+        Some(Self {
+            pattern: Pattern::new(line).ok()?,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+}
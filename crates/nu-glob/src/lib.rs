@@ -73,6 +73,7 @@ doctest!("../README.md");
 
 use std::cmp;
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
 use std::fs;
@@ -85,6 +86,12 @@ use MatchResult::{EntirePatternDoesntMatch, Match, SubPatternDoesntMatch};
 use PatternToken::AnyExcept;
 use PatternToken::{AnyChar, AnyRecursiveSequence, AnySequence, AnyWithin, Char};
 
+mod braces;
+mod ignore;
+
+pub use braces::{expand_braces, has_braces};
+pub use ignore::{Ignore, IgnoreDir};
+
 /// A trait for types that can be periodically polled to check whether to cancel an operation.
 pub trait Interruptible {
     /// Returns whether the current operation should be cancelled.
@@ -99,6 +106,7 @@ impl<I: Interruptible> Interruptible for &I {
 }
 
 /// A no-op implementor of [`Interruptible`] that always returns `false` for [`interrupted`](Interruptible::interrupted).
+#[derive(Clone, Copy, Debug, Default)]
 pub struct Uninterruptible;
 
 impl Interruptible for Uninterruptible {
@@ -124,9 +132,20 @@ pub struct Paths<I = Uninterruptible> {
     options: MatchOptions,
     todo: Vec<Result<(PathBuf, usize), GlobError>>,
     scope: Option<PathBuf>,
+    ignore: Option<Ignore>,
     interrupt: I,
 }
 
+impl<I> Paths<I> {
+    /// Skip hidden files and files excluded by `.gitignore` or `.ignore` files, and don't
+    /// descend into such directories. Relative paths are resolved against `cwd`, the absolute
+    /// current directory of the caller. See [`Ignore`] for details.
+    pub fn respect_ignore_files(mut self, cwd: &Path) -> Self {
+        self.ignore = Some(Ignore::new(cwd));
+        self
+    }
+}
+
 impl Paths<Uninterruptible> {
     /// An iterator representing a single path.
     pub fn single(path: &Path, relative_to: &Path) -> Self {
@@ -136,6 +155,7 @@ impl Paths<Uninterruptible> {
             options: MatchOptions::default(),
             todo: vec![Ok((path.to_path_buf(), 0))],
             scope: Some(relative_to.into()),
+            ignore: None,
             interrupt: Uninterruptible,
         }
     }
@@ -281,6 +301,7 @@ pub fn glob_with<I: Interruptible>(
             options,
             todo: Vec::new(),
             scope: None,
+            ignore: None,
             interrupt,
         });
     }
@@ -313,6 +334,7 @@ pub fn glob_with<I: Interruptible>(
         options,
         todo,
         scope: Some(scope),
+        ignore: None,
         interrupt,
     })
 }
@@ -343,6 +365,109 @@ pub fn glob_with_parent<I: Interruptible>(
     }
 }
 
+/// Return an iterator that produces all the `Path`s that match any of the given patterns.
+///
+/// Brace sets in the patterns are expanded first, see [`expand_braces`]. Patterns starting with
+/// `!` are negations: paths matching them are left out of the results, no matter where in the
+/// list they appear. Each path is only produced once, even if several patterns match it.
+///
+/// ```rust,no_run
+/// use nu_glob::{glob_list, MatchOptions, Uninterruptible};
+///
+/// let patterns = ["**/*.{rs,toml}", "!target/**"];
+/// for path in glob_list(&patterns, MatchOptions::default(), Uninterruptible)
+///     .unwrap()
+///     .filter_map(Result::ok)
+/// {
+///     println!("{}", path.display());
+/// }
+/// ```
+pub fn glob_list<I: Interruptible + Clone>(
+    patterns: &[&str],
+    options: MatchOptions,
+    interrupt: I,
+) -> Result<GlobList<I>, PatternError> {
+    let mut paths = Vec::new();
+    let mut exclude = Vec::new();
+
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) => {
+                for expanded in expand_braces(negated) {
+                    exclude.push(Pattern::new(&expanded)?);
+                }
+            }
+            None => {
+                for expanded in expand_braces(pattern) {
+                    paths.push(glob_with(&expanded, options, interrupt.clone())?);
+                }
+            }
+        }
+    }
+
+    // the patterns are consumed from the back
+    paths.reverse();
+
+    Ok(GlobList {
+        paths,
+        exclude,
+        options,
+        seen: HashSet::new(),
+    })
+}
+
+/// An iterator over the paths matching a list of patterns.
+///
+/// See the `glob_list` function for more details.
+#[derive(Debug)]
+pub struct GlobList<I = Uninterruptible> {
+    paths: Vec<Paths<I>>,
+    exclude: Vec<Pattern>,
+    options: MatchOptions,
+    seen: HashSet<PathBuf>,
+}
+
+impl<I> GlobList<I> {
+    /// Skip hidden files and files excluded by `.gitignore` or `.ignore` files, and don't
+    /// descend into such directories. Relative paths are resolved against `cwd`, the absolute
+    /// current directory of the caller. See [`Ignore`] for details.
+    pub fn respect_ignore_files(mut self, cwd: &Path) -> Self {
+        self.paths = self
+            .paths
+            .into_iter()
+            .map(|paths| paths.respect_ignore_files(cwd))
+            .collect();
+        self
+    }
+}
+
+impl<I: Interruptible> Iterator for GlobList<I> {
+    type Item = GlobResult;
+
+    fn next(&mut self) -> Option<GlobResult> {
+        loop {
+            let paths = self.paths.last_mut()?;
+            match paths.next() {
+                Some(Ok(path)) => {
+                    if self
+                        .exclude
+                        .iter()
+                        .any(|pattern| pattern.matches_path_with(&path, self.options))
+                        || !self.seen.insert(path.clone())
+                    {
+                        continue;
+                    }
+                    return Some(Ok(path));
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                None => {
+                    self.paths.pop();
+                }
+            }
+        }
+    }
+}
+
 const GLOB_CHARS: &[char] = &['*', '?', '['];
 
 /// Returns true if the given pattern is a glob, false if it's merely text to be
@@ -438,6 +563,9 @@ impl<I: Interruptible> Iterator for Paths<I> {
             // Shouldn't happen, but we're using -1 as a special index.
             assert!(self.dir_patterns.len() < !0);
 
+            // The walk starts at the scope
+            self.ignore = self.ignore.take().map(|ignore| ignore.below(&scope));
+
             // if there's one prefilled result, take it, otherwise fill the todo buffer
             if self.todo.len() != 1 {
                 fill_todo(
@@ -465,6 +593,12 @@ impl<I: Interruptible> Iterator for Paths<I> {
                 Err(e) => return Some(Err(e)),
             };
 
+            if let Some(ignore) = &mut self.ignore
+                && ignore.is_ignored(&path, is_dir(&path))
+            {
+                continue;
+            }
+
             // idx -1: was already checked by fill_todo, maybe path was '.' or
             // '..' that we can't match here because of normalization.
             if idx == !0 {
@@ -1157,10 +1291,11 @@ impl Default for MatchOptions {
 
 #[cfg(test)]
 mod test {
-    use crate::{Paths, PatternError, Uninterruptible};
+    use crate::{Ignore, IgnoreDir, Paths, PatternError, Uninterruptible, glob_list};
 
     use super::{MatchOptions, Pattern, glob as glob_with_signals};
-    use std::path::Path;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
     fn glob(pattern: &str) -> Result<Paths, PatternError> {
         glob_with_signals(pattern, Uninterruptible)
@@ -1618,4 +1753,91 @@ mod test {
         let pattern = Path::new("one").join(Path::new("**/*.rs"));
         assert!(Pattern::new(pattern.to_str().unwrap()).is_ok());
     }
+
+    #[test]
+    fn test_glob_list() {
+        let patterns = ["src/*.{rs,md}", "src/lib.rs", "!src/braces.rs"];
+        let paths = glob_list(&patterns, MatchOptions::default(), Uninterruptible)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(paths.contains(&PathBuf::from("src/ignore.rs")));
+        assert!(!paths.contains(&PathBuf::from("src/braces.rs")));
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|path| *path == Path::new("src/lib.rs"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_ignore_files() {
+        let dir = std::env::temp_dir().join("nu_glob_test_ignore_files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join("target/debug")).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+        for file in ["src/main.rs", "src/out.log", "keep.log", "target/debug/app"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let pattern = Pattern::escape(&dir.to_string_lossy()) + "/**/*";
+        let mut paths = glob(&pattern)
+            .unwrap()
+            .respect_ignore_files(&dir)
+            .map(|path| path.unwrap().strip_prefix(&dir).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            ["keep.log", "src", "src/main.rs"]
+                .iter()
+                .map(PathBuf::from)
+                .collect::<Vec<_>>()
+        );
+
+        let mut ignore = Ignore::new(&dir);
+        assert!(ignore.is_ignored_below(&dir, &dir.join("target/debug/app")));
+        assert!(!ignore.is_ignored_below(&dir, &dir.join("src/main.rs")));
+
+        let ignore = IgnoreDir::new(&dir, false);
+        assert!(ignore.is_ignored(&dir.join("target"), true));
+        assert!(ignore.is_ignored(&dir.join(".hidden"), false));
+        assert!(!ignore.is_ignored(&dir.join("src"), true));
+        let src = ignore.child(&dir.join("src"));
+        assert!(!src.is_ignored(&dir.join("src/main.rs"), false));
+        assert!(src.is_ignored(&dir.join("src/out.log"), false));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_ignore_files_outside_of_repository() {
+        let dir = std::env::temp_dir().join("nu_glob_test_ignore_outside");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join(".gitignore"), "*.txt\n").unwrap();
+        fs::write(dir.join("sub/.gitignore"), "*.log\n").unwrap();
+
+        // relative paths are resolved against the given directory
+        let mut ignore = Ignore::new(&dir);
+        assert!(ignore.is_ignored(Path::new("a.txt"), false));
+        assert!(ignore.is_ignored(Path::new("sub/a.txt"), false));
+
+        // the ignore files above the root of the walk don't apply
+        let mut ignore = Ignore::new(&dir).below("sub");
+        assert!(!ignore.is_ignored(Path::new("sub/a.txt"), false));
+        assert!(ignore.is_ignored(Path::new("sub/a.log"), false));
+
+        let ignore = IgnoreDir::new(&dir.join("sub"), false);
+        assert!(!ignore.is_ignored(&dir.join("sub/a.txt"), false));
+        assert!(ignore.is_ignored(&dir.join("sub/a.log"), false));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
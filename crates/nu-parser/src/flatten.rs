@@ -7,7 +7,10 @@ use nu_protocol::{
     },
     engine::StateWorkingSet,
 };
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    fmt::{Display, Formatter, Result},
+    path::Path,
+};
 
 #[derive(Debug, Eq, PartialEq, Ord, Clone, PartialOrd)]
 pub enum FlatShape {
@@ -698,3 +701,29 @@ pub fn flatten_expression(
     flatten_expression_into(working_set, expr, &mut output);
     output
 }
+
+/// Turn the [`FlatShape::External`] shapes of the external commands which are found into
+/// [`FlatShape::ExternalResolved`].
+///
+/// Command names are looked up in `paths`, the directories of `$env.PATH`, through the engine's
+/// cache of their executables, so this is fast enough to do on every keystroke. Paths to commands
+/// are resolved relative to `cwd`.
+pub fn resolve_externals(
+    working_set: &StateWorkingSet,
+    shapes: &mut [(Span, FlatShape)],
+    paths: &str,
+    cwd: &Path,
+) {
+    for (_, shape) in shapes {
+        if let FlatShape::External(head) = shape {
+            let name = String::from_utf8_lossy(working_set.get_span_contents(**head));
+            if working_set
+                .permanent_state
+                .resolve_executable(&name, paths, cwd)
+                .is_some()
+            {
+                *shape = FlatShape::ExternalResolved;
+            }
+        }
+    }
+}
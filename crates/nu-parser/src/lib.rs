@@ -14,6 +14,7 @@ mod type_check;
 pub use deparse::escape_for_script_arg;
 pub use flatten::{
     FlatShape, flatten_block, flatten_expression, flatten_pipeline, flatten_pipeline_element,
+    resolve_externals,
 };
 pub use known_external::KnownExternal;
 pub use lex::{LexState, Token, TokenContents, lex, lex_n_tokens, lex_signature};
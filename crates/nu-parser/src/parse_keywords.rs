@@ -12,9 +12,9 @@ use log::trace;
 use nu_path::canonicalize_with;
 use nu_path::is_windows_device_path;
 use nu_protocol::{
-    Alias, BlockId, CommandWideCompleter, CustomExample, DeclId, FromValue, Module, ModuleId,
-    ParseError, PositionalArg, ResolvedImportPattern, ShellError, Signature, Span, Spanned,
-    SyntaxShape, Type, Value, VarId,
+    Alias, AliasArgument, BlockId, CommandWideCompleter, CustomExample, DeclId, FromValue, Module,
+    ModuleId, ParseError, PositionalArg, ResolvedImportPattern, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value, VarId,
     ast::{
         Argument, AttributeBlock, Block, Call, Expr, Expression, ImportPattern, ImportPatternHead,
         ImportPatternMember, Pipeline, PipelineElement,
@@ -1081,6 +1081,8 @@ fn check_alias_name<'a>(working_set: &mut StateWorkingSet, spans: &'a [Span]) ->
     } else {
         return None;
     };
+    let command_len =
+        command_len + usize::from(has_parameters_flag(working_set, spans, command_len));
 
     if spans.len() == command_len {
         None
@@ -1128,6 +1130,8 @@ pub fn parse_alias(
         };
 
     let name = working_set.get_span_contents(name_span);
+    let parameters = has_parameters_flag(working_set, spans, split_id);
+    let name_id = split_id + usize::from(parameters);
 
     if name != b"alias" {
         working_set.error(ParseError::InternalError(
@@ -1200,28 +1204,28 @@ pub fn parse_alias(
             return garbage_pipeline(working_set, spans);
         };
 
-        if spans.len() >= split_id + 3 {
+        if spans.len() >= name_id + 3 {
             if let Some(mod_name) = module_name {
                 if alias_name.as_bytes() == mod_name {
                     working_set.error(ParseError::NamedAsModule(
                         "alias".to_string(),
                         alias_name,
                         "main".to_string(),
-                        spans[split_id],
+                        spans[name_id],
                     ));
 
                     return alias_pipeline;
                 }
 
                 if alias_name == "main" {
-                    working_set.error(ParseError::ExportMainAliasNotAllowed(spans[split_id]));
+                    working_set.error(ParseError::ExportMainAliasNotAllowed(spans[name_id]));
                     return alias_pipeline;
                 }
             }
 
-            let _equals = working_set.get_span_contents(spans[split_id + 1]);
+            let _equals = working_set.get_span_contents(spans[name_id + 1]);
 
-            let replacement_spans = &spans[(split_id + 2)..];
+            let replacement_spans = &spans[(name_id + 2)..];
             let first_bytes = working_set.get_span_contents(replacement_spans[0]);
 
             if first_bytes != b"if"
@@ -1243,10 +1247,26 @@ pub fn parse_alias(
                 return alias_pipeline;
             }
 
+            // Placeholders like `{branch}` are filled in by the arguments of each call, so the
+            // aliased command is checked without them. Without `--parameters`, they're blocks.
+            let placeholders: Vec<Option<String>> = replacement_spans
+                .iter()
+                .enumerate()
+                .map(|(i, span)| {
+                    (parameters && i > 0).then(|| alias_parameter(working_set, *span))?
+                })
+                .collect();
+            let call_spans: Vec<Span> = replacement_spans
+                .iter()
+                .zip(&placeholders)
+                .filter(|(_, placeholder)| placeholder.is_none())
+                .map(|(span, _)| *span)
+                .collect();
+
             let starting_error_count = working_set.parse_errors.len();
             working_set.search_predecls = false;
 
-            let expr = parse_call(working_set, replacement_spans, replacement_spans[0]);
+            let expr = parse_call(working_set, &call_spans, call_spans[0]);
 
             working_set.search_predecls = true;
 
@@ -1320,10 +1340,30 @@ pub fn parse_alias(
                 },
             };
 
+            let arguments = if placeholders.iter().any(Option::is_some) {
+                let head_end = match &wrapped_call.expr {
+                    Expr::Call(call) => call.head.end,
+                    Expr::ExternalCall(head, _) => head.span.end,
+                    _ => replacement_spans[0].end,
+                };
+                replacement_spans
+                    .iter()
+                    .zip(placeholders)
+                    .filter(|(span, _)| span.start >= head_end)
+                    .map(|(span, placeholder)| match placeholder {
+                        Some(name) => AliasArgument::Parameter(name),
+                        None => AliasArgument::Fixed(*span),
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+
             let decl = Alias {
                 name: alias_name,
                 command,
                 wrapped_call,
+                arguments,
                 description,
                 extra_description,
             };
@@ -1371,6 +1411,32 @@ pub fn parse_alias(
     garbage_pipeline(working_set, spans)
 }
 
+/// Whether the alias is declared with `--parameters`, which comes right after the keyword at
+/// `keyword_len`.
+fn has_parameters_flag(working_set: &StateWorkingSet, spans: &[Span], keyword_len: usize) -> bool {
+    spans.get(keyword_len).is_some_and(|span| {
+        matches!(
+            working_set.get_span_contents(*span),
+            b"--parameters" | b"-p"
+        )
+    })
+}
+
+/// The name of a placeholder like `{branch}` in an alias declared with `--parameters`.
+fn alias_parameter(working_set: &StateWorkingSet, span: Span) -> Option<String> {
+    let name = working_set
+        .get_span_contents(span)
+        .strip_prefix(b"{")?
+        .strip_suffix(b"}")?;
+    let is_identifier = name
+        .first()
+        .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_')
+        && name
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-'));
+    is_identifier.then(|| String::from_utf8_lossy(name).into_owned())
+}
+
 // Return false if command `export xxx` not found
 // TODO: Rather than this, handle `export xxx` correctly in `parse_xxx`
 fn warp_export_call(
@@ -1588,7 +1654,8 @@ pub fn parse_export_in_module(
 
                 let mut result = vec![];
 
-                let alias_name = match spans.get(2) {
+                let name_id = 2 + usize::from(has_parameters_flag(working_set, spans, 2));
+                let alias_name = match spans.get(name_id) {
                     Some(span) => working_set.get_span_contents(*span),
                     None => &[],
                 };
@@ -3593,8 +3660,8 @@ pub fn parse_mut(working_set: &mut StateWorkingSet, spans: &[Span]) -> Pipeline
 
 pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteCommand) -> Pipeline {
     trace!("parsing source");
-    let spans = &lite_command.parts;
-    let name = working_set.get_span_contents(spans[0]);
+    let all_spans = &lite_command.parts;
+    let name = working_set.get_span_contents(all_spans[0]);
 
     if name == b"source" || name == b"source-env" {
         if let Some(redirection) = lite_command.redirection.as_ref() {
@@ -3604,11 +3671,21 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                 "source-env"
             };
             working_set.error(redirecting_builtin_error(name, redirection));
-            return garbage_pipeline(working_set, spans);
+            return garbage_pipeline(working_set, all_spans);
         }
 
         let scoped = name == b"source-env";
 
+        // The arguments for the script go after `--`, so that mistyped flags of `source` are
+        // still reported.
+        let (spans, script_args) = match all_spans
+            .iter()
+            .position(|span| !scoped && working_set.get_span_contents(*span) == b"--")
+        {
+            Some(pos) => (&all_spans[..pos], &all_spans[pos + 1..]),
+            None => (&all_spans[..], &[][..]),
+        };
+
         if let Some(decl_id) = working_set.find_decl(name) {
             #[allow(deprecated)]
             let cwd = working_set.get_cwd();
@@ -3616,7 +3693,7 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
             // Is this the right call to be using here?
             // Some of the others (`parse_let`) use it, some of them (`parse_hide`) don't.
             let ParsedInternalCall {
-                call,
+                mut call,
                 output,
                 call_kind,
             } = parse_internal_call(working_set, spans[0], &spans[1..], decl_id);
@@ -3625,11 +3702,28 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                 return Pipeline::from_vec(vec![Expression::new(
                     working_set,
                     Expr::Call(call),
-                    Span::concat(spans),
+                    Span::concat(all_spans),
                     output,
                 )]);
             }
 
+            if !scoped {
+                if let Some(extra) = call.positional_iter().nth(1) {
+                    working_set.error(ParseError::LabeledError(
+                        "Arguments for the script go after `--`".into(),
+                        format!(
+                            "use `source <file> -- {}`",
+                            String::from_utf8_lossy(working_set.get_span_contents(extra.span))
+                        ),
+                        extra.span,
+                    ));
+                }
+                for span in script_args {
+                    let arg = parse_value(working_set, *span, &SyntaxShape::Any);
+                    call.add_positional(arg);
+                }
+            }
+
             // Command and one file name
             if spans.len() >= 2 {
                 let expr = parse_value(working_set, spans[1], &SyntaxShape::Any);
@@ -3656,7 +3750,7 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                     return Pipeline::from_vec(vec![Expression::new(
                         working_set,
                         Expr::Call(call),
-                        Span::concat(spans),
+                        Span::concat(all_spans),
                         Type::Any,
                     )]);
                 }
@@ -3679,9 +3773,25 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                         // Add the file to the stack of files being processed.
                         if let Err(e) = working_set.files.push(path.clone().path_buf(), spans[1]) {
                             working_set.error(e);
-                            return garbage_pipeline(working_set, spans);
+                            return garbage_pipeline(working_set, all_spans);
                         }
 
+                        // The arguments after `--` are available in the script, but not
+                        // after it, so remember what `$argv` was before.
+                        let outer_argv = working_set
+                            .last_overlay_mut()
+                            .vars
+                            .get(b"$argv".as_slice())
+                            .copied();
+                        let argv_var_id = (!scoped).then(|| {
+                            working_set.add_variable(
+                                b"$argv".to_vec(),
+                                spans[1],
+                                Type::List(Box::new(Type::Any)),
+                                false,
+                            )
+                        });
+
                         // This will load the defs from the file into the
                         // working set, if it was a successful parse.
                         let mut block = parse(
@@ -3698,6 +3808,14 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                         // Remove the file from the stack of files being processed.
                         working_set.files.pop();
 
+                        if argv_var_id.is_some() {
+                            let vars = &mut working_set.last_overlay_mut().vars;
+                            match outer_argv {
+                                Some(var_id) => vars.insert(b"$argv".to_vec(), var_id),
+                                None => vars.remove(b"$argv".as_slice()),
+                            };
+                        }
+
                         // Save the block into the working set
                         let block_id = working_set.add_block(block);
 
@@ -3715,6 +3833,18 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                             ),
                         );
 
+                        if let Some(var_id) = argv_var_id {
+                            call_with_block.set_parser_info(
+                                "argv_var_id".to_string(),
+                                Expression::new(
+                                    working_set,
+                                    Expr::Int(var_id.get() as i64),
+                                    spans[1],
+                                    Type::Any,
+                                ),
+                            );
+                        }
+
                         // store the file path as a string to be gathered later
                         call_with_block.set_parser_info(
                             "block_id_name".to_string(),
@@ -3729,7 +3859,7 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
                         return Pipeline::from_vec(vec![Expression::new(
                             working_set,
                             Expr::Call(call_with_block),
-                            Span::concat(spans),
+                            Span::concat(all_spans),
                             Type::Any,
                         )]);
                     }
@@ -3740,16 +3870,16 @@ pub fn parse_source(working_set: &mut StateWorkingSet, lite_command: &LiteComman
             return Pipeline::from_vec(vec![Expression::new(
                 working_set,
                 Expr::Call(call),
-                Span::concat(spans),
+                Span::concat(all_spans),
                 Type::Any,
             )]);
         }
     }
     working_set.error(ParseError::UnknownState(
         "internal error: source statement unparsable".into(),
-        Span::concat(spans),
+        Span::concat(all_spans),
     ));
-    garbage_pipeline(working_set, spans)
+    garbage_pipeline(working_set, all_spans)
 }
 
 pub fn parse_where_expr(working_set: &mut StateWorkingSet, spans: &[Span]) -> Expression {
@@ -13,9 +13,10 @@ use itertools::Itertools;
 use log::trace;
 use nu_engine::DIR_VAR_PARSER_INFO;
 use nu_protocol::{
-    BlockId, DeclId, DidYouMean, ENV_VARIABLE_ID, FilesizeUnit, Flag, IN_VARIABLE_ID, ParseError,
-    PositionalArg, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value, VarId, ast::*,
-    casing::Casing, did_you_mean, engine::StateWorkingSet, eval_const::eval_constant,
+    Alias, AliasArgument, BlockId, DeclId, DidYouMean, ENV_VARIABLE_ID, FilesizeUnit, Flag,
+    IN_VARIABLE_ID, ParseError, PositionalArg, ShellError, Signature, Span, Spanned, SyntaxShape,
+    Type, Value, VarId, ast::*, casing::Casing, did_you_mean, engine::StateWorkingSet,
+    eval_const::eval_constant,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -1407,6 +1408,17 @@ pub fn parse_call(working_set: &mut StateWorkingSet, spans: &[Span], head: Span)
         let decl = working_set.get_decl(decl_id);
 
         let parsed_call = if let Some(alias) = decl.as_alias() {
+            if !alias.arguments.is_empty() {
+                trace!("parsing: alias with parameters");
+                let alias = alias.clone();
+                return parse_alias_with_arguments(
+                    working_set,
+                    &alias,
+                    Span::concat(&spans[cmd_start..pos]),
+                    spans,
+                    &spans[pos..],
+                );
+            }
             if let Expression {
                 expr: Expr::ExternalCall(head, args),
                 span: _,
@@ -1552,6 +1564,73 @@ pub fn find_longest_decl_with_prefix(
     (cmd_start, pos, name, maybe_decl_id)
 }
 
+/// Parse a call of an alias with parameters, like
+/// `alias --parameters gco = git checkout {branch}`.
+///
+/// The arguments of the call are put in place of the parameters, and the rest of them are passed
+/// after the arguments of the alias.
+fn parse_alias_with_arguments(
+    working_set: &mut StateWorkingSet,
+    alias: &Alias,
+    name_span: Span,
+    spans: &[Span],
+    arg_spans: &[Span],
+) -> Expression {
+    let mut call_args = arg_spans.iter().copied();
+    let mut args = vec![];
+    for argument in &alias.arguments {
+        match argument {
+            AliasArgument::Fixed(span) => args.push(*span),
+            AliasArgument::Parameter(name) => match call_args.next() {
+                Some(span) => args.push(span),
+                None => {
+                    let end = Span::concat(spans).end;
+                    working_set.error(ParseError::MissingPositional(
+                        name.clone(),
+                        Span::new(end, end),
+                        alias.name.clone(),
+                    ));
+                    return garbage(working_set, Span::concat(spans));
+                }
+            },
+        }
+    }
+    args.extend(call_args);
+
+    match &alias.wrapped_call.expr {
+        Expr::ExternalCall(head, _) => {
+            let mut head = head.clone();
+            head.span = name_span; // replacing the spans preserves syntax highlighting
+            let args: Vec<_> = args
+                .iter()
+                .map(|span| parse_external_arg(working_set, *span))
+                .collect();
+            Expression::new(
+                working_set,
+                Expr::ExternalCall(head, args.into()),
+                Span::concat(spans),
+                alias.wrapped_call.ty.clone(),
+            )
+        }
+        Expr::Call(call) => {
+            let parsed_call = parse_internal_call(working_set, name_span, &args, call.decl_id);
+            Expression::new(
+                working_set,
+                Expr::Call(parsed_call.call),
+                Span::concat(spans),
+                parsed_call.output,
+            )
+        }
+        _ => {
+            working_set.error(ParseError::UnknownState(
+                "Alias does not point to a call.".to_string(),
+                name_span,
+            ));
+            garbage(working_set, Span::concat(spans))
+        }
+    }
+}
+
 pub fn parse_attribute(
     working_set: &mut StateWorkingSet,
     lite_command: &LiteCommand,
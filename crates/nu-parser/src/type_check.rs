@@ -133,6 +133,7 @@ pub fn math_result_type(
             (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -167,6 +168,7 @@ pub fn math_result_type(
             (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -204,6 +206,7 @@ pub fn math_result_type(
             (Type::Float, Type::Duration) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -234,6 +237,7 @@ pub fn math_result_type(
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -264,6 +268,7 @@ pub fn math_result_type(
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -294,6 +299,7 @@ pub fn math_result_type(
             (Type::Duration, Type::Float) => (Type::Duration, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -318,6 +324,7 @@ pub fn math_result_type(
             (Type::Float, Type::Number) => (Type::Number, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) => (Type::Any, None),
             (_, Type::Any) => (Type::Any, None),
             _ => {
@@ -355,6 +362,7 @@ pub fn math_result_type(
             (Type::Binary, Type::Binary) => (Type::Binary, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Any, _) | (_, Type::Any) => (Type::Any, None),
             _ => {
                 *op = Expression::garbage(working_set, op.span);
@@ -434,6 +442,7 @@ pub fn math_result_type(
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -480,6 +489,7 @@ pub fn math_result_type(
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -526,6 +536,7 @@ pub fn math_result_type(
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -572,6 +583,7 @@ pub fn math_result_type(
             (Type::Bool, Type::Bool) => (Type::Bool, None),
             (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
             (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+            (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
             (Type::Nothing, _) => (Type::Nothing, None), // TODO: is this right
             (_, Type::Nothing) => (Type::Nothing, None), // TODO: is this right
             // TODO: should this include:
@@ -605,6 +617,7 @@ pub fn math_result_type(
             match (&lhs.ty, &rhs.ty) {
                 (Type::Custom(a), Type::Custom(b)) if a == b => (Type::Custom(a.clone()), None),
                 (Type::Custom(a), _) => (Type::Custom(a.clone()), None),
+                (_, Type::Custom(b)) => (Type::Custom(b.clone()), None),
                 _ => (Type::Bool, None),
             }
         }
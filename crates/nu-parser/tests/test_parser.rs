@@ -3168,3 +3168,25 @@ mod record {
         assert_ne!(op.span, rhs.span)
     }
 }
+
+#[test]
+fn resolve_externals_finds_commands_in_path() {
+    let engine_state = EngineState::new();
+    let exe = std::env::current_exe().unwrap();
+    let dir = exe.parent().unwrap();
+    let name = exe.file_name().unwrap().to_str().unwrap();
+
+    let mut working_set = StateWorkingSet::new(&engine_state);
+    let source = format!("^{name}; ^nu_parser_test_missing_command");
+    let block = parse(&mut working_set, None, source.as_bytes(), true);
+    let mut shapes = flatten_block(&working_set, &block);
+    resolve_externals(&working_set, &mut shapes, dir.to_str().unwrap(), dir);
+
+    let externals: Vec<_> = shapes
+        .into_iter()
+        .filter(|(_, shape)| matches!(shape, FlatShape::External(_) | FlatShape::ExternalResolved))
+        .collect();
+    assert_eq!(externals.len(), 2);
+    assert_eq!(externals[0].1, FlatShape::ExternalResolved);
+    assert!(matches!(externals[1].1, FlatShape::External(_)));
+}
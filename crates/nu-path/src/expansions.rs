@@ -36,7 +36,7 @@ fn canonicalize(path: impl AsRef<Path>) -> io::Result<PathBuf> {
 
 #[cfg(windows)]
 fn canonicalize_path(path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
-    path.canonicalize()?.to_winuser_path()
+    crate::long_path(path).canonicalize()?.to_winuser_path()
 }
 
 #[cfg(not(windows))]
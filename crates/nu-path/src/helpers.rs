@@ -1,6 +1,11 @@
 #[cfg(windows)]
+use std::ffi::OsString;
+#[cfg(windows)]
 use std::path::{Component, Prefix};
-use std::path::{Path, PathBuf};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
 
 use crate::AbsolutePathBuf;
 
@@ -26,6 +31,55 @@ pub fn nu_config_dir() -> Option<AbsolutePathBuf> {
     })
 }
 
+/// Return the nushell data directory, which holds state such as the history and the plugin
+/// registry.
+pub fn nu_data_dir() -> Option<AbsolutePathBuf> {
+    data_dir().map(|mut p| {
+        p.push("nushell");
+        p
+    })
+}
+
+/// Return the path of a nushell state file, like the history or the plugin registry.
+///
+/// State files belong in [`nu_data_dir`], but older versions kept them in [`nu_config_dir`]. A
+/// file left there by an older version keeps being used until it's moved with `config migrate`.
+pub fn nu_state_file(name: impl AsRef<Path>) -> Option<AbsolutePathBuf> {
+    let name = name.as_ref();
+    let data_file = nu_data_dir().map(|dir| dir.join(name));
+    if data_file.as_ref().is_some_and(|file| file.exists()) {
+        return data_file;
+    }
+
+    // Use `symlink_metadata` so that a broken symlink still counts as a legacy file.
+    let legacy_file = nu_config_dir().map(|dir| dir.join(name));
+    if legacy_file
+        .as_ref()
+        .is_some_and(|file| file.symlink_metadata().is_ok())
+    {
+        return legacy_file;
+    }
+
+    data_file.or(legacy_file)
+}
+
+/// Follow a state file which was moved from [`nu_config_dir`] to [`nu_data_dir`] by
+/// `config migrate` after `path` was chosen.
+///
+/// Returns the file in the data directory if `path` is a state file in the config directory that
+/// doesn't exist anymore, and `path` itself otherwise.
+pub fn follow_state_file(path: &Path) -> Cow<'_, Path> {
+    if path.symlink_metadata().is_err()
+        && let (Some(dir), Some(name)) = (path.parent(), path.file_name())
+        && nu_config_dir().is_some_and(|config_dir| config_dir.as_std_path() == dir)
+        && let Some(data_file) = nu_data_dir().map(|dir| dir.join(name))
+        && data_file.exists()
+    {
+        return Cow::Owned(data_file.into_std_path_buf());
+    }
+    Cow::Borrowed(path)
+}
+
 fn configurable_dir_path(
     name: &'static str,
     dir: impl FnOnce() -> Option<PathBuf>,
@@ -37,6 +91,60 @@ fn configurable_dir_path(
         .map(|path| path.canonicalize().map(Into::into).unwrap_or(path))
 }
 
+/// The longest path most Windows APIs accept, unless they get a verbatim (`\\?\`) path.
+#[cfg(windows)]
+const MAX_PATH: usize = 260;
+
+/// Turn a long absolute path into a verbatim path on Windows, so the file system APIs accept it
+/// even if long paths aren't enabled for the whole system.
+///
+/// A verbatim path starts with `\\?\`, or `\\?\UNC\` for a network share, and Windows doesn't
+/// normalize it, so `.`, `..` and `/` are resolved first, with `..` at the root staying at the
+/// root like it does in a normal path. Shorter paths, relative paths and paths on other platforms
+/// are returned unchanged.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    if path.as_os_str().len() < MAX_PATH || !path.is_absolute() {
+        return Cow::Borrowed(path);
+    }
+    let mut components = path.components();
+    let mut verbatim = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(letter) => OsString::from(format!(r"\\?\{}:", letter as char)),
+            Prefix::UNC(server, share) => {
+                let mut verbatim = OsString::from(r"\\?\UNC\");
+                verbatim.push(server);
+                verbatim.push(r"\");
+                verbatim.push(share);
+                verbatim
+            }
+            // already verbatim, or a device path
+            _ => return Cow::Borrowed(path),
+        },
+        _ => return Cow::Borrowed(path),
+    };
+    let mut names = Vec::new();
+    for component in components {
+        match component {
+            Component::Normal(name) => names.push(name),
+            Component::ParentDir => {
+                names.pop();
+            }
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+        }
+    }
+    for name in names {
+        verbatim.push(r"\");
+        verbatim.push(name);
+    }
+    Cow::Owned(PathBuf::from(verbatim))
+}
+
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    Cow::Borrowed(path)
+}
+
 // List of special paths that can be written to and/or read from, even though they
 // don't appear as directory entries.
 // See https://learn.microsoft.com/en-us/windows/win32/fileio/naming-a-file
@@ -135,3 +243,55 @@ mod test_is_windows_device_path {
         assert!(!is_windows_device_path(Path::new(r"\\?\dir\file")))
     }
 }
+
+#[cfg(test)]
+mod test_long_path {
+    use crate::long_path;
+    use std::path::Path;
+
+    #[test]
+    fn short_path_is_unchanged() {
+        let path = Path::new("dir/file");
+        assert_eq!(long_path(path), path);
+    }
+
+    #[cfg_attr(not(windows), ignore = "only for Windows")]
+    #[test]
+    fn long_disk_path() {
+        let name = "a".repeat(300);
+        let path = format!(r"C:\dir\..\{name}/file");
+        assert_eq!(
+            long_path(Path::new(&path)),
+            Path::new(&format!(r"\\?\C:\{name}\file"))
+        );
+    }
+
+    #[cfg_attr(not(windows), ignore = "only for Windows")]
+    #[test]
+    fn parent_of_the_root_is_the_root() {
+        let name = "a".repeat(300);
+        let path = format!(r"C:\..\..\dir\.\..\{name}\..\{name}");
+        assert_eq!(
+            long_path(Path::new(&path)),
+            Path::new(&format!(r"\\?\C:\{name}"))
+        );
+    }
+
+    #[cfg_attr(not(windows), ignore = "only for Windows")]
+    #[test]
+    fn long_unc_path() {
+        let name = "a".repeat(300);
+        let path = format!(r"\\server\share\{name}");
+        assert_eq!(
+            long_path(Path::new(&path)),
+            Path::new(&format!(r"\\?\UNC\server\share\{name}"))
+        );
+    }
+
+    #[cfg_attr(not(windows), ignore = "only for Windows")]
+    #[test]
+    fn verbatim_path_is_unchanged() {
+        let path = format!(r"\\?\C:\{}", "a".repeat(300));
+        assert_eq!(long_path(Path::new(&path)), Path::new(&path));
+    }
+}
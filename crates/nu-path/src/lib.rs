@@ -13,7 +13,10 @@ pub use components::components;
 pub use expansions::{
     canonicalize_with, expand_path, expand_path_with, expand_to_real_path, locate_in_dirs,
 };
-pub use helpers::{cache_dir, data_dir, home_dir, is_windows_device_path, nu_config_dir};
+pub use helpers::{
+    cache_dir, data_dir, follow_state_file, home_dir, is_windows_device_path, long_path,
+    nu_config_dir, nu_data_dir, nu_state_file,
+};
 pub use path::*;
 pub use tilde::expand_tilde;
 pub use trailing_slash::{has_trailing_slash, strip_trailing_slash};
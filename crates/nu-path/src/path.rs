@@ -885,6 +885,13 @@ impl<Form: IsAbsolute> Path<Form> {
         self.inner.to_path_buf()
     }
 
+    /// The path to pass to the file system, which is a verbatim path for long paths on Windows.
+    ///
+    /// See [`long_path`](crate::long_path).
+    fn fs_path(&self) -> Cow<'_, std::path::Path> {
+        crate::long_path(&self.inner)
+    }
+
     /// Queries the file system to get information about a file, directory, etc.
     ///
     /// This function will traverse symbolic links to query information about the destination file.
@@ -902,7 +909,7 @@ impl<Form: IsAbsolute> Path<Form> {
     /// ```
     #[inline]
     pub fn metadata(&self) -> io::Result<fs::Metadata> {
-        self.inner.metadata()
+        self.fs_path().metadata()
     }
 
     /// Returns an iterator over the entries within a directory.
@@ -926,7 +933,7 @@ impl<Form: IsAbsolute> Path<Form> {
     /// ```
     #[inline]
     pub fn read_dir(&self) -> io::Result<fs::ReadDir> {
-        self.inner.read_dir()
+        self.fs_path().read_dir()
     }
 
     /// Returns `true` if the path points at an existing entity.
@@ -950,7 +957,7 @@ impl<Form: IsAbsolute> Path<Form> {
     #[must_use]
     #[inline]
     pub fn exists(&self) -> bool {
-        self.inner.exists()
+        self.fs_path().exists()
     }
 
     /// Returns `true` if the path exists on disk and is pointing at a regular file.
@@ -981,7 +988,7 @@ impl<Form: IsAbsolute> Path<Form> {
     #[must_use]
     #[inline]
     pub fn is_file(&self) -> bool {
-        self.inner.is_file()
+        self.fs_path().is_file()
     }
 
     /// Returns `true` if the path exists on disk and is pointing at a directory.
@@ -1005,7 +1012,7 @@ impl<Form: IsAbsolute> Path<Form> {
     #[must_use]
     #[inline]
     pub fn is_dir(&self) -> bool {
-        self.inner.is_dir()
+        self.fs_path().is_dir()
     }
 }
 
@@ -1028,7 +1035,7 @@ impl AbsolutePath {
     #[cfg(not(windows))]
     #[inline]
     pub fn canonicalize(&self) -> io::Result<CanonicalPathBuf> {
-        self.inner
+        self.fs_path()
             .canonicalize()
             .map(CanonicalPathBuf::new_unchecked)
     }
@@ -1052,7 +1059,7 @@ impl AbsolutePath {
     pub fn canonicalize(&self) -> io::Result<CanonicalPathBuf> {
         use omnipath::WinPathExt;
 
-        let path = self.inner.canonicalize()?.to_winuser_path()?;
+        let path = self.fs_path().canonicalize()?.to_winuser_path()?;
         Ok(CanonicalPathBuf::new_unchecked(path))
     }
 
@@ -1070,7 +1077,7 @@ impl AbsolutePath {
     /// ```
     #[inline]
     pub fn read_link(&self) -> io::Result<AbsolutePathBuf> {
-        self.inner.read_link().map(PathBuf::new_unchecked)
+        self.fs_path().read_link().map(PathBuf::new_unchecked)
     }
 
     /// Returns `Ok(true)` if the path points at an existing entity.
@@ -1101,7 +1108,7 @@ impl AbsolutePath {
     /// ```
     #[inline]
     pub fn try_exists(&self) -> io::Result<bool> {
-        self.inner.try_exists()
+        self.fs_path().try_exists()
     }
 
     /// Returns `true` if the path exists on disk and is pointing at a symbolic link.
@@ -1127,7 +1134,7 @@ impl AbsolutePath {
     #[must_use]
     #[inline]
     pub fn is_symlink(&self) -> bool {
-        self.inner.is_symlink()
+        self.fs_path().is_symlink()
     }
 
     /// Queries the metadata about a file without following symlinks.
@@ -1145,7 +1152,7 @@ impl AbsolutePath {
     /// ```
     #[inline]
     pub fn symlink_metadata(&self) -> io::Result<fs::Metadata> {
-        self.inner.symlink_metadata()
+        self.fs_path().symlink_metadata()
     }
 }
 
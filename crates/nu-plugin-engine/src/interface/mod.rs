@@ -6,19 +6,21 @@ use nu_plugin_core::{
     util::{Waitable, WaitableMut, with_custom_values_in},
 };
 use nu_plugin_protocol::{
-    CallInfo, CustomValueOp, EngineCall, EngineCallId, EngineCallResponse, EvaluatedCall, Ordering,
-    PluginCall, PluginCallId, PluginCallResponse, PluginCustomValue, PluginInput, PluginOption,
-    PluginOutput, ProtocolInfo, StreamId, StreamMessage,
+    CallInfo, CustomValueOp, EngineCall, EngineCallId, EngineCallResponse, EvaluatedCall, Feature,
+    Ordering, PluginCall, PluginCallId, PluginCallResponse, PluginCustomValue, PluginInput,
+    PluginOption, PluginOutput, ProtocolInfo, StreamId, StreamMessage,
 };
 use nu_protocol::{
     CustomValue, IntoSpanned, PipelineData, PluginMetadata, PluginSignature, ShellError,
-    SignalAction, Signals, Span, Spanned, Value, ast::Operator, casing::Casing, engine::Sequence,
+    SignalAction, Signals, Span, Spanned, Value, ast::Operator, casing::Casing,
+    default_operation_rhs, engine::Sequence,
 };
 use nu_utils::SharedCow;
 use std::{
     collections::{BTreeMap, btree_map},
     path::Path,
     sync::{Arc, OnceLock, mpsc},
+    time::{Duration, Instant},
 };
 
 use crate::{
@@ -29,6 +31,13 @@ use crate::{
 #[cfg(test)]
 mod tests;
 
+/// How often to check for an interrupt while waiting for the response to a plugin call.
+const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a plugin has to respond to a call after nushell was interrupted, before nushell stops
+/// waiting for it.
+const INTERRUPT_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
 #[derive(Debug)]
 enum ReceivedPluginCallMessage {
     /// The final response to send
@@ -808,8 +817,36 @@ impl PluginInterface {
         mut context: Option<&mut (dyn PluginExecutionContext + '_)>,
         mut state: CurrentCallState,
     ) -> Result<PluginCallResponse<PipelineData>, ShellError> {
+        let signals = context
+            .as_deref()
+            .map(|context| context.signals().clone())
+            .unwrap_or(Signals::EMPTY);
+        let mut interrupted_at = None;
+
         // Handle message from receiver
-        for msg in rx {
+        loop {
+            let msg = match rx.recv_timeout(INTERRUPT_POLL_INTERVAL) {
+                Ok(msg) => msg,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    // The plugin got a signal message when nushell was interrupted, and should
+                    // return soon. If it doesn't, stop waiting for it.
+                    if signals.interrupted() {
+                        let interrupted_at = *interrupted_at.get_or_insert_with(Instant::now);
+                        if interrupted_at.elapsed() >= INTERRUPT_GRACE_PERIOD {
+                            if state.entered_foreground
+                                && let Some(context) = context.as_deref_mut()
+                            {
+                                let _ = set_foreground(self.state.process.as_ref(), context, false);
+                            }
+                            return Err(ShellError::Interrupted {
+                                span: state.span.unwrap_or(Span::unknown()),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            };
             match msg {
                 ReceivedPluginCallMessage::Response(resp) => {
                     if state.entered_foreground {
@@ -1060,6 +1097,32 @@ impl PluginInterface {
         self.custom_value_op_expecting_value(left, CustomValueOp::Operation(operator, right))
     }
 
+    /// Invoke functionality for an operator with a custom value on the right side.
+    ///
+    /// Plugins which don't support [`Feature::OperationRhs`] get the default `operation_rhs`
+    /// behavior instead.
+    pub fn custom_value_operation_rhs(
+        &self,
+        left: Value,
+        operator: Spanned<Operator>,
+        right: Spanned<PluginCustomValueWithSource>,
+    ) -> Result<Value, ShellError> {
+        if self
+            .protocol_info()?
+            .supports_feature(&Feature::OperationRhs)
+        {
+            self.custom_value_op_expecting_value(right, CustomValueOp::OperationRhs(left, operator))
+        } else {
+            default_operation_rhs(
+                right.item.type_name(),
+                &left,
+                operator.item,
+                operator.span,
+                right.span,
+            )
+        }
+    }
+
     /// Invoke saving operation on a custom value.
     pub fn custom_value_save(
         &self,
@@ -1280,6 +1343,7 @@ impl CurrentCallState {
                     CustomValueOp::FollowPathString { .. } => Ok(()),
                     CustomValueOp::PartialCmp(value) => self.prepare_value(value, source),
                     CustomValueOp::Operation(_, value) => self.prepare_value(value, source),
+                    CustomValueOp::OperationRhs(value, _) => self.prepare_value(value, source),
                     CustomValueOp::Save { .. } => Ok(()),
                     CustomValueOp::Dropped => Ok(()),
                 }
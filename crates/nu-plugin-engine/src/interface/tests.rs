@@ -17,7 +17,7 @@ use nu_plugin_protocol::{
 use nu_protocol::{
     BlockId, ByteStreamType, CustomValue, IntoInterruptiblePipelineData, IntoSpanned, PipelineData,
     PipelineMetadata, PluginMetadata, PluginSignature, ShellError, Signals, Span, Spanned, Value,
-    ast::{Math, Operator},
+    ast::{Comparison, Math, Operator},
     engine::Closure,
     shell_error,
 };
@@ -1119,6 +1119,34 @@ fn interface_custom_value_to_base_value() -> Result<(), ShellError> {
     Ok(())
 }
 
+#[test]
+fn interface_custom_value_operation_rhs_falls_back_without_feature() -> Result<(), ShellError> {
+    let test = TestCase::new();
+    let manager = test.plugin("test");
+    manager.protocol_info_mut.set(Arc::new(ProtocolInfo {
+        features: vec![],
+        ..ProtocolInfo::default()
+    }))?;
+    let interface = manager.get_interface();
+
+    let operation_rhs = |operator| {
+        interface.custom_value_operation_rhs(
+            Value::test_int(1),
+            Operator::Comparison(operator).into_spanned(Span::test_data()),
+            test_plugin_custom_value_with_source().into_spanned(Span::test_data()),
+        )
+    };
+    assert_eq!(Value::test_bool(false), operation_rhs(Comparison::Equal)?);
+    assert_eq!(Value::test_bool(true), operation_rhs(Comparison::NotEqual)?);
+    assert!(matches!(
+        operation_rhs(Comparison::LessThan),
+        Err(ShellError::OperatorUnsupportedType { .. })
+    ));
+    // The plugin isn't asked
+    assert!(!test.has_unconsumed_write());
+    Ok(())
+}
+
 fn normal_values(interface: &PluginInterface) -> Vec<Value> {
     vec![
         Value::test_int(5),
@@ -235,6 +235,21 @@ impl CustomValue for PluginCustomValueWithSource {
             )
     }
 
+    fn operation_rhs(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op_span: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        self.get_plugin(Some(rhs_span), "invoke operator")?
+            .custom_value_operation_rhs(
+                left.clone(),
+                operator.into_spanned(op_span),
+                self.clone().into_spanned(rhs_span),
+            )
+    }
+
     fn save(
         &self,
         path: Spanned<&Path>,
@@ -212,6 +212,8 @@ pub enum CustomValueOp {
     PartialCmp(Value),
     /// [`operation()`](nu_protocol::CustomValue::operation)
     Operation(Spanned<Operator>, Value),
+    /// [`operation_rhs()`](nu_protocol::CustomValue::operation_rhs), with the left side
+    OperationRhs(Value, Spanned<Operator>),
     /// [`save()`](nu_protocol::CustomValue::save)
     Save {
         path: Spanned<PathBuf>,
@@ -231,6 +233,7 @@ impl CustomValueOp {
             CustomValueOp::FollowPathString { .. } => "follow_path_string",
             CustomValueOp::PartialCmp(_) => "partial_cmp",
             CustomValueOp::Operation(_, _) => "operation",
+            CustomValueOp::OperationRhs(_, _) => "operation_rhs",
             CustomValueOp::Save { .. } => "save",
             CustomValueOp::Dropped => "dropped",
         }
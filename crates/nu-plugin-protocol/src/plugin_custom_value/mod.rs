@@ -93,6 +93,16 @@ impl CustomValue for PluginCustomValue {
         panic!("operation() not available on plugin custom value without source");
     }
 
+    fn operation_rhs(
+        &self,
+        _left: &Value,
+        _operator: Operator,
+        _op_span: Span,
+        _rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        panic!("operation_rhs() not available on plugin custom value without source");
+    }
+
     fn save(
         &self,
         _path: Spanned<&Path>,
@@ -89,6 +89,11 @@ pub enum Feature {
     /// stdio.
     LocalSocket,
 
+    /// The plugin handles [`CustomValueOp::OperationRhs`](crate::CustomValueOp::OperationRhs).
+    /// Operators with its custom values on the right side fall back to the default
+    /// `operation_rhs` otherwise.
+    OperationRhs,
+
     /// A feature that was not recognized on deserialization. Attempting to serialize this feature
     /// is an error. Matching against it may only be used if necessary to determine whether
     /// unsupported features are present.
@@ -99,7 +104,11 @@ pub enum Feature {
 impl Feature {
     /// True if the feature is considered to be compatible with another feature.
     pub fn is_compatible_with(&self, other: &Feature) -> bool {
-        matches!((self, other), (Feature::LocalSocket, Feature::LocalSocket))
+        matches!(
+            (self, other),
+            (Feature::LocalSocket, Feature::LocalSocket)
+                | (Feature::OperationRhs, Feature::OperationRhs)
+        )
     }
 }
 
@@ -109,5 +118,6 @@ pub fn default_features() -> Vec<Feature> {
         // Only available if compiled with the `local-socket` feature flag (enabled by default).
         #[cfg(feature = "local-socket")]
         Feature::LocalSocket,
+        Feature::OperationRhs,
     ]
 }
@@ -215,6 +215,25 @@ pub trait Plugin: Sync {
             .map_err(LabeledError::from)
     }
 
+    /// Implement functionality for an operator with a custom value on the right side, when the
+    /// left side isn't a custom value.
+    ///
+    /// The default implementation of this method just calls [`CustomValue::operation_rhs`], but
+    /// the method can be implemented differently if accessing plugin state is desirable.
+    fn custom_value_operation_rhs(
+        &self,
+        engine: &EngineInterface,
+        left: Value,
+        operator: Spanned<Operator>,
+        right: Spanned<Box<dyn CustomValue>>,
+    ) -> Result<Value, LabeledError> {
+        let _ = engine;
+        right
+            .item
+            .operation_rhs(&left, operator.item, operator.span, right.span)
+            .map_err(LabeledError::from)
+    }
+
     /// Implement saving logic for a custom value.
     ///
     /// The default implementation of this method just calls [`CustomValue::save`], but
@@ -671,6 +690,15 @@ fn custom_value_op(
                 .write_response(result)
                 .and_then(|writer| writer.write())
         }
+        CustomValueOp::OperationRhs(mut left, operator) => {
+            PluginCustomValue::deserialize_custom_values_in(&mut left)?;
+            let result = plugin
+                .custom_value_operation_rhs(engine, left, operator, local_value)
+                .map(|value| PipelineData::value(value, None));
+            engine
+                .write_response(result)
+                .and_then(|writer| writer.write())
+        }
         CustomValueOp::Save {
             path,
             save_call_span,
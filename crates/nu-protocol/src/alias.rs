@@ -1,5 +1,5 @@
 use crate::{
-    PipelineData, ShellError, Signature,
+    PipelineData, ShellError, Signature, Span,
     ast::Expression,
     engine::{Call, Command, CommandType, EngineState, Stack},
 };
@@ -15,10 +15,22 @@ pub struct Alias {
     /// Wrapped inner [`Command`]. `None` if alias of external call
     pub command: Option<Box<dyn Command>>,
     pub wrapped_call: Expression,
+    /// The arguments after the aliased command, if the alias has parameters like in
+    /// `alias --parameters gco = git checkout {branch}`. Empty for other aliases.
+    pub arguments: Vec<AliasArgument>,
     pub description: String,
     pub extra_description: String,
 }
 
+/// An argument of an alias with parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AliasArgument {
+    /// An argument which is always passed.
+    Fixed(Span),
+    /// A placeholder like `{branch}`, which is replaced by the next argument of the call.
+    Parameter(String),
+}
+
 impl Command for Alias {
     fn name(&self) -> &str {
         &self.name
@@ -0,0 +1,47 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// Configures when the output of external commands is converted with `from auto`.
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Serialize, Deserialize)]
+pub struct AutoParseConfig {
+    /// Whether external output piped into commands which can't take text is converted
+    pub enabled: bool,
+    /// The confidence from 0 to 1 a format has to be detected with to convert text
+    pub threshold: f64,
+}
+
+impl Default for AutoParseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.9,
+        }
+    }
+}
+
+impl UpdateFromValue for AutoParseConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "enabled" => self.enabled.update(val, path, errors),
+                "threshold" => match val.coerce_float() {
+                    Ok(threshold) if (0.0..=1.0).contains(&threshold) => self.threshold = threshold,
+                    Ok(_) => errors.invalid_value(path, "a number between 0 and 1", val),
+                    Err(_) => errors.type_mismatch(path, Type::Number, val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
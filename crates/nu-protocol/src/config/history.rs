@@ -60,10 +60,7 @@ pub struct HistoryConfig {
 
 impl HistoryConfig {
     pub fn file_path(&self) -> Option<std::path::PathBuf> {
-        nu_path::nu_config_dir().map(|mut history_path| {
-            history_path.push(self.file_format.default_file_name());
-            history_path.into()
-        })
+        nu_path::nu_state_file(self.file_format.default_file_name()).map(Into::into)
     }
 }
 
@@ -9,6 +9,9 @@ pub struct Hooks {
     pub pre_execution: Vec<Value>,
     pub env_change: HashMap<String, Vec<Value>>,
     pub display_output: Option<Value>,
+    /// Hooks which render values at the REPL instead of `display_output`, by content type, custom
+    /// value type name or type.
+    pub display_renderers: HashMap<String, Value>,
     pub command_not_found: Option<Value>,
 }
 
@@ -22,6 +25,7 @@ impl Hooks {
                 "if (term size).columns >= 100 { table -e } else { table }",
                 Span::unknown(),
             )),
+            display_renderers: HashMap::new(),
             command_not_found: None,
         }
     }
@@ -92,6 +96,17 @@ impl UpdateFromValue for Hooks {
                         Some(val.clone())
                     }
                 }
+                "display_renderers" => {
+                    if let Ok(record) = val.as_record() {
+                        self.display_renderers = record
+                            .iter()
+                            .filter(|(_, hook)| !hook.is_nothing())
+                            .map(|(key, hook)| (key.clone(), hook.clone()))
+                            .collect();
+                    } else {
+                        errors.type_mismatch(path, Type::record(), val);
+                    }
+                }
                 "command_not_found" => {
                     self.command_not_found = if val.is_nothing() {
                         None
@@ -0,0 +1,71 @@
+use super::prelude::*;
+use crate::Filesize;
+
+/// Limits on the resources used by each pipeline entered in the REPL
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitsConfig {
+    /// The largest value a stream can be collected into
+    pub value_size: Option<Filesize>,
+    /// How long a pipeline can run (in nanoseconds)
+    pub pipeline_time: Option<i64>,
+    /// How many external processes a pipeline can spawn
+    pub processes: Option<usize>,
+}
+
+impl IntoValue for LimitsConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "value_size" => self.value_size.map(|size| Value::filesize(size, span)).unwrap_or(Value::nothing(span)),
+            "pipeline_time" => self.pipeline_time.map(|time| Value::duration(time, span)).unwrap_or(Value::nothing(span)),
+            "processes" => self.processes.map(|n| n as i64).into_value(span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for LimitsConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "value_size" => match *val {
+                    Value::Nothing { .. } => self.value_size = None,
+                    Value::Filesize { val: size, .. } if size.get() > 0 => {
+                        self.value_size = Some(size)
+                    }
+                    Value::Filesize { .. } => {
+                        errors.invalid_value(path, "a positive filesize", val)
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("filesize or nothing"), val),
+                },
+                "pipeline_time" => match *val {
+                    Value::Nothing { .. } => self.pipeline_time = None,
+                    Value::Duration { val: time, .. } if time > 0 => {
+                        self.pipeline_time = Some(time)
+                    }
+                    Value::Duration { .. } => {
+                        errors.invalid_value(path, "a positive duration", val)
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("duration or nothing"), val),
+                },
+                "processes" => match *val {
+                    Value::Nothing { .. } => self.processes = None,
+                    Value::Int { val: n, .. } if n >= 0 => self.processes = Some(n as usize),
+                    Value::Int { .. } => errors.invalid_value(path, "a non-negative integer", val),
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
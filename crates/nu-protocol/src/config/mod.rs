@@ -7,6 +7,7 @@ use prelude::*;
 use std::collections::HashMap;
 
 pub use ansi_coloring::UseAnsiColoring;
+pub use auto_parse::AutoParseConfig;
 pub use completions::{
     CompletionAlgorithm, CompletionConfig, CompletionSort, ExternalCompleterConfig,
 };
@@ -16,15 +17,22 @@ pub use filesize::FilesizeConfig;
 pub use helper::extract_value;
 pub use history::{HistoryConfig, HistoryFileFormat};
 pub use hooks::Hooks;
+pub use limits::LimitsConfig;
 pub use ls::LsConfig;
 pub use output::{BannerKind, ErrorStyle};
+pub use pager::PagerConfig;
+pub use path_cache::PathCacheConfig;
+pub use path_display::PathDisplay;
 pub use plugin_gc::{PluginGcConfig, PluginGcConfigs};
 pub use reedline::{CursorShapeConfig, EditBindings, NuCursorShape, ParsedKeybinding, ParsedMenu};
 pub use rm::RmConfig;
+pub use sandbox::SandboxConfig;
 pub use shell_integration::ShellIntegrationConfig;
+pub use strings::{AmbiguousWidth, StringsConfig};
 pub use table::{FooterMode, TableConfig, TableIndent, TableIndexMode, TableMode, TrimStrategy};
 
 mod ansi_coloring;
+mod auto_parse;
 mod completions;
 mod datetime_format;
 mod display_errors;
@@ -33,13 +41,19 @@ mod filesize;
 mod helper;
 mod history;
 mod hooks;
+mod limits;
 mod ls;
 mod output;
+mod pager;
+mod path_cache;
+mod path_display;
 mod plugin_gc;
 mod prelude;
 mod reedline;
 mod rm;
+mod sandbox;
 mod shell_integration;
+mod strings;
 mod table;
 
 #[derive(Clone, Debug, IntoValue, Serialize, Deserialize)]
@@ -47,9 +61,13 @@ pub struct Config {
     pub filesize: FilesizeConfig,
     pub table: TableConfig,
     pub ls: LsConfig,
+    pub pager: PagerConfig,
     pub color_config: HashMap<String, Value>,
     pub footer_mode: FooterMode,
     pub float_precision: i64,
+    /// The locale of formatted and parsed numbers, like `de` or `fr-CH`, or `None` for the one
+    /// of the system.
+    pub locale: Option<String>,
     pub recursion_limit: i64,
     pub use_ansi_coloring: UseAnsiColoring,
     pub completions: CompletionConfig,
@@ -59,6 +77,11 @@ pub struct Config {
     pub menus: Vec<ParsedMenu>,
     pub hooks: Hooks,
     pub rm: RmConfig,
+    pub strings: StringsConfig,
+    pub auto_parse: AutoParseConfig,
+    pub path_cache: PathCacheConfig,
+    pub sandbox: SandboxConfig,
+    pub limits: LimitsConfig,
     pub shell_integration: ShellIntegrationConfig,
     pub buffer_editor: Value,
     pub show_banner: BannerKind,
@@ -68,6 +91,7 @@ pub struct Config {
     pub cursor_shape: CursorShapeConfig,
     pub datetime_format: DatetimeFormatConfig,
     pub error_style: ErrorStyle,
+    pub path_display: PathDisplay,
     pub display_errors: DisplayErrors,
     pub use_kitty_protocol: bool,
     pub highlight_resolved_externals: bool,
@@ -88,7 +112,13 @@ impl Default for Config {
 
             table: TableConfig::default(),
             rm: RmConfig::default(),
+            strings: StringsConfig::default(),
+            auto_parse: AutoParseConfig::default(),
+            path_cache: PathCacheConfig::default(),
+            sandbox: SandboxConfig::default(),
+            limits: LimitsConfig::default(),
             ls: LsConfig::default(),
+            pager: PagerConfig::default(),
 
             datetime_format: DatetimeFormatConfig::default(),
 
@@ -107,6 +137,7 @@ impl Default for Config {
             color_config: HashMap::new(),
             footer_mode: FooterMode::RowCount(25),
             float_precision: 2,
+            locale: None,
             buffer_editor: Value::nothing(Span::unknown()),
             use_ansi_coloring: UseAnsiColoring::default(),
             bracketed_paste: true,
@@ -123,6 +154,7 @@ impl Default for Config {
             keybindings: Vec::new(),
 
             error_style: ErrorStyle::Fancy,
+            path_display: PathDisplay::default(),
             display_errors: DisplayErrors::default(),
 
             use_kitty_protocol: false,
@@ -150,7 +182,13 @@ impl UpdateFromValue for Config {
             let path = &mut path.push(col);
             match col.as_str() {
                 "ls" => self.ls.update(val, path, errors),
+                "pager" => self.pager.update(val, path, errors),
                 "rm" => self.rm.update(val, path, errors),
+                "strings" => self.strings.update(val, path, errors),
+                "auto_parse" => self.auto_parse.update(val, path, errors),
+                "path_cache" => self.path_cache.update(val, path, errors),
+                "sandbox" => self.sandbox.update(val, path, errors),
+                "limits" => self.limits.update(val, path, errors),
                 "history" => self.history.update(val, path, errors),
                 "completions" => self.completions.update(val, path, errors),
                 "cursor_shape" => self.cursor_shape.update(val, path, errors),
@@ -160,6 +198,17 @@ impl UpdateFromValue for Config {
                 "color_config" => self.color_config.update(val, path, errors),
                 "footer_mode" => self.footer_mode.update(val, path, errors),
                 "float_precision" => self.float_precision.update(val, path, errors),
+                "locale" => match val {
+                    Value::Nothing { .. } => self.locale = None,
+                    Value::String { val: name, .. } => {
+                        if nu_utils::locale_from_name(name).is_some() {
+                            self.locale = Some(name.clone());
+                        } else {
+                            errors.invalid_value(path, "a locale like 'en', 'de' or 'fr-CH'", val);
+                        }
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("string or nothing"), val),
+                },
                 "use_ansi_coloring" => self.use_ansi_coloring.update(val, path, errors),
                 "edit_mode" => self.edit_mode.update(val, path, errors),
                 "shell_integration" => self.shell_integration.update(val, path, errors),
@@ -201,6 +250,7 @@ impl UpdateFromValue for Config {
                 "hooks" => self.hooks.update(val, path, errors),
                 "datetime_format" => self.datetime_format.update(val, path, errors),
                 "error_style" => self.error_style.update(val, path, errors),
+                "path_display" => self.path_display.update(val, path, errors),
                 "recursion_limit" => {
                     if let Ok(limit) = val.as_int() {
                         if limit > 1 {
@@ -219,6 +269,14 @@ impl UpdateFromValue for Config {
 }
 
 impl Config {
+    /// The locale of numbers, from `locale` or the system.
+    pub fn number_locale(&self) -> num_format::Locale {
+        self.locale
+            .as_deref()
+            .and_then(nu_utils::locale_from_name)
+            .unwrap_or_else(nu_utils::get_system_locale)
+    }
+
     pub fn update_from_value(
         &mut self,
         old: &Config,
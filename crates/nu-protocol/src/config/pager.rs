@@ -0,0 +1,37 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+#[derive(Clone, Copy, Debug, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PagerConfig {
+    /// Show structured output of the REPL in `explore` when it doesn't fit on the screen.
+    pub auto: bool,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for PagerConfig {
+    fn default() -> Self {
+        Self { auto: false }
+    }
+}
+
+impl UpdateFromValue for PagerConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "auto" => self.auto.update(val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use super::prelude::*;
+
+/// Configures how long the executables in `$env.PATH` are cached for completions and highlighting
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathCacheConfig {
+    /// Whether the executables are cached at all
+    pub enabled: bool,
+    /// How long the executables of a directory are kept before listing it again (in nanoseconds)
+    pub ttl: i64,
+}
+
+impl Default for PathCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: 30_000_000_000, // 30sec
+        }
+    }
+}
+
+impl IntoValue for PathCacheConfig {
+    fn into_value(self, span: Span) -> Value {
+        record! {
+            "enabled" => self.enabled.into_value(span),
+            "ttl" => Value::duration(self.ttl, span),
+        }
+        .into_value(span)
+    }
+}
+
+impl UpdateFromValue for PathCacheConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "enabled" => self.enabled.update(val, path, errors),
+                "ttl" => {
+                    if let Ok(duration) = val.as_duration() {
+                        if duration >= 0 {
+                            self.ttl = duration;
+                        } else {
+                            errors.invalid_value(path, "a non-negative duration", val);
+                        }
+                    } else {
+                        errors.type_mismatch(path, Type::Duration, val);
+                    }
+                }
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::{config_update_string_enum, prelude::*};
+use crate as nu_protocol;
+
+/// Option: path_display
+///
+/// How the parts of a path value which aren't valid UTF-8 are displayed.
+#[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathDisplay {
+    /// Replace them with `�`, like a string converted from the path
+    #[default]
+    Lossy,
+    /// Show them as escapes, like `\xE9` for a byte or `\u{D800}` for a UTF-16 code unit
+    Escaped,
+}
+
+impl FromStr for PathDisplay {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lossy" => Ok(Self::Lossy),
+            "escaped" => Ok(Self::Escaped),
+            _ => Err("'lossy' or 'escaped'"),
+        }
+    }
+}
+
+impl UpdateFromValue for PathDisplay {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
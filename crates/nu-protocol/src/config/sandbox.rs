@@ -0,0 +1,63 @@
+use super::prelude::*;
+use crate as nu_protocol;
+
+/// The effects allowed by `nu --sandbox`
+#[derive(Clone, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Whether commands can create, change, move or delete files
+    pub allow_write: bool,
+    /// Whether commands can connect to other hosts
+    pub allow_network: bool,
+    /// Whether external commands and other processes can be run
+    pub allow_processes: bool,
+    /// External commands which can run even if `allow_processes` is off
+    pub allow_externals: Vec<String>,
+    /// Commands which can run even if their effects aren't allowed
+    pub allow_commands: Vec<String>,
+}
+
+impl UpdateFromValue for SandboxConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "allow_write" => self.allow_write.update(val, path, errors),
+                "allow_network" => self.allow_network.update(val, path, errors),
+                "allow_processes" => self.allow_processes.update(val, path, errors),
+                "allow_externals" => update_names(&mut self.allow_externals, val, path, errors),
+                "allow_commands" => update_names(&mut self.allow_commands, val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
+
+fn update_names<'a>(
+    names: &mut Vec<String>,
+    value: &'a Value,
+    path: &mut ConfigPath<'a>,
+    errors: &mut ConfigErrors,
+) {
+    let Value::List { vals, .. } = value else {
+        errors.type_mismatch(path, Type::list(Type::String), value);
+        return;
+    };
+    match vals
+        .iter()
+        .map(|val| val.as_str().map(String::from))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(vals) => *names = vals,
+        Err(_) => errors.type_mismatch(path, Type::list(Type::String), value),
+    }
+}
@@ -0,0 +1,63 @@
+use super::{config_update_string_enum, prelude::*};
+use crate as nu_protocol;
+
+/// How wide characters with an ambiguous East Asian width are displayed.
+#[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmbiguousWidth {
+    /// One column, like in most non-CJK locales.
+    #[default]
+    Narrow,
+    /// Two columns, like in CJK locales.
+    Wide,
+    /// Decided by the locale in `LC_ALL`, `LC_CTYPE` or `LANG`.
+    Locale,
+}
+
+impl FromStr for AmbiguousWidth {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "narrow" => Ok(Self::Narrow),
+            "wide" => Ok(Self::Wide),
+            "locale" => Ok(Self::Locale),
+            _ => Err("'narrow', 'wide' or 'locale'"),
+        }
+    }
+}
+
+impl UpdateFromValue for AmbiguousWidth {
+    fn update(&mut self, value: &Value, path: &mut ConfigPath, errors: &mut ConfigErrors) {
+        config_update_string_enum(self, value, path, errors)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, IntoValue, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StringsConfig {
+    /// Whether string commands count grapheme clusters unless told otherwise.
+    pub grapheme_clusters: bool,
+    pub ambiguous_width: AmbiguousWidth,
+}
+
+impl UpdateFromValue for StringsConfig {
+    fn update<'a>(
+        &mut self,
+        value: &'a Value,
+        path: &mut ConfigPath<'a>,
+        errors: &mut ConfigErrors,
+    ) {
+        let Value::Record { val: record, .. } = value else {
+            errors.type_mismatch(path, Type::record(), value);
+            return;
+        };
+
+        for (col, val) in record.iter() {
+            let path = &mut path.push(col);
+            match col.as_str() {
+                "grapheme_clusters" => self.grapheme_clusters.update(val, path, errors),
+                "ambiguous_width" => self.ambiguous_width.update(val, path, errors),
+                _ => errors.unknown_option(path, val),
+            }
+        }
+    }
+}
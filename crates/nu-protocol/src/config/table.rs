@@ -347,6 +347,8 @@ pub struct TableConfig {
     pub trim: TrimStrategy,
     pub header_on_separator: bool,
     pub abbreviated_row_count: Option<usize>,
+    /// How many levels of nested values `table --expand` shows, unless `--expand-deep` is given.
+    pub expand_depth: Option<usize>,
     pub footer_inheritance: bool,
     pub missing_value_symbol: String,
     pub batch_duration: Duration,
@@ -359,6 +361,7 @@ impl IntoValue for TableConfig {
             .abbreviated_row_count
             .map(|t| t as i64)
             .into_value(span);
+        let expand_depth = self.expand_depth.map(|t| t as i64).into_value(span);
 
         record! {
             "mode" => self.mode.into_value(span),
@@ -368,6 +371,7 @@ impl IntoValue for TableConfig {
             "trim" => self.trim.into_value(span),
             "header_on_separator" => self.header_on_separator.into_value(span),
             "abbreviated_row_count" => abbv_count,
+            "expand_depth" => expand_depth,
             "footer_inheritance" => self.footer_inheritance.into_value(span),
             "missing_value_symbol" => self.missing_value_symbol.into_value(span),
             "batch_duration" => self.batch_duration.into_value(span),
@@ -387,6 +391,7 @@ impl Default for TableConfig {
             header_on_separator: false,
             padding: TableIndent::default(),
             abbreviated_row_count: None,
+            expand_depth: None,
             footer_inheritance: false,
             missing_value_symbol: "❎".into(),
             batch_duration: Duration::from_secs(1),
@@ -427,6 +432,17 @@ impl UpdateFromValue for TableConfig {
                     }
                     _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
                 },
+                "expand_depth" => match val {
+                    Value::Nothing { .. } => self.expand_depth = None,
+                    &Value::Int { val: depth, .. } => {
+                        if let Ok(depth) = depth.try_into() {
+                            self.expand_depth = Some(depth);
+                        } else {
+                            errors.invalid_value(path, "a non-negative integer", val);
+                        }
+                    }
+                    _ => errors.type_mismatch(path, Type::custom("int or nothing"), val),
+                },
                 "footer_inheritance" => self.footer_inheritance.update(val, path, errors),
                 "missing_value_symbol" => match val.as_str() {
                     Ok(val) => self.missing_value_symbol = val.to_string(),
@@ -1,4 +1,4 @@
-use super::{EngineState, Stack, StateWorkingSet};
+use super::{Effect, EngineState, Stack, StateWorkingSet};
 use crate::{
     Alias, BlockId, DeprecationEntry, Example, OutDest, PipelineData, ShellError, Signature, Value,
     engine::Call,
@@ -134,6 +134,16 @@ pub trait Command: Send + Sync + CommandClone {
         self.command_type() == CommandType::Plugin
     }
 
+    /// What the command does outside of Nushell, which the [`Sandbox`](super::Sandbox) denies
+    /// unless it's allowed.
+    ///
+    /// The sandbox also denies commands whose effects are unknown, so commands without effects
+    /// return `Some(&[])`. That's the default for the categories of commands which only work on
+    /// values, like strings or math, but commands in other categories have to declare it.
+    fn effects(&self) -> Option<&'static [Effect]> {
+        super::sandbox::category_effects(&self.signature().category)
+    }
+
     fn deprecation_info(&self) -> Vec<DeprecationEntry> {
         vec![]
     }
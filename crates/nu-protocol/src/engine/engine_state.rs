@@ -5,8 +5,8 @@ use crate::{
     ast::Block,
     debugger::{Debugger, NoopDebugger},
     engine::{
-        CachedFile, Command, CommandType, DEFAULT_OVERLAY_NAME, EnvVars, OverlayFrame, ScopeFrame,
-        Stack, StateDelta, Variable, Visibility,
+        CachedFile, Closure, Command, CommandType, DEFAULT_OVERLAY_NAME, EnvVars, OverlayFrame,
+        PathCache, Sandbox, ScopeFrame, Stack, StateDelta, Variable, Visibility,
         description::{Doccomments, build_desc},
     },
     eval_const::create_nu_constant,
@@ -20,13 +20,14 @@ use nu_utils::IgnoreCaseExt;
 use std::{
     collections::HashMap,
     num::NonZeroUsize,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Arc, Mutex, MutexGuard, PoisonError,
         atomic::{AtomicBool, AtomicU32, Ordering},
         mpsc::Sender,
         mpsc::channel,
     },
+    time::Duration,
 };
 
 type PoisonDebuggerError<'a> = PoisonError<MutexGuard<'a, Box<dyn Debugger>>>;
@@ -34,11 +35,14 @@ type PoisonDebuggerError<'a> = PoisonError<MutexGuard<'a, Box<dyn Debugger>>>;
 #[cfg(feature = "plugin")]
 use crate::{PluginRegistryFile, PluginRegistryItem, RegisteredPlugin};
 
-use super::{CurrentJob, Jobs, Mail, Mailbox, ThreadJob};
+use super::{CurrentJob, Jobs, Mail, Mailbox, Schedule, ThreadJob};
 
 #[derive(Clone, Debug)]
 pub enum VirtualPath {
     File(FileId),
+    /// A file shipped in the binary, which is only added to the files of the engine state once
+    /// it's parsed, like the modules of the standard library.
+    Source(&'static str),
     Dir(Vec<VirtualPathId>),
 }
 
@@ -102,6 +106,10 @@ pub struct EngineState {
     pub config: Arc<Config>,
     pub pipeline_externals_state: Arc<(AtomicU32, AtomicU32)>,
     pub repl_state: Arc<Mutex<ReplState>>,
+    /// Source code queued by `import dynamic`, which is evaluated after the current command line
+    /// or file, when definitions can be added to the engine state again. Background jobs get their
+    /// own queue.
+    pub deferred_sources: Arc<Mutex<Vec<String>>>,
     pub table_decl_id: Option<DeclId>,
     #[cfg(feature = "plugin")]
     pub plugin_path: Option<PathBuf>,
@@ -113,9 +121,18 @@ pub struct EngineState {
     // Path to the file Nushell is currently evaluating, or None if we're in an interactive session.
     pub file: Option<PathBuf>,
     pub regex_cache: Arc<Mutex<LruCache<String, Regex>>>,
+    pub path_cache: Arc<Mutex<PathCache>>,
     pub is_interactive: bool,
     pub is_login: bool,
+    /// Name of the config profile selected with `nu --profile` or `$env.NU_PROFILE`.
+    pub config_profile: Option<String>,
     pub is_lsp: bool,
+    /// Whether nu answers the JSON requests on its stdin with `nu --serve-json`, so nothing else
+    /// may read stdin or write to stdout.
+    pub is_json_server: bool,
+    /// The effects which are allowed, if running in a sandbox from `nu --sandbox` or
+    /// `run-sandboxed`.
+    pub sandbox: Option<Arc<Sandbox>>,
     startup_time: i64,
     is_debugging: IsDebugging,
     pub debugger: Arc<Mutex<Box<dyn Debugger>>>,
@@ -123,6 +140,16 @@ pub struct EngineState {
 
     pub jobs: Arc<Mutex<Jobs>>,
 
+    /// The jobs started by `schedule add`, by the id of their background job.
+    pub schedules: Arc<Mutex<HashMap<JobId, Schedule>>>,
+
+    /// The closures added by `watch-var`, which the REPL runs when their variable changes.
+    pub var_watches: Arc<Mutex<Vec<VarWatch>>>,
+
+    /// The closures added by `env watch`, which run right after their environment variable
+    /// changes. Each [`Stack`] keeps the values it last saw of the watched variables.
+    pub env_watches: Arc<Mutex<Vec<EnvWatch>>>,
+
     // The job being executed with this engine state, or None if main thread
     pub current_job: CurrentJob,
 
@@ -137,6 +164,23 @@ pub struct EngineState {
     pub exit_warning_given: Arc<AtomicBool>,
 }
 
+/// A closure of `env watch`, with the value of the variable when the watch was added, which the
+/// stacks that haven't checked the watch yet compare with.
+#[derive(Clone)]
+pub struct EnvWatch {
+    pub name: String,
+    pub closure: Closure,
+    pub initial: Option<Value>,
+}
+
+/// A closure of `watch-var`, with the value of the variable when it was last checked.
+#[derive(Clone)]
+pub struct VarWatch {
+    pub var_id: VarId,
+    pub closure: Value,
+    pub last: Option<Value>,
+}
+
 // The max number of compiled regexes to keep around in a LRU cache, arbitrarily chosen
 const REGEX_CACHE_SIZE: usize = 100; // must be nonzero, otherwise will panic
 
@@ -190,6 +234,7 @@ impl EngineState {
                 cursor_pos: 0,
                 accept: false,
             })),
+            deferred_sources: Arc::new(Mutex::new(vec![])),
             table_decl_id: None,
             #[cfg(feature = "plugin")]
             plugin_path: None,
@@ -202,14 +247,21 @@ impl EngineState {
             regex_cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             ))),
+            path_cache: Arc::default(),
             is_interactive: false,
             is_login: false,
+            config_profile: None,
             is_lsp: false,
+            is_json_server: false,
+            sandbox: None,
             startup_time: -1,
             is_debugging: IsDebugging::new(false),
             debugger: Arc::new(Mutex::new(Box::new(NoopDebugger))),
             report_log: Arc::default(),
             jobs: Arc::new(Mutex::new(Jobs::default())),
+            schedules: Arc::new(Mutex::new(HashMap::new())),
+            var_watches: Arc::default(),
+            env_watches: Arc::default(),
             current_job: CurrentJob {
                 id: JobId::new(0),
                 background_thread_job: None,
@@ -535,9 +587,11 @@ impl EngineState {
                 help: Some("you may be running nu with --no-config-file".into()),
                 inner: vec![],
             })?;
+        // Keep using the registry if `config migrate` moved it
+        let plugin_path = nu_path::follow_state_file(plugin_path);
 
         // Read the current contents of the plugin file if it exists
-        let mut contents = match File::open(plugin_path.as_path()) {
+        let mut contents = match File::open(&plugin_path) {
             Ok(mut plugin_file) => PluginRegistryFile::read_from(&mut plugin_file, None),
             Err(err) => {
                 if err.kind() == std::io::ErrorKind::NotFound {
@@ -547,7 +601,7 @@ impl EngineState {
                         err,
                         "Failed to open plugin file",
                         crate::location!(),
-                        PathBuf::from(plugin_path),
+                        plugin_path.to_path_buf(),
                     )))
                 }
             }
@@ -559,12 +613,12 @@ impl EngineState {
         }
 
         // Write it to the same path
-        let plugin_file = File::create(plugin_path.as_path()).map_err(|err| {
+        let plugin_file = File::create(&plugin_path).map_err(|err| {
             IoError::new_internal_with_path(
                 err,
                 "Failed to write plugin file",
                 crate::location!(),
-                PathBuf::from(plugin_path),
+                plugin_path.to_path_buf(),
             )
         })?;
 
@@ -817,6 +871,44 @@ impl EngineState {
         self.config = conf;
     }
 
+    /// The names of the executables in `dir`, from the [`PathCache`] unless it's disabled with
+    /// `$env.config.path_cache.enabled`.
+    pub fn executables_in(&self, dir: &Path) -> Arc<[String]> {
+        let config = &self.config.path_cache;
+        let ttl = Duration::from_nanos(config.ttl as u64);
+        match self.path_cache.lock() {
+            Ok(mut cache) if config.enabled => cache.executables(dir, ttl),
+            _ => super::path_cache::scan_dir(dir).into(),
+        }
+    }
+
+    /// Find the executable called `name` in the first of `dirs` which has it, from the
+    /// [`PathCache`] unless it's disabled with `$env.config.path_cache.enabled`.
+    pub fn find_executable<P: AsRef<Path>>(
+        &self,
+        name: &str,
+        dirs: impl IntoIterator<Item = P>,
+    ) -> Option<PathBuf> {
+        let config = &self.config.path_cache;
+        let ttl = Duration::from_nanos(config.ttl as u64);
+        match self.path_cache.lock() {
+            Ok(mut cache) if config.enabled => cache.find(name, dirs, ttl),
+            _ => find_uncached(name, dirs),
+        }
+    }
+
+    /// Find the executable of the external command `name`: in `paths`, the directories of
+    /// `$env.PATH` joined like in the environment variable, like
+    /// [`find_executable`](Self::find_executable) for a command name, or relative to `cwd` for a
+    /// path.
+    pub fn resolve_executable(&self, name: &str, paths: &str, cwd: &Path) -> Option<PathBuf> {
+        if name.contains(std::path::is_separator) {
+            resolve_path(name, paths, cwd)
+        } else {
+            self.find_executable(name, std::env::split_paths(paths))
+        }
+    }
+
     /// Fetch the configuration for a plugin
     ///
     /// The `plugin` must match the registered name of a plugin.  For `plugin add
@@ -1089,11 +1181,23 @@ impl EngineState {
         if Mutex::is_poisoned(&self.jobs) {
             self.jobs = Arc::new(Mutex::new(Jobs::default()));
         }
+        if Mutex::is_poisoned(&self.schedules) {
+            self.schedules = Arc::default();
+        }
+        if Mutex::is_poisoned(&self.var_watches) {
+            self.var_watches = Arc::default();
+        }
+        if Mutex::is_poisoned(&self.env_watches) {
+            self.env_watches = Arc::default();
+        }
         if Mutex::is_poisoned(&self.regex_cache) {
             self.regex_cache = Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             )));
         }
+        if Mutex::is_poisoned(&self.path_cache) {
+            self.path_cache = Arc::default();
+        }
     }
 
     /// Add new span and return its ID
@@ -1131,6 +1235,29 @@ impl GetSpan for &EngineState {
     }
 }
 
+#[cfg(feature = "os")]
+fn find_uncached<P: AsRef<Path>>(name: &str, dirs: impl IntoIterator<Item = P>) -> Option<PathBuf> {
+    let paths =
+        std::env::join_paths(dirs.into_iter().map(|dir| dir.as_ref().to_path_buf())).ok()?;
+    which::which_in_global(name, Some(paths)).ok()?.next()
+}
+
+#[cfg(not(feature = "os"))]
+fn find_uncached<P: AsRef<Path>>(name: &str, dirs: impl IntoIterator<Item = P>) -> Option<PathBuf> {
+    PathCache::default().find(name, dirs, Duration::ZERO)
+}
+
+#[cfg(feature = "os")]
+fn resolve_path(name: &str, paths: &str, cwd: &Path) -> Option<PathBuf> {
+    which::which_in(name, Some(paths), cwd).ok()
+}
+
+#[cfg(not(feature = "os"))]
+fn resolve_path(name: &str, _paths: &str, cwd: &Path) -> Option<PathBuf> {
+    let path = cwd.join(name);
+    super::path_cache::is_executable(&path).then_some(path)
+}
+
 impl Default for EngineState {
     fn default() -> Self {
         Self::new()
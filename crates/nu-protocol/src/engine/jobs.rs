@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     sync::{
         Arc, Mutex,
         mpsc::{Receiver, RecvTimeoutError, Sender, TryRecvError},
@@ -403,3 +403,46 @@ impl IgnoredMail {
         Some(self.messages.remove(&id)?.1)
     }
 }
+
+/// The number of runs of a scheduled job which are kept for `schedule list`.
+pub const SCHEDULE_LOG_SIZE: usize = 20;
+
+/// A background job started by `schedule add`, which runs a closure at the times of a cron
+/// expression or an interval. The job of the same id does the waiting and running.
+pub struct Schedule {
+    pub name: Option<String>,
+    /// The cron expression or interval, as given to `schedule add`.
+    pub schedule: String,
+    pub next_run: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub run_count: u64,
+    /// The most recent runs, oldest first.
+    pub log: VecDeque<ScheduleRun>,
+}
+
+pub struct ScheduleRun {
+    pub start: chrono::DateTime<chrono::FixedOffset>,
+    pub duration: std::time::Duration,
+    /// The error of the closure, if it failed.
+    pub error: Option<String>,
+}
+
+impl Schedule {
+    pub fn new(name: Option<String>, schedule: String) -> Self {
+        Self {
+            name,
+            schedule,
+            next_run: None,
+            run_count: 0,
+            log: VecDeque::with_capacity(SCHEDULE_LOG_SIZE),
+        }
+    }
+
+    /// Record a run, dropping the oldest ones past [`SCHEDULE_LOG_SIZE`].
+    pub fn add_run(&mut self, run: ScheduleRun) {
+        if self.log.len() == SCHEDULE_LOG_SIZE {
+            self.log.pop_front();
+        }
+        self.log.push_back(run);
+        self.run_count += 1;
+    }
+}
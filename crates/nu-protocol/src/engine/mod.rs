@@ -10,7 +10,9 @@ mod engine_state;
 mod error_handler;
 mod jobs;
 mod overlay;
+mod path_cache;
 mod pattern_match;
+mod sandbox;
 mod sequence;
 mod stack;
 mod stack_out_dest;
@@ -29,7 +31,9 @@ pub use engine_state::*;
 pub use error_handler::*;
 pub use jobs::*;
 pub use overlay::*;
+pub use path_cache::PathCache;
 pub use pattern_match::*;
+pub use sandbox::{Effect, Sandbox};
 pub use sequence::*;
 pub use stack::*;
 pub use stack_out_dest::*;
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use web_time::Instant;
+
+/// The executables found in the directories of `$env.PATH`.
+///
+/// Listing a directory and checking whether each file in it is executable can take hundreds of
+/// milliseconds on network mounts, which is too slow to do on every keystroke for completions and
+/// highlighting. Instead, each directory is listed once and then listed again when its entry is
+/// older than the TTL. The modification time of the directory isn't enough to keep an entry, as it
+/// doesn't change when a file in the directory is made executable or replaced.
+#[derive(Debug, Default)]
+pub struct PathCache {
+    dirs: HashMap<PathBuf, CachedDir>,
+}
+
+#[derive(Debug)]
+struct CachedDir {
+    listed: Instant,
+    executables: Arc<[String]>,
+}
+
+impl PathCache {
+    /// The names of the executables in `dir`, sorted.
+    pub fn executables(&mut self, dir: &Path, ttl: Duration) -> Arc<[String]> {
+        if let Some(cached) = self.dirs.get(dir)
+            && cached.listed.elapsed() < ttl
+        {
+            return cached.executables.clone();
+        }
+
+        let executables: Arc<[String]> = scan_dir(dir).into();
+        self.dirs.insert(
+            dir.to_path_buf(),
+            CachedDir {
+                listed: Instant::now(),
+                executables: executables.clone(),
+            },
+        );
+        executables
+    }
+
+    /// Find the executable called `name` in the first of `dirs` which has it.
+    ///
+    /// On Windows, `name` may leave out an extension from `PATHEXT`.
+    pub fn find<P: AsRef<Path>>(
+        &mut self,
+        name: &str,
+        dirs: impl IntoIterator<Item = P>,
+        ttl: Duration,
+    ) -> Option<PathBuf> {
+        dirs.into_iter().find_map(|dir| {
+            let dir = dir.as_ref();
+            let executables = self.executables(dir, ttl);
+            executables
+                .iter()
+                .find(|executable| matches_name(executable, name))
+                .map(|executable| dir.join(executable))
+        })
+    }
+
+    /// Forget all directories, so they're listed again when needed.
+    pub fn clear(&mut self) {
+        self.dirs.clear();
+    }
+}
+
+/// List the executables in a directory, or nothing if it can't be read.
+pub(super) fn scan_dir(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut executables: Vec<String> = entries
+        .flatten()
+        .filter(|entry| is_executable(&entry.path()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    executables.sort();
+    executables
+}
+
+#[cfg(unix)]
+pub(super) fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path).is_ok_and(|md| md.is_file() && md.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(windows)]
+pub(super) fn is_executable(path: &Path) -> bool {
+    let Some(extension) = path.extension() else {
+        return false;
+    };
+    let extension = format!(".{}", extension.to_string_lossy());
+    path_extensions()
+        .iter()
+        .any(|ext| ext.eq_ignore_ascii_case(&extension))
+        && path.is_file()
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(super) fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn path_extensions() -> Vec<String> {
+    std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into())
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(windows)]
+fn matches_name(executable: &str, name: &str) -> bool {
+    if executable.eq_ignore_ascii_case(name) {
+        return true;
+    }
+    path_extensions().iter().any(|ext| {
+        executable.len() == name.len() + ext.len()
+            && executable.is_char_boundary(name.len())
+            && executable[..name.len()].eq_ignore_ascii_case(name)
+            && executable[name.len()..].eq_ignore_ascii_case(ext)
+    })
+}
+
+#[cfg(not(windows))]
+fn matches_name(executable: &str, name: &str) -> bool {
+    executable == name
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn create(dir: &Path, name: &str, mode: u32) {
+        let path = dir.join(name);
+        std::fs::write(&path, "").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn only_executables_are_found() {
+        let dir = tempfile::tempdir().unwrap();
+        create(dir.path(), "tool", 0o755);
+        create(dir.path(), "notes.txt", 0o644);
+
+        let mut cache = PathCache::default();
+        let executables = cache.executables(dir.path(), Duration::from_secs(60));
+        assert_eq!(&*executables, ["tool".to_string()]);
+        assert_eq!(
+            cache.find("tool", [dir.path()], Duration::from_secs(60)),
+            Some(dir.path().join("tool"))
+        );
+        assert_eq!(
+            cache.find("notes.txt", [dir.path()], Duration::from_secs(60)),
+            None
+        );
+    }
+
+    #[test]
+    fn entries_are_kept_within_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = PathCache::default();
+        assert!(
+            cache
+                .executables(dir.path(), Duration::from_secs(60))
+                .is_empty()
+        );
+
+        create(dir.path(), "tool", 0o755);
+        assert!(
+            cache
+                .executables(dir.path(), Duration::from_secs(60))
+                .is_empty()
+        );
+
+        cache.clear();
+        assert_eq!(cache.executables(dir.path(), Duration::ZERO).len(), 1);
+    }
+
+    #[test]
+    fn entries_are_listed_again_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        create(dir.path(), "tool", 0o644);
+        let mut cache = PathCache::default();
+        assert!(cache.executables(dir.path(), Duration::ZERO).is_empty());
+
+        // Changing the mode of a file doesn't change the modification time of its directory
+        std::fs::set_permissions(
+            dir.path().join("tool"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        assert_eq!(
+            &*cache.executables(dir.path(), Duration::ZERO),
+            ["tool".to_string()]
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use super::{Command, CommandType};
+use crate::{Category, ShellError, Span, config::SandboxConfig};
+use std::fmt::Display;
+
+/// An effect a command has outside of Nushell, which the [`Sandbox`] can deny.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Effect {
+    /// Creates, changes, moves or deletes files.
+    Write,
+    /// Connects to other hosts, or listens for connections.
+    Network,
+    /// Runs or controls other processes.
+    Process,
+}
+
+impl Effect {
+    fn allowed_by(self, sandbox: &Sandbox) -> bool {
+        match self {
+            Effect::Write => sandbox.allow_write,
+            Effect::Network => sandbox.allow_network,
+            Effect::Process => sandbox.allow_processes,
+        }
+    }
+
+    fn flag(self) -> &'static str {
+        match self {
+            Effect::Write => "allow-write",
+            Effect::Network => "allow-network",
+            Effect::Process => "allow-processes",
+        }
+    }
+}
+
+impl Display for Effect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Effect::Write => "write files",
+            Effect::Network => "use the network",
+            Effect::Process => "run processes",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// The effects of the commands in `category` which don't declare their own.
+///
+/// The commands in the categories of commands which only work on values have none, and the
+/// effects of the commands in other categories are unknown.
+pub(super) fn category_effects(category: &Category) -> Option<&'static [Effect]> {
+    match category {
+        Category::Bits
+        | Category::Bytes
+        | Category::Chart
+        | Category::Conversions
+        | Category::Date
+        | Category::Filters
+        | Category::Formats
+        | Category::Generators
+        | Category::Hash
+        | Category::Math
+        | Category::Path
+        | Category::Random
+        | Category::Strings => Some(&[]),
+        Category::Core
+        | Category::Custom(_)
+        | Category::Database
+        | Category::Debug
+        | Category::Default
+        | Category::Deprecated
+        | Category::Removed
+        | Category::Env
+        | Category::Experimental
+        | Category::FileSystem
+        | Category::History
+        | Category::Misc
+        | Category::Network
+        | Category::Platform
+        | Category::Plugin
+        | Category::Shells
+        | Category::System
+        | Category::Viewers => None,
+    }
+}
+
+/// The effects which are allowed while evaluating untrusted code.
+///
+/// The sandbox is entered with `nu --sandbox` or `run-sandboxed`. Commands declare their
+/// [`effects`](Command::effects), and calls to commands with effects which aren't allowed fail
+/// with [`ShellError::SandboxDenied`], like calls to commands which don't declare their effects.
+/// External commands are denied unless processes are allowed or their name is in
+/// `allow_externals`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sandbox {
+    pub allow_write: bool,
+    pub allow_network: bool,
+    pub allow_processes: bool,
+    /// External commands which can run even if processes aren't allowed
+    pub allow_externals: Vec<String>,
+    /// Commands which can run even if their effects aren't allowed
+    pub allow_commands: Vec<String>,
+}
+
+impl Sandbox {
+    /// Check whether `command` can run in the sandbox.
+    ///
+    /// Plugins run in their own process and can do anything, so they're treated as if they had
+    /// [`Effect::Process`]. Keywords, custom commands and aliases only have the effects they
+    /// declare, as the code they run is checked, and so do known externals, which are checked like
+    /// other external commands.
+    pub fn check_command(&self, command: &dyn Command, span: Span) -> Result<(), ShellError> {
+        let name = command.name();
+        if self.allow_commands.iter().any(|allowed| allowed == name) {
+            return Ok(());
+        }
+        let effects = match command.command_type() {
+            CommandType::Builtin => command.effects(),
+            CommandType::Plugin => Some(&[Effect::Process][..]),
+            CommandType::Keyword
+            | CommandType::Custom
+            | CommandType::Alias
+            | CommandType::External => Some(command.effects().unwrap_or_default()),
+        };
+        let Some(effects) = effects else {
+            return Err(ShellError::SandboxDenied {
+                command: name.into(),
+                effect: "run without declaring its effects".into(),
+                span,
+                help: format!("this needs `--allow-commands [{name}]` when entering the sandbox"),
+            });
+        };
+        effects
+            .iter()
+            .try_for_each(|effect| self.check_effect(name, *effect, span))
+    }
+
+    /// Check whether the command `name` can have `effect`, for commands which only have it with
+    /// some of their arguments.
+    pub fn check_effect(&self, name: &str, effect: Effect, span: Span) -> Result<(), ShellError> {
+        if effect.allowed_by(self) || self.allow_commands.iter().any(|allowed| allowed == name) {
+            Ok(())
+        } else {
+            Err(ShellError::SandboxDenied {
+                command: name.into(),
+                effect: effect.to_string(),
+                span,
+                help: format!(
+                    "this needs `--{}` or `--allow-commands [{name}]` when entering the sandbox",
+                    effect.flag()
+                ),
+            })
+        }
+    }
+
+    /// Check whether output can be redirected to a file with `o>`, `e>` or `o+e>`.
+    pub fn check_redirection(&self, span: Span) -> Result<(), ShellError> {
+        if self.allow_write {
+            Ok(())
+        } else {
+            Err(ShellError::SandboxDenied {
+                command: "redirection".into(),
+                effect: Effect::Write.to_string(),
+                span,
+                help: format!(
+                    "this needs `--{}` when entering the sandbox",
+                    Effect::Write.flag()
+                ),
+            })
+        }
+    }
+
+    /// Check whether the external command `name` can run in the sandbox.
+    pub fn check_external(&self, name: &str, span: Span) -> Result<(), ShellError> {
+        if self.allow_processes || self.allow_externals.iter().any(|allowed| allowed == name) {
+            Ok(())
+        } else {
+            Err(ShellError::SandboxDenied {
+                command: name.into(),
+                effect: Effect::Process.to_string(),
+                span,
+                help: format!("this needs `--allow-externals [{name}]` when entering the sandbox"),
+            })
+        }
+    }
+
+    /// The sandbox which only allows what both `self` and `inner` allow, for entering a sandbox
+    /// from inside another one.
+    pub fn restrict(&self, inner: &Sandbox) -> Sandbox {
+        let both = |outer: &[String], inner: &[String], outer_all: bool| -> Vec<String> {
+            inner
+                .iter()
+                .filter(|name| outer_all || outer.contains(*name))
+                .cloned()
+                .collect()
+        };
+        Sandbox {
+            allow_write: self.allow_write && inner.allow_write,
+            allow_network: self.allow_network && inner.allow_network,
+            allow_processes: self.allow_processes && inner.allow_processes,
+            allow_externals: both(
+                &self.allow_externals,
+                &inner.allow_externals,
+                self.allow_processes,
+            ),
+            allow_commands: both(&self.allow_commands, &inner.allow_commands, false),
+        }
+    }
+}
+
+impl From<&SandboxConfig> for Sandbox {
+    fn from(config: &SandboxConfig) -> Self {
+        Sandbox {
+            allow_write: config.allow_write,
+            allow_network: config.allow_network,
+            allow_processes: config.allow_processes,
+            allow_externals: config.allow_externals.clone(),
+            allow_commands: config.allow_commands.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        PipelineData, Signature,
+        engine::{Call, EngineState, Stack},
+    };
+
+    #[derive(Clone)]
+    struct Undeclared;
+
+    impl Command for Undeclared {
+        fn name(&self) -> &str {
+            "undeclared"
+        }
+
+        fn signature(&self) -> Signature {
+            Signature::build("undeclared").category(Category::System)
+        }
+
+        fn description(&self) -> &str {
+            "A command which doesn't declare its effects."
+        }
+
+        fn run(
+            &self,
+            _engine_state: &EngineState,
+            _stack: &mut Stack,
+            _call: &Call,
+            input: PipelineData,
+        ) -> Result<PipelineData, ShellError> {
+            Ok(input)
+        }
+    }
+
+    #[test]
+    fn commands_without_declared_effects_are_denied() {
+        let sandbox = Sandbox {
+            allow_write: true,
+            allow_network: true,
+            allow_processes: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            sandbox.check_command(&Undeclared, Span::test_data()),
+            Err(ShellError::SandboxDenied { .. })
+        ));
+
+        let sandbox = Sandbox {
+            allow_commands: vec!["undeclared".into()],
+            ..Default::default()
+        };
+        assert!(
+            sandbox
+                .check_command(&Undeclared, Span::test_data())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn externals_are_denied_unless_allowed() {
+        let sandbox = Sandbox {
+            allow_externals: vec!["git".into()],
+            ..Default::default()
+        };
+        assert!(sandbox.check_external("git", Span::test_data()).is_ok());
+        assert!(matches!(
+            sandbox.check_external("rm", Span::test_data()),
+            Err(ShellError::SandboxDenied { .. })
+        ));
+    }
+
+    #[test]
+    fn nested_sandboxes_only_keep_what_both_allow() {
+        let outer = Sandbox {
+            allow_write: true,
+            allow_externals: vec!["git".into()],
+            ..Default::default()
+        };
+        let inner = Sandbox {
+            allow_write: true,
+            allow_network: true,
+            allow_externals: vec!["git".into(), "curl".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            outer.restrict(&inner),
+            Sandbox {
+                allow_write: true,
+                allow_externals: vec!["git".into()],
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -54,6 +54,15 @@ pub struct Stack {
     pub parent_deletions: Vec<VarId>,
     /// Locally updated config. Use [`.get_config()`](Self::get_config) to access correctly.
     pub config: Option<Arc<Config>>,
+    /// Whether an environment variable was set or removed, or an overlay was switched, since the
+    /// closures of `env watch` were last checked.
+    pub env_changed: bool,
+    /// The values of the variables watched by `env watch` when this stack last checked them, by
+    /// the position of their watch in [`EngineState::env_watches`].
+    pub env_watch_values: Vec<Option<Value>>,
+    /// Set while the closures of `env watch` run on this stack and the stacks they create, so that
+    /// the changes the closures make don't run them again.
+    pub notifying_env_watches: bool,
     pub(crate) out_dest: StackOutDest,
 }
 
@@ -83,6 +92,9 @@ impl Stack {
             parent_stack: None,
             parent_deletions: vec![],
             config: None,
+            env_changed: false,
+            env_watch_values: vec![],
+            notifying_env_watches: false,
             out_dest: StackOutDest::new(),
         }
     }
@@ -103,6 +115,9 @@ impl Stack {
             vars: vec![],
             parent_deletions: vec![],
             config: parent.config.clone(),
+            env_changed: false,
+            env_watch_values: parent.env_watch_values.clone(),
+            notifying_env_watches: parent.notifying_env_watches,
             out_dest: parent.out_dest.clone(),
             parent_stack: Some(parent),
         }
@@ -130,6 +145,8 @@ impl Stack {
         unique_stack.env_hidden = child.env_hidden;
         unique_stack.active_overlays = child.active_overlays;
         unique_stack.config = child.config;
+        unique_stack.env_changed |= child.env_changed;
+        unique_stack.env_watch_values = child.env_watch_values;
         unique_stack
     }
 
@@ -252,6 +269,7 @@ impl Stack {
     }
 
     pub fn add_env_var(&mut self, var: String, value: Value) {
+        self.env_changed = true;
         if let Some(last_overlay) = self.active_overlays.last() {
             if let Some(env_hidden) = Arc::make_mut(&mut self.env_hidden).get_mut(last_overlay) {
                 // if the env var was hidden, let's activate it again
@@ -319,6 +337,9 @@ impl Stack {
             parent_stack: None,
             parent_deletions: vec![],
             config: self.config.clone(),
+            env_changed: false,
+            env_watch_values: self.env_watch_values.clone(),
+            notifying_env_watches: self.notifying_env_watches,
             out_dest: self.out_dest.clone(),
         }
     }
@@ -352,6 +373,9 @@ impl Stack {
             parent_stack: None,
             parent_deletions: vec![],
             config: self.config.clone(),
+            env_changed: false,
+            env_watch_values: self.env_watch_values.clone(),
+            notifying_env_watches: self.notifying_env_watches,
             out_dest: self.out_dest.clone(),
         }
     }
@@ -578,6 +602,7 @@ impl Stack {
     }
 
     pub fn remove_env_var(&mut self, engine_state: &EngineState, name: &str) -> bool {
+        self.env_changed = true;
         for scope in self.env_vars.iter_mut().rev() {
             let scope = Arc::make_mut(scope);
             for active_overlay in self.active_overlays.iter().rev() {
@@ -622,11 +647,13 @@ impl Stack {
     }
 
     pub fn add_overlay(&mut self, name: String) {
+        self.env_changed = true;
         self.active_overlays.retain(|o| o != &name);
         self.active_overlays.push(name);
     }
 
     pub fn remove_overlay(&mut self, name: &str) {
+        self.env_changed = true;
         self.active_overlays.retain(|o| o != name);
     }
 
@@ -317,6 +317,25 @@ impl<'a> StateWorkingSet<'a> {
         self.permanent_state.files().chain(self.delta.files.iter())
     }
 
+    /// The name of the file for code which is evaluated from a string, like a string hook.
+    ///
+    /// If the string was written in a file, the name tells where, like
+    /// `pre_prompt hook (config.nu:12)`, so that errors in the code lead back to it.
+    pub fn virtual_file_name(&self, name: &str, origin: Span) -> String {
+        if origin == Span::unknown() {
+            return name.to_string();
+        }
+        let Some(file) = self
+            .files()
+            .find(|file| file.covered_span.contains_span(origin))
+        else {
+            return name.to_string();
+        };
+        let before = self.get_span_contents(Span::new(file.covered_span.start, origin.start));
+        let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+        format!("{name} ({}:{line})", file.name)
+    }
+
     pub fn get_contents_of_file(&self, file_id: FileId) -> Option<&[u8]> {
         if let Some(cached_file) = self.permanent_state.get_file_contents().get(file_id.get()) {
             return Some(&cached_file.content);
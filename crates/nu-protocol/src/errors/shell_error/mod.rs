@@ -1388,6 +1388,40 @@ On Windows, this would be %USERPROFILE%\AppData\Roaming"#
         span: Span,
     },
 
+    /// A pipeline used more resources than `$env.config.limits` allows.
+    ///
+    /// ## Resolution
+    ///
+    /// Check whether the pipeline does what you meant, like collecting a whole file it should
+    /// stream. If it does, raise the limit, or set it to `null` to remove it.
+    #[error("Resource limit exceeded: {msg}")]
+    #[diagnostic(
+        code(nu::shell::resource_limit_exceeded),
+        help("this is limited by `$env.config.limits.{limit}`")
+    )]
+    ResourceLimitExceeded {
+        limit: String,
+        msg: String,
+        #[label("while running this")]
+        span: Span,
+    },
+
+    /// A command was called in a sandbox which doesn't allow what it does.
+    ///
+    /// ## Resolution
+    ///
+    /// If the code is trusted to do this, allow it when entering the sandbox with `nu --sandbox`
+    /// (through `$env.config.sandbox`) or `run-sandboxed`.
+    #[error("Not allowed in the sandbox: {command} can't {effect}")]
+    #[diagnostic(code(nu::shell::sandbox_denied), help("{help}"))]
+    SandboxDenied {
+        command: String,
+        effect: String,
+        #[label("denied by the sandbox")]
+        span: Span,
+        help: String,
+    },
+
     #[error(transparent)]
     #[diagnostic(transparent)]
     Job(#[from] JobError),
@@ -30,6 +30,20 @@ pub enum ShellWarning {
         #[related]
         warnings: Vec<ConfigWarning>,
     },
+    /// A `.nu-env` file applies to the current directory, but hasn't been trusted with `env trust`.
+    #[error("{file} is not trusted")]
+    #[diagnostic(
+        code(nu::shell::untrusted_env_file),
+        help("run `env trust` to load it")
+    )]
+    UntrustedEnvFile { file: String },
+    /// The config profile given with `--profile` or `$env.NU_PROFILE` doesn't have a directory.
+    #[error("Config profile '{profile}' not found")]
+    #[diagnostic(
+        code(nu::shell::missing_config_profile),
+        help("create {path} for it, only the base config is used until then")
+    )]
+    MissingConfigProfile { profile: String, path: String },
 }
 
 impl Reportable for ShellWarning {
@@ -37,6 +51,9 @@ impl Reportable for ShellWarning {
         match self {
             ShellWarning::Deprecated { report_mode, .. } => *report_mode,
             ShellWarning::InvalidConfig { .. } => ReportMode::FirstUse,
+            // Entering the directory again should remind the user of the file
+            ShellWarning::UntrustedEnvFile { .. } => ReportMode::EveryUse,
+            ShellWarning::MissingConfigProfile { .. } => ReportMode::FirstUse,
         }
     }
 }
@@ -53,6 +70,8 @@ impl Hash for ShellWarning {
             }
             // We always report config warnings, so no hash necessary
             ShellWarning::InvalidConfig { .. } => (),
+            ShellWarning::UntrustedEnvFile { file } => file.hash(state),
+            ShellWarning::MissingConfigProfile { profile, .. } => profile.hash(state),
         }
     }
 }
@@ -3,7 +3,7 @@
 //! This enables you to assign `const`-constants and execute parse-time code dependent on this.
 //! e.g. `source $my_const`
 use crate::{
-    BlockId, Config, HistoryFileFormat, PipelineData, Record, ShellError, Span, Value, VarId,
+    BlockId, Config, PipelineData, Record, ShellError, Span, Value, VarId,
     ast::{Assignment, Block, Call, Expr, Expression, ExternalArgument},
     debugger::{DebugContext, WithoutDebug},
     engine::{EngineState, StateWorkingSet},
@@ -34,6 +34,16 @@ pub(crate) fn create_nu_constant(engine_state: &EngineState, span: Span) -> Valu
         }
     }
 
+    // A state file may be a broken symlink left in the config directory, so canonicalize its
+    // directory on its own, like the config directory is
+    let canonicalize_state_file = |path: &Path| match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => canonicalize_path(
+            engine_state,
+            &canonicalize_path(engine_state, dir).join(name),
+        ),
+        _ => canonicalize_path(engine_state, path),
+    };
+
     let mut record = Record::new();
 
     let config_path = match nu_path::nu_config_dir() {
@@ -85,21 +95,12 @@ pub(crate) fn create_nu_constant(engine_state: &EngineState, span: Span) -> Valu
 
     record.push(
         "history-path",
-        config_path.clone().map_or_else(
-            |e| e,
-            |mut path| {
-                match engine_state.config.history.file_format {
-                    HistoryFileFormat::Sqlite => {
-                        path.push("history.sqlite3");
-                    }
-                    HistoryFileFormat::Plaintext => {
-                        path.push("history.txt");
-                    }
-                }
-                let canon_hist_path = canonicalize_path(engine_state, &path);
-                Value::string(canon_hist_path.to_string_lossy(), span)
-            },
-        ),
+        if let Some(path) = engine_state.config.history.file_path() {
+            let canon_hist_path = canonicalize_state_file(&path);
+            Value::string(canon_hist_path.to_string_lossy(), span)
+        } else {
+            Value::error(ShellError::ConfigDirNotFound { span }, span)
+        },
     );
 
     record.push(
@@ -119,18 +120,17 @@ pub(crate) fn create_nu_constant(engine_state: &EngineState, span: Span) -> Valu
         record.push(
             "plugin-path",
             if let Some(path) = &engine_state.plugin_path {
-                let canon_plugin_path = canonicalize_path(engine_state, path);
+                let canon_plugin_path = canonicalize_state_file(path);
                 Value::string(canon_plugin_path.to_string_lossy(), span)
             } else {
                 // If there are no signatures, we should still populate the plugin path
-                config_path.clone().map_or_else(
-                    |e| e,
-                    |mut path| {
-                        path.push("plugin.msgpackz");
-                        let canonical_plugin_path = canonicalize_path(engine_state, &path);
+                match nu_path::nu_state_file("plugin.msgpackz") {
+                    Some(path) => {
+                        let canonical_plugin_path = canonicalize_state_file(path.as_ref());
                         Value::string(canonical_plugin_path.to_string_lossy(), span)
-                    },
-                )
+                    }
+                    None => Value::error(ShellError::ConfigDirNotFound { span }, span),
+                }
             },
         );
     }
@@ -248,6 +248,14 @@ pub(crate) fn create_nu_constant(engine_state: &EngineState, span: Span) -> Valu
 
     record.push("is-login", Value::bool(engine_state.is_login, span));
 
+    record.push(
+        "profile",
+        match &engine_state.config_profile {
+            Some(profile) => Value::string(profile, span),
+            None => Value::nothing(span),
+        },
+    );
+
     record.push(
         "history-enabled",
         Value::bool(engine_state.history_enabled, span),
@@ -16,6 +16,7 @@ use std::{
 pub enum ParserPath {
     RealPath(PathBuf),
     VirtualFile(PathBuf, usize),
+    VirtualSource(PathBuf, &'static str),
     VirtualDir(PathBuf, Vec<ParserPath>),
 }
 
@@ -23,7 +24,7 @@ impl ParserPath {
     pub fn is_dir(&self) -> bool {
         match self {
             ParserPath::RealPath(p) => p.is_dir(),
-            ParserPath::VirtualFile(..) => false,
+            ParserPath::VirtualFile(..) | ParserPath::VirtualSource(..) => false,
             ParserPath::VirtualDir(..) => true,
         }
     }
@@ -31,7 +32,7 @@ impl ParserPath {
     pub fn is_file(&self) -> bool {
         match self {
             ParserPath::RealPath(p) => p.is_file(),
-            ParserPath::VirtualFile(..) => true,
+            ParserPath::VirtualFile(..) | ParserPath::VirtualSource(..) => true,
             ParserPath::VirtualDir(..) => false,
         }
     }
@@ -39,7 +40,7 @@ impl ParserPath {
     pub fn exists(&self) -> bool {
         match self {
             ParserPath::RealPath(p) => p.exists(),
-            ParserPath::VirtualFile(..) => true,
+            ParserPath::VirtualFile(..) | ParserPath::VirtualSource(..) => true,
             ParserPath::VirtualDir(..) => true,
         }
     }
@@ -48,6 +49,7 @@ impl ParserPath {
         match self {
             ParserPath::RealPath(p) => p,
             ParserPath::VirtualFile(p, _) => p,
+            ParserPath::VirtualSource(p, _) => p,
             ParserPath::VirtualDir(p, _) => p,
         }
     }
@@ -56,6 +58,7 @@ impl ParserPath {
         match self {
             ParserPath::RealPath(p) => p,
             ParserPath::VirtualFile(p, _) => p,
+            ParserPath::VirtualSource(p, _) => p,
             ParserPath::VirtualDir(p, _) => p,
         }
     }
@@ -64,6 +67,7 @@ impl ParserPath {
         match self {
             ParserPath::RealPath(p) => p.parent(),
             ParserPath::VirtualFile(p, _) => p.parent(),
+            ParserPath::VirtualSource(p, _) => p.parent(),
             ParserPath::VirtualDir(p, _) => p.parent(),
         }
     }
@@ -76,7 +80,7 @@ impl ParserPath {
                     .map(|dir_entry| ParserPath::RealPath(dir_entry.path()))
                     .collect()
             }),
-            ParserPath::VirtualFile(..) => None,
+            ParserPath::VirtualFile(..) | ParserPath::VirtualSource(..) => None,
             ParserPath::VirtualDir(_, files) => Some(files.clone()),
         }
     }
@@ -93,6 +97,7 @@ impl ParserPath {
         match self {
             ParserPath::RealPath(p) => ParserPath::RealPath(p.join(path)),
             ParserPath::VirtualFile(p, file_id) => ParserPath::VirtualFile(p.join(path), file_id),
+            ParserPath::VirtualSource(p, source) => ParserPath::VirtualSource(p.join(path), source),
             ParserPath::VirtualDir(p, entries) => {
                 let new_p = p.join(path);
                 let mut pp = ParserPath::RealPath(new_p.clone());
@@ -118,6 +123,9 @@ impl ParserPath {
                 .get_contents_of_file(FileId::new(*file_id))
                 .map(|bytes| Box::new(bytes) as Box<dyn std::io::Read>)
                 .ok_or(std::io::ErrorKind::NotFound.into()),
+            ParserPath::VirtualSource(_, source) => {
+                Ok(Box::new(source.as_bytes()) as Box<dyn std::io::Read>)
+            }
 
             ParserPath::VirtualDir(..) => Err(std::io::ErrorKind::NotFound.into()),
         }
@@ -142,6 +150,7 @@ impl ParserPath {
             VirtualPath::File(file_id) => {
                 ParserPath::VirtualFile(PathBuf::from(name), file_id.get())
             }
+            VirtualPath::Source(source) => ParserPath::VirtualSource(PathBuf::from(name), source),
             VirtualPath::Dir(entries) => ParserPath::VirtualDir(
                 PathBuf::from(name),
                 entries
@@ -508,6 +508,7 @@ impl ByteStream {
             reader: BufReader::new(reader),
             span: self.span,
             signals: self.signals,
+            ended: false,
         })
     }
 
@@ -592,29 +593,43 @@ impl ByteStream {
 
     /// Collect all the bytes of the [`ByteStream`] into a [`Vec<u8>`].
     ///
-    /// Any trailing new lines are kept in the returned [`Vec`].
+    /// Any trailing new lines are kept in the returned [`Vec`]. Collecting fails if there are more
+    /// bytes than the `value_size` of the [`Limits`](crate::Limits) allows.
     pub fn into_bytes(self) -> Result<Vec<u8>, ShellError> {
         // todo!() ctrlc
         let from_io_error = IoError::factory(self.span, None);
-        match self.stream {
-            ByteStreamSource::Read(mut read) => {
+        // Read one byte more than the limit allows, to know whether it's exceeded without reading
+        // the whole stream.
+        let max_len = self
+            .signals
+            .limits()
+            .and_then(|limits| limits.value_size())
+            .map_or(u64::MAX, |size| size.saturating_add(1));
+        let buf = match self.stream {
+            ByteStreamSource::Read(read) => {
                 let mut buf = Vec::new();
-                read.read_to_end(&mut buf).map_err(|err| {
+                read.take(max_len).read_to_end(&mut buf).map_err(|err| {
                     match ShellErrorBridge::try_from(err) {
                         Ok(ShellErrorBridge(err)) => err,
                         Err(err) => ShellError::Io(from_io_error(err)),
                     }
                 })?;
-                Ok(buf)
+                buf
             }
-            ByteStreamSource::File(mut file) => {
+            ByteStreamSource::File(file) => {
                 let mut buf = Vec::new();
-                file.read_to_end(&mut buf).map_err(&from_io_error)?;
-                Ok(buf)
+                file.take(max_len)
+                    .read_to_end(&mut buf)
+                    .map_err(&from_io_error)?;
+                buf
             }
             #[cfg(feature = "os")]
-            ByteStreamSource::Child(child) => child.into_bytes(),
+            ByteStreamSource::Child(child) => child.into_bytes_limited(max_len)?,
+        };
+        if let Some(limits) = self.signals.limits() {
+            limits.check_value_size(buf.len() as u64, self.span)?;
         }
+        Ok(buf)
     }
 
     /// Collect the stream into a `String` in-memory. This can only succeed if the data contained is
@@ -834,6 +849,7 @@ pub struct Lines {
     reader: BufReader<SourceReader>,
     span: Span,
     signals: Signals,
+    ended: bool,
 }
 
 impl Lines {
@@ -846,8 +862,11 @@ impl Iterator for Lines {
     type Item = Result<String, ShellError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.signals.interrupted() {
+        if self.ended {
             None
+        } else if self.signals.interrupted() {
+            self.ended = true;
+            self.signals.timeout_error(self.span).map(Err)
         } else {
             let mut buf = Vec::new();
             match self.reader.read_until(b'\n', &mut buf) {
@@ -869,6 +888,7 @@ pub struct SplitRead {
     internal: SplitReadInner<BufReader<SourceReader>>,
     span: Span,
     signals: Signals,
+    ended: bool,
 }
 
 impl SplitRead {
@@ -882,6 +902,7 @@ impl SplitRead {
             internal: SplitReadInner::new(BufReader::new(reader), delimiter),
             span,
             signals,
+            ended: false,
         }
     }
 
@@ -894,9 +915,13 @@ impl Iterator for SplitRead {
     type Item = Result<Vec<u8>, ShellError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.signals.interrupted() {
+        if self.ended {
             return None;
         }
+        if self.signals.interrupted() {
+            self.ended = true;
+            return self.signals.timeout_error(self.span).map(Err);
+        }
         self.internal.next().map(|r| {
             r.map_err(|err| {
                 ShellError::Io(IoError::new_internal(
@@ -1015,8 +1040,11 @@ impl Iterator for Chunks {
     type Item = Result<Value, ShellError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.error || self.signals.interrupted() {
+        if self.error {
             None
+        } else if self.signals.interrupted() {
+            self.error = true;
+            self.signals.timeout_error(self.span).map(Err)
         } else {
             match self.type_ {
                 // Binary should always be binary
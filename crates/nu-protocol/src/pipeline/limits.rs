@@ -0,0 +1,224 @@
+use crate::{Filesize, ShellError, Span, Value, config::LimitsConfig, format_duration};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+/// The limits from `$env.config.limits` for the pipeline which is running, shared by everything
+/// holding the same [`Signals`](crate::Signals).
+///
+/// Nothing is limited until [`start`](Self::start) is called, and [`stop`](Self::stop) lifts the
+/// limits again, so only the pipelines entered in the REPL are limited and not the prompt or hooks.
+#[derive(Debug)]
+pub struct Limits {
+    started_at: Instant,
+    /// Nanoseconds after `started_at` when the pipeline times out, or `u64::MAX`
+    deadline: AtomicU64,
+    pipeline_time: AtomicU64,
+    value_size: AtomicU64,
+    processes: AtomicU64,
+    spawned: AtomicU64,
+}
+
+const UNLIMITED: u64 = u64::MAX;
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            deadline: AtomicU64::new(UNLIMITED),
+            pipeline_time: AtomicU64::new(UNLIMITED),
+            value_size: AtomicU64::new(UNLIMITED),
+            processes: AtomicU64::new(UNLIMITED),
+            spawned: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Limits {
+    /// Apply `config` to the pipeline which is about to run.
+    pub fn start(&self, config: &LimitsConfig) {
+        let pipeline_time = config
+            .pipeline_time
+            .map_or(UNLIMITED, |time| time.max(0) as u64);
+        let deadline = (self.started_at.elapsed().as_nanos() as u64).saturating_add(pipeline_time);
+        self.pipeline_time.store(pipeline_time, Ordering::Relaxed);
+        self.deadline.store(deadline, Ordering::Relaxed);
+        self.value_size.store(
+            config
+                .value_size
+                .map_or(UNLIMITED, |size| size.get().max(0) as u64),
+            Ordering::Relaxed,
+        );
+        self.processes.store(
+            config.processes.map_or(UNLIMITED, |n| n as u64),
+            Ordering::Relaxed,
+        );
+        self.spawned.store(0, Ordering::Relaxed);
+    }
+
+    /// Lift all limits, once the pipeline is done.
+    pub fn stop(&self) {
+        self.start(&LimitsConfig::default());
+    }
+
+    /// Whether the pipeline has run for longer than it's allowed to.
+    #[inline]
+    pub fn timed_out(&self) -> bool {
+        let deadline = self.deadline.load(Ordering::Relaxed);
+        deadline != UNLIMITED && self.started_at.elapsed().as_nanos() as u64 > deadline
+    }
+
+    pub(crate) fn time_error(&self, span: Span) -> ShellError {
+        let pipeline_time = self.pipeline_time.load(Ordering::Relaxed);
+        ShellError::ResourceLimitExceeded {
+            limit: "pipeline_time".into(),
+            msg: format!(
+                "the pipeline ran for longer than {}",
+                format_duration(pipeline_time as i64)
+            ),
+            span,
+        }
+    }
+
+    /// The largest value, in bytes, a stream can be collected into.
+    pub fn value_size(&self) -> Option<u64> {
+        match self.value_size.load(Ordering::Relaxed) {
+            UNLIMITED => None,
+            size => Some(size),
+        }
+    }
+
+    /// Returns an error if `size` bytes are more than a collected value can have.
+    pub fn check_value_size(&self, size: u64, span: Span) -> Result<(), ShellError> {
+        match self.value_size() {
+            Some(limit) if size > limit => Err(ShellError::ResourceLimitExceeded {
+                limit: "value_size".into(),
+                msg: format!(
+                    "collecting the stream needs more than {}",
+                    Filesize::new(limit as i64)
+                ),
+                span,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count a process the pipeline is about to spawn, and return an error if it has spawned as
+    /// many as it's allowed to.
+    pub fn add_process(&self, span: Span) -> Result<(), ShellError> {
+        let limit = self.processes.load(Ordering::Relaxed);
+        let spawned = self.spawned.fetch_add(1, Ordering::Relaxed);
+        if limit != UNLIMITED && spawned >= limit {
+            Err(ShellError::ResourceLimitExceeded {
+                limit: "processes".into(),
+                msg: format!("the pipeline already spawned {limit} processes"),
+                span,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Roughly how many bytes of memory a value takes up, for enforcing the `value_size` limit.
+pub(crate) fn approximate_size(value: &Value) -> u64 {
+    let heap = match value {
+        Value::String { val, .. } | Value::Glob { val, .. } => val.len() as u64,
+        Value::Binary { val, .. } => val.len() as u64,
+        Value::List { vals, .. } => vals.iter().map(approximate_size).sum(),
+        Value::Record { val, .. } => val
+            .iter()
+            .map(|(col, val)| col.len() as u64 + approximate_size(val))
+            .sum(),
+        _ => 0,
+    };
+    std::mem::size_of::<Value>() as u64 + heap
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ByteStream, ListStream, Signals};
+
+    #[test]
+    fn limits_only_apply_between_start_and_stop() {
+        let limits = Limits::default();
+        let config = LimitsConfig {
+            value_size: Some(Filesize::new(10)),
+            pipeline_time: Some(0),
+            processes: Some(1),
+        };
+        assert!(limits.check_value_size(1000, Span::test_data()).is_ok());
+
+        limits.start(&config);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(limits.timed_out());
+        assert!(limits.check_value_size(10, Span::test_data()).is_ok());
+        assert!(limits.check_value_size(11, Span::test_data()).is_err());
+        assert!(limits.add_process(Span::test_data()).is_ok());
+        assert!(limits.add_process(Span::test_data()).is_err());
+
+        limits.stop();
+        assert!(!limits.timed_out());
+        assert!(limits.check_value_size(1000, Span::test_data()).is_ok());
+        assert!(limits.add_process(Span::test_data()).is_ok());
+    }
+
+    #[test]
+    fn collecting_streams_is_limited() {
+        let signals = Signals::new(Default::default());
+        let limits = signals.limits().expect("signals should have limits");
+        limits.start(&LimitsConfig {
+            value_size: Some(Filesize::new(1024)),
+            ..Default::default()
+        });
+
+        let stream = ListStream::new(
+            std::iter::repeat(Value::test_string("hello")),
+            Span::test_data(),
+            signals.clone(),
+        );
+        assert!(matches!(
+            stream.into_value(),
+            Err(ShellError::ResourceLimitExceeded { .. })
+        ));
+
+        let bytes = ByteStream::read_binary(vec![0; 2048], Span::test_data(), signals.clone());
+        assert!(bytes.into_bytes().is_err());
+        let bytes = ByteStream::read_binary(vec![0; 1024], Span::test_data(), signals);
+        assert_eq!(bytes.into_bytes().unwrap().len(), 1024);
+    }
+
+    #[test]
+    fn timed_out_streams_end_with_an_error() {
+        let signals = Signals::new(Default::default());
+        let limits = signals.limits().expect("signals should have limits");
+        limits.start(&LimitsConfig {
+            pipeline_time: Some(0),
+            ..Default::default()
+        });
+        std::thread::sleep(std::time::Duration::from_millis(1));
+
+        let mut values = ListStream::new(
+            std::iter::repeat(Value::test_int(1)),
+            Span::test_data(),
+            signals.clone(),
+        )
+        .into_iter();
+        assert!(matches!(
+            values.next(),
+            Some(Value::Error { error, .. }) if matches!(*error, ShellError::ResourceLimitExceeded { .. })
+        ));
+        assert!(values.next().is_none());
+
+        let mut lines = ByteStream::read_string("a\nb\n".into(), Span::test_data(), signals)
+            .lines()
+            .expect("string streams have lines");
+        assert!(matches!(
+            lines.next(),
+            Some(Err(ShellError::ResourceLimitExceeded { .. }))
+        ));
+        assert!(lines.next().is_none());
+    }
+}
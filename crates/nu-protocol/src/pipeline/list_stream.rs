@@ -2,6 +2,7 @@
 //! elements
 //!
 //! For more general infos regarding our pipelining model refer to [`PipelineData`]
+use super::limits::approximate_size;
 use crate::{Config, PipelineData, ShellError, Signals, Span, Value};
 use std::fmt::Debug;
 
@@ -15,6 +16,7 @@ pub type ValueIterator = Box<dyn Iterator<Item = Value> + Send + 'static>;
 pub struct ListStream {
     stream: ValueIterator,
     span: Span,
+    signals: Signals,
     caller_spans: Vec<Span>,
 }
 
@@ -26,8 +28,9 @@ impl ListStream {
         signals: Signals,
     ) -> Self {
         Self {
-            stream: Box::new(InterruptIter::new(iter, signals)),
+            stream: Box::new(InterruptIter::new(iter, span, signals.clone())),
             span,
+            signals,
             caller_spans: vec![],
         }
     }
@@ -77,7 +80,25 @@ impl ListStream {
     /// Collect the values of a [`ListStream`] into a list [`Value`].
     ///
     /// If any of the values in the stream is a [Value::Error], its inner [ShellError] is returned.
+    /// Collecting fails once the values are larger than the `value_size` of the [`Limits`](crate::Limits).
     pub fn into_value(self) -> Result<Value, ShellError> {
+        if let Some(limits) = self
+            .signals
+            .limits()
+            .filter(|limits| limits.value_size().is_some())
+        {
+            let mut size = 0;
+            let vals = self
+                .stream
+                .map(|val| {
+                    let val = val.unwrap_error()?;
+                    size += approximate_size(&val);
+                    limits.check_value_size(size, self.span)?;
+                    Ok(val)
+                })
+                .collect::<Result<_, ShellError>>()?;
+            return Ok(Value::list(vals, self.span));
+        }
         Ok(Value::list(
             self.stream
                 .map(Value::unwrap_error)
@@ -121,6 +142,7 @@ impl ListStream {
         Self {
             stream: Box::new(f(self.stream)),
             span: self.span,
+            signals: self.signals,
             caller_spans: self.caller_spans,
         }
     }
@@ -170,21 +192,33 @@ impl Iterator for IntoIter {
 
 struct InterruptIter<I: Iterator> {
     iter: I,
+    span: Span,
     signals: Signals,
+    ended: bool,
 }
 
 impl<I: Iterator> InterruptIter<I> {
-    fn new(iter: I, signals: Signals) -> Self {
-        Self { iter, signals }
+    fn new(iter: I, span: Span, signals: Signals) -> Self {
+        Self {
+            iter,
+            span,
+            signals,
+            ended: false,
+        }
     }
 }
 
-impl<I: Iterator> Iterator for InterruptIter<I> {
-    type Item = <I as Iterator>::Item;
+impl<I: Iterator<Item = Value>> Iterator for InterruptIter<I> {
+    type Item = Value;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.signals.interrupted() {
+        if self.ended {
             None
+        } else if self.signals.interrupted() {
+            self.ended = true;
+            self.signals
+                .timeout_error(self.span)
+                .map(|err| Value::error(err, self.span))
         } else {
             self.iter.next()
         }
@@ -1,5 +1,6 @@
 pub mod byte_stream;
 mod handlers;
+mod limits;
 pub mod list_stream;
 mod metadata;
 mod out_dest;
@@ -8,6 +9,7 @@ mod signals;
 
 pub use byte_stream::*;
 pub use handlers::*;
+pub use limits::*;
 pub use list_stream::*;
 pub use metadata::*;
 pub use out_dest::*;
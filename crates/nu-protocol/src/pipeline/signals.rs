@@ -1,4 +1,4 @@
-use crate::{ShellError, Span};
+use crate::{Limits, ShellError, Span};
 use nu_glob::Interruptible;
 use serde::{Deserialize, Serialize};
 use std::sync::{
@@ -8,17 +8,22 @@ use std::sync::{
 
 /// Used to check for signals to suspend or terminate the execution of Nushell code.
 ///
-/// For now, this struct only supports interruption (ctrl+c or SIGINT).
+/// For now, this struct only supports interruption (ctrl+c or SIGINT), and timing out after the
+/// `pipeline_time` of its [`Limits`].
 #[derive(Debug, Clone)]
 pub struct Signals {
     signals: Option<Arc<AtomicBool>>,
+    limits: Option<Arc<Limits>>,
 }
 
 impl Signals {
     /// A [`Signals`] that is not hooked up to any event/signals source.
     ///
     /// So, this [`Signals`] will never be interrupted.
-    pub const EMPTY: Self = Signals { signals: None };
+    pub const EMPTY: Self = Signals {
+        signals: None,
+        limits: None,
+    };
 
     /// Create a new [`Signals`] with `ctrlc` as the interrupt source.
     ///
@@ -27,6 +32,7 @@ impl Signals {
     pub fn new(ctrlc: Arc<AtomicBool>) -> Self {
         Self {
             signals: Some(ctrlc),
+            limits: Some(Arc::default()),
         }
     }
 
@@ -52,7 +58,10 @@ impl Signals {
         }
 
         if self.interrupted() {
-            interrupt_error(span)
+            match &self.limits {
+                Some(limits) if limits.timed_out() => Err(limits.time_error(*span)),
+                _ => interrupt_error(span),
+            }
         } else {
             Ok(())
         }
@@ -65,12 +74,27 @@ impl Signals {
         }
     }
 
-    /// Returns whether an interrupt has been triggered.
+    /// Returns whether an interrupt has been triggered, or the pipeline timed out.
     #[inline]
     pub fn interrupted(&self) -> bool {
         self.signals
             .as_deref()
             .is_some_and(|b| b.load(Ordering::Relaxed))
+            || self.limits.as_deref().is_some_and(Limits::timed_out)
+    }
+
+    /// The error for a pipeline which timed out. Streams end with it instead of just stopping like
+    /// they do when interrupted, so the output isn't silently truncated.
+    pub(crate) fn timeout_error(&self, span: Span) -> Option<ShellError> {
+        self.limits
+            .as_deref()
+            .filter(|limits| limits.timed_out())
+            .map(|limits| limits.time_error(span))
+    }
+
+    /// The resource limits of the running pipeline, if these signals are hooked up to any.
+    pub fn limits(&self) -> Option<&Limits> {
+        self.limits.as_deref()
     }
 
     pub(crate) fn is_empty(&self) -> bool {
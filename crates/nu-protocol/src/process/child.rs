@@ -340,6 +340,14 @@ impl ChildProcess {
     }
 
     pub fn into_bytes(self) -> Result<Vec<u8>, ShellError> {
+        self.into_bytes_limited(u64::MAX)
+    }
+
+    /// Like [`into_bytes`](Self::into_bytes), but stops reading stdout after `max_len` bytes.
+    ///
+    /// The exit status isn't checked if the output was cut off, because the process may still be
+    /// writing to it.
+    pub fn into_bytes_limited(self, max_len: u64) -> Result<Vec<u8>, ShellError> {
         if self.stderr.is_some() {
             debug_assert!(false, "stderr should not exist");
             return Err(ShellError::GenericError {
@@ -352,10 +360,13 @@ impl ChildProcess {
         }
 
         let bytes = if let Some(stdout) = self.stdout {
-            collect_bytes(stdout).map_err(|err| IoError::new(err, self.span, None))?
+            collect_bytes(stdout, max_len).map_err(|err| IoError::new(err, self.span, None))?
         } else {
             Vec::new()
         };
+        if bytes.len() as u64 >= max_len {
+            return Ok(bytes);
+        }
 
         let mut exit_status = self
             .exit_status
@@ -422,11 +433,11 @@ impl ChildProcess {
         let (stdout, stderr) = if let Some(stdout) = self.stdout {
             let stderr = self
                 .stderr
-                .map(|stderr| thread::Builder::new().spawn(move || collect_bytes(stderr)))
+                .map(|stderr| thread::Builder::new().spawn(move || collect_bytes(stderr, u64::MAX)))
                 .transpose()
                 .map_err(&from_io_error)?;
 
-            let stdout = collect_bytes(stdout).map_err(&from_io_error)?;
+            let stdout = collect_bytes(stdout, u64::MAX).map_err(&from_io_error)?;
 
             let stderr = stderr
                 .map(|handle| {
@@ -449,7 +460,7 @@ impl ChildProcess {
         } else {
             let stderr = self
                 .stderr
-                .map(collect_bytes)
+                .map(|pipe| collect_bytes(pipe, u64::MAX))
                 .transpose()
                 .map_err(&from_io_error)?;
 
@@ -474,12 +485,9 @@ impl ChildProcess {
     }
 }
 
-fn collect_bytes(pipe: ChildPipe) -> io::Result<Vec<u8>> {
+fn collect_bytes(pipe: ChildPipe, max_len: u64) -> io::Result<Vec<u8>> {
     let mut buf = Vec::new();
-    match pipe {
-        ChildPipe::Pipe(mut pipe) => pipe.read_to_end(&mut buf),
-        ChildPipe::Tee(mut tee) => tee.read_to_end(&mut buf),
-    }?;
+    pipe.take(max_len).read_to_end(&mut buf)?;
     Ok(buf)
 }
 
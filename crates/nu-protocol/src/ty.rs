@@ -104,6 +104,35 @@ impl Type {
         }
     }
 
+    /// The type of a list with elements of the given types.
+    ///
+    /// Lists of records are tables, lists of mixed numbers are lists of numbers, and any other
+    /// mix is a list of `any`. The types after the first one which makes it `any` aren't used.
+    pub fn of_list(types: impl IntoIterator<Item = Type>) -> Type {
+        let mut ty = None;
+        for val_ty in types {
+            match &ty {
+                Some(x) => {
+                    if &val_ty != x {
+                        if x.is_numeric() && val_ty.is_numeric() {
+                            ty = Some(Type::Number)
+                        } else {
+                            ty = Some(Type::Any);
+                            break;
+                        }
+                    }
+                }
+                None => ty = Some(val_ty),
+            }
+        }
+
+        match ty {
+            Some(Type::Record(columns)) => Type::Table(columns),
+            Some(ty) => Type::List(Box::new(ty)),
+            None => Type::List(Box::new(Type::Any)),
+        }
+    }
+
     pub fn is_numeric(&self) -> bool {
         matches!(self, Type::Int | Type::Float | Type::Number)
     }
@@ -1,6 +1,10 @@
 use std::{cmp::Ordering, fmt, path::Path};
 
-use crate::{ShellError, Span, Spanned, Type, Value, ast::Operator, casing::Casing};
+use crate::{
+    ShellError, Span, Spanned, Type, Value,
+    ast::{Comparison, Operator},
+    casing::Casing,
+};
 
 /// Trait definition for a custom [`Value`](crate::Value) type
 #[typetag::serde(tag = "type")]
@@ -110,6 +114,25 @@ pub trait CustomValue: fmt::Debug + Send + Sync {
         })
     }
 
+    /// Definition of an operation between another Value and the object that implements the
+    /// trait, when the custom value is on the right side of the operator.
+    ///
+    /// This is only used if the left side isn't a custom value, so values like money or vectors
+    /// can support both `$money * 2` and `2 * $money`. Only math and comparison operators
+    /// are dispatched here; `in`, `has` and the others are handled by the left side.
+    ///
+    /// Default impl keeps the behavior of values which don't implement it: `==` is `false`, `!=`
+    /// is `true`, and other operators raise [`ShellError::OperatorUnsupportedType`].
+    fn operation_rhs(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        default_operation_rhs(self.type_name(), left, operator, op, rhs_span)
+    }
+
     /// Save custom value to disk.
     ///
     /// This method is used in `save` to save a custom value to disk.
@@ -149,3 +172,26 @@ pub trait CustomValue: fmt::Debug + Send + Sync {
         false
     }
 }
+
+/// The default impl of [`CustomValue::operation_rhs`] for a custom value named `type_name`, for
+/// impls which can't always handle the operator themselves.
+pub fn default_operation_rhs(
+    type_name: String,
+    left: &Value,
+    operator: Operator,
+    op: Span,
+    rhs_span: Span,
+) -> Result<Value, ShellError> {
+    let span = left.span().merge(rhs_span);
+    match operator {
+        Operator::Comparison(Comparison::Equal) => Ok(Value::bool(false, span)),
+        Operator::Comparison(Comparison::NotEqual) => Ok(Value::bool(true, span)),
+        _ => Err(ShellError::OperatorUnsupportedType {
+            op: operator,
+            unsupported: Type::Custom(type_name.into()),
+            op_span: op,
+            unsupported_span: rhs_span,
+            help: None,
+        }),
+    }
+}
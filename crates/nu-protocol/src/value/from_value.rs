@@ -1,5 +1,5 @@
 use crate::{
-    NuGlob, Range, Record, ShellError, Span, Spanned, Type, Value,
+    NuGlob, PathValue, Range, Record, ShellError, Span, Spanned, Type, Value,
     ast::{CellPath, PathMember},
     casing::Casing,
     engine::Closure,
@@ -495,6 +495,9 @@ tuple_from_value!("[{}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}]", T0:0, T1:1
 
 impl FromValue for PathBuf {
     fn from_value(v: Value) -> Result<Self, ShellError> {
+        if let Some(path) = PathValue::try_from_value(&v) {
+            return Ok(path.to_path_buf());
+        }
         match v {
             Value::String { val, .. } => Ok(val.into()),
             v => Err(ShellError::CantConvert {
@@ -0,0 +1,369 @@
+use crate::{
+    CustomValue, ShellError, Span, Value,
+    ast::{Comparison, Math, Operator},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+/// An IP address, or a network in CIDR notation like `10.0.0.0/8`.
+///
+/// IP values are created with `into ip`, and are shown and converted to strings in their usual
+/// notation. They compare with other IP values and with strings, addresses are in the networks
+/// which contain them, so `$ip in '10.0.0.0/8'` works, and integers can be added to them to get
+/// the next addresses.
+///
+/// The address of a network can have host bits, like the `192.168.1.10/24` of an interface. The
+/// network itself has them cleared; it's `192.168.1.0/24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct IpValue {
+    pub addr: IpAddr,
+    /// The prefix length of a network, or nothing for an address.
+    pub prefix: Option<u8>,
+}
+
+impl IpValue {
+    pub fn new(addr: IpAddr, prefix: Option<u8>) -> Self {
+        Self { addr, prefix }
+    }
+
+    /// Parse an address like `10.0.0.1` or `::1`, or a network like `10.0.0.0/8`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (addr, prefix) = match text.trim().split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (text.trim(), None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("'{addr}' is not an IP address"))?;
+        let prefix = prefix
+            .map(|prefix| match prefix.parse::<u8>() {
+                Ok(prefix) if prefix <= max_prefix(addr) => Ok(prefix),
+                _ => Err(format!(
+                    "the prefix length has to be between 0 and {}",
+                    max_prefix(addr)
+                )),
+            })
+            .transpose()?;
+        Ok(Self { addr, prefix })
+    }
+
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    /// The IP value in `value`, if it's one.
+    pub fn try_from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Custom { val, .. } => val.as_any().downcast_ref::<IpValue>().copied(),
+            _ => None,
+        }
+    }
+
+    /// An IP value, or a string which is one.
+    fn other_ip(other: &Value) -> Option<Self> {
+        match other {
+            Value::String { val, .. } => IpValue::parse(val).ok(),
+            other => IpValue::try_from_value(other),
+        }
+    }
+
+    /// The prefix length, which is the number of bits of the address for an address.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix.unwrap_or(max_prefix(self.addr))
+    }
+
+    /// The first and last addresses of the network, as numbers.
+    pub fn range(&self) -> (u128, u128) {
+        let host_bits = (max_prefix(self.addr) - self.prefix_len()) as u32;
+        let host_mask = u128::MAX.checked_shr(128 - host_bits).unwrap_or(0);
+        let bits = to_bits(self.addr);
+        (bits & !host_mask, bits | host_mask)
+    }
+
+    /// The network with the host bits of its address cleared, or the address itself.
+    pub fn network(&self) -> Self {
+        Self {
+            addr: self.with_bits(self.range().0),
+            prefix: self.prefix,
+        }
+    }
+
+    /// The address of the same version with the number `bits`.
+    pub fn with_bits(&self, bits: u128) -> IpAddr {
+        match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(bits as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        }
+    }
+
+    /// Whether all the addresses of `other` are in this network.
+    pub fn contains(&self, other: &IpValue) -> bool {
+        let (first, last) = self.range();
+        let (other_first, other_last) = other.range();
+        self.addr.is_ipv4() == other.addr.is_ipv4() && first <= other_first && other_last <= last
+    }
+
+    /// The address `offset` addresses after this one, with the same prefix.
+    fn offset(&self, offset: i64) -> Option<Self> {
+        let bits = to_bits(self.addr).checked_add_signed(offset as i128)?;
+        let max = u128::MAX.checked_shr(128 - max_prefix(self.addr) as u32)?;
+        (bits <= max).then(|| Self {
+            addr: self.with_bits(bits),
+            prefix: self.prefix,
+        })
+    }
+}
+
+impl fmt::Display for IpValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.prefix {
+            Some(prefix) => write!(f, "{}/{prefix}", self.addr),
+            None => write!(f, "{}", self.addr),
+        }
+    }
+}
+
+fn max_prefix(addr: IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn to_bits(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(addr) => u32::from(addr) as u128,
+        IpAddr::V6(addr) => u128::from(addr),
+    }
+}
+
+const HELP: &str = "IP values compare with IP values and strings, can be tested with `in` and `has`, and integers can be added to them";
+
+impl CustomValue for IpValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(*self), span)
+    }
+
+    fn type_name(&self) -> String {
+        "ip".into()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::string(self.to_string(), span))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        IpValue::other_ip(other).map(|other| Ord::cmp(self, &other))
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        let span = lhs_span.merge(right.span());
+        let other = IpValue::other_ip(right);
+        let ordering = other.map(|other| Ord::cmp(self, &other));
+        let out_of_range = || ShellError::OperatorOverflow {
+            msg: "the address is out of the range of IP addresses".into(),
+            span,
+            help: None,
+        };
+        let result = match (operator, right) {
+            (Operator::Math(Math::Add), Value::Int { val, .. }) => {
+                Some(self.offset(*val).ok_or_else(out_of_range)?.into_value(span))
+            }
+            (Operator::Math(Math::Subtract), Value::Int { val, .. }) => Some(
+                val.checked_neg()
+                    .and_then(|val| self.offset(val))
+                    .ok_or_else(out_of_range)?
+                    .into_value(span),
+            ),
+            // The number of addresses between two addresses
+            (Operator::Math(Math::Subtract), _) => match other {
+                Some(other) if other.addr.is_ipv4() == self.addr.is_ipv4() => {
+                    let (lhs, rhs) = (to_bits(self.addr), to_bits(other.addr));
+                    let difference = if lhs >= rhs {
+                        i64::try_from(lhs - rhs).ok()
+                    } else {
+                        i64::try_from(rhs - lhs).ok().map(|difference| -difference)
+                    };
+                    Some(Value::int(difference.ok_or_else(out_of_range)?, span))
+                }
+                _ => None,
+            },
+            (Operator::Comparison(Comparison::In), _) => {
+                other.map(|other| Value::bool(other.contains(self), span))
+            }
+            (Operator::Comparison(Comparison::NotIn), _) => {
+                other.map(|other| Value::bool(!other.contains(self), span))
+            }
+            // IP values are never equal to values which aren't IP values
+            (Operator::Comparison(Comparison::Equal), _) => {
+                Some(Value::bool(ordering == Some(Ordering::Equal), span))
+            }
+            (Operator::Comparison(Comparison::NotEqual), _) => {
+                Some(Value::bool(ordering != Some(Ordering::Equal), span))
+            }
+            (Operator::Comparison(comparison), _) => ordering
+                .and_then(|ordering| match comparison {
+                    Comparison::LessThan => Some(ordering.is_lt()),
+                    Comparison::GreaterThan => Some(ordering.is_gt()),
+                    Comparison::LessThanOrEqual => Some(ordering.is_le()),
+                    Comparison::GreaterThanOrEqual => Some(ordering.is_ge()),
+                    _ => None,
+                })
+                .map(|val| Value::bool(val, span)),
+            _ => None,
+        };
+        result.ok_or_else(|| ShellError::OperatorUnsupportedType {
+            op: operator,
+            unsupported: right.get_type(),
+            op_span: op,
+            unsupported_span: right.span(),
+            help: Some(HELP),
+        })
+    }
+
+    fn operation_rhs(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        let right = self.into_value(rhs_span);
+        match (left, operator) {
+            // `1 + $ip` is the same as `$ip + 1`
+            (Value::Int { .. }, Operator::Math(Math::Add)) => {
+                self.operation(rhs_span, operator, op, left)
+            }
+            // A string on the left works like an IP value, so `'10.0.0.1' < $ip` works too
+            (Value::String { val, .. }, _) if IpValue::parse(val).is_ok() => IpValue::parse(val)
+                .expect("checked above")
+                .operation(left.span(), operator, op, &right),
+            _ => {
+                let span = left.span().merge(rhs_span);
+                match operator {
+                    Operator::Comparison(Comparison::Equal) => Ok(Value::bool(false, span)),
+                    Operator::Comparison(Comparison::NotEqual) => Ok(Value::bool(true, span)),
+                    _ => Err(ShellError::OperatorUnsupportedType {
+                        op: operator,
+                        unsupported: left.get_type(),
+                        op_span: op,
+                        unsupported_span: left.span(),
+                        help: Some(HELP),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn typetag_name(&self) -> &'static str {
+        "IpValue"
+    }
+
+    fn typetag_deserialize(&self) {
+        unimplemented!("typetag_deserialize")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ip(text: &str) -> IpValue {
+        IpValue::parse(text).unwrap()
+    }
+
+    fn operation(lhs: &str, operator: Operator, rhs: Value) -> Value {
+        ip(lhs)
+            .operation(Span::test_data(), operator, Span::test_data(), &rhs)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_addresses_and_networks() {
+        assert_eq!(ip("10.1.2.3").to_string(), "10.1.2.3");
+        assert_eq!(
+            ip("192.168.1.10/24").network().to_string(),
+            "192.168.1.0/24"
+        );
+        assert_eq!(ip("2001:db8::1/32").network().to_string(), "2001:db8::/32");
+        assert!(IpValue::parse("10.0.0.0/33").is_err());
+        assert!(IpValue::parse("10.0.0").is_err());
+    }
+
+    #[test]
+    fn networks_contain_addresses() {
+        assert!(ip("10.0.0.0/8").contains(&ip("10.20.30.40")));
+        assert!(ip("10.0.0.0/8").contains(&ip("10.128.0.0/9")));
+        assert!(!ip("10.0.0.0/8").contains(&ip("11.0.0.1")));
+        assert!(!ip("10.0.0.0/8").contains(&ip("::a00:1")));
+        assert!(ip("0.0.0.0/0").contains(&ip("255.255.255.255")));
+        assert_eq!(
+            operation(
+                "10.1.1.1",
+                Operator::Comparison(Comparison::In),
+                Value::test_string("10.0.0.0/8")
+            ),
+            Value::test_bool(true)
+        );
+    }
+
+    #[test]
+    fn integers_are_added_to_addresses() {
+        assert_eq!(
+            IpValue::try_from_value(&operation(
+                "10.0.0.255",
+                Operator::Math(Math::Add),
+                Value::test_int(1)
+            )),
+            Some(ip("10.0.1.0"))
+        );
+        assert!(
+            ip("255.255.255.255")
+                .operation(
+                    Span::test_data(),
+                    Operator::Math(Math::Add),
+                    Span::test_data(),
+                    &Value::test_int(1)
+                )
+                .is_err()
+        );
+        assert_eq!(
+            operation(
+                "10.0.1.0",
+                Operator::Math(Math::Subtract),
+                ip("10.0.0.0").into_value(Span::test_data())
+            ),
+            Value::test_int(256)
+        );
+    }
+
+    #[test]
+    fn addresses_compare_numerically() {
+        assert_eq!(
+            operation(
+                "10.0.0.9",
+                Operator::Comparison(Comparison::LessThan),
+                Value::test_string("10.0.0.10")
+            ),
+            Value::test_bool(true)
+        );
+    }
+}
@@ -4,18 +4,22 @@ mod filesize;
 mod from_value;
 mod glob;
 mod into_value;
+mod ip;
+mod path;
 mod range;
 #[cfg(test)]
 mod test_derive;
 
 pub mod record;
-pub use custom_value::CustomValue;
+pub use custom_value::{CustomValue, default_operation_rhs};
 pub use duration::*;
 pub use filesize::*;
 pub use from_value::FromValue;
 pub use glob::*;
 pub use into_value::{IntoValue, TryIntoValue};
+pub use ip::IpValue;
 pub use nu_utils::MultiLife;
+pub use path::PathValue;
 pub use range::{FloatRange, IntRange, Range};
 pub use record::Record;
 
@@ -805,31 +809,7 @@ impl Value {
             Value::Record { val, .. } => {
                 Type::Record(val.iter().map(|(x, y)| (x.clone(), y.get_type())).collect())
             }
-            Value::List { vals, .. } => {
-                let mut ty = None;
-                for val in vals {
-                    let val_ty = val.get_type();
-                    match &ty {
-                        Some(x) => {
-                            if &val_ty != x {
-                                if x.is_numeric() && val_ty.is_numeric() {
-                                    ty = Some(Type::Number)
-                                } else {
-                                    ty = Some(Type::Any);
-                                    break;
-                                }
-                            }
-                        }
-                        None => ty = Some(val_ty),
-                    }
-                }
-
-                match ty {
-                    Some(Type::Record(columns)) => Type::Table(columns),
-                    Some(ty) => Type::List(Box::new(ty)),
-                    None => Type::List(Box::new(Type::Any)),
-                }
-            }
+            Value::List { vals, .. } => Type::of_list(vals.iter().map(Value::get_type)),
             Value::Nothing { .. } => Type::Nothing,
             Value::Closure { .. } => Type::Closure,
             Value::Error { .. } => Type::Error,
@@ -1002,8 +982,7 @@ impl Value {
             Value::CellPath { val, .. } => val.to_string(),
             // If we fail to collapse the custom value, just print <{type_name}> - failure is not
             // that critical here
-            Value::Custom { val, .. } => val
-                .to_base_value(span)
+            Value::Custom { val, .. } => PathValue::display_base_value(&**val, span, config)
                 .map(|val| val.to_expanded_string(separator, config))
                 .unwrap_or_else(|_| format!("<{}>", val.type_name())),
         }
@@ -2265,6 +2244,19 @@ impl Default for Value {
     }
 }
 
+/// Values of different types are ordered by their type, in this order:
+///
+/// bool, int and float, string and glob, filesize, duration, date, range, record, list,
+/// closure, error, binary, cell path, custom value, nothing.
+///
+/// Ints and floats are compared to each other by their numeric value, and strings and globs by
+/// their text. Records are compared by their sorted columns, and then by the values in that order.
+///
+/// Custom values are compared with other values by the custom value, whichever side it's on, so
+/// that `a.partial_cmp(b)` is always the reverse of `b.partial_cmp(a)`.
+///
+/// Floats which are NaN and most custom values have no ordering; see [`Value::total_cmp`] for an
+/// ordering of all values.
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         // Compare two floating point numbers. The decision interval for equality is dynamically
@@ -2299,7 +2291,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Int { val: lhs, .. }, rhs) => match rhs {
@@ -2318,7 +2310,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Float { val: lhs, .. }, rhs) => match rhs {
@@ -2337,7 +2329,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::String { val: lhs, .. }, rhs) => match rhs {
@@ -2356,7 +2348,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Glob { val: lhs, .. }, rhs) => match rhs {
@@ -2375,7 +2367,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Filesize { val: lhs, .. }, rhs) => match rhs {
@@ -2394,7 +2386,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Duration { val: lhs, .. }, rhs) => match rhs {
@@ -2413,7 +2405,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Date { val: lhs, .. }, rhs) => match rhs {
@@ -2432,7 +2424,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Range { val: lhs, .. }, rhs) => match rhs {
@@ -2451,7 +2443,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Record { val: lhs, .. }, rhs) => match rhs {
@@ -2468,20 +2460,19 @@ impl PartialOrd for Value {
                     // reorder cols and vals to make more logically compare.
                     // more general, if two record have same col and values,
                     // the order of cols shouldn't affect the equal property.
-                    let mut lhs = lhs.clone().into_owned();
-                    let mut rhs = rhs.clone().into_owned();
-                    lhs.sort_cols();
-                    rhs.sort_cols();
+                    // The entries are sorted by reference, so the records aren't copied.
+                    let lhs = sorted_entries(lhs);
+                    let rhs = sorted_entries(rhs);
 
                     // Check columns first
-                    for (a, b) in lhs.columns().zip(rhs.columns()) {
+                    for ((a, _), (b, _)) in lhs.iter().zip(&rhs) {
                         let result = a.partial_cmp(b);
                         if result != Some(Ordering::Equal) {
                             return result;
                         }
                     }
                     // Then check the values
-                    for (a, b) in lhs.values().zip(rhs.values()) {
+                    for ((_, a), (_, b)) in lhs.iter().zip(&rhs) {
                         let result = a.partial_cmp(b);
                         if result != Some(Ordering::Equal) {
                             return result;
@@ -2496,7 +2487,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::List { vals: lhs, .. }, rhs) => match rhs {
@@ -2515,7 +2506,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Closure { val: lhs, .. }, rhs) => match rhs {
@@ -2534,7 +2525,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Less),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Error { .. }, rhs) => match rhs {
@@ -2553,7 +2544,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Equal),
                 Value::Binary { .. } => Some(Ordering::Less),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Binary { val: lhs, .. }, rhs) => match rhs {
@@ -2572,7 +2563,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Greater),
                 Value::Binary { val: rhs, .. } => lhs.partial_cmp(rhs),
                 Value::CellPath { .. } => Some(Ordering::Less),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::CellPath { val: lhs, .. }, rhs) => match rhs {
@@ -2591,7 +2582,7 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Greater),
                 Value::Binary { .. } => Some(Ordering::Greater),
                 Value::CellPath { val: rhs, .. } => lhs.partial_cmp(rhs),
-                Value::Custom { .. } => Some(Ordering::Less),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Less),
             },
             (Value::Custom { val: lhs, .. }, rhs) => lhs.partial_cmp(rhs),
@@ -2611,19 +2602,97 @@ impl PartialOrd for Value {
                 Value::Error { .. } => Some(Ordering::Greater),
                 Value::Binary { .. } => Some(Ordering::Greater),
                 Value::CellPath { .. } => Some(Ordering::Greater),
-                Value::Custom { .. } => Some(Ordering::Greater),
+                Value::Custom { val: rhs, .. } => rhs.partial_cmp(self).map(Ordering::reverse),
                 Value::Nothing { .. } => Some(Ordering::Equal),
             },
         }
     }
 }
 
+/// The entries of a record sorted by their column.
+fn sorted_entries(record: &Record) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = record.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         self.partial_cmp(other).is_some_and(Ordering::is_eq)
     }
 }
 
+impl Value {
+    /// Compare two values, with an ordering even for the values which [`PartialOrd`] can't order.
+    ///
+    /// This is the order of `partial_cmp`, with these additions, so that sorting is stable and
+    /// doesn't depend on the order of the input:
+    /// - NaN is greater than every other number, and equal to itself.
+    /// - Custom values which can't be compared with a value are ordered by their type, after the
+    ///   cell paths, and custom values of different types by the name of their type.
+    /// - Records and lists are compared element by element with `total_cmp`.
+    pub fn total_cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Record { val: lhs, .. }, Value::Record { val: rhs, .. }) => {
+                let lhs = sorted_entries(lhs);
+                let rhs = sorted_entries(rhs);
+                // Like `partial_cmp`, the columns first, then the values, then the length
+                lhs.iter()
+                    .zip(&rhs)
+                    .map(|((a, _), (b, _))| a.cmp(b))
+                    .chain(lhs.iter().zip(&rhs).map(|((_, a), (_, b))| a.total_cmp(b)))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| lhs.len().cmp(&rhs.len()))
+            }
+            (Value::List { vals: lhs, .. }, Value::List { vals: rhs, .. }) => lhs
+                .iter()
+                .zip(rhs)
+                .map(|(a, b)| a.total_cmp(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| lhs.len().cmp(&rhs.len())),
+            _ => match self.partial_cmp(other) {
+                Some(ordering) => ordering,
+                None => self
+                    .type_rank()
+                    .cmp(&other.type_rank())
+                    .then_with(|| self.is_nan().cmp(&other.is_nan()))
+                    .then_with(|| match (self, other) {
+                        (Value::Custom { val: lhs, .. }, Value::Custom { val: rhs, .. }) => {
+                            lhs.type_name().cmp(&rhs.type_name())
+                        }
+                        _ => Ordering::Equal,
+                    }),
+            },
+        }
+    }
+
+    /// The position of the type of the value in the order of [`PartialOrd`] for values of
+    /// different types.
+    fn type_rank(&self) -> u8 {
+        match self {
+            Value::Bool { .. } => 0,
+            Value::Int { .. } | Value::Float { .. } => 1,
+            Value::String { .. } | Value::Glob { .. } => 2,
+            Value::Filesize { .. } => 3,
+            Value::Duration { .. } => 4,
+            Value::Date { .. } => 5,
+            Value::Range { .. } => 6,
+            Value::Record { .. } => 7,
+            Value::List { .. } => 8,
+            Value::Closure { .. } => 9,
+            Value::Error { .. } => 10,
+            Value::Binary { .. } => 11,
+            Value::CellPath { .. } => 12,
+            Value::Custom { .. } => 13,
+            Value::Nothing { .. } => 14,
+        }
+    }
+
+    fn is_nan(&self) -> bool {
+        matches!(self, Value::Float { val, .. } if val.is_nan())
+    }
+}
+
 impl Value {
     pub fn add(&self, op: Span, rhs: &Value, span: Span) -> Result<Value, ShellError> {
         match (self, rhs) {
@@ -2697,6 +2766,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Add), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Add), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Add),
                 op,
@@ -2786,6 +2858,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Subtract), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Subtract), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Subtract),
                 op,
@@ -2886,6 +2961,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Multiply), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Multiply), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Multiply),
                 op,
@@ -3008,6 +3086,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Divide), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Divide), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Divide),
                 op,
@@ -3171,6 +3252,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::FloorDivide), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::FloorDivide), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::FloorDivide),
                 op,
@@ -3335,6 +3419,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(span, Operator::Math(Math::Modulo), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Modulo), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Modulo),
                 op,
@@ -3386,6 +3473,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(span, Operator::Math(Math::Pow), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Pow), op, rhs.span())
+            }
             _ => Err(operator_type_error(
                 Operator::Math(Math::Pow),
                 op,
@@ -3411,6 +3501,9 @@ impl Value {
             (Value::Custom { val: lhs, .. }, rhs) => {
                 lhs.operation(self.span(), Operator::Math(Math::Concatenate), op, rhs)
             }
+            (lhs, Value::Custom { val: custom, .. }) => {
+                custom.operation_rhs(lhs, Operator::Math(Math::Concatenate), op, rhs.span())
+            }
             _ => {
                 let help = if matches!(self, Value::List { .. })
                     || matches!(rhs, Value::List { .. })
@@ -3473,6 +3566,15 @@ impl Value {
             return Ok(Value::nothing(span));
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::LessThan),
+                op,
+                rhs.span(),
+            );
+        }
+
         if !type_compatible(self.get_type(), rhs.get_type()) {
             return Err(operator_type_error(
                 Operator::Comparison(Comparison::LessThan),
@@ -3515,6 +3617,15 @@ impl Value {
             return Ok(Value::nothing(span));
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::LessThanOrEqual),
+                op,
+                rhs.span(),
+            );
+        }
+
         if !type_compatible(self.get_type(), rhs.get_type()) {
             return Err(operator_type_error(
                 Operator::Comparison(Comparison::LessThanOrEqual),
@@ -3560,6 +3671,15 @@ impl Value {
             return Ok(Value::nothing(span));
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::GreaterThan),
+                op,
+                rhs.span(),
+            );
+        }
+
         if !type_compatible(self.get_type(), rhs.get_type()) {
             return Err(operator_type_error(
                 Operator::Comparison(Comparison::GreaterThan),
@@ -3602,6 +3722,15 @@ impl Value {
             return Ok(Value::nothing(span));
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::GreaterThanOrEqual),
+                op,
+                rhs.span(),
+            );
+        }
+
         if !type_compatible(self.get_type(), rhs.get_type()) {
             return Err(operator_type_error(
                 Operator::Comparison(Comparison::GreaterThanOrEqual),
@@ -3643,6 +3772,15 @@ impl Value {
             );
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::Equal),
+                op,
+                rhs.span(),
+            );
+        }
+
         Ok(Value::bool(
             matches!(self.partial_cmp(rhs), Some(Ordering::Equal)),
             span,
@@ -3659,6 +3797,15 @@ impl Value {
             );
         }
 
+        if let Value::Custom { val: custom, .. } = rhs {
+            return custom.operation_rhs(
+                self,
+                Operator::Comparison(Comparison::NotEqual),
+                op,
+                rhs.span(),
+            );
+        }
+
         Ok(Value::bool(
             !matches!(self.partial_cmp(rhs), Some(Ordering::Equal)),
             span,
@@ -4560,6 +4707,61 @@ mod tests {
         }
     }
 
+    mod total_cmp {
+        use super::*;
+        use std::cmp::Ordering;
+
+        #[test]
+        fn nan_is_after_the_numbers() {
+            let mut values = [
+                Value::test_float(f64::NAN),
+                Value::test_int(3),
+                Value::test_float(f64::NAN),
+                Value::test_float(1.5),
+            ];
+            values.sort_by(Value::total_cmp);
+            assert_eq!(values[0], Value::test_float(1.5));
+            assert_eq!(values[1], Value::test_int(3));
+            assert_eq!(
+                values[2].total_cmp(&values[3]),
+                Ordering::Equal,
+                "NaN is equal to itself"
+            );
+        }
+
+        #[test]
+        fn nested_values_have_an_order() {
+            let nan = Value::test_list(vec![Value::test_float(f64::NAN)]);
+            let one = Value::test_list(vec![Value::test_float(1.0)]);
+            assert_eq!(nan.partial_cmp(&one), None);
+            assert_eq!(nan.total_cmp(&one), Ordering::Greater);
+            assert_eq!(one.total_cmp(&nan), Ordering::Less);
+
+            let lhs = Value::test_record(record! {"a" => Value::test_int(1), "b" => nan });
+            let rhs = Value::test_record(record! {"b" => one, "a" => Value::test_int(1)});
+            assert_eq!(lhs.total_cmp(&rhs), Ordering::Greater);
+        }
+
+        #[test]
+        fn types_are_in_order() {
+            let values = [
+                Value::test_bool(true),
+                Value::test_int(1),
+                Value::test_string("a"),
+                Value::test_filesize(1),
+                Value::test_duration(1),
+                Value::test_record(Record::new()),
+                Value::test_list(vec![]),
+                Value::test_binary(vec![]),
+                Value::test_nothing(),
+            ];
+            for pair in values.windows(2) {
+                assert_eq!(pair[0].total_cmp(&pair[1]), Ordering::Less);
+                assert_eq!(pair[1].total_cmp(&pair[0]), Ordering::Greater);
+            }
+        }
+    }
+
     mod into_string {
         use chrono::{DateTime, FixedOffset};
 
@@ -0,0 +1,333 @@
+use crate::{
+    Config, CustomValue, PathDisplay, ShellError, Span, Value,
+    ast::{Comparison, Math, Operator},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+/// A filesystem path, kept exactly as the operating system returned it.
+///
+/// Paths are usually strings, which lose the segments of a path that aren't valid UTF-8. A path
+/// value keeps them, so it can be passed to `path` subcommands, other commands taking paths and
+/// external commands without changing which file it refers to. It's created with `into path`, is
+/// shown in the [`PathDisplay`] style of the config, and is converted to a string lossily.
+///
+/// Paths can be joined with `/`, and compare with other paths and with strings on either side.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PathValue {
+    pub path: PathBuf,
+}
+
+impl PathValue {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn into_value(self, span: Span) -> Value {
+        Value::custom(Box::new(self), span)
+    }
+
+    /// A string for a path which is valid UTF-8, and otherwise a path value, so the path is kept
+    /// exactly while most paths stay strings.
+    pub fn string_or_path(path: impl Into<PathBuf>, span: Span) -> Value {
+        match path.into().into_os_string().into_string() {
+            Ok(string) => Value::string(string, span),
+            Err(path) => PathValue::new(path).into_value(span),
+        }
+    }
+
+    /// The path in `value`, if it's a path value.
+    pub fn try_from_value(value: &Value) -> Option<&Path> {
+        match value {
+            Value::Custom { val, .. } => val
+                .as_any()
+                .downcast_ref::<PathValue>()
+                .map(|val| val.path.as_path()),
+            _ => None,
+        }
+    }
+
+    /// The path as it's shown in `style`.
+    pub fn display(&self, style: PathDisplay) -> Cow<'_, str> {
+        match (style, self.path.to_str()) {
+            (_, Some(path)) => Cow::Borrowed(path),
+            (PathDisplay::Lossy, None) => self.path.to_string_lossy(),
+            (PathDisplay::Escaped, None) => Cow::Owned(escape_os_str(self.path.as_os_str())),
+        }
+    }
+
+    /// The base value of `custom` to show it with `config`, which is a string in the
+    /// [`PathDisplay`] style of the config for a path value.
+    pub fn display_base_value(
+        custom: &dyn CustomValue,
+        span: Span,
+        config: &Config,
+    ) -> Result<Value, ShellError> {
+        match custom.as_any().downcast_ref::<PathValue>() {
+            Some(path) => Ok(Value::string(path.display(config.path_display), span)),
+            None => custom.to_base_value(span),
+        }
+    }
+
+    fn other_path(other: &Value) -> Option<&Path> {
+        match other {
+            Value::String { val, .. } => Some(Path::new(val)),
+            other => PathValue::try_from_value(other),
+        }
+    }
+}
+
+/// `s` with the bytes which aren't valid UTF-8 escaped like `\xE9`.
+#[cfg(unix)]
+fn escape_os_str(s: &OsStr) -> String {
+    use std::{fmt::Write, os::unix::ffi::OsStrExt};
+
+    let mut escaped = String::new();
+    for chunk in s.as_bytes().utf8_chunks() {
+        escaped.push_str(chunk.valid());
+        for byte in chunk.invalid() {
+            let _ = write!(escaped, "\\x{byte:02X}");
+        }
+    }
+    escaped
+}
+
+/// `s` with the unpaired surrogates escaped like `\u{D800}`.
+#[cfg(windows)]
+fn escape_os_str(s: &OsStr) -> String {
+    use std::{fmt::Write, os::windows::ffi::OsStrExt};
+
+    let mut escaped = String::new();
+    for char in char::decode_utf16(s.encode_wide()) {
+        match char {
+            Ok(char) => escaped.push(char),
+            Err(err) => {
+                let _ = write!(escaped, "\\u{{{:04X}}}", err.unpaired_surrogate());
+            }
+        }
+    }
+    escaped
+}
+
+#[cfg(not(any(unix, windows)))]
+fn escape_os_str(s: &OsStr) -> String {
+    s.to_string_lossy().into_owned()
+}
+
+impl CustomValue for PathValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::custom(Box::new(self.clone()), span)
+    }
+
+    fn type_name(&self) -> String {
+        "path".into()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::string(self.path.to_string_lossy(), span))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        PathValue::other_path(other).map(|other| self.path.as_path().cmp(other))
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        let span = lhs_span.merge(right.span());
+        let ordering = CustomValue::partial_cmp(self, right);
+        let result = match (operator, ordering) {
+            (Operator::Math(Math::Divide), _) => PathValue::other_path(right)
+                .map(|other| PathValue::new(self.path.join(other)).into_value(span)),
+            // Paths are never equal to values which aren't paths
+            (Operator::Comparison(Comparison::Equal), ordering) => {
+                Some(Value::bool(ordering == Some(Ordering::Equal), span))
+            }
+            (Operator::Comparison(Comparison::NotEqual), ordering) => {
+                Some(Value::bool(ordering != Some(Ordering::Equal), span))
+            }
+            (Operator::Comparison(comparison), Some(ordering)) => match comparison {
+                Comparison::LessThan => Some(ordering.is_lt()),
+                Comparison::GreaterThan => Some(ordering.is_gt()),
+                Comparison::LessThanOrEqual => Some(ordering.is_le()),
+                Comparison::GreaterThanOrEqual => Some(ordering.is_ge()),
+                _ => None,
+            }
+            .map(|val| Value::bool(val, span)),
+            _ => None,
+        };
+        result.ok_or_else(|| ShellError::OperatorUnsupportedType {
+            op: operator,
+            unsupported: right.get_type(),
+            op_span: op,
+            unsupported_span: right.span(),
+            help: Some("paths can be joined with `/` and compared with paths and strings"),
+        })
+    }
+
+    fn operation_rhs(
+        &self,
+        left: &Value,
+        operator: Operator,
+        op: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        match left {
+            // A string on the left works like a path, so `'/tmp' / $path` is a path too
+            Value::String { val, .. } => PathValue::new(val).operation(
+                left.span(),
+                operator,
+                op,
+                &self.clone().into_value(rhs_span),
+            ),
+            _ => {
+                let span = left.span().merge(rhs_span);
+                match operator {
+                    Operator::Comparison(Comparison::Equal) => Ok(Value::bool(false, span)),
+                    Operator::Comparison(Comparison::NotEqual) => Ok(Value::bool(true, span)),
+                    _ => Err(ShellError::OperatorUnsupportedType {
+                        op: operator,
+                        unsupported: left.get_type(),
+                        op_span: op,
+                        unsupported_span: left.span(),
+                        help: Some(
+                            "paths can be joined with `/` and compared with paths and strings",
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn typetag_name(&self) -> &'static str {
+        "PathValue"
+    }
+
+    fn typetag_deserialize(&self) {
+        unimplemented!("typetag_deserialize")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paths_are_joined_with_slash() {
+        let path = PathValue::new("/tmp");
+        assert_eq!(
+            PathValue::try_from_value(
+                &path
+                    .operation(
+                        Span::test_data(),
+                        Operator::Math(Math::Divide),
+                        Span::test_data(),
+                        &Value::test_string("file.txt"),
+                    )
+                    .unwrap()
+            ),
+            Some(Path::new("/tmp/file.txt"))
+        );
+    }
+
+    #[test]
+    fn paths_compare_with_strings() {
+        let path = PathValue::new("a/b");
+        let compare = |comparison, rhs: &str| {
+            path.operation(
+                Span::test_data(),
+                Operator::Comparison(comparison),
+                Span::test_data(),
+                &Value::test_string(rhs),
+            )
+            .unwrap()
+        };
+        assert_eq!(compare(Comparison::Equal, "a/b"), Value::test_bool(true));
+        assert_eq!(compare(Comparison::LessThan, "a/c"), Value::test_bool(true));
+        assert_eq!(
+            path.operation(
+                Span::test_data(),
+                Operator::Comparison(Comparison::Equal),
+                Span::test_data(),
+                &Value::test_int(1),
+            )
+            .unwrap(),
+            Value::test_bool(false)
+        );
+    }
+
+    #[test]
+    fn strings_work_on_the_left() {
+        let path = PathValue::new("b").into_value(Span::test_data());
+        let joined = Value::test_string("a")
+            .div(Span::test_data(), &path, Span::test_data())
+            .unwrap();
+        assert_eq!(
+            PathValue::try_from_value(&joined),
+            Some(Path::new("a").join("b").as_path())
+        );
+        assert_eq!(
+            Value::test_string("a")
+                .lt(Span::test_data(), &path, Span::test_data())
+                .unwrap(),
+            Value::test_bool(true)
+        );
+    }
+
+    #[test]
+    fn paths_equal_strings_from_both_sides() {
+        let path = PathValue::new("a").into_value(Span::test_data());
+        assert_eq!(path, Value::test_string("a"));
+        assert_eq!(Value::test_string("a"), path);
+        assert_ne!(Value::test_string("b"), path);
+    }
+
+    #[test]
+    fn strings_and_paths_are_ordered_both_ways() {
+        let path = PathValue::new("a").into_value(Span::test_data());
+        let string = Value::test_string("b");
+        assert_eq!(path.total_cmp(&string), Ordering::Less);
+        assert_eq!(string.total_cmp(&path), Ordering::Greater);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_segments_are_kept() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let name = OsStr::from_bytes(b"caf\xe9");
+        let value = PathValue::new(Path::new("/tmp").join(name)).into_value(Span::test_data());
+        let path = PathValue::try_from_value(&value).unwrap();
+        assert_eq!(path.file_name(), Some(name));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_segments_are_shown_in_the_configured_style() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let value = PathValue::new(OsStr::from_bytes(b"caf\xe9/x")).into_value(Span::test_data());
+        let mut config = Config::default();
+        assert_eq!(value.to_expanded_string("", &config), "caf\u{FFFD}/x");
+        config.path_display = PathDisplay::Escaped;
+        assert_eq!(value.to_expanded_string("", &config), r"caf\xE9/x");
+        assert_eq!(PathValue::new("café").display(PathDisplay::Escaped), "café");
+    }
+}
@@ -903,3 +903,51 @@ macro_rules! record {
         $crate::Record::new()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    fn wide_record() -> Record {
+        (0..4)
+            .map(|i| (format!("col_{i}"), Value::test_int(i)))
+            .collect()
+    }
+
+    #[test]
+    fn comparisons_ignore_the_order_of_columns() {
+        let record = Value::test_record(wide_record());
+        let reversed = Value::test_record(wide_record().into_iter().rev().collect());
+        assert_eq!(record, reversed);
+        assert_eq!(record.partial_cmp(&reversed), Some(Ordering::Equal));
+        assert_eq!(record.total_cmp(&reversed), Ordering::Equal);
+
+        // Columns are compared first, then values, then the lengths
+        let greater_column = Value::test_record(record! {"a" => Value::test_int(9)});
+        let greater_value = Value::test_record(record! {"b" => Value::test_int(1)});
+        let smaller = Value::test_record(record! {"b" => Value::test_int(0)});
+        let longer = Value::test_record(record! {
+            "b" => Value::test_int(0),
+            "c" => Value::test_int(0),
+        });
+        for (lesser, greater) in [
+            (&greater_column, &smaller),
+            (&smaller, &greater_value),
+            (&smaller, &longer),
+        ] {
+            assert_eq!(lesser.partial_cmp(greater), Some(Ordering::Less));
+            assert_eq!(lesser.total_cmp(greater), Ordering::Less);
+            assert_eq!(greater.total_cmp(lesser), Ordering::Greater);
+            assert_ne!(lesser, greater);
+        }
+
+        // A copy which is changed isn't equal to the original anymore
+        let mut copy = record.clone();
+        if let Value::Record { val, .. } = &mut copy {
+            *val.to_mut().get_mut("col_0").expect("the column exists") = Value::test_int(5);
+        }
+        assert_ne!(record, copy);
+        assert_eq!(record.total_cmp(&copy), Ordering::Less);
+    }
+}
@@ -8,13 +8,19 @@ use nu_protocol::{
 };
 use std::path::PathBuf;
 
-fn create_virt_file(working_set: &mut StateWorkingSet, name: &str, content: &str) -> VirtualPathId {
+fn create_virt_file(
+    working_set: &mut StateWorkingSet,
+    name: &str,
+    content: &'static str,
+) -> VirtualPathId {
     let sanitized_name = PathBuf::from(name).to_string_lossy().to_string();
-    let file_id = working_set.add_file(sanitized_name.clone(), content.as_bytes());
-
-    working_set.add_virtual_path(sanitized_name, VirtualPath::File(file_id))
+    working_set.add_virtual_path(sanitized_name, VirtualPath::Source(content))
 }
 
+/// Registers the standard library as virtual paths and parses the prelude.
+///
+/// The modules are only added to the engine state and parsed the first time they're used, so
+/// startup only pays for the prelude and the modules it uses.
 pub fn load_standard_library(
     engine_state: &mut nu_protocol::engine::EngineState,
 ) -> Result<(), miette::ErrReport> {
@@ -66,6 +72,9 @@ pub fn load_standard_library(
         ),
         ("mod.nu", "std/clip", include_str!("../std/clip/mod.nu")),
         ("mod.nu", "std/random", include_str!("../std/random/mod.nu")),
+        ("mod.nu", "std/cli", include_str!("../std/cli/mod.nu")),
+        ("mod.nu", "std/kube", include_str!("../std/kube/mod.nu")),
+        ("mod.nu", "std/task", include_str!("../std/task/mod.nu")),
     ];
 
     for (filename, std_subdir_name, content) in std_submodules.drain(..) {
@@ -39,10 +39,34 @@ pub struct ForegroundChild {
     // this is unix-only since we don't have to deal with process groups in windows
     #[cfg(unix)]
     interactive: bool,
+
+    // windows doesn't have process groups, so descendants are tracked with a job object instead
+    #[cfg(windows)]
+    job: Option<Arc<job_object::JobObject>>,
 }
 
 impl ForegroundChild {
-    #[cfg(not(unix))]
+    /// The child is started suspended, so it can't start other processes before it's in the job
+    /// object. `Command` doesn't expose the creation flags set on it, so they're passed as
+    /// `creation_flags` instead, and `CREATE_SUSPENDED` is added to them.
+    #[cfg(windows)]
+    pub fn spawn(mut command: Command, creation_flags: u32) -> io::Result<Self> {
+        use std::os::windows::process::CommandExt;
+
+        command
+            .creation_flags(creation_flags | windows::Win32::System::Threading::CREATE_SUSPENDED.0);
+        let mut child = command.spawn()?;
+        // Nushell still works with the child if it can't be put into a job object, only
+        // interrupting it doesn't kill its descendants then.
+        let job = job_object::JobObject::assign(&child).ok().map(Arc::new);
+        if let Err(err) = job_object::resume(&child) {
+            let _ = child.kill();
+            return Err(err);
+        }
+        Ok(Self { inner: child, job })
+    }
+
+    #[cfg(not(any(unix, windows)))]
     pub fn spawn(mut command: Command) -> io::Result<Self> {
         command.spawn().map(|child| Self { inner: child })
     }
@@ -109,6 +133,79 @@ impl ForegroundChild {
     pub fn pid(&self) -> u32 {
         self.inner.id()
     }
+
+    /// A handle which interrupts the child from other threads, like the handler for Ctrl-C.
+    pub fn interrupter(&self) -> ChildInterrupter {
+        ChildInterrupter {
+            pid: self.inner.id(),
+            #[cfg(windows)]
+            job: self.job.clone(),
+            #[cfg(windows)]
+            process: job_object::ProcessHandle::open(self.inner.id())
+                .ok()
+                .map(Arc::new),
+        }
+    }
+}
+
+/// How long a child on Windows has to exit after an interrupt, before it's killed.
+#[cfg(windows)]
+const INTERRUPT_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Interrupts a child process and the processes it started, see [`ForegroundChild::interrupter`].
+///
+/// On Unix, this sends `SIGINT` to the process group of the child if it has its own, which
+/// includes the other external commands of the same pipeline. A child in nushell's process group
+/// already got the signal from the terminal with nushell, so it isn't sent again.
+/// On Windows, the child shares nushell's console and already got the console ctrl event. If it
+/// hasn't exited after a grace period, it's killed with its descendants, which are all in the same
+/// job object.
+#[derive(Debug, Clone)]
+pub struct ChildInterrupter {
+    pid: u32,
+    #[cfg(windows)]
+    job: Option<Arc<job_object::JobObject>>,
+    #[cfg(windows)]
+    process: Option<Arc<job_object::ProcessHandle>>,
+}
+
+impl ChildInterrupter {
+    pub fn interrupt(&self) {
+        #[cfg(unix)]
+        {
+            use nix::unistd;
+
+            let pid = Pid::from_raw(self.pid as i32);
+            if let Ok(pgid) = unistd::getpgid(Some(pid))
+                && pgid != unistd::getpgrp()
+            {
+                let _ = signal::killpg(pgid, signal::SIGINT);
+            }
+        }
+        #[cfg(windows)]
+        {
+            let interrupter = self.clone();
+            let _ = std::thread::Builder::new()
+                .name("interrupt child".into())
+                .spawn(move || {
+                    if interrupter
+                        .process
+                        .as_ref()
+                        .is_some_and(|process| process.wait(INTERRUPT_GRACE_PERIOD))
+                    {
+                        return;
+                    }
+                    match &interrupter.job {
+                        Some(job) => job.terminate(),
+                        None => {
+                            let _ = Command::new("taskkill")
+                                .args(["/T", "/F", "/PID", &interrupter.pid.to_string()])
+                                .output();
+                        }
+                    }
+                });
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -429,3 +526,152 @@ mod child_pgroup {
         }
     }
 }
+
+#[cfg(windows)]
+mod job_object {
+    use std::{io, os::windows::io::AsRawHandle, process::Child, time::Duration};
+    use windows::{
+        Win32::{
+            Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0},
+            System::{
+                JobObjects::{
+                    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_BREAKAWAY_OK,
+                    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation,
+                    SetInformationJobObject, TerminateJobObject,
+                },
+                Threading::{OpenProcess, PROCESS_SYNCHRONIZE, WaitForSingleObject},
+            },
+        },
+        core::PCWSTR,
+    };
+
+    /// A job object holding a child process, which the processes it starts join too, so they can
+    /// be terminated together when the child is interrupted.
+    ///
+    /// Closing the job doesn't kill its processes, so the ones a launcher leaves behind on
+    /// purpose, like an editor opened with `code .` or an `ssh-agent`, keep running after the
+    /// child has exited. Processes may also leave the job with `CREATE_BREAKAWAY_FROM_JOB`.
+    #[derive(Debug)]
+    pub struct JobObject(HANDLE);
+
+    // SAFETY: job object handles can be used from any thread.
+    unsafe impl Send for JobObject {}
+    unsafe impl Sync for JobObject {}
+
+    impl JobObject {
+        pub fn assign(child: &Child) -> io::Result<Self> {
+            // SAFETY: this creates an unnamed job object with the default security attributes.
+            let job = JobObject(unsafe { CreateJobObjectW(None, PCWSTR::null()) }?);
+            let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+            limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_BREAKAWAY_OK;
+            // SAFETY: the information matches the information class and outlives the call.
+            unsafe {
+                SetInformationJobObject(
+                    job.0,
+                    JobObjectExtendedLimitInformation,
+                    (&raw const limits).cast(),
+                    size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                )
+            }?;
+            // SAFETY: both handles are valid for the duration of the call.
+            unsafe { AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle())) }?;
+            Ok(job)
+        }
+
+        pub fn terminate(&self) {
+            // SAFETY: the handle is valid until the job object is dropped.
+            let _ = unsafe { TerminateJobObject(self.0, 1) };
+        }
+    }
+
+    impl Drop for JobObject {
+        fn drop(&mut self) {
+            // SAFETY: the handle is owned by the job object and closed only once.
+            let _ = unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    /// Resume a child which was spawned with `CREATE_SUSPENDED`.
+    pub fn resume(child: &Child) -> io::Result<()> {
+        // SAFETY: the handle of the child is valid while it's borrowed.
+        let status = unsafe { ntapi::ntpsapi::NtResumeProcess(child.as_raw_handle().cast()) };
+        if status < 0 {
+            Err(io::Error::other(format!(
+                "could not resume the process: NTSTATUS {status:#x}"
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// A handle to wait for a process with, which stays valid after the process exited, unlike
+    /// its pid.
+    #[derive(Debug)]
+    pub struct ProcessHandle(HANDLE);
+
+    // SAFETY: process handles can be used from any thread.
+    unsafe impl Send for ProcessHandle {}
+    unsafe impl Sync for ProcessHandle {}
+
+    impl ProcessHandle {
+        pub fn open(pid: u32) -> io::Result<Self> {
+            // SAFETY: this only opens a new handle, which is closed on drop.
+            Ok(ProcessHandle(unsafe {
+                OpenProcess(PROCESS_SYNCHRONIZE, false, pid)
+            }?))
+        }
+
+        /// Wait for the process to exit for up to `timeout`, and return whether it did.
+        pub fn wait(&self, timeout: Duration) -> bool {
+            let millis = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+            // SAFETY: the handle is valid until the process handle is dropped.
+            unsafe { WaitForSingleObject(self.0, millis) == WAIT_OBJECT_0 }
+        }
+    }
+
+    impl Drop for ProcessHandle {
+        fn drop(&mut self) {
+            // SAFETY: the handle is owned by the process handle and closed only once.
+            let _ = unsafe { CloseHandle(self.0) };
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::{thread, time::Duration};
+
+    fn sleep_command() -> Command {
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        command
+    }
+
+    fn exited_by_sigint(child: &mut ForegroundChild) -> bool {
+        thread::sleep(Duration::from_millis(200));
+        let status = child.as_mut().try_wait().expect("child should be waitable");
+        let _ = child.as_mut().kill();
+        let _ = child.as_mut().wait();
+        status.and_then(|status| std::os::unix::process::ExitStatusExt::signal(&status))
+            == Some(signal::SIGINT as i32)
+    }
+
+    #[test]
+    fn interrupt_is_forwarded_to_own_process_group() {
+        let state = Arc::new((AtomicU32::new(0), AtomicU32::new(0)));
+        let mut child = ForegroundChild::spawn(sleep_command(), false, true, &state)
+            .expect("sleep should spawn");
+        child.interrupter().interrupt();
+        assert!(exited_by_sigint(&mut child));
+    }
+
+    #[test]
+    fn interrupt_is_not_sent_again_to_nushells_process_group() {
+        let state = Arc::new((AtomicU32::new(0), AtomicU32::new(0)));
+        let mut child = ForegroundChild::spawn(sleep_command(), false, false, &state)
+            .expect("sleep should spawn");
+        child.interrupter().interrupt();
+        assert!(!exited_by_sigint(&mut child));
+    }
+}
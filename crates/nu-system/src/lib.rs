@@ -19,7 +19,7 @@ pub use self::exit_status::ExitStatus;
 #[cfg(unix)]
 pub use self::foreground::stdin_fd;
 pub use self::foreground::{
-    ForegroundChild, ForegroundGuard, ForegroundWaitStatus, UnfreezeHandle,
+    ChildInterrupter, ForegroundChild, ForegroundGuard, ForegroundWaitStatus, UnfreezeHandle,
 };
 
 pub use self::util::*;
@@ -1,6 +1,5 @@
 use nu_ansi_term::Style;
-use nu_color_config::StyleComputer;
-use nu_protocol::{Config, Value};
+use nu_protocol::{ShellError, Value};
 use nu_utils::SharedCow;
 
 use crate::{
@@ -17,7 +16,7 @@ impl CollapsedTable {
 }
 
 fn collapsed_table(mut value: Value, opts: TableOpts<'_>) -> StringResult {
-    colorize_value(&mut value, opts.config, &opts.style_computer);
+    colorize_value(&mut value, &opts)?;
 
     let mut table = UnstructuredTable::new(value, opts.config);
 
@@ -32,12 +31,14 @@ fn collapsed_table(mut value: Value, opts: TableOpts<'_>) -> StringResult {
     Ok(Some(table))
 }
 
-fn colorize_value(value: &mut Value, config: &Config, style_computer: &StyleComputer) {
+/// Colorize every value, which takes long for huge values, so it can be interrupted.
+fn colorize_value(value: &mut Value, opts: &TableOpts<'_>) -> Result<(), ShellError> {
     // todo: Remove recursion?
 
     match value {
         Value::Record { val, .. } => {
-            let style = get_index_style(style_computer);
+            opts.signals.check(&opts.span)?;
+            let style = get_index_style(&opts.style_computer);
             // Take ownership of the record and reassign to &mut
             // We do this to have owned keys through `.into_iter`
             let record = std::mem::take(val);
@@ -46,26 +47,29 @@ fn colorize_value(value: &mut Value, config: &Config, style_computer: &StyleComp
                     .into_owned()
                     .into_iter()
                     .map(|(mut header, mut val)| {
-                        colorize_value(&mut val, config, style_computer);
+                        colorize_value(&mut val, opts)?;
                         header = colorize_text(&header, style.color_style).unwrap_or(header);
 
-                        (header, val)
+                        Ok((header, val))
                     })
-                    .collect(),
+                    .collect::<Result<_, ShellError>>()?,
             );
         }
         Value::List { vals, .. } => {
+            opts.signals.check(&opts.span)?;
             for val in vals {
-                colorize_value(val, config, style_computer);
+                colorize_value(val, opts)?;
             }
         }
         value => {
-            let (text, style) = nu_value_to_string_clean(value, config, style_computer);
+            let (text, style) = nu_value_to_string_clean(value, opts.config, &opts.style_computer);
             if let Some(text) = colorize_text(&text, style.color_style) {
                 *value = Value::string(text, value.span());
             }
         }
     }
+
+    Ok(())
 }
 
 fn colorize_text(text: &str, color: Option<Style>) -> Option<String> {
@@ -50,6 +50,14 @@ pub mod users {
         Group::from_gid(gid).ok().flatten()
     }
 
+    pub fn get_user_by_name(name: &str) -> Option<User> {
+        User::from_name(name).ok().flatten()
+    }
+
+    pub fn get_group_by_name(name: &str) -> Option<Group> {
+        Group::from_name(name).ok().flatten()
+    }
+
     pub fn get_current_uid() -> Uid {
         Uid::current()
     }
@@ -11,13 +11,14 @@ mod nu_cow;
 mod quoting;
 mod shared_cow;
 mod split_read;
+pub mod startup_profile;
 pub mod strings;
 pub mod utils;
 
-pub use locale::get_system_locale;
+pub use locale::{get_system_locale, group_digits, locale_from_name};
 pub use utils::{
-    ConfigFileKind, enable_vt_processing, get_ls_colors, stderr_write_all_and_flush,
-    stdout_write_all_and_flush, terminal_size,
+    CONFIG_HOSTS_DIR, CONFIG_PROFILES_DIR, ConfigFileKind, enable_vt_processing, get_ls_colors,
+    stderr_write_all_and_flush, stdout_write_all_and_flush, terminal_size,
 };
 
 pub use casing::IgnoreCaseExt;
@@ -1,25 +1,50 @@
-use num_format::Locale;
+use num_format::{Grouping, Locale};
 
 pub const LOCALE_OVERRIDE_ENV_VAR: &str = "NU_TEST_LOCALE_OVERRIDE";
 
 pub fn get_system_locale() -> Locale {
     let locale_string = get_system_locale_string().unwrap_or_else(|| String::from("en-US"));
+    locale_from_name(&locale_string).unwrap_or(Locale::en)
+}
+
+/// The locale with the name `name`, like `de`, `de-CH` or `de_CH.UTF-8`, or the one of its
+/// language if there's none for the region.
+pub fn locale_from_name(name: &str) -> Option<Locale> {
     // Since get_locale() and Locale::from_name() don't always return the same items
     // we need to try and parse it to match. For instance, a valid locale is de_DE
     // however Locale::from_name() wants only de so we split and parse it out.
-    let locale_string = locale_string.replace('_', "-"); // en_AU -> en-AU
-
-    Locale::from_name(&locale_string).unwrap_or_else(|_| {
-        let all = num_format::Locale::available_names();
-        let locale_prefix = &locale_string.split('-').collect::<Vec<&str>>();
-        if all.contains(&locale_prefix[0]) {
-            Locale::from_name(locale_prefix[0]).unwrap_or(Locale::en)
-        } else {
-            Locale::en
-        }
+    let name = name.split('.').next().unwrap_or(name); // de_DE.UTF-8 -> de_DE
+    let name = name.replace('_', "-"); // en_AU -> en-AU
+
+    Locale::from_name(&name).ok().or_else(|| {
+        let language = name.split('-').next()?;
+        Locale::from_name(language).ok()
     })
 }
 
+/// `digits`, which are ASCII digits, with the separators of `locale` between their groups.
+pub fn group_digits(digits: &str, locale: &Locale) -> String {
+    // The last group has 3 digits, and the others too except in the Indian grouping, like in
+    // 12,34,567
+    let size = match locale.grouping() {
+        Grouping::Standard => 3,
+        Grouping::Indian => 2,
+        Grouping::Posix => return digits.to_string(),
+    };
+
+    let mut groups = vec![];
+    let mut end = digits.len();
+    let mut group_size = 3;
+    while end > 0 {
+        let start = end.saturating_sub(group_size);
+        groups.push(&digits[start..end]);
+        end = start;
+        group_size = size;
+    }
+    groups.reverse();
+    groups.join(locale.separator())
+}
+
 #[cfg(debug_assertions)]
 pub fn get_system_locale_string() -> Option<String> {
     std::env::var(LOCALE_OVERRIDE_ENV_VAR).ok().or_else(
@@ -0,0 +1,95 @@
+//! The phases of startup recorded for `nu --profile-startup`.
+//!
+//! Every [`perf!`](crate::perf) call records its phase here while profiling is enabled, so the
+//! breakdown has the same phases as the `info` log, without having to enable logging.
+
+use std::{
+    fmt::Write,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PHASES: Mutex<Vec<(String, Duration)>> = Mutex::new(Vec::new());
+
+/// Start recording the phases of startup.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the phases of startup are being recorded.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Record how long a phase took, if profiling is enabled.
+pub fn record(phase: impl ToString, took: Duration) {
+    if is_enabled()
+        && let Ok(mut phases) = PHASES.lock()
+    {
+        phases.push((phase.to_string(), took));
+    }
+}
+
+/// Stop recording and return the phases recorded so far, in order.
+pub fn finish() -> Vec<(String, Duration)> {
+    ENABLED.store(false, Ordering::Relaxed);
+    PHASES
+        .lock()
+        .map(|mut phases| std::mem::take(&mut *phases))
+        .unwrap_or_default()
+}
+
+/// Render the phases as a table, with the share each phase had of the `total` startup time.
+pub fn render(phases: &[(String, Duration)], total: Duration) -> String {
+    let name_width = phases
+        .iter()
+        .map(|(phase, _)| phase.chars().count())
+        .chain(["total".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = String::new();
+    let mut line = |phase: &str, took: Duration| {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            took.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        let _ = writeln!(
+            table,
+            "{phase:<name_width$}  {:>10.3}ms  {share:>5.1}%",
+            took.as_secs_f64() * 1000.0
+        );
+    };
+
+    for (phase, took) in phases {
+        line(phase, *took);
+    }
+    let measured: Duration = phases.iter().map(|(_, took)| *took).sum();
+    line("other", total.saturating_sub(measured));
+    line("total", total);
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_adds_other_and_total() {
+        let phases = vec![
+            ("load std-lib".to_string(), Duration::from_millis(3)),
+            ("read config.nu".to_string(), Duration::from_millis(5)),
+        ];
+        let table = render(&phases, Duration::from_millis(10));
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "load std-lib         3.000ms   30.0%");
+        assert_eq!(lines[2], "other                2.000ms   20.0%");
+        assert_eq!(lines[3], "total               10.000ms  100.0%");
+    }
+}
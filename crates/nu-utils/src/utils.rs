@@ -87,6 +87,15 @@ where
     }
 }
 
+/// Directory inside the nushell config directory which holds the named config profiles.
+///
+/// Each profile is a directory of its own which may contain an `env.nu` and a `config.nu`.
+pub const CONFIG_PROFILES_DIR: &str = "profiles";
+
+/// Directory inside the nushell config directory which holds host-specific config layers,
+/// one directory per host name, laid out like a profile.
+pub const CONFIG_HOSTS_DIR: &str = "hosts";
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ConfigFileKind {
     Config,
@@ -490,30 +499,40 @@ pub fn get_ls_colors(lscolors_env_string: Option<String>) -> LsColors {
     }
 }
 
-// Log some performance metrics (green text with yellow timings)
+// Log some performance metrics (green text with yellow timings), and record them for
+// `nu --profile-startup`. The message is only built while profiling or logging at info level, as
+// `perf!` is also used on the hot paths of the REPL.
 #[macro_export]
 macro_rules! perf {
-    ($msg:expr, $dur:expr, $use_color:expr) => {
-        if $use_color {
-            log::info!(
-                "perf: {}:{}:{} \x1b[32m{}\x1b[0m took \x1b[33m{:?}\x1b[0m",
-                file!(),
-                line!(),
-                column!(),
-                $msg,
-                $dur.elapsed(),
-            );
-        } else {
-            log::info!(
-                "perf: {}:{}:{} {} took {:?}",
-                file!(),
-                line!(),
-                column!(),
-                $msg,
-                $dur.elapsed(),
-            );
+    ($msg:expr, $dur:expr, $use_color:expr) => {{
+        let profiling = $crate::startup_profile::is_enabled();
+        if profiling || log::log_enabled!(log::Level::Info) {
+            let msg = $msg;
+            let elapsed = $dur.elapsed();
+            if profiling {
+                $crate::startup_profile::record(&msg, elapsed);
+            }
+            if $use_color {
+                log::info!(
+                    "perf: {}:{}:{} \x1b[32m{}\x1b[0m took \x1b[33m{:?}\x1b[0m",
+                    file!(),
+                    line!(),
+                    column!(),
+                    msg,
+                    elapsed,
+                );
+            } else {
+                log::info!(
+                    "perf: {}:{}:{} {} took {:?}",
+                    file!(),
+                    line!(),
+                    column!(),
+                    msg,
+                    elapsed,
+                );
+            }
         }
-    };
+    }};
 }
 
 /// Returns the terminal size (columns, rows).
@@ -157,6 +157,31 @@ impl CustomValue for CoolCustomValue {
         }
     }
 
+    fn operation_rhs(
+        &self,
+        left: &Value,
+        operator: ast::Operator,
+        op_span: Span,
+        rhs_span: Span,
+    ) -> Result<Value, ShellError> {
+        match (operator, left) {
+            // Prepend a string to the string inside `cool`
+            (Operator::Math(Math::Concatenate), Value::String { val, .. }) => Ok(Value::custom(
+                Box::new(CoolCustomValue {
+                    cool: format!("{val}{}", self.cool),
+                }),
+                op_span,
+            )),
+            _ => Err(ShellError::OperatorUnsupportedType {
+                op: operator,
+                unsupported: left.get_type(),
+                op_span,
+                unsupported_span: left.span().merge(rhs_span),
+                help: None,
+            }),
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
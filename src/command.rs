@@ -30,7 +30,7 @@ pub(crate) fn gather_commandline_args() -> (Vec<String>, String, Vec<String>) {
 
         let flag_value = match arg.as_ref() {
             "--commands" | "-c" | "--table-mode" | "-m" | "--error-style" | "-e" | "--execute"
-            | "--config" | "--env-config" | "-I" | "ide-ast" => {
+            | "--config" | "--env-config" | "--profile" | "--kernel" | "-I" | "ide-ast" => {
                 args.next().map(|a| escape_quote_string(&a))
             }
             #[cfg(feature = "plugin")]
@@ -109,8 +109,12 @@ pub(crate) fn parse_commandline_args(
         let no_config_file = call.get_named_arg("no-config-file");
         let no_history = call.get_named_arg("no-history");
         let no_std_lib = call.get_named_arg("no-std-lib");
+        let profile_startup = call.get_named_arg("profile-startup");
+        let sandbox = call.get_named_arg("sandbox");
+        let daemon = call.get_named_arg("daemon");
         let config_file = call.get_flag_expr("config");
         let env_file = call.get_flag_expr("env-config");
+        let profile = call.get_flag_expr("profile");
         let log_level = call.get_flag_expr("log-level");
         let log_target = call.get_flag_expr("log-target");
         let log_include = call.get_flag_expr("log-include");
@@ -123,6 +127,8 @@ pub(crate) fn parse_commandline_args(
 
         // ide flags
         let lsp = call.has_flag(engine_state, &mut stack, "lsp")?;
+        let kernel = call.get_flag_expr("kernel");
+        let serve_json = call.has_flag(engine_state, &mut stack, "serve-json")?;
         let include_path = call.get_flag_expr("include-path");
         let ide_goto_def: Option<Value> =
             call.get_flag(engine_state, &mut stack, "ide-goto-def")?;
@@ -211,6 +217,8 @@ pub(crate) fn parse_commandline_args(
         let plugins = extract_list(plugins, "path", |expr| expr.as_filepath().map(|t| t.0))?;
         let config_file = extract_path(config_file)?;
         let env_file = extract_path(env_file)?;
+        let kernel = extract_path(kernel)?;
+        let profile = extract_contents(profile)?;
         let log_level = extract_contents(log_level)?;
         let log_target = extract_contents(log_target)?;
         let log_include = extract_list(log_include, "string", |expr| expr.as_string())?;
@@ -252,8 +260,12 @@ pub(crate) fn parse_commandline_args(
             no_config_file,
             no_history,
             no_std_lib,
+            profile_startup,
+            sandbox,
+            daemon,
             config_file,
             env_file,
+            profile,
             log_level,
             log_target,
             log_include,
@@ -264,6 +276,8 @@ pub(crate) fn parse_commandline_args(
             ide_hover,
             ide_complete,
             lsp,
+            kernel,
+            serve_json,
             ide_check,
             ide_ast,
             table_mode,
@@ -295,8 +309,12 @@ pub(crate) struct NushellCliArgs {
     pub(crate) no_config_file: Option<Spanned<String>>,
     pub(crate) no_history: Option<Spanned<String>>,
     pub(crate) no_std_lib: Option<Spanned<String>>,
+    pub(crate) profile_startup: Option<Spanned<String>>,
+    pub(crate) sandbox: Option<Spanned<String>>,
+    pub(crate) daemon: Option<Spanned<String>>,
     pub(crate) config_file: Option<Spanned<String>>,
     pub(crate) env_file: Option<Spanned<String>>,
+    pub(crate) profile: Option<Spanned<String>>,
     pub(crate) log_level: Option<Spanned<String>>,
     pub(crate) log_target: Option<Spanned<String>>,
     pub(crate) log_include: Option<Vec<Spanned<String>>>,
@@ -307,6 +325,8 @@ pub(crate) struct NushellCliArgs {
     pub(crate) no_newline: Option<Spanned<String>>,
     pub(crate) include_path: Option<Spanned<String>>,
     pub(crate) lsp: bool,
+    pub(crate) kernel: Option<Spanned<String>>,
+    pub(crate) serve_json: bool,
     pub(crate) ide_goto_def: Option<Value>,
     pub(crate) ide_hover: Option<Value>,
     pub(crate) ide_complete: Option<Value>,
@@ -372,6 +392,21 @@ impl Command for Nu {
                 None,
             )
             .switch("no-std-lib", "start with no standard library", None)
+            .switch(
+                "profile-startup",
+                "print how long each phase of startup took to stderr, before running anything",
+                None,
+            )
+            .switch(
+                "sandbox",
+                "deny writing files, using the network and running processes, except what $env.config.sandbox allows",
+                None,
+            )
+            .switch(
+                "daemon",
+                "keep running after the script or --commands(-c) while jobs added with `schedule add` remain",
+                None,
+            )
             .named(
                 "threads",
                 SyntaxShape::Int,
@@ -391,11 +426,28 @@ impl Command for Nu {
                 "start with an alternate environment config file",
                 None,
             )
+            .named(
+                "profile",
+                SyntaxShape::String,
+                "layer the named config profile on top of the config files, $env.NU_PROFILE by default",
+                None,
+            )
             .switch(
                "lsp",
                "start nu's language server protocol",
                None,
             )
+            .named(
+                "kernel",
+                SyntaxShape::Filepath,
+                "start nu as a Jupyter kernel, with the given connection file; what `print` and externals write goes to the kernel's output instead of the notebook",
+                None,
+            )
+            .switch(
+                "serve-json",
+                "answer requests written as lines of JSON on stdin, like evaluating code",
+                None,
+            )
            .named(
                 "ide-goto-def",
                 SyntaxShape::Int,
@@ -15,7 +15,7 @@ pub(crate) fn add_command_context(engine_state: EngineState) -> EngineState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use nu_protocol::{Category, PositionalArg};
+    use nu_protocol::{Category, PositionalArg, engine::CommandType};
 
     #[test]
     fn arguments_end_period() {
@@ -164,6 +164,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builtins_declare_their_effects() {
+        let ctx = add_command_context(EngineState::new());
+        let decls = ctx.get_decls_sorted(true);
+        let mut failures = Vec::new();
+
+        for (name_bytes, decl_id) in decls {
+            let cmd = ctx.get_decl(decl_id);
+            let cmd_name = String::from_utf8_lossy(&name_bytes);
+            let category = cmd.signature().category;
+
+            if cmd.command_type() == CommandType::Builtin && cmd.effects().is_none() {
+                failures.push(format!("{cmd_name} ({category:?})"));
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "Commands denied by the sandbox as they don't declare their effects:\n{}",
+            failures.join("\n")
+        );
+    }
+
     #[test]
     fn no_search_term_duplicates() {
         let ctx = add_command_context(EngineState::new());
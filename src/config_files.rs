@@ -4,12 +4,12 @@ use nu_cli::read_plugin_file;
 use nu_cli::{eval_config_contents, eval_source};
 use nu_path::canonicalize_with;
 use nu_protocol::{
-    Config, ParseError, PipelineData, Spanned,
+    Config, ParseError, PipelineData, ShellWarning, Spanned,
     engine::{EngineState, Stack, StateWorkingSet},
     eval_const::{get_user_autoload_dirs, get_vendor_autoload_dirs},
-    report_parse_error, report_shell_error,
+    report_parse_error, report_shell_error, report_shell_warning,
 };
-use nu_utils::ConfigFileKind;
+use nu_utils::{CONFIG_HOSTS_DIR, CONFIG_PROFILES_DIR, ConfigFileKind};
 use std::{
     fs,
     fs::File,
@@ -18,6 +18,7 @@ use std::{
     path::Path,
     sync::Arc,
 };
+use sysinfo::System;
 
 const LOGINSHELL_FILE: &str = "login.nu";
 
@@ -27,6 +28,23 @@ pub(crate) fn read_config_file(
     config_file: Option<Spanned<String>>,
     config_kind: ConfigFileKind,
     create_scaffold: bool,
+) {
+    read_base_config_file(
+        engine_state,
+        stack,
+        config_file,
+        config_kind,
+        create_scaffold,
+    );
+    read_config_layers(engine_state, stack, config_kind);
+}
+
+fn read_base_config_file(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    config_file: Option<Spanned<String>>,
+    config_kind: ConfigFileKind,
+    create_scaffold: bool,
 ) {
     warn!("read_config_file() {config_kind:?} at {config_file:?}",);
 
@@ -95,6 +113,63 @@ pub(crate) fn read_config_file(
     }
 }
 
+/// Evaluate the config layers on top of the base config file. Later layers take precedence:
+/// first the active profile, then the layer for the current host name.
+///
+/// The layers come from the config directory, and also apply on top of a base config file given
+/// with `--config` or `--env-config`, since scripts and `-c` only read config files given that
+/// way.
+fn read_config_layers(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    config_kind: ConfigFileKind,
+) {
+    let Some(config_dir) = nu_path::nu_config_dir() else {
+        return;
+    };
+
+    let profile_dir = engine_state
+        .config_profile
+        .as_ref()
+        .map(|profile| config_dir.join(CONFIG_PROFILES_DIR).join(profile));
+    let host_dir = System::host_name().map(|host| config_dir.join(CONFIG_HOSTS_DIR).join(host));
+
+    for layer_dir in profile_dir.into_iter().chain(host_dir) {
+        let layer_path = layer_dir.join(config_kind.path());
+
+        warn!(
+            "read_config_layers() {config_kind:?} at {}",
+            layer_path.display()
+        );
+
+        if layer_path.exists() {
+            eval_config_contents(layer_path.into(), engine_state, stack);
+        }
+    }
+}
+
+/// Warn if the config profile that was asked for doesn't exist, since the shell would otherwise
+/// start with the base config without any hint why the profile didn't apply.
+pub(crate) fn check_config_profile(engine_state: &EngineState) {
+    let Some(profile) = &engine_state.config_profile else {
+        return;
+    };
+    let Some(config_dir) = nu_path::nu_config_dir() else {
+        return;
+    };
+
+    let profile_dir = config_dir.join(CONFIG_PROFILES_DIR).join(profile);
+    if !profile_dir.exists() {
+        report_shell_warning(
+            engine_state,
+            &ShellWarning::MissingConfigProfile {
+                profile: profile.clone(),
+                path: profile_dir.display().to_string(),
+            },
+        );
+    }
+}
+
 pub(crate) fn read_loginshell_file(engine_state: &mut EngineState, stack: &mut Stack) {
     warn!(
         "read_loginshell_file() {}:{}:{}",
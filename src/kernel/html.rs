@@ -0,0 +1,121 @@
+//! HTML tables for the output of cells, which notebooks show instead of the text tables.
+
+use nu_engine::get_columns;
+use nu_protocol::{Config, Value};
+use std::fmt::Write;
+
+/// The HTML of records and lists, or `None` for values which are better shown as text.
+pub(crate) fn render(value: &Value, config: &Config) -> Option<String> {
+    match value {
+        Value::Record { .. } | Value::List { .. } => {
+            let mut html = String::new();
+            write_value(&mut html, value, config);
+            Some(html)
+        }
+        _ => None,
+    }
+}
+
+fn write_value(html: &mut String, value: &Value, config: &Config) {
+    match value {
+        Value::Record { val, .. } => {
+            html.push_str("<table>");
+            for (column, value) in val.iter() {
+                let _ = write!(html, "<tr><th>{}</th><td>", escape(column));
+                write_value(html, value, config);
+                html.push_str("</td></tr>");
+            }
+            html.push_str("</table>");
+        }
+        Value::List { vals, .. } if vals.is_empty() => {}
+        Value::List { vals, .. } => {
+            let columns = get_columns(vals);
+            html.push_str("<table><thead><tr><th>#</th>");
+            if columns.is_empty() {
+                html.push_str("<th></th>");
+            }
+            for column in &columns {
+                let _ = write!(html, "<th>{}</th>", escape(column));
+            }
+            html.push_str("</tr></thead><tbody>");
+            for (index, item) in vals.iter().enumerate() {
+                let _ = write!(html, "<tr><td>{index}</td>");
+                match item {
+                    Value::Record { val, .. } if !columns.is_empty() => {
+                        for column in &columns {
+                            html.push_str("<td>");
+                            if let Some(value) = val.get(column) {
+                                write_value(html, value, config);
+                            }
+                            html.push_str("</td>");
+                        }
+                    }
+                    _ => {
+                        html.push_str("<td>");
+                        write_value(html, item, config);
+                        html.push_str("</td>");
+                    }
+                }
+                html.push_str("</tr>");
+            }
+            html.push_str("</tbody></table>");
+        }
+        Value::Nothing { .. } => {}
+        Value::Error { error, .. } => html.push_str(&escape(&error.to_string())),
+        _ => html.push_str(&escape(&value.to_abbreviated_string(config))),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn table_of_records() {
+        let value = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("<a>"),
+                "size" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("b"),
+            }),
+        ]);
+        assert_eq!(
+            render(&value, &Config::default()).unwrap(),
+            "<table><thead><tr><th>#</th><th>name</th><th>size</th></tr></thead><tbody>\
+             <tr><td>0</td><td>&lt;a&gt;</td><td>1</td></tr>\
+             <tr><td>1</td><td>b</td><td></td></tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn scalars_are_text() {
+        assert_eq!(render(&Value::test_int(3), &Config::default()), None);
+        assert_eq!(
+            render(
+                &Value::test_list(vec![Value::test_int(3)]),
+                &Config::default()
+            )
+            .unwrap(),
+            "<table><thead><tr><th>#</th><th></th></tr></thead><tbody>\
+             <tr><td>0</td><td>3</td></tr></tbody></table>"
+        );
+    }
+}
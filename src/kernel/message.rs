@@ -0,0 +1,226 @@
+//! Jupyter messages, and the HMAC signatures which authenticate them.
+
+use hmac::{Hmac, Mac};
+use serde_json::{Value as JsonValue, json};
+use sha2::Sha256;
+use std::{
+    io,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const DELIMITER: &[u8] = b"<IDS|MSG>";
+pub(crate) const PROTOCOL_VERSION: &str = "5.3";
+
+/// The key messages are signed with, using HMAC-SHA256.
+pub(crate) struct Key(Option<Hmac<Sha256>>);
+
+impl Key {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        // Messages aren't signed without a key; HMAC takes keys of any other length
+        Self(if key.is_empty() {
+            None
+        } else {
+            Hmac::new_from_slice(key).ok()
+        })
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> Option<Hmac<Sha256>> {
+        let mut mac = self.0.clone()?;
+        for part in parts {
+            mac.update(part);
+        }
+        Some(mac)
+    }
+
+    /// The hex-encoded signature of the parts of a message, which is empty if there's no key.
+    fn sign(&self, parts: &[&[u8]]) -> String {
+        self.mac(parts).map_or_else(String::new, |mac| {
+            mac.finalize()
+                .into_bytes()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect()
+        })
+    }
+
+    fn verify(&self, signature: &[u8], parts: &[&[u8]]) -> bool {
+        let Some(mac) = self.mac(parts) else {
+            return signature.is_empty();
+        };
+        let signature: Option<Vec<u8>> = signature
+            .chunks(2)
+            .map(|hex| {
+                std::str::from_utf8(hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect();
+        // `verify_slice` compares in constant time, so the signature can't be guessed byte by byte
+        signature.is_some_and(|signature| mac.verify_slice(&signature).is_ok())
+    }
+}
+
+/// What identifies the kernel in the headers of the messages it sends.
+pub(crate) struct Session {
+    id: String,
+    count: AtomicU64,
+}
+
+impl Session {
+    pub(crate) fn new() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|time| time.as_nanos())
+            .unwrap_or_default();
+        Self {
+            id: format!("{:08x}-{nanos:x}", std::process::id()),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn header(&self, msg_type: &str) -> JsonValue {
+        let count = self.count.fetch_add(1, Ordering::Relaxed);
+        let date = time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default();
+        json!({
+            "msg_id": format!("{}_{count}", self.id),
+            "session": self.id,
+            "username": "kernel",
+            "date": date,
+            "msg_type": msg_type,
+            "version": PROTOCOL_VERSION,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Message {
+    /// The frames before the delimiter, which route the message on ROUTER sockets and are the
+    /// topic on PUB sockets.
+    pub(crate) identities: Vec<Vec<u8>>,
+    pub(crate) header: JsonValue,
+    pub(crate) parent_header: JsonValue,
+    pub(crate) metadata: JsonValue,
+    pub(crate) content: JsonValue,
+}
+
+impl Message {
+    /// Read a message from its frames, if it's signed with the key.
+    pub(crate) fn from_frames(frames: Vec<Vec<u8>>, key: &Key) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        let delimiter = frames
+            .iter()
+            .position(|frame| frame == DELIMITER)
+            .ok_or_else(|| invalid("the message has no delimiter"))?;
+        let mut frames = frames.into_iter();
+        let identities = frames.by_ref().take(delimiter).collect();
+        let mut parts = frames.skip(1);
+        let (Some(signature), Some(header), Some(parent_header), Some(metadata), Some(content)) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(invalid("the message doesn't have all its parts"));
+        };
+
+        if !key.verify(&signature, &[&header, &parent_header, &metadata, &content]) {
+            return Err(invalid("the signature of the message is wrong"));
+        }
+
+        let parse = |part: &[u8]| {
+            serde_json::from_slice::<JsonValue>(part).map_err(|err| invalid(&err.to_string()))
+        };
+        Ok(Self {
+            identities,
+            header: parse(&header)?,
+            parent_header: parse(&parent_header)?,
+            metadata: parse(&metadata)?,
+            content: parse(&content)?,
+        })
+    }
+
+    pub(crate) fn to_frames(&self, key: &Key) -> Vec<Vec<u8>> {
+        let header = self.header.to_string().into_bytes();
+        let parent_header = self.parent_header.to_string().into_bytes();
+        let metadata = self.metadata.to_string().into_bytes();
+        let content = self.content.to_string().into_bytes();
+        let signature = key.sign(&[&header, &parent_header, &metadata, &content]);
+
+        let mut frames = self.identities.clone();
+        frames.push(DELIMITER.to_vec());
+        frames.push(signature.into_bytes());
+        frames.extend([header, parent_header, metadata, content]);
+        frames
+    }
+
+    pub(crate) fn msg_type(&self) -> &str {
+        self.header["msg_type"].as_str().unwrap_or_default()
+    }
+
+    /// A message sent because of this one, to the same peer.
+    pub(crate) fn reply(&self, session: &Session, msg_type: &str, content: JsonValue) -> Self {
+        Self {
+            identities: self.identities.clone(),
+            header: session.header(msg_type),
+            parent_header: self.header.clone(),
+            metadata: json!({}),
+            content,
+        }
+    }
+
+    /// A message published on IOPub because of this one.
+    pub(crate) fn broadcast(&self, session: &Session, msg_type: &str, content: JsonValue) -> Self {
+        Self {
+            identities: vec![format!("kernel.{}.{msg_type}", session.id).into_bytes()],
+            ..self.reply(session, msg_type, content)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_sha256() {
+        // The second test case of RFC 4231
+        let key = Key::new(b"Jefe");
+        assert_eq!(
+            key.sign(&[b"what do ya ", b"want for nothing?"]),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(Key::new(b"").sign(&[b"anything"]), "");
+
+        let signature = key.sign(&[b"message"]);
+        assert!(key.verify(signature.as_bytes(), &[b"message"]));
+        assert!(!key.verify(signature.as_bytes(), &[b"other message"]));
+        assert!(!key.verify(&signature.as_bytes()[1..], &[b"message"]));
+    }
+
+    #[test]
+    fn signed_round_trip() {
+        let key = Key::new(b"secret");
+        let session = Session::new();
+        let request = Message {
+            identities: vec![b"client".to_vec()],
+            header: session.header("execute_request"),
+            parent_header: json!({}),
+            metadata: json!({}),
+            content: json!({"code": "1 + 1"}),
+        };
+        let reply = request.reply(&session, "execute_reply", json!({"status": "ok"}));
+
+        let frames = reply.to_frames(&key);
+        let read = Message::from_frames(frames.clone(), &key).unwrap();
+        assert_eq!(read.identities, vec![b"client".to_vec()]);
+        assert_eq!(read.msg_type(), "execute_reply");
+        assert_eq!(read.parent_header, request.header);
+        assert_eq!(read.content, json!({"status": "ok"}));
+
+        assert!(Message::from_frames(frames, &Key::new(b"other")).is_err());
+    }
+}
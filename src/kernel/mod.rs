@@ -0,0 +1,527 @@
+//! Running nu as a Jupyter kernel, so notebooks can use it.
+//!
+//! Jupyter starts `nu --kernel <connection file>` with a kernel spec like this one, saved as
+//! `kernel.json` in a `nushell` directory of the `kernels` directory of Jupyter:
+//!
+//! ```json
+//! {
+//!   "argv": ["nu", "--kernel", "{connection_file}"],
+//!   "display_name": "Nushell",
+//!   "language": "nushell",
+//!   "interrupt_mode": "signal"
+//! }
+//! ```
+//!
+//! The cells are evaluated one after the other with the same engine state and stack, like the
+//! lines of the REPL. The value of a cell is shown as a table, and also as HTML when it's a record
+//! or a list. What commands print themselves, like `print` or the externals which don't end a
+//! cell, goes to the output of the kernel process and not to the notebook: it isn't sent to the
+//! frontends as `stream` messages, because nu writes it straight to its stdout and stderr. Cells
+//! should return what they want to show instead, like `[$a $b]` instead of `print $a; $b`.
+
+mod html;
+mod message;
+mod zmtp;
+
+use crate::snippet::eval_snippet;
+use message::{Key, Message, PROTOCOL_VERSION, Session};
+use nu_cli::{NuCompleter, NuValidator};
+use nu_protocol::{
+    Config, PipelineData, ShellError, Signals, Spanned, UseAnsiColoring, Value, ast,
+    engine::{EngineState, Stack, StateWorkingSet},
+    format_cli_error,
+    shell_error::io::IoError,
+};
+use reedline::{Completer, ValidationResult, Validator};
+use serde_json::{Value as JsonValue, json};
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+use zmtp::SocketType;
+
+/// The socket of a frontend, which replies are written to.
+type Peer = Arc<Mutex<TcpStream>>;
+
+/// A message of a frontend on the shell or control socket.
+struct Request {
+    message: Message,
+    peer: Peer,
+}
+
+/// The addresses and the key of the connection file Jupyter writes for the kernel.
+struct Connection {
+    ip: String,
+    key: Key,
+    shell_port: u16,
+    iopub_port: u16,
+    stdin_port: u16,
+    control_port: u16,
+    hb_port: u16,
+}
+
+impl Connection {
+    fn read(path: &Spanned<String>) -> Result<Self, ShellError> {
+        let text = std::fs::read_to_string(&path.item)
+            .map_err(|err| IoError::new(err, path.span, PathBuf::from(&path.item)))?;
+        let invalid = |msg: String| ShellError::GenericError {
+            error: "Invalid Jupyter connection file".into(),
+            msg,
+            span: Some(path.span),
+            help: None,
+            inner: vec![],
+        };
+
+        let json: JsonValue =
+            serde_json::from_str(&text).map_err(|err| invalid(err.to_string()))?;
+        if json["transport"] != "tcp" {
+            return Err(invalid(format!(
+                "the {} transport isn't supported",
+                json["transport"]
+            )));
+        }
+        let key = json["key"].as_str().unwrap_or_default();
+        if !key.is_empty() && json["signature_scheme"] != "hmac-sha256" {
+            return Err(invalid(format!(
+                "the {} signature scheme isn't supported",
+                json["signature_scheme"]
+            )));
+        }
+        let port = |name: &str| {
+            json[name]
+                .as_u64()
+                .and_then(|port| u16::try_from(port).ok())
+                .ok_or_else(|| invalid(format!("{name} is missing")))
+        };
+
+        Ok(Self {
+            ip: json["ip"].as_str().unwrap_or("127.0.0.1").to_string(),
+            key: Key::new(key.as_bytes()),
+            shell_port: port("shell_port")?,
+            iopub_port: port("iopub_port")?,
+            stdin_port: port("stdin_port")?,
+            control_port: port("control_port")?,
+            hb_port: port("hb_port")?,
+        })
+    }
+}
+
+/// Serve the notebooks of Jupyter until it asks the kernel to shut down.
+pub(crate) fn run_kernel(
+    mut engine_state: EngineState,
+    stack: Stack,
+    connection_file: &Spanned<String>,
+) -> Result<(), ShellError> {
+    let connection = Connection::read(connection_file)?;
+    let ip = connection.ip.as_str();
+    let key = Arc::new(connection.key);
+    let session = Arc::new(Session::new());
+
+    // Notebooks show the tables as HTML, and the text ones shouldn't have escape codes
+    let mut config = Config::clone(engine_state.get_config());
+    config.use_ansi_coloring = UseAnsiColoring::False;
+    engine_state.set_config(config);
+
+    serve(ip, connection.hb_port, SocketType::Rep, |mut stream| {
+        loop {
+            let message = zmtp::read_message(&mut stream)?;
+            zmtp::write_message(&mut stream, &message)?;
+        }
+    })?;
+
+    let iopub = Arc::new(Mutex::new(Vec::new()));
+    let subscribers = iopub.clone();
+    serve(
+        ip,
+        connection.iopub_port,
+        SocketType::Pub,
+        move |mut stream| {
+            subscribers
+                .lock()
+                .expect("the lock of the Jupyter subscribers is poisoned")
+                .push(stream.try_clone()?);
+            // Frontends subscribe to every message, so the subscriptions are skipped
+            loop {
+                zmtp::read_message(&mut stream)?;
+            }
+        },
+    )?;
+
+    // Input requests aren't supported, but frontends connect to the stdin socket anyway
+    serve(
+        ip,
+        connection.stdin_port,
+        SocketType::Router,
+        |mut stream| {
+            loop {
+                zmtp::read_message(&mut stream)?;
+            }
+        },
+    )?;
+
+    let (sender, receiver) = mpsc::channel();
+    for (port, signals) in [
+        (connection.shell_port, None),
+        (
+            connection.control_port,
+            Some(engine_state.signals().clone()),
+        ),
+    ] {
+        let (key, session, sender) = (key.clone(), session.clone(), sender.clone());
+        serve(ip, port, SocketType::Router, move |stream| {
+            read_requests(stream, &key, &session, &sender, signals.as_ref())
+        })?;
+    }
+    drop(sender);
+
+    let mut kernel = Kernel {
+        engine_state,
+        stack,
+        key,
+        session,
+        iopub,
+        execution_count: 0,
+    };
+    for request in receiver {
+        if !kernel.handle(request) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// How many peers can connect to each socket at once. Each one has its own thread, and Jupyter
+/// only connects a few frontends to a kernel.
+const MAX_PEERS: usize = 16;
+
+/// Listen on a port, and serve each peer which connects to it in its own thread.
+fn serve(
+    ip: &str,
+    port: u16,
+    socket_type: SocketType,
+    serve_peer: impl Fn(TcpStream) -> io::Result<()> + Send + Sync + 'static,
+) -> Result<(), ShellError> {
+    let listener = TcpListener::bind((ip, port)).map_err(|err| {
+        ShellError::Io(IoError::new_internal(
+            err,
+            format!("Could not listen on {ip}:{port}"),
+            nu_protocol::location!(),
+        ))
+    })?;
+    let serve_peer = Arc::new(serve_peer);
+    let peers = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            if peers.fetch_add(1, Ordering::SeqCst) >= MAX_PEERS {
+                peers.fetch_sub(1, Ordering::SeqCst);
+                log::warn!("Refusing a Jupyter {socket_type:?} peer, {MAX_PEERS} are connected");
+                continue;
+            }
+            let (serve_peer, peers) = (serve_peer.clone(), peers.clone());
+            thread::spawn(move || {
+                let result =
+                    zmtp::handshake(&mut stream, socket_type).and_then(|()| (*serve_peer)(stream));
+                if let Err(err) = result {
+                    log::debug!("A Jupyter {socket_type:?} peer disconnected: {err}");
+                }
+                peers.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Pass the requests of a peer to the kernel, except for interrupts, which can't wait for the
+/// cell that runs.
+fn read_requests(
+    mut stream: TcpStream,
+    key: &Key,
+    session: &Session,
+    sender: &mpsc::Sender<Request>,
+    signals: Option<&Signals>,
+) -> io::Result<()> {
+    let peer = Arc::new(Mutex::new(stream.try_clone()?));
+    loop {
+        let frames = zmtp::read_message(&mut stream)?;
+        let message = match Message::from_frames(frames, key) {
+            Ok(message) => message,
+            Err(err) => {
+                log::warn!("Skipping a Jupyter message: {err}");
+                continue;
+            }
+        };
+
+        match signals {
+            Some(signals) if message.msg_type() == "interrupt_request" => {
+                signals.trigger();
+                let reply = message.reply(session, "interrupt_reply", json!({"status": "ok"}));
+                send(&peer, key, &reply);
+            }
+            _ => {
+                let peer = peer.clone();
+                if sender.send(Request { message, peer }).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn send(peer: &Peer, key: &Key, message: &Message) {
+    let mut stream = peer.lock().expect("the lock of a Jupyter peer is poisoned");
+    if let Err(err) = zmtp::write_message(&mut *stream, &message.to_frames(key)) {
+        log::warn!("Could not send a Jupyter message: {err}");
+    }
+}
+
+/// The error of a cell, as Jupyter shows it.
+struct CellError {
+    ename: String,
+    evalue: String,
+    traceback: Vec<String>,
+}
+
+impl CellError {
+    fn new(
+        working_set: &StateWorkingSet,
+        error: &dyn miette::Diagnostic,
+        default_code: &'static str,
+    ) -> Self {
+        Self {
+            ename: error
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| default_code.into()),
+            evalue: error.to_string(),
+            traceback: format_cli_error(working_set, error, Some(default_code))
+                .lines()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+struct Kernel {
+    engine_state: EngineState,
+    stack: Stack,
+    key: Arc<Key>,
+    session: Arc<Session>,
+    iopub: Arc<Mutex<Vec<TcpStream>>>,
+    execution_count: u64,
+}
+
+impl Kernel {
+    /// Handle a request, and return whether to keep going.
+    fn handle(&mut self, request: Request) -> bool {
+        let Request { message, peer } = request;
+        self.publish(&message, "status", json!({"execution_state": "busy"}));
+
+        let mut shutdown = false;
+        let content = &message.content;
+        let reply = match message.msg_type() {
+            "kernel_info_request" => Some(("kernel_info_reply", kernel_info())),
+            "execute_request" => Some(("execute_reply", self.execute(&message))),
+            "is_complete_request" => Some((
+                "is_complete_reply",
+                self.is_complete(content["code"].as_str().unwrap_or_default()),
+            )),
+            "complete_request" => Some((
+                "complete_reply",
+                self.complete(
+                    content["code"].as_str().unwrap_or_default(),
+                    content["cursor_pos"].as_u64().unwrap_or_default() as usize,
+                ),
+            )),
+            "comm_info_request" => Some(("comm_info_reply", json!({"status": "ok", "comms": {}}))),
+            "shutdown_request" => {
+                shutdown = true;
+                let restart = content["restart"].as_bool().unwrap_or(false);
+                Some((
+                    "shutdown_reply",
+                    json!({"status": "ok", "restart": restart}),
+                ))
+            }
+            msg_type => {
+                log::debug!("Skipping a Jupyter {msg_type}");
+                None
+            }
+        };
+        if let Some((msg_type, content)) = reply {
+            send(
+                &peer,
+                &self.key,
+                &message.reply(&self.session, msg_type, content),
+            );
+        }
+
+        self.publish(&message, "status", json!({"execution_state": "idle"}));
+        !shutdown
+    }
+
+    /// Send a message to every frontend, dropping the ones which disconnected.
+    fn publish(&self, parent: &Message, msg_type: &str, content: JsonValue) {
+        let frames = parent
+            .broadcast(&self.session, msg_type, content)
+            .to_frames(&self.key);
+        self.iopub
+            .lock()
+            .expect("the lock of the Jupyter subscribers is poisoned")
+            .retain(|mut stream| zmtp::write_message(&mut stream, &frames).is_ok());
+    }
+
+    fn execute(&mut self, request: &Message) -> JsonValue {
+        let code = request.content["code"].as_str().unwrap_or_default();
+        let silent = request.content["silent"].as_bool().unwrap_or(false);
+        if request.content["store_history"]
+            .as_bool()
+            .unwrap_or(!silent)
+        {
+            self.execution_count += 1;
+        }
+        let execution_count = self.execution_count;
+        if !silent {
+            self.publish(
+                request,
+                "execute_input",
+                json!({"code": code, "execution_count": execution_count}),
+            );
+        }
+
+        match self.evaluate(code) {
+            Ok(value) => {
+                if !silent && !value.is_nothing() {
+                    let data = self.display(&value);
+                    self.publish(
+                        request,
+                        "execute_result",
+                        json!({"execution_count": execution_count, "data": data, "metadata": {}}),
+                    );
+                }
+                json!({
+                    "status": "ok",
+                    "execution_count": execution_count,
+                    "payload": [],
+                    "user_expressions": {},
+                })
+            }
+            Err(error) => {
+                let mut content = json!({
+                    "ename": error.ename,
+                    "evalue": error.evalue,
+                    "traceback": error.traceback,
+                });
+                if !silent {
+                    self.publish(request, "error", content.clone());
+                }
+                content["status"] = "error".into();
+                content["execution_count"] = execution_count.into();
+                content
+            }
+        }
+    }
+
+    fn evaluate(&mut self, code: &str) -> Result<Value, CellError> {
+        let fname = format!("cell #{}", self.execution_count);
+        eval_snippet(
+            &mut self.engine_state,
+            &mut self.stack,
+            &fname,
+            code,
+            CellError::new,
+        )
+    }
+
+    /// The outputs of a value, by MIME type.
+    fn display(&mut self, value: &Value) -> JsonValue {
+        let config = self.engine_state.get_config().clone();
+        let text = self
+            .table(value)
+            .unwrap_or_else(|_| value.to_expanded_string("\n", &config));
+        let mut data = json!({"text/plain": text.trim_end()});
+        if let Some(html) = html::render(value, &config) {
+            data["text/html"] = html.into();
+        }
+        data
+    }
+
+    fn table(&mut self, value: &Value) -> Result<String, ShellError> {
+        let config = self.engine_state.get_config().clone();
+        let Some(decl_id) = self.engine_state.find_decl(b"table", &[]) else {
+            return Ok(value.to_expanded_string("\n", &config));
+        };
+        let call = ast::Call::new(value.span());
+        self.engine_state
+            .get_decl(decl_id)
+            .run(
+                &self.engine_state,
+                &mut self.stack,
+                &(&call).into(),
+                PipelineData::value(value.clone(), None),
+            )?
+            .collect_string("", &config)
+    }
+
+    fn is_complete(&self, code: &str) -> JsonValue {
+        let validator = NuValidator {
+            engine_state: Arc::new(self.engine_state.clone()),
+        };
+        match validator.validate(code) {
+            ValidationResult::Incomplete => json!({"status": "incomplete", "indent": ""}),
+            ValidationResult::Complete => json!({"status": "complete"}),
+        }
+    }
+
+    /// The completions at a position, which is in characters for Jupyter and in bytes for nu.
+    fn complete(&self, code: &str, cursor_pos: usize) -> JsonValue {
+        let pos = code
+            .char_indices()
+            .nth(cursor_pos)
+            .map_or(code.len(), |(i, _)| i);
+        let mut completer = NuCompleter::new(
+            Arc::new(self.engine_state.clone()),
+            Arc::new(self.stack.clone()),
+        );
+        let suggestions = completer.complete(code, pos);
+
+        let chars = |bytes: usize| code.get(..bytes).unwrap_or(code).chars().count();
+        let (start, end) = suggestions.first().map_or((pos, pos), |suggestion| {
+            (suggestion.span.start, suggestion.span.end)
+        });
+        let matches: Vec<_> = suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.value)
+            .collect();
+        json!({
+            "status": "ok",
+            "matches": matches,
+            "cursor_start": chars(start),
+            "cursor_end": chars(end),
+            "metadata": {},
+        })
+    }
+}
+
+fn kernel_info() -> JsonValue {
+    let version = env!("CARGO_PKG_VERSION");
+    json!({
+        "status": "ok",
+        "protocol_version": PROTOCOL_VERSION,
+        "implementation": "nushell",
+        "implementation_version": version,
+        "language_info": {
+            "name": "nushell",
+            "version": version,
+            "mimetype": "text/x-nushell",
+            "file_extension": ".nu",
+        },
+        "banner": format!("Nushell {version}"),
+        "help_links": [{"text": "The Nushell book", "url": "https://www.nushell.sh/book/"}],
+    })
+}
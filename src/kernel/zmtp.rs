@@ -0,0 +1,210 @@
+//! The parts of ZMTP 3, the ZeroMQ wire protocol, that a Jupyter kernel needs.
+//!
+//! Only the NULL security mechanism is supported, which is what Jupyter uses: messages are
+//! authenticated with their HMAC signature instead.
+
+use std::io::{self, Read, Write};
+
+const MORE: u8 = 0x01;
+const LONG: u8 = 0x02;
+const COMMAND: u8 = 0x04;
+
+/// The largest message a peer can send, so that a wrong size can't make the kernel run out of
+/// memory. Messages hold things like the code of a cell, so this leaves plenty of room.
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// The most frames a message can have. Jupyter messages have a few identities, the delimiter, the
+/// signature, four parts and their buffers, so this is only reached by a broken peer.
+const MAX_MESSAGE_FRAMES: usize = 1024;
+
+/// The type of a socket, which is told to the peer during the handshake.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SocketType {
+    Router,
+    Pub,
+    Rep,
+}
+
+impl SocketType {
+    fn name(self) -> &'static str {
+        match self {
+            SocketType::Router => "ROUTER",
+            SocketType::Pub => "PUB",
+            SocketType::Rep => "REP",
+        }
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Exchange the greeting and the READY commands with a peer which just connected.
+pub(crate) fn handshake(
+    stream: &mut (impl Read + Write),
+    socket_type: SocketType,
+) -> io::Result<()> {
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xff;
+    greeting[9] = 0x7f;
+    greeting[10] = 3;
+    greeting[12..16].copy_from_slice(b"NULL");
+    stream.write_all(&greeting)?;
+
+    let mut ready = vec![5];
+    ready.extend_from_slice(b"READY");
+    let name = b"Socket-Type";
+    let value = socket_type.name().as_bytes();
+    ready.push(name.len() as u8);
+    ready.extend_from_slice(name);
+    ready.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    ready.extend_from_slice(value);
+    write_frame(stream, COMMAND, &ready)?;
+    stream.flush()?;
+
+    let mut peer = [0u8; 64];
+    stream.read_exact(&mut peer)?;
+    if peer[0] != 0xff || peer[9] & 1 != 1 {
+        return Err(invalid_data("the peer doesn't speak ZMTP"));
+    }
+    if peer[10] < 3 {
+        return Err(invalid_data("the peer needs a version of ZMTP before 3.0"));
+    }
+    let mechanism = &peer[12..32];
+    if !mechanism.starts_with(b"NULL") || mechanism[4..].iter().any(|&byte| byte != 0) {
+        return Err(invalid_data(
+            "the peer needs a security mechanism other than NULL",
+        ));
+    }
+
+    let (flags, body) = read_frame(stream, MAX_MESSAGE_SIZE)?;
+    if flags & COMMAND == 0 || !body.starts_with(b"\x05READY") {
+        return Err(invalid_data("the peer didn't send READY"));
+    }
+    Ok(())
+}
+
+fn write_frame(writer: &mut impl Write, flags: u8, body: &[u8]) -> io::Result<()> {
+    if body.len() > u8::MAX as usize {
+        writer.write_all(&[flags | LONG])?;
+        writer.write_all(&(body.len() as u64).to_be_bytes())?;
+    } else {
+        writer.write_all(&[flags, body.len() as u8])?;
+    }
+    writer.write_all(body)
+}
+
+/// Read a frame, which can't be larger than `limit`.
+fn read_frame(reader: &mut impl Read, limit: usize) -> io::Result<(u8, Vec<u8>)> {
+    let mut flags = [0u8];
+    reader.read_exact(&mut flags)?;
+    let flags = flags[0];
+    let size = if flags & LONG != 0 {
+        let mut size = [0u8; 8];
+        reader.read_exact(&mut size)?;
+        u64::from_be_bytes(size)
+    } else {
+        let mut size = [0u8];
+        reader.read_exact(&mut size)?;
+        u64::from(size[0])
+    };
+    let size = usize::try_from(size)
+        .ok()
+        .filter(|&size| size <= limit)
+        .ok_or_else(|| invalid_data("the peer sent a message larger than 64 MiB"))?;
+    let mut body = vec![0u8; size];
+    reader.read_exact(&mut body)?;
+    Ok((flags, body))
+}
+
+/// Read the frames of the next message, skipping commands like the heartbeats of ZMTP 3.1.
+pub(crate) fn read_message(reader: &mut impl Read) -> io::Result<Vec<Vec<u8>>> {
+    let mut frames = vec![];
+    let mut size = 0;
+    loop {
+        let (flags, body) = read_frame(reader, MAX_MESSAGE_SIZE - size)?;
+        if flags & COMMAND != 0 {
+            continue;
+        }
+        if frames.len() == MAX_MESSAGE_FRAMES {
+            return Err(invalid_data("the peer sent a message with too many frames"));
+        }
+        size += body.len();
+        frames.push(body);
+        if flags & MORE == 0 {
+            return Ok(frames);
+        }
+    }
+}
+
+pub(crate) fn write_message(
+    writer: &mut impl Write,
+    frames: &[impl AsRef<[u8]>],
+) -> io::Result<()> {
+    for (i, frame) in frames.iter().enumerate() {
+        let flags = if i + 1 < frames.len() { MORE } else { 0 };
+        write_frame(writer, flags, frame.as_ref())?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn message_round_trip() {
+        let long = vec![b'x'; 300];
+        let frames: Vec<&[u8]> = vec![b"<IDS|MSG>", b"", &long];
+        let mut buffer = vec![];
+        write_message(&mut buffer, &frames).unwrap();
+
+        assert_eq!(&buffer[..2], &[MORE, 9]);
+        assert_eq!(&buffer[11..13], &[MORE, 0]);
+        assert_eq!(buffer[13], LONG);
+
+        let read = read_message(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(read, frames);
+    }
+
+    #[test]
+    fn commands_are_skipped() {
+        let mut buffer = vec![];
+        write_frame(&mut buffer, COMMAND, b"\x04PING\x00\x00").unwrap();
+        write_message(&mut buffer, &[b"hello"]).unwrap();
+
+        let read = read_message(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(read, vec![b"hello".to_vec()]);
+    }
+
+    #[test]
+    fn large_frames_are_rejected() {
+        let mut buffer = vec![LONG];
+        buffer.extend_from_slice(&u64::MAX.to_be_bytes());
+
+        let err = read_message(&mut Cursor::new(buffer)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn large_messages_are_rejected() {
+        let frame = vec![0u8; 1024 * 1024];
+        let frames = vec![frame.as_slice(); 65];
+        let mut buffer = vec![];
+        write_message(&mut buffer, &frames).unwrap();
+
+        let err = read_message(&mut Cursor::new(buffer)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn messages_with_many_frames_are_rejected() {
+        let frames = vec![b""; MAX_MESSAGE_FRAMES + 1];
+        let mut buffer = vec![];
+        write_message(&mut buffer, &frames).unwrap();
+
+        let err = read_message(&mut Cursor::new(buffer)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -3,10 +3,13 @@ mod command_context;
 mod config_files;
 mod experimental_options;
 mod ide;
+mod kernel;
 mod logger;
 mod run;
+mod serve_json;
 #[cfg(not(feature = "mcp"))]
 mod signals;
+mod snippet;
 #[cfg(unix)]
 mod terminal;
 mod test_bins;
@@ -30,7 +33,7 @@ use nu_protocol::{
 };
 use nu_std::load_standard_library;
 use nu_utils::perf;
-use run::{run_commands, run_file, run_repl};
+use run::{StartupOptions, run_commands, run_file, run_repl};
 #[cfg(not(feature = "mcp"))]
 use signals::ctrlc_protection;
 use std::{borrow::Cow, path::PathBuf, str::FromStr, sync::Arc};
@@ -217,11 +220,26 @@ fn main() -> Result<()> {
         || (parsed_nu_cli_args.testbin.is_none()
             && parsed_nu_cli_args.commands.is_none()
             && script_name.is_empty()
-            && !parsed_nu_cli_args.lsp);
+            && !parsed_nu_cli_args.lsp
+            && parsed_nu_cli_args.kernel.is_none()
+            && !parsed_nu_cli_args.serve_json);
 
     engine_state.is_login = parsed_nu_cli_args.login_shell.is_some();
+    engine_state.config_profile = parsed_nu_cli_args
+        .profile
+        .as_ref()
+        .map(|profile| profile.item.clone())
+        .or_else(|| std::env::var("NU_PROFILE").ok())
+        .filter(|profile| !profile.is_empty());
+    if parsed_nu_cli_args.no_config_file.is_none() {
+        config_files::check_config_profile(&engine_state);
+    }
     engine_state.history_enabled = parsed_nu_cli_args.no_history.is_none();
     engine_state.is_lsp = parsed_nu_cli_args.lsp;
+    engine_state.is_json_server = parsed_nu_cli_args.serve_json;
+    if parsed_nu_cli_args.profile_startup.is_some() {
+        nu_utils::startup_profile::enable();
+    }
 
     let use_color = engine_state
         .get_config()
@@ -344,9 +362,11 @@ fn main() -> Result<()> {
         Value::string(env!("CARGO_PKG_VERSION"), Span::unknown()),
     );
 
+    start_time = std::time::Instant::now();
     if parsed_nu_cli_args.no_std_lib.is_none() {
         load_standard_library(&mut engine_state)?;
     }
+    perf!("load std-lib", start_time, use_color);
 
     // IDE commands
     if let Some(ide_goto_def) = parsed_nu_cli_args.ide_goto_def {
@@ -480,6 +500,40 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(connection_file) = parsed_nu_cli_args.kernel.clone() {
+        perf!("kernel starting", start_time, use_color);
+        if parsed_nu_cli_args.no_config_file.is_none() {
+            config_files::setup_config(
+                &mut engine_state,
+                &mut stack,
+                #[cfg(feature = "plugin")]
+                parsed_nu_cli_args.plugin_file,
+                parsed_nu_cli_args.config_file,
+                parsed_nu_cli_args.env_file,
+                false,
+            );
+        }
+        kernel::run_kernel(engine_state, stack, &connection_file)?;
+        return Ok(());
+    }
+
+    if parsed_nu_cli_args.serve_json {
+        perf!("json server starting", start_time, use_color);
+        if parsed_nu_cli_args.no_config_file.is_none() {
+            config_files::setup_config(
+                &mut engine_state,
+                &mut stack,
+                #[cfg(feature = "plugin")]
+                parsed_nu_cli_args.plugin_file,
+                parsed_nu_cli_args.config_file,
+                parsed_nu_cli_args.env_file,
+                false,
+            );
+        }
+        serve_json::serve_json(engine_state, stack)?;
+        return Ok(());
+    }
+
     if parsed_nu_cli_args.lsp {
         perf!("lsp starting", start_time, use_color);
 
@@ -502,10 +556,12 @@ fn main() -> Result<()> {
             &mut engine_state,
             stack,
             parsed_nu_cli_args,
-            use_color,
             &commands,
             input,
-            entire_start_time,
+            StartupOptions {
+                use_color,
+                start_time: entire_start_time,
+            },
         );
 
         cleanup_exit(0, &engine_state, 0);
@@ -514,10 +570,13 @@ fn main() -> Result<()> {
             &mut engine_state,
             stack,
             parsed_nu_cli_args,
-            use_color,
             script_name,
             args_to_script,
             input,
+            StartupOptions {
+                use_color,
+                start_time: entire_start_time,
+            },
         );
 
         cleanup_exit(0, &engine_state, 0);
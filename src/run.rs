@@ -8,22 +8,42 @@ use nu_cli::read_plugin_file;
 use nu_cli::{EvaluateCommandsOpts, evaluate_commands, evaluate_file, evaluate_repl};
 use nu_protocol::{
     PipelineData, Spanned,
-    engine::{EngineState, Stack},
+    engine::{EngineState, Sandbox, Stack},
     report_shell_error,
 };
-use nu_utils::perf;
+use nu_utils::{perf, startup_profile};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How often `--daemon` checks whether scheduled jobs remain or nushell was interrupted.
+const DAEMON_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The options of the startup, which are shared by the ways of running nushell.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StartupOptions {
+    /// Whether the `perf!` logs are colored
+    pub(crate) use_color: bool,
+    /// When nushell started, for `$nu.startup-time` and `--profile-startup`
+    pub(crate) start_time: Instant,
+}
 
 pub(crate) fn run_commands(
     engine_state: &mut EngineState,
     mut stack: Stack,
     parsed_nu_cli_args: command::NushellCliArgs,
-    use_color: bool,
     commands: &Spanned<String>,
     input: PipelineData,
-    entire_start_time: std::time::Instant,
+    startup: StartupOptions,
 ) {
     trace!("run_commands");
 
+    let StartupOptions {
+        use_color,
+        start_time: entire_start_time,
+    } = startup;
+
     let start_time = std::time::Instant::now();
     let create_scaffold = nu_path::nu_config_dir().is_some_and(|p| !p.exists());
 
@@ -84,6 +104,8 @@ pub(crate) fn run_commands(
 
     // Regenerate the $nu constant to contain the startup time and any other potential updates
     engine_state.generate_nu_constant();
+    print_startup_profile(entire_start_time);
+    enter_sandbox(engine_state, parsed_nu_cli_args.sandbox.is_some());
 
     let start_time = std::time::Instant::now();
     let result = evaluate_commands(
@@ -103,19 +125,28 @@ pub(crate) fn run_commands(
         report_shell_error(engine_state, &err);
         std::process::exit(err.exit_code().unwrap_or(0));
     }
+
+    if parsed_nu_cli_args.daemon.is_some() {
+        wait_for_schedules(engine_state);
+    }
 }
 
 pub(crate) fn run_file(
     engine_state: &mut EngineState,
     mut stack: Stack,
     parsed_nu_cli_args: command::NushellCliArgs,
-    use_color: bool,
     script_name: String,
     args_to_script: Vec<String>,
     input: PipelineData,
+    startup: StartupOptions,
 ) {
     trace!("run_file");
 
+    let StartupOptions {
+        use_color,
+        start_time: entire_start_time,
+    } = startup;
+
     // if the --no-config-file(-n) option is NOT passed, load the plugin file,
     // load the default env file or custom (depending on parsed_nu_cli_args.env_file),
     // and maybe a custom config file (depending on parsed_nu_cli_args.config_file)
@@ -158,6 +189,8 @@ pub(crate) fn run_file(
 
     // Regenerate the $nu constant to contain the startup time and any other potential updates
     engine_state.generate_nu_constant();
+    print_startup_profile(entire_start_time);
+    enter_sandbox(engine_state, parsed_nu_cli_args.sandbox.is_some());
 
     let start_time = std::time::Instant::now();
     let result = evaluate_file(
@@ -173,6 +206,10 @@ pub(crate) fn run_file(
         report_shell_error(engine_state, &err);
         std::process::exit(err.exit_code().unwrap_or(0));
     }
+
+    if parsed_nu_cli_args.daemon.is_some() {
+        wait_for_schedules(engine_state);
+    }
 }
 
 pub(crate) fn run_repl(
@@ -203,6 +240,9 @@ pub(crate) fn run_repl(
         .get(engine_state);
     perf!("setup_config", start_time, use_color);
 
+    print_startup_profile(entire_start_time);
+    enter_sandbox(engine_state, parsed_nu_cli_args.sandbox.is_some());
+
     let start_time = std::time::Instant::now();
     let ret_val = evaluate_repl(
         engine_state,
@@ -215,3 +255,37 @@ pub(crate) fn run_repl(
 
     ret_val
 }
+
+/// Restrict everything evaluated after the config files to `$env.config.sandbox` for `--sandbox`.
+fn enter_sandbox(engine_state: &mut EngineState, sandbox: bool) {
+    if sandbox {
+        let sandbox = Sandbox::from(&engine_state.get_config().sandbox);
+        engine_state.sandbox = Some(Arc::new(sandbox));
+    }
+}
+
+/// Wait for the scheduled jobs to end for `--daemon`, so they keep running after the commands or
+/// the script. Ctrl-C stops waiting, and the jobs end with nushell.
+fn wait_for_schedules(engine_state: &EngineState) {
+    while !engine_state.signals().interrupted()
+        && !engine_state
+            .schedules
+            .lock()
+            .expect("schedules lock is poisoned!")
+            .is_empty()
+    {
+        std::thread::sleep(DAEMON_CHECK_INTERVAL);
+    }
+}
+
+/// Print the phases of startup for `--profile-startup`, once everything before running the
+/// commands, the script or the REPL is done.
+fn print_startup_profile(entire_start_time: std::time::Instant) {
+    if startup_profile::is_enabled() {
+        let phases = startup_profile::finish();
+        eprint!(
+            "{}",
+            startup_profile::render(&phases, entire_start_time.elapsed())
+        );
+    }
+}
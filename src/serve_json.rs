@@ -0,0 +1,191 @@
+//! `nu --serve-json`, so that editors, GUIs and test harnesses can drive nu without a terminal.
+//!
+//! Each line of stdin is a request, and nu writes a line of stdout with the response, which has
+//! the `id` of the request:
+//!
+//! ```text
+//! {"id": 1, "method": "eval", "source": "ls | length"}
+//! {"id": 1, "value": 12, "type": "int"}
+//! {"id": 2, "method": "complete", "source": "ls --a", "position": 6}
+//! {"id": 2, "completions": [{"value": "--all", "description": "Show hidden files", "start": 3, "end": 6}]}
+//! {"id": 3, "method": "highlight", "source": "ls"}
+//! {"id": 3, "highlights": [{"start": 0, "end": 2, "shape": "shape_internalcall"}]}
+//! ```
+//!
+//! Positions are byte offsets in the source. Requests which fail get an `error` with the
+//! `message`, the `code` and the `rendered` error instead.
+//!
+//! The snippets are evaluated one after the other with the same engine state and stack, like the
+//! lines of the REPL. `print` and the externals which don't end a snippet write to stderr, so that
+//! stdout only has the responses, and externals don't read the requests on stdin.
+
+use crate::snippet::eval_snippet;
+use nu_cli::NuCompleter;
+use nu_parser::{flatten_block, parse};
+use nu_protocol::{
+    Config, PipelineData, ShellError, UseAnsiColoring, Value, ast,
+    engine::{EngineState, Stack, StateWorkingSet},
+    format_cli_error,
+    shell_error::io::IoError,
+};
+use reedline::Completer;
+use serde_json::{Value as JsonValue, json};
+use std::{
+    io::{self, Write},
+    sync::Arc,
+};
+
+/// Answer the requests on stdin until it's closed.
+pub(crate) fn serve_json(mut engine_state: EngineState, stack: Stack) -> Result<(), ShellError> {
+    let mut config = Config::clone(engine_state.get_config());
+    config.use_ansi_coloring = UseAnsiColoring::False;
+    engine_state.set_config(config);
+
+    let mut server = JsonServer {
+        engine_state,
+        stack,
+    };
+    let mut stdout = io::stdout();
+    for line in io::stdin().lines() {
+        let line = line.map_err(|err| {
+            IoError::new_internal(err, "Could not read a request", nu_protocol::location!())
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = server.respond(&line);
+        writeln!(stdout, "{response}")
+            .and_then(|()| stdout.flush())
+            .map_err(|err| {
+                IoError::new_internal(err, "Could not write a response", nu_protocol::location!())
+            })?;
+    }
+    Ok(())
+}
+
+struct JsonServer {
+    engine_state: EngineState,
+    stack: Stack,
+}
+
+impl JsonServer {
+    fn respond(&mut self, line: &str) -> JsonValue {
+        let request: JsonValue = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return json!({
+                    "id": null,
+                    "error": {"message": format!("The request isn't valid JSON: {err}")},
+                });
+            }
+        };
+
+        let source = request["source"].as_str().unwrap_or_default();
+        let result = match request["method"].as_str() {
+            Some("eval") => self.eval(source),
+            Some("complete") => {
+                let position = request["position"]
+                    .as_u64()
+                    .map(|position| position as usize);
+                Ok(self.complete(source, position.unwrap_or(source.len())))
+            }
+            Some("highlight") => Ok(self.highlight(source)),
+            Some(method) => Err(json!({"message": format!("There's no {method} method")})),
+            None => Err(json!({"message": "The request has no method"})),
+        };
+
+        let mut response = result.unwrap_or_else(|error| json!({"error": error}));
+        response["id"] = request["id"].clone();
+        response
+    }
+
+    fn eval(&mut self, source: &str) -> Result<JsonValue, JsonValue> {
+        let value = eval_snippet(
+            &mut self.engine_state,
+            &mut self.stack,
+            "source",
+            source,
+            error,
+        )?;
+        Ok(json!({
+            "value": self.json_value(&value),
+            "type": value.get_type().to_string(),
+        }))
+    }
+
+    /// A value as `to json` writes it, or as a string for values like closures which it can't.
+    fn json_value(&mut self, value: &Value) -> JsonValue {
+        let config = self.engine_state.get_config().clone();
+        let text = self
+            .engine_state
+            .find_decl(b"to json", &[])
+            .and_then(|decl_id| {
+                let call = ast::Call::new(value.span());
+                self.engine_state
+                    .get_decl(decl_id)
+                    .run(
+                        &self.engine_state,
+                        &mut self.stack,
+                        &(&call).into(),
+                        PipelineData::value(value.clone(), None),
+                    )
+                    .and_then(|data| data.collect_string("", &config))
+                    .ok()
+            });
+        text.and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_else(|| value.to_expanded_string(", ", &config).into())
+    }
+
+    fn complete(&self, source: &str, position: usize) -> JsonValue {
+        let mut position = position.min(source.len());
+        while !source.is_char_boundary(position) {
+            position -= 1;
+        }
+        let mut completer = NuCompleter::new(
+            Arc::new(self.engine_state.clone()),
+            Arc::new(self.stack.clone()),
+        );
+        let completions: Vec<_> = completer
+            .complete(source, position)
+            .into_iter()
+            .map(|suggestion| {
+                json!({
+                    "value": suggestion.value,
+                    "description": suggestion.description,
+                    "start": suggestion.span.start,
+                    "end": suggestion.span.end,
+                })
+            })
+            .collect();
+        json!({"completions": completions})
+    }
+
+    fn highlight(&self, source: &str) -> JsonValue {
+        let offset = self.engine_state.next_span_start();
+        let mut working_set = StateWorkingSet::new(&self.engine_state);
+        let block = parse(&mut working_set, None, source.as_bytes(), false);
+        let highlights: Vec<_> = flatten_block(&working_set, &block)
+            .into_iter()
+            .map(|(span, shape)| {
+                json!({
+                    "start": span.start.saturating_sub(offset),
+                    "end": span.end.saturating_sub(offset),
+                    "shape": shape.to_string(),
+                })
+            })
+            .collect();
+        json!({"highlights": highlights})
+    }
+}
+
+fn error(
+    working_set: &StateWorkingSet,
+    error: &dyn miette::Diagnostic,
+    default_code: &'static str,
+) -> JsonValue {
+    json!({
+        "message": error.to_string(),
+        "code": error.code().map_or(default_code.into(), |code| code.to_string()),
+        "rendered": format_cli_error(working_set, error, Some(default_code)),
+    })
+}
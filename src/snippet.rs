@@ -0,0 +1,71 @@
+//! Evaluating snippets of code for the frontends which aren't a terminal, like `nu --kernel` and
+//! `nu --serve-json`.
+
+use nu_cli::eval_deferred_sources;
+use nu_engine::eval_block;
+use nu_parser::parse;
+use nu_protocol::{
+    PipelineData, ShellError, Span, Value,
+    debugger::WithoutDebug,
+    engine::{EngineState, Stack, StateWorkingSet},
+};
+
+/// Evaluate a snippet after the ones before it, and collect its value.
+///
+/// `error` turns the parse, compile and shell errors into what the frontend sends, given the
+/// working set to render them with and the code to use for errors without one.
+pub(crate) fn eval_snippet<E>(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    fname: &str,
+    source: &str,
+    error: impl Fn(&StateWorkingSet, &dyn miette::Diagnostic, &'static str) -> E,
+) -> Result<Value, E> {
+    // Like the REPL, keep the environment of the snippets before
+    if let Err(err) = engine_state.merge_env(stack) {
+        log::warn!("Could not merge the environment: {err}");
+    }
+    engine_state.reset_signals();
+
+    let shell_error = |engine_state: &EngineState, err: ShellError| {
+        error(
+            &StateWorkingSet::new(engine_state),
+            &err,
+            "nu::shell::error",
+        )
+    };
+
+    let (block, delta) = {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let block = parse(&mut working_set, Some(fname), source.as_bytes(), false);
+        if let Some(err) = working_set.parse_errors.first() {
+            return Err(error(&working_set, err, "nu::parser::error"));
+        }
+        if let Some(err) = working_set.compile_errors.first() {
+            return Err(error(&working_set, err, "nu::compile::error"));
+        }
+        (block, working_set.render())
+    };
+    engine_state
+        .merge_delta(delta)
+        .map_err(|err| shell_error(engine_state, err))?;
+
+    let mut collect_stack = stack.start_collect_value();
+    let result = eval_block::<WithoutDebug>(
+        engine_state,
+        &mut collect_stack,
+        &block,
+        PipelineData::empty(),
+    )
+    .and_then(|data| data.body.into_value(Span::unknown()));
+    drop(collect_stack);
+
+    // The files queued by `import dynamic` are defined for the next snippets
+    eval_deferred_sources(engine_state, stack);
+
+    match result {
+        Ok(Value::Error { error, .. }) => Err(shell_error(engine_state, *error)),
+        Ok(value) => Ok(value),
+        Err(err) => Err(shell_error(engine_state, err)),
+    }
+}
@@ -1,4 +1,4 @@
-use nu_cmd_base::hook::{eval_env_change_hook, eval_hooks};
+use nu_cmd_base::hook::{eval_env_change_hook, eval_hooks, eval_var_watches};
 use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_protocol::{
@@ -391,6 +391,11 @@ pub fn nu_repl() {
             outcome_err(&engine_state, &err);
         }
 
+        // Check for the closures of `watch-var`
+        if let Err(err) = eval_var_watches(&mut engine_state, &mut stack) {
+            outcome_err(&engine_state, &err);
+        }
+
         // Check for pre_execution hook
 
         engine_state
@@ -552,6 +552,22 @@ fn err_hook_parse_error() {
     assert_eq!(actual_repl.out, "");
 }
 
+#[test]
+fn err_hook_parse_error_points_to_the_hook() {
+    let inp = &[
+        r#"$env.config = {
+            hooks: {
+                pre_prompt: ["print ("]
+            }
+        }"#,
+        "",
+    ];
+
+    let actual_repl = nu!(nu_repl_code(inp));
+
+    assert!(actual_repl.err.contains("hook (line0:3)"));
+}
+
 #[test]
 fn env_change_overlay() {
     let inp = &[
@@ -564,3 +580,37 @@ fn env_change_overlay() {
     let actual_repl = nu!(nu_repl_code(inp));
     assert_eq!(actual_repl.out, "2");
 }
+
+#[test]
+fn display_renderer_by_type() {
+    let actual = nu!(r#"
+        $env.config.hooks.display_renderers = { duration: {|| $"took ($in)" } }
+        2sec
+    "#);
+
+    assert_eq!(actual.out, "took 2sec");
+}
+
+#[test]
+fn display_renderer_by_content_type() {
+    let actual = nu!(r#"
+        $env.config.hooks.display_renderers = {
+            duration: {|| "by type" }
+            "text/x-shout": {|| str upcase }
+        }
+        "hello" | metadata set --content-type "text/x-shout; charset=utf-8"
+    "#);
+
+    assert_eq!(actual.out, "HELLO");
+}
+
+#[test]
+fn display_renderer_structured_output_uses_display_output() {
+    let actual = nu!(r#"
+        $env.config.hooks.display_renderers = { record: {|| transpose name amount } }
+        { a: 1 }
+    "#);
+
+    assert!(actual.out.contains("name"));
+    assert!(actual.out.contains("amount"));
+}
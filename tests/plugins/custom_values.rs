@@ -174,6 +174,20 @@ fn can_append_plugin_custom_values() {
     );
 }
 
+#[test]
+fn can_prepend_strings_to_plugin_custom_values() {
+    let actual = nu_with_plugins!(
+        cwd: "tests",
+        plugin: ("nu_plugin_custom_values"),
+        "'xyz' ++ (custom-value generate)"
+    );
+
+    assert_eq!(
+        actual.out,
+        "I used to be a custom value! My data was (xyzabc)"
+    );
+}
+
 // There are currently no custom values defined by the engine that aren't hidden behind an extra
 // feature
 #[cfg(feature = "sqlite")]
@@ -4,6 +4,7 @@ mod test_commandline;
 mod test_conditionals;
 mod test_config;
 mod test_config_path;
+mod test_config_profiles;
 mod test_converters;
 mod test_custom_commands;
 mod test_engine;
@@ -41,6 +41,41 @@ fn non_xdg_config_dir() -> AbsolutePathBuf {
     config_dir_nushell
 }
 
+/// The default Nushell data directory, ignoring XDG_DATA_HOME
+fn non_xdg_data_dir() -> AbsolutePathBuf {
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    let data_dir = dirs::data_dir().expect("Could not get data directory");
+
+    #[cfg(target_os = "linux")]
+    let data_dir = {
+        let mut dir = dirs::home_dir().expect("Could not get data directory");
+        dir.push(".local");
+        dir.push("share");
+        dir
+    };
+
+    let data_dir = data_dir.canonicalize().unwrap_or(data_dir);
+    let mut data_dir_nushell = AbsolutePathBuf::try_from(data_dir).expect("Invalid data directory");
+    data_dir_nushell.push("nushell");
+    data_dir_nushell
+}
+
+/// Point the data directory to an empty temporary folder.
+/// Returns the path to the `nushell` data folder inside.
+fn setup_fake_data(playground: &mut Playground) -> AbsolutePathBuf {
+    let data_home = "data_real";
+    playground.mkdir(data_home);
+
+    let data_home = playground.cwd().join(data_home);
+    playground.with_env("XDG_DATA_HOME", data_home.to_str().unwrap());
+
+    let data_home = data_home
+        .canonicalize()
+        .map(Into::into)
+        .unwrap_or(data_home);
+    data_home.join("nushell")
+}
+
 /// Make the config directory a symlink that points to a temporary folder, and also makes
 /// the nushell directory inside a symlink.
 /// Returns the path to the `nushell` config folder inside, via the symlink.
@@ -107,8 +142,10 @@ fn run_interactive_stderr(xdg_config_home: impl AsRef<Path>) -> String {
 fn test_config_path_helper(
     playground: &mut Playground,
     config_dir_nushell: impl AsRef<AbsolutePath>,
+    data_dir_nushell: impl AsRef<AbsolutePath>,
 ) {
     let config_dir_nushell = config_dir_nushell.as_ref();
+    let data_dir_nushell = data_dir_nushell.as_ref();
 
     // Create the config dir folder structure if it does not already exist
     if !config_dir_nushell.exists() {
@@ -134,7 +171,19 @@ fn test_config_path_helper(
     let actual = run(playground, "$nu.env-path");
     assert_eq!(actual, canon_env_path);
 
-    let history_path = config_dir_nushell.join("history.txt");
+    // State files left in the config directory by older versions are still used, otherwise they
+    // are placed in the data directory
+    let state_path = |name: &str| {
+        let data_path = data_dir_nushell.join(name);
+        let legacy_path = config_dir_nushell.join(name);
+        if !data_path.exists() && legacy_path.symlink_metadata().is_ok() {
+            legacy_path
+        } else {
+            data_path
+        }
+    };
+
+    let history_path = state_path("history.txt");
     let canon_history_path = adjust_canonicalization(
         std::fs::canonicalize(&history_path).unwrap_or(history_path.into()),
     );
@@ -149,7 +198,7 @@ fn test_config_path_helper(
 
     #[cfg(feature = "plugin")]
     {
-        let plugin_path = config_dir_nushell.join("plugin.msgpackz");
+        let plugin_path = state_path("plugin.msgpackz");
         let canon_plugin_path = adjust_canonicalization(
             std::fs::canonicalize(&plugin_path).unwrap_or(plugin_path.into()),
         );
@@ -162,7 +211,7 @@ fn test_config_path_helper(
 #[test]
 fn test_default_config_path() {
     Playground::setup("default_config_path", |_, playground| {
-        test_config_path_helper(playground, non_xdg_config_dir());
+        test_config_path_helper(playground, non_xdg_config_dir(), non_xdg_data_dir());
     });
 }
 
@@ -172,7 +221,8 @@ fn test_default_config_path() {
 fn test_default_symlinked_config_path_empty() {
     Playground::setup("symlinked_empty_config_dir", |_, playground| {
         let config_dir_nushell = setup_fake_config(playground);
-        test_config_path_helper(playground, config_dir_nushell);
+        let data_dir_nushell = setup_fake_data(playground);
+        test_config_path_helper(playground, config_dir_nushell, data_dir_nushell);
     });
 }
 
@@ -184,6 +234,7 @@ fn test_default_symlink_config_path_broken_symlink_config_files() {
         "symlinked_cfg_dir_with_symlinked_cfg_files_broken",
         |_, playground| {
             let fake_config_dir_nushell = setup_fake_config(playground);
+            let fake_data_dir_nushell = setup_fake_data(playground);
 
             let fake_dir = "fake";
             playground.mkdir(fake_dir);
@@ -208,7 +259,7 @@ fn test_default_symlink_config_path_broken_symlink_config_files() {
             // to break the symlinks
             std::fs::remove_dir_all(playground.cwd().join(fake_dir)).unwrap();
 
-            test_config_path_helper(playground, fake_config_dir_nushell);
+            test_config_path_helper(playground, fake_config_dir_nushell, fake_data_dir_nushell);
         },
     );
 }
@@ -221,6 +272,7 @@ fn test_default_config_path_symlinked_config_files() {
         "symlinked_cfg_dir_with_symlinked_cfg_files",
         |_, playground| {
             let fake_config_dir_nushell = setup_fake_config(playground);
+            let fake_data_dir_nushell = setup_fake_data(playground);
 
             for config_file in [
                 "config.nu",
@@ -235,7 +287,7 @@ fn test_default_config_path_symlinked_config_files() {
                 playground.symlink(empty_file, fake_config_dir_nushell.join(config_file));
             }
 
-            test_config_path_helper(playground, fake_config_dir_nushell);
+            test_config_path_helper(playground, fake_config_dir_nushell, fake_data_dir_nushell);
         },
     );
 }
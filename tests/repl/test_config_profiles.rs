@@ -0,0 +1,145 @@
+use nu_test_support::fs::{Stub::FileWithContent, executable_path};
+use nu_test_support::playground::{Dirs, Playground};
+use pretty_assertions::assert_eq;
+use std::process::Command;
+
+/// The host name which nushell looks up the host layer of the config with.
+fn host_name() -> String {
+    let output = Command::new(executable_path())
+        .args(["-n", "-c", "sys host | get hostname"])
+        .output()
+        .expect("failed to execute process");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// A config file which sets `$env.LAYER` to `layer`.
+fn layer(layer: &str) -> String {
+    format!("$env.LAYER = '{layer}'")
+}
+
+/// Run `nu` with `args` and the config directory of the playground, and return `$env.LAYER`.
+fn run_layer(dirs: &Dirs, args: &[&str], nu_profile: Option<&str>) -> String {
+    let mut command = Command::new(executable_path());
+    command
+        .current_dir(dirs.test())
+        .env("XDG_CONFIG_HOME", dirs.test().join("config"))
+        .env("XDG_DATA_HOME", dirs.test().join("data"))
+        .env_remove("NU_PROFILE")
+        .args(args)
+        .args(["-c", "$env.LAYER? | default none"]);
+    if let Some(profile) = nu_profile {
+        command.env("NU_PROFILE", profile);
+    }
+    let output = command.output().expect("failed to execute process");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn profile_layer_overrides_base_config() {
+    Playground::setup("profile_layer_overrides_base_config", |dirs, sandbox| {
+        sandbox.mkdir("config/nushell/profiles/work").with_files(&[
+            FileWithContent("config/nushell/config.nu", &layer("base")),
+            FileWithContent("config/nushell/profiles/work/config.nu", &layer("work")),
+        ]);
+
+        assert_eq!(run_layer(&dirs, &["--login"], None), "base");
+        assert_eq!(
+            run_layer(&dirs, &["--login", "--profile", "work"], None),
+            "work"
+        );
+    })
+}
+
+#[test]
+fn nu_profile_selects_profile_unless_flag_is_given() {
+    Playground::setup(
+        "nu_profile_selects_profile_unless_flag_is_given",
+        |dirs, sandbox| {
+            sandbox
+                .mkdir("config/nushell/profiles/work")
+                .mkdir("config/nushell/profiles/home")
+                .with_files(&[
+                    FileWithContent("config/nushell/config.nu", &layer("base")),
+                    FileWithContent("config/nushell/profiles/work/config.nu", &layer("work")),
+                    FileWithContent("config/nushell/profiles/home/config.nu", &layer("home")),
+                ]);
+
+            assert_eq!(run_layer(&dirs, &["--login"], Some("work")), "work");
+            assert_eq!(
+                run_layer(&dirs, &["--login", "--profile", "home"], Some("work")),
+                "home"
+            );
+            // An empty NU_PROFILE selects no profile
+            assert_eq!(run_layer(&dirs, &["--login"], Some("")), "base");
+        },
+    )
+}
+
+#[test]
+fn host_layer_overrides_profile_layer() {
+    Playground::setup("host_layer_overrides_profile_layer", |dirs, sandbox| {
+        let host_dir = format!("config/nushell/hosts/{}", host_name());
+        let host_config = format!("{host_dir}/config.nu");
+        sandbox
+            .mkdir("config/nushell/profiles/work")
+            .mkdir(&host_dir)
+            .with_files(&[
+                FileWithContent("config/nushell/config.nu", &layer("base")),
+                FileWithContent("config/nushell/profiles/work/config.nu", &layer("work")),
+                FileWithContent(&host_config, &layer("host")),
+            ]);
+
+        assert_eq!(run_layer(&dirs, &["--login"], None), "host");
+        assert_eq!(
+            run_layer(&dirs, &["--login", "--profile", "work"], None),
+            "host"
+        );
+    })
+}
+
+#[test]
+fn layers_apply_on_top_of_explicit_config() {
+    Playground::setup("layers_apply_on_top_of_explicit_config", |dirs, sandbox| {
+        sandbox.mkdir("config/nushell/profiles/work").with_files(&[
+            FileWithContent("explicit.nu", &layer("explicit")),
+            FileWithContent("config/nushell/config.nu", &layer("base")),
+            FileWithContent("config/nushell/profiles/work/config.nu", &layer("work")),
+        ]);
+
+        assert_eq!(
+            run_layer(&dirs, &["--config", "explicit.nu"], None),
+            "explicit"
+        );
+        assert_eq!(
+            run_layer(
+                &dirs,
+                &["--config", "explicit.nu", "--profile", "work"],
+                None
+            ),
+            "work"
+        );
+    })
+}
+
+#[test]
+fn missing_profile_is_reported() {
+    Playground::setup("missing_profile_is_reported", |dirs, sandbox| {
+        sandbox
+            .mkdir("config/nushell")
+            .with_files(&[FileWithContent("config/nushell/config.nu", &layer("base"))]);
+
+        let output = Command::new(executable_path())
+            .current_dir(dirs.test())
+            .env("XDG_CONFIG_HOME", dirs.test().join("config"))
+            .env("XDG_DATA_HOME", dirs.test().join("data"))
+            .env_remove("NU_PROFILE")
+            .args(["--login", "--profile", "missing", "-c", "$env.LAYER"])
+            .output()
+            .expect("failed to execute process");
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "base");
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("Config profile 'missing' not found")
+        );
+    })
+}
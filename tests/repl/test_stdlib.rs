@@ -9,3 +9,12 @@ fn not_loaded() -> TestResult {
 fn use_command() -> TestResult {
     run_test_std("use std/assert; assert true; print 'it works'", "it works")
 }
+
+#[test]
+fn modules_are_loaded_on_first_use() -> TestResult {
+    run_test_std("view files | where filename =~ assert | length", "0")?;
+    run_test_std(
+        "use std/assert; view files | where filename =~ assert | length",
+        "1",
+    )
+}
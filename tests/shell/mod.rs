@@ -56,6 +56,307 @@ fn do_not_panic_if_broken_pipe() {
     assert!(child_output.stderr.is_empty());
 }
 
+#[test]
+fn profile_startup_prints_phases_to_stderr() {
+    let child_output = std::process::Command::new(nu_test_support::fs::executable_path())
+        .args(["--profile-startup", "-n", "-c", "print done"])
+        .output()
+        .expect("failed to execute process");
+
+    let stderr = String::from_utf8_lossy(&child_output.stderr);
+    assert_eq!(String::from_utf8_lossy(&child_output.stdout).trim(), "done");
+    assert!(stderr.contains("load std-lib"), "{stderr}");
+    assert!(
+        stderr
+            .lines()
+            .last()
+            .is_some_and(|line| line.starts_with("total"))
+    );
+}
+
+#[test]
+fn sandbox_denies_effects() {
+    let child_output = std::process::Command::new(nu_test_support::fs::executable_path())
+        .args(["--sandbox", "-n", "-c", "http get http://localhost:1"])
+        .output()
+        .expect("failed to execute process");
+
+    let stderr = String::from_utf8_lossy(&child_output.stderr);
+    assert!(!child_output.status.success());
+    assert!(stderr.contains("sandbox_denied"), "{stderr}");
+}
+
+#[test]
+fn daemon_waits_for_scheduled_jobs() {
+    Playground::setup("daemon_waits_for_scheduled_jobs", |dirs, _| {
+        let child_output = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args([
+                "--daemon",
+                "-n",
+                "-c",
+                "schedule add 100ms { 'ran' | save ran.txt; schedule remove (job id) }",
+            ])
+            .output()
+            .expect("failed to execute process");
+
+        assert!(child_output.status.success());
+        let ran = std::fs::read_to_string(dirs.test().join("ran.txt"));
+        assert_eq!(ran.ok().as_deref(), Some("ran"));
+    })
+}
+
+#[test]
+fn commands_errors_name_the_commandline() {
+    let actual = nu!("print (");
+
+    assert!(actual.err.contains("<commandline>"), "{}", actual.err);
+}
+
+/// Send requests to `nu --serve-json`, and get its responses and its stderr.
+fn serve_json(requests: &[&str]) -> (Vec<serde_json::Value>, String) {
+    use std::io::Write;
+
+    let mut child = std::process::Command::new(nu_test_support::fs::executable_path())
+        .args(["--serve-json", "-n"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to execute process");
+    let mut stdin = child.stdin.take().expect("stdin should be piped");
+    writeln!(stdin, "{}", requests.join("\n")).expect("failed to write the requests");
+    drop(stdin);
+    let child_output = child.wait_with_output().expect("failed to wait for nu");
+
+    let stdout = String::from_utf8_lossy(&child_output.stdout);
+    let responses = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("responses should be JSON"))
+        .collect();
+    (
+        responses,
+        String::from_utf8_lossy(&child_output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn serve_json_keeps_state_between_requests() {
+    let (responses, stderr) = serve_json(&[
+        r#"{"id": 1, "method": "eval", "source": "mut x = 2"}"#,
+        r#"{"id": 2, "method": "eval", "source": "print hello; $x += 1; {x: $x}"}"#,
+        r#"{"id": 3, "method": "eval", "source": "error make {msg: oops}"}"#,
+        r#"{"id": 4, "method": "highlight", "source": "ls"}"#,
+        "not json",
+    ]);
+
+    assert_eq!(responses.len(), 5, "{responses:?}");
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[1]["value"], serde_json::json!({"x": 3}));
+    assert_eq!(responses[1]["type"], "record<x: int>");
+    assert_eq!(responses[2]["error"]["message"], "oops");
+    assert_eq!(responses[3]["highlights"][0]["start"], 0);
+    assert_eq!(responses[3]["highlights"][0]["end"], 2);
+    assert_eq!(responses[4]["id"], serde_json::Value::Null);
+    assert!(stderr.contains("hello"));
+}
+
+#[test]
+fn serve_json_reports_failing_snippets() {
+    let (responses, _) = serve_json(&[
+        r#"{"id": 1, "method": "eval", "source": "let x = (1 +"}"#,
+        r#"{"id": 2, "method": "eval", "source": "1 / 0"}"#,
+        r#"{"id": 3, "method": "eval", "source": "1 + 1"}"#,
+    ]);
+
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    let code = responses[0]["error"]["code"].as_str().unwrap_or_default();
+    assert!(code.starts_with("nu::parser::"), "{code}");
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["error"]["code"], "nu::shell::division_by_zero");
+    assert!(
+        responses[1]["error"]["rendered"]
+            .as_str()
+            .is_some_and(|rendered| rendered.contains("1 / 0"))
+    );
+    assert_eq!(responses[2]["value"], 2);
+}
+
+#[test]
+fn serve_json_keeps_externals_off_stdin_and_stdout() {
+    // `relay` copies its stdin to its stdout, so it would answer with the next request
+    let (responses, stderr) = serve_json(&[
+        r#"{"id": 1, "method": "eval", "source": "^$nu.current-exe --testbin relay; ^$nu.current-exe --testbin cococo from-external; 1"}"#,
+        r#"{"id": 2, "method": "eval", "source": "^$nu.current-exe --testbin cococo last | str trim"}"#,
+    ]);
+
+    assert_eq!(responses.len(), 2, "{responses:?}");
+    assert_eq!(responses[0]["value"], 1);
+    assert_eq!(responses[1]["value"], "last");
+    assert!(stderr.contains("from-external"), "{stderr}");
+}
+
+#[test]
+fn kernel_runs_cells_like_the_repl() {
+    use serde_json::{Value as JsonValue, json};
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        process::{Command, Stdio},
+        time::Duration,
+    };
+
+    fn write_frames(stream: &mut TcpStream, frames: &[&[u8]]) {
+        let mut buffer = vec![];
+        for (i, frame) in frames.iter().enumerate() {
+            let more = u8::from(i + 1 < frames.len());
+            buffer.extend_from_slice(&[more | 0x02]);
+            buffer.extend_from_slice(&(frame.len() as u64).to_be_bytes());
+            buffer.extend_from_slice(frame);
+        }
+        stream
+            .write_all(&buffer)
+            .expect("failed to write a message");
+    }
+
+    fn read_frames(stream: &mut TcpStream) -> Vec<Vec<u8>> {
+        let mut frames = vec![];
+        loop {
+            let mut flags = [0u8];
+            stream
+                .read_exact(&mut flags)
+                .expect("failed to read a frame");
+            let size = if flags[0] & 0x02 != 0 {
+                let mut size = [0u8; 8];
+                stream
+                    .read_exact(&mut size)
+                    .expect("failed to read a frame");
+                u64::from_be_bytes(size) as usize
+            } else {
+                let mut size = [0u8];
+                stream
+                    .read_exact(&mut size)
+                    .expect("failed to read a frame");
+                size[0] as usize
+            };
+            let mut body = vec![0u8; size];
+            stream
+                .read_exact(&mut body)
+                .expect("failed to read a frame");
+            if flags[0] & 0x04 == 0 {
+                frames.push(body);
+                if flags[0] & 0x01 == 0 {
+                    return frames;
+                }
+            }
+        }
+    }
+
+    fn request(stream: &mut TcpStream, msg_type: &str, content: JsonValue) -> JsonValue {
+        let header = json!({"msg_id": msg_type, "session": "test", "msg_type": msg_type});
+        write_frames(
+            stream,
+            &[
+                b"<IDS|MSG>",
+                b"",
+                header.to_string().as_bytes(),
+                b"{}",
+                b"{}",
+                content.to_string().as_bytes(),
+            ],
+        );
+        let frames = read_frames(stream);
+        serde_json::from_slice(frames.last().expect("the reply should have a content"))
+            .expect("the content should be JSON")
+    }
+
+    Playground::setup("kernel_runs_cells_like_the_repl", |dirs, _| {
+        // Pick free ports, which the kernel listens on once they're released
+        let ports: Vec<u16> = (0..5)
+            .map(|_| TcpListener::bind("127.0.0.1:0").expect("failed to bind a port"))
+            .collect::<Vec<_>>()
+            .iter()
+            .map(|listener| listener.local_addr().expect("no address").port())
+            .collect();
+        let connection = json!({
+            "transport": "tcp",
+            "ip": "127.0.0.1",
+            "key": "",
+            "signature_scheme": "hmac-sha256",
+            "shell_port": ports[0],
+            "iopub_port": ports[1],
+            "stdin_port": ports[2],
+            "control_port": ports[3],
+            "hb_port": ports[4],
+        });
+        let connection_file = dirs.test().join("connection.json");
+        std::fs::write(&connection_file, connection.to_string())
+            .expect("failed to write the connection file");
+
+        let mut child = Command::new(nu_test_support::fs::executable_path())
+            .arg("--kernel")
+            .arg(&connection_file)
+            .arg("-n")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to execute process");
+
+        let mut shell = (0..100)
+            .find_map(|_| {
+                TcpStream::connect(("127.0.0.1", ports[0]))
+                    .inspect_err(|_| std::thread::sleep(Duration::from_millis(100)))
+                    .ok()
+            })
+            .expect("the kernel should listen on the shell port");
+        shell
+            .set_read_timeout(Some(Duration::from_secs(60)))
+            .expect("failed to set a timeout");
+
+        let mut greeting = [0u8; 64];
+        greeting[0] = 0xff;
+        greeting[9] = 0x7f;
+        greeting[10] = 3;
+        greeting[12..16].copy_from_slice(b"NULL");
+        shell.write_all(&greeting).expect("failed to greet");
+        let mut peer = [0u8; 64];
+        shell
+            .read_exact(&mut peer)
+            .expect("the kernel should greet");
+        assert_eq!(&peer[12..16], b"NULL");
+        let ready = b"\x05READY\x0bSocket-Type\x00\x00\x00\x06DEALER";
+        shell
+            .write_all(&[&[0x04, ready.len() as u8][..], ready].concat())
+            .expect("failed to send READY");
+
+        let info = request(&mut shell, "kernel_info_request", json!({}));
+        assert_eq!(info["implementation"], "nushell");
+
+        let reply = request(&mut shell, "execute_request", json!({"code": "mut x = 2"}));
+        assert_eq!(reply["status"], "ok", "{reply}");
+        assert_eq!(reply["execution_count"], 1);
+
+        let code = "$x += 1; if $x != 3 { error make {msg: wrong} }";
+        let reply = request(&mut shell, "execute_request", json!({"code": code}));
+        assert_eq!(reply["status"], "ok", "{reply}");
+
+        let reply = request(
+            &mut shell,
+            "execute_request",
+            json!({"code": "error make {msg: oops}"}),
+        );
+        assert_eq!(reply["status"], "error");
+        assert_eq!(reply["evalue"], "oops");
+        assert_eq!(reply["execution_count"], 3);
+
+        let reply = request(&mut shell, "shutdown_request", json!({"restart": false}));
+        assert_eq!(reply["status"], "ok");
+        let status = child.wait().expect("failed to wait for nu");
+        assert!(status.success());
+    });
+}
+
 #[test]
 fn nu_lib_dirs_repl() {
     Playground::setup("nu_lib_dirs_repl", |dirs, sandbox| {